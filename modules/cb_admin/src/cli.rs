@@ -0,0 +1,208 @@
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+/// Operational tasks for a Crab Rocket database, runs without the HTTP server.
+#[derive(Parser, Debug)]
+#[command(name = "crab_rocket-admin", version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Create a user with the `Admin` role.
+    CreateAdmin(CreateAdminArgs),
+    /// Replace the password of an existing user.
+    ResetPassword(ResetPasswordArgs),
+    /// Create a user for a device or job, it can not log in with a password.
+    CreateServiceAccount(CreateServiceAccountArgs),
+    /// Create an API key for a user or service account and print it once.
+    CreateApiKey(CreateApiKeyArgs),
+    /// Insert the default roles, their grants and the permission catalogue, existing rows are kept.
+    SeedReference,
+    /// Insert a reproducible demo dataset across users, employees, products, orders and more.
+    Seed(SeedArgs),
+    /// Rebuild the indexes of every table (or one) and refresh planner statistics.
+    Reindex(ReindexArgs),
+    /// Delete uploaded files older than the given age, both the rows and the files on disk.
+    PurgeUploads(PurgeUploadsArgs),
+    /// Write an entity as JSON, a single row with `--id` or every row otherwise.
+    Dump(DumpArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct CreateAdminArgs {
+    #[arg(long)]
+    pub username: String,
+    #[arg(long)]
+    pub email: String,
+    /// Must be unique across users.
+    #[arg(long)]
+    pub mobile_phone: String,
+    #[arg(long)]
+    pub full_name: Option<String>,
+    /// Read from standard input when omitted.
+    #[arg(long, env = "CRAB_ROCKET_ADMIN_PASSWORD", hide_env_values = true)]
+    pub password: Option<String>,
+}
+
+#[derive(Args, Debug)]
+#[group(required = true, multiple = false)]
+pub struct UserSelector {
+    #[arg(long)]
+    pub username: Option<String>,
+    #[arg(long)]
+    pub user_id: Option<i32>,
+}
+
+#[derive(Args, Debug)]
+pub struct ResetPasswordArgs {
+    #[command(flatten)]
+    pub user: UserSelector,
+    /// Read from standard input when omitted.
+    #[arg(long, env = "CRAB_ROCKET_ADMIN_PASSWORD", hide_env_values = true)]
+    pub password: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct CreateServiceAccountArgs {
+    #[arg(long)]
+    pub username: String,
+    /// Name of an existing role, its permissions bound what the account's keys can do.
+    #[arg(long)]
+    pub role: String,
+    #[arg(long)]
+    pub email: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct CreateApiKeyArgs {
+    #[command(flatten)]
+    pub user: UserSelector,
+    /// What the key is for, e.g. `"ERP sync"`.
+    #[arg(long)]
+    pub name: String,
+    /// A `resource:action` permission of the user's role, repeat for several.
+    #[arg(long = "scope", required = true)]
+    pub scopes: Vec<String>,
+    /// Never expires when omitted.
+    #[arg(long)]
+    pub expires_in_days: Option<u32>,
+}
+
+#[derive(Args, Debug)]
+pub struct SeedArgs {
+    /// Same seed and volumes, same data.
+    #[arg(long, default_value_t = 42)]
+    pub seed: u64,
+    /// Multiplies the default volumes, the flags below override single entities.
+    #[arg(long, default_value_t = 1)]
+    pub scale: usize,
+    #[arg(long)]
+    pub roles: Option<usize>,
+    #[arg(long)]
+    pub users: Option<usize>,
+    #[arg(long)]
+    pub employees: Option<usize>,
+    #[arg(long)]
+    pub suppliers: Option<usize>,
+    #[arg(long)]
+    pub categories: Option<usize>,
+    #[arg(long)]
+    pub products: Option<usize>,
+    /// Stock locations per product.
+    #[arg(long)]
+    pub warehouses: Option<usize>,
+    #[arg(long)]
+    pub customers: Option<usize>,
+    #[arg(long)]
+    pub orders: Option<usize>,
+}
+
+#[derive(Args, Debug)]
+pub struct ReindexArgs {
+    /// Only this table, e.g. `user_table`.
+    #[arg(long)]
+    pub table: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct PurgeUploadsArgs {
+    #[arg(long)]
+    pub older_than_days: u32,
+    /// List what would be deleted without deleting anything.
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct DumpArgs {
+    pub entity: Entity,
+    #[arg(long)]
+    pub id: Option<i32>,
+    /// Defaults to standard output.
+    #[arg(long, short)]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Entity {
+    User,
+    Role,
+    Permission,
+    Employee,
+    Department,
+    Task,
+    Post,
+    Follow,
+    Supplier,
+    Category,
+    Product,
+    Inventory,
+    Customer,
+    Order,
+    Shipment,
+    File,
+}
+
+#[cfg(test)]
+mod test {
+    use clap::{CommandFactory, Parser};
+
+    use super::{Cli, Command, Entity};
+
+    #[test]
+    fn test_cli_definition() {
+        Cli::command().debug_assert();
+        let cli = Cli::parse_from(["crab_rocket-admin", "dump", "role", "--id", "1"]);
+        match cli.command {
+            Command::Dump(args) => {
+                assert_eq!(args.entity, Entity::Role);
+                assert_eq!(args.id, Some(1));
+            }
+            command => panic!("unexpected command {command:?}"),
+        }
+        assert!(Cli::try_parse_from(["crab_rocket-admin", "reset-password"]).is_err());
+        let cli = Cli::parse_from([
+            "crab_rocket-admin",
+            "create-api-key",
+            "--username",
+            "scanner",
+            "--name",
+            "Aisle B",
+            "--scope",
+            "inventory:read",
+            "--scope",
+            "inventory:update",
+        ]);
+        match cli.command {
+            Command::CreateApiKey(args) => {
+                assert_eq!(args.scopes, ["inventory:read", "inventory:update"]);
+                assert_eq!(args.expires_in_days, None);
+            }
+            command => panic!("unexpected command {command:?}"),
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use colored::Colorize;
+use crab_rocket_role::mappers::role_mapper::RoleMapper;
+use crab_rocket_user::mappers::user_mapper::UserMapper;
+use crab_rocket_user::models::user::PostUser;
+use crab_rocket_utils::time::get_e8_time;
+use diesel::{OptionalExtension, PgConnection};
+use obj_traits::mapper::mapper_crud::MapperCRUD;
+
+use crate::cli::CreateAdminArgs;
+use crate::error::AdminError;
+use crate::password_or_stdin;
+
+pub const ADMIN_ROLE: &str = "Admin";
+
+pub fn run(conn: &mut PgConnection, args: CreateAdminArgs) -> Result<(), AdminError> {
+    let role = RoleMapper::get_by_name(conn, ADMIN_ROLE).optional()?.ok_or_else(|| {
+        AdminError::invalid(format!(
+            "role `{ADMIN_ROLE}` does not exist, run `crab_rocket-admin seed-reference` first"
+        ))
+    })?;
+    if UserMapper::get_by_username(conn, &args.username).optional()?.is_some() {
+        return Err(AdminError::invalid(format!("user `{}` already exists", args.username)));
+    }
+    let password = password_or_stdin(args.password)?;
+
+    let now = get_e8_time();
+    let user = PostUser::new(
+        args.username,
+        Some(role.role_id()),
+        Some(now),
+        Some(args.email),
+        password,
+        args.full_name,
+        None,
+        None,
+        Some(now),
+        args.mobile_phone,
+    );
+    let user = UserMapper::add_single(conn, &user)?;
+    println!("{} {} (user_id {})", "Created admin".green(), user.username(), user.user_id());
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use colored::Colorize;
+use crab_rocket_auth::error::AuthError;
+use crab_rocket_auth::models::api_key::NewApiKey;
+use crab_rocket_auth::services::api_key_service::{ApiKeyError, ApiKeyService};
+use diesel::PgConnection;
+
+use crate::cli::CreateApiKeyArgs;
+use crate::error::AdminError;
+use crate::find_user;
+
+pub fn run(conn: &mut PgConnection, args: CreateApiKeyArgs) -> Result<(), AdminError> {
+    let user = find_user(conn, &args.user)?;
+    let new_key = NewApiKey {
+        name: args.name,
+        scopes: args.scopes,
+        expires_in_days: args.expires_in_days,
+    };
+    let created =
+        ApiKeyService::create_for_user(conn, user.user_id(), &new_key).map_err(|e| match e {
+            ApiKeyError::Auth(e @ AuthError::Forbidden(_)) => {
+                AdminError::invalid(format!("{e}, grant it to the role first"))
+            }
+            e => AdminError::invalid(e.to_string()),
+        })?;
+    eprintln!(
+        "{} {} for {}, scopes {}. Store it now, it is not shown again:",
+        "Created API key".green(),
+        created.info.api_key.api_key_id(),
+        user.username(),
+        created.info.scopes.join(", ")
+    );
+    println!("{}", created.key);
+    Ok(())
+}
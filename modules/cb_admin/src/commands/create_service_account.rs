@@ -0,0 +1,45 @@
+use colored::Colorize;
+use crab_rocket_role::mappers::role_mapper::RoleMapper;
+use crab_rocket_user::mappers::user_mapper::UserMapper;
+use crab_rocket_user::models::user::PostUser;
+use crab_rocket_utils::time::get_e8_time;
+use crab_rocket_utils::token::generate_token;
+use diesel::{OptionalExtension, PgConnection};
+use obj_traits::mapper::mapper_crud::MapperCRUD;
+
+use crate::cli::CreateServiceAccountArgs;
+use crate::error::AdminError;
+
+/// Service accounts are users whose password nobody knows, they authenticate with API keys.
+pub fn run(conn: &mut PgConnection, args: CreateServiceAccountArgs) -> Result<(), AdminError> {
+    let role = RoleMapper::get_by_name(conn, &args.role)
+        .optional()?
+        .ok_or_else(|| AdminError::invalid(format!("role `{}` does not exist", args.role)))?;
+    if UserMapper::get_by_username(conn, &args.username).optional()?.is_some() {
+        return Err(AdminError::invalid(format!("user `{}` already exists", args.username)));
+    }
+
+    let now = get_e8_time();
+    let user = PostUser::new(
+        args.username.clone(),
+        Some(role.role_id()),
+        Some(now),
+        args.email,
+        generate_token(),
+        Some(String::from("Service account")),
+        None,
+        None,
+        Some(now),
+        // Must be unique, service accounts have no phone.
+        format!("service:{}", args.username),
+    );
+    let user = UserMapper::add_single(conn, &user)?;
+    println!(
+        "{} {} (user_id {}), create a key with `crab_rocket-admin create-api-key --user-id {}`",
+        "Created service account".green(),
+        user.username(),
+        user.user_id(),
+        user.user_id()
+    );
+    Ok(())
+}
@@ -0,0 +1,124 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use colored::Colorize;
+use crab_rocket_category::mappers::category_mapper::CategoryMapper;
+use crab_rocket_config::app_config;
+use crab_rocket_customer::mappers::customer_mapper::CustomerMapper;
+use crab_rocket_department::mappers::department_mapper::DepartmentMapper;
+use crab_rocket_employee::mappers::employee_mapper::EmployeeMapper;
+use crab_rocket_file::mappers::file_mapper::fetch_all_files;
+use crab_rocket_follow::mappers::follow_mapper::FollowMapper;
+use crab_rocket_inventory::mappers::inventory_mapper::InventoryMapper;
+use crab_rocket_order::mappers::order_mapper::OrderMapper;
+use crab_rocket_permission::mappers::permission_mapper::PermissionMapper;
+use crab_rocket_post::mappers::post_mapper::PostMapper;
+use crab_rocket_product::mappers::product_mapper::ProductMapper;
+use crab_rocket_role::mappers::role_mapper::RoleMapper;
+use crab_rocket_shipment::mappers::shipment_mapper::ShipmentMapper;
+use crab_rocket_supplier::mappers::supplier_mapper::SupplierMapper;
+use crab_rocket_task::mappers::task_mapper::TaskMapper;
+use crab_rocket_user::mappers::user_mapper::UserMapper;
+use diesel::PgConnection;
+use obj_traits::mapper::mapper_crud::MapperCRUD;
+use obj_traits::request::pagination_request_param::{PaginationParam, PaginationParamTrait};
+use obj_traits::request::request_param::RequestParam;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::cli::{DumpArgs, Entity};
+use crate::error::AdminError;
+
+pub fn run(conn: &mut PgConnection, args: DumpArgs) -> Result<(), AdminError> {
+    let id = args.id;
+    let value = match args.entity {
+        Entity::User => dump::<UserMapper, _>(conn, id)?,
+        Entity::Role => dump::<RoleMapper, _>(conn, id)?,
+        Entity::Permission => dump::<PermissionMapper, _>(conn, id)?,
+        Entity::Employee => dump::<EmployeeMapper, _>(conn, id)?,
+        Entity::Department => dump::<DepartmentMapper, _>(conn, id)?,
+        Entity::Task => dump::<TaskMapper, _>(conn, id)?,
+        Entity::Post => dump::<PostMapper, _>(conn, id)?,
+        Entity::Follow => dump::<FollowMapper, _>(conn, id)?,
+        Entity::Supplier => dump::<SupplierMapper, _>(conn, id)?,
+        Entity::Category => dump::<CategoryMapper, _>(conn, id)?,
+        Entity::Product => dump::<ProductMapper, _>(conn, id)?,
+        Entity::Inventory => dump::<InventoryMapper, _>(conn, id)?,
+        Entity::Customer => dump::<CustomerMapper, _>(conn, id)?,
+        Entity::Order => dump::<OrderMapper, _>(conn, id)?,
+        Entity::Shipment => dump::<ShipmentMapper, _>(conn, id)?,
+        // Files are keyed by uuid and have no paginated mapper.
+        Entity::File if id.is_some() => {
+            return Err(AdminError::invalid("files can not be selected by --id"))
+        }
+        Entity::File => serde_json::to_value(fetch_all_files(conn)?)?,
+    };
+
+    match &args.output {
+        Some(path) => {
+            write_json(BufWriter::new(File::create(path)?), &value)?;
+            eprintln!("{} {}", "Wrote".green(), path.display());
+        }
+        None => write_json(io::stdout().lock(), &value)?,
+    }
+    Ok(())
+}
+
+/// One row when `id` is given, otherwise every row, fetched page by page with the largest
+/// page size the config allows.
+pub fn dump<M, F>(conn: &mut PgConnection, id: Option<i32>) -> Result<Value, AdminError>
+where
+    M: MapperCRUD<Param = RequestParam<PaginationParam, F>>,
+    M::Item: Serialize,
+{
+    if let Some(id) = id {
+        let item = M::get_by_id(conn, id).map_err(|e| match e {
+            diesel::result::Error::NotFound => AdminError::invalid(format!("no row with id {id}")),
+            e => e.into(),
+        })?;
+        return Ok(serde_json::to_value(item)?);
+    }
+    let limit = app_config().pagination.max_limit;
+    let mut items = Vec::new();
+    loop {
+        let param =
+            RequestParam::new(PaginationParam::new(Some(limit), Some(items.len() as i32)), None);
+        let page = M::get_all(conn, &param)?;
+        let fetched = page.data().len();
+        for item in page.data() {
+            items.push(serde_json::to_value(item)?);
+        }
+        if fetched < limit as usize {
+            break;
+        }
+    }
+    Ok(Value::Array(items))
+}
+
+fn write_json(mut writer: impl Write, value: &Value) -> Result<(), AdminError> {
+    serde_json::to_writer_pretty(&mut writer, value)?;
+    writeln!(writer)?;
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crab_rocket_role::mappers::role_mapper::RoleMapper;
+    use crab_rocket_schema::establish_pg_connection;
+
+    use super::dump;
+
+    #[test]
+    fn test_dump_roles() {
+        if let Ok(mut conn) = establish_pg_connection() {
+            let all = dump::<RoleMapper, _>(&mut conn, None).unwrap();
+            let roles = all.as_array().unwrap();
+            assert!(!roles.is_empty());
+
+            let role_id = roles[0]["role_id"].as_i64().unwrap() as i32;
+            let one = dump::<RoleMapper, _>(&mut conn, Some(role_id)).unwrap();
+            assert_eq!(one, roles[0]);
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use std::io::ErrorKind;
+
+use chrono::Duration;
+use colored::Colorize;
+use crab_rocket_file::mappers::file_mapper::{
+    count_other_files_at, delete_file_by_uuid, fetch_files_uploaded_before,
+};
+use crab_rocket_utils::time::get_e8_time;
+use diesel::PgConnection;
+
+use crate::cli::PurgeUploadsArgs;
+use crate::error::AdminError;
+
+/// Removes uploads older than `--older-than-days`. The file is deleted before its row so a
+/// failure never leaves an orphaned file behind; a file already missing on disk is ignored and
+/// one another row still points at is kept.
+pub fn run(conn: &mut PgConnection, args: PurgeUploadsArgs) -> Result<(), AdminError> {
+    let cutoff = get_e8_time() - Duration::days(i64::from(args.older_than_days));
+    let files = fetch_files_uploaded_before(conn, cutoff)?;
+
+    for file in &files {
+        if args.dry_run {
+            println!("{} {} ({})", "Would delete".yellow(), file.file_url, file.file_id);
+            continue;
+        }
+        if count_other_files_at(conn, &file.file_url, file.file_id)? == 0 {
+            match std::fs::remove_file(&file.file_url) {
+                Ok(()) => {}
+                Err(e) if e.kind() == ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        delete_file_by_uuid(conn, file.file_id)?;
+        println!("{} {} ({})", "Deleted".green(), file.file_url, file.file_id);
+    }
+    println!("{} files older than {cutoff}", files.len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::Duration;
+    use crab_rocket_file::mappers::file_mapper::fetch_all_files;
+    use crab_rocket_schema::schema::file_table::dsl;
+    use crab_rocket_test_support::{fixtures, test_conn};
+    use crab_rocket_utils::time::get_e8_time;
+    use diesel::prelude::*;
+
+    use super::run;
+    use crate::cli::PurgeUploadsArgs;
+
+    #[test]
+    fn test_shared_file_is_kept_while_referenced() {
+        let mut conn = test_conn();
+        let path = std::env::temp_dir().join(format!("purge-{}.png", std::process::id()));
+        std::fs::write(&path, b"png").unwrap();
+        let old_id =
+            fixtures::file(&mut conn, "shared.png", Some(get_e8_time() - Duration::days(400)));
+        let new_id = fixtures::file(&mut conn, "shared.png", Some(get_e8_time()));
+        diesel::update(dsl::file_table.filter(dsl::file_id.eq_any([old_id, new_id])))
+            .set(dsl::file_url.eq(path.to_str().unwrap()))
+            .execute(&mut conn)
+            .unwrap();
+
+        let purge = |conn: &mut PgConnection, older_than_days| {
+            run(
+                conn,
+                PurgeUploadsArgs {
+                    older_than_days,
+                    dry_run: false,
+                },
+            )
+            .unwrap();
+        };
+        purge(&mut conn, 365);
+        let ids: Vec<_> = fetch_all_files(&mut conn).unwrap().iter().map(|f| f.file_id).collect();
+        assert!(!ids.contains(&old_id));
+        assert!(ids.contains(&new_id));
+        assert!(path.exists(), "the newer row still serves the file");
+
+        diesel::update(dsl::file_table.filter(dsl::file_id.eq(new_id)))
+            .set(dsl::uploaded_at.eq(get_e8_time() - Duration::days(400)))
+            .execute(&mut conn)
+            .unwrap();
+        purge(&mut conn, 365);
+        assert!(!path.exists(), "the last row took the file with it");
+    }
+}
@@ -0,0 +1,39 @@
+use colored::Colorize;
+use diesel::sql_types::Text;
+use diesel::{sql_query, PgConnection, QueryableByName, RunQueryDsl};
+
+use crate::cli::ReindexArgs;
+use crate::error::AdminError;
+
+#[derive(QueryableByName)]
+struct TableName {
+    #[diesel(sql_type = Text)]
+    tablename: String,
+}
+
+/// Application tables in the `public` schema, diesel's bookkeeping excluded.
+fn public_tables(conn: &mut PgConnection) -> Result<Vec<String>, AdminError> {
+    let tables = sql_query(
+        "SELECT tablename FROM pg_tables WHERE schemaname = 'public' \
+         AND tablename <> '__diesel_schema_migrations' ORDER BY tablename",
+    )
+    .load::<TableName>(conn)?;
+    Ok(tables.into_iter().map(|t| t.tablename).collect())
+}
+
+/// Runs `REINDEX TABLE` and `ANALYZE` on every table, or only on `--table`.
+pub fn run(conn: &mut PgConnection, args: ReindexArgs) -> Result<(), AdminError> {
+    let tables = public_tables(conn)?;
+    let tables = match args.table {
+        // Only names read from the catalog end up in the statements below.
+        Some(table) if tables.contains(&table) => vec![table],
+        Some(table) => return Err(AdminError::invalid(format!("unknown table `{table}`"))),
+        None => tables,
+    };
+    for table in tables {
+        sql_query(format!("REINDEX TABLE \"{table}\"")).execute(conn)?;
+        sql_query(format!("ANALYZE \"{table}\"")).execute(conn)?;
+        println!("{} {table}", "Reindexed".green());
+    }
+    Ok(())
+}
@@ -0,0 +1,16 @@
+use colored::Colorize;
+use crab_rocket_user::mappers::user_mapper::UserMapper;
+use diesel::PgConnection;
+
+use crate::cli::ResetPasswordArgs;
+use crate::error::AdminError;
+use crate::{find_user, password_or_stdin};
+
+pub fn run(conn: &mut PgConnection, args: ResetPasswordArgs) -> Result<(), AdminError> {
+    let user = find_user(conn, &args.user)?;
+    let password = password_or_stdin(args.password)?;
+
+    UserMapper::update_password(conn, user.user_id(), &password)?;
+    println!("{} {}", "Password reset for".green(), user.username());
+    Ok(())
+}
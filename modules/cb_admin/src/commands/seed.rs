@@ -0,0 +1,33 @@
+use colored::Colorize;
+use crab_rocket_seed::faker::MAX_ROLES;
+use crab_rocket_seed::{seed, SeedConfig};
+use diesel::PgConnection;
+
+use crate::cli::SeedArgs;
+use crate::error::AdminError;
+
+pub fn run(conn: &mut PgConnection, args: SeedArgs) -> Result<(), AdminError> {
+    let config = config_from(&args);
+    if config.roles > MAX_ROLES {
+        return Err(AdminError::invalid(format!("--roles can be at most {MAX_ROLES}")));
+    }
+    let report = seed(conn, &config)?;
+    println!("{} seed {}: {report}", "Inserted".green(), config.seed);
+    Ok(())
+}
+
+fn config_from(args: &SeedArgs) -> SeedConfig {
+    let base = SeedConfig::scaled(args.seed, args.scale);
+    SeedConfig {
+        seed: args.seed,
+        roles: args.roles.unwrap_or(base.roles),
+        users: args.users.unwrap_or(base.users),
+        employees: args.employees.unwrap_or(base.employees),
+        suppliers: args.suppliers.unwrap_or(base.suppliers),
+        categories: args.categories.unwrap_or(base.categories),
+        products: args.products.unwrap_or(base.products),
+        warehouses: args.warehouses.unwrap_or(base.warehouses),
+        customers: args.customers.unwrap_or(base.customers),
+        orders: args.orders.unwrap_or(base.orders),
+    }
+}
@@ -0,0 +1,253 @@
+use colored::Colorize;
+use crab_rocket_auth::services::impersonation_service::IMPERSONATE;
+use crab_rocket_auth::services::lockout_service::MANAGE_LOCKOUTS;
+use crab_rocket_auth::services::ownership_service::MODERATE;
+use crab_rocket_permission::mappers::permission_mapper::PermissionMapper;
+use crab_rocket_permission::models::permission::PostPermission;
+use crab_rocket_role::mappers::role_mapper::RoleMapper;
+use crab_rocket_role::mappers::role_permission_mapper::RolePermissionMapper;
+use crab_rocket_role::models::role::PostRole;
+use crab_rocket_utils::time::get_e8_time;
+use diesel::{Connection, OptionalExtension, PgConnection};
+use obj_traits::mapper::mapper_crud::MapperCRUD;
+
+use crate::error::AdminError;
+
+/// `(role_name, description, permissions)` of the built-in roles.
+pub const ROLES: [(&str, &str, &str); 3] = [
+    ("Admin", "Administrator role with full access", "all"),
+    ("User", "Standard user role with limited access", "read,write"),
+    ("Guest", "Guest role with read-only access", "read"),
+];
+
+/// Built-in roles whose members have to log in with a second factor, they can edit roles
+/// and permissions.
+pub const TWO_FACTOR_ROLES: [&str; 1] = ["Admin"];
+
+/// Every resource exposed under `/api`, each gets one permission per entry of [`ACTIONS`].
+pub const RESOURCES: [&str; 20] = [
+    "user",
+    "role",
+    "permission",
+    "employee",
+    "department",
+    "leave",
+    "attendance",
+    "review",
+    "task",
+    "project",
+    "post",
+    "follow",
+    "supplier",
+    "category",
+    "product",
+    "inventory",
+    "customer",
+    "order",
+    "shipment",
+    "file",
+];
+
+pub const ACTIONS: [&str; 4] = ["read", "create", "update", "delete"];
+
+/// Resources that decide who may do what, only the [`MANAGER_ROLES`] get more than `read`.
+pub const MANAGEMENT_RESOURCES: [&str; 3] = ["user", "role", "permission"];
+
+/// Built-in roles granted every action on the [`MANAGEMENT_RESOURCES`] and the
+/// [`MANAGEMENT_PERMISSIONS`].
+pub const MANAGER_ROLES: [&str; 1] = ["Admin"];
+
+/// Permissions outside of the CRUD catalogue that only the [`MANAGER_ROLES`] are granted.
+pub const MANAGEMENT_PERMISSIONS: [(&str, &str); 1] = [MANAGE_LOCKOUTS];
+
+/// Resources whose rows belong to a user, `<resource>:moderate` lets a role change everyone's.
+pub const OWNED_RESOURCES: [&str; 5] = ["task", "project", "post", "product", "follow"];
+
+/// Permissions outside of the CRUD catalogue that no built-in role is granted, they have to
+/// be handed out deliberately, e.g. to a support role.
+pub const RESTRICTED: [(&str, &str); 1] = [IMPERSONATE];
+
+/// Built-in roles granted `moderate` on every entry of [`OWNED_RESOURCES`].
+pub const MODERATOR_ROLES: [&str; 1] = ["Admin"];
+
+/// Resources holding personal data such as pay, each gets one permission per entry of
+/// [`ACTIONS`] but only the [`CONFIDENTIAL_ROLES`] are granted them.
+pub const CONFIDENTIAL_RESOURCES: [&str; 1] = ["payroll"];
+
+/// Built-in roles granted every action on the [`CONFIDENTIAL_RESOURCES`].
+pub const CONFIDENTIAL_ROLES: [&str; 1] = ["Admin"];
+
+/// Actions each built-in role is granted on every resource, short of the
+/// [`MANAGEMENT_RESOURCES`].
+pub const ROLE_ACTIONS: [(&str, &[&str]); 3] =
+    [("Admin", &ACTIONS), ("User", &["read", "create", "update"]), ("Guest", &["read"])];
+
+/// Inserts whatever is missing of [`ROLES`] and the `resource:action` permission catalogue.
+/// A built-in role is granted what [`granted_to`] lists when either the role or the permission
+/// is created here, so the roles inserted by the migrations get their grants on the first run.
+/// [`TWO_FACTOR_ROLES`] get the two-factor requirement along with their first grants and
+/// [`RESTRICTED`] permissions go to nobody. Safe to run repeatedly, existing rows are never
+/// modified and grants revoked from an existing role stay revoked.
+pub fn run(conn: &mut PgConnection) -> Result<(), AdminError> {
+    let (roles, permissions, grants) = conn.transaction::<_, AdminError, _>(|conn| {
+        let mut created_roles = Vec::new();
+        for (name, description, permissions) in ROLES {
+            if RoleMapper::get_by_name(conn, name).optional()?.is_none() {
+                let role = PostRole::new(
+                    name.to_string(),
+                    Some(description.to_string()),
+                    Some(permissions.to_string()),
+                );
+                RoleMapper::add_single(conn, &role)?;
+                created_roles.push(name);
+            }
+        }
+
+        let mut created_permissions = Vec::new();
+        let now = get_e8_time();
+        let catalogue = crud(&RESOURCES)
+            .chain(OWNED_RESOURCES.iter().map(|resource| (*resource, MODERATE)))
+            .chain(crud(&CONFIDENTIAL_RESOURCES))
+            .chain(MANAGEMENT_PERMISSIONS)
+            .chain(RESTRICTED);
+        for (resource, action) in catalogue {
+            if PermissionMapper::get_by_resource_action(conn, resource, action)
+                .optional()?
+                .is_some()
+            {
+                continue;
+            }
+            let permission = PostPermission {
+                permission_name: format!("{resource}:{action}"),
+                permission_description: Some(format!("Can {action} {resource} records")),
+                resource: resource.to_string(),
+                action: action.to_string(),
+                is_active: Some(true),
+                created_at: Some(now),
+                updated_at: Some(now),
+                created_by: Some(String::from("crab_rocket-admin")),
+                updated_by: Some(String::from("crab_rocket-admin")),
+                notes: Some(String::from("Reference data")),
+            };
+            created_permissions
+                .push(PermissionMapper::add_single(conn, &permission)?.permission_id);
+        }
+
+        let mut grants = 0;
+        for (name, _, _) in ROLES {
+            let role_id = RoleMapper::get_by_name(conn, name)?.role_id();
+            let created = created_roles.contains(&name);
+            let ungranted =
+                RolePermissionMapper::get_permissions_of_role(conn, role_id)?.is_empty();
+            let mut role_grants = 0;
+            for (resource, action) in granted_to(name) {
+                let permission = PermissionMapper::get_by_resource_action(conn, resource, action)?;
+                if (created || created_permissions.contains(&permission.permission_id))
+                    && RolePermissionMapper::grant(conn, role_id, permission.permission_id)?
+                {
+                    role_grants += 1;
+                }
+            }
+            if TWO_FACTOR_ROLES.contains(&name) && (created || (ungranted && role_grants > 0)) {
+                RoleMapper::set_require_two_factor(conn, role_id, true)?;
+            }
+            grants += role_grants;
+        }
+        Ok((created_roles.len(), created_permissions.len(), grants))
+    })?;
+    println!("{} {roles} roles, {permissions} permissions, {grants} grants", "Inserted".green());
+    Ok(())
+}
+
+/// Every action of [`ACTIONS`] on each of `resources`.
+fn crud(resources: &'static [&'static str]) -> impl Iterator<Item = (&'static str, &'static str)> {
+    resources.iter().flat_map(|resource| ACTIONS.iter().map(move |action| (*resource, *action)))
+}
+
+/// The `(resource, action)` pairs the built-in role `name` is granted.
+pub fn granted_to(name: &str) -> Vec<(&'static str, &'static str)> {
+    let actions = ROLE_ACTIONS.iter().find(|(role, _)| *role == name).map_or(&[][..], |r| r.1);
+    let manager = MANAGER_ROLES.contains(&name);
+    let mut granted: Vec<_> = RESOURCES
+        .iter()
+        .flat_map(|resource| actions.iter().map(move |action| (*resource, *action)))
+        .filter(|(resource, action)| {
+            manager || *action == "read" || !MANAGEMENT_RESOURCES.contains(resource)
+        })
+        .collect();
+    if MODERATOR_ROLES.contains(&name) {
+        granted.extend(OWNED_RESOURCES.iter().map(|resource| (*resource, MODERATE)));
+    }
+    if CONFIDENTIAL_ROLES.contains(&name) {
+        granted.extend(crud(&CONFIDENTIAL_RESOURCES));
+    }
+    if manager {
+        granted.extend(MANAGEMENT_PERMISSIONS);
+    }
+    granted
+}
+
+#[cfg(test)]
+mod test {
+    use crab_rocket_role::mappers::role_mapper::RoleMapper;
+    use crab_rocket_role::mappers::role_permission_mapper::RolePermissionMapper;
+    use crab_rocket_schema::schema::{permission_table, role_permission_table, role_table};
+    use crab_rocket_test_support::test_conn;
+    use diesel::prelude::*;
+
+    use super::run;
+
+    fn granted(conn: &mut PgConnection, role: &str) -> Vec<String> {
+        let role_id = RoleMapper::get_by_name(conn, role).unwrap().role_id();
+        let permissions = RolePermissionMapper::get_permissions_of_role(conn, role_id).unwrap();
+        permissions.into_iter().map(|p| p.permission_name).collect()
+    }
+
+    #[test]
+    fn test_grants_the_migrated_roles() {
+        let mut conn = test_conn();
+        // Only the roles of the migrations, as on a freshly migrated database.
+        diesel::delete(role_permission_table::table).execute(&mut conn).unwrap();
+        diesel::delete(permission_table::table).execute(&mut conn).unwrap();
+        diesel::update(role_table::table)
+            .set(role_table::require_two_factor.eq(false))
+            .execute(&mut conn)
+            .unwrap();
+
+        run(&mut conn).unwrap();
+        let admin = granted(&mut conn, "Admin");
+        for permission in
+            ["role:update", "user:delete", "lockout:manage", "payroll:read", "post:moderate"]
+        {
+            assert!(admin.iter().any(|p| p == permission), "Admin lacks {permission}");
+        }
+        assert!(!admin.iter().any(|p| p == "user:impersonate"));
+        assert!(RoleMapper::get_by_name(&mut conn, "Admin").unwrap().require_two_factor());
+
+        let user = granted(&mut conn, "User");
+        for permission in ["user:read", "role:read", "post:create", "employee:update"] {
+            assert!(user.iter().any(|p| p == permission), "User lacks {permission}");
+        }
+        for permission in
+            ["user:update", "role:update", "permission:create", "lockout:manage", "payroll:read"]
+        {
+            assert!(!user.iter().any(|p| p == permission), "User has {permission}");
+        }
+        assert!(!RoleMapper::get_by_name(&mut conn, "User").unwrap().require_two_factor());
+        let guest = granted(&mut conn, "Guest");
+        assert!(guest.iter().all(|p| p.ends_with(":read")));
+
+        // A rerun leaves revoked grants and the two-factor policy alone.
+        let user_id = RoleMapper::get_by_name(&mut conn, "User").unwrap().role_id();
+        diesel::delete(
+            role_permission_table::table.filter(role_permission_table::role_id.eq(user_id)),
+        )
+        .execute(&mut conn)
+        .unwrap();
+        let admin_id = RoleMapper::get_by_name(&mut conn, "Admin").unwrap().role_id();
+        RoleMapper::set_require_two_factor(&mut conn, admin_id, false).unwrap();
+        run(&mut conn).unwrap();
+        assert!(granted(&mut conn, "User").is_empty());
+        assert!(!RoleMapper::get_by_name(&mut conn, "Admin").unwrap().require_two_factor());
+    }
+}
@@ -0,0 +1,47 @@
+use std::fmt;
+
+#[derive(Debug)]
+pub enum AdminError {
+    Database(diesel::result::Error),
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    /// The command can not run with the given arguments or database state.
+    Invalid(String),
+}
+
+impl AdminError {
+    pub fn invalid(reason: impl Into<String>) -> Self {
+        Self::Invalid(reason.into())
+    }
+}
+
+impl fmt::Display for AdminError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AdminError::Database(e) => write!(f, "database error: {e}"),
+            AdminError::Io(e) => write!(f, "io error: {e}"),
+            AdminError::Json(e) => write!(f, "json error: {e}"),
+            AdminError::Invalid(reason) => f.write_str(reason),
+        }
+    }
+}
+
+impl std::error::Error for AdminError {}
+
+impl From<diesel::result::Error> for AdminError {
+    fn from(e: diesel::result::Error) -> Self {
+        AdminError::Database(e)
+    }
+}
+
+impl From<std::io::Error> for AdminError {
+    fn from(e: std::io::Error) -> Self {
+        AdminError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for AdminError {
+    fn from(e: serde_json::Error) -> Self {
+        AdminError::Json(e)
+    }
+}
@@ -0,0 +1,93 @@
+pub mod cli;
+pub mod error;
+
+pub mod commands {
+    pub mod create_admin;
+    pub mod create_api_key;
+    pub mod create_service_account;
+    pub mod dump;
+    pub mod purge_uploads;
+    pub mod reindex;
+    pub mod reset_password;
+    pub mod seed;
+    pub mod seed_reference;
+}
+
+use colored::Colorize;
+use crab_rocket_schema::establish_pg_connection;
+use crab_rocket_user::mappers::user_mapper::UserMapper;
+use crab_rocket_user::models::user::User;
+use crab_rocket_utils::password::validate_password;
+use diesel::PgConnection;
+use obj_traits::mapper::mapper_crud::MapperCRUD;
+
+use crate::cli::{Command, UserSelector};
+use crate::error::AdminError;
+
+/// Runs a `crab_rocket-admin` subcommand, returns the process exit code.
+pub fn run(command: Command) -> i32 {
+    let mut conn = match establish_pg_connection() {
+        Ok(conn) => conn,
+        Err(e) => {
+            eprintln!("{} {e}", "Failed to connect to database:".red());
+            return 1;
+        }
+    };
+    let result: Result<(), AdminError> = match command {
+        Command::CreateAdmin(args) => commands::create_admin::run(&mut conn, args),
+        Command::ResetPassword(args) => commands::reset_password::run(&mut conn, args),
+        Command::CreateServiceAccount(args) => {
+            commands::create_service_account::run(&mut conn, args)
+        }
+        Command::CreateApiKey(args) => commands::create_api_key::run(&mut conn, args),
+        Command::SeedReference => commands::seed_reference::run(&mut conn),
+        Command::Seed(args) => commands::seed::run(&mut conn, args),
+        Command::Reindex(args) => commands::reindex::run(&mut conn, args),
+        Command::PurgeUploads(args) => commands::purge_uploads::run(&mut conn, args),
+        Command::Dump(args) => commands::dump::run(&mut conn, args),
+    };
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("{} {e}", "Error:".red());
+            1
+        }
+    }
+}
+
+/// The password given on the command line, otherwise one line read from standard input.
+pub(crate) fn password_or_stdin(password: Option<String>) -> Result<String, AdminError> {
+    let password = match password {
+        Some(password) => password,
+        None => {
+            eprint!("Password: ");
+            let mut line = String::new();
+            std::io::stdin().read_line(&mut line)?;
+            line.trim_end_matches(['\r', '\n']).to_string()
+        }
+    };
+    validate_password(&password).map_err(|e| AdminError::invalid(e.to_string()))?;
+    Ok(password)
+}
+
+/// The user picked by `--username` or `--user-id`.
+pub(crate) fn find_user(
+    conn: &mut PgConnection,
+    selector: &UserSelector,
+) -> Result<User, AdminError> {
+    match selector {
+        UserSelector {
+            user_id: Some(user_id),
+            ..
+        } => UserMapper::get_by_id(conn, *user_id),
+        UserSelector {
+            username: Some(username),
+            ..
+        } => UserMapper::get_by_username(conn, username),
+        _ => return Err(AdminError::invalid("either --username or --user-id is required")),
+    }
+    .map_err(|e| match e {
+        diesel::result::Error::NotFound => AdminError::invalid("user not found"),
+        e => e.into(),
+    })
+}
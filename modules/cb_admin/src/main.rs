@@ -0,0 +1,16 @@
+use clap::Parser;
+use crab_rocket_admin::cli::Cli;
+use dotenvy::dotenv;
+use std::env;
+
+fn main() {
+    // Clear environment variable before running.
+    env::remove_var("DATABASE_URL");
+    dotenv().ok();
+
+    let cli = Cli::parse();
+    // Load config, exits on invalid values.
+    crab_rocket_config::init();
+
+    std::process::exit(crab_rocket_admin::run(cli.command));
+}
@@ -0,0 +1,59 @@
+use chrono::NaiveDate;
+
+use crate::error::AttendanceError;
+use crate::models::attendance::Attendance;
+use crate::models::attendance_summary::{DailyAttendance, DepartmentAttendance};
+use crate::services::attendance_service::AttendanceService;
+
+pub struct AttendanceController {}
+
+fn to_tuple<T>(result: Result<T, AttendanceError>) -> (i32, String, Option<T>) {
+    match result {
+        Ok(data) => (200, String::from("Success"), Some(data)),
+        Err(AttendanceError::Internal(e)) => {
+            println!("{e:?}");
+            (500, String::from("Internal Server Error"), None)
+        }
+        Err(e) => (e.status(), e.to_string(), None),
+    }
+}
+
+/// Parses an optional `YYYY-MM-DD` query parameter.
+fn parse_date(date: Option<&str>) -> Result<Option<NaiveDate>, AttendanceError> {
+    date.map(|date| {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|_| AttendanceError::InvalidDate(date.to_string()))
+    })
+    .transpose()
+}
+
+impl AttendanceController {
+    pub fn clock_in(employee_id: i32) -> (i32, String, Option<Attendance>) {
+        to_tuple(AttendanceService::clock_in(employee_id))
+    }
+
+    pub fn clock_out(employee_id: i32) -> (i32, String, Option<Attendance>) {
+        to_tuple(AttendanceService::clock_out(employee_id))
+    }
+
+    pub fn by_employee(
+        employee_id: i32,
+        from: Option<&str>,
+        to: Option<&str>,
+    ) -> (i32, String, Option<Vec<DailyAttendance>>) {
+        to_tuple(
+            parse_date(from).and_then(|from| {
+                AttendanceService::by_employee(employee_id, from, parse_date(to)?)
+            }),
+        )
+    }
+
+    pub fn by_department(
+        department_id: i32,
+        day: Option<&str>,
+    ) -> (i32, String, Option<DepartmentAttendance>) {
+        to_tuple(
+            parse_date(day).and_then(|day| AttendanceService::by_department(department_id, day)),
+        )
+    }
+}
@@ -0,0 +1,83 @@
+use std::error::Error;
+use std::fmt;
+
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel::ConnectionError;
+
+/// Why an employee could not clock in or out, or their attendance not be summed up.
+#[derive(Debug)]
+pub enum AttendanceError {
+    EmployeeNotFound(i32),
+    DepartmentNotFound(i32),
+    /// Not a `YYYY-MM-DD` date.
+    InvalidDate(String),
+    InvalidRange {
+        from: NaiveDate,
+        to: NaiveDate,
+    },
+    /// More days than [`MAX_SUMMARY_DAYS`](crate::services::attendance_service::MAX_SUMMARY_DAYS).
+    RangeTooLong(i64),
+    AlreadyClockedIn {
+        employee_id: i32,
+        since: NaiveDateTime,
+    },
+    NotClockedIn(i32),
+    Internal(String),
+}
+
+impl AttendanceError {
+    pub fn status(&self) -> i32 {
+        match self {
+            AttendanceError::EmployeeNotFound(_) | AttendanceError::DepartmentNotFound(_) => 404,
+            AttendanceError::InvalidDate(_)
+            | AttendanceError::InvalidRange {
+                ..
+            }
+            | AttendanceError::RangeTooLong(_) => 400,
+            AttendanceError::AlreadyClockedIn {
+                ..
+            }
+            | AttendanceError::NotClockedIn(_) => 409,
+            AttendanceError::Internal(_) => 500,
+        }
+    }
+}
+
+impl fmt::Display for AttendanceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AttendanceError::EmployeeNotFound(id) => write!(f, "employee {id} not found"),
+            AttendanceError::DepartmentNotFound(id) => write!(f, "department {id} not found"),
+            AttendanceError::InvalidDate(date) => {
+                write!(f, "`{date}` is not a date, expected YYYY-MM-DD")
+            }
+            AttendanceError::InvalidRange {
+                from,
+                to,
+            } => write!(f, "the range from {from} to {to} ends before it starts"),
+            AttendanceError::RangeTooLong(days) => {
+                write!(f, "{days} days are too many for one summary")
+            }
+            AttendanceError::AlreadyClockedIn {
+                employee_id,
+                since,
+            } => write!(f, "employee {employee_id} is clocked in since {since}"),
+            AttendanceError::NotClockedIn(id) => write!(f, "employee {id} is not clocked in"),
+            AttendanceError::Internal(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for AttendanceError {}
+
+impl From<diesel::result::Error> for AttendanceError {
+    fn from(e: diesel::result::Error) -> Self {
+        AttendanceError::Internal(e.to_string())
+    }
+}
+
+impl From<ConnectionError> for AttendanceError {
+    fn from(e: ConnectionError) -> Self {
+        AttendanceError::Internal(e.to_string())
+    }
+}
@@ -0,0 +1,22 @@
+pub mod error;
+
+pub mod models {
+    pub mod attendance;
+    pub mod attendance_summary;
+}
+
+pub mod mappers {
+    pub mod attendance_mapper;
+}
+
+pub mod controllers {
+    pub mod attendance_controller;
+}
+
+pub mod routes {
+    pub mod attendance_route;
+}
+
+pub mod services {
+    pub mod attendance_service;
+}
@@ -0,0 +1,29 @@
+#[macro_use]
+extern crate rocket;
+
+use crab_rocket_attendance::routes::attendance_route::*;
+use dotenvy::dotenv;
+use std::env;
+#[launch]
+fn rocket() -> _ {
+    // Clear environment variable before running.
+    env::remove_var("DATABASE_URL");
+    dotenv().ok();
+    let figment = crab_rocket_config::init();
+
+    crab_rocket_utils::run_preload();
+
+    let cors = crab_rocket_config::cors::cors();
+
+    rocket::custom(figment)
+        .mount(
+            "/api",
+            routes![
+                clock_in_employee,
+                clock_out_employee,
+                get_employee_attendance,
+                get_department_attendance
+            ],
+        )
+        .attach(cors)
+}
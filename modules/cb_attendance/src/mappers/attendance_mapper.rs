@@ -0,0 +1,164 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use crab_rocket_schema::schema::{attendance_table, department_table, employee_table};
+use diesel::sql_types::{Date, Integer, Timestamp};
+use diesel::{prelude::*, result::Error, sql_query};
+
+use crate::models::attendance::{Attendance, NewAttendance};
+use crate::models::attendance_summary::DailyAttendance;
+
+/// The aggregates of [`DailyAttendance`] over `a`, a record still open is counted until `$now`.
+const SUMMARY_COLUMNS: &str = "e.employee_id, e.employee_name, \
+     COUNT(a.attendance_id) AS records, \
+     MIN(a.clock_in) AS first_clock_in, \
+     MAX(a.clock_out) AS last_clock_out, \
+     COALESCE(BOOL_OR(a.attendance_id IS NOT NULL AND a.clock_out IS NULL), false) AS clocked_in, \
+     COALESCE(FLOOR(EXTRACT(EPOCH FROM SUM( \
+         GREATEST(COALESCE(a.clock_out, $now) - a.clock_in, INTERVAL '0')) \
+     ) / 60), 0)::INT8 AS worked_minutes";
+
+pub struct AttendanceMapper {}
+
+impl AttendanceMapper {
+    /// The record the employee is clocked in on.
+    pub fn get_open(
+        conn: &mut PgConnection,
+        employee_id: i32,
+    ) -> Result<Option<Attendance>, Error> {
+        attendance_table::table
+            .filter(attendance_table::employee_id.eq(employee_id))
+            .filter(attendance_table::clock_out.is_null())
+            .select(Attendance::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn add_single(conn: &mut PgConnection, obj: &NewAttendance) -> Result<Attendance, Error> {
+        diesel::insert_into(attendance_table::table)
+            .values(obj)
+            .returning(Attendance::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn clock_out(
+        conn: &mut PgConnection,
+        pid: i32,
+        clock_out: NaiveDateTime,
+    ) -> Result<Attendance, Error> {
+        diesel::update(attendance_table::table.find(pid))
+            .set(attendance_table::clock_out.eq(clock_out))
+            .returning(Attendance::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn get_employee_name(conn: &mut PgConnection, employee_id: i32) -> Result<String, Error> {
+        employee_table::table.find(employee_id).select(employee_table::employee_name).first(conn)
+    }
+
+    pub fn get_department_name(
+        conn: &mut PgConnection,
+        department_id: i32,
+    ) -> Result<String, Error> {
+        department_table::table
+            .find(department_id)
+            .select(department_table::department_name)
+            .first(conn)
+    }
+
+    /// One entry per day of `from..=to` the employee clocked in on, the earliest first.
+    pub fn daily_by_employee(
+        conn: &mut PgConnection,
+        employee_id: i32,
+        from: NaiveDate,
+        to: NaiveDate,
+        now: NaiveDateTime,
+    ) -> Result<Vec<DailyAttendance>, Error> {
+        sql_query(format!(
+            "SELECT a.clock_in::DATE AS day, {} \
+             FROM attendance_table a JOIN employee_table e ON e.employee_id = a.employee_id \
+             WHERE a.employee_id = $1 AND a.clock_in >= $2 AND a.clock_in < $3 + 1 \
+             GROUP BY a.clock_in::DATE, e.employee_id, e.employee_name \
+             ORDER BY day",
+            SUMMARY_COLUMNS.replace("$now", "$4")
+        ))
+        .bind::<Integer, _>(employee_id)
+        .bind::<Date, _>(from)
+        .bind::<Date, _>(to)
+        .bind::<Timestamp, _>(now)
+        .load(conn)
+    }
+
+    /// One entry per employee of the department on `day`, absent ones included, by name.
+    /// Employees of sub-departments are not included.
+    pub fn daily_by_department(
+        conn: &mut PgConnection,
+        department_id: i32,
+        day: NaiveDate,
+        now: NaiveDateTime,
+    ) -> Result<Vec<DailyAttendance>, Error> {
+        sql_query(format!(
+            "SELECT $2::DATE AS day, {} \
+             FROM employee_table e LEFT JOIN attendance_table a \
+               ON a.employee_id = e.employee_id AND a.clock_in >= $2 AND a.clock_in < $2 + 1 \
+             WHERE e.department_id = $1 \
+             GROUP BY e.employee_id, e.employee_name \
+             ORDER BY e.employee_name, e.employee_id",
+            SUMMARY_COLUMNS.replace("$now", "$3")
+        ))
+        .bind::<Integer, _>(department_id)
+        .bind::<Date, _>(day)
+        .bind::<Timestamp, _>(now)
+        .load(conn)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{NaiveDate, NaiveDateTime};
+    use crab_rocket_test_support::{fixtures, test_conn};
+    use diesel::PgConnection;
+
+    use super::AttendanceMapper;
+    use crate::models::attendance::NewAttendance;
+
+    fn at(time: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(time, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn record(conn: &mut PgConnection, employee_id: i32, clock_in: &str, clock_out: Option<&str>) {
+        let obj = NewAttendance {
+            employee_id,
+            clock_in: at(clock_in),
+            clock_out: clock_out.map(at),
+        };
+        AttendanceMapper::add_single(conn, &obj).unwrap();
+    }
+
+    #[test]
+    fn test_daily_summaries_add_up_records() {
+        let mut conn = test_conn();
+        let department = fixtures::department(&mut conn, "Attendance floor", None);
+        let worker = fixtures::employee(&mut conn, "att_worker", Some(department), None);
+        fixtures::employee(&mut conn, "att_absent", Some(department), None);
+        // A split shift on the 1st, a shift still running on the 2nd.
+        record(&mut conn, worker, "2026-03-01 08:00", Some("2026-03-01 12:00"));
+        record(&mut conn, worker, "2026-03-01 13:00", Some("2026-03-01 17:30"));
+        record(&mut conn, worker, "2026-03-02 09:00", None);
+        let now = at("2026-03-02 10:15");
+        let (from, to) = (
+            NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 3, 2).unwrap(),
+        );
+
+        let days = AttendanceMapper::daily_by_employee(&mut conn, worker, from, to, now).unwrap();
+        let summary: Vec<_> = days
+            .iter()
+            .map(|d| (d.day(), d.records(), d.worked_minutes(), d.clocked_in()))
+            .collect();
+        assert_eq!(summary, [(from, 2, 510, false), (to, 1, 75, true)]);
+
+        let floor =
+            AttendanceMapper::daily_by_department(&mut conn, department, from, now).unwrap();
+        let summary: Vec<_> = floor.iter().map(|d| (d.records(), d.worked_minutes())).collect();
+        assert_eq!(summary, [(0, 0), (2, 510)]);
+    }
+}
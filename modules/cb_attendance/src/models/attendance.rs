@@ -0,0 +1,43 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use rocket::serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// The time an employee spent clocked in, still running while `clock_out` is `None`.
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+#[diesel(table_name = crab_rocket_schema::schema::attendance_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Attendance {
+    attendance_id: i32,
+    employee_id: i32,
+    clock_in: NaiveDateTime,
+    clock_out: Option<NaiveDateTime>,
+}
+
+impl Attendance {
+    pub fn attendance_id(&self) -> i32 {
+        self.attendance_id
+    }
+
+    pub fn employee_id(&self) -> i32 {
+        self.employee_id
+    }
+
+    pub fn clock_in(&self) -> NaiveDateTime {
+        self.clock_in
+    }
+
+    pub fn clock_out(&self) -> Option<NaiveDateTime> {
+        self.clock_out
+    }
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crab_rocket_schema::schema::attendance_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NewAttendance {
+    pub employee_id: i32,
+    pub clock_in: NaiveDateTime,
+    pub clock_out: Option<NaiveDateTime>,
+}
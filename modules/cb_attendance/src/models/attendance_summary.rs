@@ -0,0 +1,77 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel::sql_types::{Bool, Date, Int4, Int8, Nullable, Timestamp, Varchar};
+use diesel::QueryableByName;
+use rocket::serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// An employee's attendance on one day, records count for the day they were clocked in on.
+#[derive(QueryableByName, Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct DailyAttendance {
+    #[diesel(sql_type = Date)]
+    day: NaiveDate,
+    #[diesel(sql_type = Int4)]
+    employee_id: i32,
+    #[diesel(sql_type = Varchar)]
+    employee_name: String,
+    /// Times clocked in, `0` for an absent employee.
+    #[diesel(sql_type = Int8)]
+    records: i64,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    first_clock_in: Option<NaiveDateTime>,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    last_clock_out: Option<NaiveDateTime>,
+    /// Still clocked in, the time so far is part of `worked_minutes`.
+    #[diesel(sql_type = Bool)]
+    clocked_in: bool,
+    #[diesel(sql_type = Int8)]
+    worked_minutes: i64,
+}
+
+impl DailyAttendance {
+    pub fn day(&self) -> NaiveDate {
+        self.day
+    }
+
+    pub fn employee_id(&self) -> i32 {
+        self.employee_id
+    }
+
+    pub fn records(&self) -> i64 {
+        self.records
+    }
+
+    pub fn clocked_in(&self) -> bool {
+        self.clocked_in
+    }
+
+    pub fn worked_minutes(&self) -> i64 {
+        self.worked_minutes
+    }
+}
+
+/// A department's attendance on one day, one entry per employee of the department.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct DepartmentAttendance {
+    pub department_id: i32,
+    pub day: NaiveDate,
+    pub employees: usize,
+    /// Employees that clocked in at least once.
+    pub present: usize,
+    pub worked_minutes: i64,
+    pub attendance: Vec<DailyAttendance>,
+}
+
+impl DepartmentAttendance {
+    pub fn new(department_id: i32, day: NaiveDate, attendance: Vec<DailyAttendance>) -> Self {
+        Self {
+            department_id,
+            day,
+            employees: attendance.len(),
+            present: attendance.iter().filter(|a| a.records > 0).count(),
+            worked_minutes: attendance.iter().map(|a| a.worked_minutes).sum(),
+            attendance,
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use crab_rocket_auth::guards::authorized::Authorized;
+use crab_rocket_auth::permission;
+use obj_traits::response::api_response::ApiResponse;
+use rocket::http::Status;
+use rocket::response::status;
+use rocket::serde::json::Json;
+use rocket::{get, post};
+
+use crate::controllers::attendance_controller::AttendanceController;
+
+permission!(pub AttendanceRead, "attendance", "read");
+permission!(pub AttendanceCreate, "attendance", "create");
+permission!(pub AttendanceUpdate, "attendance", "update");
+
+/// Answers with `code` as the HTTP status, in the shape of the other attendance routes.
+fn to_response<T: rocket::serde::Serialize + Default>(
+    code: i32,
+    message: String,
+    data: T,
+) -> status::Custom<Json<serde_json::Value>> {
+    let response = serde_json::to_value(ApiResponse::new(code, message, data)).unwrap();
+    let status = Status::from_code(code as u16).unwrap_or(Status::InternalServerError);
+    status::Custom(status, Json(response))
+}
+
+/// Clocking in twice answers `409` with the time of the first.
+#[post("/employee/<id>/attendance/clock-in")]
+pub fn clock_in_employee(
+    _auth: Authorized<AttendanceCreate>,
+    id: i32,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = AttendanceController::clock_in(id);
+    to_response(code, message, data)
+}
+
+#[post("/employee/<id>/attendance/clock-out")]
+pub fn clock_out_employee(
+    _auth: Authorized<AttendanceUpdate>,
+    id: i32,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = AttendanceController::clock_out(id);
+    to_response(code, message, data)
+}
+
+/// One summary per day the employee clocked in on in `from..=to`, the last week by default.
+#[get("/employee/<id>/attendance?<from>&<to>")]
+pub fn get_employee_attendance(
+    _auth: Authorized<AttendanceRead>,
+    id: i32,
+    from: Option<&str>,
+    to: Option<&str>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = AttendanceController::by_employee(id, from, to);
+    to_response(code, message, data)
+}
+
+/// Every employee of the department on `day`, today by default.
+#[get("/department/<id>/attendance?<day>")]
+pub fn get_department_attendance(
+    _auth: Authorized<AttendanceRead>,
+    id: i32,
+    day: Option<&str>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = AttendanceController::by_department(id, day);
+    to_response(code, message, data)
+}
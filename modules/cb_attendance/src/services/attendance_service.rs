@@ -0,0 +1,112 @@
+use chrono::{Duration, NaiveDate};
+use crab_rocket_schema::establish_pg_connection;
+use crab_rocket_utils::time::get_e8_time;
+use diesel::result::DatabaseErrorKind;
+use diesel::{Connection, PgConnection};
+
+use crate::error::AttendanceError;
+use crate::mappers::attendance_mapper::AttendanceMapper;
+use crate::models::attendance::{Attendance, NewAttendance};
+use crate::models::attendance_summary::{DailyAttendance, DepartmentAttendance};
+
+/// Days of an employee's summary without a `from`.
+pub const DEFAULT_SUMMARY_DAYS: i64 = 7;
+/// Days one employee summary covers at most.
+pub const MAX_SUMMARY_DAYS: i64 = 366;
+
+pub struct AttendanceService {}
+
+impl AttendanceService {
+    /// Opens a record at the current time, an employee clocked in already answers with the
+    /// record they are on.
+    pub fn clock_in(employee_id: i32) -> Result<Attendance, AttendanceError> {
+        let mut conn = establish_pg_connection()?;
+        conn.transaction(|conn| {
+            find_employee(conn, employee_id)?;
+            if let Some(open) = AttendanceMapper::get_open(conn, employee_id)? {
+                return Err(AttendanceError::AlreadyClockedIn {
+                    employee_id,
+                    since: open.clock_in(),
+                });
+            }
+            let now = get_e8_time();
+            let obj = NewAttendance {
+                employee_id,
+                clock_in: now,
+                clock_out: None,
+            };
+            // Another clock-in may have won the race since the check.
+            AttendanceMapper::add_single(conn, &obj).map_err(|e| match e {
+                diesel::result::Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
+                    AttendanceError::AlreadyClockedIn {
+                        employee_id,
+                        since: now,
+                    }
+                }
+                e => e.into(),
+            })
+        })
+    }
+
+    /// Closes the record the employee is clocked in on at the current time.
+    pub fn clock_out(employee_id: i32) -> Result<Attendance, AttendanceError> {
+        let mut conn = establish_pg_connection()?;
+        conn.transaction(|conn| {
+            find_employee(conn, employee_id)?;
+            let Some(open) = AttendanceMapper::get_open(conn, employee_id)? else {
+                return Err(AttendanceError::NotClockedIn(employee_id));
+            };
+            let now = get_e8_time().max(open.clock_in());
+            Ok(AttendanceMapper::clock_out(conn, open.attendance_id(), now)?)
+        })
+    }
+
+    /// The employee's days in `from..=to`, by default the [`DEFAULT_SUMMARY_DAYS`] up to today.
+    pub fn by_employee(
+        employee_id: i32,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    ) -> Result<Vec<DailyAttendance>, AttendanceError> {
+        let now = get_e8_time();
+        let to = to.unwrap_or(now.date());
+        let from = from.unwrap_or(to - Duration::days(DEFAULT_SUMMARY_DAYS - 1));
+        if to < from {
+            return Err(AttendanceError::InvalidRange {
+                from,
+                to,
+            });
+        }
+        let days = (to - from).num_days() + 1;
+        if days > MAX_SUMMARY_DAYS {
+            return Err(AttendanceError::RangeTooLong(days));
+        }
+        let mut conn = establish_pg_connection()?;
+        find_employee(&mut conn, employee_id)?;
+        Ok(AttendanceMapper::daily_by_employee(&mut conn, employee_id, from, to, now)?)
+    }
+
+    /// Every employee of the department on `day`, today by default.
+    pub fn by_department(
+        department_id: i32,
+        day: Option<NaiveDate>,
+    ) -> Result<DepartmentAttendance, AttendanceError> {
+        let now = get_e8_time();
+        let day = day.unwrap_or(now.date());
+        let mut conn = establish_pg_connection()?;
+        match AttendanceMapper::get_department_name(&mut conn, department_id) {
+            Err(diesel::result::Error::NotFound) => {
+                return Err(AttendanceError::DepartmentNotFound(department_id))
+            }
+            result => result?,
+        };
+        let attendance = AttendanceMapper::daily_by_department(&mut conn, department_id, day, now)?;
+        Ok(DepartmentAttendance::new(department_id, day, attendance))
+    }
+}
+
+fn find_employee(conn: &mut PgConnection, employee_id: i32) -> Result<String, AttendanceError> {
+    match AttendanceMapper::get_employee_name(conn, employee_id) {
+        Err(diesel::result::Error::NotFound) => Err(AttendanceError::EmployeeNotFound(employee_id)),
+        result => Ok(result?),
+    }
+}
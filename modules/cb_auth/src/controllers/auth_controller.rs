@@ -0,0 +1,326 @@
+use crate::error::AuthError;
+use crate::guards::auth_user::AuthUser;
+use crate::models::account::{EmailStatus, PasswordResetConfirm};
+use crate::models::api_key::{ApiKeyInfo, CreatedApiKey, NewApiKey};
+use crate::models::impersonation::{Impersonation, ImpersonationRequest, ImpersonationToken};
+use crate::models::lockout::LockoutStatus;
+use crate::models::login::{LoginRequest, RefreshRequest, TokenResponse};
+use crate::models::oidc::{OidcAuthorization, OidcCallback, OidcIdentity, OidcProviderInfo};
+use crate::models::session::{ClientInfo, SessionInfo};
+use crate::models::two_factor::{RecoveryCodes, TwoFactorEnrollment, TwoFactorStatus};
+use crate::services::account_service::{AccountError, AccountService, VerificationRequest};
+use crate::services::api_key_service::{ApiKeyError, ApiKeyService};
+use crate::services::auth_service::AuthService;
+use crate::services::impersonation_service::{ImpersonationError, ImpersonationService};
+use crate::services::lockout_service::{LockoutClear, LockoutService};
+use crate::services::oidc_service::{OidcError, OidcService};
+use crate::services::rbac_service::RbacService;
+use crate::services::session_service::SessionService;
+use crate::services::two_factor_service::{TwoFactorError, TwoFactorService};
+use crab_rocket_user::models::user::{ChangePassword, User};
+use uuid::Uuid;
+
+fn from_error<T>(e: AuthError) -> (i32, String, Option<T>) {
+    match e {
+        AuthError::Internal(_) => {
+            println!("{e:?}");
+            (500, String::from("Internal Server Error"), None)
+        }
+        e => (e.status().code as i32, e.to_string(), None),
+    }
+}
+
+pub fn login(obj: &LoginRequest, client: &ClientInfo) -> (i32, String, Option<TokenResponse>) {
+    match AuthService::login(obj, client) {
+        Ok(token) => (200, String::from("Ok"), Some(token)),
+        Err(e) => from_error(e),
+    }
+}
+
+pub fn refresh(obj: &RefreshRequest) -> (i32, String, Option<TokenResponse>) {
+    match AuthService::refresh(obj) {
+        Ok(token) => (200, String::from("Ok"), Some(token)),
+        Err(e) => from_error(e),
+    }
+}
+
+pub fn get_me(auth: AuthUser) -> (i32, String, User) {
+    (200, String::from("Ok"), auth.user)
+}
+
+pub fn logout(auth: &AuthUser) -> (i32, String, Option<()>) {
+    match ImpersonationService::end(auth) {
+        Ok(true) => return (200, String::from("Impersonation ended"), None),
+        Ok(false) => {}
+        Err(e) => return from_error(e),
+    }
+    let Some(session_id) = auth.session_id() else {
+        return (200, String::from("Ok"), None);
+    };
+    match SessionService::logout(session_id) {
+        Ok(()) => (200, String::from("Logged out"), None),
+        Err(e) => from_error(e),
+    }
+}
+
+pub fn get_sessions(auth: &AuthUser) -> (i32, String, Option<Vec<SessionInfo>>) {
+    match SessionService::list(auth.user_id(), auth.session_id()) {
+        Ok(sessions) => (200, String::from("Ok"), Some(sessions)),
+        Err(e) => from_error(e),
+    }
+}
+
+pub fn revoke_session(auth: &AuthUser, session_id: Uuid) -> (i32, String, Option<()>) {
+    match SessionService::revoke(auth.user_id(), session_id) {
+        Ok(true) => (200, String::from("Session revoked"), None),
+        Ok(false) => (404, String::from("No such session"), None),
+        Err(e) => from_error(e),
+    }
+}
+
+/// `keep_current` spares the session of the calling token.
+pub fn revoke_sessions(auth: &AuthUser, keep_current: bool) -> (i32, String, Option<usize>) {
+    let keep = auth.session_id().filter(|_| keep_current);
+    match SessionService::revoke_all(auth.user_id(), keep) {
+        Ok(count) => (200, format!("{count} sessions revoked"), Some(count)),
+        Err(e) => from_error(e),
+    }
+}
+
+pub fn get_permissions(auth: &AuthUser) -> (i32, String, Option<Vec<String>>) {
+    match RbacService::permissions_of(auth) {
+        Ok(permissions) => {
+            (200, String::from("Ok"), Some(permissions.names().map(String::from).collect()))
+        }
+        Err(e) => from_error(e),
+    }
+}
+
+fn from_api_key_error<T>(e: ApiKeyError) -> (i32, String, Option<T>) {
+    match e {
+        ApiKeyError::Auth(e) => from_error(e),
+        e => (e.status(), e.to_string(), None),
+    }
+}
+
+pub fn create_api_key(auth: &AuthUser, obj: &NewApiKey) -> (i32, String, Option<CreatedApiKey>) {
+    match ApiKeyService::create(auth, obj) {
+        Ok(created) => {
+            (200, String::from("Store the key now, it is not shown again"), Some(created))
+        }
+        Err(e) => from_api_key_error(e),
+    }
+}
+
+pub fn get_api_keys(auth: &AuthUser) -> (i32, String, Option<Vec<ApiKeyInfo>>) {
+    match ApiKeyService::list(auth) {
+        Ok(api_keys) => (200, String::from("Ok"), Some(api_keys)),
+        Err(e) => from_api_key_error(e),
+    }
+}
+
+pub fn revoke_api_key(auth: &AuthUser, api_key_id: Uuid) -> (i32, String, Option<()>) {
+    match ApiKeyService::revoke(auth, api_key_id) {
+        Ok(true) => (200, String::from("API key revoked"), None),
+        Ok(false) => (404, String::from("No such API key"), None),
+        Err(e) => from_api_key_error(e),
+    }
+}
+
+fn from_account_error<T>(e: AccountError) -> (i32, String, Option<T>) {
+    match e {
+        AccountError::Auth(e) => from_error(e),
+        e => (e.status(), e.to_string(), None),
+    }
+}
+
+pub fn get_email_status(auth: &AuthUser) -> (i32, String, Option<EmailStatus>) {
+    match AccountService::email_status(auth) {
+        Ok(status) => (200, String::from("Ok"), Some(status)),
+        Err(e) => from_account_error(e),
+    }
+}
+
+pub fn request_email_verification(auth: &AuthUser) -> (i32, String, Option<()>) {
+    match AccountService::request_email_verification(auth) {
+        Ok(VerificationRequest::Sent) => (200, String::from("Verification mail sent"), None),
+        Ok(VerificationRequest::AlreadyVerified) => {
+            (200, String::from("Email address is verified already"), None)
+        }
+        Err(e) => from_account_error(e),
+    }
+}
+
+pub fn confirm_email_verification(token: &str) -> (i32, String, Option<()>) {
+    match AccountService::confirm_email_verification(token) {
+        Ok(()) => (200, String::from("Email address verified"), None),
+        Err(e) => from_account_error(e),
+    }
+}
+
+pub fn request_password_reset(login: &str) -> (i32, String, Option<()>) {
+    match AccountService::request_password_reset(login) {
+        Ok(()) => (
+            200,
+            String::from("If the account has an email address, a reset mail is on its way"),
+            None,
+        ),
+        Err(e) => from_account_error(e),
+    }
+}
+
+pub fn confirm_password_reset(obj: &PasswordResetConfirm) -> (i32, String, Option<()>) {
+    match AccountService::confirm_password_reset(obj) {
+        Ok(()) => (200, String::from("Password reset, log in with the new password"), None),
+        Err(e) => from_account_error(e),
+    }
+}
+
+pub fn change_password(
+    auth: &AuthUser,
+    user_id: i32,
+    obj: &ChangePassword,
+    client: &ClientInfo,
+) -> (i32, String, Option<()>) {
+    match AccountService::change_password(auth, user_id, obj, client) {
+        Ok(()) => (200, String::from("Password changed"), None),
+        Err(e) => from_account_error(e),
+    }
+}
+
+fn from_two_factor_error<T>(e: TwoFactorError) -> (i32, String, Option<T>) {
+    match e {
+        TwoFactorError::Auth(e) => from_error(e),
+        e => (e.status(), e.to_string(), None),
+    }
+}
+
+pub fn get_two_factor_status(auth: &AuthUser) -> (i32, String, Option<TwoFactorStatus>) {
+    match TwoFactorService::status(auth) {
+        Ok(status) => (200, String::from("Ok"), Some(status)),
+        Err(e) => from_two_factor_error(e),
+    }
+}
+
+pub fn enroll_two_factor(auth: &AuthUser) -> (i32, String, Option<TwoFactorEnrollment>) {
+    match TwoFactorService::enroll(auth) {
+        Ok(enrollment) => (
+            200,
+            String::from("Add the secret to an authenticator app and activate it with a code"),
+            Some(enrollment),
+        ),
+        Err(e) => from_two_factor_error(e),
+    }
+}
+
+pub fn activate_two_factor(auth: &AuthUser, code: &str) -> (i32, String, Option<RecoveryCodes>) {
+    match TwoFactorService::activate(auth, code) {
+        Ok(codes) => (
+            200,
+            String::from("Two-factor authentication enabled, store the recovery codes now"),
+            Some(codes),
+        ),
+        Err(e) => from_two_factor_error(e),
+    }
+}
+
+pub fn disable_two_factor(auth: &AuthUser, code: &str) -> (i32, String, Option<()>) {
+    match TwoFactorService::disable(auth, code) {
+        Ok(()) => (200, String::from("Two-factor authentication disabled"), None),
+        Err(e) => from_two_factor_error(e),
+    }
+}
+
+pub fn regenerate_recovery_codes(
+    auth: &AuthUser,
+    code: &str,
+) -> (i32, String, Option<RecoveryCodes>) {
+    match TwoFactorService::regenerate_recovery_codes(auth, code) {
+        Ok(codes) => {
+            (200, String::from("Store the new recovery codes, the old ones are void"), Some(codes))
+        }
+        Err(e) => from_two_factor_error(e),
+    }
+}
+
+pub fn get_lockout(user_id: i32) -> (i32, String, Option<LockoutStatus>) {
+    match LockoutService::status(user_id) {
+        Ok(Some(status)) => (200, String::from("Ok"), Some(status)),
+        Ok(None) => (404, String::from("No such user"), None),
+        Err(e) => from_error(e),
+    }
+}
+
+pub fn clear_lockout(auth: &AuthUser, user_id: i32) -> (i32, String, Option<()>) {
+    match LockoutService::clear(auth, user_id) {
+        Ok(LockoutClear::Cleared) => (200, String::from("Lockout cleared"), None),
+        Ok(LockoutClear::NothingToClear) => (200, String::from("No failed logins to clear"), None),
+        Ok(LockoutClear::UnknownUser) => (404, String::from("No such user"), None),
+        Err(e) => from_error(e),
+    }
+}
+
+fn from_impersonation_error<T>(e: ImpersonationError) -> (i32, String, Option<T>) {
+    match e {
+        ImpersonationError::Auth(e) => from_error(e),
+        e => (e.status(), e.to_string(), None),
+    }
+}
+
+pub fn impersonate(
+    auth: &AuthUser,
+    user_id: i32,
+    obj: &ImpersonationRequest,
+    client: &ClientInfo,
+) -> (i32, String, Option<ImpersonationToken>) {
+    match ImpersonationService::start(auth, user_id, obj, client.ip_address.as_deref()) {
+        Ok(token) => (200, String::from("Ok"), Some(token)),
+        Err(e) => from_impersonation_error(e),
+    }
+}
+
+pub fn get_impersonations(
+    user_id: Option<i32>,
+    limit: Option<i64>,
+) -> (i32, String, Option<Vec<Impersonation>>) {
+    match ImpersonationService::history(user_id, limit.unwrap_or(50)) {
+        Ok(impersonations) => (200, String::from("Ok"), Some(impersonations)),
+        Err(e) => from_error(e),
+    }
+}
+
+fn from_oidc_error<T>(e: OidcError) -> (i32, String, Option<T>) {
+    match e {
+        OidcError::Auth(e) => from_error(e),
+        e => (e.status(), e.to_string(), None),
+    }
+}
+
+pub fn get_oidc_providers() -> (i32, String, Option<Vec<OidcProviderInfo>>) {
+    (200, String::from("Ok"), Some(OidcService::providers()))
+}
+
+pub fn authorize_oidc(provider: &str) -> (i32, String, Option<OidcAuthorization>) {
+    match OidcService::authorize(provider) {
+        Ok(authorization) => (200, String::from("Ok"), Some(authorization)),
+        Err(e) => from_oidc_error(e),
+    }
+}
+
+pub fn oidc_callback(
+    provider: &str,
+    callback: &OidcCallback,
+    client: &ClientInfo,
+) -> (i32, String, Option<TokenResponse>) {
+    match OidcService::callback(provider, callback, client) {
+        Ok(token) => (200, String::from("Ok"), Some(token)),
+        Err(e) => from_oidc_error(e),
+    }
+}
+
+pub fn get_oidc_identities(auth: &AuthUser) -> (i32, String, Option<Vec<OidcIdentity>>) {
+    match OidcService::identities(auth) {
+        Ok(identities) => (200, String::from("Ok"), Some(identities)),
+        Err(e) => from_oidc_error(e),
+    }
+}
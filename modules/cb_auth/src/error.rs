@@ -0,0 +1,95 @@
+use std::fmt;
+
+use rocket::http::Status;
+
+/// Why a caller could not be authenticated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthError {
+    /// No `Authorization: Bearer ...` header.
+    MissingToken,
+    InvalidToken(String),
+    ExpiredToken,
+    /// Unknown login or wrong password, deliberately not telling which.
+    InvalidCredentials,
+    /// The token is fine but its user no longer exists.
+    UnknownUser,
+    /// The session of the token was logged out, revoked or has expired.
+    SessionEnded,
+    /// A refresh token was presented a second time, its session got revoked.
+    RefreshTokenReused,
+    /// The password was right, but the account has two-factor authentication enabled and the
+    /// login carried no `code`.
+    TwoFactorCodeMissing,
+    InvalidTwoFactorCode,
+    /// Too many failed logins for the account or the address, retry after the given seconds.
+    TooManyAttempts(i64),
+    /// Authenticated, but the role does not grant this `resource:action`.
+    Forbidden(String),
+    /// The row belongs to another user and the caller may not moderate the resource.
+    NotOwner {
+        resource: String,
+        id: i32,
+    },
+    /// The role requires two-factor authentication and the session was opened without it.
+    TwoFactorRequired,
+    /// An impersonation token asked for a request only the user themselves may make.
+    Impersonating,
+    Internal(String),
+}
+
+impl AuthError {
+    pub fn status(&self) -> Status {
+        match self {
+            AuthError::Forbidden(_)
+            | AuthError::NotOwner {
+                ..
+            }
+            | AuthError::TwoFactorRequired
+            | AuthError::Impersonating => Status::Forbidden,
+            AuthError::TooManyAttempts(_) => Status::TooManyRequests,
+            AuthError::Internal(_) => Status::InternalServerError,
+            _ => Status::Unauthorized,
+        }
+    }
+
+    pub(crate) fn internal(e: impl fmt::Display) -> Self {
+        AuthError::Internal(e.to_string())
+    }
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthError::MissingToken => write!(f, "missing bearer token"),
+            AuthError::InvalidToken(reason) => write!(f, "invalid token: {reason}"),
+            AuthError::ExpiredToken => write!(f, "token expired"),
+            AuthError::InvalidCredentials => write!(f, "invalid login or password"),
+            AuthError::UnknownUser => write!(f, "user no longer exists"),
+            AuthError::SessionEnded => write!(f, "session has ended, log in again"),
+            AuthError::RefreshTokenReused => {
+                write!(f, "refresh token was used before, the session has been revoked")
+            }
+            AuthError::TwoFactorCodeMissing => write!(f, "two-factor code required"),
+            AuthError::InvalidTwoFactorCode => write!(f, "invalid two-factor code"),
+            AuthError::TooManyAttempts(secs) => {
+                write!(f, "too many failed logins, try again in {secs} seconds")
+            }
+            AuthError::Forbidden(permission) => write!(f, "missing permission `{permission}`"),
+            AuthError::NotOwner {
+                resource,
+                id,
+            } => write!(
+                f,
+                "{resource} {id} belongs to another user, changing it needs `{resource}:moderate`"
+            ),
+            AuthError::TwoFactorRequired => write!(
+                f,
+                "your role requires two-factor authentication, enroll and log in again with a code"
+            ),
+            AuthError::Impersonating => write!(f, "not allowed while impersonating"),
+            AuthError::Internal(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for AuthError {}
@@ -0,0 +1,107 @@
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::{Header, Method};
+use rocket::{Data, Request, Response};
+
+use crate::error::AuthError;
+use crate::guards::auth_user::{bearer_token, AuthUser};
+use crate::guards::authorized::Denied;
+use crate::services::api_key_service::is_api_key;
+use crate::services::impersonation_service::ImpersonationService;
+use crate::services::token_service::verify_access_token;
+
+/// Names the user really making a request.
+pub const ACTOR_HEADER: &str = "X-Actor-User-Id";
+/// Names the user a request is made as, which differs from the actor while impersonating.
+pub const EFFECTIVE_USER_HEADER: &str = "X-Effective-User-Id";
+
+/// What the bearer token of a request claims, checked for its signature only.
+#[derive(Debug, Clone)]
+struct Impersonated {
+    user_id: i32,
+    actor_user_id: i32,
+    method: Method,
+    path: String,
+}
+
+/// Marks every response with [`ACTOR_HEADER`] and [`EFFECTIVE_USER_HEADER`] and logs the
+/// requests made with impersonation tokens. [`AuthUser`] refuses the ones they may not make.
+pub struct ActorTracking {
+    mount: &'static str,
+}
+
+impl ActorTracking {
+    /// For routes mounted at `mount`, e.g. `/api`.
+    pub fn new(mount: &'static str) -> Self {
+        Self {
+            mount,
+        }
+    }
+
+    fn impersonated(&self, request: &Request<'_>) -> Option<Impersonated> {
+        let token = bearer_token(request.headers().get_one("Authorization")).ok()?;
+        if is_api_key(token) {
+            return None;
+        }
+        let claims = verify_access_token(token).ok()?;
+        let actor_user_id = claims.act.as_ref()?.user_id()?;
+        let path = request.uri().path().as_str();
+        path.strip_prefix(self.mount)?;
+        Some(Impersonated {
+            user_id: claims.user_id()?,
+            actor_user_id,
+            method: request.method(),
+            path: path.to_string(),
+        })
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for ActorTracking {
+    fn info(&self) -> Info {
+        Info {
+            name: "Actor tracking",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _: &mut Data<'_>) {
+        let impersonated = self.impersonated(request);
+        request.local_cache(|| impersonated);
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let impersonated = request.local_cache(|| None::<Impersonated>);
+        let users = match request.local_cache(|| Err::<AuthUser, _>(AuthError::MissingToken)) {
+            Ok(auth) => Some((auth.impersonator_id().unwrap_or(auth.user_id()), auth.user_id())),
+            Err(_) => impersonated.as_ref().map(|i| (i.actor_user_id, i.user_id)),
+        };
+        let Some((actor_user_id, user_id)) = users else {
+            return;
+        };
+        response.set_header(Header::new(ACTOR_HEADER, actor_user_id.to_string()));
+        response.set_header(Header::new(EFFECTIVE_USER_HEADER, user_id.to_string()));
+
+        let Some(impersonated) = impersonated else {
+            return;
+        };
+        let status = response.status().code;
+        let blocked = matches!(
+            request.local_cache(|| Denied(AuthError::MissingToken)),
+            Denied(AuthError::Impersonating)
+        );
+        let detail = format!(
+            "{} {} -> {status}{}",
+            impersonated.method,
+            impersonated.path,
+            if blocked {
+                " (blocked)"
+            } else {
+                ""
+            }
+        );
+        println!("user {actor_user_id} as user {user_id}: {detail}");
+        if let Err(e) = ImpersonationService::record_request(user_id, actor_user_id, &detail) {
+            println!("{e:?}");
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use crab_rocket_user::models::user::User;
+use rocket::request::{FromRequest, Outcome, Request};
+
+use uuid::Uuid;
+
+use crate::error::AuthError;
+use crate::guards::authorized::Denied;
+use crate::models::claims::AccessClaims;
+use crate::services::api_key_service::{is_api_key, ApiKeyService};
+use crate::services::auth_service::AuthService;
+use crate::services::impersonation_service::{check_allowed, impersonator};
+use crate::services::rbac_service::EffectivePermissions;
+
+/// The caller of a request, authenticated by `Authorization: Bearer <access token>` or
+/// `Authorization: Bearer <API key>`.
+///
+/// A route requires authentication by taking it as an argument, requests without a valid
+/// token are answered with `401` before the handler runs:
+///
+/// ```ignore
+/// #[get("/auth/me")]
+/// pub fn get_me(auth: AuthUser) -> Json<serde_json::Value> { ... }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AuthUser {
+    pub user: User,
+    pub credential: Credential,
+}
+
+/// What the caller presented.
+#[derive(Debug, Clone)]
+pub enum Credential {
+    AccessToken(AccessClaims),
+    ApiKey(ApiKeyCredential),
+}
+
+#[derive(Debug, Clone)]
+pub struct ApiKeyCredential {
+    pub api_key_id: Uuid,
+    /// The key may use no more than these, whatever the role of its user grants.
+    pub scopes: EffectivePermissions,
+}
+
+impl AuthUser {
+    pub fn user_id(&self) -> i32 {
+        self.user.user_id()
+    }
+
+    /// The user really making the request when the token impersonates `user`.
+    pub fn impersonator_id(&self) -> Option<i32> {
+        impersonator(self).and_then(|actor| actor.user_id())
+    }
+
+    /// The login session behind an access token, API keys and impersonation tokens have
+    /// none.
+    pub fn session_id(&self) -> Option<Uuid> {
+        match &self.credential {
+            Credential::AccessToken(claims) => claims.sid,
+            Credential::ApiKey(_) => None,
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for AuthUser {
+    type Error = AuthError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        // Cached, several guards on one route authenticate once.
+        let result: &Result<AuthUser, AuthError> = request.local_cache(|| {
+            let token = bearer_token(request.headers().get_one("Authorization"))?;
+            if is_api_key(token) {
+                let (user, api_key) = ApiKeyService::authenticate(token)?;
+                return Ok(AuthUser {
+                    user,
+                    credential: Credential::ApiKey(api_key),
+                });
+            }
+            let (user, claims) = AuthService::authenticate(token)?;
+            Ok(AuthUser {
+                user,
+                credential: Credential::AccessToken(claims),
+            })
+        });
+        match result {
+            Ok(auth) => {
+                // Checked on every route taking the caller, however its token spells `Bearer`.
+                if let Err(e) = check_allowed(auth, request.method(), route_path(request)) {
+                    request.local_cache(|| Denied(e.clone()));
+                    return Outcome::Error((e.status(), e));
+                }
+                Outcome::Success(auth.clone())
+            }
+            Err(e) => Outcome::Error((e.status(), e.clone())),
+        }
+    }
+}
+
+/// The path of `request` below the mount point of its route, `user/7` for `/api/user/7`.
+fn route_path<'r>(request: &'r Request<'_>) -> &'r str {
+    let path = request.uri().path().as_str();
+    let base = request.route().map_or("/", |route| route.uri.base());
+    path.strip_prefix(base).unwrap_or(path).trim_start_matches('/')
+}
+
+/// The token of an `Authorization: Bearer <token>` header, the scheme is case-insensitive.
+pub fn bearer_token(header: Option<&str>) -> Result<&str, AuthError> {
+    let header = header.ok_or(AuthError::MissingToken)?;
+    match header.split_once(' ') {
+        Some((scheme, token))
+            if scheme.eq_ignore_ascii_case("bearer") && !token.trim().is_empty() =>
+        {
+            Ok(token.trim())
+        }
+        _ => Err(AuthError::MissingToken),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::bearer_token;
+    use crate::error::AuthError;
+
+    #[test]
+    fn test_bearer_token() {
+        assert_eq!(bearer_token(Some("Bearer abc.def")), Ok("abc.def"));
+        assert_eq!(bearer_token(Some("bearer  abc.def ")), Ok("abc.def"));
+        assert_eq!(bearer_token(Some("Basic dXNlcjpwYXNz")), Err(AuthError::MissingToken));
+        assert_eq!(bearer_token(Some("Bearer ")), Err(AuthError::MissingToken));
+        assert_eq!(bearer_token(None), Err(AuthError::MissingToken));
+    }
+}
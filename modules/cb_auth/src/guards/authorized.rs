@@ -0,0 +1,78 @@
+use std::marker::PhantomData;
+
+use rocket::http::Status;
+use rocket::outcome::try_outcome;
+use rocket::request::{FromRequest, Outcome, Request};
+
+use crate::error::AuthError;
+use crate::guards::auth_user::AuthUser;
+use crate::services::rbac_service::{permission_name, EffectivePermissions, RbacService};
+
+/// A `resource:action` pair a route can demand, declared with [`permission!`](crate::permission).
+pub trait RequiredPermission {
+    const RESOURCE: &'static str;
+    const ACTION: &'static str;
+}
+
+/// Declares a marker type for [`Authorized`]:
+///
+/// ```ignore
+/// permission!(pub ProductUpdate, "product", "update");
+///
+/// #[patch("/product/<id>", data = "<product>")]
+/// pub fn update_product_by_id(_auth: Authorized<ProductUpdate>, ...) { ... }
+/// ```
+#[macro_export]
+macro_rules! permission {
+    ($vis:vis $name:ident, $resource:literal, $action:literal) => {
+        $vis struct $name;
+
+        impl $crate::guards::authorized::RequiredPermission for $name {
+            const RESOURCE: &'static str = $resource;
+            const ACTION: &'static str = $action;
+        }
+    };
+}
+
+/// An [`AuthUser`] whose role grants `P`. Answers `401` without a valid token and `403`
+/// without the permission, the handler does not run in either case.
+#[derive(Debug, Clone)]
+pub struct Authorized<P> {
+    pub auth: AuthUser,
+    permission: PhantomData<fn() -> P>,
+}
+
+/// The reason a guard answered `403`, read by the `403` catcher.
+#[derive(Debug, Clone)]
+pub struct Denied(pub AuthError);
+
+#[rocket::async_trait]
+impl<'r, P: RequiredPermission> FromRequest<'r> for Authorized<P> {
+    type Error = AuthError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let auth = try_outcome!(request.guard::<AuthUser>().await);
+        // Loaded once per request, however many permissions its guards ask for.
+        let permissions: &Result<EffectivePermissions, AuthError> =
+            request.local_cache(|| RbacService::permissions_of(&auth));
+        match permissions {
+            Ok(permissions) if permissions.allows(P::RESOURCE, P::ACTION) => {
+                Outcome::Success(Authorized {
+                    auth,
+                    permission: PhantomData,
+                })
+            }
+            Ok(_) => {
+                let e = AuthError::Forbidden(permission_name(P::RESOURCE, P::ACTION));
+                request.local_cache(|| Denied(e.clone()));
+                Outcome::Error((e.status(), e))
+            }
+            Err(e) => {
+                if e.status() == Status::Forbidden {
+                    request.local_cache(|| Denied(e.clone()));
+                }
+                Outcome::Error((e.status(), e.clone()))
+            }
+        }
+    }
+}
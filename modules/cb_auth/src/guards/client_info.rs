@@ -0,0 +1,16 @@
+use rocket::request::{FromRequest, Outcome, Request};
+
+use crate::models::session::ClientInfo;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for ClientInfo {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        Outcome::Success(ClientInfo {
+            user_agent: request.headers().get_one("User-Agent").map(String::from),
+            // Honours `X-Real-IP` when Rocket's `ip_header` is configured for a proxy.
+            ip_address: request.client_ip().map(|ip| ip.to_string()),
+        })
+    }
+}
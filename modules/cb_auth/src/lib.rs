@@ -0,0 +1,57 @@
+pub mod error;
+
+pub mod models {
+    pub mod account;
+    pub mod api_key;
+    pub mod claims;
+    pub mod impersonation;
+    pub mod lockout;
+    pub mod login;
+    pub mod oidc;
+    pub mod session;
+    pub mod two_factor;
+}
+
+pub mod mappers {
+    pub mod api_key_mapper;
+    pub mod impersonation_mapper;
+    pub mod lockout_mapper;
+    pub mod oidc_mapper;
+    pub mod one_time_token_mapper;
+    pub mod rbac_mapper;
+    pub mod session_mapper;
+    pub mod two_factor_mapper;
+}
+
+pub mod fairings {
+    pub mod actor_tracking;
+}
+
+pub mod guards {
+    pub mod auth_user;
+    pub mod authorized;
+    pub mod client_info;
+}
+
+pub mod controllers {
+    pub mod auth_controller;
+}
+
+pub mod routes {
+    pub mod auth_route;
+    pub mod user_route;
+}
+
+pub mod services {
+    pub mod account_service;
+    pub mod api_key_service;
+    pub mod auth_service;
+    pub mod impersonation_service;
+    pub mod lockout_service;
+    pub mod oidc_service;
+    pub mod ownership_service;
+    pub mod rbac_service;
+    pub mod session_service;
+    pub mod token_service;
+    pub mod two_factor_service;
+}
@@ -0,0 +1,117 @@
+use chrono::NaiveDateTime;
+use crab_rocket_schema::schema::api_key_scope_table;
+use crab_rocket_schema::schema::api_key_table::dsl;
+use crab_rocket_schema::schema::permission_table;
+use diesel::prelude::*;
+use diesel::result::Error;
+use uuid::Uuid;
+
+use crate::models::api_key::ApiKey;
+
+pub struct ApiKeyMapper {}
+
+impl ApiKeyMapper {
+    /// Inserts the key together with its scopes.
+    pub fn add_single(
+        conn: &mut PgConnection,
+        obj: &ApiKey,
+        permission_ids: &[i32],
+    ) -> Result<ApiKey, Error> {
+        conn.transaction(|conn| {
+            let api_key = diesel::insert_into(dsl::api_key_table)
+                .values(obj)
+                .returning(ApiKey::as_returning())
+                .get_result(conn)?;
+            let scopes: Vec<_> = permission_ids
+                .iter()
+                .map(|permission_id| {
+                    (
+                        api_key_scope_table::api_key_id.eq(api_key.api_key_id()),
+                        api_key_scope_table::permission_id.eq(permission_id),
+                    )
+                })
+                .collect();
+            diesel::insert_into(api_key_scope_table::table).values(&scopes).execute(conn)?;
+            Ok(api_key)
+        })
+    }
+
+    pub fn get_by_hash(conn: &mut PgConnection, key_hash: &str) -> Result<ApiKey, Error> {
+        dsl::api_key_table.filter(dsl::key_hash.eq(key_hash)).first(conn)
+    }
+
+    /// Keys of `user_id` that are not revoked, newest first. Expired ones are included.
+    pub fn get_by_user(conn: &mut PgConnection, user_id: i32) -> Result<Vec<ApiKey>, Error> {
+        dsl::api_key_table
+            .filter(dsl::user_id.eq(user_id))
+            .filter(dsl::revoked_at.is_null())
+            .order(dsl::created_at.desc())
+            .load(conn)
+    }
+
+    /// The active permission a scope names.
+    pub fn get_permission_id(
+        conn: &mut PgConnection,
+        resource: &str,
+        action: &str,
+    ) -> Result<i32, Error> {
+        permission_table::table
+            .filter(permission_table::resource.eq(resource))
+            .filter(permission_table::action.eq(action))
+            .filter(permission_table::is_active.is_distinct_from(false))
+            .select(permission_table::permission_id)
+            .first(conn)
+    }
+
+    /// `(api_key_id, resource, action)` of the active permissions in the scopes of the keys.
+    pub fn get_scopes(
+        conn: &mut PgConnection,
+        api_key_ids: &[Uuid],
+    ) -> Result<Vec<(Uuid, String, String)>, Error> {
+        api_key_scope_table::table
+            .inner_join(permission_table::table)
+            .filter(api_key_scope_table::api_key_id.eq_any(api_key_ids))
+            .filter(permission_table::is_active.is_distinct_from(false))
+            .order((permission_table::resource, permission_table::action))
+            .select((
+                api_key_scope_table::api_key_id,
+                permission_table::resource,
+                permission_table::action,
+            ))
+            .load(conn)
+    }
+
+    /// Records a use, at most once a minute so busy keys do not write on every request.
+    pub fn touch(
+        conn: &mut PgConnection,
+        api_key_id: Uuid,
+        now: NaiveDateTime,
+    ) -> Result<usize, Error> {
+        diesel::update(
+            dsl::api_key_table.find(api_key_id).filter(
+                dsl::last_used_at
+                    .is_null()
+                    .or(dsl::last_used_at.lt(now - chrono::Duration::minutes(1))),
+            ),
+        )
+        .set(dsl::last_used_at.eq(now))
+        .execute(conn)
+    }
+
+    /// Revokes a key of `user_id`, returns `0` when there is no such open key.
+    pub fn revoke(
+        conn: &mut PgConnection,
+        user_id: i32,
+        api_key_id: Uuid,
+        now: NaiveDateTime,
+    ) -> Result<usize, Error> {
+        diesel::update(
+            dsl::api_key_table
+                .find(api_key_id)
+                .filter(dsl::user_id.eq(user_id))
+                .filter(dsl::revoked_at.is_null()),
+        )
+        .set(dsl::revoked_at.eq(now))
+        .execute(conn)
+    }
+}
@@ -0,0 +1,48 @@
+use chrono::NaiveDateTime;
+use crab_rocket_schema::schema::impersonation_table;
+use diesel::prelude::*;
+use diesel::result::Error;
+use uuid::Uuid;
+
+use crate::models::impersonation::Impersonation;
+
+pub struct ImpersonationMapper {}
+
+impl ImpersonationMapper {
+    pub fn add(conn: &mut PgConnection, obj: &Impersonation) -> Result<usize, Error> {
+        diesel::insert_into(impersonation_table::table).values(obj).execute(conn)
+    }
+
+    pub fn get(conn: &mut PgConnection, impersonation_id: Uuid) -> Result<Impersonation, Error> {
+        impersonation_table::table.find(impersonation_id).first(conn)
+    }
+
+    /// Returns whether it was still going on.
+    pub fn end(
+        conn: &mut PgConnection,
+        impersonation_id: Uuid,
+        now: NaiveDateTime,
+    ) -> Result<bool, Error> {
+        diesel::update(
+            impersonation_table::table
+                .find(impersonation_id)
+                .filter(impersonation_table::ended_at.is_null()),
+        )
+        .set(impersonation_table::ended_at.eq(now))
+        .execute(conn)
+        .map(|count| count > 0)
+    }
+
+    /// The newest `limit` impersonations, of `target_user_id` when given.
+    pub fn get_recent(
+        conn: &mut PgConnection,
+        target_user_id: Option<i32>,
+        limit: i64,
+    ) -> Result<Vec<Impersonation>, Error> {
+        let mut query = impersonation_table::table.into_boxed();
+        if let Some(target_user_id) = target_user_id {
+            query = query.filter(impersonation_table::target_user_id.eq(target_user_id));
+        }
+        query.order(impersonation_table::started_at.desc()).limit(limit).load(conn)
+    }
+}
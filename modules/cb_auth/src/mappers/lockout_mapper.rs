@@ -0,0 +1,62 @@
+use crab_rocket_schema::schema::login_throttle_table::dsl;
+use crab_rocket_schema::schema::security_event_table;
+use diesel::prelude::*;
+use diesel::result::Error;
+
+use crate::models::lockout::{LoginThrottle, NewSecurityEvent, SecurityEvent};
+
+pub struct LockoutMapper {}
+
+impl LockoutMapper {
+    pub fn get(
+        conn: &mut PgConnection,
+        scope: &str,
+        subject: &str,
+    ) -> Result<LoginThrottle, Error> {
+        dsl::login_throttle_table.find((scope, subject)).first(conn)
+    }
+
+    /// Like [`get`](Self::get), locking the row until the transaction ends.
+    pub fn get_for_update(
+        conn: &mut PgConnection,
+        scope: &str,
+        subject: &str,
+    ) -> Result<LoginThrottle, Error> {
+        dsl::login_throttle_table.find((scope, subject)).for_update().first(conn)
+    }
+
+    pub fn save(conn: &mut PgConnection, obj: &LoginThrottle) -> Result<usize, Error> {
+        diesel::insert_into(dsl::login_throttle_table)
+            .values(obj)
+            .on_conflict((dsl::scope, dsl::subject))
+            .do_update()
+            .set((
+                dsl::failed_count.eq(obj.failed_count()),
+                dsl::last_failed_at.eq(obj.last_failed_at()),
+                dsl::locked_until.eq(obj.locked_until()),
+            ))
+            .execute(conn)
+    }
+
+    /// Forgets the failures, returns `0` when there were none.
+    pub fn delete(conn: &mut PgConnection, scope: &str, subject: &str) -> Result<usize, Error> {
+        diesel::delete(dsl::login_throttle_table.find((scope, subject))).execute(conn)
+    }
+
+    pub fn add_event(conn: &mut PgConnection, obj: &NewSecurityEvent) -> Result<usize, Error> {
+        diesel::insert_into(security_event_table::table).values(obj).execute(conn)
+    }
+
+    /// The newest `limit` events of `user_id`.
+    pub fn get_events_of_user(
+        conn: &mut PgConnection,
+        user_id: i32,
+        limit: i64,
+    ) -> Result<Vec<SecurityEvent>, Error> {
+        security_event_table::table
+            .filter(security_event_table::user_id.eq(user_id))
+            .order(security_event_table::event_id.desc())
+            .limit(limit)
+            .load(conn)
+    }
+}
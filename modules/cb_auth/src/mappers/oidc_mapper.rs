@@ -0,0 +1,71 @@
+use chrono::NaiveDateTime;
+use crab_rocket_schema::schema::{oidc_identity_table, oidc_login_table, user_table};
+use crab_rocket_user::models::user::User;
+use diesel::prelude::*;
+use diesel::result::Error;
+
+use crate::models::oidc::{OidcIdentity, OidcLogin};
+
+pub struct OidcMapper {}
+
+impl OidcMapper {
+    pub fn add_login(conn: &mut PgConnection, obj: &OidcLogin) -> Result<usize, Error> {
+        diesel::insert_into(oidc_login_table::table).values(obj).execute(conn)
+    }
+
+    /// Removes the pending login of `state_hash` and returns it, each one can be used once.
+    pub fn take_login(conn: &mut PgConnection, state_hash: &str) -> Result<OidcLogin, Error> {
+        diesel::delete(oidc_login_table::table.find(state_hash))
+            .returning(OidcLogin::as_returning())
+            .get_result(conn)
+    }
+
+    /// Forgets logins started before `before` whose users never came back.
+    pub fn delete_logins_before(
+        conn: &mut PgConnection,
+        before: NaiveDateTime,
+    ) -> Result<usize, Error> {
+        diesel::delete(oidc_login_table::table.filter(oidc_login_table::created_at.lt(before)))
+            .execute(conn)
+    }
+
+    pub fn get_identity(
+        conn: &mut PgConnection,
+        provider: &str,
+        subject: &str,
+    ) -> Result<OidcIdentity, Error> {
+        oidc_identity_table::table.find((provider, subject)).first(conn)
+    }
+
+    pub fn add_identity(conn: &mut PgConnection, obj: &OidcIdentity) -> Result<usize, Error> {
+        diesel::insert_into(oidc_identity_table::table).values(obj).execute(conn)
+    }
+
+    pub fn touch_identity(
+        conn: &mut PgConnection,
+        provider: &str,
+        subject: &str,
+        email: Option<&str>,
+        now: NaiveDateTime,
+    ) -> Result<usize, Error> {
+        diesel::update(oidc_identity_table::table.find((provider, subject)))
+            .set((oidc_identity_table::email.eq(email), oidc_identity_table::last_login_at.eq(now)))
+            .execute(conn)
+    }
+
+    pub fn get_identities_of_user(
+        conn: &mut PgConnection,
+        user_id: i32,
+    ) -> Result<Vec<OidcIdentity>, Error> {
+        oidc_identity_table::table
+            .filter(oidc_identity_table::user_id.eq(user_id))
+            .order((oidc_identity_table::provider, oidc_identity_table::created_at))
+            .load(conn)
+    }
+
+    /// Users with exactly this email, at most two are loaded to tell a match from an
+    /// ambiguous one.
+    pub fn get_users_by_email(conn: &mut PgConnection, email: &str) -> Result<Vec<User>, Error> {
+        user_table::table.filter(user_table::email.eq(email)).limit(2).load(conn)
+    }
+}
@@ -0,0 +1,97 @@
+use chrono::NaiveDateTime;
+use crab_rocket_schema::schema::email_verification_table;
+use crab_rocket_schema::schema::one_time_token_table::dsl;
+use diesel::prelude::*;
+use diesel::result::Error;
+use uuid::Uuid;
+
+pub struct OneTimeTokenMapper {}
+
+impl OneTimeTokenMapper {
+    pub fn add_single(
+        conn: &mut PgConnection,
+        token_id: Uuid,
+        user_id: i32,
+        purpose: &str,
+        now: NaiveDateTime,
+        expires_at: NaiveDateTime,
+    ) -> Result<usize, Error> {
+        diesel::insert_into(dsl::one_time_token_table)
+            .values((
+                dsl::token_id.eq(token_id),
+                dsl::user_id.eq(user_id),
+                dsl::purpose.eq(purpose),
+                dsl::created_at.eq(now),
+                dsl::expires_at.eq(expires_at),
+            ))
+            .execute(conn)
+    }
+
+    /// Marks a token used, returns `0` when it was used before or does not exist.
+    pub fn use_token(
+        conn: &mut PgConnection,
+        token_id: Uuid,
+        purpose: &str,
+        now: NaiveDateTime,
+    ) -> Result<usize, Error> {
+        diesel::update(
+            dsl::one_time_token_table
+                .find(token_id)
+                .filter(dsl::purpose.eq(purpose))
+                .filter(dsl::used_at.is_null()),
+        )
+        .set(dsl::used_at.eq(now))
+        .execute(conn)
+    }
+
+    /// Uses up every open token of `user_id` for `purpose`, older mails stop working once
+    /// one of them did its job.
+    pub fn use_all(
+        conn: &mut PgConnection,
+        user_id: i32,
+        purpose: &str,
+        now: NaiveDateTime,
+    ) -> Result<usize, Error> {
+        diesel::update(
+            dsl::one_time_token_table
+                .filter(dsl::user_id.eq(user_id))
+                .filter(dsl::purpose.eq(purpose))
+                .filter(dsl::used_at.is_null()),
+        )
+        .set(dsl::used_at.eq(now))
+        .execute(conn)
+    }
+
+    /// The address `user_id` verified last and when, whether or not it is still theirs.
+    pub fn get_verified_email(
+        conn: &mut PgConnection,
+        user_id: i32,
+    ) -> Result<Option<(String, NaiveDateTime)>, Error> {
+        email_verification_table::table
+            .find(user_id)
+            .select((email_verification_table::email, email_verification_table::verified_at))
+            .first(conn)
+            .optional()
+    }
+
+    pub fn set_verified_email(
+        conn: &mut PgConnection,
+        user_id: i32,
+        email: &str,
+        now: NaiveDateTime,
+    ) -> Result<usize, Error> {
+        diesel::insert_into(email_verification_table::table)
+            .values((
+                email_verification_table::user_id.eq(user_id),
+                email_verification_table::email.eq(email),
+                email_verification_table::verified_at.eq(now),
+            ))
+            .on_conflict(email_verification_table::user_id)
+            .do_update()
+            .set((
+                email_verification_table::email.eq(email),
+                email_verification_table::verified_at.eq(now),
+            ))
+            .execute(conn)
+    }
+}
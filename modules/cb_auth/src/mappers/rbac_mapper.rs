@@ -0,0 +1,43 @@
+use crab_rocket_schema::schema::{permission_table, role_permission_table, role_table, user_table};
+use diesel::prelude::*;
+use diesel::result::Error;
+
+pub struct RbacMapper {}
+
+impl RbacMapper {
+    /// `(resource, action)` of every active permission the role of `user_id` grants.
+    pub fn get_permissions_of_user(
+        conn: &mut PgConnection,
+        user_id: i32,
+    ) -> Result<Vec<(String, String)>, Error> {
+        role_permission_table::table
+            .inner_join(permission_table::table)
+            .inner_join(
+                user_table::table
+                    .on(user_table::role_id.eq(role_permission_table::role_id.nullable())),
+            )
+            .filter(user_table::user_id.eq(user_id))
+            // `is_active` is nullable, only an explicit `false` switches a permission off.
+            .filter(permission_table::is_active.is_distinct_from(false))
+            .select((permission_table::resource, permission_table::action))
+            .load(conn)
+    }
+
+    pub fn get_role_id_by_name(conn: &mut PgConnection, role_name: &str) -> Result<i32, Error> {
+        role_table::table
+            .filter(role_table::role_name.eq(role_name))
+            .select(role_table::role_id)
+            .first(conn)
+    }
+
+    /// Whether the role of `user_id` asks for a second factor, `false` without a role.
+    pub fn requires_two_factor(conn: &mut PgConnection, user_id: i32) -> Result<bool, Error> {
+        Ok(user_table::table
+            .inner_join(role_table::table)
+            .filter(user_table::user_id.eq(user_id))
+            .select(role_table::require_two_factor)
+            .first(conn)
+            .optional()?
+            .unwrap_or(false))
+    }
+}
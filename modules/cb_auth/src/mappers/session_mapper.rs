@@ -0,0 +1,127 @@
+use chrono::NaiveDateTime;
+use crab_rocket_schema::schema::refresh_token_table;
+use crab_rocket_schema::schema::session_table::dsl;
+use diesel::prelude::*;
+use diesel::result::Error;
+use uuid::Uuid;
+
+use crate::models::session::Session;
+
+pub struct SessionMapper {}
+
+impl SessionMapper {
+    pub fn add_single(conn: &mut PgConnection, obj: &Session) -> Result<Session, Error> {
+        diesel::insert_into(dsl::session_table)
+            .values(obj)
+            .returning(Session::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn get_by_id(conn: &mut PgConnection, session_id: Uuid) -> Result<Session, Error> {
+        dsl::session_table.find(session_id).first(conn)
+    }
+
+    /// Sessions of `user_id` that are neither revoked nor expired, most recently used first.
+    pub fn get_active_by_user(
+        conn: &mut PgConnection,
+        user_id: i32,
+        now: NaiveDateTime,
+    ) -> Result<Vec<Session>, Error> {
+        dsl::session_table
+            .filter(dsl::user_id.eq(user_id))
+            .filter(dsl::revoked_at.is_null())
+            .filter(dsl::expires_at.gt(now))
+            .order(dsl::last_used_at.desc())
+            .load(conn)
+    }
+
+    pub fn touch(
+        conn: &mut PgConnection,
+        session_id: Uuid,
+        now: NaiveDateTime,
+    ) -> Result<usize, Error> {
+        diesel::update(dsl::session_table.find(session_id))
+            .set(dsl::last_used_at.eq(now))
+            .execute(conn)
+    }
+
+    /// Records that the user of the session proved a second factor.
+    pub fn set_two_factor(conn: &mut PgConnection, session_id: Uuid) -> Result<usize, Error> {
+        diesel::update(dsl::session_table.find(session_id))
+            .set(dsl::two_factor.eq(true))
+            .execute(conn)
+    }
+
+    /// Revokes one session, returns `0` when it was revoked already.
+    pub fn revoke(
+        conn: &mut PgConnection,
+        session_id: Uuid,
+        reason: &str,
+        now: NaiveDateTime,
+    ) -> Result<usize, Error> {
+        diesel::update(dsl::session_table.find(session_id).filter(dsl::revoked_at.is_null()))
+            .set((dsl::revoked_at.eq(now), dsl::revoked_reason.eq(reason)))
+            .execute(conn)
+    }
+
+    /// Revokes every open session of `user_id` except `keep`, returns how many.
+    pub fn revoke_all(
+        conn: &mut PgConnection,
+        user_id: i32,
+        keep: Option<Uuid>,
+        reason: &str,
+        now: NaiveDateTime,
+    ) -> Result<usize, Error> {
+        let mut query = diesel::update(dsl::session_table)
+            .filter(dsl::user_id.eq(user_id))
+            .filter(dsl::revoked_at.is_null())
+            .into_boxed();
+        if let Some(keep) = keep {
+            query = query.filter(dsl::session_id.ne(keep));
+        }
+        query.set((dsl::revoked_at.eq(now), dsl::revoked_reason.eq(reason))).execute(conn)
+    }
+
+    pub fn add_refresh_token(
+        conn: &mut PgConnection,
+        session_id: Uuid,
+        token_hash: &str,
+        now: NaiveDateTime,
+    ) -> Result<usize, Error> {
+        diesel::insert_into(refresh_token_table::table)
+            .values((
+                refresh_token_table::token_hash.eq(token_hash),
+                refresh_token_table::session_id.eq(session_id),
+                refresh_token_table::created_at.eq(now),
+            ))
+            .execute(conn)
+    }
+
+    /// The session a refresh token belongs to and whether the token was used before.
+    pub fn get_by_refresh_token(
+        conn: &mut PgConnection,
+        token_hash: &str,
+    ) -> Result<(Session, Option<NaiveDateTime>), Error> {
+        refresh_token_table::table
+            .inner_join(dsl::session_table)
+            .filter(refresh_token_table::token_hash.eq(token_hash))
+            .select((Session::as_select(), refresh_token_table::used_at))
+            .first(conn)
+    }
+
+    /// Marks a refresh token as used. Returns `0` when somebody else used it first, which
+    /// is treated like reuse.
+    pub fn use_refresh_token(
+        conn: &mut PgConnection,
+        token_hash: &str,
+        now: NaiveDateTime,
+    ) -> Result<usize, Error> {
+        diesel::update(
+            refresh_token_table::table
+                .find(token_hash)
+                .filter(refresh_token_table::used_at.is_null()),
+        )
+        .set(refresh_token_table::used_at.eq(now))
+        .execute(conn)
+    }
+}
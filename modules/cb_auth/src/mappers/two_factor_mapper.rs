@@ -0,0 +1,105 @@
+use chrono::NaiveDateTime;
+use crab_rocket_schema::schema::recovery_code_table;
+use crab_rocket_schema::schema::two_factor_table::dsl;
+use diesel::prelude::*;
+use diesel::result::Error;
+
+use crate::models::two_factor::TwoFactor;
+
+pub struct TwoFactorMapper {}
+
+impl TwoFactorMapper {
+    pub fn get(conn: &mut PgConnection, user_id: i32) -> Result<TwoFactor, Error> {
+        dsl::two_factor_table.find(user_id).first(conn)
+    }
+
+    /// Stores a pending enrollment, replacing an earlier one that was never confirmed.
+    /// Returns `0` when an enabled secret is in the way.
+    pub fn save_pending(conn: &mut PgConnection, obj: &TwoFactor) -> Result<usize, Error> {
+        diesel::delete(dsl::two_factor_table.find(obj.user_id()).filter(dsl::enabled_at.is_null()))
+            .execute(conn)?;
+        diesel::insert_into(dsl::two_factor_table)
+            .values(obj)
+            .on_conflict_do_nothing()
+            .execute(conn)
+    }
+
+    pub fn enable(
+        conn: &mut PgConnection,
+        user_id: i32,
+        now: NaiveDateTime,
+    ) -> Result<usize, Error> {
+        diesel::update(dsl::two_factor_table.find(user_id).filter(dsl::enabled_at.is_null()))
+            .set(dsl::enabled_at.eq(now))
+            .execute(conn)
+    }
+
+    /// Accepts the code of `step` once. Returns `0` when that step or a later one was used
+    /// already, e.g. by a concurrent login with the same code.
+    pub fn use_step(conn: &mut PgConnection, user_id: i32, step: i64) -> Result<usize, Error> {
+        diesel::update(
+            dsl::two_factor_table
+                .find(user_id)
+                .filter(dsl::last_used_step.is_null().or(dsl::last_used_step.lt(step))),
+        )
+        .set(dsl::last_used_step.eq(step))
+        .execute(conn)
+    }
+
+    /// Removes the secret and every recovery code of `user_id`.
+    pub fn delete(conn: &mut PgConnection, user_id: i32) -> Result<usize, Error> {
+        diesel::delete(recovery_code_table::table.filter(recovery_code_table::user_id.eq(user_id)))
+            .execute(conn)?;
+        diesel::delete(dsl::two_factor_table.find(user_id)).execute(conn)
+    }
+
+    /// Replaces the recovery codes of `user_id` with `code_hashes`.
+    pub fn replace_recovery_codes(
+        conn: &mut PgConnection,
+        user_id: i32,
+        code_hashes: &[String],
+        now: NaiveDateTime,
+    ) -> Result<usize, Error> {
+        diesel::delete(recovery_code_table::table.filter(recovery_code_table::user_id.eq(user_id)))
+            .execute(conn)?;
+        let rows: Vec<_> = code_hashes
+            .iter()
+            .map(|code_hash| {
+                (
+                    recovery_code_table::code_hash.eq(code_hash),
+                    recovery_code_table::user_id.eq(user_id),
+                    recovery_code_table::created_at.eq(now),
+                )
+            })
+            .collect();
+        diesel::insert_into(recovery_code_table::table).values(&rows).execute(conn)
+    }
+
+    /// Marks an unused recovery code of `user_id` as used, returns `0` when there is none.
+    pub fn use_recovery_code(
+        conn: &mut PgConnection,
+        user_id: i32,
+        code_hash: &str,
+        now: NaiveDateTime,
+    ) -> Result<usize, Error> {
+        diesel::update(
+            recovery_code_table::table
+                .find(code_hash)
+                .filter(recovery_code_table::user_id.eq(user_id))
+                .filter(recovery_code_table::used_at.is_null()),
+        )
+        .set(recovery_code_table::used_at.eq(now))
+        .execute(conn)
+    }
+
+    pub fn count_unused_recovery_codes(
+        conn: &mut PgConnection,
+        user_id: i32,
+    ) -> Result<i64, Error> {
+        recovery_code_table::table
+            .filter(recovery_code_table::user_id.eq(user_id))
+            .filter(recovery_code_table::used_at.is_null())
+            .count()
+            .get_result(conn)
+    }
+}
@@ -0,0 +1,33 @@
+use chrono::NaiveDateTime;
+use rocket::serde::{Deserialize, Serialize};
+
+/// Body of `POST /auth/verify-email/confirm`.
+#[derive(Deserialize, Debug)]
+#[serde(crate = "rocket::serde")]
+pub struct ConfirmEmail {
+    pub token: String,
+}
+
+/// Body of `POST /auth/password-reset/request`, the same `login` as for `POST /auth/login`.
+#[derive(Deserialize, Debug)]
+#[serde(crate = "rocket::serde")]
+pub struct PasswordResetRequest {
+    pub login: String,
+}
+
+/// Body of `POST /auth/password-reset/confirm`.
+#[derive(Deserialize, Debug)]
+#[serde(crate = "rocket::serde")]
+pub struct PasswordResetConfirm {
+    pub token: String,
+    pub new_password: String,
+}
+
+/// Answer of `GET /auth/verify-email`.
+#[derive(Serialize, Debug, PartialEq, Eq)]
+#[serde(crate = "rocket::serde")]
+pub struct EmailStatus {
+    pub email: Option<String>,
+    /// `None` until the current address was confirmed.
+    pub verified_at: Option<NaiveDateTime>,
+}
@@ -0,0 +1,114 @@
+use chrono::NaiveDateTime;
+use diesel::{Insertable, Queryable, Selectable};
+use rocket::serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A long-lived credential of a user, scoped by `api_key_scope_table`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "rocket::serde")]
+#[derive(Queryable, Selectable, Insertable)]
+#[diesel(table_name = crab_rocket_schema::schema::api_key_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ApiKey {
+    api_key_id: Uuid,
+    user_id: i32,
+    name: String,
+    /// The start of the key, enough to tell keys apart in a listing.
+    key_prefix: String,
+    #[serde(skip_serializing, default)]
+    key_hash: String,
+    created_at: NaiveDateTime,
+    expires_at: Option<NaiveDateTime>,
+    last_used_at: Option<NaiveDateTime>,
+    revoked_at: Option<NaiveDateTime>,
+}
+
+impl ApiKey {
+    pub fn new(
+        user_id: i32,
+        name: String,
+        key_prefix: String,
+        key_hash: String,
+        now: NaiveDateTime,
+        expires_at: Option<NaiveDateTime>,
+    ) -> Self {
+        Self {
+            api_key_id: Uuid::new_v4(),
+            user_id,
+            name,
+            key_prefix,
+            key_hash,
+            created_at: now,
+            expires_at,
+            last_used_at: None,
+            revoked_at: None,
+        }
+    }
+
+    pub fn api_key_id(&self) -> Uuid {
+        self.api_key_id
+    }
+
+    pub fn user_id(&self) -> i32 {
+        self.user_id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn key_prefix(&self) -> &str {
+        &self.key_prefix
+    }
+
+    pub fn created_at(&self) -> NaiveDateTime {
+        self.created_at
+    }
+
+    pub fn expires_at(&self) -> Option<NaiveDateTime> {
+        self.expires_at
+    }
+
+    pub fn last_used_at(&self) -> Option<NaiveDateTime> {
+        self.last_used_at
+    }
+
+    pub fn revoked_at(&self) -> Option<NaiveDateTime> {
+        self.revoked_at
+    }
+
+    pub fn is_active(&self, now: NaiveDateTime) -> bool {
+        self.revoked_at.is_none() && self.expires_at.is_none_or(|expires_at| expires_at > now)
+    }
+}
+
+/// Body of `POST /auth/api-keys`.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(crate = "rocket::serde")]
+pub struct NewApiKey {
+    /// What the key is for, e.g. `"Scanner 4, aisle B"`.
+    pub name: String,
+    /// `resource:action` names from `permission_table`, the owner must hold each of them.
+    pub scopes: Vec<String>,
+    /// Never expires when omitted.
+    #[serde(default)]
+    pub expires_in_days: Option<u32>,
+}
+
+/// An entry of `GET /auth/api-keys`.
+#[derive(Serialize, Debug)]
+#[serde(crate = "rocket::serde")]
+pub struct ApiKeyInfo {
+    #[serde(flatten)]
+    pub api_key: ApiKey,
+    pub scopes: Vec<String>,
+}
+
+/// Answer of `POST /auth/api-keys`, the only time `key` is shown.
+#[derive(Serialize, Debug)]
+#[serde(crate = "rocket::serde")]
+pub struct CreatedApiKey {
+    #[serde(flatten)]
+    pub info: ApiKeyInfo,
+    pub key: String,
+}
@@ -0,0 +1,80 @@
+use rocket::serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Payload of an access token.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(crate = "rocket::serde")]
+pub struct AccessClaims {
+    /// `user_id` of the caller, a string as the JWT spec asks for.
+    pub sub: String,
+    pub iss: String,
+    pub iat: i64,
+    pub exp: i64,
+    /// The login session the token was issued for, revoking it invalidates the token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<Uuid>,
+    /// Set on impersonation tokens, `sub` is then the impersonated user.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub act: Option<ActorClaim>,
+}
+
+impl AccessClaims {
+    pub fn user_id(&self) -> Option<i32> {
+        self.sub.parse().ok()
+    }
+}
+
+/// The `act` claim of RFC 8693, who is really behind a token issued for someone else.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(crate = "rocket::serde")]
+pub struct ActorClaim {
+    /// `user_id` of the actor.
+    pub sub: String,
+    /// Row in `impersonation_table`, ending it invalidates the token.
+    pub iid: Uuid,
+}
+
+impl ActorClaim {
+    pub fn user_id(&self) -> Option<i32> {
+        self.sub.parse().ok()
+    }
+}
+
+/// What a mailed one-time token may be used for, its `aud` claim.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenPurpose {
+    VerifyEmail,
+    ResetPassword,
+}
+
+impl TokenPurpose {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TokenPurpose::VerifyEmail => "verify_email",
+            TokenPurpose::ResetPassword => "reset_password",
+        }
+    }
+}
+
+/// Payload of a one-time token from a verification or password reset mail.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(crate = "rocket::serde")]
+pub struct OneTimeClaims {
+    pub sub: String,
+    /// The [`TokenPurpose`], an access token check rejects tokens that carry one.
+    pub aud: String,
+    pub iss: String,
+    pub iat: i64,
+    pub exp: i64,
+    /// Row in `one_time_token_table` that records the use.
+    pub jti: Uuid,
+    /// The address a verification token was sent to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+}
+
+impl OneTimeClaims {
+    pub fn user_id(&self) -> Option<i32> {
+        self.sub.parse().ok()
+    }
+}
@@ -0,0 +1,106 @@
+use chrono::NaiveDateTime;
+use crab_rocket_user::models::user::User;
+use diesel::{Insertable, Queryable, Selectable};
+use rocket::serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A user acting as another one, see `POST /auth/impersonate/<user_id>`. Both ids are cleared
+/// when the user is deleted, the record stays.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(crate = "rocket::serde")]
+#[derive(Queryable, Selectable, Insertable)]
+#[diesel(table_name = crab_rocket_schema::schema::impersonation_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Impersonation {
+    impersonation_id: Uuid,
+    /// Who really acts.
+    actor_user_id: Option<i32>,
+    /// Who requests are made as.
+    target_user_id: Option<i32>,
+    reason: String,
+    ip_address: Option<String>,
+    started_at: NaiveDateTime,
+    expires_at: NaiveDateTime,
+    /// Set by `POST /auth/logout` with the impersonation token.
+    ended_at: Option<NaiveDateTime>,
+}
+
+impl Impersonation {
+    pub fn new(
+        actor_user_id: i32,
+        target_user_id: i32,
+        reason: String,
+        ip_address: Option<String>,
+        now: NaiveDateTime,
+        expires_at: NaiveDateTime,
+    ) -> Self {
+        Self {
+            impersonation_id: Uuid::new_v4(),
+            actor_user_id: Some(actor_user_id),
+            target_user_id: Some(target_user_id),
+            reason,
+            ip_address,
+            started_at: now,
+            expires_at,
+            ended_at: None,
+        }
+    }
+
+    pub fn impersonation_id(&self) -> Uuid {
+        self.impersonation_id
+    }
+
+    pub fn actor_user_id(&self) -> Option<i32> {
+        self.actor_user_id
+    }
+
+    pub fn target_user_id(&self) -> Option<i32> {
+        self.target_user_id
+    }
+
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+
+    pub fn ip_address(&self) -> &Option<String> {
+        &self.ip_address
+    }
+
+    pub fn started_at(&self) -> NaiveDateTime {
+        self.started_at
+    }
+
+    pub fn expires_at(&self) -> NaiveDateTime {
+        self.expires_at
+    }
+
+    pub fn ended_at(&self) -> Option<NaiveDateTime> {
+        self.ended_at
+    }
+
+    pub fn is_active(&self, now: NaiveDateTime) -> bool {
+        self.ended_at.is_none() && self.expires_at > now
+    }
+}
+
+/// Body of `POST /auth/impersonate/<user_id>`.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(crate = "rocket::serde")]
+pub struct ImpersonationRequest {
+    /// Why, e.g. the ticket being worked on. Kept in the audit trail.
+    pub reason: String,
+}
+
+/// Answer of `POST /auth/impersonate/<user_id>`.
+#[derive(Serialize, Debug)]
+#[serde(crate = "rocket::serde")]
+pub struct ImpersonationToken {
+    pub access_token: String,
+    /// Always `Bearer`.
+    pub token_type: String,
+    /// Seconds until `access_token` expires, there is no refresh token.
+    pub expires_in: i64,
+    pub impersonation: Impersonation,
+    /// The user requests are made as.
+    pub user: User,
+}
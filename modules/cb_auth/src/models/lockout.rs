@@ -0,0 +1,234 @@
+use chrono::{Duration, NaiveDateTime};
+use crab_rocket_config::app_config::LockoutConfig;
+use diesel::{Insertable, Queryable, Selectable};
+use rocket::serde::Serialize;
+
+/// What failed logins are counted for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThrottleScope {
+    /// `subject` is the `user_id`.
+    Account,
+    /// `subject` is the client address.
+    Ip,
+}
+
+impl ThrottleScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ThrottleScope::Account => "account",
+            ThrottleScope::Ip => "ip",
+        }
+    }
+
+    pub fn max_failures(&self, config: &LockoutConfig) -> i32 {
+        match self {
+            ThrottleScope::Account => config.max_failures_per_account,
+            ThrottleScope::Ip => config.max_failures_per_ip,
+        }
+    }
+}
+
+/// Recent failed logins of one account or address.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(crate = "rocket::serde")]
+#[derive(Queryable, Selectable, Insertable)]
+#[diesel(table_name = crab_rocket_schema::schema::login_throttle_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct LoginThrottle {
+    scope: String,
+    subject: String,
+    failed_count: i32,
+    last_failed_at: NaiveDateTime,
+    locked_until: Option<NaiveDateTime>,
+}
+
+impl LoginThrottle {
+    /// The state after one more failure at `now`. Failures outside the window start over,
+    /// each one below the maximum waits twice as long as the one before, the maximum locks.
+    pub fn after_failure(
+        previous: Option<&LoginThrottle>,
+        scope: ThrottleScope,
+        subject: &str,
+        now: NaiveDateTime,
+        config: &LockoutConfig,
+    ) -> Self {
+        let window = Duration::seconds(config.failure_window_secs);
+        let failed_count = match previous {
+            Some(previous) if previous.last_failed_at + window > now => previous.failed_count + 1,
+            _ => 1,
+        };
+        let wait_secs = if failed_count >= scope.max_failures(config) {
+            config.lockout_secs
+        } else {
+            let doublings = (failed_count - 1).min(30) as u32;
+            config.base_delay_secs.saturating_mul(1 << doublings).min(config.lockout_secs)
+        };
+        Self {
+            scope: scope.as_str().to_string(),
+            subject: subject.to_string(),
+            failed_count,
+            last_failed_at: now,
+            locked_until: (wait_secs > 0).then(|| now + Duration::seconds(wait_secs)),
+        }
+    }
+
+    pub fn scope(&self) -> &str {
+        &self.scope
+    }
+
+    pub fn subject(&self) -> &str {
+        &self.subject
+    }
+
+    pub fn failed_count(&self) -> i32 {
+        self.failed_count
+    }
+
+    pub fn last_failed_at(&self) -> NaiveDateTime {
+        self.last_failed_at
+    }
+
+    pub fn locked_until(&self) -> Option<NaiveDateTime> {
+        self.locked_until
+    }
+
+    /// Seconds until the next attempt is allowed, `None` when it is allowed now.
+    pub fn retry_after_secs(&self, now: NaiveDateTime) -> Option<i64> {
+        self.locked_until
+            .filter(|locked_until| *locked_until > now)
+            .map(|locked_until| (locked_until - now).num_seconds().max(1))
+    }
+}
+
+/// Kinds of [`SecurityEvent`], its `event_type`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecurityEventType {
+    LoginSucceeded,
+    LoginFailed,
+    /// Refused without checking the password, the account or address was waiting.
+    LoginBlocked,
+    AccountLocked,
+    IpLocked,
+    LockoutCleared,
+    ImpersonationStarted,
+    ImpersonationEnded,
+    /// A request made with an impersonation token, `actor_user_id` made it.
+    ImpersonatedRequest,
+}
+
+impl SecurityEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SecurityEventType::LoginSucceeded => "login_succeeded",
+            SecurityEventType::LoginFailed => "login_failed",
+            SecurityEventType::LoginBlocked => "login_blocked",
+            SecurityEventType::AccountLocked => "account_locked",
+            SecurityEventType::IpLocked => "ip_locked",
+            SecurityEventType::LockoutCleared => "lockout_cleared",
+            SecurityEventType::ImpersonationStarted => "impersonation_started",
+            SecurityEventType::ImpersonationEnded => "impersonation_ended",
+            SecurityEventType::ImpersonatedRequest => "impersonated_request",
+        }
+    }
+}
+
+/// An entry of the security log in `security_event_table`.
+#[derive(Serialize, Debug, Clone, PartialEq, Queryable, Selectable)]
+#[serde(crate = "rocket::serde")]
+#[diesel(table_name = crab_rocket_schema::schema::security_event_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct SecurityEvent {
+    pub event_id: i64,
+    pub event_type: String,
+    pub user_id: Option<i32>,
+    /// What was typed into the login field, also for unknown accounts.
+    pub login: Option<String>,
+    pub ip_address: Option<String>,
+    pub detail: Option<String>,
+    pub created_at: NaiveDateTime,
+    /// Who really acted, when not the user, e.g. the support agent impersonating them.
+    pub actor_user_id: Option<i32>,
+}
+
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = crab_rocket_schema::schema::security_event_table)]
+pub struct NewSecurityEvent<'a> {
+    pub event_type: &'a str,
+    pub user_id: Option<i32>,
+    pub login: Option<&'a str>,
+    pub ip_address: Option<&'a str>,
+    pub detail: Option<&'a str>,
+    pub created_at: NaiveDateTime,
+    pub actor_user_id: Option<i32>,
+}
+
+/// Answer of `GET /auth/lockouts/<user_id>`.
+#[derive(Serialize, Debug)]
+#[serde(crate = "rocket::serde")]
+pub struct LockoutStatus {
+    pub user_id: i32,
+    /// Whether logins are refused right now.
+    pub locked: bool,
+    /// `None` without recent failures.
+    pub throttle: Option<LoginThrottle>,
+    /// Newest first.
+    pub recent_events: Vec<SecurityEvent>,
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::Duration;
+    use crab_rocket_config::app_config::LockoutConfig;
+    use crab_rocket_utils::time::get_e8_time;
+
+    use super::{LoginThrottle, ThrottleScope};
+
+    #[test]
+    fn test_delays_double_until_the_lockout() {
+        let config = LockoutConfig {
+            max_failures_per_account: 4,
+            base_delay_secs: 2,
+            lockout_secs: 60,
+            failure_window_secs: 600,
+            ..LockoutConfig::default()
+        };
+        let now = get_e8_time();
+        let mut throttle = None;
+        let mut waits = Vec::new();
+        for _ in 0..4 {
+            let next = LoginThrottle::after_failure(
+                throttle.as_ref(),
+                ThrottleScope::Account,
+                "7",
+                now,
+                &config,
+            );
+            waits.push(next.retry_after_secs(now));
+            throttle = Some(next);
+        }
+        assert_eq!(waits, [Some(2), Some(4), Some(8), Some(60)]);
+        let locked = throttle.unwrap();
+        assert_eq!(locked.failed_count(), 4);
+        assert_eq!(locked.retry_after_secs(now + Duration::seconds(60)), None);
+
+        // Outside the window the count starts over.
+        let later = now + Duration::seconds(601);
+        let fresh = LoginThrottle::after_failure(
+            Some(&locked),
+            ThrottleScope::Account,
+            "7",
+            later,
+            &config,
+        );
+        assert_eq!(fresh.failed_count(), 1);
+
+        let no_delay = LockoutConfig {
+            base_delay_secs: 0,
+            ..config
+        };
+        let first =
+            LoginThrottle::after_failure(None, ThrottleScope::Ip, "10.0.0.1", now, &no_delay);
+        assert_eq!(first.locked_until(), None);
+        assert_eq!(first.scope(), "ip");
+    }
+}
@@ -0,0 +1,53 @@
+use crab_rocket_user::models::user::User;
+use rocket::serde::{Deserialize, Serialize};
+
+/// Body of `POST /auth/login`. `login` is a username, email address or mobile phone number.
+#[derive(Deserialize, Debug)]
+#[serde(crate = "rocket::serde")]
+pub struct LoginRequest {
+    pub login: String,
+    pub password: String,
+    /// Shown in `GET /auth/sessions`, e.g. `"Warehouse tablet"`.
+    #[serde(default)]
+    pub device_name: Option<String>,
+    /// A TOTP or recovery code, required once two-factor authentication is enabled.
+    #[serde(default)]
+    pub code: Option<String>,
+}
+
+/// Body of `POST /auth/refresh`.
+#[derive(Deserialize, Debug)]
+#[serde(crate = "rocket::serde")]
+pub struct RefreshRequest {
+    pub refresh_token: String,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(crate = "rocket::serde")]
+pub struct TokenResponse {
+    pub access_token: String,
+    /// Always `Bearer`.
+    pub token_type: String,
+    /// Seconds until `access_token` expires.
+    pub expires_in: i64,
+    /// Single use, `POST /auth/refresh` returns a new one next to the new access token.
+    pub refresh_token: String,
+    pub user: User,
+}
+
+impl TokenResponse {
+    pub fn bearer(
+        access_token: String,
+        expires_in: i64,
+        refresh_token: String,
+        user: User,
+    ) -> Self {
+        Self {
+            access_token,
+            token_type: String::from("Bearer"),
+            expires_in,
+            refresh_token,
+            user,
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use chrono::NaiveDateTime;
+use diesel::{Insertable, Queryable, Selectable};
+use rocket::serde::{Deserialize, Serialize};
+
+/// A user's account at an OpenID Connect provider, logging in with it logs in as `user_id`.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(crate = "rocket::serde")]
+#[derive(Queryable, Selectable, Insertable)]
+#[diesel(table_name = crab_rocket_schema::schema::oidc_identity_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct OidcIdentity {
+    provider: String,
+    /// The `sub` claim, stable for the account at that provider.
+    subject: String,
+    user_id: i32,
+    /// As the provider reported it at the last login.
+    email: Option<String>,
+    created_at: NaiveDateTime,
+    last_login_at: NaiveDateTime,
+}
+
+impl OidcIdentity {
+    pub fn new(
+        provider: String,
+        subject: String,
+        user_id: i32,
+        email: Option<String>,
+        now: NaiveDateTime,
+    ) -> Self {
+        Self {
+            provider,
+            subject,
+            user_id,
+            email,
+            created_at: now,
+            last_login_at: now,
+        }
+    }
+
+    pub fn provider(&self) -> &str {
+        &self.provider
+    }
+
+    pub fn subject(&self) -> &str {
+        &self.subject
+    }
+
+    pub fn user_id(&self) -> i32 {
+        self.user_id
+    }
+
+    pub fn email(&self) -> &Option<String> {
+        &self.email
+    }
+
+    pub fn created_at(&self) -> NaiveDateTime {
+        self.created_at
+    }
+
+    pub fn last_login_at(&self) -> NaiveDateTime {
+        self.last_login_at
+    }
+}
+
+/// A login sent to a provider by `GET /auth/oidc/<provider>/authorize`, waiting for the
+/// callback that carries its `state`.
+#[derive(Debug, Clone, PartialEq, Queryable, Selectable, Insertable)]
+#[diesel(table_name = crab_rocket_schema::schema::oidc_login_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct OidcLogin {
+    state_hash: String,
+    provider: String,
+    code_verifier: String,
+    nonce: String,
+    created_at: NaiveDateTime,
+}
+
+impl OidcLogin {
+    pub fn new(
+        state_hash: String,
+        provider: String,
+        code_verifier: String,
+        nonce: String,
+        now: NaiveDateTime,
+    ) -> Self {
+        Self {
+            state_hash,
+            provider,
+            code_verifier,
+            nonce,
+            created_at: now,
+        }
+    }
+
+    pub fn provider(&self) -> &str {
+        &self.provider
+    }
+
+    pub fn code_verifier(&self) -> &str {
+        &self.code_verifier
+    }
+
+    pub fn nonce(&self) -> &str {
+        &self.nonce
+    }
+
+    pub fn created_at(&self) -> NaiveDateTime {
+        self.created_at
+    }
+}
+
+/// An entry of `GET /auth/oidc/providers`.
+#[derive(Serialize, Debug, PartialEq, Eq)]
+#[serde(crate = "rocket::serde")]
+pub struct OidcProviderInfo {
+    pub name: String,
+    pub display_name: String,
+}
+
+/// Answer of `GET /auth/oidc/<provider>/authorize`.
+#[derive(Serialize, Debug)]
+#[serde(crate = "rocket::serde")]
+pub struct OidcAuthorization {
+    /// Send the user's browser here, the provider redirects it to the callback afterwards.
+    pub authorization_url: String,
+}
+
+/// What the provider appends to the callback URL.
+#[derive(Debug, Default)]
+pub struct OidcCallback {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    /// Set instead of `code` when the user declined or the provider failed.
+    pub error: Option<String>,
+    pub error_description: Option<String>,
+}
+
+/// The claims of an ID token this backend uses.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(crate = "rocket::serde")]
+pub struct IdTokenClaims {
+    pub sub: String,
+    #[serde(default)]
+    pub nonce: Option<String>,
+    #[serde(default)]
+    pub email: Option<String>,
+    #[serde(default)]
+    pub email_verified: Option<bool>,
+    #[serde(default)]
+    pub preferred_username: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+    /// How the user authenticated at the provider, e.g. `["pwd", "mfa"]`.
+    #[serde(default)]
+    pub amr: Vec<String>,
+}
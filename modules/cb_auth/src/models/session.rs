@@ -0,0 +1,116 @@
+use chrono::NaiveDateTime;
+use diesel::{Insertable, Queryable, Selectable};
+use rocket::serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A login on one device, see `refresh_token_table` for its tokens.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(crate = "rocket::serde")]
+#[derive(Queryable, Selectable, Insertable)]
+#[diesel(table_name = crab_rocket_schema::schema::session_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Session {
+    session_id: Uuid,
+    user_id: i32,
+    device_name: Option<String>,
+    user_agent: Option<String>,
+    ip_address: Option<String>,
+    created_at: NaiveDateTime,
+    last_used_at: NaiveDateTime,
+    expires_at: NaiveDateTime,
+    revoked_at: Option<NaiveDateTime>,
+    revoked_reason: Option<String>,
+    /// The login was confirmed with a TOTP or recovery code.
+    two_factor: bool,
+}
+
+impl Session {
+    /// A fresh session for `user_id` that lasts until `expires_at`.
+    pub fn new(
+        user_id: i32,
+        client: &ClientInfo,
+        device_name: Option<String>,
+        now: NaiveDateTime,
+        expires_at: NaiveDateTime,
+        two_factor: bool,
+    ) -> Self {
+        Self {
+            session_id: Uuid::new_v4(),
+            user_id,
+            device_name,
+            user_agent: client.user_agent.clone(),
+            ip_address: client.ip_address.clone(),
+            created_at: now,
+            last_used_at: now,
+            expires_at,
+            revoked_at: None,
+            revoked_reason: None,
+            two_factor,
+        }
+    }
+
+    pub fn session_id(&self) -> Uuid {
+        self.session_id
+    }
+
+    pub fn user_id(&self) -> i32 {
+        self.user_id
+    }
+
+    pub fn device_name(&self) -> Option<&str> {
+        self.device_name.as_deref()
+    }
+
+    pub fn user_agent(&self) -> Option<&str> {
+        self.user_agent.as_deref()
+    }
+
+    pub fn ip_address(&self) -> Option<&str> {
+        self.ip_address.as_deref()
+    }
+
+    pub fn created_at(&self) -> NaiveDateTime {
+        self.created_at
+    }
+
+    pub fn last_used_at(&self) -> NaiveDateTime {
+        self.last_used_at
+    }
+
+    pub fn expires_at(&self) -> NaiveDateTime {
+        self.expires_at
+    }
+
+    pub fn revoked_at(&self) -> Option<NaiveDateTime> {
+        self.revoked_at
+    }
+
+    pub fn revoked_reason(&self) -> Option<&str> {
+        self.revoked_reason.as_deref()
+    }
+
+    pub fn two_factor(&self) -> bool {
+        self.two_factor
+    }
+
+    pub fn is_active(&self, now: NaiveDateTime) -> bool {
+        self.revoked_at.is_none() && self.expires_at > now
+    }
+}
+
+/// An entry of `GET /auth/sessions`.
+#[derive(Serialize, Debug)]
+#[serde(crate = "rocket::serde")]
+pub struct SessionInfo {
+    #[serde(flatten)]
+    pub session: Session,
+    /// Whether this is the session of the token that asked.
+    pub current: bool,
+}
+
+/// Who is calling from where, recorded on new sessions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClientInfo {
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+}
@@ -0,0 +1,90 @@
+use chrono::NaiveDateTime;
+use diesel::{Insertable, Queryable, Selectable};
+use rocket::serde::{Deserialize, Serialize};
+
+/// The TOTP secret of a user, pending until `enabled_at` is set.
+#[derive(Debug, Clone, PartialEq, Queryable, Selectable, Insertable)]
+#[diesel(table_name = crab_rocket_schema::schema::two_factor_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct TwoFactor {
+    user_id: i32,
+    /// Base32, the way authenticator apps take it.
+    secret: String,
+    created_at: NaiveDateTime,
+    enabled_at: Option<NaiveDateTime>,
+    /// The time step of the last accepted code, older and equal steps are refused.
+    last_used_step: Option<i64>,
+}
+
+impl TwoFactor {
+    pub fn new(user_id: i32, secret: String, now: NaiveDateTime) -> Self {
+        Self {
+            user_id,
+            secret,
+            created_at: now,
+            enabled_at: None,
+            last_used_step: None,
+        }
+    }
+
+    pub fn user_id(&self) -> i32 {
+        self.user_id
+    }
+
+    pub fn secret(&self) -> &str {
+        &self.secret
+    }
+
+    pub fn created_at(&self) -> NaiveDateTime {
+        self.created_at
+    }
+
+    pub fn enabled_at(&self) -> Option<NaiveDateTime> {
+        self.enabled_at
+    }
+
+    pub fn last_used_step(&self) -> Option<i64> {
+        self.last_used_step
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled_at.is_some()
+    }
+}
+
+/// Answer of `POST /auth/2fa/enroll`.
+#[derive(Serialize, Debug)]
+#[serde(crate = "rocket::serde")]
+pub struct TwoFactorEnrollment {
+    /// For typing into an authenticator app by hand.
+    pub secret: String,
+    /// `otpauth://totp/...`, the payload to render as a QR code.
+    pub otpauth_uri: String,
+}
+
+/// Body of `POST /auth/2fa/activate`, `/disable` and `/recovery-codes`.
+#[derive(Deserialize, Debug)]
+#[serde(crate = "rocket::serde")]
+pub struct TwoFactorCode {
+    /// Six digits from the authenticator app, or a recovery code.
+    pub code: String,
+}
+
+/// Shown once, only their hashes are stored.
+#[derive(Serialize, Debug)]
+#[serde(crate = "rocket::serde")]
+pub struct RecoveryCodes {
+    pub recovery_codes: Vec<String>,
+}
+
+/// Answer of `GET /auth/2fa`.
+#[derive(Serialize, Debug, PartialEq, Eq)]
+#[serde(crate = "rocket::serde")]
+pub struct TwoFactorStatus {
+    pub enabled_at: Option<NaiveDateTime>,
+    pub recovery_codes_left: i64,
+    /// The role of the user asks for a second factor.
+    pub required: bool,
+    /// The calling session was opened with a second factor.
+    pub session_verified: bool,
+}
@@ -0,0 +1,307 @@
+use rocket::{
+    catch, delete, get, http::Status, post, response::status, serde::json::Json, Request,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::controllers::auth_controller;
+use crate::error::AuthError;
+use crate::guards::auth_user::AuthUser;
+use crate::guards::authorized::{Authorized, Denied};
+use crate::models::account::{ConfirmEmail, PasswordResetConfirm, PasswordResetRequest};
+use crate::models::api_key::NewApiKey;
+use crate::models::impersonation::ImpersonationRequest;
+use crate::models::login::{LoginRequest, RefreshRequest};
+use crate::models::oidc::OidcCallback;
+use crate::models::session::ClientInfo;
+use crate::models::two_factor::TwoFactorCode;
+use crate::permission;
+
+permission!(pub LockoutManage, "lockout", "manage");
+permission!(pub UserImpersonate, "user", "impersonate");
+
+fn to_response(
+    status: i32,
+    message: String,
+    data: impl rocket::serde::Serialize,
+) -> status::Custom<Json<serde_json::Value>> {
+    let response = json!(
+        {
+            "status": status,
+            "message": message,
+            "body":{
+                "data":data
+            }
+        }
+    );
+    let code = Status::from_code(status as u16).unwrap_or(Status::InternalServerError);
+    status::Custom(code, Json(response))
+}
+
+/// Answers `401` for an unknown login and a wrong password alike. With two-factor
+/// authentication enabled, a right password without `code` answers `401` as well, asking
+/// for it. After a failure the account and the address wait before the next attempt, `429`
+/// until then, see `[lockout]` in `Rocket.toml`.
+#[post("/auth/login", data = "<body>")]
+pub fn login(
+    body: Json<LoginRequest>,
+    client: ClientInfo,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, token) = auth_controller::login(&body, &client);
+    to_response(status, message, token)
+}
+
+/// Single use: the answer carries the next refresh token, presenting an old one again
+/// revokes the session.
+#[post("/auth/refresh", data = "<body>")]
+pub fn refresh(body: Json<RefreshRequest>) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, token) = auth_controller::refresh(&body);
+    to_response(status, message, token)
+}
+
+/// Ends the session of the calling token.
+#[post("/auth/logout")]
+pub fn logout(auth: AuthUser) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, data) = auth_controller::logout(&auth);
+    to_response(status, message, data)
+}
+
+#[get("/auth/sessions")]
+pub fn get_sessions(auth: AuthUser) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, sessions) = auth_controller::get_sessions(&auth);
+    to_response(status, message, sessions)
+}
+
+#[delete("/auth/sessions/<session_id>")]
+pub fn revoke_session(auth: AuthUser, session_id: Uuid) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, data) = auth_controller::revoke_session(&auth, session_id);
+    to_response(status, message, data)
+}
+
+/// Logs the user out everywhere, `?keep_current=true` keeps the calling session.
+#[delete("/auth/sessions?<keep_current>")]
+pub fn revoke_sessions(
+    auth: AuthUser,
+    keep_current: Option<bool>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, count) =
+        auth_controller::revoke_sessions(&auth, keep_current.unwrap_or(false));
+    to_response(status, message, count)
+}
+
+#[get("/auth/me")]
+pub fn get_me(auth: AuthUser) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, user) = auth_controller::get_me(auth);
+    to_response(status, message, user)
+}
+
+/// `resource:action` names the caller's role grants.
+#[get("/auth/permissions")]
+pub fn get_permissions(auth: AuthUser) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, permissions) = auth_controller::get_permissions(&auth);
+    to_response(status, message, permissions)
+}
+
+/// The key is part of this answer only, afterwards just its prefix is shown.
+#[post("/auth/api-keys", data = "<body>")]
+pub fn create_api_key(
+    auth: AuthUser,
+    body: Json<NewApiKey>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, created) = auth_controller::create_api_key(&auth, &body);
+    to_response(status, message, created)
+}
+
+#[get("/auth/api-keys")]
+pub fn get_api_keys(auth: AuthUser) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, api_keys) = auth_controller::get_api_keys(&auth);
+    to_response(status, message, api_keys)
+}
+
+#[delete("/auth/api-keys/<api_key_id>")]
+pub fn revoke_api_key(auth: AuthUser, api_key_id: Uuid) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, data) = auth_controller::revoke_api_key(&auth, api_key_id);
+    to_response(status, message, data)
+}
+
+#[get("/auth/verify-email")]
+pub fn get_email_status(auth: AuthUser) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, email) = auth_controller::get_email_status(&auth);
+    to_response(status, message, email)
+}
+
+/// Mails the caller a token for `POST /auth/verify-email/confirm`.
+#[post("/auth/verify-email/request")]
+pub fn request_email_verification(auth: AuthUser) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, data) = auth_controller::request_email_verification(&auth);
+    to_response(status, message, data)
+}
+
+#[post("/auth/verify-email/confirm", data = "<body>")]
+pub fn confirm_email_verification(
+    body: Json<ConfirmEmail>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, data) = auth_controller::confirm_email_verification(&body.token);
+    to_response(status, message, data)
+}
+
+/// Answers the same whether or not the account exists.
+#[post("/auth/password-reset/request", data = "<body>")]
+pub fn request_password_reset(
+    body: Json<PasswordResetRequest>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, data) = auth_controller::request_password_reset(&body.login);
+    to_response(status, message, data)
+}
+
+#[post("/auth/password-reset/confirm", data = "<body>")]
+pub fn confirm_password_reset(
+    body: Json<PasswordResetConfirm>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, data) = auth_controller::confirm_password_reset(&body);
+    to_response(status, message, data)
+}
+
+#[get("/auth/2fa")]
+pub fn get_two_factor_status(auth: AuthUser) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, data) = auth_controller::get_two_factor_status(&auth);
+    to_response(status, message, data)
+}
+
+/// A new TOTP secret with its `otpauth://` URI, pending until `POST /auth/2fa/activate`.
+#[post("/auth/2fa/enroll")]
+pub fn enroll_two_factor(auth: AuthUser) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, data) = auth_controller::enroll_two_factor(&auth);
+    to_response(status, message, data)
+}
+
+/// Answers the recovery codes, the only time they are shown.
+#[post("/auth/2fa/activate", data = "<body>")]
+pub fn activate_two_factor(
+    auth: AuthUser,
+    body: Json<TwoFactorCode>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, data) = auth_controller::activate_two_factor(&auth, &body.code);
+    to_response(status, message, data)
+}
+
+#[post("/auth/2fa/disable", data = "<body>")]
+pub fn disable_two_factor(
+    auth: AuthUser,
+    body: Json<TwoFactorCode>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, data) = auth_controller::disable_two_factor(&auth, &body.code);
+    to_response(status, message, data)
+}
+
+#[post("/auth/2fa/recovery-codes", data = "<body>")]
+pub fn regenerate_recovery_codes(
+    auth: AuthUser,
+    body: Json<TwoFactorCode>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, data) = auth_controller::regenerate_recovery_codes(&auth, &body.code);
+    to_response(status, message, data)
+}
+
+/// The configured OpenID Connect providers, for a login page to offer.
+#[get("/auth/oidc/providers")]
+pub fn get_oidc_providers() -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, data) = auth_controller::get_oidc_providers();
+    to_response(status, message, data)
+}
+
+/// The URL to send the user to for logging in at `provider`, with PKCE.
+#[get("/auth/oidc/<provider>/authorize")]
+pub fn authorize_oidc(provider: &str) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, data) = auth_controller::authorize_oidc(provider);
+    to_response(status, message, data)
+}
+
+/// Where the provider sends the user back to. Answers the same tokens as `POST /auth/login`.
+#[get("/auth/oidc/<provider>/callback?<code>&<state>&<error>&<error_description>")]
+pub fn oidc_callback(
+    provider: &str,
+    code: Option<String>,
+    state: Option<String>,
+    error: Option<String>,
+    error_description: Option<String>,
+    client: ClientInfo,
+) -> status::Custom<Json<serde_json::Value>> {
+    let callback = OidcCallback {
+        code,
+        state,
+        error,
+        error_description,
+    };
+    let (status, message, data) = auth_controller::oidc_callback(provider, &callback, &client);
+    to_response(status, message, data)
+}
+
+/// The provider accounts linked to the caller.
+#[get("/auth/oidc/identities")]
+pub fn get_oidc_identities(auth: AuthUser) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, data) = auth_controller::get_oidc_identities(&auth);
+    to_response(status, message, data)
+}
+
+/// The failed logins of a user and its latest security events.
+#[get("/auth/lockouts/<user_id>")]
+pub fn get_lockout(
+    _auth: Authorized<LockoutManage>,
+    user_id: i32,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, data) = auth_controller::get_lockout(user_id);
+    to_response(status, message, data)
+}
+
+/// Lets a locked out user log in again, the failures of its addresses stay counted.
+#[delete("/auth/lockouts/<user_id>")]
+pub fn clear_lockout(
+    auth: Authorized<LockoutManage>,
+    user_id: i32,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, data) = auth_controller::clear_lockout(&auth.auth, user_id);
+    to_response(status, message, data)
+}
+
+/// Answers with a token that acts as `user_id` for `auth.impersonation_ttl_secs`, see
+/// `Rocket.toml`. `POST /auth/logout` with it ends the impersonation early.
+#[post("/auth/impersonate/<user_id>", data = "<body>")]
+pub fn impersonate(
+    auth: Authorized<UserImpersonate>,
+    user_id: i32,
+    body: Json<ImpersonationRequest>,
+    client: ClientInfo,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, token) =
+        auth_controller::impersonate(&auth.auth, user_id, &body, &client);
+    to_response(status, message, token)
+}
+
+/// The latest impersonations, of `user_id` when given.
+#[get("/auth/impersonations?<user_id>&<limit>")]
+pub fn get_impersonations(
+    _auth: Authorized<UserImpersonate>,
+    user_id: Option<i32>,
+    limit: Option<i64>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, data) = auth_controller::get_impersonations(user_id, limit);
+    to_response(status, message, data)
+}
+
+/// Turns a refused [`AuthUser`] into the usual JSON envelope with the reason.
+#[catch(401)]
+pub fn unauthorized(request: &Request) -> Json<serde_json::Value> {
+    let reason = match request.local_cache(|| Err::<AuthUser, _>(AuthError::MissingToken)) {
+        Err(e) => e.to_string(),
+        Ok(_) => String::from("Unauthorized"),
+    };
+    Json(json!({ "status": 401, "message": reason, "body": { "data": null } }))
+}
+
+/// Names the permission an [`Authorized`](crate::guards::authorized::Authorized) guard missed.
+#[catch(403)]
+pub fn forbidden(request: &Request) -> Json<serde_json::Value> {
+    let Denied(e) = request.local_cache(|| Denied(AuthError::Forbidden(String::new())));
+    Json(json!({ "status": 403, "message": e.to_string(), "body": { "data": null } }))
+}
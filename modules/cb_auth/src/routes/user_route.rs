@@ -1,20 +1,26 @@
+use crab_rocket_user::controllers::user_controller::UserController;
+use crab_rocket_user::models::user::{ChangePassword, PatchUser, PostUser};
+use crab_rocket_user::models::user_filter::UserFilter;
 use obj_traits::controller::controller_crud::ControllerCRUD;
 use obj_traits::request::pagination_request_param::{PaginationParam, PaginationParamTrait};
 use obj_traits::request::request_param::RequestParam;
-use rocket::{delete, get, http::Status, options, patch, post, serde::json::Json};
+use rocket::{
+    delete, get, http::Status, options, patch, post, response::status, serde::json::Json,
+};
+use serde_json::json;
 
-use crate::controllers::user_controller::UserController;
-use crate::models::user::{PostUser, PatchUser};
-use crate::models::user_filter::UserFilter;
+use crate::controllers::auth_controller;
+use crate::guards::auth_user::AuthUser;
+use crate::guards::authorized::Authorized;
+use crate::models::session::ClientInfo;
+use crate::permission;
+
+permission!(pub UserCreate, "user", "create");
+permission!(pub UserUpdate, "user", "update");
+permission!(pub UserDelete, "user", "delete");
 
 #[get("/user?<limit>&<offset>")]
-pub fn get_users(mut limit: Option<i32>, mut offset: Option<i32>) -> Json<serde_json::Value> {
-    if limit.is_none() {
-        limit = Some(10);
-    };
-    if offset.is_none() {
-        offset = Some(0);
-    };
+pub fn get_users(limit: Option<i32>, offset: Option<i32>) -> Json<serde_json::Value> {
     let params = RequestParam::new(PaginationParam::new(limit, offset), None);
     println!("{:?}", params);
     crab_rocket_schema::update_reload::update_reload_count();
@@ -45,7 +51,10 @@ pub fn get_user_by_id(id: i32) -> Json<serde_json::Value> {
 }
 
 #[post("/user", data = "<user>")]
-pub fn insert_single_user(user: Json<PostUser>) -> Json<serde_json::Value> {
+pub fn insert_single_user(
+    _auth: Authorized<UserCreate>,
+    user: Json<PostUser>,
+) -> Json<serde_json::Value> {
     let mut obj: PostUser = user.into_inner();
 
     let resp = UserController::add_single(&mut obj).unwrap();
@@ -54,19 +63,49 @@ pub fn insert_single_user(user: Json<PostUser>) -> Json<serde_json::Value> {
 }
 
 #[delete("/user/<id>")]
-pub fn delete_user_by_id(id: i32) -> Json<serde_json::Value> {
+pub fn delete_user_by_id(_auth: Authorized<UserDelete>, id: i32) -> Json<serde_json::Value> {
     let resp = UserController::delete_by_id(id).unwrap();
     let json_value = serde_json::to_value(&resp).unwrap();
     Json(serde_json::from_value(json_value).unwrap())
 }
 
+/// Changing `role_id` is part of the update, so only roles granted `user:update` move users
+/// between roles.
 #[patch("/user/<id>", data = "<task>")]
-pub fn update_user_by_id(id: i32, task: Json<PatchUser>) -> Json<serde_json::Value> {
+pub fn update_user_by_id(
+    _auth: Authorized<UserUpdate>,
+    id: i32,
+    task: Json<PatchUser>,
+) -> Json<serde_json::Value> {
     let resp = UserController::update_by_id(id, &task).unwrap();
     let json_value = serde_json::to_value(&resp).unwrap();
     Json(serde_json::from_value(json_value).unwrap())
 }
 
+/// Requires the current password, the caller's own or, with `user:update`, anyone's. Answers
+/// `400`, `403` or `404` when the change is refused and `429` while the account is locked out
+/// after failed attempts, see `[lockout]` in `Rocket.toml`.
+#[patch("/user/<id>/password", data = "<body>")]
+pub fn change_user_password(
+    auth: AuthUser,
+    id: i32,
+    body: Json<ChangePassword>,
+    client: ClientInfo,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, data) = auth_controller::change_password(&auth, id, &body, &client);
+    let response = json!(
+        {
+            "status": status,
+            "message": message,
+            "body":{
+                "data":data
+            }
+        }
+    );
+    let code = Status::from_code(status as u16).unwrap_or(Status::InternalServerError);
+    status::Custom(code, Json(response))
+}
+
 #[options("/user")]
 pub fn options_user() -> Status {
     Status::Ok
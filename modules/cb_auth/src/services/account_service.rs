@@ -0,0 +1,308 @@
+use std::fmt;
+
+use chrono::Duration;
+use crab_rocket_config::app_config;
+use crab_rocket_mail::{mailer, Mail};
+use crab_rocket_schema::establish_pg_connection;
+use crab_rocket_user::mappers::user_mapper::UserMapper;
+use crab_rocket_user::models::user::{ChangePassword, User};
+use crab_rocket_utils::password::validate_password;
+use crab_rocket_utils::time::get_e8_time;
+use diesel::prelude::*;
+use diesel::result::Error;
+use obj_traits::mapper::mapper_crud::MapperCRUD;
+
+use crate::error::AuthError;
+use crate::guards::auth_user::AuthUser;
+use crate::mappers::one_time_token_mapper::OneTimeTokenMapper;
+use crate::models::account::{EmailStatus, PasswordResetConfirm};
+use crate::models::claims::{OneTimeClaims, TokenPurpose};
+use crate::models::session::ClientInfo;
+use crate::services::lockout_service::{LockoutService, LoginAttempt};
+use crate::services::rbac_service::{permission_name, RbacService};
+use crate::services::token_service::{decode_one_time_token, encode_one_time_token};
+
+#[derive(Debug)]
+pub enum AccountError {
+    /// A bad token or new password, answered with `400`.
+    Invalid(String),
+    /// The user whose password should change does not exist, `404`.
+    NotFound,
+    /// The current password given for a change is wrong, `403`.
+    WrongPassword,
+    Auth(AuthError),
+}
+
+impl AccountError {
+    pub fn status(&self) -> i32 {
+        match self {
+            AccountError::Invalid(_) => 400,
+            AccountError::NotFound => 404,
+            AccountError::WrongPassword => 403,
+            AccountError::Auth(e) => e.status().code as i32,
+        }
+    }
+}
+
+impl fmt::Display for AccountError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AccountError::Invalid(reason) => f.write_str(reason),
+            AccountError::NotFound => write!(f, "user not found"),
+            AccountError::WrongPassword => write!(f, "old password is wrong"),
+            AccountError::Auth(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<AuthError> for AccountError {
+    fn from(e: AuthError) -> Self {
+        AccountError::Auth(e)
+    }
+}
+
+impl From<Error> for AccountError {
+    fn from(e: Error) -> Self {
+        AccountError::Auth(AuthError::internal(e))
+    }
+}
+
+/// Whether `POST /auth/verify-email/request` sent a mail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerificationRequest {
+    Sent,
+    AlreadyVerified,
+}
+
+pub struct AccountService {}
+
+impl AccountService {
+    pub fn email_status(auth: &AuthUser) -> Result<EmailStatus, AccountError> {
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        Ok(EmailStatus {
+            email: auth.user.email().clone(),
+            verified_at: verified_at(&mut conn, &auth.user)?,
+        })
+    }
+
+    /// Mails the caller a token that confirms their current address.
+    pub fn request_email_verification(
+        auth: &AuthUser,
+    ) -> Result<VerificationRequest, AccountError> {
+        let Some(email) = auth.user.email().clone().filter(|email| !email.trim().is_empty()) else {
+            return Err(AccountError::Invalid("the account has no email address".to_string()));
+        };
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        if verified_at(&mut conn, &auth.user)?.is_some() {
+            return Ok(VerificationRequest::AlreadyVerified);
+        }
+        let ttl_secs = app_config().auth.email_verification_ttl_secs;
+        let token = issue(&mut conn, TokenPurpose::VerifyEmail, &auth.user, &email, ttl_secs)?;
+        send(Mail {
+            to: email.clone(),
+            subject: String::from("Confirm your email address"),
+            body: format!(
+                "Hello {},\n\nconfirm that {email} is your address by sending this token to \
+                 {} within {}:\n\n{token}\n\nIf you did not ask for this, ignore this mail.\n",
+                auth.user.username(),
+                app_config().public_url("/api/auth/verify-email/confirm"),
+                describe(ttl_secs),
+            ),
+        })?;
+        Ok(VerificationRequest::Sent)
+    }
+
+    /// Marks the address the token was sent to as verified, unless the user changed it since.
+    pub fn confirm_email_verification(token: &str) -> Result<(), AccountError> {
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        conn.transaction(|conn| {
+            let (claims, user) = consume(conn, TokenPurpose::VerifyEmail, token)?;
+            let email = claims.email.unwrap_or_default();
+            if user.email().as_deref() != Some(email.as_str()) {
+                return Err(AccountError::Invalid(
+                    "the email address changed since the token was sent".to_string(),
+                ));
+            }
+            OneTimeTokenMapper::set_verified_email(conn, user.user_id(), &email, get_e8_time())?;
+            Ok(())
+        })
+    }
+
+    /// Mails a reset token if `login` names a user with an email address. Says nothing about
+    /// whether it did, so accounts can not be probed.
+    pub fn request_password_reset(login: &str) -> Result<(), AccountError> {
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        let user = match UserMapper::get_by_login(&mut conn, login) {
+            Ok(user) => user,
+            Err(Error::NotFound) => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+        let Some(email) = user.email().clone().filter(|email| !email.trim().is_empty()) else {
+            return Ok(());
+        };
+        let ttl_secs = app_config().auth.password_reset_ttl_secs;
+        let token = issue(&mut conn, TokenPurpose::ResetPassword, &user, &email, ttl_secs)?;
+        send(Mail {
+            to: email,
+            subject: String::from("Reset your password"),
+            body: format!(
+                "Hello {},\n\nsend this token with your new password to {} within {}:\n\n\
+                 {token}\n\nIf you did not ask for this, ignore this mail, your password stays \
+                 unchanged.\n",
+                user.username(),
+                app_config().public_url("/api/auth/password-reset/confirm"),
+                describe(ttl_secs),
+            ),
+        })?;
+        Ok(())
+    }
+
+    /// Replaces the password of `user_id` after checking the current one and ends every
+    /// session of that user. Callers change their own password, others need `user:update`. A
+    /// wrong current password counts as a failed login of the account, see [`LockoutService`].
+    pub fn change_password(
+        auth: &AuthUser,
+        user_id: i32,
+        obj: &ChangePassword,
+        client: &ClientInfo,
+    ) -> Result<(), AccountError> {
+        validate_password(&obj.new_password).map_err(|e| AccountError::Invalid(e.to_string()))?;
+        if user_id != auth.user_id() && !RbacService::permissions_of(auth)?.allows("user", "update")
+        {
+            return Err(AuthError::Forbidden(permission_name("user", "update")).into());
+        }
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        let user = match UserMapper::get_by_id(&mut conn, user_id) {
+            Ok(user) => user,
+            Err(Error::NotFound) => return Err(AccountError::NotFound),
+            Err(e) => return Err(e.into()),
+        };
+        let attempt = LoginAttempt {
+            user_id: Some(user_id),
+            login: user.username(),
+            ip_address: client.ip_address.as_deref(),
+        };
+        LockoutService::check(&mut conn, &attempt)?;
+        if !UserMapper::verify_password(&mut conn, &user, &obj.old_password)? {
+            LockoutService::record_failure(&mut conn, &attempt, "wrong password on change")?;
+            return Err(AccountError::WrongPassword);
+        }
+        UserMapper::update_password(&mut conn, user_id, &obj.new_password)?;
+        Ok(())
+    }
+
+    /// Sets the new password and ends every session, like a password change does. The mail
+    /// reached the address, so it counts as verified as well.
+    pub fn confirm_password_reset(obj: &PasswordResetConfirm) -> Result<(), AccountError> {
+        validate_password(&obj.new_password).map_err(|e| AccountError::Invalid(e.to_string()))?;
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        conn.transaction(|conn| {
+            let (claims, user) = consume(conn, TokenPurpose::ResetPassword, &obj.token)?;
+            UserMapper::update_password(conn, user.user_id(), &obj.new_password)?;
+            // Proving control of the mailbox is as good as the password.
+            LockoutService::clear_account(conn, user.user_id())?;
+            if let Some(email) = claims.email.filter(|email| user.email().as_ref() == Some(email)) {
+                OneTimeTokenMapper::set_verified_email(
+                    conn,
+                    user.user_id(),
+                    &email,
+                    get_e8_time(),
+                )?;
+            }
+            Ok(())
+        })
+    }
+}
+
+/// When the user verified their current address.
+fn verified_at(
+    conn: &mut PgConnection,
+    user: &User,
+) -> Result<Option<chrono::NaiveDateTime>, AccountError> {
+    Ok(OneTimeTokenMapper::get_verified_email(conn, user.user_id())?
+        .filter(|(email, _)| user.email().as_ref() == Some(email))
+        .map(|(_, verified_at)| verified_at))
+}
+
+fn issue(
+    conn: &mut PgConnection,
+    purpose: TokenPurpose,
+    user: &User,
+    email: &str,
+    ttl_secs: i64,
+) -> Result<String, AccountError> {
+    let (token, claims) = encode_one_time_token(
+        &app_config().auth,
+        purpose,
+        user.user_id(),
+        Some(email.to_string()),
+        chrono::Utc::now().timestamp(),
+        ttl_secs,
+    )?;
+    let now = get_e8_time();
+    OneTimeTokenMapper::add_single(
+        conn,
+        claims.jti,
+        user.user_id(),
+        purpose.as_str(),
+        now,
+        now + Duration::seconds(ttl_secs),
+    )?;
+    Ok(token)
+}
+
+/// Checks the token and uses it up together with every other open token of its kind.
+fn consume(
+    conn: &mut PgConnection,
+    purpose: TokenPurpose,
+    token: &str,
+) -> Result<(OneTimeClaims, User), AccountError> {
+    let claims = decode_one_time_token(&app_config().auth, purpose, token.trim())
+        .map_err(|e| AccountError::Invalid(e.to_string()))?;
+    let invalid = |reason: &str| AccountError::Invalid(format!("invalid token: {reason}"));
+    let user_id = claims.user_id().ok_or_else(|| invalid("bad subject"))?;
+    let now = get_e8_time();
+    if OneTimeTokenMapper::use_token(conn, claims.jti, purpose.as_str(), now)? == 0 {
+        return Err(invalid("it was used already"));
+    }
+    OneTimeTokenMapper::use_all(conn, user_id, purpose.as_str(), now)?;
+    let user = match UserMapper::get_by_id(conn, user_id) {
+        Ok(user) => user,
+        Err(Error::NotFound) => return Err(AuthError::UnknownUser.into()),
+        Err(e) => return Err(e.into()),
+    };
+    Ok((claims, user))
+}
+
+fn send(mail: Mail) -> Result<(), AccountError> {
+    mailer().send(&mail).map_err(|e| AccountError::Auth(AuthError::internal(e)))
+}
+
+/// `172800` is "2 days", `3600` is "1 hour".
+fn describe(secs: i64) -> String {
+    let (n, unit) = match secs {
+        s if s % 86_400 == 0 => (s / 86_400, "day"),
+        s if s % 3_600 == 0 => (s / 3_600, "hour"),
+        s => ((s + 59) / 60, "minute"),
+    };
+    format!(
+        "{n} {unit}{}",
+        if n == 1 {
+            ""
+        } else {
+            "s"
+        }
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::describe;
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(describe(172_800), "2 days");
+        assert_eq!(describe(3_600), "1 hour");
+        assert_eq!(describe(90), "2 minutes");
+    }
+}
@@ -0,0 +1,281 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use chrono::Duration;
+use crab_rocket_schema::establish_pg_connection;
+use crab_rocket_user::mappers::user_mapper::UserMapper;
+use crab_rocket_user::models::user::User;
+use crab_rocket_utils::time::get_e8_time;
+use crab_rocket_utils::token::{generate_token, hash_token};
+use diesel::prelude::*;
+use diesel::result::Error;
+use obj_traits::mapper::mapper_crud::MapperCRUD;
+use uuid::Uuid;
+
+use crate::error::AuthError;
+use crate::guards::auth_user::{ApiKeyCredential, AuthUser, Credential};
+use crate::mappers::api_key_mapper::ApiKeyMapper;
+use crate::mappers::rbac_mapper::RbacMapper;
+use crate::models::api_key::{ApiKey, ApiKeyInfo, CreatedApiKey, NewApiKey};
+use crate::services::rbac_service::{permission_name, EffectivePermissions, RbacService};
+
+/// Every API key starts with this, which is how a bearer token is told apart from an access
+/// token.
+pub const API_KEY_PREFIX: &str = "crk_";
+/// How much of a key is stored in the clear to recognize it.
+const KEY_PREFIX_LEN: usize = 12;
+
+pub fn is_api_key(token: &str) -> bool {
+    token.starts_with(API_KEY_PREFIX)
+}
+
+#[derive(Debug)]
+pub enum ApiKeyError {
+    /// The request can not be turned into a key, e.g. an unknown scope.
+    Invalid(String),
+    /// Keys are managed from a login session, not with another key.
+    SessionRequired,
+    Auth(AuthError),
+}
+
+impl ApiKeyError {
+    pub fn status(&self) -> i32 {
+        match self {
+            ApiKeyError::Invalid(_) => 400,
+            ApiKeyError::SessionRequired => 403,
+            ApiKeyError::Auth(e) => e.status().code as i32,
+        }
+    }
+}
+
+impl fmt::Display for ApiKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiKeyError::Invalid(reason) => f.write_str(reason),
+            ApiKeyError::SessionRequired => {
+                write!(f, "API keys can only be managed after logging in with a password")
+            }
+            ApiKeyError::Auth(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<AuthError> for ApiKeyError {
+    fn from(e: AuthError) -> Self {
+        ApiKeyError::Auth(e)
+    }
+}
+
+impl From<Error> for ApiKeyError {
+    fn from(e: Error) -> Self {
+        ApiKeyError::Auth(AuthError::internal(e))
+    }
+}
+
+pub struct ApiKeyService {}
+
+impl ApiKeyService {
+    /// Creates a key for the caller.
+    pub fn create(auth: &AuthUser, obj: &NewApiKey) -> Result<CreatedApiKey, ApiKeyError> {
+        require_session(auth)?;
+        RbacService::require_two_factor(auth)?;
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        Self::create_for_user(&mut conn, auth.user_id(), obj)
+    }
+
+    /// Creates a key for any user, e.g. a service account. Every scope has to name an active
+    /// permission the role of the user grants.
+    pub fn create_for_user(
+        conn: &mut PgConnection,
+        user_id: i32,
+        obj: &NewApiKey,
+    ) -> Result<CreatedApiKey, ApiKeyError> {
+        let name = obj.name.trim();
+        if name.is_empty() || name.len() > 255 {
+            return Err(ApiKeyError::Invalid("name must have 1 to 255 characters".to_string()));
+        }
+        if obj.scopes.is_empty() {
+            return Err(ApiKeyError::Invalid("at least one scope is required".to_string()));
+        }
+        if obj.expires_in_days == Some(0) {
+            return Err(ApiKeyError::Invalid("expires_in_days must be at least 1".to_string()));
+        }
+
+        let held = EffectivePermissions::new(RbacMapper::get_permissions_of_user(conn, user_id)?);
+        let mut permission_ids = BTreeMap::new();
+        for scope in &obj.scopes {
+            let Some((resource, action)) = scope.split_once(':') else {
+                return Err(ApiKeyError::Invalid(format!(
+                    "scope `{scope}` is not of the form `resource:action`"
+                )));
+            };
+            let permission_id = match ApiKeyMapper::get_permission_id(conn, resource, action) {
+                Ok(permission_id) => permission_id,
+                Err(Error::NotFound) => {
+                    return Err(ApiKeyError::Invalid(format!("unknown scope `{scope}`")))
+                }
+                Err(e) => return Err(e.into()),
+            };
+            if !held.allows(resource, action) {
+                return Err(AuthError::Forbidden(permission_name(resource, action)).into());
+            }
+            permission_ids.insert(permission_name(resource, action), permission_id);
+        }
+
+        let now = get_e8_time();
+        let key = format!("{API_KEY_PREFIX}{}", generate_token());
+        let api_key = ApiKey::new(
+            user_id,
+            name.to_string(),
+            key[..KEY_PREFIX_LEN].to_string(),
+            hash_token(&key),
+            now,
+            obj.expires_in_days.map(|days| now + Duration::days(days.into())),
+        );
+        let ids: Vec<i32> = permission_ids.values().copied().collect();
+        let api_key = ApiKeyMapper::add_single(conn, &api_key, &ids)?;
+        Ok(CreatedApiKey {
+            info: ApiKeyInfo {
+                api_key,
+                scopes: permission_ids.into_keys().collect(),
+            },
+            key,
+        })
+    }
+
+    /// The user a key belongs to and what it may do, records the use.
+    pub fn authenticate(key: &str) -> Result<(User, ApiKeyCredential), AuthError> {
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        let api_key = match ApiKeyMapper::get_by_hash(&mut conn, &hash_token(key)) {
+            Ok(api_key) => api_key,
+            Err(Error::NotFound) => {
+                return Err(AuthError::InvalidToken("unknown API key".to_string()))
+            }
+            Err(e) => return Err(AuthError::internal(e)),
+        };
+        let now = get_e8_time();
+        if api_key.revoked_at().is_some() {
+            return Err(AuthError::InvalidToken("API key was revoked".to_string()));
+        }
+        if !api_key.is_active(now) {
+            return Err(AuthError::ExpiredToken);
+        }
+        ApiKeyMapper::touch(&mut conn, api_key.api_key_id(), now).map_err(AuthError::internal)?;
+        let user = match UserMapper::get_by_id(&mut conn, api_key.user_id()) {
+            Ok(user) => user,
+            Err(Error::NotFound) => return Err(AuthError::UnknownUser),
+            Err(e) => return Err(AuthError::internal(e)),
+        };
+        let scopes = ApiKeyMapper::get_scopes(&mut conn, &[api_key.api_key_id()])
+            .map_err(AuthError::internal)?;
+        Ok((
+            user,
+            ApiKeyCredential {
+                api_key_id: api_key.api_key_id(),
+                scopes: EffectivePermissions::new(
+                    scopes.into_iter().map(|(_, resource, action)| (resource, action)),
+                ),
+            },
+        ))
+    }
+
+    /// The caller's keys that are not revoked.
+    pub fn list(auth: &AuthUser) -> Result<Vec<ApiKeyInfo>, ApiKeyError> {
+        require_session(auth)?;
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        let api_keys = ApiKeyMapper::get_by_user(&mut conn, auth.user_id())?;
+        let ids: Vec<Uuid> = api_keys.iter().map(|api_key| api_key.api_key_id()).collect();
+        let mut scopes: BTreeMap<Uuid, Vec<String>> = BTreeMap::new();
+        for (api_key_id, resource, action) in ApiKeyMapper::get_scopes(&mut conn, &ids)? {
+            scopes.entry(api_key_id).or_default().push(permission_name(&resource, &action));
+        }
+        Ok(api_keys
+            .into_iter()
+            .map(|api_key| ApiKeyInfo {
+                scopes: scopes.remove(&api_key.api_key_id()).unwrap_or_default(),
+                api_key,
+            })
+            .collect())
+    }
+
+    /// `false` when the caller has no such open key.
+    pub fn revoke(auth: &AuthUser, api_key_id: Uuid) -> Result<bool, ApiKeyError> {
+        require_session(auth)?;
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        Ok(ApiKeyMapper::revoke(&mut conn, auth.user_id(), api_key_id, get_e8_time())? > 0)
+    }
+}
+
+fn require_session(auth: &AuthUser) -> Result<(), ApiKeyError> {
+    match &auth.credential {
+        Credential::AccessToken(claims) if claims.act.is_none() => Ok(()),
+        _ => Err(ApiKeyError::SessionRequired),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crab_rocket_test_support::{fixtures, TestDb};
+
+    use super::{is_api_key, ApiKeyError, ApiKeyService};
+    use crate::error::AuthError;
+    use crate::models::api_key::NewApiKey;
+
+    fn new_key(scopes: &[&str]) -> NewApiKey {
+        NewApiKey {
+            name: String::from("Scanner"),
+            scopes: scopes.iter().map(|s| s.to_string()).collect(),
+            expires_in_days: Some(30),
+        }
+    }
+
+    #[test]
+    fn test_api_keys_are_hashed_and_scoped() {
+        // `authenticate` opens its own connection, the rows have to be committed.
+        let db = TestDb::new();
+        let mut conn = db.conn();
+        let role_id = fixtures::role(&mut conn, "Warehouse");
+        let user_id = fixtures::user(&mut conn, "scanner");
+        fixtures::assign_role(&mut conn, user_id, role_id);
+        fixtures::grant(&mut conn, role_id, "inventory", "read");
+        fixtures::grant(&mut conn, role_id, "inventory", "update");
+        let sales = fixtures::role(&mut conn, "Sales");
+        fixtures::grant(&mut conn, sales, "order", "read");
+
+        let created = ApiKeyService::create_for_user(
+            &mut conn,
+            user_id,
+            &new_key(&["inventory:update", "inventory:update"]),
+        )
+        .unwrap();
+        assert!(is_api_key(&created.key));
+        assert!(created.key.starts_with(created.info.api_key.key_prefix()));
+        assert_eq!(created.info.scopes, ["inventory:update"]);
+        assert!(created.info.api_key.expires_at().is_some());
+        let listed = serde_json::to_value(&created.info).unwrap();
+        assert!(listed.get("key_hash").is_none());
+
+        let (user, credential) = ApiKeyService::authenticate(&created.key).unwrap();
+        assert_eq!(user.user_id(), user_id);
+        assert!(credential.scopes.allows("inventory", "update"));
+        assert!(!credential.scopes.allows("inventory", "read"));
+        assert!(matches!(
+            ApiKeyService::authenticate("crk_not-a-key"),
+            Err(AuthError::InvalidToken(_))
+        ));
+
+        // Scopes are limited to what the role grants.
+        assert!(matches!(
+            ApiKeyService::create_for_user(&mut conn, user_id, &new_key(&["order:read"])),
+            Err(ApiKeyError::Auth(AuthError::Forbidden(_)))
+        ));
+        assert!(matches!(
+            ApiKeyService::create_for_user(&mut conn, user_id, &new_key(&["ledger:read"])),
+            Err(ApiKeyError::Invalid(_))
+        ));
+        assert!(matches!(
+            ApiKeyService::create_for_user(&mut conn, user_id, &new_key(&[])),
+            Err(ApiKeyError::Invalid(_))
+        ));
+    }
+}
@@ -0,0 +1,117 @@
+use std::sync::OnceLock;
+
+use crab_rocket_schema::establish_pg_connection;
+use crab_rocket_user::mappers::user_mapper::UserMapper;
+use crab_rocket_user::models::user::User;
+use crab_rocket_utils::password::{hash_password, verify_password};
+use diesel::result::Error;
+use obj_traits::mapper::mapper_crud::MapperCRUD;
+
+use crate::error::AuthError;
+use crate::models::claims::AccessClaims;
+use crate::models::login::{LoginRequest, RefreshRequest, TokenResponse};
+use crate::models::session::ClientInfo;
+use crate::services::impersonation_service::ImpersonationService;
+use crate::services::lockout_service::{LockoutService, LoginAttempt};
+use crate::services::session_service::SessionService;
+use crate::services::token_service::{issue_access_token, verify_access_token};
+use crate::services::two_factor_service::TwoFactorService;
+
+pub struct AuthService {}
+
+impl AuthService {
+    /// Checks the credentials and, when enabled, the second factor, opens a session and issues
+    /// its first tokens. Failures are counted per account and client address, see
+    /// [`LockoutService`].
+    pub fn login(obj: &LoginRequest, client: &ClientInfo) -> Result<TokenResponse, AuthError> {
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        let user = match UserMapper::get_by_login(&mut conn, &obj.login) {
+            Ok(user) => Some(user),
+            Err(Error::NotFound) => None,
+            Err(e) => return Err(AuthError::internal(e)),
+        };
+        let attempt = LoginAttempt {
+            user_id: user.as_ref().map(|user| user.user_id()),
+            login: &obj.login,
+            ip_address: client.ip_address.as_deref(),
+        };
+        LockoutService::check(&mut conn, &attempt)?;
+        let Some(user) = user else {
+            // Spend the same time as for a wrong password so logins can not be probed.
+            verify_password(&obj.password, dummy_hash());
+            LockoutService::record_failure(&mut conn, &attempt, "unknown login")?;
+            return Err(AuthError::InvalidCredentials);
+        };
+        if !UserMapper::verify_password(&mut conn, &user, &obj.password)
+            .map_err(AuthError::internal)?
+        {
+            LockoutService::record_failure(&mut conn, &attempt, "wrong password")?;
+            return Err(AuthError::InvalidCredentials);
+        }
+        let two_factor =
+            match TwoFactorService::check_login(&mut conn, user.user_id(), obj.code.as_deref()) {
+                Ok(two_factor) => two_factor,
+                Err(AuthError::InvalidTwoFactorCode) => {
+                    LockoutService::record_failure(&mut conn, &attempt, "invalid two-factor code")?;
+                    return Err(AuthError::InvalidTwoFactorCode);
+                }
+                Err(e) => return Err(e),
+            };
+        LockoutService::record_success(&mut conn, &attempt)?;
+        let (session, refresh_token) = SessionService::start(
+            &mut conn,
+            user.user_id(),
+            client,
+            obj.device_name.clone(),
+            two_factor,
+        )
+        .map_err(AuthError::internal)?;
+        let (token, claims) = issue_access_token(user.user_id(), Some(session.session_id()))?;
+        Ok(TokenResponse::bearer(token, claims.exp - claims.iat, refresh_token, user))
+    }
+
+    /// Rotates the refresh token and issues a new access token for the same session.
+    pub fn refresh(obj: &RefreshRequest) -> Result<TokenResponse, AuthError> {
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        let (session, refresh_token) = SessionService::rotate(&mut conn, &obj.refresh_token)?;
+        let user = match UserMapper::get_by_id(&mut conn, session.user_id()) {
+            Ok(user) => user,
+            Err(Error::NotFound) => return Err(AuthError::UnknownUser),
+            Err(e) => return Err(AuthError::internal(e)),
+        };
+        let (token, claims) = issue_access_token(user.user_id(), Some(session.session_id()))?;
+        Ok(TokenResponse::bearer(token, claims.exp - claims.iat, refresh_token, user))
+    }
+
+    /// The user a bearer token belongs to.
+    pub fn authenticate(token: &str) -> Result<(User, AccessClaims), AuthError> {
+        let claims = verify_access_token(token)?;
+        let user_id =
+            claims.user_id().ok_or_else(|| AuthError::InvalidToken("bad subject".to_string()))?;
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        if let Some(actor) = &claims.act {
+            if !ImpersonationService::is_active(&mut conn, &claims, actor)
+                .map_err(AuthError::internal)?
+            {
+                return Err(AuthError::SessionEnded);
+            }
+        }
+        if let Some(session_id) = claims.sid {
+            if !SessionService::is_active(&mut conn, user_id, session_id)
+                .map_err(AuthError::internal)?
+            {
+                return Err(AuthError::SessionEnded);
+            }
+        }
+        match UserMapper::get_by_id(&mut conn, user_id) {
+            Ok(user) => Ok((user, claims)),
+            Err(Error::NotFound) => Err(AuthError::UnknownUser),
+            Err(e) => Err(AuthError::internal(e)),
+        }
+    }
+}
+
+fn dummy_hash() -> &'static str {
+    static HASH: OnceLock<String> = OnceLock::new();
+    HASH.get_or_init(|| hash_password("no such user").unwrap_or_default())
+}
@@ -0,0 +1,323 @@
+use std::fmt;
+
+use chrono::Duration;
+use crab_rocket_config::app_config;
+use crab_rocket_schema::establish_pg_connection;
+use crab_rocket_user::mappers::user_mapper::UserMapper;
+use crab_rocket_utils::time::get_e8_time;
+use diesel::prelude::*;
+use diesel::result::Error;
+use obj_traits::mapper::mapper_crud::MapperCRUD;
+use rocket::http::Method;
+
+use crate::error::AuthError;
+use crate::guards::auth_user::{AuthUser, Credential};
+use crate::mappers::impersonation_mapper::ImpersonationMapper;
+use crate::mappers::lockout_mapper::LockoutMapper;
+use crate::mappers::rbac_mapper::RbacMapper;
+use crate::models::claims::{AccessClaims, ActorClaim};
+use crate::models::impersonation::{Impersonation, ImpersonationRequest, ImpersonationToken};
+use crate::models::lockout::{NewSecurityEvent, SecurityEventType};
+use crate::services::rbac_service::EffectivePermissions;
+use crate::services::token_service::encode_impersonation_token;
+
+/// The permission that lets support staff act as other users.
+pub const IMPERSONATE: (&str, &str) = ("user", "impersonate");
+
+/// Requests an impersonation token can not make, by path below the mount point of the routes.
+/// `*` stands for one path segment and a trailing `**` for any number of them.
+const BLOCKED: &[(Method, &str)] = &[
+    (Method::Patch, "user/*"),
+    (Method::Patch, "user/*/password"),
+    (Method::Delete, "user/*"),
+    (Method::Post, "auth/2fa/**"),
+    (Method::Post, "auth/api-keys"),
+    (Method::Delete, "auth/api-keys/*"),
+    (Method::Delete, "auth/sessions/**"),
+    (Method::Post, "auth/impersonate/*"),
+    (Method::Post, "role"),
+    (Method::Patch, "role/*"),
+    (Method::Delete, "role/**"),
+    (Method::Put, "role/**"),
+    (Method::Post, "permission"),
+    (Method::Patch, "permission/*"),
+    (Method::Delete, "permission/*"),
+];
+
+#[derive(Debug)]
+pub enum ImpersonationError {
+    UnknownUser,
+    /// A reason is required for the audit trail.
+    ReasonRequired,
+    /// The caller may hold the permission, but not for this target or with this credential.
+    NotAllowed(&'static str),
+    Auth(AuthError),
+}
+
+impl ImpersonationError {
+    pub fn status(&self) -> i32 {
+        match self {
+            ImpersonationError::UnknownUser => 404,
+            ImpersonationError::ReasonRequired => 400,
+            ImpersonationError::NotAllowed(_) => 403,
+            ImpersonationError::Auth(e) => e.status().code as i32,
+        }
+    }
+}
+
+impl fmt::Display for ImpersonationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImpersonationError::UnknownUser => write!(f, "no such user"),
+            ImpersonationError::ReasonRequired => {
+                write!(f, "reason must have 1 to 255 characters")
+            }
+            ImpersonationError::NotAllowed(reason) => f.write_str(reason),
+            ImpersonationError::Auth(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<AuthError> for ImpersonationError {
+    fn from(e: AuthError) -> Self {
+        ImpersonationError::Auth(e)
+    }
+}
+
+impl From<Error> for ImpersonationError {
+    fn from(e: Error) -> Self {
+        ImpersonationError::Auth(AuthError::internal(e))
+    }
+}
+
+pub struct ImpersonationService {}
+
+impl ImpersonationService {
+    /// Issues a token that acts as `target_user_id` for `auth.impersonation_ttl_secs`. The
+    /// caller has to be logged in with a password themselves, and users who may impersonate or
+    /// hold a permission the caller lacks can not be impersonated.
+    pub fn start(
+        auth: &AuthUser,
+        target_user_id: i32,
+        obj: &ImpersonationRequest,
+        ip_address: Option<&str>,
+    ) -> Result<ImpersonationToken, ImpersonationError> {
+        match &auth.credential {
+            Credential::AccessToken(claims) if claims.act.is_none() => {}
+            Credential::AccessToken(_) => {
+                return Err(ImpersonationError::NotAllowed("end the current impersonation first"))
+            }
+            Credential::ApiKey(_) => {
+                return Err(ImpersonationError::NotAllowed(
+                    "impersonation needs a login, not an API key",
+                ))
+            }
+        }
+        let reason = obj.reason.trim();
+        if reason.is_empty() || reason.len() > 255 {
+            return Err(ImpersonationError::ReasonRequired);
+        }
+        if target_user_id == auth.user_id() {
+            return Err(ImpersonationError::NotAllowed("you can not impersonate yourself"));
+        }
+
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        let user = match UserMapper::get_by_id(&mut conn, target_user_id) {
+            Ok(user) => user,
+            Err(Error::NotFound) => return Err(ImpersonationError::UnknownUser),
+            Err(e) => return Err(e.into()),
+        };
+        let target = EffectivePermissions::new(RbacMapper::get_permissions_of_user(
+            &mut conn,
+            target_user_id,
+        )?);
+        let (resource, action) = IMPERSONATE;
+        if target.allows(resource, action) {
+            return Err(ImpersonationError::NotAllowed(
+                "users who may impersonate others can not be impersonated",
+            ));
+        }
+        let own = EffectivePermissions::new(RbacMapper::get_permissions_of_user(
+            &mut conn,
+            auth.user_id(),
+        )?);
+        if !target.is_subset(&own) {
+            return Err(ImpersonationError::NotAllowed(
+                "users with permissions you do not hold can not be impersonated",
+            ));
+        }
+
+        let config = &app_config().auth;
+        let now = get_e8_time();
+        let impersonation = Impersonation::new(
+            auth.user_id(),
+            target_user_id,
+            reason.to_string(),
+            ip_address.map(String::from),
+            now,
+            now + Duration::seconds(config.impersonation_ttl_secs),
+        );
+        let actor = ActorClaim {
+            sub: auth.user_id().to_string(),
+            iid: impersonation.impersonation_id(),
+        };
+        let (access_token, _) = encode_impersonation_token(
+            config,
+            target_user_id,
+            actor,
+            chrono::Utc::now().timestamp(),
+        )?;
+        conn.transaction::<_, Error, _>(|conn| {
+            ImpersonationMapper::add(conn, &impersonation)?;
+            let detail = format!("{}: {reason}", impersonation.impersonation_id());
+            log(
+                conn,
+                SecurityEventType::ImpersonationStarted,
+                target_user_id,
+                auth.user_id(),
+                &detail,
+            )
+        })?;
+        Ok(ImpersonationToken {
+            access_token,
+            token_type: String::from("Bearer"),
+            expires_in: config.impersonation_ttl_secs,
+            impersonation,
+            user,
+        })
+    }
+
+    /// Ends the impersonation of the calling token, returns `false` for other tokens.
+    pub fn end(auth: &AuthUser) -> Result<bool, AuthError> {
+        let Some(actor) = impersonator(auth) else {
+            return Ok(false);
+        };
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        let ended = ImpersonationMapper::end(&mut conn, actor.iid, get_e8_time())
+            .map_err(AuthError::internal)?;
+        if ended {
+            let actor_user_id = actor.user_id().unwrap_or_default();
+            let detail = actor.iid.to_string();
+            log(
+                &mut conn,
+                SecurityEventType::ImpersonationEnded,
+                auth.user_id(),
+                actor_user_id,
+                &detail,
+            )
+            .map_err(AuthError::internal)?;
+        }
+        Ok(true)
+    }
+
+    /// Whether the impersonation behind `claims` still runs for the users it names. Ended
+    /// ones are treated like logged out sessions.
+    pub fn is_active(
+        conn: &mut PgConnection,
+        claims: &AccessClaims,
+        actor: &ActorClaim,
+    ) -> Result<bool, Error> {
+        match ImpersonationMapper::get(conn, actor.iid) {
+            Ok(impersonation) => Ok(impersonation.is_active(get_e8_time())
+                && impersonation.actor_user_id() == actor.user_id()
+                && impersonation.target_user_id() == claims.user_id()),
+            Err(Error::NotFound) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// The audit trail, newest first.
+    pub fn history(
+        target_user_id: Option<i32>,
+        limit: i64,
+    ) -> Result<Vec<Impersonation>, AuthError> {
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        ImpersonationMapper::get_recent(&mut conn, target_user_id, limit.clamp(1, 200))
+            .map_err(AuthError::internal)
+    }
+
+    /// Notes a request made with an impersonation token, `detail` says what it was.
+    pub fn record_request(user_id: i32, actor_user_id: i32, detail: &str) -> Result<(), AuthError> {
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        log(&mut conn, SecurityEventType::ImpersonatedRequest, user_id, actor_user_id, detail)
+            .map_err(AuthError::internal)
+    }
+}
+
+/// Refuses the [`BLOCKED`] requests of an impersonation token, `path` is relative to the mount
+/// point of the route.
+pub fn check_allowed(auth: &AuthUser, method: Method, path: &str) -> Result<(), AuthError> {
+    if impersonator(auth).is_some() && is_blocked(method, path) {
+        return Err(AuthError::Impersonating);
+    }
+    Ok(())
+}
+
+pub fn is_blocked(method: Method, path: &str) -> bool {
+    let segments: Vec<&str> = path.trim_end_matches('/').split('/').collect();
+    BLOCKED.iter().any(|(blocked, pattern)| *blocked == method && matches(pattern, &segments))
+}
+
+fn matches(pattern: &str, segments: &[&str]) -> bool {
+    let pattern: Vec<&str> = pattern.split('/').collect();
+    if let Some((&"**", prefix)) = pattern.split_last() {
+        return segments.len() >= prefix.len() && matches_segments(prefix, segments);
+    }
+    pattern.len() == segments.len() && matches_segments(&pattern, segments)
+}
+
+fn matches_segments(pattern: &[&str], segments: &[&str]) -> bool {
+    pattern.iter().zip(segments).all(|(p, s)| *p == "*" || p == s)
+}
+
+/// The actor behind an impersonation token.
+pub fn impersonator(auth: &AuthUser) -> Option<&ActorClaim> {
+    match &auth.credential {
+        Credential::AccessToken(claims) => claims.act.as_ref(),
+        Credential::ApiKey(_) => None,
+    }
+}
+
+fn log(
+    conn: &mut PgConnection,
+    event_type: SecurityEventType,
+    user_id: i32,
+    actor_user_id: i32,
+    detail: &str,
+) -> Result<(), Error> {
+    let detail: String = detail.chars().take(255).collect();
+    let event = NewSecurityEvent {
+        event_type: event_type.as_str(),
+        user_id: Some(user_id),
+        login: None,
+        ip_address: None,
+        detail: Some(&detail),
+        created_at: get_e8_time(),
+        actor_user_id: Some(actor_user_id),
+    };
+    LockoutMapper::add_event(conn, &event).map(|_| ())
+}
+
+#[cfg(test)]
+mod test {
+    use rocket::http::Method;
+
+    use super::is_blocked;
+
+    #[test]
+    fn test_sensitive_requests_are_blocked() {
+        assert!(is_blocked(Method::Patch, "user/7/password"));
+        assert!(is_blocked(Method::Patch, "user/7"));
+        assert!(is_blocked(Method::Post, "auth/2fa/disable"));
+        assert!(is_blocked(Method::Delete, "auth/sessions"));
+        assert!(is_blocked(Method::Delete, "auth/sessions/abc"));
+        assert!(is_blocked(Method::Put, "role/2/permission/5"));
+        assert!(is_blocked(Method::Post, "permission"));
+
+        assert!(!is_blocked(Method::Get, "user/7"));
+        assert!(!is_blocked(Method::Post, "role/filter"));
+        assert!(!is_blocked(Method::Post, "permission/filter"));
+        assert!(!is_blocked(Method::Post, "auth/logout"));
+        assert!(!is_blocked(Method::Patch, "post/3"));
+    }
+}
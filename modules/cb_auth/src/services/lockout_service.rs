@@ -0,0 +1,191 @@
+use chrono::NaiveDateTime;
+use crab_rocket_config::app_config;
+use crab_rocket_schema::establish_pg_connection;
+use crab_rocket_user::mappers::user_mapper::UserMapper;
+use crab_rocket_utils::time::get_e8_time;
+use diesel::prelude::*;
+use diesel::result::Error;
+use obj_traits::mapper::mapper_crud::MapperCRUD;
+
+use crate::error::AuthError;
+use crate::guards::auth_user::AuthUser;
+use crate::mappers::lockout_mapper::LockoutMapper;
+use crate::models::lockout::{
+    LockoutStatus, LoginThrottle, NewSecurityEvent, SecurityEventType, ThrottleScope,
+};
+
+/// The permission that lets staff see and clear login lockouts.
+pub const MANAGE_LOCKOUTS: (&str, &str) = ("lockout", "manage");
+
+/// How many events `GET /auth/lockouts/<user_id>` shows.
+const RECENT_EVENTS: i64 = 20;
+
+/// Who a login attempt named and where it came from.
+#[derive(Debug, Clone, Copy)]
+pub struct LoginAttempt<'a> {
+    /// `None` when `login` matched no account, only the address is counted then.
+    pub user_id: Option<i32>,
+    pub login: &'a str,
+    pub ip_address: Option<&'a str>,
+}
+
+impl LoginAttempt<'_> {
+    fn throttle_keys(&self) -> Vec<(ThrottleScope, String)> {
+        let account = self.user_id.map(|user_id| (ThrottleScope::Account, user_id.to_string()));
+        let ip = self.ip_address.map(|ip| (ThrottleScope::Ip, ip.to_string()));
+        account.into_iter().chain(ip).collect()
+    }
+}
+
+/// Outcome of `DELETE /auth/lockouts/<user_id>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockoutClear {
+    Cleared,
+    NothingToClear,
+    UnknownUser,
+}
+
+pub struct LockoutService {}
+
+impl LockoutService {
+    /// Refuses the attempt, without looking at the password, while its account or address
+    /// has to wait.
+    pub fn check(conn: &mut PgConnection, attempt: &LoginAttempt) -> Result<(), AuthError> {
+        let now = get_e8_time();
+        let mut retry_after = None;
+        for (scope, subject) in attempt.throttle_keys() {
+            let throttle = LockoutMapper::get(conn, scope.as_str(), &subject)
+                .optional()
+                .map_err(AuthError::internal)?;
+            retry_after = retry_after.max(throttle.and_then(|t| t.retry_after_secs(now)));
+        }
+        let Some(secs) = retry_after else {
+            return Ok(());
+        };
+        log(conn, attempt, SecurityEventType::LoginBlocked, &format!("retry in {secs}s"), now)?;
+        Err(AuthError::TooManyAttempts(secs))
+    }
+
+    /// Counts a failed attempt against its account and address, `reason` goes to the log.
+    pub fn record_failure(
+        conn: &mut PgConnection,
+        attempt: &LoginAttempt,
+        reason: &str,
+    ) -> Result<(), AuthError> {
+        let now = get_e8_time();
+        let config = &app_config().lockout;
+        log(conn, attempt, SecurityEventType::LoginFailed, reason, now)?;
+        for (scope, subject) in attempt.throttle_keys() {
+            let throttle = conn
+                .transaction::<_, Error, _>(|conn| {
+                    let previous =
+                        LockoutMapper::get_for_update(conn, scope.as_str(), &subject).optional()?;
+                    let next = LoginThrottle::after_failure(
+                        previous.as_ref(),
+                        scope,
+                        &subject,
+                        now,
+                        config,
+                    );
+                    LockoutMapper::save(conn, &next)?;
+                    Ok(next)
+                })
+                .map_err(AuthError::internal)?;
+            if throttle.failed_count() == scope.max_failures(config) {
+                let event_type = match scope {
+                    ThrottleScope::Account => SecurityEventType::AccountLocked,
+                    ThrottleScope::Ip => SecurityEventType::IpLocked,
+                };
+                let detail = format!("{} failed logins", throttle.failed_count());
+                log(conn, attempt, event_type, &detail, now)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// A successful login forgets the failures of its account, not those of its address.
+    pub fn record_success(
+        conn: &mut PgConnection,
+        attempt: &LoginAttempt,
+    ) -> Result<(), AuthError> {
+        if let Some(user_id) = attempt.user_id {
+            LockoutMapper::delete(conn, ThrottleScope::Account.as_str(), &user_id.to_string())
+                .map_err(AuthError::internal)?;
+        }
+        log(conn, attempt, SecurityEventType::LoginSucceeded, "", get_e8_time())
+    }
+
+    /// The failures of `user_id` and its latest security events, `None` for an unknown user.
+    pub fn status(user_id: i32) -> Result<Option<LockoutStatus>, AuthError> {
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        if UserMapper::get_by_id(&mut conn, user_id)
+            .optional()
+            .map_err(AuthError::internal)?
+            .is_none()
+        {
+            return Ok(None);
+        }
+        let throttle =
+            LockoutMapper::get(&mut conn, ThrottleScope::Account.as_str(), &user_id.to_string())
+                .optional()
+                .map_err(AuthError::internal)?;
+        Ok(Some(LockoutStatus {
+            user_id,
+            locked: throttle.as_ref().and_then(|t| t.retry_after_secs(get_e8_time())).is_some(),
+            throttle,
+            recent_events: LockoutMapper::get_events_of_user(&mut conn, user_id, RECENT_EVENTS)
+                .map_err(AuthError::internal)?,
+        }))
+    }
+
+    /// Lets `user_id` log in again right away, recorded with the admin who did it.
+    pub fn clear(admin: &AuthUser, user_id: i32) -> Result<LockoutClear, AuthError> {
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        if UserMapper::get_by_id(&mut conn, user_id)
+            .optional()
+            .map_err(AuthError::internal)?
+            .is_none()
+        {
+            return Ok(LockoutClear::UnknownUser);
+        }
+        let cleared = Self::clear_account(&mut conn, user_id)?;
+        if !cleared {
+            return Ok(LockoutClear::NothingToClear);
+        }
+        let attempt = LoginAttempt {
+            user_id: Some(user_id),
+            login: "",
+            ip_address: None,
+        };
+        let detail = format!("by user {}", admin.user_id());
+        log(&mut conn, &attempt, SecurityEventType::LockoutCleared, &detail, get_e8_time())?;
+        Ok(LockoutClear::Cleared)
+    }
+
+    /// Forgets the failed logins of `user_id`, `false` when there were none.
+    pub fn clear_account(conn: &mut PgConnection, user_id: i32) -> Result<bool, AuthError> {
+        LockoutMapper::delete(conn, ThrottleScope::Account.as_str(), &user_id.to_string())
+            .map(|deleted| deleted > 0)
+            .map_err(AuthError::internal)
+    }
+}
+
+fn log(
+    conn: &mut PgConnection,
+    attempt: &LoginAttempt,
+    event_type: SecurityEventType,
+    detail: &str,
+    now: NaiveDateTime,
+) -> Result<(), AuthError> {
+    let event = NewSecurityEvent {
+        event_type: event_type.as_str(),
+        user_id: attempt.user_id,
+        login: Some(attempt.login).filter(|login| !login.is_empty()),
+        ip_address: attempt.ip_address,
+        detail: Some(detail).filter(|detail| !detail.is_empty()),
+        created_at: now,
+        actor_user_id: None,
+    };
+    LockoutMapper::add_event(conn, &event).map_err(AuthError::internal)?;
+    Ok(())
+}
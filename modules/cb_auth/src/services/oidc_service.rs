@@ -0,0 +1,438 @@
+use std::fmt;
+use std::sync::OnceLock;
+use std::time::Duration as StdDuration;
+
+use chrono::Duration;
+use crab_rocket_config::app_config;
+use crab_rocket_config::app_config::OidcProviderConfig;
+use crab_rocket_schema::establish_pg_connection;
+use crab_rocket_user::mappers::user_mapper::UserMapper;
+use crab_rocket_user::models::user::{PostUser, User};
+use crab_rocket_utils::time::get_e8_time;
+use crab_rocket_utils::token::{generate_token, hash_token, pkce_challenge};
+use diesel::prelude::*;
+use diesel::result::Error;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use obj_traits::mapper::mapper_crud::MapperCRUD;
+use rocket::serde::de::DeserializeOwned;
+use rocket::serde::Deserialize;
+use url::Url;
+
+use crate::error::AuthError;
+use crate::guards::auth_user::AuthUser;
+use crate::mappers::oidc_mapper::OidcMapper;
+use crate::mappers::one_time_token_mapper::OneTimeTokenMapper;
+use crate::mappers::rbac_mapper::RbacMapper;
+use crate::models::login::TokenResponse;
+use crate::models::oidc::{
+    IdTokenClaims, OidcAuthorization, OidcCallback, OidcIdentity, OidcLogin, OidcProviderInfo,
+};
+use crate::models::session::ClientInfo;
+use crate::services::lockout_service::{LockoutService, LoginAttempt};
+use crate::services::session_service::SessionService;
+use crate::services::token_service::issue_access_token;
+
+/// For each request to a provider, a login waits for all of them.
+const HTTP_TIMEOUT_SECS: u64 = 10;
+
+#[derive(Debug)]
+pub enum OidcError {
+    UnknownProvider,
+    /// No pending login for the `state` of the callback, it was used or took too long.
+    InvalidState,
+    /// The provider sent the user back with an `error` instead of a `code`.
+    Denied(String),
+    /// The provider could not be reached or answered nonsense.
+    Provider(String),
+    InvalidIdToken(String),
+    /// Without a verified email an unknown identity can neither be linked nor provisioned.
+    EmailNotVerified,
+    /// No user has the email and the provider does not provision new ones.
+    NoAccount,
+    /// More than one user has the email, it has to be linked by hand.
+    AmbiguousEmail,
+    Auth(AuthError),
+}
+
+impl OidcError {
+    pub fn status(&self) -> i32 {
+        match self {
+            OidcError::UnknownProvider => 404,
+            OidcError::InvalidState => 400,
+            OidcError::Denied(_) | OidcError::InvalidIdToken(_) => 401,
+            OidcError::EmailNotVerified | OidcError::NoAccount => 403,
+            OidcError::AmbiguousEmail => 409,
+            OidcError::Provider(_) => 502,
+            OidcError::Auth(e) => e.status().code as i32,
+        }
+    }
+}
+
+impl fmt::Display for OidcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OidcError::UnknownProvider => write!(f, "no such identity provider"),
+            OidcError::InvalidState => {
+                write!(f, "unknown or expired login, start again from the login page")
+            }
+            OidcError::Denied(reason) => write!(f, "the identity provider refused: {reason}"),
+            OidcError::Provider(reason) => write!(f, "identity provider failed: {reason}"),
+            OidcError::InvalidIdToken(reason) => write!(f, "invalid ID token: {reason}"),
+            OidcError::EmailNotVerified => {
+                write!(f, "the identity provider did not confirm an email address for this account")
+            }
+            OidcError::NoAccount => write!(f, "no user has this email address, ask an admin"),
+            OidcError::AmbiguousEmail => {
+                write!(f, "several users have this email address, ask an admin to link one")
+            }
+            OidcError::Auth(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<AuthError> for OidcError {
+    fn from(e: AuthError) -> Self {
+        OidcError::Auth(e)
+    }
+}
+
+impl From<Error> for OidcError {
+    fn from(e: Error) -> Self {
+        OidcError::Auth(AuthError::internal(e))
+    }
+}
+
+/// The parts of the discovery document a login needs.
+#[derive(Deserialize, Debug)]
+#[serde(crate = "rocket::serde")]
+struct ProviderMetadata {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    jwks_uri: String,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(crate = "rocket::serde")]
+struct TokenEndpointResponse {
+    id_token: Option<String>,
+}
+
+pub struct OidcService {}
+
+impl OidcService {
+    pub fn providers() -> Vec<OidcProviderInfo> {
+        app_config()
+            .oidc
+            .providers
+            .iter()
+            .map(|(name, provider)| OidcProviderInfo {
+                name: name.clone(),
+                display_name: display_name(name, provider).to_string(),
+            })
+            .collect()
+    }
+
+    /// Starts a login at `provider`: remembers a fresh `state`, `nonce` and PKCE verifier and
+    /// returns the URL to send the user to.
+    pub fn authorize(provider: &str) -> Result<OidcAuthorization, OidcError> {
+        let config = provider_config(provider)?;
+        let metadata = discover(config)?;
+        let (state, nonce, code_verifier) = (generate_token(), generate_token(), generate_token());
+        let now = get_e8_time();
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        OidcMapper::delete_logins_before(
+            &mut conn,
+            now - Duration::seconds(app_config().oidc.login_ttl_secs),
+        )?;
+        OidcMapper::add_login(
+            &mut conn,
+            &OidcLogin::new(
+                hash_token(&state),
+                provider.to_string(),
+                code_verifier.clone(),
+                nonce.clone(),
+                now,
+            ),
+        )?;
+        let authorization_url = Url::parse_with_params(
+            &metadata.authorization_endpoint,
+            [
+                ("response_type", "code"),
+                ("client_id", config.client_id.as_str()),
+                ("redirect_uri", redirect_uri(provider, config).as_str()),
+                ("scope", config.scopes.join(" ").as_str()),
+                ("state", state.as_str()),
+                ("nonce", nonce.as_str()),
+                ("code_challenge", pkce_challenge(&code_verifier).as_str()),
+                ("code_challenge_method", "S256"),
+            ],
+        )
+        .map_err(|e| OidcError::Provider(format!("authorization_endpoint: {e}")))?;
+        Ok(OidcAuthorization {
+            authorization_url: authorization_url.into(),
+        })
+    }
+
+    /// Finishes a login: trades the code for an ID token, finds or creates the user of its
+    /// identity and opens a session like a password login.
+    pub fn callback(
+        provider: &str,
+        callback: &OidcCallback,
+        client: &ClientInfo,
+    ) -> Result<TokenResponse, OidcError> {
+        let config = provider_config(provider)?;
+        if let Some(error) = &callback.error {
+            return Err(OidcError::Denied(
+                callback.error_description.clone().unwrap_or_else(|| error.clone()),
+            ));
+        }
+        let (Some(code), Some(state)) = (&callback.code, &callback.state) else {
+            return Err(OidcError::InvalidState);
+        };
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        let login = OidcMapper::take_login(&mut conn, &hash_token(state))
+            .optional()?
+            .filter(|login| login.provider() == provider)
+            .filter(|login| {
+                login.created_at() + Duration::seconds(app_config().oidc.login_ttl_secs)
+                    > get_e8_time()
+            })
+            .ok_or(OidcError::InvalidState)?;
+
+        let metadata = discover(config)?;
+        let response: TokenEndpointResponse = post_form(
+            &metadata.token_endpoint,
+            &[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", &redirect_uri(provider, config)),
+                ("client_id", &config.client_id),
+                ("client_secret", &config.client_secret),
+                ("code_verifier", login.code_verifier()),
+            ],
+        )?;
+        let id_token = response
+            .id_token
+            .ok_or_else(|| OidcError::Provider(String::from("token response without id_token")))?;
+        let claims = verify_id_token(&id_token, config, &metadata, login.nonce())?;
+
+        let user = conn.transaction(|conn| find_or_create_user(conn, provider, config, &claims))?;
+        let attempt = LoginAttempt {
+            user_id: Some(user.user_id()),
+            login: claims.email.as_deref().unwrap_or(&claims.sub),
+            ip_address: client.ip_address.as_deref(),
+        };
+        LockoutService::record_success(&mut conn, &attempt)?;
+        // The provider decides how many factors it asks for, its `amr` says what it did.
+        let two_factor = claims.amr.iter().any(|method| method == "mfa");
+        let (session, refresh_token) =
+            SessionService::start(&mut conn, user.user_id(), client, None, two_factor)?;
+        let (token, access_claims) =
+            issue_access_token(user.user_id(), Some(session.session_id()))?;
+        Ok(TokenResponse::bearer(token, access_claims.exp - access_claims.iat, refresh_token, user))
+    }
+
+    /// The provider accounts the caller can log in with.
+    pub fn identities(auth: &AuthUser) -> Result<Vec<OidcIdentity>, OidcError> {
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        Ok(OidcMapper::get_identities_of_user(&mut conn, auth.user_id())?)
+    }
+}
+
+fn provider_config(provider: &str) -> Result<&'static OidcProviderConfig, OidcError> {
+    app_config().oidc.providers.get(provider).ok_or(OidcError::UnknownProvider)
+}
+
+fn display_name<'a>(name: &'a str, provider: &'a OidcProviderConfig) -> &'a str {
+    if provider.display_name.is_empty() {
+        name
+    } else {
+        &provider.display_name
+    }
+}
+
+fn redirect_uri(provider: &str, config: &OidcProviderConfig) -> String {
+    config
+        .redirect_uri
+        .clone()
+        .unwrap_or_else(|| app_config().public_url(&format!("/api/auth/oidc/{provider}/callback")))
+}
+
+/// The identity's user, linked on first use to the user with its verified email, or created
+/// when the provider allows it.
+fn find_or_create_user(
+    conn: &mut PgConnection,
+    provider: &str,
+    config: &OidcProviderConfig,
+    claims: &IdTokenClaims,
+) -> Result<User, OidcError> {
+    let now = get_e8_time();
+    if let Some(identity) = OidcMapper::get_identity(conn, provider, &claims.sub).optional()? {
+        OidcMapper::touch_identity(conn, provider, &claims.sub, claims.email.as_deref(), now)?;
+        return Ok(UserMapper::get_by_id(conn, identity.user_id())?);
+    }
+    let email = claims
+        .email
+        .as_deref()
+        .filter(|_| claims.email_verified == Some(true))
+        .ok_or(OidcError::EmailNotVerified)?;
+    let mut users = OidcMapper::get_users_by_email(conn, email)?;
+    let user = match users.len() {
+        0 if config.auto_provision => provision_user(conn, provider, config, claims, email)?,
+        0 => return Err(OidcError::NoAccount),
+        1 => users.remove(0),
+        _ => return Err(OidcError::AmbiguousEmail),
+    };
+    OidcMapper::add_identity(
+        conn,
+        &OidcIdentity::new(
+            provider.to_string(),
+            claims.sub.clone(),
+            user.user_id(),
+            Some(email.to_string()),
+            now,
+        ),
+    )?;
+    // The provider vouched for the address, no need for a verification mail.
+    OneTimeTokenMapper::set_verified_email(conn, user.user_id(), email, now)?;
+    Ok(user)
+}
+
+fn provision_user(
+    conn: &mut PgConnection,
+    provider: &str,
+    config: &OidcProviderConfig,
+    claims: &IdTokenClaims,
+    email: &str,
+) -> Result<User, OidcError> {
+    let role_name = config.default_role.as_deref().unwrap_or_default();
+    let role_id =
+        RbacMapper::get_role_id_by_name(conn, role_name).optional()?.ok_or_else(|| {
+            AuthError::Internal(format!("oidc default_role `{role_name}` does not exist"))
+        })?;
+    let wanted = claims
+        .preferred_username
+        .as_deref()
+        .unwrap_or_else(|| email.split('@').next().unwrap_or_default());
+    let now = get_e8_time();
+    let user = PostUser::new(
+        free_username(conn, wanted)?,
+        Some(role_id),
+        Some(now),
+        Some(email.to_string()),
+        // Nobody knows it, the user logs in through the provider or resets it by mail.
+        generate_token(),
+        claims.name.clone(),
+        None,
+        None,
+        Some(now),
+        // Must be unique, the provider does not tell the phone number.
+        format!("oidc:{}", &hash_token(&format!("{provider}:{}", claims.sub))[..32]),
+    );
+    Ok(UserMapper::add_single(conn, &user)?)
+}
+
+/// `wanted` reduced to letters, digits, `.`, `_` and `-`, numbered when taken.
+fn free_username(conn: &mut PgConnection, wanted: &str) -> Result<String, Error> {
+    let mut base: String = wanted
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+        .take(200)
+        .collect();
+    if base.is_empty() {
+        base = String::from("user");
+    }
+    let mut candidate = base.clone();
+    for n in 2.. {
+        if UserMapper::get_by_username(conn, &candidate).optional()?.is_none() {
+            break;
+        }
+        candidate = format!("{base}{n}");
+    }
+    Ok(candidate)
+}
+
+fn verify_id_token(
+    id_token: &str,
+    config: &OidcProviderConfig,
+    metadata: &ProviderMetadata,
+    nonce: &str,
+) -> Result<IdTokenClaims, OidcError> {
+    let invalid = |e: jsonwebtoken::errors::Error| OidcError::InvalidIdToken(e.to_string());
+    let header = decode_header(id_token).map_err(invalid)?;
+    let key = match header.alg {
+        // Symmetric ID tokens are signed with the client secret, never with a published key.
+        Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512 => {
+            if config.client_secret.is_empty() {
+                return Err(OidcError::InvalidIdToken(String::from(
+                    "HMAC signed but no client_secret configured",
+                )));
+            }
+            DecodingKey::from_secret(config.client_secret.as_bytes())
+        }
+        _ => {
+            let jwks: JwkSet = get_json(&metadata.jwks_uri)?;
+            let jwk = match &header.kid {
+                Some(kid) => jwks.find(kid),
+                None if jwks.keys.len() == 1 => jwks.keys.first(),
+                None => None,
+            }
+            .ok_or_else(|| OidcError::InvalidIdToken(String::from("unknown signing key")))?;
+            DecodingKey::from_jwk(jwk).map_err(invalid)?
+        }
+    };
+    let mut validation = Validation::new(header.alg);
+    validation.set_issuer(&[&metadata.issuer]);
+    validation.set_audience(&[&config.client_id]);
+    validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+    let claims = decode::<IdTokenClaims>(id_token, &key, &validation).map_err(invalid)?.claims;
+    if claims.nonce.as_deref() != Some(nonce) {
+        return Err(OidcError::InvalidIdToken(String::from("nonce does not match the login")));
+    }
+    Ok(claims)
+}
+
+/// Read on every login, a provider serves it with caching headers anyway.
+fn discover(config: &OidcProviderConfig) -> Result<ProviderMetadata, OidcError> {
+    let url = format!("{}/.well-known/openid-configuration", config.issuer.trim_end_matches('/'));
+    let metadata: ProviderMetadata = get_json(&url)?;
+    if metadata.issuer != config.issuer {
+        return Err(OidcError::Provider(format!(
+            "discovery document is for issuer `{}`, not `{}`",
+            metadata.issuer, config.issuer
+        )));
+    }
+    Ok(metadata)
+}
+
+fn agent() -> &'static ureq::Agent {
+    static AGENT: OnceLock<ureq::Agent> = OnceLock::new();
+    AGENT.get_or_init(|| {
+        ureq::AgentBuilder::new().timeout(StdDuration::from_secs(HTTP_TIMEOUT_SECS)).build()
+    })
+}
+
+fn get_json<T: DeserializeOwned>(url: &str) -> Result<T, OidcError> {
+    read_json(url, agent().get(url).call())
+}
+
+fn post_form<T: DeserializeOwned>(url: &str, form: &[(&str, &str)]) -> Result<T, OidcError> {
+    read_json(url, agent().post(url).send_form(form))
+}
+
+fn read_json<T: DeserializeOwned>(
+    url: &str,
+    response: Result<ureq::Response, ureq::Error>,
+) -> Result<T, OidcError> {
+    let failed = |reason: String| OidcError::Provider(format!("{url}: {reason}"));
+    match response {
+        Ok(response) => response.into_json().map_err(|e| failed(e.to_string())),
+        Err(ureq::Error::Status(status, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            Err(failed(format!("status {status} {}", body.trim())))
+        }
+        Err(e) => Err(failed(e.to_string())),
+    }
+}
@@ -0,0 +1,122 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::error::AuthError;
+use crate::guards::auth_user::{AuthUser, Credential};
+use crate::services::rbac_service::{permission_name, RbacService};
+
+/// The action that lets a role change rows of a resource whoever owns them, e.g.
+/// `post:moderate`.
+pub const MODERATE: &str = "moderate";
+
+/// Why a user-owned row could not be changed.
+#[derive(Debug)]
+pub enum OwnershipError {
+    NotFound {
+        resource: &'static str,
+        id: i32,
+    },
+    Auth(AuthError),
+}
+
+impl OwnershipError {
+    pub fn status(&self) -> i32 {
+        match self {
+            OwnershipError::NotFound {
+                ..
+            } => 404,
+            OwnershipError::Auth(e) => e.status().code as i32,
+        }
+    }
+
+    /// Sorts out what loading row `id` of `resource` failed with, a missing row is a 404.
+    pub fn lookup(resource: &'static str, id: i32, e: Box<dyn Error>) -> Self {
+        match e.downcast_ref::<diesel::result::Error>() {
+            Some(diesel::result::Error::NotFound) => OwnershipError::NotFound {
+                resource,
+                id,
+            },
+            _ => OwnershipError::Auth(AuthError::internal(e)),
+        }
+    }
+}
+
+impl fmt::Display for OwnershipError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OwnershipError::NotFound {
+                resource,
+                id,
+            } => write!(f, "{resource} {id} not found"),
+            OwnershipError::Auth(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for OwnershipError {}
+
+impl From<AuthError> for OwnershipError {
+    fn from(e: AuthError) -> Self {
+        OwnershipError::Auth(e)
+    }
+}
+
+impl From<Box<dyn Error>> for OwnershipError {
+    fn from(e: Box<dyn Error>) -> Self {
+        OwnershipError::Auth(AuthError::internal(e))
+    }
+}
+
+/// Who may change rows that carry a `user_id`: their owner, and roles granted
+/// `<resource>:moderate` for everyone's. Rows without an owner are left to moderators.
+pub struct OwnershipService {}
+
+impl OwnershipService {
+    pub fn can_moderate(auth: &AuthUser, resource: &str) -> Result<bool, AuthError> {
+        Ok(RbacService::permissions_of(auth)?.allows(resource, MODERATE))
+    }
+
+    /// Lets the caller `action` row `id` of `resource`, owned by `owner_id`. An API key acts for
+    /// its owner only within its scopes.
+    pub fn authorize(
+        auth: &AuthUser,
+        resource: &str,
+        action: &str,
+        id: i32,
+        owner_id: Option<i32>,
+    ) -> Result<(), AuthError> {
+        if owner_id == Some(auth.user_id()) {
+            if let Credential::ApiKey(api_key) = &auth.credential {
+                if !api_key.scopes.allows(resource, action) {
+                    return Err(AuthError::Forbidden(permission_name(resource, action)));
+                }
+            }
+            return Ok(());
+        }
+        if Self::can_moderate(auth, resource)? {
+            return Ok(());
+        }
+        Err(AuthError::NotOwner {
+            resource: resource.to_string(),
+            id,
+        })
+    }
+
+    /// The owner of a row the caller creates or hands over, the caller unless `requested`
+    /// names someone else, which only moderators may.
+    pub fn assign_owner(
+        auth: &AuthUser,
+        resource: &str,
+        requested: Option<i32>,
+    ) -> Result<i32, AuthError> {
+        match requested {
+            Some(user_id) if user_id != auth.user_id() => {
+                if !Self::can_moderate(auth, resource)? {
+                    return Err(AuthError::Forbidden(permission_name(resource, MODERATE)));
+                }
+                Ok(user_id)
+            }
+            _ => Ok(auth.user_id()),
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::BTreeSet;
+
+use crab_rocket_schema::establish_pg_connection;
+
+use crate::error::AuthError;
+use crate::guards::auth_user::{AuthUser, Credential};
+use crate::mappers::rbac_mapper::RbacMapper;
+use crate::services::two_factor_service::session_verified;
+
+/// The `resource:action` names a user may use, granted through `user_table.role_id`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EffectivePermissions(BTreeSet<String>);
+
+impl EffectivePermissions {
+    pub fn new<I: IntoIterator<Item = (String, String)>>(grants: I) -> Self {
+        Self(
+            grants
+                .into_iter()
+                .map(|(resource, action)| permission_name(&resource, &action))
+                .collect(),
+        )
+    }
+
+    pub fn allows(&self, resource: &str, action: &str) -> bool {
+        self.0.contains(&permission_name(resource, action))
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+
+    /// Whether `other` allows everything these do.
+    pub fn is_subset(&self, other: &EffectivePermissions) -> bool {
+        self.0.is_subset(&other.0)
+    }
+
+    /// What both allow, e.g. the role of a user narrowed to the scopes of an API key.
+    pub fn intersection(&self, other: &EffectivePermissions) -> Self {
+        Self(self.0.intersection(&other.0).cloned().collect())
+    }
+}
+
+pub fn permission_name(resource: &str, action: &str) -> String {
+    format!("{resource}:{action}")
+}
+
+pub struct RbacService {}
+
+impl RbacService {
+    pub fn effective_permissions(user_id: i32) -> Result<EffectivePermissions, AuthError> {
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        let grants =
+            RbacMapper::get_permissions_of_user(&mut conn, user_id).map_err(AuthError::internal)?;
+        Ok(EffectivePermissions::new(grants))
+    }
+
+    /// The permissions of the caller, an API key only gets those of its scopes.
+    pub fn permissions_of(auth: &AuthUser) -> Result<EffectivePermissions, AuthError> {
+        Self::require_two_factor(auth)?;
+        let permissions = Self::effective_permissions(auth.user_id())?;
+        Ok(match &auth.credential {
+            Credential::AccessToken(_) => permissions,
+            Credential::ApiKey(api_key) => permissions.intersection(&api_key.scopes),
+        })
+    }
+
+    /// Refuses a session opened without a second factor when the role asks for one. API keys
+    /// pass, creating one already took such a session.
+    pub fn require_two_factor(auth: &AuthUser) -> Result<(), AuthError> {
+        if let Credential::ApiKey(_) = auth.credential {
+            return Ok(());
+        }
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        if !RbacMapper::requires_two_factor(&mut conn, auth.user_id())
+            .map_err(AuthError::internal)?
+            || session_verified(&mut conn, auth).map_err(AuthError::internal)?
+        {
+            return Ok(());
+        }
+        Err(AuthError::TwoFactorRequired)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crab_rocket_schema::schema::permission_table;
+    use crab_rocket_test_support::{fixtures, test_conn};
+    use diesel::prelude::*;
+
+    use super::EffectivePermissions;
+    use crate::mappers::rbac_mapper::RbacMapper;
+
+    #[test]
+    fn test_permissions_follow_the_role() {
+        let mut conn = test_conn();
+        let role_id = fixtures::role(&mut conn, "Auditor");
+        let user_id = fixtures::user(&mut conn, "auditor");
+        fixtures::assign_role(&mut conn, user_id, role_id);
+        fixtures::grant(&mut conn, role_id, "order", "read");
+        let disabled = fixtures::grant(&mut conn, role_id, "order", "delete");
+        diesel::update(permission_table::table.find(disabled))
+            .set(permission_table::is_active.eq(false))
+            .execute(&mut conn)
+            .unwrap();
+
+        let permissions = EffectivePermissions::new(
+            RbacMapper::get_permissions_of_user(&mut conn, user_id).unwrap(),
+        );
+        assert!(permissions.allows("order", "read"));
+        assert!(!permissions.allows("order", "delete"));
+        assert!(!permissions.allows("product", "read"));
+        assert_eq!(permissions.names().collect::<Vec<_>>(), ["order:read"]);
+
+        let outsider = fixtures::user(&mut conn, "outsider");
+        assert!(RbacMapper::get_permissions_of_user(&mut conn, outsider).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,181 @@
+use chrono::Duration;
+use crab_rocket_config::app_config;
+use crab_rocket_schema::establish_pg_connection;
+use crab_rocket_utils::time::get_e8_time;
+use crab_rocket_utils::token::{generate_token, hash_token};
+use diesel::prelude::*;
+use diesel::result::Error;
+use uuid::Uuid;
+
+use crate::error::AuthError;
+use crate::mappers::session_mapper::SessionMapper;
+use crate::models::session::{ClientInfo, Session, SessionInfo};
+
+const REASON_LOGOUT: &str = "logout";
+const REASON_REVOKED: &str = "revoked";
+const REASON_REUSE: &str = "refresh token reuse";
+
+pub struct SessionService {}
+
+impl SessionService {
+    /// Opens a session for `user_id`, returns it with its first refresh token. `two_factor`
+    /// tells whether the login included a second factor.
+    pub fn start(
+        conn: &mut PgConnection,
+        user_id: i32,
+        client: &ClientInfo,
+        device_name: Option<String>,
+        two_factor: bool,
+    ) -> Result<(Session, String), Error> {
+        let now = get_e8_time();
+        let expires_at = now + Duration::seconds(app_config().auth.refresh_token_ttl_secs);
+        let session = SessionMapper::add_single(
+            conn,
+            &Session::new(user_id, client, device_name, now, expires_at, two_factor),
+        )?;
+        let refresh_token = generate_token();
+        SessionMapper::add_refresh_token(
+            conn,
+            session.session_id(),
+            &hash_token(&refresh_token),
+            now,
+        )?;
+        Ok((session, refresh_token))
+    }
+
+    /// Trades a refresh token for the next one. Every token works once, presenting one again
+    /// means it leaked, so the whole session is revoked.
+    pub fn rotate(
+        conn: &mut PgConnection,
+        refresh_token: &str,
+    ) -> Result<(Session, String), AuthError> {
+        let now = get_e8_time();
+        let token_hash = hash_token(refresh_token);
+        let (session, used_at) = match SessionMapper::get_by_refresh_token(conn, &token_hash) {
+            Ok(found) => found,
+            Err(Error::NotFound) => {
+                return Err(AuthError::InvalidToken("unknown refresh token".to_string()))
+            }
+            Err(e) => return Err(AuthError::internal(e)),
+        };
+        if !session.is_active(now) {
+            return Err(AuthError::SessionEnded);
+        }
+        let next = match used_at {
+            Some(_) => None,
+            None => conn
+                .transaction::<_, Error, _>(|conn| {
+                    // Zero rows when a concurrent request used the token first.
+                    if SessionMapper::use_refresh_token(conn, &token_hash, now)? == 0 {
+                        return Ok(None);
+                    }
+                    let next = generate_token();
+                    SessionMapper::add_refresh_token(
+                        conn,
+                        session.session_id(),
+                        &hash_token(&next),
+                        now,
+                    )?;
+                    SessionMapper::touch(conn, session.session_id(), now)?;
+                    Ok(Some(next))
+                })
+                .map_err(AuthError::internal)?,
+        };
+        match next {
+            Some(next) => Ok((session, next)),
+            None => {
+                SessionMapper::revoke(conn, session.session_id(), REASON_REUSE, now)
+                    .map_err(AuthError::internal)?;
+                Err(AuthError::RefreshTokenReused)
+            }
+        }
+    }
+
+    /// Whether `session_id` is an open session of `user_id`.
+    pub fn is_active(
+        conn: &mut PgConnection,
+        user_id: i32,
+        session_id: Uuid,
+    ) -> Result<bool, Error> {
+        match SessionMapper::get_by_id(conn, session_id) {
+            Ok(session) => Ok(session.user_id() == user_id && session.is_active(get_e8_time())),
+            Err(Error::NotFound) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Open sessions of `user_id`, `current` is flagged.
+    pub fn list(user_id: i32, current: Option<Uuid>) -> Result<Vec<SessionInfo>, AuthError> {
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        let sessions = SessionMapper::get_active_by_user(&mut conn, user_id, get_e8_time())
+            .map_err(AuthError::internal)?;
+        Ok(sessions
+            .into_iter()
+            .map(|session| SessionInfo {
+                current: Some(session.session_id()) == current,
+                session,
+            })
+            .collect())
+    }
+
+    pub fn logout(session_id: Uuid) -> Result<(), AuthError> {
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        SessionMapper::revoke(&mut conn, session_id, REASON_LOGOUT, get_e8_time())
+            .map_err(AuthError::internal)?;
+        Ok(())
+    }
+
+    /// Revokes one session of `user_id`, `false` when there is no such open session.
+    pub fn revoke(user_id: i32, session_id: Uuid) -> Result<bool, AuthError> {
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        if !Self::is_active(&mut conn, user_id, session_id).map_err(AuthError::internal)? {
+            return Ok(false);
+        }
+        SessionMapper::revoke(&mut conn, session_id, REASON_REVOKED, get_e8_time())
+            .map_err(AuthError::internal)?;
+        Ok(true)
+    }
+
+    /// Revokes every open session of `user_id` but `keep`, returns how many.
+    pub fn revoke_all(user_id: i32, keep: Option<Uuid>) -> Result<usize, AuthError> {
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        SessionMapper::revoke_all(&mut conn, user_id, keep, REASON_REVOKED, get_e8_time())
+            .map_err(AuthError::internal)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crab_rocket_test_support::{fixtures, test_conn};
+
+    use super::SessionService;
+    use crate::error::AuthError;
+    use crate::models::session::ClientInfo;
+
+    #[test]
+    fn test_refresh_tokens_rotate_and_reuse_revokes() {
+        let mut conn = test_conn();
+        let user_id = fixtures::user(&mut conn, "rotator");
+        let client = ClientInfo {
+            user_agent: Some("curl/8.0".to_string()),
+            ip_address: Some("127.0.0.1".to_string()),
+        };
+        let (session, first) =
+            SessionService::start(&mut conn, user_id, &client, Some("laptop".to_string()), false)
+                .unwrap();
+        assert_eq!(session.user_agent(), Some("curl/8.0"));
+
+        let (_, second) = SessionService::rotate(&mut conn, &first).unwrap();
+        assert_ne!(first, second);
+        let (_, third) = SessionService::rotate(&mut conn, &second).unwrap();
+
+        // The first token shows up again: somebody kept a copy.
+        assert_eq!(SessionService::rotate(&mut conn, &first), Err(AuthError::RefreshTokenReused));
+        assert!(!SessionService::is_active(&mut conn, user_id, session.session_id()).unwrap());
+        assert_eq!(SessionService::rotate(&mut conn, &third), Err(AuthError::SessionEnded));
+        assert!(matches!(
+            SessionService::rotate(&mut conn, "unknown"),
+            Err(AuthError::InvalidToken(_))
+        ));
+    }
+}
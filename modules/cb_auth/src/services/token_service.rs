@@ -0,0 +1,264 @@
+use crab_rocket_config::app_config;
+use crab_rocket_config::app_config::AuthConfig;
+use jsonwebtoken::errors::ErrorKind;
+use jsonwebtoken::{
+    decode, decode_header, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation,
+};
+
+use rocket::serde::de::DeserializeOwned;
+use rocket::serde::Serialize;
+use uuid::Uuid;
+
+use crate::error::AuthError;
+use crate::models::claims::{AccessClaims, ActorClaim, OneTimeClaims, TokenPurpose};
+
+/// Signs an access token for `user_id` with the configured active key.
+pub fn issue_access_token(
+    user_id: i32,
+    session_id: Option<Uuid>,
+) -> Result<(String, AccessClaims), AuthError> {
+    encode_access_token(&app_config().auth, user_id, session_id, chrono::Utc::now().timestamp())
+}
+
+/// Checks signature, issuer and expiry against the configured keys.
+pub fn verify_access_token(token: &str) -> Result<AccessClaims, AuthError> {
+    decode_access_token(&app_config().auth, token)
+}
+
+pub fn encode_access_token(
+    config: &AuthConfig,
+    user_id: i32,
+    session_id: Option<Uuid>,
+    now: i64,
+) -> Result<(String, AccessClaims), AuthError> {
+    let claims = AccessClaims {
+        sub: user_id.to_string(),
+        iss: config.issuer.clone(),
+        iat: now,
+        exp: now + config.access_token_ttl_secs,
+        sid: session_id,
+        act: None,
+    };
+    Ok((sign(config, &claims)?, claims))
+}
+
+/// Signs a token that acts as `user_id` on behalf of `actor`, valid for
+/// `auth.impersonation_ttl_secs` and not refreshable.
+pub fn encode_impersonation_token(
+    config: &AuthConfig,
+    user_id: i32,
+    actor: ActorClaim,
+    now: i64,
+) -> Result<(String, AccessClaims), AuthError> {
+    let claims = AccessClaims {
+        sub: user_id.to_string(),
+        iss: config.issuer.clone(),
+        iat: now,
+        exp: now + config.impersonation_ttl_secs,
+        sid: None,
+        act: Some(actor),
+    };
+    Ok((sign(config, &claims)?, claims))
+}
+
+pub fn decode_access_token(config: &AuthConfig, token: &str) -> Result<AccessClaims, AuthError> {
+    verify(config, token, None)
+}
+
+/// Signs a token for a verification or password reset mail, valid for `ttl_secs`.
+pub fn encode_one_time_token(
+    config: &AuthConfig,
+    purpose: TokenPurpose,
+    user_id: i32,
+    email: Option<String>,
+    now: i64,
+    ttl_secs: i64,
+) -> Result<(String, OneTimeClaims), AuthError> {
+    let claims = OneTimeClaims {
+        sub: user_id.to_string(),
+        aud: purpose.as_str().to_string(),
+        iss: config.issuer.clone(),
+        iat: now,
+        exp: now + ttl_secs,
+        jti: Uuid::new_v4(),
+        email,
+    };
+    Ok((sign(config, &claims)?, claims))
+}
+
+/// Checks a one-time token like an access token, and that it was issued for `purpose`.
+pub fn decode_one_time_token(
+    config: &AuthConfig,
+    purpose: TokenPurpose,
+    token: &str,
+) -> Result<OneTimeClaims, AuthError> {
+    verify(config, token, Some(purpose.as_str()))
+}
+
+fn sign(config: &AuthConfig, claims: &impl Serialize) -> Result<String, AuthError> {
+    let secret = config
+        .signing_keys
+        .get(&config.active_key_id)
+        .ok_or_else(|| AuthError::internal("active signing key is missing"))?;
+    // The key id tells `verify` which key to check, which is what makes keys rotatable
+    // without logging everybody out.
+    let mut header = Header::new(Algorithm::HS256);
+    header.kid = Some(config.active_key_id.clone());
+    encode(&header, claims, &EncodingKey::from_secret(secret.as_bytes()))
+        .map_err(AuthError::internal)
+}
+
+/// Without an `audience`, tokens that name one are rejected, so a mailed token never
+/// passes as an access token.
+fn verify<T: DeserializeOwned>(
+    config: &AuthConfig,
+    token: &str,
+    audience: Option<&str>,
+) -> Result<T, AuthError> {
+    let invalid = |reason: &str| AuthError::InvalidToken(reason.to_string());
+    let header = decode_header(token).map_err(|_| invalid("malformed"))?;
+    let key_id = header.kid.ok_or_else(|| invalid("no key id"))?;
+    let secret = config.signing_keys.get(&key_id).ok_or_else(|| invalid("unknown key id"))?;
+
+    let mut validation = Validation::new(Algorithm::HS256);
+    validation.set_issuer(&[&config.issuer]);
+    match audience {
+        Some(audience) => {
+            validation.set_audience(&[audience]);
+            validation.set_required_spec_claims(&["exp", "iss", "sub", "aud"]);
+        }
+        None => validation.set_required_spec_claims(&["exp", "iss", "sub"]),
+    }
+    let data = decode::<T>(token, &DecodingKey::from_secret(secret.as_bytes()), &validation)
+        .map_err(|e| match e.kind() {
+            ErrorKind::ExpiredSignature => AuthError::ExpiredToken,
+            ErrorKind::InvalidSignature => invalid("bad signature"),
+            ErrorKind::InvalidIssuer => invalid("wrong issuer"),
+            ErrorKind::InvalidAudience => invalid("wrong purpose"),
+            _ => invalid("malformed"),
+        })?;
+    Ok(data.claims)
+}
+
+#[cfg(test)]
+mod test {
+    use crab_rocket_config::app_config::AuthConfig;
+
+    use super::{
+        decode_access_token, decode_one_time_token, encode_access_token,
+        encode_impersonation_token, encode_one_time_token,
+    };
+    use crate::error::AuthError;
+    use crate::models::claims::{ActorClaim, TokenPurpose};
+
+    fn config(active_key_id: &str, keys: &[&str]) -> AuthConfig {
+        AuthConfig {
+            active_key_id: active_key_id.to_string(),
+            signing_keys: keys
+                .iter()
+                .map(|id| (id.to_string(), format!("{id}-secret-that-is-long-enough-for-hs256")))
+                .collect(),
+            ..AuthConfig::default()
+        }
+    }
+
+    fn now() -> i64 {
+        chrono::Utc::now().timestamp()
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let config = config("k1", &["k1"]);
+        let session_id = uuid::Uuid::new_v4();
+        let (token, claims) = encode_access_token(&config, 42, Some(session_id), now()).unwrap();
+        let decoded = decode_access_token(&config, &token).unwrap();
+        assert_eq!(decoded, claims);
+        assert_eq!(decoded.user_id(), Some(42));
+        assert_eq!(decoded.sid, Some(session_id));
+        assert_eq!(decoded.exp - decoded.iat, config.access_token_ttl_secs);
+    }
+
+    #[test]
+    fn test_impersonation_tokens_name_the_actor() {
+        let config = config("k1", &["k1"]);
+        let actor = ActorClaim {
+            sub: String::from("3"),
+            iid: uuid::Uuid::new_v4(),
+        };
+        let (token, _) = encode_impersonation_token(&config, 42, actor.clone(), now()).unwrap();
+        let decoded = decode_access_token(&config, &token).unwrap();
+        assert_eq!(decoded.user_id(), Some(42));
+        assert_eq!(decoded.sid, None);
+        assert_eq!(decoded.act, Some(actor));
+        assert_eq!(decoded.exp - decoded.iat, config.impersonation_ttl_secs);
+
+        let (plain, _) = encode_access_token(&config, 42, None, now()).unwrap();
+        assert_eq!(decode_access_token(&config, &plain).unwrap().act, None);
+    }
+
+    #[test]
+    fn test_key_rotation() {
+        let (old_token, _) = encode_access_token(&config("k1", &["k1"]), 1, None, now()).unwrap();
+
+        // k2 signs new tokens while tokens signed with k1 stay valid until it is removed.
+        let rotated = config("k2", &["k1", "k2"]);
+        let (new_token, _) = encode_access_token(&rotated, 1, None, now()).unwrap();
+        assert!(decode_access_token(&rotated, &old_token).is_ok());
+        assert!(decode_access_token(&rotated, &new_token).is_ok());
+
+        let retired = config("k2", &["k2"]);
+        assert!(decode_access_token(&retired, &new_token).is_ok());
+        assert_eq!(
+            decode_access_token(&retired, &old_token),
+            Err(AuthError::InvalidToken("unknown key id".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_rejected_tokens() {
+        let config = config("k1", &["k1"]);
+        let (expired, _) = encode_access_token(&config, 1, None, now() - 3600).unwrap();
+        assert_eq!(decode_access_token(&config, &expired), Err(AuthError::ExpiredToken));
+
+        let mut forger = config.clone();
+        forger
+            .signing_keys
+            .insert("k1".to_string(), "a-guessed-secret-that-is-long-enough".to_string());
+        let (forged, _) = encode_access_token(&forger, 1, None, now()).unwrap();
+        assert_eq!(
+            decode_access_token(&config, &forged),
+            Err(AuthError::InvalidToken("bad signature".to_string()))
+        );
+        assert!(decode_access_token(&config, "not a token").is_err());
+
+        let (token, _) = encode_access_token(&config, 1, None, now()).unwrap();
+
+        let other_issuer = AuthConfig {
+            issuer: "someone-else".to_string(),
+            ..config.clone()
+        };
+        assert!(decode_access_token(&other_issuer, &token).is_err());
+    }
+
+    #[test]
+    fn test_one_time_tokens_are_bound_to_their_purpose() {
+        let config = config("k1", &["k1"]);
+        let (token, claims) =
+            encode_one_time_token(&config, TokenPurpose::ResetPassword, 7, None, now(), 3600)
+                .unwrap();
+        let decoded = decode_one_time_token(&config, TokenPurpose::ResetPassword, &token).unwrap();
+        assert_eq!(decoded, claims);
+        assert_eq!(decoded.user_id(), Some(7));
+        assert_eq!(
+            decode_one_time_token(&config, TokenPurpose::VerifyEmail, &token),
+            Err(AuthError::InvalidToken("wrong purpose".to_string()))
+        );
+        assert_eq!(
+            decode_access_token(&config, &token),
+            Err(AuthError::InvalidToken("wrong purpose".to_string()))
+        );
+
+        let (access, _) = encode_access_token(&config, 7, None, now()).unwrap();
+        assert!(decode_one_time_token(&config, TokenPurpose::ResetPassword, &access).is_err());
+    }
+}
@@ -0,0 +1,344 @@
+use std::fmt;
+
+use crab_rocket_config::app_config;
+use crab_rocket_schema::establish_pg_connection;
+use crab_rocket_user::models::user::User;
+use crab_rocket_utils::time::get_e8_time;
+use crab_rocket_utils::token::hash_token;
+use diesel::prelude::*;
+use diesel::result::Error;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use totp_rs::{Algorithm, Secret, TOTP};
+
+use crate::error::AuthError;
+use crate::guards::auth_user::{AuthUser, Credential};
+use crate::mappers::rbac_mapper::RbacMapper;
+use crate::mappers::session_mapper::SessionMapper;
+use crate::mappers::two_factor_mapper::TwoFactorMapper;
+use crate::models::two_factor::{RecoveryCodes, TwoFactor, TwoFactorEnrollment, TwoFactorStatus};
+
+/// RFC 6238 defaults, the only settings every authenticator app understands.
+const DIGITS: usize = 6;
+const STEP_SECS: u64 = 30;
+/// 160 bits, the length RFC 4226 recommends for HMAC-SHA1.
+const SECRET_LEN: usize = 20;
+const RECOVERY_CODE_COUNT: usize = 10;
+/// 80 bits each, shown as four groups of five hex digits.
+const RECOVERY_CODE_LEN: usize = 10;
+
+#[derive(Debug)]
+pub enum TwoFactorError {
+    /// `activate` without a pending enrollment.
+    NotEnrolled,
+    NotEnabled,
+    /// Enrolling again needs `disable` first, so a stolen session can not swap the secret.
+    AlreadyEnabled,
+    InvalidCode,
+    /// The role of the user does not allow turning it off.
+    RequiredByRole,
+    /// A second factor is managed from a login session, not with an API key.
+    SessionRequired,
+    Auth(AuthError),
+}
+
+impl TwoFactorError {
+    pub fn status(&self) -> i32 {
+        match self {
+            TwoFactorError::NotEnrolled
+            | TwoFactorError::NotEnabled
+            | TwoFactorError::AlreadyEnabled => 409,
+            TwoFactorError::InvalidCode => 400,
+            TwoFactorError::RequiredByRole | TwoFactorError::SessionRequired => 403,
+            TwoFactorError::Auth(e) => e.status().code as i32,
+        }
+    }
+}
+
+impl fmt::Display for TwoFactorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TwoFactorError::NotEnrolled => {
+                write!(f, "no pending enrollment, start with POST /auth/2fa/enroll")
+            }
+            TwoFactorError::NotEnabled => write!(f, "two-factor authentication is not enabled"),
+            TwoFactorError::AlreadyEnabled => {
+                write!(f, "two-factor authentication is enabled already, disable it first")
+            }
+            TwoFactorError::InvalidCode => write!(f, "invalid two-factor code"),
+            TwoFactorError::RequiredByRole => {
+                write!(f, "your role requires two-factor authentication, it can not be disabled")
+            }
+            TwoFactorError::SessionRequired => write!(
+                f,
+                "two-factor authentication can only be managed after logging in with a password"
+            ),
+            TwoFactorError::Auth(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<AuthError> for TwoFactorError {
+    fn from(e: AuthError) -> Self {
+        TwoFactorError::Auth(e)
+    }
+}
+
+impl From<Error> for TwoFactorError {
+    fn from(e: Error) -> Self {
+        TwoFactorError::Auth(AuthError::internal(e))
+    }
+}
+
+pub struct TwoFactorService {}
+
+impl TwoFactorService {
+    pub fn status(auth: &AuthUser) -> Result<TwoFactorStatus, TwoFactorError> {
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        let two_factor = TwoFactorMapper::get(&mut conn, auth.user_id()).optional()?;
+        Ok(TwoFactorStatus {
+            enabled_at: two_factor.and_then(|two_factor| two_factor.enabled_at()),
+            recovery_codes_left: TwoFactorMapper::count_unused_recovery_codes(
+                &mut conn,
+                auth.user_id(),
+            )?,
+            required: RbacMapper::requires_two_factor(&mut conn, auth.user_id())?,
+            session_verified: session_verified(&mut conn, auth)?,
+        })
+    }
+
+    /// Generates a secret for the caller. It is pending until a code from it is presented to
+    /// [`activate`](Self::activate), enrolling again meanwhile replaces it.
+    pub fn enroll(auth: &AuthUser) -> Result<TwoFactorEnrollment, TwoFactorError> {
+        require_session(auth)?;
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        let mut bytes = vec![0u8; SECRET_LEN];
+        OsRng.fill_bytes(&mut bytes);
+        let totp = totp(bytes, Some(app_config().auth.issuer.clone()), account_name(&auth.user));
+        let secret = totp.get_secret_base32();
+        if TwoFactorMapper::save_pending(
+            &mut conn,
+            &TwoFactor::new(auth.user_id(), secret.clone(), get_e8_time()),
+        )? == 0
+        {
+            return Err(TwoFactorError::AlreadyEnabled);
+        }
+        Ok(TwoFactorEnrollment {
+            secret,
+            otpauth_uri: totp.get_url(),
+        })
+    }
+
+    /// Confirms the pending enrollment with a first code. The calling session counts as
+    /// verified from now on.
+    pub fn activate(auth: &AuthUser, code: &str) -> Result<RecoveryCodes, TwoFactorError> {
+        require_session(auth)?;
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        let two_factor = match TwoFactorMapper::get(&mut conn, auth.user_id()).optional()? {
+            Some(two_factor) if two_factor.is_enabled() => {
+                return Err(TwoFactorError::AlreadyEnabled)
+            }
+            Some(two_factor) => two_factor,
+            None => return Err(TwoFactorError::NotEnrolled),
+        };
+        if !verify_code(&mut conn, &two_factor, code)? {
+            return Err(TwoFactorError::InvalidCode);
+        }
+        let now = get_e8_time();
+        let codes = generate_recovery_codes();
+        conn.transaction::<_, Error, _>(|conn| {
+            TwoFactorMapper::enable(conn, auth.user_id(), now)?;
+            TwoFactorMapper::replace_recovery_codes(
+                conn,
+                auth.user_id(),
+                &hash_recovery_codes(&codes),
+                now,
+            )?;
+            if let Some(session_id) = auth.session_id() {
+                SessionMapper::set_two_factor(conn, session_id)?;
+            }
+            Ok(())
+        })?;
+        Ok(RecoveryCodes {
+            recovery_codes: codes,
+        })
+    }
+
+    /// Removes the secret and the recovery codes, confirmed with a current code.
+    pub fn disable(auth: &AuthUser, code: &str) -> Result<(), TwoFactorError> {
+        require_session(auth)?;
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        let two_factor = enabled(&mut conn, auth.user_id())?;
+        if RbacMapper::requires_two_factor(&mut conn, auth.user_id())? {
+            return Err(TwoFactorError::RequiredByRole);
+        }
+        if !verify_code(&mut conn, &two_factor, code)? {
+            return Err(TwoFactorError::InvalidCode);
+        }
+        TwoFactorMapper::delete(&mut conn, auth.user_id())?;
+        Ok(())
+    }
+
+    /// Replaces every recovery code, used or not, confirmed with a current code.
+    pub fn regenerate_recovery_codes(
+        auth: &AuthUser,
+        code: &str,
+    ) -> Result<RecoveryCodes, TwoFactorError> {
+        require_session(auth)?;
+        let mut conn = establish_pg_connection().map_err(AuthError::internal)?;
+        let two_factor = enabled(&mut conn, auth.user_id())?;
+        if !verify_code(&mut conn, &two_factor, code)? {
+            return Err(TwoFactorError::InvalidCode);
+        }
+        let codes = generate_recovery_codes();
+        TwoFactorMapper::replace_recovery_codes(
+            &mut conn,
+            auth.user_id(),
+            &hash_recovery_codes(&codes),
+            get_e8_time(),
+        )?;
+        Ok(RecoveryCodes {
+            recovery_codes: codes,
+        })
+    }
+
+    /// The second step of a login whose password was right. `Ok(false)` when the user has no
+    /// second factor enabled, `Ok(true)` when `code` proved it.
+    pub fn check_login(
+        conn: &mut PgConnection,
+        user_id: i32,
+        code: Option<&str>,
+    ) -> Result<bool, AuthError> {
+        let two_factor =
+            match TwoFactorMapper::get(conn, user_id).optional().map_err(AuthError::internal)? {
+                Some(two_factor) if two_factor.is_enabled() => two_factor,
+                _ => return Ok(false),
+            };
+        let code = code.filter(|code| !code.trim().is_empty());
+        let code = code.ok_or(AuthError::TwoFactorCodeMissing)?;
+        match verify_code(conn, &two_factor, code).map_err(AuthError::internal)? {
+            true => Ok(true),
+            false => Err(AuthError::InvalidTwoFactorCode),
+        }
+    }
+}
+
+fn require_session(auth: &AuthUser) -> Result<(), TwoFactorError> {
+    match auth.credential {
+        Credential::AccessToken(_) => Ok(()),
+        Credential::ApiKey(_) => Err(TwoFactorError::SessionRequired),
+    }
+}
+
+fn enabled(conn: &mut PgConnection, user_id: i32) -> Result<TwoFactor, TwoFactorError> {
+    match TwoFactorMapper::get(conn, user_id).optional()? {
+        Some(two_factor) if two_factor.is_enabled() => Ok(two_factor),
+        _ => Err(TwoFactorError::NotEnabled),
+    }
+}
+
+/// Whether the session of the caller was opened, or later confirmed, with a second factor.
+pub fn session_verified(conn: &mut PgConnection, auth: &AuthUser) -> Result<bool, Error> {
+    let Some(session_id) = auth.session_id() else {
+        return Ok(false);
+    };
+    Ok(SessionMapper::get_by_id(conn, session_id)
+        .optional()?
+        .is_some_and(|session| session.two_factor()))
+}
+
+/// Checks a TOTP code, or else a recovery code, and uses it up.
+fn verify_code(conn: &mut PgConnection, two_factor: &TwoFactor, code: &str) -> Result<bool, Error> {
+    let code = normalize_code(code);
+    if code.len() == DIGITS && code.bytes().all(|b| b.is_ascii_digit()) {
+        let now = chrono::Utc::now().timestamp() as u64;
+        let Some(step) = matching_step(two_factor.secret(), &code, now) else {
+            return Ok(false);
+        };
+        // Refused when this step or a later one was accepted before, a code works once.
+        return Ok(TwoFactorMapper::use_step(conn, two_factor.user_id(), step as i64)? == 1);
+    }
+    Ok(TwoFactorMapper::use_recovery_code(
+        conn,
+        two_factor.user_id(),
+        &hash_token(&code),
+        get_e8_time(),
+    )? == 1)
+}
+
+/// The time step `code` belongs to, allowing one step of clock drift either way.
+pub fn matching_step(secret: &str, code: &str, unix_time: u64) -> Option<u64> {
+    let bytes = Secret::Encoded(secret.to_string()).to_bytes().ok()?;
+    let totp = totp(bytes, None, String::new());
+    let current = unix_time / STEP_SECS;
+    (current.saturating_sub(1)..=current + 1).find(|step| totp.check(code, step * STEP_SECS))
+}
+
+/// Exactly one step per check, [`matching_step`] tries the neighbours itself to learn
+/// which one matched.
+fn totp(secret: Vec<u8>, issuer: Option<String>, account_name: String) -> TOTP {
+    TOTP::new_unchecked(Algorithm::SHA1, DIGITS, 0, STEP_SECS, secret, issuer, account_name)
+}
+
+/// The label in the authenticator app, which may not contain `:`.
+fn account_name(user: &User) -> String {
+    user.email().as_deref().unwrap_or(user.username()).replace(':', "")
+}
+
+/// Lowercase without spaces and dashes, however the code was typed.
+fn normalize_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .collect::<String>()
+        .to_ascii_lowercase()
+}
+
+fn generate_recovery_codes() -> Vec<String> {
+    (0..RECOVERY_CODE_COUNT)
+        .map(|_| {
+            let mut bytes = [0u8; RECOVERY_CODE_LEN];
+            OsRng.fill_bytes(&mut bytes);
+            let hex: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+            hex.as_bytes()
+                .chunks(5)
+                .map(|chunk| std::str::from_utf8(chunk).unwrap_or_default())
+                .collect::<Vec<_>>()
+                .join("-")
+        })
+        .collect()
+}
+
+fn hash_recovery_codes(codes: &[String]) -> Vec<String> {
+    codes.iter().map(|code| hash_token(&normalize_code(code))).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{generate_recovery_codes, matching_step, normalize_code};
+
+    /// The SHA1 secret of RFC 6238 appendix B, `12345678901234567890` in base32.
+    const RFC_SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+    #[test]
+    fn test_rfc_6238_codes() {
+        // The eight digit values of the RFC, cut to the six digits apps show.
+        assert_eq!(matching_step(RFC_SECRET, "287082", 59), Some(1));
+        assert_eq!(matching_step(RFC_SECRET, "081804", 1111111109), Some(37037036));
+        assert_eq!(matching_step(RFC_SECRET, "279037", 2000000000), Some(66666666));
+        // One step of drift is tolerated, two are not.
+        assert_eq!(matching_step(RFC_SECRET, "287082", 89), Some(1));
+        assert_eq!(matching_step(RFC_SECRET, "287082", 119), None);
+        assert_eq!(matching_step(RFC_SECRET, "000000", 59), None);
+        assert_eq!(matching_step("not base32!", "287082", 59), None);
+    }
+
+    #[test]
+    fn test_recovery_codes() {
+        let codes = generate_recovery_codes();
+        assert_eq!(codes.len(), 10);
+        assert_eq!(codes[0].len(), 23);
+        assert_ne!(codes[0], codes[1]);
+        assert_eq!(normalize_code(&codes[0].to_uppercase()), codes[0].replace('-', ""));
+        assert_eq!(normalize_code(" 123 456 "), "123456");
+    }
+}
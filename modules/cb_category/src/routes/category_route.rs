@@ -1,3 +1,5 @@
+use crab_rocket_auth::guards::authorized::Authorized;
+use crab_rocket_auth::permission;
 use obj_traits::controller::controller_crud::ControllerCRUD;
 use obj_traits::request::pagination_request_param::{PaginationParam, PaginationParamTrait};
 use obj_traits::request::request_param::RequestParam;
@@ -7,14 +9,12 @@ use crate::controllers::category_controller::CategoryController;
 use crate::models::category::{PatchCategory, PostCategory};
 use crate::models::category_filter::CategoryFilter;
 
+permission!(pub CategoryCreate, "category", "create");
+permission!(pub CategoryUpdate, "category", "update");
+permission!(pub CategoryDelete, "category", "delete");
+
 #[get("/category?<limit>&<offset>")]
-pub fn get_categorys(mut limit: Option<i32>, mut offset: Option<i32>) -> Json<serde_json::Value> {
-    if limit.is_none() {
-        limit = Some(10);
-    };
-    if offset.is_none() {
-        offset = Some(0);
-    };
+pub fn get_categorys(limit: Option<i32>, offset: Option<i32>) -> Json<serde_json::Value> {
     let params = RequestParam::new(PaginationParam::new(limit, offset), None);
     println!("{:?}", params);
     crab_rocket_schema::update_reload::update_reload_count();
@@ -43,7 +43,10 @@ pub fn get_category_by_id(id: i32) -> Json<serde_json::Value> {
 }
 
 #[post("/category", data = "<category>")]
-pub fn insert_single_category(category: Json<PostCategory>) -> Json<serde_json::Value> {
+pub fn insert_single_category(
+    _auth: Authorized<CategoryCreate>,
+    category: Json<PostCategory>,
+) -> Json<serde_json::Value> {
     let mut obj: PostCategory = category.into_inner();
 
     let resp = CategoryController::add_single(&mut obj).unwrap();
@@ -52,14 +55,21 @@ pub fn insert_single_category(category: Json<PostCategory>) -> Json<serde_json::
 }
 
 #[delete("/category/<id>")]
-pub fn delete_category_by_id(id: i32) -> Json<serde_json::Value> {
+pub fn delete_category_by_id(
+    _auth: Authorized<CategoryDelete>,
+    id: i32,
+) -> Json<serde_json::Value> {
     let resp = CategoryController::delete_by_id(id).unwrap();
     let json_value = serde_json::to_value(&resp).unwrap();
     Json(serde_json::from_value(json_value).unwrap())
 }
 
 #[patch("/category/<id>", data = "<task>")]
-pub fn update_category_by_id(id: i32, task: Json<PatchCategory>) -> Json<serde_json::Value> {
+pub fn update_category_by_id(
+    _auth: Authorized<CategoryUpdate>,
+    id: i32,
+    task: Json<PatchCategory>,
+) -> Json<serde_json::Value> {
     let resp = CategoryController::update_by_id(id, &task).unwrap();
     let json_value = serde_json::to_value(&resp).unwrap();
     Json(serde_json::from_value(json_value).unwrap())
@@ -0,0 +1,525 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use rocket::data::{ByteUnit, ToByteUnit};
+use rocket::figment::Figment;
+use rocket::http::uri::Absolute;
+use rocket::serde::{Deserialize, Serialize};
+
+use crate::error::ConfigError;
+
+/// Everything the application reads from `Rocket.toml` besides Rocket's own settings.
+///
+/// ```toml
+/// [default]
+/// public_base_url = "http://localhost:8000"
+///
+/// [default.pagination]
+/// default_limit = 10
+/// max_limit = 100
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde", default)]
+pub struct AppConfig {
+    /// Externally reachable address of the server, used to build absolute links.
+    pub public_base_url: String,
+    pub database: DatabaseConfig,
+    pub cors: CorsConfig,
+    pub storage: StorageConfig,
+    pub pagination: PaginationConfig,
+    pub upload: UploadConfig,
+    pub password: PasswordConfig,
+    pub auth: AuthConfig,
+    pub lockout: LockoutConfig,
+    pub oidc: OidcConfig,
+    pub mail: MailConfig,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(crate = "rocket::serde", default)]
+pub struct DatabaseConfig {
+    /// Apply pending embedded migrations before serving. When off, the server refuses to
+    /// start until `crab_rocket migrate run` brought the schema up to date.
+    pub run_migrations_on_launch: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde", default)]
+pub struct CorsConfig {
+    /// Exact origins, `"*"` allows every origin.
+    pub allowed_origins: Vec<String>,
+    pub allowed_methods: Vec<String>,
+    pub allowed_headers: Vec<String>,
+    pub allow_credentials: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde", default)]
+pub struct StorageConfig {
+    /// Where uploaded files are written, relative paths are resolved from the working directory.
+    pub upload_dir: PathBuf,
+    /// Served by `/api/static_file/<file..>`.
+    pub static_dir: PathBuf,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde", default)]
+pub struct PaginationConfig {
+    /// Page size used when a request does not give a `limit`.
+    pub default_limit: i32,
+    /// Upper bound for any requested `limit`.
+    pub max_limit: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde", default)]
+pub struct UploadConfig {
+    /// Size limit for a single uploaded file, e.g. `"128 MiB"`.
+    pub max_file_size: ByteUnit,
+    /// How many files a single multipart upload may carry.
+    pub max_files: usize,
+}
+
+/// Argon2id cost parameters. Raising them takes effect for existing users on their next
+/// successful login, when the stored hash is rehashed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde", default)]
+pub struct PasswordConfig {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+    pub min_length: usize,
+}
+
+/// Access token signing. Every key in `signing_keys` is accepted, new tokens are signed
+/// with `active_key_id`. To rotate, add a key, make it active and drop the old one once
+/// the tokens it signed have expired.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde", default)]
+pub struct AuthConfig {
+    /// The `iss` claim, tokens from another issuer are rejected.
+    pub issuer: String,
+    pub access_token_ttl_secs: i64,
+    /// Lifetime of a login session, refreshing does not extend it.
+    pub refresh_token_ttl_secs: i64,
+    pub active_key_id: String,
+    /// HMAC-SHA256 secrets by key id, at least 32 bytes each.
+    pub signing_keys: BTreeMap<String, String>,
+    /// How long a link from an email verification mail works.
+    pub email_verification_ttl_secs: i64,
+    /// How long a link from a password reset mail works.
+    pub password_reset_ttl_secs: i64,
+    /// How long a token from `POST /auth/impersonate/<user_id>` works, it can not be refreshed.
+    pub impersonation_ttl_secs: i64,
+}
+
+/// Failed login throttling. Every failure makes the account and the client address wait
+/// `base_delay_secs`, doubled per further failure, before the next attempt; reaching a
+/// maximum locks them for `lockout_secs`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde", default)]
+pub struct LockoutConfig {
+    pub max_failures_per_account: i32,
+    /// Higher than per account, many users may share an address.
+    pub max_failures_per_ip: i32,
+    /// Failures further apart than this start counting from one again.
+    pub failure_window_secs: i64,
+    /// `0` turns the delays off and only locks at the maximum.
+    pub base_delay_secs: i64,
+    pub lockout_secs: i64,
+}
+
+/// Login through OpenID Connect identity providers, by name as used in
+/// `/api/auth/oidc/<provider>/...`. None are configured by default.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde", default)]
+pub struct OidcConfig {
+    /// How long a user may take at the provider between `authorize` and `callback`.
+    pub login_ttl_secs: i64,
+    pub providers: BTreeMap<String, OidcProviderConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde", default)]
+pub struct OidcProviderConfig {
+    /// Shown on login pages, the provider name when empty.
+    pub display_name: String,
+    /// Its discovery document is read from `<issuer>/.well-known/openid-configuration`.
+    pub issuer: String,
+    pub client_id: String,
+    pub client_secret: String,
+    /// Where the provider sends the user back to, `/api/auth/oidc/<provider>/callback` under
+    /// `public_base_url` when not set.
+    pub redirect_uri: Option<String>,
+    /// Must contain `openid`, `email` is needed to link existing users.
+    pub scopes: Vec<String>,
+    /// Create a user for a verified email nobody has yet, instead of refusing the login.
+    pub auto_provision: bool,
+    /// The `role_name` of users created by `auto_provision`.
+    pub default_role: Option<String>,
+}
+
+/// Outgoing mail. `smtp` talks plain SMTP, meant for a local catcher such as Mailpit;
+/// `memory` keeps mails in the process for tests.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde", default)]
+pub struct MailConfig {
+    pub transport: MailTransport,
+    /// The `From` header, e.g. `"Crab Rocket <no-reply@localhost>"`.
+    pub from: String,
+    pub smtp_host: String,
+    pub smtp_port: u16,
+    pub smtp_username: Option<String>,
+    pub smtp_password: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(crate = "rocket::serde", rename_all = "lowercase")]
+pub enum MailTransport {
+    Smtp,
+    Memory,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            public_base_url: String::from("http://localhost:8000"),
+            database: DatabaseConfig::default(),
+            cors: CorsConfig::default(),
+            storage: StorageConfig::default(),
+            pagination: PaginationConfig::default(),
+            upload: UploadConfig::default(),
+            password: PasswordConfig::default(),
+            auth: AuthConfig::default(),
+            lockout: LockoutConfig::default(),
+            oidc: OidcConfig::default(),
+            mail: MailConfig::default(),
+        }
+    }
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            allowed_origins: vec![String::from("*")],
+            allowed_methods: ["GET", "POST", "PATCH", "PUT", "DELETE"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            allowed_headers: [
+                "Content-Type",
+                "Authorization",
+                "Accept",
+                "User-Agent",
+                "X-Requested-With",
+                "Referer",
+            ]
+            .into_iter()
+            .map(String::from)
+            .collect(),
+            allow_credentials: true,
+        }
+    }
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            upload_dir: PathBuf::from("upload"),
+            static_dir: PathBuf::from("static"),
+        }
+    }
+}
+
+impl Default for PaginationConfig {
+    fn default() -> Self {
+        Self {
+            default_limit: 10,
+            max_limit: 100,
+        }
+    }
+}
+
+impl Default for UploadConfig {
+    fn default() -> Self {
+        Self {
+            max_file_size: 128.mebibytes(),
+            max_files: 10,
+        }
+    }
+}
+
+impl Default for PasswordConfig {
+    /// The OWASP recommendation for argon2id.
+    fn default() -> Self {
+        Self {
+            memory_kib: 19 * 1024,
+            iterations: 2,
+            parallelism: 1,
+            min_length: 8,
+        }
+    }
+}
+
+impl Default for AuthConfig {
+    /// No signing keys, they have to come from `Rocket.toml` or the environment.
+    fn default() -> Self {
+        Self {
+            issuer: String::from("crab_rocket"),
+            access_token_ttl_secs: 15 * 60,
+            refresh_token_ttl_secs: 30 * 24 * 60 * 60,
+            active_key_id: String::new(),
+            signing_keys: BTreeMap::new(),
+            email_verification_ttl_secs: 2 * 24 * 60 * 60,
+            password_reset_ttl_secs: 60 * 60,
+            impersonation_ttl_secs: 30 * 60,
+        }
+    }
+}
+
+impl Default for LockoutConfig {
+    fn default() -> Self {
+        Self {
+            max_failures_per_account: 5,
+            max_failures_per_ip: 20,
+            failure_window_secs: 15 * 60,
+            base_delay_secs: 1,
+            lockout_secs: 15 * 60,
+        }
+    }
+}
+
+impl Default for OidcConfig {
+    fn default() -> Self {
+        Self {
+            login_ttl_secs: 10 * 60,
+            providers: BTreeMap::new(),
+        }
+    }
+}
+
+impl Default for OidcProviderConfig {
+    fn default() -> Self {
+        Self {
+            display_name: String::new(),
+            issuer: String::new(),
+            client_id: String::new(),
+            client_secret: String::new(),
+            redirect_uri: None,
+            scopes: ["openid", "email", "profile"].into_iter().map(String::from).collect(),
+            auto_provision: false,
+            default_role: None,
+        }
+    }
+}
+
+impl Default for MailConfig {
+    fn default() -> Self {
+        Self {
+            transport: MailTransport::Smtp,
+            from: String::from("Crab Rocket <no-reply@localhost>"),
+            smtp_host: String::from("localhost"),
+            smtp_port: 1025,
+            smtp_username: None,
+            smtp_password: None,
+        }
+    }
+}
+
+impl AppConfig {
+    /// Extracts the config for the figment's selected profile and validates it.
+    pub fn from_figment(figment: &Figment) -> Result<Self, ConfigError> {
+        let config: AppConfig = figment.extract()?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        match Absolute::parse(&self.public_base_url) {
+            Ok(uri) if uri.scheme() == "http" || uri.scheme() == "https" => {}
+            Ok(_) => return Err(ConfigError::invalid("public_base_url", "must be http or https")),
+            Err(e) => return Err(ConfigError::invalid("public_base_url", e.to_string())),
+        }
+        crate::cors::build_cors(&self.cors)?;
+        if self.storage.upload_dir.as_os_str().is_empty() {
+            return Err(ConfigError::invalid("storage.upload_dir", "must not be empty"));
+        }
+        if self.pagination.default_limit < 1 {
+            return Err(ConfigError::invalid("pagination.default_limit", "must be at least 1"));
+        }
+        if self.pagination.max_limit < self.pagination.default_limit {
+            return Err(ConfigError::invalid(
+                "pagination.max_limit",
+                "must not be smaller than pagination.default_limit",
+            ));
+        }
+        if self.upload.max_file_size == ByteUnit::Byte(0) {
+            return Err(ConfigError::invalid("upload.max_file_size", "must be greater than 0"));
+        }
+        if self.upload.max_files < 1 {
+            return Err(ConfigError::invalid("upload.max_files", "must be at least 1"));
+        }
+        // Lower bounds of the argon2 crate, memory must cover 8 KiB per lane.
+        if self.password.iterations < 1 {
+            return Err(ConfigError::invalid("password.iterations", "must be at least 1"));
+        }
+        if self.password.parallelism < 1 {
+            return Err(ConfigError::invalid("password.parallelism", "must be at least 1"));
+        }
+        if self.password.memory_kib < 8 * self.password.parallelism {
+            return Err(ConfigError::invalid(
+                "password.memory_kib",
+                "must be at least 8 times password.parallelism",
+            ));
+        }
+        if self.auth.access_token_ttl_secs < 1 {
+            return Err(ConfigError::invalid("auth.access_token_ttl_secs", "must be at least 1"));
+        }
+        if self.auth.refresh_token_ttl_secs < self.auth.access_token_ttl_secs {
+            return Err(ConfigError::invalid(
+                "auth.refresh_token_ttl_secs",
+                "must not be shorter than auth.access_token_ttl_secs",
+            ));
+        }
+        if !self.auth.signing_keys.contains_key(&self.auth.active_key_id) {
+            return Err(ConfigError::invalid(
+                "auth.active_key_id",
+                format!("no signing key `{}` in auth.signing_keys", self.auth.active_key_id),
+            ));
+        }
+        if let Some((key_id, _)) = self.auth.signing_keys.iter().find(|(_, key)| key.len() < 32) {
+            return Err(ConfigError::invalid(
+                "auth.signing_keys",
+                format!("key `{key_id}` is shorter than 32 bytes"),
+            ));
+        }
+        if self.auth.email_verification_ttl_secs < 1 {
+            return Err(ConfigError::invalid(
+                "auth.email_verification_ttl_secs",
+                "must be at least 1",
+            ));
+        }
+        if self.auth.password_reset_ttl_secs < 1 {
+            return Err(ConfigError::invalid("auth.password_reset_ttl_secs", "must be at least 1"));
+        }
+        if self.auth.impersonation_ttl_secs < 1 {
+            return Err(ConfigError::invalid("auth.impersonation_ttl_secs", "must be at least 1"));
+        }
+        if self.lockout.max_failures_per_account < 1 {
+            return Err(ConfigError::invalid(
+                "lockout.max_failures_per_account",
+                "must be at least 1",
+            ));
+        }
+        if self.lockout.max_failures_per_ip < 1 {
+            return Err(ConfigError::invalid("lockout.max_failures_per_ip", "must be at least 1"));
+        }
+        if self.lockout.failure_window_secs < 1 {
+            return Err(ConfigError::invalid("lockout.failure_window_secs", "must be at least 1"));
+        }
+        if self.lockout.base_delay_secs < 0 {
+            return Err(ConfigError::invalid("lockout.base_delay_secs", "must not be negative"));
+        }
+        if self.lockout.lockout_secs < self.lockout.base_delay_secs.max(1) {
+            return Err(ConfigError::invalid(
+                "lockout.lockout_secs",
+                "must be at least 1 and not shorter than lockout.base_delay_secs",
+            ));
+        }
+        if self.oidc.login_ttl_secs < 1 {
+            return Err(ConfigError::invalid("oidc.login_ttl_secs", "must be at least 1"));
+        }
+        for (name, provider) in &self.oidc.providers {
+            let invalid = |reason: &str| {
+                ConfigError::invalid("oidc.providers", format!("provider `{name}`: {reason}"))
+            };
+            if !name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_') {
+                return Err(invalid("names may only contain lowercase letters, digits and `_`"));
+            }
+            let urls = std::iter::once(&provider.issuer).chain(provider.redirect_uri.as_ref());
+            for url in urls {
+                match Absolute::parse(url) {
+                    Ok(uri) if uri.scheme() == "http" || uri.scheme() == "https" => {}
+                    _ => return Err(invalid(&format!("`{url}` is not an http or https URL"))),
+                }
+            }
+            if provider.client_id.is_empty() {
+                return Err(invalid("client_id must not be empty"));
+            }
+            if !provider.scopes.iter().any(|scope| scope == "openid") {
+                return Err(invalid("scopes must contain `openid`"));
+            }
+            if provider.auto_provision && provider.default_role.is_none() {
+                return Err(invalid("default_role is required with auto_provision"));
+            }
+        }
+        if !self.mail.from.contains('@') {
+            return Err(ConfigError::invalid("mail.from", "must contain an email address"));
+        }
+        Ok(())
+    }
+
+    /// Absolute URL for a path served by this instance, e.g. `/api/retrieve_bin/abc`.
+    pub fn public_url(&self, path: &str) -> String {
+        format!("{}/{}", self.public_base_url.trim_end_matches('/'), path.trim_start_matches('/'))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rocket::figment::providers::Serialized;
+    use rocket::figment::Figment;
+
+    use super::AppConfig;
+
+    fn config_with(key: &str, value: impl rocket::serde::Serialize) -> Figment {
+        Figment::from(Serialized::defaults(AppConfig::default()))
+            .merge(("auth.active_key_id", "test"))
+            .merge(("auth.signing_keys.test", "0123456789abcdef0123456789abcdef"))
+            .merge((key, value))
+    }
+
+    #[test]
+    fn test_defaults_are_valid() {
+        let config = AppConfig::from_figment(&config_with("pagination.default_limit", 10)).unwrap();
+        assert_eq!(config.pagination.max_limit, 100);
+        assert_eq!(config.public_url("/api/info"), "http://localhost:8000/api/info");
+    }
+
+    #[test]
+    fn test_invalid_values_are_rejected() {
+        assert!(AppConfig::from_figment(&config_with("public_base_url", "localhost")).is_err());
+        assert!(AppConfig::from_figment(&config_with("pagination.max_limit", 5)).is_err());
+        assert!(AppConfig::from_figment(&config_with("upload.max_files", 0)).is_err());
+        assert!(AppConfig::from_figment(&config_with("password.memory_kib", 4)).is_err());
+        assert!(AppConfig::from_figment(&config_with("auth.active_key_id", "missing")).is_err());
+        assert!(AppConfig::from_figment(&config_with("auth.refresh_token_ttl_secs", 60)).is_err());
+        assert!(AppConfig::from_figment(&config_with("auth.signing_keys.test", "short")).is_err());
+        assert!(AppConfig::from_figment(&config_with("auth.password_reset_ttl_secs", 0)).is_err());
+        assert!(AppConfig::from_figment(&config_with("auth.impersonation_ttl_secs", 0)).is_err());
+        assert!(AppConfig::from_figment(&config_with("lockout.max_failures_per_ip", 0)).is_err());
+        assert!(AppConfig::from_figment(&config_with("lockout.base_delay_secs", -1)).is_err());
+        assert!(AppConfig::from_figment(&config_with("lockout.lockout_secs", 0)).is_err());
+        assert!(AppConfig::from_figment(&config_with("oidc.login_ttl_secs", 0)).is_err());
+        let provider = |key: &str, value: &str| {
+            config_with("oidc.providers.sso.issuer", "https://sso.example.com")
+                .merge(("oidc.providers.sso.client_id", "crab_rocket"))
+                .merge((key, value))
+        };
+        assert!(
+            AppConfig::from_figment(&provider("oidc.providers.sso.display_name", "SSO")).is_ok()
+        );
+        assert!(AppConfig::from_figment(&provider("oidc.providers.sso.issuer", "sso")).is_err());
+        assert!(AppConfig::from_figment(&provider("oidc.providers.sso.client_id", "")).is_err());
+        let upper_case = config_with("oidc.login_ttl_secs", 600)
+            .merge(("oidc.providers.SSO.issuer", "https://sso.example.com"))
+            .merge(("oidc.providers.SSO.client_id", "crab_rocket"));
+        let e = AppConfig::from_figment(&upper_case).unwrap_err();
+        assert!(e.to_string().contains("lowercase"), "{e}");
+        assert!(AppConfig::from_figment(&provider("oidc.providers.sso.auto_provision", "true"))
+            .is_err());
+        assert!(AppConfig::from_figment(&config_with("mail.transport", "pigeon")).is_err());
+        assert!(AppConfig::from_figment(&config_with("cors.allowed_methods", ["FETCH"])).is_err());
+        assert!(AppConfig::from_figment(&config_with("upload.max_file_size", "lots")).is_err());
+    }
+}
@@ -0,0 +1,38 @@
+use rocket_cors::{AllowedHeaders, AllowedOrigins, Cors, CorsOptions, Method};
+
+use crate::app_config::CorsConfig;
+use crate::error::ConfigError;
+
+/// Builds the CORS fairing shared by the main server and every module binary.
+pub fn build_cors(config: &CorsConfig) -> Result<Cors, ConfigError> {
+    let allowed_origins = if config.allowed_origins.iter().any(|origin| origin == "*") {
+        AllowedOrigins::All
+    } else {
+        AllowedOrigins::some_exact(&config.allowed_origins)
+    };
+    let allowed_methods = config
+        .allowed_methods
+        .iter()
+        .map(|method| {
+            method.to_uppercase().parse::<Method>().map_err(|_| {
+                ConfigError::invalid("cors.allowed_methods", format!("unknown method `{method}`"))
+            })
+        })
+        .collect::<Result<_, _>>()?;
+    let allowed_headers: Vec<&str> = config.allowed_headers.iter().map(String::as_str).collect();
+
+    CorsOptions {
+        allowed_origins,
+        allowed_methods,
+        allowed_headers: AllowedHeaders::some(&allowed_headers),
+        allow_credentials: config.allow_credentials,
+        ..Default::default()
+    }
+    .to_cors()
+    .map_err(|e| ConfigError::invalid("cors", e.to_string()))
+}
+
+/// CORS fairing for the loaded [`crate::app_config`], already validated at startup.
+pub fn cors() -> Cors {
+    build_cors(&crate::app_config().cors).expect("CORS configuration was validated at startup")
+}
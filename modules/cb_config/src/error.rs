@@ -0,0 +1,41 @@
+use std::fmt;
+
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The sources could not be read or a value has the wrong type.
+    Extract(Box<rocket::figment::Error>),
+    /// A value was read but is not acceptable.
+    Invalid {
+        key: &'static str,
+        reason: String,
+    },
+}
+
+impl ConfigError {
+    pub fn invalid(key: &'static str, reason: impl Into<String>) -> Self {
+        ConfigError::Invalid {
+            key,
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Extract(e) => write!(f, "{e}"),
+            ConfigError::Invalid {
+                key,
+                reason,
+            } => write!(f, "`{key}`: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl From<rocket::figment::Error> for ConfigError {
+    fn from(e: rocket::figment::Error) -> Self {
+        ConfigError::Extract(Box::new(e))
+    }
+}
@@ -0,0 +1,35 @@
+use rocket::data::ByteUnit;
+use rocket::figment::providers::{Env, Serialized};
+use rocket::figment::{Figment, Profile};
+
+use crate::app_config::AppConfig;
+
+pub const DEV_PROFILE: &str = "dev";
+pub const TEST_PROFILE: &str = "test";
+pub const PROD_PROFILE: &str = "prod";
+
+/// `dev` for debug builds and `prod` for release builds, `ROCKET_PROFILE` wins over both.
+pub fn default_profile() -> Profile {
+    if cfg!(debug_assertions) {
+        Profile::const_new(DEV_PROFILE)
+    } else {
+        Profile::const_new(PROD_PROFILE)
+    }
+}
+
+/// Rocket's own sources (`Rocket.toml`, `ROCKET_*`) plus `CRAB_ROCKET_*` overrides, where
+/// `__` separates nested keys, e.g. `CRAB_ROCKET_PAGINATION__MAX_LIMIT=50`.
+pub fn figment() -> Figment {
+    rocket::Config::figment()
+        .merge(Env::prefixed("CRAB_ROCKET_").split("__").global())
+        .select(Profile::from_env_or("ROCKET_PROFILE", default_profile()))
+}
+
+/// Rocket's body limits follow the upload settings so they cannot drift apart.
+pub fn with_derived_limits(figment: Figment, config: &AppConfig) -> Figment {
+    let max_file_size = config.upload.max_file_size;
+    let max_form_size = max_file_size * config.upload.max_files as u64 + ByteUnit::Mebibyte(1);
+    figment
+        .merge(Serialized::global("limits.file", max_file_size))
+        .merge(Serialized::global("limits.data-form", max_form_size))
+}
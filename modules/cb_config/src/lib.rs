@@ -0,0 +1,58 @@
+use std::sync::OnceLock;
+
+use colored::Colorize;
+use rocket::figment::Figment;
+
+pub mod app_config;
+pub mod cors;
+pub mod error;
+pub mod figment;
+
+pub use app_config::AppConfig;
+pub use error::ConfigError;
+
+static APP_CONFIG: OnceLock<AppConfig> = OnceLock::new();
+
+/// Loads and validates the configuration for the selected profile and makes it available
+/// through [`app_config`]. Returns the figment so it can be handed to `rocket::custom`.
+///
+/// Invalid configuration is reported and the process exits, nothing is served with a
+/// half-valid config.
+pub fn init() -> Figment {
+    let figment = figment::figment();
+    match AppConfig::from_figment(&figment) {
+        Ok(config) => {
+            let figment = figment::with_derived_limits(figment, &config);
+            let _ = APP_CONFIG.set(config);
+            figment
+        }
+        Err(e) => {
+            eprintln!("{} {}", "Invalid configuration:".red(), e.to_string().red());
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Makes [`app_config`] use `profile` rather than the default one, unless the configuration
+/// was loaded already. Test harnesses call it to get the `test` profile outside of Rocket.
+///
+/// # Panics
+/// When the configuration is invalid.
+pub fn init_profile(profile: &str) -> &'static AppConfig {
+    APP_CONFIG.get_or_init(|| match AppConfig::from_figment(&figment::figment().select(profile)) {
+        Ok(config) => config,
+        Err(e) => panic!("Invalid configuration: {e}"),
+    })
+}
+
+/// The application configuration, loaded from the current profile on first use when
+/// [`init`] was not called (tests, CLI tools).
+///
+/// # Panics
+/// When the configuration is invalid.
+pub fn app_config() -> &'static AppConfig {
+    APP_CONFIG.get_or_init(|| match AppConfig::from_figment(&figment::figment()) {
+        Ok(config) => config,
+        Err(e) => panic!("Invalid configuration: {e}"),
+    })
+}
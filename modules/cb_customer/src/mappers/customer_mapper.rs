@@ -40,8 +40,8 @@ impl MapperCRUD for CustomerMapper {
         //
         // limit 始终为 per_page
         // 计算分页相关
-        let page = (param.pagination.offset.unwrap() / param.pagination.limit.unwrap()) + 1;
-        let per_page = param.pagination.limit.unwrap();
+        let page = (param.pagination.offset_or_default() / param.pagination.limit_or_default()) + 1;
+        let per_page = param.pagination.limit_or_default();
         // 获取总记录数
         let total_count = dsl::customer_table.count().get_result::<i64>(conn)? as i32;
         // 计算总页数
@@ -126,8 +126,8 @@ impl MapperCRUD for CustomerMapper {
         //
         // limit 始终为 per_page
         // 计算分页相关
-        let page = (param.pagination.offset.unwrap() / param.pagination.limit.unwrap()) + 1;
-        let per_page = param.pagination.limit.unwrap();
+        let page = (param.pagination.offset_or_default() / param.pagination.limit_or_default()) + 1;
+        let per_page = param.pagination.limit_or_default();
         // 获取总记录数
         let total_count = dsl::customer_table.count().get_result::<i64>(conn)? as i32;
         // 计算总页数
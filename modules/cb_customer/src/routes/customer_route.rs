@@ -1,3 +1,5 @@
+use crab_rocket_auth::guards::authorized::Authorized;
+use crab_rocket_auth::permission;
 use obj_traits::controller::controller_crud::ControllerCRUD;
 use obj_traits::request::pagination_request_param::{PaginationParam, PaginationParamTrait};
 use obj_traits::request::request_param::RequestParam;
@@ -7,14 +9,12 @@ use crate::controllers::customer_controller::CustomerController;
 use crate::models::customer::{PatchCustomer, PostCustomer};
 use crate::models::customer_filter::CustomerFilter;
 
+permission!(pub CustomerCreate, "customer", "create");
+permission!(pub CustomerUpdate, "customer", "update");
+permission!(pub CustomerDelete, "customer", "delete");
+
 #[get("/customer?<limit>&<offset>")]
-pub fn get_customers(mut limit: Option<i32>, mut offset: Option<i32>) -> Json<serde_json::Value> {
-    if limit.is_none() {
-        limit = Some(10);
-    };
-    if offset.is_none() {
-        offset = Some(0);
-    };
+pub fn get_customers(limit: Option<i32>, offset: Option<i32>) -> Json<serde_json::Value> {
     let params = RequestParam::new(PaginationParam::new(limit, offset), None);
     println!("{:?}", params);
     crab_rocket_schema::update_reload::update_reload_count();
@@ -43,7 +43,10 @@ pub fn get_customer_by_id(id: i32) -> Json<serde_json::Value> {
 }
 
 #[post("/customer", data = "<customer>")]
-pub fn insert_single_customer(customer: Json<PostCustomer>) -> Json<serde_json::Value> {
+pub fn insert_single_customer(
+    _auth: Authorized<CustomerCreate>,
+    customer: Json<PostCustomer>,
+) -> Json<serde_json::Value> {
     let mut obj: PostCustomer = customer.into_inner();
 
     let resp = CustomerController::add_single(&mut obj).unwrap();
@@ -52,14 +55,21 @@ pub fn insert_single_customer(customer: Json<PostCustomer>) -> Json<serde_json::
 }
 
 #[delete("/customer/<id>")]
-pub fn delete_customer_by_id(id: i32) -> Json<serde_json::Value> {
+pub fn delete_customer_by_id(
+    _auth: Authorized<CustomerDelete>,
+    id: i32,
+) -> Json<serde_json::Value> {
     let resp = CustomerController::delete_by_id(id).unwrap();
     let json_value = serde_json::to_value(&resp).unwrap();
     Json(serde_json::from_value(json_value).unwrap())
 }
 
 #[patch("/customer/<id>", data = "<task>")]
-pub fn update_customer_by_id(id: i32, task: Json<PatchCustomer>) -> Json<serde_json::Value> {
+pub fn update_customer_by_id(
+    _auth: Authorized<CustomerUpdate>,
+    id: i32,
+    task: Json<PatchCustomer>,
+) -> Json<serde_json::Value> {
     let resp = CustomerController::update_by_id(id, &task).unwrap();
     let json_value = serde_json::to_value(&resp).unwrap();
     Json(serde_json::from_value(json_value).unwrap())
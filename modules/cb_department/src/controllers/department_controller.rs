@@ -0,0 +1,107 @@
+use std::error::Error;
+
+use obj_traits::{
+    controller::controller_crud::{
+        controller_add_single, controller_delete_by_id, controller_filter, controller_get_all,
+        controller_get_by_id, controller_update_by_id, ControllerCRUD,
+    },
+    request::{pagination_request_param::PaginationParam, request_param::RequestParam},
+    response::{api_response::ApiResponse, data::Data},
+};
+
+use crate::{
+    models::{
+        department::{Department, PatchDepartment, PostDepartment},
+        department_filter::DepartmentFilter,
+        department_tree::DepartmentNode,
+    },
+    services::department_service::{DepartmentError, DepartmentService},
+};
+
+pub struct DepartmentController {}
+
+impl ControllerCRUD for DepartmentController {
+    type Item = Department;
+    type PostItem = PostDepartment;
+    type PatchItem = PatchDepartment;
+    type Param = RequestParam<PaginationParam, DepartmentFilter>;
+    fn get_all(
+        param: &RequestParam<PaginationParam, DepartmentFilter>,
+    ) -> Result<ApiResponse<Data<Vec<Department>>>, Box<dyn Error>> {
+        controller_get_all::<Department, DepartmentService, DepartmentFilter>(param)
+    }
+    fn get_by_id(pid: i32) -> Result<ApiResponse<Department>, Box<dyn Error>> {
+        controller_get_by_id::<Department, DepartmentService>(pid)
+    }
+    fn add_single(obj: &mut PostDepartment) -> Result<ApiResponse<Department>, Box<dyn Error>> {
+        controller_add_single::<Department, DepartmentService, PostDepartment>(obj)
+    }
+    fn delete_by_id(pid: i32) -> Result<ApiResponse<Department>, Box<dyn Error>> {
+        controller_delete_by_id::<Department, DepartmentService>(pid)
+    }
+    fn update_by_id(
+        pid: i32,
+        obj: &PatchDepartment,
+    ) -> Result<ApiResponse<Department>, Box<dyn Error>> {
+        controller_update_by_id::<Department, DepartmentService, PatchDepartment>(pid, obj)
+    }
+    fn filter(
+        param: &RequestParam<PaginationParam, DepartmentFilter>,
+    ) -> Result<ApiResponse<Data<Vec<Department>>>, Box<dyn Error>> {
+        controller_filter::<Department, DepartmentService, DepartmentFilter>(param)
+    }
+}
+
+fn from_department_error<T>(e: DepartmentError) -> (i32, String, Option<T>) {
+    match e {
+        DepartmentError::Internal(_) => {
+            println!("{e:?}");
+            (500, String::from("Internal Server Error"), None)
+        }
+        e => (e.status(), e.to_string(), None),
+    }
+}
+
+impl DepartmentController {
+    pub fn add(obj: &PostDepartment) -> (i32, String, Option<Department>) {
+        match DepartmentService::add(obj) {
+            Ok(department) => (200, String::from("Success"), Some(department)),
+            Err(e) => from_department_error(e),
+        }
+    }
+
+    pub fn update(pid: i32, obj: &PatchDepartment) -> (i32, String, Option<Department>) {
+        match DepartmentService::update(pid, obj) {
+            Ok(department) => (200, String::from("Success"), Some(department)),
+            Err(e) => from_department_error(e),
+        }
+    }
+
+    pub fn delete(pid: i32) -> (i32, String, Option<Department>) {
+        match DepartmentService::delete(pid) {
+            Ok(department) => (200, String::from("Success"), Some(department)),
+            Err(e) => from_department_error(e),
+        }
+    }
+
+    pub fn set_manager(pid: i32, employee_id: Option<i32>) -> (i32, String, Option<Department>) {
+        match DepartmentService::set_manager(pid, employee_id) {
+            Ok(department) => (200, String::from("Success"), Some(department)),
+            Err(e) => from_department_error(e),
+        }
+    }
+
+    pub fn get_children(pid: i32) -> (i32, String, Option<Vec<Department>>) {
+        match DepartmentService::get_children(pid) {
+            Ok(departments) => (200, String::from("Success"), Some(departments)),
+            Err(e) => from_department_error(e),
+        }
+    }
+
+    pub fn subtree(pid: i32, depth: Option<i32>) -> (i32, String, Option<DepartmentNode>) {
+        match DepartmentService::subtree(pid, depth) {
+            Ok(tree) => (200, String::from("Success"), Some(tree)),
+            Err(e) => from_department_error(e),
+        }
+    }
+}
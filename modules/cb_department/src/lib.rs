@@ -0,0 +1,20 @@
+pub mod models {
+    pub mod department;
+    pub mod department_filter;
+    pub mod department_tree;
+}
+
+pub mod mappers {
+    pub mod department_mapper;
+}
+
+pub mod controllers {
+    pub mod department_controller;
+}
+
+pub mod routes {
+    pub mod department_route;
+}
+pub mod services {
+    pub mod department_service;
+}
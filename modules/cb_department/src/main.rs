@@ -0,0 +1,36 @@
+#[macro_use]
+extern crate rocket;
+
+use crab_rocket_department::routes::department_route::*;
+use dotenvy::dotenv;
+use std::env;
+#[launch]
+fn rocket() -> _ {
+    // Clear environment variable before running.
+    env::remove_var("DATABASE_URL");
+    dotenv().ok();
+    let figment = crab_rocket_config::init();
+
+    crab_rocket_utils::run_preload();
+
+    let cors = crab_rocket_config::cors::cors();
+
+    rocket::custom(figment)
+        .mount(
+            "/api",
+            routes![
+                get_departments,
+                filter_departments,
+                get_department_by_id,
+                get_department_children,
+                get_department_tree,
+                insert_single_department,
+                delete_department_by_id,
+                update_department_by_id,
+                set_department_manager,
+                remove_department_manager,
+                options_department
+            ],
+        )
+        .attach(cors)
+}
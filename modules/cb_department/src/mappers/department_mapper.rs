@@ -0,0 +1,258 @@
+use crab_rocket_schema::schema::department_table::dsl;
+use crab_rocket_schema::schema::employee_table;
+use crab_rocket_utils::time::get_e8_time;
+use diesel::{prelude::*, result::Error, sql_query, sql_types::Integer};
+use obj_traits::{
+    mapper::mapper_crud::MapperCRUD,
+    request::{
+        pagination_request_param::{Pagination, PaginationParam},
+        request_param::RequestParam,
+    },
+    response::data::Data,
+};
+
+use crate::models::{
+    department::{employee_count, Department, PatchDepartment, PostDepartment},
+    department_filter::DepartmentFilter,
+    department_tree::DepartmentTreeRow,
+};
+
+pub struct DepartmentMapper {}
+
+impl MapperCRUD for DepartmentMapper {
+    type Item = Department;
+    type PostItem = PostDepartment;
+    type PatchItem = PatchDepartment;
+    type Param = RequestParam<PaginationParam, DepartmentFilter>;
+    fn get_all(
+        conn: &mut PgConnection,
+        param: &RequestParam<PaginationParam, DepartmentFilter>,
+    ) -> Result<Data<Vec<Department>>, Error> {
+        let page = (param.pagination.offset_or_default() / param.pagination.limit_or_default()) + 1;
+        let per_page = param.pagination.limit_or_default();
+        let total_count = dsl::department_table.count().get_result::<i64>(conn)? as i32;
+        let pagination = pagination(page, per_page, total_count);
+
+        let data = dsl::department_table
+            .select(Department::as_select())
+            .order(dsl::department_id.asc())
+            .limit(per_page as i64)
+            .offset(((page - 1) * per_page) as i64)
+            .load::<Department>(conn)?;
+        Ok(Data::new(data, pagination))
+    }
+    fn get_by_id(conn: &mut PgConnection, pid: i32) -> Result<Department, Error> {
+        dsl::department_table.find(pid).select(Department::as_select()).first(conn)
+    }
+    fn add_single(conn: &mut PgConnection, obj: &PostDepartment) -> Result<Department, Error> {
+        diesel::insert_into(dsl::department_table)
+            .values(obj)
+            .returning(Department::as_returning())
+            .get_result(conn)
+    }
+    fn delete_by_id(conn: &mut PgConnection, pid: i32) -> Result<Department, Error> {
+        diesel::delete(dsl::department_table.find(pid))
+            .returning(Department::as_returning())
+            .get_result(conn)
+    }
+    fn update_by_id(
+        conn: &mut PgConnection,
+        pid: i32,
+        obj: &PatchDepartment,
+    ) -> Result<Department, Error> {
+        diesel::update(dsl::department_table.find(pid))
+            .set((
+                dsl::department_name.eq(obj.department_name()),
+                dsl::manager_id.eq(obj.manager_id()),
+                dsl::location.eq(obj.location()),
+                dsl::description.eq(obj.description()),
+                dsl::budget.eq(obj.budget()),
+                dsl::parent_department_id.eq(obj.parent_department_id()),
+                dsl::email.eq(obj.email()),
+                dsl::phone_number.eq(obj.phone_number()),
+                dsl::address.eq(obj.address()),
+                dsl::city.eq(obj.city()),
+                dsl::state.eq(obj.state()),
+                dsl::postal_code.eq(obj.postal_code()),
+                dsl::last_update.eq(get_e8_time()),
+            ))
+            .returning(Department::as_returning())
+            .get_result(conn)
+    }
+    fn filter(
+        conn: &mut PgConnection,
+        param: &RequestParam<PaginationParam, DepartmentFilter>,
+    ) -> Result<Data<Vec<Department>>, Error> {
+        let filter = &param.filter;
+        let page = (param.pagination.offset_or_default() / param.pagination.limit_or_default()) + 1;
+        let per_page = param.pagination.limit_or_default();
+        let total_count = dsl::department_table.count().get_result::<i64>(conn)? as i32;
+        let pagination = pagination(page, per_page, total_count);
+
+        let mut query = dsl::department_table
+            .select(Department::as_select())
+            .order(dsl::department_id.asc())
+            .limit(per_page as i64)
+            .offset(((page - 1) * per_page) as i64)
+            .into_boxed();
+
+        if let Some(f) = filter {
+            if let Some(department_name) = &f.department_name {
+                query = query.filter(dsl::department_name.like(format!("%{}%", department_name)));
+            }
+            if let Some(manager_id) = &f.manager_id {
+                query = query.filter(dsl::manager_id.eq(manager_id));
+            }
+            if let Some(location) = &f.location {
+                query = query.filter(dsl::location.like(format!("%{}%", location)));
+            }
+            if let Some(parent_department_id) = &f.parent_department_id {
+                query = query.filter(dsl::parent_department_id.eq(parent_department_id));
+            }
+            match f.top_level {
+                Some(true) => query = query.filter(dsl::parent_department_id.is_null()),
+                Some(false) => query = query.filter(dsl::parent_department_id.is_not_null()),
+                None => {}
+            }
+            if let Some(budget_min) = &f.budget_min {
+                query = query.filter(dsl::budget.ge(budget_min));
+            }
+            if let Some(budget_max) = &f.budget_max {
+                query = query.filter(dsl::budget.le(budget_max));
+            }
+            if let Some(number_of_employees_min) = f.number_of_employees_min {
+                query = query.filter(employee_count().ge(number_of_employees_min));
+            }
+            if let Some(number_of_employees_max) = f.number_of_employees_max {
+                query = query.filter(employee_count().le(number_of_employees_max));
+            }
+            if let Some(city) = &f.city {
+                query = query.filter(dsl::city.like(format!("%{}%", city)));
+            }
+            if let Some(state) = &f.state {
+                query = query.filter(dsl::state.like(format!("%{}%", state)));
+            }
+            if let Some(last_update_min) = &f.last_update_min {
+                query = query.filter(dsl::last_update.ge(last_update_min));
+            }
+            if let Some(last_update_max) = &f.last_update_max {
+                query = query.filter(dsl::last_update.le(last_update_max));
+            }
+        }
+        let data = query.load::<Department>(conn)?;
+        Ok(Data::new(data, pagination))
+    }
+}
+
+impl DepartmentMapper {
+    /// The direct sub-departments of `pid`.
+    pub fn get_children(conn: &mut PgConnection, pid: i32) -> Result<Vec<Department>, Error> {
+        dsl::department_table
+            .filter(dsl::parent_department_id.eq(pid))
+            .select(Department::as_select())
+            .order(dsl::department_id.asc())
+            .load(conn)
+    }
+
+    pub fn count_children(conn: &mut PgConnection, pid: i32) -> Result<i64, Error> {
+        dsl::department_table.filter(dsl::parent_department_id.eq(pid)).count().get_result(conn)
+    }
+
+    pub fn get_parent_id(conn: &mut PgConnection, pid: i32) -> Result<Option<i32>, Error> {
+        dsl::department_table.find(pid).select(dsl::parent_department_id).first(conn)
+    }
+
+    /// `None` removes the manager.
+    pub fn set_manager(
+        conn: &mut PgConnection,
+        pid: i32,
+        employee_id: Option<i32>,
+    ) -> Result<Department, Error> {
+        diesel::update(dsl::department_table.find(pid))
+            .set((dsl::manager_id.eq(employee_id), dsl::last_update.eq(get_e8_time())))
+            .returning(Department::as_returning())
+            .get_result(conn)
+    }
+
+    /// `pid` at depth `0` and all departments below it. A department met twice has `cycle` set
+    /// and nothing is walked below it.
+    pub fn subtree(conn: &mut PgConnection, pid: i32) -> Result<Vec<DepartmentTreeRow>, Error> {
+        sql_query(
+            "WITH RECURSIVE subtree AS ( \
+                 SELECT d.department_id, 0 AS depth, ARRAY[d.department_id] AS path, \
+                        false AS cycle \
+                 FROM department_table d WHERE d.department_id = $1 \
+               UNION ALL \
+                 SELECT c.department_id, s.depth + 1, s.path || c.department_id, \
+                        c.department_id = ANY(s.path) \
+                 FROM subtree s \
+                 JOIN department_table c ON c.parent_department_id = s.department_id \
+                 WHERE NOT s.cycle \
+             ) \
+             SELECT d.department_id, d.department_name, d.manager_id, d.parent_department_id, \
+                    s.depth, s.cycle, \
+                    (SELECT COUNT(*) FROM employee_table e \
+                     WHERE e.department_id = d.department_id) AS number_of_employees \
+             FROM subtree s JOIN department_table d ON d.department_id = s.department_id \
+             ORDER BY s.depth, d.department_id",
+        )
+        .bind::<Integer, _>(pid)
+        .load(conn)
+    }
+
+    pub fn employee_exists(conn: &mut PgConnection, employee_id: i32) -> Result<bool, Error> {
+        diesel::select(diesel::dsl::exists(employee_table::table.find(employee_id)))
+            .get_result(conn)
+    }
+}
+
+fn pagination(page: i32, per_page: i32, total_count: i32) -> Pagination {
+    let total_pages = (total_count + per_page - 1) / per_page;
+    let previous_page_offset = (page - 2) * per_page;
+    let next_page_offset = page * per_page;
+    Pagination::new(
+        page,
+        per_page,
+        total_pages,
+        total_count,
+        Some(format!("?limit={}&offset={}", per_page, next_page_offset)),
+        Some(format!("?limit={}&offset={}", per_page, previous_page_offset)),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use crab_rocket_test_support::{fixtures, test_conn};
+    use obj_traits::mapper::mapper_crud::MapperCRUD;
+    use obj_traits::request::pagination_request_param::{PaginationParam, PaginationParamTrait};
+    use obj_traits::request::request_param::RequestParam;
+
+    use super::DepartmentMapper;
+    use crate::models::department_filter::DepartmentFilter;
+
+    #[test]
+    fn test_number_of_employees_is_counted() {
+        let mut conn = test_conn();
+        let sales = fixtures::department(&mut conn, "Counted sales", None);
+        let east = fixtures::department(&mut conn, "Counted sales east", Some(sales));
+        for name in ["counted_a", "counted_b"] {
+            fixtures::employee(&mut conn, name, Some(sales), None);
+        }
+        fixtures::employee(&mut conn, "counted_c", Some(east), None);
+
+        assert_eq!(DepartmentMapper::get_by_id(&mut conn, sales).unwrap().number_of_employees(), 2);
+        let children = DepartmentMapper::get_children(&mut conn, sales).unwrap();
+        assert_eq!(children.len(), 1);
+        assert_eq!(children[0].number_of_employees(), 1);
+
+        let filter = DepartmentFilter {
+            department_name: Some(String::from("Counted")),
+            number_of_employees_min: Some(2),
+            ..Default::default()
+        };
+        let param = RequestParam::new(PaginationParam::default(), Some(filter));
+        let found = DepartmentMapper::filter(&mut conn, &param).unwrap();
+        let ids: Vec<_> = found.data().iter().map(|d| d.department_id()).collect();
+        assert_eq!(ids, [sales]);
+    }
+}
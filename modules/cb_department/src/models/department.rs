@@ -0,0 +1,254 @@
+use diesel::dsl::sql;
+use diesel::expression::SqlLiteral;
+use diesel::prelude::*;
+use diesel::sql_types::BigInt;
+use rocket::serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// `COUNT(*)` of the employees whose `department_id` is the department's, usable in the
+/// select, filter and order of any `department_table` query.
+pub fn employee_count() -> SqlLiteral<BigInt> {
+    sql::<BigInt>(
+        "(SELECT COUNT(*) FROM employee_table \
+         WHERE employee_table.department_id = department_table.department_id)",
+    )
+}
+
+#[derive(Selectable, Debug, Serialize, Deserialize, Queryable, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+#[diesel(table_name = crab_rocket_schema::schema::department_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Department {
+    department_id: i32,
+    department_name: String,
+    /// The `employee_id` heading the department.
+    manager_id: Option<i32>,
+    location: Option<String>,
+    creation_date: Option<chrono::NaiveDateTime>,
+    last_update: Option<chrono::NaiveDateTime>,
+    description: Option<String>,
+    budget: Option<i32>,
+    parent_department_id: Option<i32>,
+    email: Option<String>,
+    phone_number: Option<String>,
+    address: Option<String>,
+    city: Option<String>,
+    state: Option<String>,
+    postal_code: Option<String>,
+    /// Counted from `employee_table` on every read, sub-departments are not included.
+    #[diesel(select_expression = employee_count())]
+    #[diesel(select_expression_type = SqlLiteral<BigInt>)]
+    number_of_employees: i64,
+}
+
+impl Department {
+    pub fn department_id(&self) -> i32 {
+        self.department_id
+    }
+
+    pub fn department_name(&self) -> &str {
+        &self.department_name
+    }
+
+    pub fn manager_id(&self) -> Option<i32> {
+        self.manager_id
+    }
+
+    pub fn location(&self) -> &Option<String> {
+        &self.location
+    }
+
+    pub fn creation_date(&self) -> Option<chrono::NaiveDateTime> {
+        self.creation_date
+    }
+
+    pub fn last_update(&self) -> Option<chrono::NaiveDateTime> {
+        self.last_update
+    }
+
+    pub fn description(&self) -> &Option<String> {
+        &self.description
+    }
+
+    pub fn budget(&self) -> Option<i32> {
+        self.budget
+    }
+
+    pub fn parent_department_id(&self) -> Option<i32> {
+        self.parent_department_id
+    }
+
+    pub fn email(&self) -> &Option<String> {
+        &self.email
+    }
+
+    pub fn phone_number(&self) -> &Option<String> {
+        &self.phone_number
+    }
+
+    pub fn address(&self) -> &Option<String> {
+        &self.address
+    }
+
+    pub fn city(&self) -> &Option<String> {
+        &self.city
+    }
+
+    pub fn state(&self) -> &Option<String> {
+        &self.state
+    }
+
+    pub fn postal_code(&self) -> &Option<String> {
+        &self.postal_code
+    }
+
+    pub fn number_of_employees(&self) -> i64 {
+        self.number_of_employees
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+#[derive(Insertable)]
+#[diesel(table_name = crab_rocket_schema::schema::department_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PostDepartment {
+    department_name: String,
+    manager_id: Option<i32>,
+    location: Option<String>,
+    description: Option<String>,
+    budget: Option<i32>,
+    parent_department_id: Option<i32>,
+    email: Option<String>,
+    phone_number: Option<String>,
+    address: Option<String>,
+    city: Option<String>,
+    state: Option<String>,
+    postal_code: Option<String>,
+}
+
+impl PostDepartment {
+    pub fn new(
+        department_name: String,
+        manager_id: Option<i32>,
+        parent_department_id: Option<i32>,
+    ) -> Self {
+        Self {
+            department_name,
+            manager_id,
+            parent_department_id,
+            ..Default::default()
+        }
+    }
+
+    pub fn demo() -> Self {
+        Self {
+            department_name: "Research Department".to_string(),
+            manager_id: None,
+            location: Some("Portland".to_string()),
+            description: Some("Research and Prototyping".to_string()),
+            budget: Some(70000),
+            parent_department_id: None,
+            email: Some("research@example.com".to_string()),
+            phone_number: Some("555-010-2030".to_string()),
+            address: Some("808 Alder St".to_string()),
+            city: Some("Portland".to_string()),
+            state: Some("OR".to_string()),
+            postal_code: Some("97201".to_string()),
+        }
+    }
+
+    pub fn department_name(&self) -> &str {
+        &self.department_name
+    }
+
+    pub fn manager_id(&self) -> Option<i32> {
+        self.manager_id
+    }
+
+    pub fn parent_department_id(&self) -> Option<i32> {
+        self.parent_department_id
+    }
+}
+
+/// Replaces every field of a department, see `PUT /department/<id>/manager/<employee_id>`
+/// for changing the manager alone.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct PatchDepartment {
+    department_name: String,
+    manager_id: Option<i32>,
+    location: Option<String>,
+    description: Option<String>,
+    budget: Option<i32>,
+    parent_department_id: Option<i32>,
+    email: Option<String>,
+    phone_number: Option<String>,
+    address: Option<String>,
+    city: Option<String>,
+    state: Option<String>,
+    postal_code: Option<String>,
+}
+
+impl PatchDepartment {
+    pub fn new(
+        department_name: String,
+        manager_id: Option<i32>,
+        parent_department_id: Option<i32>,
+    ) -> Self {
+        Self {
+            department_name,
+            manager_id,
+            parent_department_id,
+            ..Default::default()
+        }
+    }
+
+    pub fn department_name(&self) -> &str {
+        &self.department_name
+    }
+
+    pub fn manager_id(&self) -> Option<i32> {
+        self.manager_id
+    }
+
+    pub fn location(&self) -> &Option<String> {
+        &self.location
+    }
+
+    pub fn description(&self) -> &Option<String> {
+        &self.description
+    }
+
+    pub fn budget(&self) -> Option<i32> {
+        self.budget
+    }
+
+    pub fn parent_department_id(&self) -> Option<i32> {
+        self.parent_department_id
+    }
+
+    pub fn email(&self) -> &Option<String> {
+        &self.email
+    }
+
+    pub fn phone_number(&self) -> &Option<String> {
+        &self.phone_number
+    }
+
+    pub fn address(&self) -> &Option<String> {
+        &self.address
+    }
+
+    pub fn city(&self) -> &Option<String> {
+        &self.city
+    }
+
+    pub fn state(&self) -> &Option<String> {
+        &self.state
+    }
+
+    pub fn postal_code(&self) -> &Option<String> {
+        &self.postal_code
+    }
+}
@@ -0,0 +1,26 @@
+use rocket::serde::Deserialize;
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct DepartmentFilter {
+    pub department_name: Option<String>,
+    pub manager_id: Option<i32>,
+    pub location: Option<String>,
+    pub parent_department_id: Option<i32>,
+    /// `true` keeps the departments without a parent only, `false` the others.
+    pub top_level: Option<bool>,
+    pub budget_min: Option<i32>,
+    pub budget_max: Option<i32>,
+    pub number_of_employees_min: Option<i64>,
+    pub number_of_employees_max: Option<i64>,
+    pub city: Option<String>,
+    pub state: Option<String>,
+    pub last_update_min: Option<chrono::NaiveDateTime>,
+    pub last_update_max: Option<chrono::NaiveDateTime>,
+}
+
+impl DepartmentFilter {
+    pub fn from_json(json_str: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json_str)
+    }
+}
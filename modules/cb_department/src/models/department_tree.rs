@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+
+use diesel::sql_types::{Bool, Int4, Int8, Nullable, Varchar};
+use diesel::QueryableByName;
+use rocket::serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A department met while walking down from the root of a subtree, as read by
+/// [`DepartmentMapper::subtree`](crate::mappers::department_mapper::DepartmentMapper::subtree).
+#[derive(QueryableByName, Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct DepartmentTreeRow {
+    #[diesel(sql_type = Int4)]
+    department_id: i32,
+    #[diesel(sql_type = Varchar)]
+    department_name: String,
+    #[diesel(sql_type = Nullable<Int4>)]
+    manager_id: Option<i32>,
+    #[diesel(sql_type = Nullable<Int4>)]
+    parent_department_id: Option<i32>,
+    /// `0` for the root of the subtree.
+    #[diesel(sql_type = Int4)]
+    depth: i32,
+    #[diesel(sql_type = Int8)]
+    number_of_employees: i64,
+    /// The department was met before on the same walk.
+    #[diesel(sql_type = Bool)]
+    #[serde(skip)]
+    cycle: bool,
+}
+
+impl DepartmentTreeRow {
+    pub fn department_id(&self) -> i32 {
+        self.department_id
+    }
+
+    pub fn depth(&self) -> i32 {
+        self.depth
+    }
+
+    pub fn number_of_employees(&self) -> i64 {
+        self.number_of_employees
+    }
+
+    pub fn cycle(&self) -> bool {
+        self.cycle
+    }
+}
+
+/// A department with its sub-departments. `total_employees` counts the whole subtree, also
+/// the parts cut off by the depth limit.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct DepartmentNode {
+    #[serde(flatten)]
+    department: DepartmentTreeRow,
+    total_employees: i64,
+    sub_departments: Vec<DepartmentNode>,
+}
+
+impl DepartmentNode {
+    /// Nests `rows` below the department at their root and keeps `max_depth` levels of it.
+    pub fn tree(root: DepartmentTreeRow, rows: Vec<DepartmentTreeRow>, max_depth: i32) -> Self {
+        let mut children: HashMap<i32, Vec<DepartmentTreeRow>> = HashMap::new();
+        for row in rows {
+            if let Some(parent_department_id) = row.parent_department_id {
+                children.entry(parent_department_id).or_default().push(row);
+            }
+        }
+        Self::build(root, &mut children, max_depth)
+    }
+
+    fn build(
+        department: DepartmentTreeRow,
+        children: &mut HashMap<i32, Vec<DepartmentTreeRow>>,
+        max_depth: i32,
+    ) -> Self {
+        let direct = children.remove(&department.department_id).unwrap_or_default();
+        let sub_departments: Vec<DepartmentNode> =
+            direct.into_iter().map(|child| Self::build(child, children, max_depth)).collect();
+        let total_employees = department.number_of_employees
+            + sub_departments.iter().map(|d| d.total_employees).sum::<i64>();
+        let sub_departments = if department.depth < max_depth {
+            sub_departments
+        } else {
+            Vec::new()
+        };
+        DepartmentNode {
+            department,
+            total_employees,
+            sub_departments,
+        }
+    }
+
+    pub fn department(&self) -> &DepartmentTreeRow {
+        &self.department
+    }
+
+    pub fn total_employees(&self) -> i64 {
+        self.total_employees
+    }
+
+    pub fn sub_departments(&self) -> &[DepartmentNode] {
+        &self.sub_departments
+    }
+}
@@ -0,0 +1,127 @@
+use crab_rocket_auth::guards::authorized::Authorized;
+use crab_rocket_auth::permission;
+use obj_traits::controller::controller_crud::ControllerCRUD;
+use obj_traits::request::pagination_request_param::{PaginationParam, PaginationParamTrait};
+use obj_traits::request::request_param::RequestParam;
+use obj_traits::response::api_response::ApiResponse;
+use rocket::http::Status;
+use rocket::response::status;
+use rocket::serde::json::Json;
+use rocket::{delete, get, options, patch, post, put};
+
+use crate::controllers::department_controller::DepartmentController;
+use crate::models::department::{PatchDepartment, PostDepartment};
+use crate::models::department_filter::DepartmentFilter;
+
+permission!(pub DepartmentCreate, "department", "create");
+permission!(pub DepartmentUpdate, "department", "update");
+permission!(pub DepartmentDelete, "department", "delete");
+
+/// Answers with `code` as the HTTP status, in the shape of the other department routes.
+fn to_response<T: rocket::serde::Serialize + Default>(
+    code: i32,
+    message: String,
+    data: T,
+) -> status::Custom<Json<serde_json::Value>> {
+    let response = serde_json::to_value(ApiResponse::new(code, message, data)).unwrap();
+    let status = Status::from_code(code as u16).unwrap_or(Status::InternalServerError);
+    status::Custom(status, Json(response))
+}
+
+#[get("/department?<limit>&<offset>")]
+pub fn get_departments(limit: Option<i32>, offset: Option<i32>) -> Json<serde_json::Value> {
+    let params = RequestParam::new(PaginationParam::new(limit, offset), None);
+    crab_rocket_schema::update_reload::update_reload_count();
+    let resp = DepartmentController::get_all(&params).unwrap();
+    let json_value = serde_json::to_value(&resp).unwrap();
+    Json(serde_json::from_value(json_value).unwrap())
+}
+
+#[post("/department/filter", data = "<param>")]
+pub fn filter_departments(
+    param: Option<Json<RequestParam<PaginationParam, DepartmentFilter>>>,
+) -> Json<serde_json::Value> {
+    let param = param.unwrap_or(Json(RequestParam::new(PaginationParam::default(), None)));
+    let param = param.into_inner();
+    crab_rocket_schema::update_reload::update_reload_count();
+    let resp = DepartmentController::filter(&param).unwrap();
+    let json_value = serde_json::to_value(&resp).unwrap();
+    Json(serde_json::from_value(json_value).unwrap())
+}
+
+#[get("/department/<id>")]
+pub fn get_department_by_id(id: i32) -> Json<serde_json::Value> {
+    crab_rocket_schema::update_reload::update_reload_count();
+    let resp = DepartmentController::get_by_id(id).unwrap();
+    let json_value = serde_json::to_value(&resp).unwrap();
+    Json(serde_json::from_value(json_value).unwrap())
+}
+
+/// The direct sub-departments.
+#[get("/department/<id>/children")]
+pub fn get_department_children(id: i32) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = DepartmentController::get_children(id);
+    to_response(code, message, data)
+}
+
+/// The department with everything below it, `depth` levels deep (at most and by default
+/// `MAX_TREE_DEPTH`), and the head count of each subtree.
+#[get("/department/<id>/tree?<depth>")]
+pub fn get_department_tree(id: i32, depth: Option<i32>) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = DepartmentController::subtree(id, depth);
+    to_response(code, message, data)
+}
+
+#[post("/department", data = "<department>")]
+pub fn insert_single_department(
+    _auth: Authorized<DepartmentCreate>,
+    department: Json<PostDepartment>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = DepartmentController::add(&department);
+    to_response(code, message, data)
+}
+
+/// Only departments without employees and sub-departments, `409` otherwise.
+#[delete("/department/<id>")]
+pub fn delete_department_by_id(
+    _auth: Authorized<DepartmentDelete>,
+    id: i32,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = DepartmentController::delete(id);
+    to_response(code, message, data)
+}
+
+/// A `parent_department_id` below the department itself answers `409`.
+#[patch("/department/<id>", data = "<department>")]
+pub fn update_department_by_id(
+    _auth: Authorized<DepartmentUpdate>,
+    id: i32,
+    department: Json<PatchDepartment>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = DepartmentController::update(id, &department);
+    to_response(code, message, data)
+}
+
+#[put("/department/<id>/manager/<employee_id>")]
+pub fn set_department_manager(
+    _auth: Authorized<DepartmentUpdate>,
+    id: i32,
+    employee_id: i32,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = DepartmentController::set_manager(id, Some(employee_id));
+    to_response(code, message, data)
+}
+
+#[delete("/department/<id>/manager")]
+pub fn remove_department_manager(
+    _auth: Authorized<DepartmentUpdate>,
+    id: i32,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = DepartmentController::set_manager(id, None);
+    to_response(code, message, data)
+}
+
+#[options("/department")]
+pub fn options_department() -> Status {
+    Status::Ok
+}
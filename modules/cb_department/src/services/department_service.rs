@@ -0,0 +1,288 @@
+use std::error::Error;
+use std::fmt;
+
+use crab_rocket_schema::establish_pg_connection;
+use diesel::result::DatabaseErrorKind;
+use diesel::{Connection, ConnectionError, PgConnection};
+use obj_traits::mapper::mapper_crud::MapperCRUD;
+use obj_traits::request::pagination_request_param::PaginationParam;
+use obj_traits::request::request_param::RequestParam;
+use obj_traits::response::data::Data;
+use obj_traits::service::service_crud::{
+    service_filter, service_get_all, service_get_by_id, ServiceCRUD,
+};
+
+use crate::mappers::department_mapper::DepartmentMapper;
+use crate::models::department::{Department, PatchDepartment, PostDepartment};
+use crate::models::department_filter::DepartmentFilter;
+use crate::models::department_tree::DepartmentNode;
+
+/// How deep `GET /department/<id>/tree` nests at most, and without a `depth`.
+pub const MAX_TREE_DEPTH: i32 = 10;
+
+/// Why a department could not be written.
+#[derive(Debug)]
+pub enum DepartmentError {
+    NotFound(i32),
+    UnknownEmployee(i32),
+    UnknownParent(i32),
+    /// The parent would be the department itself or one of its sub-departments.
+    Cycle {
+        department_id: i32,
+        parent_department_id: i32,
+    },
+    NameTaken(String),
+    /// The stored hierarchy already loops, met at `department_id`.
+    CycleDetected {
+        department_id: i32,
+    },
+    InvalidDepth(i32),
+    /// Deleting needs the employees and sub-departments moved elsewhere first.
+    NotEmpty {
+        employees: i64,
+        departments: i64,
+    },
+    Internal(String),
+}
+
+impl DepartmentError {
+    pub fn status(&self) -> i32 {
+        match self {
+            DepartmentError::NotFound(_) => 404,
+            DepartmentError::UnknownEmployee(_)
+            | DepartmentError::UnknownParent(_)
+            | DepartmentError::InvalidDepth(_) => 400,
+            DepartmentError::Cycle {
+                ..
+            }
+            | DepartmentError::CycleDetected {
+                ..
+            }
+            | DepartmentError::NameTaken(_)
+            | DepartmentError::NotEmpty {
+                ..
+            } => 409,
+            DepartmentError::Internal(_) => 500,
+        }
+    }
+}
+
+impl fmt::Display for DepartmentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DepartmentError::NotFound(id) => write!(f, "department {id} not found"),
+            DepartmentError::UnknownEmployee(id) => write!(f, "employee {id} not found"),
+            DepartmentError::UnknownParent(id) => write!(f, "parent department {id} not found"),
+            DepartmentError::Cycle {
+                department_id,
+                parent_department_id,
+            } => write!(
+                f,
+                "department {parent_department_id} can not be the parent of department \
+                 {department_id}, it is the department itself or one of its sub-departments"
+            ),
+            DepartmentError::NameTaken(name) => write!(f, "department name `{name}` is taken"),
+            DepartmentError::CycleDetected {
+                department_id,
+            } => write!(f, "the department hierarchy loops at department {department_id}"),
+            DepartmentError::InvalidDepth(depth) => {
+                write!(f, "depth {depth} is not between 1 and {MAX_TREE_DEPTH}")
+            }
+            DepartmentError::NotEmpty {
+                employees,
+                departments,
+            } => write!(
+                f,
+                "department still has {employees} employees and {departments} sub-departments"
+            ),
+            DepartmentError::Internal(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for DepartmentError {}
+
+impl From<diesel::result::Error> for DepartmentError {
+    fn from(e: diesel::result::Error) -> Self {
+        DepartmentError::Internal(e.to_string())
+    }
+}
+
+impl From<ConnectionError> for DepartmentError {
+    fn from(e: ConnectionError) -> Self {
+        DepartmentError::Internal(e.to_string())
+    }
+}
+
+pub struct DepartmentService {}
+
+impl ServiceCRUD for DepartmentService {
+    type Item = Department;
+    type PostItem = PostDepartment;
+    type PatchItem = PatchDepartment;
+    type Param = RequestParam<PaginationParam, DepartmentFilter>;
+    fn get_all(
+        param: &RequestParam<PaginationParam, DepartmentFilter>,
+    ) -> Result<Data<Vec<Department>>, Box<dyn Error>> {
+        service_get_all::<Department, DepartmentMapper, DepartmentFilter>(param)
+    }
+    fn get_by_id(pid: i32) -> Result<Department, Box<dyn Error>> {
+        service_get_by_id::<Department, DepartmentMapper>(pid)
+    }
+    fn add_single(obj: &PostDepartment) -> Result<Department, Box<dyn Error>> {
+        Ok(Self::add(obj)?)
+    }
+    fn delete_by_id(pid: i32) -> Result<Department, Box<dyn Error>> {
+        Ok(Self::delete(pid)?)
+    }
+    fn update_by_id(pid: i32, obj: &PatchDepartment) -> Result<Department, Box<dyn Error>> {
+        Ok(Self::update(pid, obj)?)
+    }
+    fn filter(
+        param: &RequestParam<PaginationParam, DepartmentFilter>,
+    ) -> Result<Data<Vec<Department>>, Box<dyn Error>> {
+        service_filter::<Department, DepartmentMapper, DepartmentFilter>(param)
+    }
+}
+
+impl DepartmentService {
+    /// Checks that the manager and the parent exist before inserting.
+    pub fn add(obj: &PostDepartment) -> Result<Department, DepartmentError> {
+        let mut conn = establish_pg_connection()?;
+        conn.transaction(|conn| {
+            check_manager(conn, obj.manager_id())?;
+            if let Some(parent_department_id) = obj.parent_department_id() {
+                match DepartmentMapper::get_parent_id(conn, parent_department_id) {
+                    Err(diesel::result::Error::NotFound) => {
+                        return Err(DepartmentError::UnknownParent(parent_department_id))
+                    }
+                    result => {
+                        result?;
+                    }
+                }
+            }
+            DepartmentMapper::add_single(conn, obj)
+                .map_err(|e| from_write_error(e, obj.department_name()))
+        })
+    }
+
+    /// Replaces the department, refusing a parent below it in the hierarchy.
+    pub fn update(pid: i32, obj: &PatchDepartment) -> Result<Department, DepartmentError> {
+        let mut conn = establish_pg_connection()?;
+        conn.transaction(|conn| {
+            find(conn, pid)?;
+            check_manager(conn, obj.manager_id())?;
+            if let Some(parent_department_id) = obj.parent_department_id() {
+                check_parent(conn, pid, parent_department_id)?;
+            }
+            DepartmentMapper::update_by_id(conn, pid, obj)
+                .map_err(|e| from_write_error(e, obj.department_name()))
+        })
+    }
+
+    /// Only empty departments can be deleted.
+    pub fn delete(pid: i32) -> Result<Department, DepartmentError> {
+        let mut conn = establish_pg_connection()?;
+        conn.transaction(|conn| {
+            let department = find(conn, pid)?;
+            let departments = DepartmentMapper::count_children(conn, pid)?;
+            if department.number_of_employees() > 0 || departments > 0 {
+                return Err(DepartmentError::NotEmpty {
+                    employees: department.number_of_employees(),
+                    departments,
+                });
+            }
+            Ok(DepartmentMapper::delete_by_id(conn, pid)?)
+        })
+    }
+
+    /// `None` leaves the department without a manager. The manager may work in any
+    /// department.
+    pub fn set_manager(pid: i32, employee_id: Option<i32>) -> Result<Department, DepartmentError> {
+        let mut conn = establish_pg_connection()?;
+        conn.transaction(|conn| {
+            find(conn, pid)?;
+            check_manager(conn, employee_id)?;
+            Ok(DepartmentMapper::set_manager(conn, pid, employee_id)?)
+        })
+    }
+
+    pub fn get_children(pid: i32) -> Result<Vec<Department>, DepartmentError> {
+        let mut conn = establish_pg_connection()?;
+        find(&mut conn, pid)?;
+        Ok(DepartmentMapper::get_children(&mut conn, pid)?)
+    }
+
+    /// `pid` with the departments below it nested `depth` levels deep, each with its own and
+    /// its subtree's head count.
+    pub fn subtree(pid: i32, depth: Option<i32>) -> Result<DepartmentNode, DepartmentError> {
+        let depth = depth.unwrap_or(MAX_TREE_DEPTH);
+        if !(1..=MAX_TREE_DEPTH).contains(&depth) {
+            return Err(DepartmentError::InvalidDepth(depth));
+        }
+        let mut conn = establish_pg_connection()?;
+        let mut rows = DepartmentMapper::subtree(&mut conn, pid)?;
+        if rows.is_empty() {
+            return Err(DepartmentError::NotFound(pid));
+        }
+        if let Some(row) = rows.iter().find(|row| row.cycle()) {
+            return Err(DepartmentError::CycleDetected {
+                department_id: row.department_id(),
+            });
+        }
+        let root = rows.remove(0);
+        Ok(DepartmentNode::tree(root, rows, depth))
+    }
+}
+
+fn find(conn: &mut PgConnection, pid: i32) -> Result<Department, DepartmentError> {
+    match DepartmentMapper::get_by_id(conn, pid) {
+        Err(diesel::result::Error::NotFound) => Err(DepartmentError::NotFound(pid)),
+        result => Ok(result?),
+    }
+}
+
+fn check_manager(conn: &mut PgConnection, employee_id: Option<i32>) -> Result<(), DepartmentError> {
+    match employee_id {
+        Some(employee_id) if !DepartmentMapper::employee_exists(conn, employee_id)? => {
+            Err(DepartmentError::UnknownEmployee(employee_id))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Walks up from `parent_department_id`, meeting `pid` on the way means `pid` would become
+/// its own ancestor.
+fn check_parent(
+    conn: &mut PgConnection,
+    pid: i32,
+    parent_department_id: i32,
+) -> Result<(), DepartmentError> {
+    let cycle = DepartmentError::Cycle {
+        department_id: pid,
+        parent_department_id,
+    };
+    let mut current = Some(parent_department_id);
+    while let Some(department_id) = current {
+        if department_id == pid {
+            return Err(cycle);
+        }
+        current = match DepartmentMapper::get_parent_id(conn, department_id) {
+            Ok(parent) => parent,
+            Err(diesel::result::Error::NotFound) if department_id == parent_department_id => {
+                return Err(DepartmentError::UnknownParent(parent_department_id))
+            }
+            Err(e) => return Err(e.into()),
+        };
+    }
+    Ok(())
+}
+
+fn from_write_error(e: diesel::result::Error, department_name: &str) -> DepartmentError {
+    match e {
+        diesel::result::Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
+            DepartmentError::NameTaken(department_name.to_string())
+        }
+        e => e.into(),
+    }
+}
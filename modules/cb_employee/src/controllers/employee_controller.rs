@@ -1,6 +1,8 @@
 use crate::models::employee::{Employee, PatchEmployee, PostEmployee};
 use crate::models::employee_filter::EmployeeFilter;
-use crate::services::employee_service::EmployeeService;
+use crate::models::onboarding::{Onboarded, Onboarding};
+use crate::models::org_chart::{OrgEmployee, ReportNode};
+use crate::services::employee_service::{EmployeeError, EmployeeService};
 use obj_traits::controller::controller_crud::{
     controller_add_single, controller_delete_by_id, controller_filter, controller_get_all,
     controller_get_by_id, controller_update_by_id, ControllerCRUD,
@@ -48,3 +50,64 @@ impl ControllerCRUD for EmployeeController {
         controller_filter::<Employee, EmployeeService, EmployeeFilter>(param)
     }
 }
+
+fn from_employee_error<T>(e: EmployeeError) -> (i32, String, Option<T>) {
+    match e {
+        EmployeeError::Internal(_) => {
+            println!("{e:?}");
+            (500, String::from("Internal Server Error"), None)
+        }
+        e => (e.status(), e.to_string(), None),
+    }
+}
+
+impl EmployeeController {
+    pub fn update(pid: i32, obj: &PatchEmployee) -> (i32, String, Option<Employee>) {
+        match EmployeeService::update(pid, obj) {
+            Ok(employee) => (200, String::from("Success"), Some(employee)),
+            Err(e) => from_employee_error(e),
+        }
+    }
+
+    pub fn management_chain(pid: i32) -> (i32, String, Option<Vec<OrgEmployee>>) {
+        match EmployeeService::management_chain(pid) {
+            Ok(chain) => (200, String::from("Success"), Some(chain)),
+            Err(e) => from_employee_error(e),
+        }
+    }
+
+    pub fn reports(pid: i32, depth: Option<i32>) -> (i32, String, Option<ReportNode>) {
+        match EmployeeService::reports(pid, depth) {
+            Ok(tree) => (200, String::from("Success"), Some(tree)),
+            Err(e) => from_employee_error(e),
+        }
+    }
+
+    pub fn link_user(pid: i32, user_id: i32) -> (i32, String, Option<Employee>) {
+        match EmployeeService::link_user(pid, user_id) {
+            Ok(employee) => (200, String::from("Success"), Some(employee)),
+            Err(e) => from_employee_error(e),
+        }
+    }
+
+    pub fn unlink_user(pid: i32) -> (i32, String, Option<Employee>) {
+        match EmployeeService::unlink_user(pid) {
+            Ok(employee) => (200, String::from("Success"), Some(employee)),
+            Err(e) => from_employee_error(e),
+        }
+    }
+
+    pub fn get_by_user(user_id: i32) -> (i32, String, Option<Employee>) {
+        match EmployeeService::get_by_user(user_id) {
+            Ok(employee) => (200, String::from("Success"), Some(employee)),
+            Err(e) => from_employee_error(e),
+        }
+    }
+
+    pub fn onboard(obj: &Onboarding) -> (i32, String, Option<Onboarded>) {
+        match EmployeeService::onboard(obj) {
+            Ok(onboarded) => (201, String::from("Created"), Some(onboarded)),
+            Err(e) => from_employee_error(e),
+        }
+    }
+}
@@ -1,6 +1,8 @@
 pub mod models {
     pub mod employee;
     pub mod employee_filter;
+    pub mod onboarding;
+    pub mod org_chart;
 }
 
 pub mod mappers {
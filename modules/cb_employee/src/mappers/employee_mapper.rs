@@ -9,11 +9,12 @@ use obj_traits::{
 };
 
 use crate::models::{
-    employee::{Employee, PostEmployee, PatchEmployee},
+    employee::{current_salary, Employee, PatchEmployee, PostEmployee},
     employee_filter::EmployeeFilter,
+    org_chart::OrgEmployee,
 };
 use crab_rocket_schema::schema::employee_table::dsl;
-use diesel::{prelude::*, result::Error};
+use diesel::{prelude::*, result::Error, sql_query, sql_types::Integer};
 pub struct EmployeeMapper {}
 
 impl MapperCRUD for EmployeeMapper {
@@ -40,8 +41,8 @@ impl MapperCRUD for EmployeeMapper {
         //
         // limit 始终为 per_page
         // 计算分页相关
-        let page = (param.pagination.offset.unwrap() / param.pagination.limit.unwrap()) + 1;
-        let per_page = param.pagination.limit.unwrap();
+        let page = (param.pagination.offset_or_default() / param.pagination.limit_or_default()) + 1;
+        let per_page = param.pagination.limit_or_default();
         // 获取总记录数
         let total_count = dsl::employee_table.count().get_result::<i64>(conn)? as i32;
         // 计算总页数
@@ -60,7 +61,9 @@ impl MapperCRUD for EmployeeMapper {
 
         // 分页查询
         let data = dsl::employee_table
+            .select(Employee::as_select())
             .order(dsl::last_update.desc())
+            .then_order_by(dsl::employee_id.desc())
             .limit(per_page as i64)
             .offset(((page - 1) * per_page) as i64)
             .load::<Employee>(conn)?;
@@ -68,7 +71,10 @@ impl MapperCRUD for EmployeeMapper {
         Ok(body)
     }
     fn get_by_id(conn: &mut PgConnection, pid: i32) -> Result<Employee, Error> {
-        dsl::employee_table.filter(dsl::employee_id.eq(pid)).first(conn)
+        dsl::employee_table
+            .filter(dsl::employee_id.eq(pid))
+            .select(Employee::as_select())
+            .first(conn)
     }
     fn add_single(conn: &mut PgConnection, obj: &PostEmployee) -> Result<Employee, Error> {
         diesel::insert_into(dsl::employee_table)
@@ -77,7 +83,9 @@ impl MapperCRUD for EmployeeMapper {
             .get_result(conn)
     }
     fn delete_by_id(conn: &mut PgConnection, pid: i32) -> Result<Employee, Error> {
-        diesel::delete(dsl::employee_table.filter(dsl::employee_id.eq(pid))).get_result(conn)
+        diesel::delete(dsl::employee_table.filter(dsl::employee_id.eq(pid)))
+            .returning(Employee::as_returning())
+            .get_result(conn)
     }
     fn update_by_id(
         conn: &mut PgConnection,
@@ -96,7 +104,6 @@ impl MapperCRUD for EmployeeMapper {
                 dsl::phone_number.eq(obj.phone_number()),
                 dsl::department_id.eq(obj.department_id()),
                 dsl::job_title.eq(obj.job_title()),
-                dsl::salary.eq(obj.salary()),
                 dsl::manager_id.eq(obj.manager_id()),
                 dsl::address.eq(obj.address()),
                 dsl::city.eq(obj.city()),
@@ -107,6 +114,7 @@ impl MapperCRUD for EmployeeMapper {
                 dsl::role_name.eq(obj.role_name()),
                 dsl::role_id.eq(obj.role_id()),
             ))
+            .returning(Employee::as_returning())
             .get_result(conn)
     }
     fn filter(
@@ -130,8 +138,8 @@ impl MapperCRUD for EmployeeMapper {
         let filter = &param.filter;
         println!("{filter:?}");
         // 计算分页相关
-        let page = (param.pagination.offset.unwrap() / param.pagination.limit.unwrap()) + 1;
-        let per_page = param.pagination.limit.unwrap();
+        let page = (param.pagination.offset_or_default() / param.pagination.limit_or_default()) + 1;
+        let per_page = param.pagination.limit_or_default();
         // 获取总记录数
         let total_count = dsl::employee_table.count().get_result::<i64>(conn)? as i32;
         // 计算总页数
@@ -148,11 +156,12 @@ impl MapperCRUD for EmployeeMapper {
             Some(format!("?limit={}&offset={}", per_page, previous_page_offset)),
         );
 
-        let mut query = dsl::employee_table.into_boxed();
+        let mut query = dsl::employee_table.select(Employee::as_select()).into_boxed();
 
         // 分页查询
         query = query
             .order(dsl::last_update.desc())
+            .then_order_by(dsl::employee_id.desc())
             .limit(per_page as i64)
             .offset(((page - 1) * per_page) as i64);
 
@@ -185,10 +194,10 @@ impl MapperCRUD for EmployeeMapper {
                 query = query.filter(dsl::job_title.like(format!("%{}%", job_title)));
             }
             if let Some(salary_min) = &f.salary_min {
-                query = query.filter(dsl::salary.ge(salary_min));
+                query = query.filter(current_salary().ge(salary_min));
             }
             if let Some(salary_max) = &f.salary_max {
-                query = query.filter(dsl::salary.le(salary_max));
+                query = query.filter(current_salary().le(salary_max));
             }
             if let Some(manager_id) = &f.manager_id {
                 query = query.filter(dsl::manager_id.eq(manager_id));
@@ -226,10 +235,99 @@ impl MapperCRUD for EmployeeMapper {
         Ok(body)
     }
 }
+
+/// The columns of an [`OrgEmployee`] for a row `e` of `employee_table`.
+const ORG_COLUMNS: &str = "e.employee_id, e.employee_name, e.job_title, e.department_id, \
+     e.manager_id, (SELECT COUNT(*) FROM employee_table r WHERE r.manager_id = e.employee_id) \
+     AS direct_reports";
+
+impl EmployeeMapper {
+    /// `pid` at depth `0` followed by their manager, the manager's manager and so on. The walk
+    /// stops at the first employee met twice, that row has `cycle` set.
+    pub fn management_chain(conn: &mut PgConnection, pid: i32) -> Result<Vec<OrgEmployee>, Error> {
+        sql_query(format!(
+            "WITH RECURSIVE chain AS ( \
+                 SELECT e.employee_id, e.manager_id, 0 AS depth, ARRAY[e.employee_id] AS path, \
+                        false AS cycle \
+                 FROM employee_table e WHERE e.employee_id = $1 \
+               UNION ALL \
+                 SELECT m.employee_id, m.manager_id, c.depth + 1, c.path || m.employee_id, \
+                        m.employee_id = ANY(c.path) \
+                 FROM chain c JOIN employee_table m ON m.employee_id = c.manager_id \
+                 WHERE NOT c.cycle \
+             ) \
+             SELECT {ORG_COLUMNS}, c.depth, c.cycle \
+             FROM chain c JOIN employee_table e ON e.employee_id = c.employee_id \
+             ORDER BY c.depth"
+        ))
+        .bind::<Integer, _>(pid)
+        .load(conn)
+    }
+
+    /// `pid` at depth `0` and everyone reporting to them directly or indirectly, down to
+    /// `max_depth`. A report met twice has `cycle` set and nobody is walked below it.
+    pub fn reports(
+        conn: &mut PgConnection,
+        pid: i32,
+        max_depth: i32,
+    ) -> Result<Vec<OrgEmployee>, Error> {
+        sql_query(format!(
+            "WITH RECURSIVE reports AS ( \
+                 SELECT e.employee_id, 0 AS depth, ARRAY[e.employee_id] AS path, false AS cycle \
+                 FROM employee_table e WHERE e.employee_id = $1 \
+               UNION ALL \
+                 SELECT r.employee_id, p.depth + 1, p.path || r.employee_id, \
+                        r.employee_id = ANY(p.path) \
+                 FROM reports p JOIN employee_table r ON r.manager_id = p.employee_id \
+                 WHERE NOT p.cycle AND p.depth < $2 \
+             ) \
+             SELECT {ORG_COLUMNS}, p.depth, p.cycle \
+             FROM reports p JOIN employee_table e ON e.employee_id = p.employee_id \
+             ORDER BY p.depth, e.employee_id"
+        ))
+        .bind::<Integer, _>(pid)
+        .bind::<Integer, _>(max_depth)
+        .load(conn)
+    }
+
+    /// Inserts the employee already linked to `user_id`.
+    pub fn add_for_user(
+        conn: &mut PgConnection,
+        obj: &PostEmployee,
+        user_id: i32,
+    ) -> Result<Employee, Error> {
+        diesel::insert_into(dsl::employee_table)
+            .values((obj, dsl::user_id.eq(user_id)))
+            .returning(Employee::as_returning())
+            .get_result(conn)
+    }
+
+    /// Links the employee to `user_id`, or unlinks them with `None`.
+    pub fn set_user(
+        conn: &mut PgConnection,
+        pid: i32,
+        user_id: Option<i32>,
+    ) -> Result<Employee, Error> {
+        diesel::update(dsl::employee_table.find(pid))
+            .set((dsl::user_id.eq(user_id), dsl::last_update.eq(get_e8_time())))
+            .returning(Employee::as_returning())
+            .get_result(conn)
+    }
+
+    /// The employee the user logs in as.
+    pub fn get_by_user(conn: &mut PgConnection, user_id: i32) -> Result<Option<Employee>, Error> {
+        dsl::employee_table
+            .filter(dsl::user_id.eq(user_id))
+            .select(Employee::as_select())
+            .first(conn)
+            .optional()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::models::{
-        employee::{PostEmployee, PatchEmployee},
+        employee::{PatchEmployee, PostEmployee},
         employee_filter::EmployeeFilter,
     };
     use crab_rocket_schema::establish_pg_connection;
@@ -299,4 +397,36 @@ mod test {
             Err(e) => println!("{e:?}"),
         }
     }
+
+    #[test]
+    fn test_reporting_lines_are_walked_recursively() {
+        use crab_rocket_schema::schema::employee_table::dsl;
+        use crab_rocket_test_support::{fixtures, test_conn};
+        use diesel::prelude::*;
+
+        let mut conn = test_conn();
+        let ceo = fixtures::employee(&mut conn, "walk_ceo", None, None);
+        let vp = fixtures::employee(&mut conn, "walk_vp", None, Some(ceo));
+        let lead = fixtures::employee(&mut conn, "walk_lead", None, Some(vp));
+        let dev = fixtures::employee(&mut conn, "walk_dev", None, Some(lead));
+
+        let chain = EmployeeMapper::management_chain(&mut conn, dev).unwrap();
+        let ids: Vec<_> = chain.iter().map(|e| (e.employee_id(), e.depth())).collect();
+        assert_eq!(ids, [(dev, 0), (lead, 1), (vp, 2), (ceo, 3)]);
+        let reports = EmployeeMapper::reports(&mut conn, ceo, 2).unwrap();
+        let ids: Vec<_> = reports.iter().map(|e| e.employee_id()).collect();
+        assert_eq!(ids, [ceo, vp, lead]);
+        assert_eq!(reports[2].direct_reports(), 1);
+
+        // A loop written around the API ends the walk instead of recursing forever.
+        diesel::update(dsl::employee_table.find(ceo))
+            .set(dsl::manager_id.eq(dev))
+            .execute(&mut conn)
+            .unwrap();
+        let chain = EmployeeMapper::management_chain(&mut conn, dev).unwrap();
+        assert_eq!(chain.len(), 5);
+        assert!(chain[4].cycle() && chain[4].employee_id() == dev);
+        let reports = EmployeeMapper::reports(&mut conn, ceo, 10).unwrap();
+        assert!(reports.last().unwrap().cycle());
+    }
 }
@@ -1,9 +1,24 @@
 use crab_rocket_utils::time::get_e8_time;
+use diesel::dsl::sql;
+use diesel::expression::SqlLiteral;
 use diesel::prelude::*;
+use diesel::sql_types::{Int4, Nullable};
 use rocket::serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-#[derive(Selectable, Insertable, Debug, Serialize, Deserialize, Queryable, ToSchema, Default)]
+/// The `amount` of the employee's salary record in effect today, usable in the select, filter
+/// and order of any `employee_table` query.
+pub fn current_salary() -> SqlLiteral<Nullable<Int4>> {
+    sql::<Nullable<Int4>>(
+        "(SELECT c.amount FROM compensation_table c \
+         WHERE c.employee_id = employee_table.employee_id AND c.component = 'salary' \
+         AND c.effective_from <= CURRENT_DATE \
+         AND (c.effective_to IS NULL OR c.effective_to >= CURRENT_DATE) \
+         ORDER BY c.effective_from DESC LIMIT 1)",
+    )
+}
+
+#[derive(Selectable, Debug, Serialize, Deserialize, Queryable, ToSchema, Default)]
 #[serde(crate = "rocket::serde")]
 #[diesel(table_name = crab_rocket_schema::schema::employee_table)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
@@ -19,6 +34,9 @@ pub struct Employee {
     phone_number: Option<String>,
     department_id: Option<i32>,
     job_title: Option<String>,
+    /// Read from the compensation history, see `POST /employee/<id>/compensation`.
+    #[diesel(select_expression = current_salary())]
+    #[diesel(select_expression_type = SqlLiteral<Nullable<Int4>>)]
     salary: Option<i32>,
     manager_id: Option<i32>,
     address: Option<String>,
@@ -27,11 +45,16 @@ pub struct Employee {
     postal_code: Option<String>,
     valid: Option<bool>,
     last_update: Option<chrono::NaiveDateTime>,
+    /// Kept in line with `role_id` by the database.
     role_name: Option<String>,
+    /// The role of the linked user when there is one.
     role_id: Option<i32>,
+    /// The user account the employee logs in with.
+    user_id: Option<i32>,
 }
 
 impl Employee {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         employee_id: i32,
         first_name: Option<String>,
@@ -54,6 +77,7 @@ impl Employee {
         last_update: Option<chrono::NaiveDateTime>,
         role_name: Option<String>,
         role_id: Option<i32>,
+        user_id: Option<i32>,
     ) -> Self {
         Self {
             employee_id,
@@ -77,6 +101,7 @@ impl Employee {
             last_update,
             role_name,
             role_id,
+            user_id,
         }
     }
 
@@ -164,6 +189,10 @@ impl Employee {
         self.role_id
     }
 
+    pub fn user_id(&self) -> Option<i32> {
+        self.user_id
+    }
+
     pub fn set_employee_id(&mut self, employee_id: i32) {
         self.employee_id = employee_id;
     }
@@ -208,10 +237,6 @@ impl Employee {
         self.job_title = job_title;
     }
 
-    pub fn set_salary(&mut self, salary: Option<i32>) {
-        self.salary = salary;
-    }
-
     pub fn set_manager_id(&mut self, manager_id: Option<i32>) {
         self.manager_id = manager_id;
     }
@@ -249,7 +274,7 @@ impl Employee {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, ToSchema, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
 #[serde(crate = "rocket::serde")]
 #[derive(Queryable, Selectable, Insertable)]
 #[diesel(table_name = crab_rocket_schema::schema::employee_table)]
@@ -265,7 +290,6 @@ pub struct PostEmployee {
     phone_number: Option<String>,
     department_id: Option<i32>,
     job_title: Option<String>,
-    salary: Option<i32>,
     manager_id: Option<i32>,
     address: Option<String>,
     city: Option<String>,
@@ -277,6 +301,7 @@ pub struct PostEmployee {
 }
 
 impl PostEmployee {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         employee_name: String,
         first_name: Option<String>,
@@ -288,7 +313,6 @@ impl PostEmployee {
         phone_number: Option<String>,
         department_id: Option<i32>,
         job_title: Option<String>,
-        salary: Option<i32>,
         manager_id: Option<i32>,
         address: Option<String>,
         city: Option<String>,
@@ -309,7 +333,6 @@ impl PostEmployee {
             phone_number,
             department_id,
             job_title,
-            salary,
             manager_id,
             address,
             city,
@@ -332,7 +355,6 @@ impl PostEmployee {
             phone_number: Some("123-456-7890".to_string()),
             department_id: Some(1),
             job_title: Some("Software Engineer".to_string()),
-            salary: Some(60000),
             manager_id: Some(2),
             address: Some("123 Main St".to_string()),
             city: Some("Anytown".to_string()),
@@ -383,10 +405,6 @@ impl PostEmployee {
         &self.job_title
     }
 
-    pub fn salary(&self) -> Option<i32> {
-        self.salary
-    }
-
     pub fn manager_id(&self) -> Option<i32> {
         self.manager_id
     }
@@ -459,10 +477,6 @@ impl PostEmployee {
         self.job_title = job_title;
     }
 
-    pub fn set_salary(&mut self, salary: Option<i32>) {
-        self.salary = salary;
-    }
-
     pub fn set_manager_id(&mut self, manager_id: Option<i32>) {
         self.manager_id = manager_id;
     }
@@ -512,7 +526,6 @@ pub struct PatchEmployee {
     phone_number: Option<String>,
     department_id: Option<i32>,
     job_title: Option<String>,
-    salary: Option<i32>,
     manager_id: Option<i32>,
     address: Option<String>,
     city: Option<String>,
@@ -524,6 +537,7 @@ pub struct PatchEmployee {
 }
 
 impl PatchEmployee {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         employee_name: String,
         first_name: Option<String>,
@@ -535,7 +549,6 @@ impl PatchEmployee {
         phone_number: Option<String>,
         department_id: Option<i32>,
         job_title: Option<String>,
-        salary: Option<i32>,
         manager_id: Option<i32>,
         address: Option<String>,
         city: Option<String>,
@@ -556,7 +569,6 @@ impl PatchEmployee {
             phone_number,
             department_id,
             job_title,
-            salary,
             manager_id,
             address,
             city,
@@ -580,7 +592,6 @@ impl PatchEmployee {
             phone_number: Some("123-456-7890".to_string()),
             department_id: Some(1),
             job_title: Some("Software Engineer".to_string()),
-            salary: Some(60000),
             manager_id: Some(2),
             address: Some("123 Main St".to_string()),
             city: Some("Anytown".to_string()),
@@ -632,10 +643,6 @@ impl PatchEmployee {
         &self.job_title
     }
 
-    pub fn salary(&self) -> Option<i32> {
-        self.salary
-    }
-
     pub fn manager_id(&self) -> Option<i32> {
         self.manager_id
     }
@@ -708,10 +715,6 @@ impl PatchEmployee {
         self.job_title = job_title;
     }
 
-    pub fn set_salary(&mut self, salary: Option<i32>) {
-        self.salary = salary;
-    }
-
     pub fn set_manager_id(&mut self, manager_id: Option<i32>) {
         self.manager_id = manager_id;
     }
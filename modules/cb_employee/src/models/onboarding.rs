@@ -0,0 +1,20 @@
+use crab_rocket_user::models::user::{PostUser, User};
+use rocket::serde::{Deserialize, Serialize};
+
+use crate::models::employee::{Employee, PostEmployee};
+
+/// A new hire's login and employee record, created together. The employee's `email`
+/// defaults to the user's and their role is the user's.
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct Onboarding {
+    pub user: PostUser,
+    pub employee: PostEmployee,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct Onboarded {
+    pub user: User,
+    pub employee: Employee,
+}
@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+
+use diesel::sql_types::{Bool, Int4, Int8, Nullable, Varchar};
+use diesel::QueryableByName;
+use rocket::serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// One employee met while walking a reporting line, as read by the recursive queries of
+/// [`EmployeeMapper`](crate::mappers::employee_mapper::EmployeeMapper).
+#[derive(QueryableByName, Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct OrgEmployee {
+    #[diesel(sql_type = Int4)]
+    employee_id: i32,
+    #[diesel(sql_type = Varchar)]
+    employee_name: String,
+    #[diesel(sql_type = Nullable<Varchar>)]
+    job_title: Option<String>,
+    #[diesel(sql_type = Nullable<Int4>)]
+    department_id: Option<i32>,
+    #[diesel(sql_type = Nullable<Int4>)]
+    manager_id: Option<i32>,
+    /// Steps away from the employee the walk started at, `1` for a direct manager or report.
+    #[diesel(sql_type = Int4)]
+    depth: i32,
+    #[diesel(sql_type = Int8)]
+    direct_reports: i64,
+    /// The employee was met before on the same walk.
+    #[diesel(sql_type = Bool)]
+    #[serde(skip)]
+    cycle: bool,
+}
+
+impl OrgEmployee {
+    pub fn employee_id(&self) -> i32 {
+        self.employee_id
+    }
+
+    pub fn employee_name(&self) -> &str {
+        &self.employee_name
+    }
+
+    pub fn manager_id(&self) -> Option<i32> {
+        self.manager_id
+    }
+
+    pub fn depth(&self) -> i32 {
+        self.depth
+    }
+
+    pub fn direct_reports(&self) -> i64 {
+        self.direct_reports
+    }
+
+    pub fn cycle(&self) -> bool {
+        self.cycle
+    }
+}
+
+/// An employee with the reports below them, down to the requested depth. `direct_reports`
+/// still counts the reports cut off by the depth limit.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct ReportNode {
+    #[serde(flatten)]
+    employee: OrgEmployee,
+    reports: Vec<ReportNode>,
+}
+
+impl ReportNode {
+    /// Nests `rows` below the employee at their root, each under their `manager_id`.
+    pub fn tree(root: OrgEmployee, rows: Vec<OrgEmployee>) -> Self {
+        let mut reports: HashMap<i32, Vec<OrgEmployee>> = HashMap::new();
+        for row in rows {
+            if let Some(manager_id) = row.manager_id {
+                reports.entry(manager_id).or_default().push(row);
+            }
+        }
+        Self::build(root, &mut reports)
+    }
+
+    fn build(employee: OrgEmployee, reports: &mut HashMap<i32, Vec<OrgEmployee>>) -> Self {
+        let direct = reports.remove(&employee.employee_id).unwrap_or_default();
+        ReportNode {
+            employee,
+            reports: direct.into_iter().map(|report| Self::build(report, reports)).collect(),
+        }
+    }
+
+    pub fn employee(&self) -> &OrgEmployee {
+        &self.employee
+    }
+
+    pub fn reports(&self) -> &[ReportNode] {
+        &self.reports
+    }
+}
@@ -1,20 +1,36 @@
+use crab_rocket_auth::guards::auth_user::AuthUser;
+use crab_rocket_auth::guards::authorized::Authorized;
+use crab_rocket_auth::permission;
 use obj_traits::controller::controller_crud::ControllerCRUD;
 use obj_traits::request::pagination_request_param::{PaginationParam, PaginationParamTrait};
 use obj_traits::request::request_param::RequestParam;
-use rocket::{delete, get, http::Status, options, patch, post, serde::json::Json};
+use obj_traits::response::api_response::ApiResponse;
+use rocket::response::status;
+use rocket::{delete, get, http::Status, options, patch, post, put, serde::json::Json};
 
 use crate::controllers::employee_controller::EmployeeController;
-use crate::models::employee::{PostEmployee, PatchEmployee};
+use crate::models::employee::{PatchEmployee, PostEmployee};
 use crate::models::employee_filter::EmployeeFilter;
+use crate::models::onboarding::Onboarding;
+
+permission!(pub EmployeeCreate, "employee", "create");
+permission!(pub EmployeeUpdate, "employee", "update");
+permission!(pub EmployeeDelete, "employee", "delete");
+permission!(pub UserCreate, "user", "create");
+
+/// Answers with `code` as the HTTP status, in the shape of the other employee routes.
+fn to_response<T: rocket::serde::Serialize + Default>(
+    code: i32,
+    message: String,
+    data: T,
+) -> status::Custom<Json<serde_json::Value>> {
+    let response = serde_json::to_value(ApiResponse::new(code, message, data)).unwrap();
+    let status = Status::from_code(code as u16).unwrap_or(Status::InternalServerError);
+    status::Custom(status, Json(response))
+}
 
 #[get("/employee?<limit>&<offset>")]
-pub fn get_employees(mut limit: Option<i32>, mut offset: Option<i32>) -> Json<serde_json::Value> {
-    if limit.is_none() {
-        limit = Some(10);
-    };
-    if offset.is_none() {
-        offset = Some(0);
-    };
+pub fn get_employees(limit: Option<i32>, offset: Option<i32>) -> Json<serde_json::Value> {
     let params = RequestParam::new(PaginationParam::new(limit, offset), None);
     println!("{:?}", params);
     crab_rocket_schema::update_reload::update_reload_count();
@@ -34,6 +50,13 @@ pub fn filter_employees(
     Json(serde_json::from_value(json_value).unwrap())
 }
 
+/// The employee the caller logs in as.
+#[get("/employee/me")]
+pub fn get_my_employee(auth: AuthUser) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = EmployeeController::get_by_user(auth.user_id());
+    to_response(code, message, data)
+}
+
 #[get("/employee/<id>")]
 pub fn get_employee_by_id(id: i32) -> Json<serde_json::Value> {
     crab_rocket_schema::update_reload::update_reload_count();
@@ -42,8 +65,29 @@ pub fn get_employee_by_id(id: i32) -> Json<serde_json::Value> {
     Json(serde_json::from_value(json_value).unwrap())
 }
 
+/// The managers above the employee, the direct manager first.
+#[get("/employee/<id>/managers")]
+pub fn get_employee_managers(id: i32) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = EmployeeController::management_chain(id);
+    to_response(code, message, data)
+}
+
+/// The employee with their direct and indirect reports as a tree, `depth` levels deep (at
+/// most and by default `MAX_REPORT_DEPTH`).
+#[get("/employee/<id>/reports?<depth>")]
+pub fn get_employee_reports(
+    id: i32,
+    depth: Option<i32>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = EmployeeController::reports(id, depth);
+    to_response(code, message, data)
+}
+
 #[post("/employee", data = "<employee>")]
-pub fn insert_single_employee(employee: Json<PostEmployee>) -> Json<serde_json::Value> {
+pub fn insert_single_employee(
+    _auth: Authorized<EmployeeCreate>,
+    employee: Json<PostEmployee>,
+) -> Json<serde_json::Value> {
     let mut obj: PostEmployee = employee.into_inner();
 
     let resp = EmployeeController::add_single(&mut obj).unwrap();
@@ -52,17 +96,56 @@ pub fn insert_single_employee(employee: Json<PostEmployee>) -> Json<serde_json::
 }
 
 #[delete("/employee/<id>")]
-pub fn delete_employee_by_id(id: i32) -> Json<serde_json::Value> {
+pub fn delete_employee_by_id(
+    _auth: Authorized<EmployeeDelete>,
+    id: i32,
+) -> Json<serde_json::Value> {
     let resp = EmployeeController::delete_by_id(id).unwrap();
     let json_value = serde_json::to_value(&resp).unwrap();
     Json(serde_json::from_value(json_value).unwrap())
 }
 
+/// A `manager_id` reporting to the employee answers `409`, so does a new `role_id` for an
+/// employee linked to a user.
 #[patch("/employee/<id>", data = "<task>")]
-pub fn update_employee_by_id(id: i32, task: Json<PatchEmployee>) -> Json<serde_json::Value> {
-    let resp = EmployeeController::update_by_id(id, &task).unwrap();
-    let json_value = serde_json::to_value(&resp).unwrap();
-    Json(serde_json::from_value(json_value).unwrap())
+pub fn update_employee_by_id(
+    _auth: Authorized<EmployeeUpdate>,
+    id: i32,
+    task: Json<PatchEmployee>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = EmployeeController::update(id, &task);
+    to_response(code, message, data)
+}
+
+/// Creates the user and their employee in one go, the employee has the role of the user.
+#[post("/employee/onboard", data = "<onboarding>")]
+pub fn onboard_employee(
+    _user: Authorized<UserCreate>,
+    _employee: Authorized<EmployeeCreate>,
+    onboarding: Json<Onboarding>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = EmployeeController::onboard(&onboarding);
+    to_response(code, message, data)
+}
+
+/// Lets the employee log in as the user, `409` when either of them is linked already.
+#[put("/employee/<id>/user/<user_id>")]
+pub fn link_employee_user(
+    _auth: Authorized<EmployeeUpdate>,
+    id: i32,
+    user_id: i32,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = EmployeeController::link_user(id, user_id);
+    to_response(code, message, data)
+}
+
+#[delete("/employee/<id>/user")]
+pub fn unlink_employee_user(
+    _auth: Authorized<EmployeeUpdate>,
+    id: i32,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = EmployeeController::unlink_user(id);
+    to_response(code, message, data)
 }
 
 #[options("/employee")]
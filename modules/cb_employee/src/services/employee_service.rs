@@ -1,14 +1,154 @@
 use crate::mappers::employee_mapper::EmployeeMapper;
-use crate::models::employee::{Employee, PostEmployee, PatchEmployee};
+use crate::models::employee::{Employee, PatchEmployee, PostEmployee};
 use crate::models::employee_filter::EmployeeFilter;
+use crate::models::onboarding::{Onboarded, Onboarding};
+use crate::models::org_chart::{OrgEmployee, ReportNode};
+use crab_rocket_schema::establish_pg_connection;
+use crab_rocket_user::mappers::user_mapper::UserMapper;
+use crab_rocket_utils::password::validate_password;
+use diesel::result::DatabaseErrorKind;
+use diesel::{Connection, ConnectionError, PgConnection};
+use obj_traits::mapper::mapper_crud::MapperCRUD;
 use obj_traits::request::pagination_request_param::PaginationParam;
 use obj_traits::request::request_param::RequestParam;
 use obj_traits::response::data::Data;
 use obj_traits::service::service_crud::{
     service_add_single, service_delete_by_id, service_filter, service_get_all, service_get_by_id,
-    service_update_by_id, ServiceCRUD,
+    ServiceCRUD,
 };
 use std::error::Error;
+use std::fmt;
+
+/// How deep `GET /employee/<id>/reports` walks at most, and without a `depth`.
+pub const MAX_REPORT_DEPTH: i32 = 10;
+
+/// Why an employee, their reporting line or their user account could not be read or changed.
+#[derive(Debug)]
+pub enum EmployeeError {
+    NotFound(i32),
+    UserNotFound(i32),
+    /// The user is not linked to an employee.
+    NoEmployee(i32),
+    /// The new manager reports to the employee, directly or indirectly.
+    ManagerCycle {
+        employee_id: i32,
+        manager_id: i32,
+    },
+    /// The stored reporting line already loops, met at `employee_id`.
+    CycleDetected {
+        employee_id: i32,
+    },
+    InvalidDepth(i32),
+    /// The employee is linked to another user already.
+    AlreadyLinked {
+        employee_id: i32,
+        user_id: i32,
+    },
+    /// The user is linked to another employee already.
+    UserTaken {
+        user_id: i32,
+        employee_id: i32,
+    },
+    /// A linked employee has the role of their user, it is changed on the user.
+    RoleFollowsUser {
+        employee_id: i32,
+        user_id: i32,
+    },
+    InvalidPassword(String),
+    /// A unique column, named by its constraint, has the value already.
+    Taken(String),
+    Internal(String),
+}
+
+impl EmployeeError {
+    pub fn status(&self) -> i32 {
+        match self {
+            EmployeeError::NotFound(_)
+            | EmployeeError::UserNotFound(_)
+            | EmployeeError::NoEmployee(_) => 404,
+            EmployeeError::InvalidDepth(_) | EmployeeError::InvalidPassword(_) => 400,
+            EmployeeError::ManagerCycle {
+                ..
+            }
+            | EmployeeError::CycleDetected {
+                ..
+            }
+            | EmployeeError::AlreadyLinked {
+                ..
+            }
+            | EmployeeError::UserTaken {
+                ..
+            }
+            | EmployeeError::RoleFollowsUser {
+                ..
+            }
+            | EmployeeError::Taken(_) => 409,
+            EmployeeError::Internal(_) => 500,
+        }
+    }
+}
+
+impl fmt::Display for EmployeeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EmployeeError::NotFound(id) => write!(f, "employee {id} not found"),
+            EmployeeError::UserNotFound(id) => write!(f, "user {id} not found"),
+            EmployeeError::NoEmployee(id) => write!(f, "user {id} is not linked to an employee"),
+            EmployeeError::ManagerCycle {
+                employee_id,
+                manager_id,
+            } => write!(
+                f,
+                "employee {manager_id} can not manage employee {employee_id}, they report to \
+                 employee {employee_id}"
+            ),
+            EmployeeError::CycleDetected {
+                employee_id,
+            } => write!(f, "the reporting line loops at employee {employee_id}"),
+            EmployeeError::InvalidDepth(depth) => {
+                write!(f, "depth {depth} is not between 1 and {MAX_REPORT_DEPTH}")
+            }
+            EmployeeError::AlreadyLinked {
+                employee_id,
+                user_id,
+            } => write!(f, "employee {employee_id} is linked to user {user_id} already"),
+            EmployeeError::UserTaken {
+                user_id,
+                employee_id,
+            } => write!(f, "user {user_id} is linked to employee {employee_id} already"),
+            EmployeeError::RoleFollowsUser {
+                employee_id,
+                user_id,
+            } => write!(
+                f,
+                "employee {employee_id} has the role of user {user_id}, change the role of the \
+                 user instead"
+            ),
+            EmployeeError::InvalidPassword(e) => write!(f, "{e}"),
+            EmployeeError::Taken(constraint) => write!(f, "`{constraint}` is taken already"),
+            EmployeeError::Internal(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for EmployeeError {}
+
+impl From<diesel::result::Error> for EmployeeError {
+    fn from(e: diesel::result::Error) -> Self {
+        match e {
+            diesel::result::Error::DatabaseError(DatabaseErrorKind::UniqueViolation, info) => {
+                EmployeeError::Taken(info.constraint_name().unwrap_or("unique").to_string())
+            }
+            e => EmployeeError::Internal(e.to_string()),
+        }
+    }
+}
+
+impl From<ConnectionError> for EmployeeError {
+    fn from(e: ConnectionError) -> Self {
+        EmployeeError::Internal(e.to_string())
+    }
+}
 
 pub struct EmployeeService {}
 
@@ -35,7 +175,7 @@ impl ServiceCRUD for EmployeeService {
     }
 
     fn update_by_id(pid: i32, obj: &PatchEmployee) -> Result<Employee, Box<dyn Error>> {
-        service_update_by_id::<Employee, EmployeeMapper, PatchEmployee>(pid, obj)
+        Ok(Self::update(pid, obj)?)
     }
     fn filter(
         param: &RequestParam<PaginationParam, EmployeeFilter>,
@@ -44,6 +184,144 @@ impl ServiceCRUD for EmployeeService {
     }
 }
 
+impl EmployeeService {
+    /// Refuses a `manager_id` that would make the employee report to themselves, and a new
+    /// `role_id` for an employee that has the role of their user.
+    pub fn update(pid: i32, obj: &PatchEmployee) -> Result<Employee, EmployeeError> {
+        let mut conn = establish_pg_connection()?;
+        conn.transaction(|conn| {
+            let employee = find(conn, pid)?;
+            if let (Some(user_id), Some(role_id)) = (employee.user_id(), obj.role_id()) {
+                if employee.role_id() != Some(role_id) {
+                    return Err(EmployeeError::RoleFollowsUser {
+                        employee_id: pid,
+                        user_id,
+                    });
+                }
+            }
+            if let Some(manager_id) = obj.manager_id() {
+                let chain = EmployeeMapper::management_chain(conn, manager_id)?;
+                if chain.iter().any(|manager| manager.employee_id() == pid) {
+                    return Err(EmployeeError::ManagerCycle {
+                        employee_id: pid,
+                        manager_id,
+                    });
+                }
+            }
+            Ok(EmployeeMapper::update_by_id(conn, pid, obj)?)
+        })
+    }
+
+    /// The managers above `pid`, the direct manager first.
+    pub fn management_chain(pid: i32) -> Result<Vec<OrgEmployee>, EmployeeError> {
+        let mut conn = establish_pg_connection()?;
+        let mut chain = EmployeeMapper::management_chain(&mut conn, pid)?;
+        if chain.is_empty() {
+            return Err(EmployeeError::NotFound(pid));
+        }
+        check_cycle(&chain)?;
+        chain.remove(0);
+        Ok(chain)
+    }
+
+    /// `pid` with their direct and indirect reports nested below them, `depth` levels deep.
+    pub fn reports(pid: i32, depth: Option<i32>) -> Result<ReportNode, EmployeeError> {
+        let depth = depth.unwrap_or(MAX_REPORT_DEPTH);
+        if !(1..=MAX_REPORT_DEPTH).contains(&depth) {
+            return Err(EmployeeError::InvalidDepth(depth));
+        }
+        let mut conn = establish_pg_connection()?;
+        let mut rows = EmployeeMapper::reports(&mut conn, pid, depth)?;
+        if rows.is_empty() {
+            return Err(EmployeeError::NotFound(pid));
+        }
+        check_cycle(&rows)?;
+        let root = rows.remove(0);
+        Ok(ReportNode::tree(root, rows))
+    }
+
+    /// Lets the employee log in as `user_id`, from then on they have the role of the user.
+    /// Linking the same pair again changes nothing.
+    pub fn link_user(pid: i32, user_id: i32) -> Result<Employee, EmployeeError> {
+        let mut conn = establish_pg_connection()?;
+        conn.transaction(|conn| {
+            let employee = find(conn, pid)?;
+            match employee.user_id() {
+                Some(linked) if linked == user_id => return Ok(employee),
+                Some(linked) => {
+                    return Err(EmployeeError::AlreadyLinked {
+                        employee_id: pid,
+                        user_id: linked,
+                    })
+                }
+                None => {}
+            }
+            if let Err(diesel::result::Error::NotFound) = UserMapper::get_by_id(conn, user_id) {
+                return Err(EmployeeError::UserNotFound(user_id));
+            }
+            if let Some(other) = EmployeeMapper::get_by_user(conn, user_id)? {
+                return Err(EmployeeError::UserTaken {
+                    user_id,
+                    employee_id: other.employee_id(),
+                });
+            }
+            Ok(EmployeeMapper::set_user(conn, pid, Some(user_id))?)
+        })
+    }
+
+    /// Removes the link to the employee's user, they keep the role they had.
+    pub fn unlink_user(pid: i32) -> Result<Employee, EmployeeError> {
+        let mut conn = establish_pg_connection()?;
+        conn.transaction(|conn| {
+            find(conn, pid)?;
+            Ok(EmployeeMapper::set_user(conn, pid, None)?)
+        })
+    }
+
+    /// The employee `user_id` logs in as.
+    pub fn get_by_user(user_id: i32) -> Result<Employee, EmployeeError> {
+        let mut conn = establish_pg_connection()?;
+        EmployeeMapper::get_by_user(&mut conn, user_id)?.ok_or(EmployeeError::NoEmployee(user_id))
+    }
+
+    /// Creates the user and the employee linked to them, or neither.
+    pub fn onboard(obj: &Onboarding) -> Result<Onboarded, EmployeeError> {
+        validate_password(obj.user.password())
+            .map_err(|e| EmployeeError::InvalidPassword(e.to_string()))?;
+        let mut conn = establish_pg_connection()?;
+        conn.transaction(|conn| {
+            let user = UserMapper::add_single(conn, &obj.user)?;
+            let employee = if obj.employee.email().is_none() {
+                let mut employee = obj.employee.clone();
+                employee.set_email(user.email().clone());
+                EmployeeMapper::add_for_user(conn, &employee, user.user_id())?
+            } else {
+                EmployeeMapper::add_for_user(conn, &obj.employee, user.user_id())?
+            };
+            Ok(Onboarded {
+                user,
+                employee,
+            })
+        })
+    }
+}
+
+fn find(conn: &mut PgConnection, pid: i32) -> Result<Employee, EmployeeError> {
+    match EmployeeMapper::get_by_id(conn, pid) {
+        Err(diesel::result::Error::NotFound) => Err(EmployeeError::NotFound(pid)),
+        result => Ok(result?),
+    }
+}
+
+fn check_cycle(rows: &[OrgEmployee]) -> Result<(), EmployeeError> {
+    match rows.iter().find(|row| row.cycle()) {
+        Some(row) => Err(EmployeeError::CycleDetected {
+            employee_id: row.employee_id(),
+        }),
+        None => Ok(()),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::services::employee_service::EmployeeService;
@@ -1,4 +1,5 @@
 use crate::models::file::{File, PostFile};
+use crab_rocket_config::app_config;
 use crab_rocket_schema::schema::file_table::dsl::*;
 use crab_rocket_schema::schema::file_table::{self};
 use diesel::prelude::*;
@@ -15,8 +16,7 @@ pub async fn insert_files(
     for mut f in files {
         // 如果 base_dir 为 None，则使用默认值
         //遍历文件列表
-        let key_value = std::env::var("CRAB_ROCKET_WORKING_DIR").unwrap();
-        let upload_folder = format!("{}/upload", key_value);
+        let upload_folder = app_config().storage.upload_dir.to_string_lossy().to_string();
 
         println!("upload_folder: {:?}", upload_folder);
         let original_file_name =
@@ -57,18 +57,68 @@ pub fn fetch_all_files(conn: &mut PgConnection) -> Result<Vec<File>, diesel::res
     file_table.order(file_table::uploaded_at.desc()).load::<File>(conn)
 }
 
+/// Files whose `uploaded_at` is older than `cutoff`, oldest first.
+pub fn fetch_files_uploaded_before(
+    conn: &mut PgConnection,
+    cutoff: chrono::NaiveDateTime,
+) -> Result<Vec<File>, diesel::result::Error> {
+    file_table
+        .filter(file_table::uploaded_at.lt(cutoff))
+        .order(file_table::uploaded_at.asc())
+        .load::<File>(conn)
+}
+
+/// How many files other than `uuid` are stored at `url`, uploads keep their original name so
+/// several rows can point at one file.
+pub fn count_other_files_at(
+    conn: &mut PgConnection,
+    url: &str,
+    uuid: Uuid,
+) -> Result<i64, diesel::result::Error> {
+    file_table
+        .filter(file_table::file_url.eq(url))
+        .filter(file_table::file_id.ne(uuid))
+        .count()
+        .get_result(conn)
+}
+
+pub fn delete_file_by_uuid(
+    conn: &mut PgConnection,
+    uuid: Uuid,
+) -> Result<usize, diesel::result::Error> {
+    diesel::delete(file_table.filter(file_table::file_id.eq(uuid))).execute(conn)
+}
+
 #[cfg(test)]
 mod test {
-    use crate::mappers::file_mapper::fetch_all_files;
-    use crab_rocket_schema::establish_pg_connection;
+    use crate::mappers::file_mapper::{
+        delete_file_by_uuid, fetch_all_files, fetch_files_uploaded_before,
+    };
+    use crab_rocket_test_support::{fixtures, test_conn};
+    use crab_rocket_utils::time::get_e8_time;
+
     #[test]
     fn test_fetch_all_files() {
-        match establish_pg_connection() {
-            Ok(mut conn) => {
-                let all_files = fetch_all_files(&mut conn);
-                println!("{all_files:?}");
-            }
-            Err(_) => (),
-        }
+        let mut conn = test_conn();
+        let file_id = fixtures::file(&mut conn, "report.pdf", None);
+
+        let all_files = fetch_all_files(&mut conn).unwrap();
+        assert!(all_files.iter().any(|f| f.file_id == file_id && f.file_name == "report.pdf"));
+    }
+
+    #[test]
+    fn test_purge_old_files() {
+        let mut conn = test_conn();
+        let old = get_e8_time() - chrono::Duration::days(400);
+        let old_id = fixtures::file(&mut conn, "old.png", Some(old));
+        let new_id = fixtures::file(&mut conn, "new.png", Some(get_e8_time()));
+
+        let cutoff = get_e8_time() - chrono::Duration::days(365);
+        let stale = fetch_files_uploaded_before(&mut conn, cutoff).unwrap();
+        assert!(stale.iter().any(|f| f.file_id == old_id));
+        assert!(!stale.iter().any(|f| f.file_id == new_id));
+
+        assert_eq!(delete_file_by_uuid(&mut conn, old_id).unwrap(), 1);
+        assert!(!fetch_all_files(&mut conn).unwrap().iter().any(|f| f.file_id == old_id));
     }
 }
@@ -1,17 +1,16 @@
+use crab_rocket_auth::guards::authorized::Authorized;
+use crab_rocket_config::app_config;
 use rand::Rng;
-use rocket::data::ToByteUnit;
-use rocket::http::uri::Absolute;
 use rocket::request::FromParam;
 use rocket::uri;
 use rocket::UriDisplayPath;
 use rocket::{fs::NamedFile, get, post, Data};
 use std::{
     borrow::Cow,
-    path::{Path, PathBuf},
+    path::PathBuf,
 };
 
-// In a real application, these would be retrieved dynamically from a config.
-const HOST: Absolute<'static> = uri!("http://localhost:8000");
+use crate::routes::form_file_route::FileCreate;
 
 #[derive(UriDisplayPath)]
 pub struct PasteId<'a>(Cow<'a, str>);
@@ -28,8 +27,7 @@ impl PasteId<'_> {
     }
 
     pub fn file_path(&self) -> PathBuf {
-        let root = concat!(env!("CARGO_MANIFEST_DIR"), "/", "upload");
-        Path::new(root).join(&self.0.as_ref())
+        app_config().storage.upload_dir.join(self.0.as_ref())
     }
 }
 
@@ -46,17 +44,17 @@ impl<'a> FromParam<'a> for PasteId<'a> {
 }
 #[get("/static_file/<file..>")]
 pub async fn files(file: PathBuf) -> Option<NamedFile> {
-    NamedFile::open(Path::new("static/").join(file)).await.ok()
+    NamedFile::open(app_config().storage.static_dir.join(file)).await.ok()
 }
 #[get("/retrieve_bin/<id>")]
 pub async fn retrieve_bin(id: PasteId<'_>) -> Option<rocket::fs::NamedFile> {
     rocket::fs::NamedFile::open(id.file_path()).await.ok()
 }
 #[post("/upload_bin", data = "<paste>")]
-pub async fn upload_bin(paste: Data<'_>) -> std::io::Result<String> {
+pub async fn upload_bin(_auth: Authorized<FileCreate>, paste: Data<'_>) -> std::io::Result<String> {
     // println!("{:?}", paste);
     let id = PasteId::new(3);
     let path = id.file_path();
-    paste.open(128.megabytes()).into_file(path).await?; //设置文件大小限制
-    Ok(uri!(HOST, retrieve_bin(id)).to_string())
+    paste.open(app_config().upload.max_file_size).into_file(path).await?; //设置文件大小限制
+    Ok(app_config().public_url(&format!("/api{}", uri!(retrieve_bin(id)))))
 }
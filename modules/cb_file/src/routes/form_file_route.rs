@@ -7,6 +7,9 @@ use crate::models::file::File;
 use crate::models::file_response::{FileDownloadResponse, FileRetrieveResponse};
 use crate::models::upload::{AvatarUpload, Upload};
 use crate::services::file_service::GetFile;
+use crab_rocket_auth::guards::authorized::Authorized;
+use crab_rocket_auth::permission;
+use crab_rocket_config::app_config;
 use mime_guess::mime;
 use rocket::form::Form;
 use rocket::http::Status;
@@ -17,10 +20,23 @@ use rocket::{get, options, post};
 use serde_json::json;
 use uuid::Uuid;
 
+permission!(pub FileCreate, "file", "create");
+
 #[post("/upload", data = "<upload>")]
-pub async fn upload(upload: Form<Upload<'_>>) -> Json<serde_json::Value> {
+pub async fn upload(
+    _auth: Authorized<FileCreate>,
+    upload: Form<Upload<'_>>,
+) -> Json<serde_json::Value> {
     println!("{:?}", upload.file);
     let upload_data = upload.into_inner();
+    let max_files = app_config().upload.max_files;
+    if upload_data.file.len() > max_files {
+        return Json(json!({
+            "code": 400,
+            "message": format!("At most {max_files} files can be uploaded at once."),
+            "data": null
+        }));
+    }
     // 如果 save 字段为 true，保存文件
     // 在这里处理上传的文件，例如将其保存到磁盘或进行其他操作
     // 例如：
@@ -51,7 +67,10 @@ pub async fn retrieve_file(uuid: Uuid) -> Option<FileRetrieveResponse> {
 }
 
 #[post("/avatar_upload", data = "<upload>")]
-pub async fn upload_avatar(upload: Form<AvatarUpload<'_>>) -> Json<serde_json::Value> {
+pub async fn upload_avatar(
+    _auth: Authorized<FileCreate>,
+    upload: Form<AvatarUpload<'_>>,
+) -> Json<serde_json::Value> {
     let upload_data = upload.into_inner();
 
     // 验证上传的文件是否为图片类型
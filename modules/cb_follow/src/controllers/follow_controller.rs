@@ -1,5 +1,8 @@
 use std::error::Error;
 
+use crab_rocket_auth::error::AuthError;
+use crab_rocket_auth::guards::auth_user::AuthUser;
+use crab_rocket_auth::services::ownership_service::OwnershipError;
 use obj_traits::{
     controller::controller_crud::{
         controller_add_single, controller_delete_by_id, controller_filter, controller_get_all,
@@ -58,6 +61,53 @@ impl ControllerCRUD for FollowController {
     }
 }
 
+fn from_ownership_error<T>(e: OwnershipError) -> (i32, String, Option<T>) {
+    match e {
+        OwnershipError::Auth(AuthError::Internal(_)) => {
+            println!("{e:?}");
+            (500, String::from("Internal Server Error"), None)
+        }
+        e => (e.status(), e.to_string(), None),
+    }
+}
+
+impl FollowController {
+    pub fn add_owned(auth: &AuthUser, obj: &PostFollow) -> (i32, String, Option<Follow>) {
+        match FollowService::add_owned(auth, obj) {
+            Ok(follow) => (200, String::from("Success"), Some(follow)),
+            Err(e) => from_ownership_error(e),
+        }
+    }
+
+    pub fn update_owned(
+        auth: &AuthUser,
+        pid: i32,
+        obj: &PatchFollow,
+    ) -> (i32, String, Option<Follow>) {
+        match FollowService::update_owned(auth, pid, obj) {
+            Ok(follow) => (200, String::from("Success"), Some(follow)),
+            Err(e) => from_ownership_error(e),
+        }
+    }
+
+    pub fn delete_owned(auth: &AuthUser, pid: i32) -> (i32, String, Option<Follow>) {
+        match FollowService::delete_owned(auth, pid) {
+            Ok(follow) => (200, String::from("Success"), Some(follow)),
+            Err(e) => from_ownership_error(e),
+        }
+    }
+
+    pub fn delete_specifically_owned(
+        auth: &AuthUser,
+        obj: &PostFollow,
+    ) -> (i32, String, Option<Follow>) {
+        match FollowService::delete_specifically_owned(auth, obj) {
+            Ok(follow) => (200, String::from("Success"), Some(follow)),
+            Err(e) => from_ownership_error(e),
+        }
+    }
+}
+
 impl FollowControllerTrait<RequestParam<PaginationParam, FollowFilter>> for FollowController {
     fn delete_follow_specifically(
         obj: &PostFollow,
@@ -8,7 +8,7 @@ use obj_traits::{
 };
 
 use crate::models::{
-    follow::{Follow, PostFollow, PatchFollow},
+    follow::{Follow, PatchFollow, PostFollow},
     follow_filter::FollowFilter,
 };
 use crab_rocket_schema::schema::follow_table::dsl;
@@ -41,8 +41,8 @@ impl MapperCRUD for FollowMapper {
         //
         // limit 始终为 per_page
         // 计算分页相关
-        let page = (param.pagination.offset.unwrap() / param.pagination.limit.unwrap()) + 1;
-        let per_page = param.pagination.limit.unwrap();
+        let page = (param.pagination.offset_or_default() / param.pagination.limit_or_default()) + 1;
+        let per_page = param.pagination.limit_or_default();
         // 获取总记录数
         let total_count = dsl::follow_table.count().get_result::<i64>(conn)? as i32;
         // 计算总页数
@@ -62,6 +62,7 @@ impl MapperCRUD for FollowMapper {
         // 分页查询
         let data = dsl::follow_table
             .order(dsl::created_at.desc())
+            .then_order_by(dsl::follow_id.desc())
             .limit(per_page as i64)
             .offset(((page - 1) * per_page) as i64)
             .load::<Follow>(conn)?;
@@ -134,8 +135,8 @@ impl MapperCRUD for FollowMapper {
         let filter = &param.filter;
         println!("{filter:?}");
         // 计算分页相关
-        let page = (param.pagination.offset.unwrap() / param.pagination.limit.unwrap()) + 1;
-        let per_page = param.pagination.limit.unwrap();
+        let page = (param.pagination.offset_or_default() / param.pagination.limit_or_default()) + 1;
+        let per_page = param.pagination.limit_or_default();
         // 获取总记录数
         let total_count = dsl::follow_table.count().get_result::<i64>(conn)? as i32;
         // 计算总页数
@@ -157,6 +158,7 @@ impl MapperCRUD for FollowMapper {
         // 分页查询
         query = query
             .order(dsl::created_at.desc())
+            .then_order_by(dsl::follow_id.desc())
             .limit(per_page as i64)
             .offset(((page - 1) * per_page) as i64);
 
@@ -225,8 +227,8 @@ impl FollowMapperTrait for FollowMapper {
         //
         // limit 始终为 per_page
         // 计算分页相关
-        let page = (param.pagination.offset.unwrap() / param.pagination.limit.unwrap()) + 1;
-        let per_page = param.pagination.limit.unwrap();
+        let page = (param.pagination.offset_or_default() / param.pagination.limit_or_default()) + 1;
+        let per_page = param.pagination.limit_or_default();
         // 获取总记录数
         let total_count = dsl::follow_table.count().get_result::<i64>(conn)? as i32;
         // 计算总页数
@@ -247,6 +249,7 @@ impl FollowMapperTrait for FollowMapper {
         let data = dsl::follow_table
             .filter(dsl::followed_user_id.eq(uid))
             .order(dsl::created_at.desc())
+            .then_order_by(dsl::follow_id.desc())
             .limit(per_page as i64)
             .offset(((page - 1) * per_page) as i64)
             .load::<Follow>(conn)?;
@@ -275,8 +278,8 @@ impl FollowMapperTrait for FollowMapper {
         //
         // limit 始终为 per_page
         // 计算分页相关
-        let page = (param.pagination.offset.unwrap() / param.pagination.limit.unwrap()) + 1;
-        let per_page = param.pagination.limit.unwrap();
+        let page = (param.pagination.offset_or_default() / param.pagination.limit_or_default()) + 1;
+        let per_page = param.pagination.limit_or_default();
         // 获取总记录数
         let total_count = dsl::follow_table.count().get_result::<i64>(conn)? as i32;
         // 计算总页数
@@ -297,6 +300,7 @@ impl FollowMapperTrait for FollowMapper {
         let data = dsl::follow_table
             .filter(dsl::following_user_id.eq(uid))
             .order(dsl::created_at.desc())
+            .then_order_by(dsl::follow_id.desc())
             .limit(per_page as i64)
             .offset(((page - 1) * per_page) as i64)
             .load::<Follow>(conn)?;
@@ -312,82 +316,69 @@ pub fn check_exist_follow(
     check_followed_user_id: i32,
 ) -> bool {
     // 执行查询，查看是否存在给定的关注关系
-    match dsl::follow_table
+    dsl::follow_table
         .filter(
             dsl::following_user_id
                 .eq(check_following_user_id)
                 .and(dsl::followed_user_id.eq(check_followed_user_id)),
         )
         .first::<Follow>(conn)
-    {
-        Ok(_) => true,   // 如果查询到结果，则关注关系存在
-        Err(_) => false, // 如果没有查询到结果，则关注关系不存在
-    }
+        .is_ok()
 }
 
 #[cfg(test)]
 mod test {
     use obj_traits::mapper::mapper_crud::MapperCRUD;
 
+    use super::check_exist_follow;
     use crate::{
         mappers::{follow_mapper::FollowMapper, follow_mapper_trait::FollowMapperTrait},
         models::follow::PostFollow,
     };
+    use crab_rocket_test_support::{fixtures, test_conn};
 
     #[test]
     fn test_create_new_follow() {
-        use crate::models::follow::PostFollow;
-        use crab_rocket_schema::establish_pg_connection;
-        let follow = PostFollow::new(1, 3, None);
-        match establish_pg_connection() {
-            Ok(mut conn) => match FollowMapper::add_single(&mut conn, &follow) {
-                Ok(inserted_follow) => println!("{inserted_follow:?}"),
-                Err(e) => {
-                    println!("{e:?}");
-                    ()
-                }
-            },
-            Err(_) => (),
-        }
+        let mut conn = test_conn();
+        let (alice, bob) = (fixtures::user(&mut conn, "alice"), fixtures::user(&mut conn, "bob"));
+
+        let follow = PostFollow::new(alice, bob, None);
+        let inserted = FollowMapper::add_single(&mut conn, &follow).unwrap();
+        assert_eq!(inserted.following_user_id(), alice);
+        assert_eq!(inserted.followed_user_id(), bob);
+        // The same relation can not be created twice.
+        assert!(FollowMapper::add_single(&mut conn, &follow).is_err());
     }
 
     #[test]
     fn test_check_exist_follow() {
-        use super::check_exist_follow;
-        use crab_rocket_schema::establish_pg_connection;
-        let following_id = 1;
-        let followed_id = 3;
-        match establish_pg_connection() {
-            Ok(mut conn) => {
-                let result = check_exist_follow(&mut conn, following_id, followed_id);
-                println!("following: {following_id} -> followed: {followed_id}: {result}");
-            }
-            Err(_) => (),
-        }
+        let mut conn = test_conn();
+        let (alice, bob) = (fixtures::user(&mut conn, "alice"), fixtures::user(&mut conn, "bob"));
+        fixtures::follow(&mut conn, alice, bob);
+
+        assert!(check_exist_follow(&mut conn, alice, bob));
+        assert!(!check_exist_follow(&mut conn, bob, alice));
     }
 
     #[test]
     fn test_delete_follow() {
-        use crab_rocket_schema::establish_pg_connection;
-        match establish_pg_connection() {
-            Ok(mut conn) => {
-                let deleted_follow = FollowMapper::delete_by_id(&mut conn, 1);
-                println!("{deleted_follow:?}");
-            }
-            Err(_) => (),
-        }
+        let mut conn = test_conn();
+        let (alice, bob) = (fixtures::user(&mut conn, "alice"), fixtures::user(&mut conn, "bob"));
+        let follow_id = fixtures::follow(&mut conn, alice, bob);
+
+        let deleted = FollowMapper::delete_by_id(&mut conn, follow_id).unwrap();
+        assert_eq!(deleted.follow_id(), follow_id);
+        assert!(!check_exist_follow(&mut conn, alice, bob));
     }
 
     #[test]
     fn test_delete_follow_specifically() {
-        use crab_rocket_schema::establish_pg_connection;
-        let follow = PostFollow::demo();
-        match establish_pg_connection() {
-            Ok(mut conn) => {
-                let deleted_follow = FollowMapper::delete_follow_specifically(&mut conn, &follow);
-                println!("{deleted_follow:?}");
-            }
-            Err(_) => (),
-        }
+        let mut conn = test_conn();
+        let (alice, bob) = (fixtures::user(&mut conn, "alice"), fixtures::user(&mut conn, "bob"));
+        fixtures::follow(&mut conn, alice, bob);
+
+        let follow = PostFollow::new(alice, bob, None);
+        assert!(FollowMapper::delete_follow_specifically(&mut conn, &follow).is_ok());
+        assert!(FollowMapper::delete_follow_specifically(&mut conn, &follow).is_err());
     }
 }
@@ -1,3 +1,4 @@
+use crab_rocket_auth::guards::auth_user::AuthUser;
 use crab_rocket_utils::time::get_e8_time;
 use obj_traits::{
     controller::controller_crud::ControllerCRUD,
@@ -5,28 +6,32 @@ use obj_traits::{
         pagination_request_param::{PaginationParam, PaginationParamTrait},
         request_param::RequestParam,
     },
+    response::api_response::ApiResponse,
 };
-use rocket::{delete, get, patch, post, serde::json::Json};
+use rocket::{delete, get, http::Status, patch, post, response::status, serde::json::Json};
 
 use crate::{
-    controllers::{
-        follow_controller::FollowController, follow_controller_trait::FollowControllerTrait,
-    },
+    controllers::follow_controller::FollowController,
     models::{
         follow::{PostFollow, PatchFollow},
         follow_filter::FollowFilter,
     },
 };
 
+/// Answers with `code` as the HTTP status, in the shape of the other follow routes.
+fn to_response<T: rocket::serde::Serialize + Default>(
+    code: i32,
+    message: String,
+    data: T,
+) -> status::Custom<Json<serde_json::Value>> {
+    let response = serde_json::to_value(ApiResponse::new(code, message, data)).unwrap();
+    let status = Status::from_code(code as u16).unwrap_or(Status::InternalServerError);
+    status::Custom(status, Json(response))
+}
+
 #[get("/follow?<limit>&<offset>")]
-pub fn get_follows(mut limit: Option<i32>, mut offset: Option<i32>) -> Json<serde_json::Value> {
+pub fn get_follows(limit: Option<i32>, offset: Option<i32>) -> Json<serde_json::Value> {
     crab_rocket_schema::update_reload::update_reload_count();
-    if limit.is_none() {
-        limit = Some(10);
-    };
-    if offset.is_none() {
-        offset = Some(0);
-    };
     let params = RequestParam::new(PaginationParam::new(limit, offset), None);
     let resp = FollowController::get_all(&params).unwrap();
     let json_value = serde_json::to_value(&resp).unwrap();
@@ -45,41 +50,55 @@ pub fn filter_follows(
     Json(serde_json::from_value(json_value).unwrap())
 }
 
+/// The caller follows `follow_id`, only moderators may name another `follower_id`.
 #[post("/follow?<follower_id>&<follow_id>")]
-pub fn insert_single_follow(follower_id: i32, follow_id: i32) -> Json<serde_json::Value> {
-    let mut obj: PostFollow = PostFollow::new(follower_id, follow_id, Some(get_e8_time()));
-    let resp = FollowController::add_single(&mut obj).unwrap();
-    let json_value = serde_json::to_value(&resp).unwrap();
-    Json(serde_json::from_value(json_value).unwrap())
+pub fn insert_single_follow(
+    auth: AuthUser,
+    follower_id: i32,
+    follow_id: i32,
+) -> status::Custom<Json<serde_json::Value>> {
+    let obj: PostFollow = PostFollow::new(follower_id, follow_id, Some(get_e8_time()));
+    let (code, message, data) = FollowController::add_owned(&auth, &obj);
+    to_response(code, message, data)
 }
 
+/// The caller follows `followed_user_id`, only moderators may name another
+/// `following_user_id`.
 #[post("/follow", data = "<follow>")]
-pub fn insert_single_follow_by_params(follow: Json<PostFollow>) -> Json<serde_json::Value> {
-    let mut obj: PostFollow = follow.into_inner();
-    let resp = FollowController::add_single(&mut obj).unwrap();
-    let json_value = serde_json::to_value(&resp).unwrap();
-    Json(serde_json::from_value(json_value).unwrap())
+pub fn insert_single_follow_by_params(
+    auth: AuthUser,
+    follow: Json<PostFollow>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = FollowController::add_owned(&auth, &follow);
+    to_response(code, message, data)
 }
 
+/// Only the follower, or a role granted `follow:moderate`.
 #[delete("/follow/<id>")]
-pub fn delete_follow_by_id(id: i32) -> Json<serde_json::Value> {
-    let resp = FollowController::delete_by_id(id).unwrap();
-    let json_value = serde_json::to_value(&resp).unwrap();
-    Json(serde_json::from_value(json_value).unwrap())
+pub fn delete_follow_by_id(auth: AuthUser, id: i32) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = FollowController::delete_owned(&auth, id);
+    to_response(code, message, data)
 }
 
+/// Only the follower, or a role granted `follow:moderate`.
 #[patch("/follow/<id>", data = "<follow>")]
-pub fn update_follow_by_id(id: i32, follow: Json<PatchFollow>) -> Json<serde_json::Value> {
-    let resp = FollowController::update_by_id(id, &follow).unwrap();
-    let json_value = serde_json::to_value(&resp).unwrap();
-    Json(serde_json::from_value(json_value).unwrap())
+pub fn update_follow_by_id(
+    auth: AuthUser,
+    id: i32,
+    follow: Json<PatchFollow>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = FollowController::update_owned(&auth, id, &follow);
+    to_response(code, message, data)
 }
 
+/// Only the follower, or a role granted `follow:moderate`.
 #[delete("/follow/spec", data = "<follow>")]
-pub fn delete_follow_specifically(follow: Json<PostFollow>) -> Json<serde_json::Value> {
-    let resp = FollowController::delete_follow_specifically(&follow).unwrap();
-    let json_value = serde_json::to_value(&resp).unwrap();
-    Json(serde_json::from_value(json_value).unwrap())
+pub fn delete_follow_specifically(
+    auth: AuthUser,
+    follow: Json<PostFollow>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = FollowController::delete_specifically_owned(&auth, &follow);
+    to_response(code, message, data)
 }
 
 // #[post("/follow/<uid>/followeds", data = "<param>")]
@@ -1,5 +1,7 @@
 use std::error::Error;
 
+use crab_rocket_auth::guards::auth_user::AuthUser;
+use crab_rocket_auth::services::ownership_service::{OwnershipError, OwnershipService};
 use crab_rocket_schema::establish_pg_connection;
 use obj_traits::{
     request::{pagination_request_param::PaginationParam, request_param::RequestParam},
@@ -54,6 +56,57 @@ impl ServiceCRUD for FollowService {
     }
 }
 
+const RESOURCE: &str = "follow";
+
+/// A follow belongs to its follower, `following_user_id`.
+impl FollowService {
+    /// Makes the caller follow `followed_user_id`, moderators may name another follower.
+    pub fn add_owned(auth: &AuthUser, obj: &PostFollow) -> Result<Follow, OwnershipError> {
+        let mut obj = obj.clone();
+        let follower = Some(obj.following_user_id());
+        obj.set_following_user_id(OwnershipService::assign_owner(auth, RESOURCE, follower)?);
+        Ok(Self::add_single(&obj)?)
+    }
+
+    /// Replaces follow `pid` when the caller is its follower or moderates follows, only
+    /// moderators hand it to another follower.
+    pub fn update_owned(
+        auth: &AuthUser,
+        pid: i32,
+        obj: &PatchFollow,
+    ) -> Result<Follow, OwnershipError> {
+        let follow = Self::get_by_id(pid).map_err(|e| OwnershipError::lookup(RESOURCE, pid, e))?;
+        let follower = follow.following_user_id();
+        OwnershipService::authorize(auth, RESOURCE, "update", pid, Some(follower))?;
+        if obj.following_user_id() != follower {
+            OwnershipService::assign_owner(auth, RESOURCE, Some(obj.following_user_id()))?;
+        }
+        Ok(Self::update_by_id(pid, obj)?)
+    }
+
+    pub fn delete_owned(auth: &AuthUser, pid: i32) -> Result<Follow, OwnershipError> {
+        let follow = Self::get_by_id(pid).map_err(|e| OwnershipError::lookup(RESOURCE, pid, e))?;
+        OwnershipService::authorize(
+            auth,
+            RESOURCE,
+            "delete",
+            pid,
+            Some(follow.following_user_id()),
+        )?;
+        Ok(Self::delete_by_id(pid)?)
+    }
+
+    /// Like [`FollowServiceTrait::delete_follow_specifically`], for the caller's own follows
+    /// unless the caller moderates follows.
+    pub fn delete_specifically_owned(
+        auth: &AuthUser,
+        obj: &PostFollow,
+    ) -> Result<Follow, OwnershipError> {
+        OwnershipService::assign_owner(auth, RESOURCE, Some(obj.following_user_id()))?;
+        Ok(Self::delete_follow_specifically(obj)?)
+    }
+}
+
 impl FollowServiceTrait<RequestParam<PaginationParam, FollowFilter>> for FollowService {
     fn delete_follow_specifically(obj: &PostFollow) -> Result<Follow, Box<dyn std::error::Error>> {
         match establish_pg_connection() {
@@ -0,0 +1,14 @@
+use crate::{models::health::Health, services::health_service::CheckHealth};
+
+pub fn get_live() -> (i32, String, Health) {
+    (200, String::from("Ok"), Health::check_live())
+}
+
+pub fn get_ready() -> (i32, String, Health) {
+    let health = Health::check_ready();
+    if health.is_up() {
+        (200, String::from("Ok"), health)
+    } else {
+        (503, String::from("Service Unavailable"), health)
+    }
+}
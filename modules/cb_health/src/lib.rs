@@ -0,0 +1,21 @@
+pub mod models {
+    pub mod health;
+}
+
+pub mod mappers {
+    pub mod health_mapper;
+}
+
+pub mod controllers {
+    pub mod health_controller;
+}
+
+pub mod routes {
+    pub mod health_route;
+}
+pub mod services {
+    pub mod health_service;
+    pub mod r#impl {
+        pub mod health_impl;
+    }
+}
@@ -0,0 +1,28 @@
+use crab_rocket_schema::migrations::pending_migrations;
+use diesel::{sql_query, PgConnection, RunQueryDsl};
+
+/// Round-trips a trivial query to make sure the connection is usable.
+pub fn ping(conn: &mut PgConnection) -> Result<(), diesel::result::Error> {
+    sql_query("SELECT 1").execute(conn)?;
+    Ok(())
+}
+
+/// Versions of the embedded migrations that the database is still missing.
+pub fn fetch_pending_migrations(
+    conn: &mut PgConnection,
+) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+    pending_migrations(conn)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{fetch_pending_migrations, ping};
+    use crab_rocket_test_support::test_conn;
+
+    #[test]
+    fn test_ping() {
+        let mut conn = test_conn();
+        assert!(ping(&mut conn).is_ok());
+        assert!(fetch_pending_migrations(&mut conn).is_ok());
+    }
+}
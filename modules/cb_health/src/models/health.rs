@@ -0,0 +1,94 @@
+use std::collections::BTreeMap;
+
+use rocket::serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(crate = "rocket::serde", rename_all = "lowercase")]
+pub enum HealthStatus {
+    #[default]
+    Up,
+    Down,
+}
+
+/// The state of a single dependency checked by the readiness probe.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct ComponentHealth {
+    status: HealthStatus,
+    message: Option<String>,
+}
+
+impl ComponentHealth {
+    pub fn up(message: Option<String>) -> Self {
+        Self {
+            status: HealthStatus::Up,
+            message,
+        }
+    }
+
+    pub fn down(message: String) -> Self {
+        Self {
+            status: HealthStatus::Down,
+            message: Some(message),
+        }
+    }
+
+    pub fn status(&self) -> HealthStatus {
+        self.status
+    }
+
+    pub fn message(&self) -> Option<&String> {
+        self.message.as_ref()
+    }
+}
+
+/// Overall health, `up` only when every component is `up`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct Health {
+    status: HealthStatus,
+    components: BTreeMap<String, ComponentHealth>,
+}
+
+impl Health {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_component(mut self, name: &str, component: ComponentHealth) -> Self {
+        if component.status() == HealthStatus::Down {
+            self.status = HealthStatus::Down;
+        }
+        self.components.insert(name.to_string(), component);
+        self
+    }
+
+    pub fn status(&self) -> HealthStatus {
+        self.status
+    }
+
+    pub fn is_up(&self) -> bool {
+        self.status == HealthStatus::Up
+    }
+
+    pub fn components(&self) -> &BTreeMap<String, ComponentHealth> {
+        &self.components
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::models::health::{ComponentHealth, Health, HealthStatus};
+
+    #[test]
+    fn test_health_is_down_when_any_component_is_down() {
+        let health = Health::new()
+            .with_component("database", ComponentHealth::up(None))
+            .with_component("storage", ComponentHealth::down("read-only".to_string()));
+        assert_eq!(health.status(), HealthStatus::Down);
+
+        let json_string = serde_json::to_string(&health).unwrap();
+        assert!(json_string.contains(r#""status":"down""#));
+        println!("{json_string:?}");
+    }
+}
@@ -0,0 +1,37 @@
+use rocket::{get, http::Status, response::status, serde::json::Json};
+use serde_json::json;
+
+use crate::controllers::health_controller;
+use crate::models::health::Health;
+
+fn to_response(
+    status: i32,
+    message: String,
+    health: Health,
+) -> status::Custom<Json<serde_json::Value>> {
+    let response = json!(
+        {
+            "status": status,
+            "message": message,
+            "body":{
+                "data":health
+            }
+        }
+    );
+    let code = Status::from_code(status as u16).unwrap_or(Status::InternalServerError);
+    status::Custom(code, Json(response))
+}
+
+/// Liveness probe, never touches external dependencies.
+#[get("/live")]
+pub fn get_live() -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, health) = health_controller::get_live();
+    to_response(status, message, health)
+}
+
+/// Readiness probe, answers `503` until the database, schema and upload directory are usable.
+#[get("/ready")]
+pub fn get_ready() -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, health) = health_controller::get_ready();
+    to_response(status, message, health)
+}
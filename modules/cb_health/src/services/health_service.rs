@@ -0,0 +1,8 @@
+use crate::models::health::Health;
+
+pub trait CheckHealth {
+    /// The process is up and able to answer requests.
+    fn check_live() -> Health;
+    /// The process can do useful work: database, schema and storage are all usable.
+    fn check_ready() -> Health;
+}
@@ -0,0 +1,87 @@
+use std::{
+    env, fs,
+    io::Write,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    mappers::health_mapper::{fetch_pending_migrations, ping},
+    models::health::{ComponentHealth, Health},
+    services::health_service::CheckHealth,
+};
+use crab_rocket_config::app_config;
+use crab_rocket_schema::establish_pg_connection;
+
+impl CheckHealth for Health {
+    fn check_live() -> Health {
+        Health::new().with_component("process", ComponentHealth::up(None))
+    }
+
+    fn check_ready() -> Health {
+        let (database, migrations) = check_database();
+        Health::new()
+            .with_component("database", database)
+            .with_component("migrations", migrations)
+            .with_component("storage", check_storage())
+    }
+}
+
+/// Checks connectivity first; the schema can only be inspected over a live connection.
+fn check_database() -> (ComponentHealth, ComponentHealth) {
+    if env::var("DATABASE_URL").is_err() {
+        let message = String::from("DATABASE_URL is not set");
+        return (ComponentHealth::down(message.clone()), ComponentHealth::down(message));
+    }
+    let mut conn = match establish_pg_connection() {
+        Ok(conn) => conn,
+        Err(e) => {
+            return (
+                ComponentHealth::down(e.to_string()),
+                ComponentHealth::down(String::from("database unreachable")),
+            );
+        }
+    };
+    if let Err(e) = ping(&mut conn) {
+        return (
+            ComponentHealth::down(e.to_string()),
+            ComponentHealth::down(String::from("database unreachable")),
+        );
+    }
+    let migrations = match fetch_pending_migrations(&mut conn) {
+        Ok(pending) if pending.is_empty() => ComponentHealth::up(None),
+        Ok(pending) => ComponentHealth::down(format!("pending migrations: {}", pending.join(", "))),
+        Err(e) => ComponentHealth::down(e.to_string()),
+    };
+    (ComponentHealth::up(None), migrations)
+}
+
+fn check_storage() -> ComponentHealth {
+    let upload_dir = &app_config().storage.upload_dir;
+    match probe_writable(upload_dir) {
+        Ok(()) => ComponentHealth::up(Some(upload_dir.display().to_string())),
+        Err(e) => ComponentHealth::down(format!("{}: {e}", upload_dir.display())),
+    }
+}
+
+/// Writes and removes a scratch file, which is the only reliable way to know the
+/// directory accepts uploads (permission bits alone miss read-only mounts).
+fn probe_writable(dir: &Path) -> std::io::Result<()> {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos()).unwrap_or(0);
+    let probe: PathBuf = dir.join(format!(".health-{}-{nanos}", std::process::id()));
+    let result = fs::File::create(&probe).and_then(|mut file| file.write_all(b"ok"));
+    let _ = fs::remove_file(&probe);
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::probe_writable;
+
+    #[test]
+    fn test_probe_writable() {
+        let dir = std::env::temp_dir();
+        assert!(probe_writable(&dir).is_ok());
+        assert!(probe_writable(&dir.join("does-not-exist")).is_err());
+    }
+}
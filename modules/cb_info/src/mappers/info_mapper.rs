@@ -20,25 +20,19 @@ pub fn get_info(conn: &mut PgConnection) -> Result<Info, diesel::result::Error>
 #[cfg(test)]
 mod test {
     use super::get_info;
-    use crab_rocket_schema::establish_pg_connection; // 建立数据库连接
+    use crab_rocket_test_support::{fixtures, test_conn};
 
     #[test]
     fn test_get_info() {
-        match establish_pg_connection() {
-            Ok(mut conn) => match get_info(&mut conn) {
-                Ok(data) => {
-                    println!("{data:?}");
-                    ()
-                }
-                Err(e) => {
-                    println!("{e:?}");
-                    ()
-                }
-            },
-            Err(e) => {
-                println!("{e:?}");
-                ()
-            }
-        }
+        let mut conn = test_conn();
+        let before = get_info(&mut conn).unwrap();
+
+        let user_id = fixtures::user(&mut conn, "info_user");
+        fixtures::post(&mut conn, user_id, "Counted");
+
+        let after = get_info(&mut conn).unwrap();
+        assert_eq!(after.user_count(), before.user_count() + 1);
+        assert_eq!(after.post_count(), before.post_count() + 1);
+        assert_eq!(after.task_count(), before.task_count());
     }
 }
@@ -41,8 +41,8 @@ impl MapperCRUD for InventoryMapper {
         //
         // limit 始终为 per_page
         // 计算分页相关
-        let page = (param.pagination.offset.unwrap() / param.pagination.limit.unwrap()) + 1;
-        let per_page = param.pagination.limit.unwrap();
+        let page = (param.pagination.offset_or_default() / param.pagination.limit_or_default()) + 1;
+        let per_page = param.pagination.limit_or_default();
         // 获取总记录数
         let total_count = dsl::inventory_table.count().get_result::<i64>(conn)? as i32;
         // 计算总页数
@@ -65,6 +65,7 @@ impl MapperCRUD for InventoryMapper {
         // 分页查询
         let data = dsl::inventory_table
             .order(dsl::last_updated.desc())
+            .then_order_by(dsl::inventory_id.desc())
             .limit(per_page as i64)
             .offset(((page - 1) * per_page) as i64)
             .load::<Inventory>(conn)?;
@@ -127,8 +128,8 @@ impl MapperCRUD for InventoryMapper {
         //
         // limit 始终为 per_page
         // 计算分页相关
-        let page = (param.pagination.offset.unwrap() / param.pagination.limit.unwrap()) + 1;
-        let per_page = param.pagination.limit.unwrap();
+        let page = (param.pagination.offset_or_default() / param.pagination.limit_or_default()) + 1;
+        let per_page = param.pagination.limit_or_default();
         // 获取总记录数
         let total_count = dsl::inventory_table.count().get_result::<i64>(conn)? as i32;
         // 计算总页数
@@ -1,3 +1,5 @@
+use crab_rocket_auth::guards::authorized::Authorized;
+use crab_rocket_auth::permission;
 use obj_traits::controller::controller_crud::ControllerCRUD;
 use obj_traits::request::pagination_request_param::{PaginationParam, PaginationParamTrait};
 use obj_traits::request::request_param::RequestParam;
@@ -7,14 +9,12 @@ use crate::controllers::inventory_controller::InventoryController;
 use crate::models::inventory::{PatchInventory, PostInventory};
 use crate::models::inventory_filter::InventoryFilter;
 
+permission!(pub InventoryCreate, "inventory", "create");
+permission!(pub InventoryUpdate, "inventory", "update");
+permission!(pub InventoryDelete, "inventory", "delete");
+
 #[get("/inventory?<limit>&<offset>")]
-pub fn get_inventorys(mut limit: Option<i32>, mut offset: Option<i32>) -> Json<serde_json::Value> {
-    if limit.is_none() {
-        limit = Some(10);
-    };
-    if offset.is_none() {
-        offset = Some(0);
-    };
+pub fn get_inventorys(limit: Option<i32>, offset: Option<i32>) -> Json<serde_json::Value> {
     let params = RequestParam::new(PaginationParam::new(limit, offset), None);
     println!("{:?}", params);
     crab_rocket_schema::update_reload::update_reload_count();
@@ -43,7 +43,10 @@ pub fn get_inventory_by_id(id: i32) -> Json<serde_json::Value> {
 }
 
 #[post("/inventory", data = "<inventory>")]
-pub fn insert_single_inventory(inventory: Json<PostInventory>) -> Json<serde_json::Value> {
+pub fn insert_single_inventory(
+    _auth: Authorized<InventoryCreate>,
+    inventory: Json<PostInventory>,
+) -> Json<serde_json::Value> {
     let mut obj: PostInventory = inventory.into_inner();
 
     let resp = InventoryController::add_single(&mut obj).unwrap();
@@ -52,14 +55,21 @@ pub fn insert_single_inventory(inventory: Json<PostInventory>) -> Json<serde_jso
 }
 
 #[delete("/inventory/<id>")]
-pub fn delete_inventory_by_id(id: i32) -> Json<serde_json::Value> {
+pub fn delete_inventory_by_id(
+    _auth: Authorized<InventoryDelete>,
+    id: i32,
+) -> Json<serde_json::Value> {
     let resp = InventoryController::delete_by_id(id).unwrap();
     let json_value = serde_json::to_value(&resp).unwrap();
     Json(serde_json::from_value(json_value).unwrap())
 }
 
 #[patch("/inventory/<id>", data = "<task>")]
-pub fn update_inventory_by_id(id: i32, task: Json<PatchInventory>) -> Json<serde_json::Value> {
+pub fn update_inventory_by_id(
+    _auth: Authorized<InventoryUpdate>,
+    id: i32,
+    task: Json<PatchInventory>,
+) -> Json<serde_json::Value> {
     let resp = InventoryController::update_by_id(id, &task).unwrap();
     let json_value = serde_json::to_value(&resp).unwrap();
     Json(serde_json::from_value(json_value).unwrap())
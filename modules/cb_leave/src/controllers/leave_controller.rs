@@ -0,0 +1,74 @@
+use crate::error::LeaveError;
+use crate::models::leave_balance::{Accrual, LeaveBalanceDetail};
+use crate::models::leave_request::{LeaveDecision, LeaveRequest, PostLeaveRequest};
+use crate::models::leave_type::{LeaveType, PostLeaveType};
+use crate::services::leave_service::LeaveService;
+
+pub struct LeaveController {}
+
+fn to_tuple<T>(result: Result<T, LeaveError>) -> (i32, String, Option<T>) {
+    match result {
+        Ok(data) => (200, String::from("Success"), Some(data)),
+        Err(LeaveError::Internal(e)) => {
+            println!("{e:?}");
+            (500, String::from("Internal Server Error"), None)
+        }
+        Err(e) => (e.status(), e.to_string(), None),
+    }
+}
+
+impl LeaveController {
+    pub fn get_types() -> (i32, String, Option<Vec<LeaveType>>) {
+        to_tuple(LeaveService::get_types())
+    }
+
+    pub fn add_type(obj: &PostLeaveType) -> (i32, String, Option<LeaveType>) {
+        to_tuple(LeaveService::add_type(obj))
+    }
+
+    pub fn accrue(year: i32) -> (i32, String, Option<Accrual>) {
+        to_tuple(LeaveService::accrue(year))
+    }
+
+    pub fn get_balances(
+        employee_id: i32,
+        year: i32,
+    ) -> (i32, String, Option<Vec<LeaveBalanceDetail>>) {
+        to_tuple(LeaveService::get_balances(employee_id, year))
+    }
+
+    pub fn request(
+        employee_id: i32,
+        obj: &PostLeaveRequest,
+    ) -> (i32, String, Option<LeaveRequest>) {
+        to_tuple(LeaveService::request(employee_id, obj))
+    }
+
+    pub fn get_requests(employee_id: i32) -> (i32, String, Option<Vec<LeaveRequest>>) {
+        to_tuple(LeaveService::get_requests(employee_id))
+    }
+
+    pub fn get_approvals(manager_id: i32) -> (i32, String, Option<Vec<LeaveRequest>>) {
+        to_tuple(LeaveService::get_approvals(manager_id))
+    }
+
+    pub fn approve(
+        pid: i32,
+        user_id: i32,
+        decision: &LeaveDecision,
+    ) -> (i32, String, Option<LeaveRequest>) {
+        to_tuple(LeaveService::approve(pid, user_id, decision))
+    }
+
+    pub fn reject(
+        pid: i32,
+        user_id: i32,
+        decision: &LeaveDecision,
+    ) -> (i32, String, Option<LeaveRequest>) {
+        to_tuple(LeaveService::reject(pid, user_id, decision))
+    }
+
+    pub fn cancel(pid: i32) -> (i32, String, Option<LeaveRequest>) {
+        to_tuple(LeaveService::cancel(pid))
+    }
+}
@@ -0,0 +1,153 @@
+use std::error::Error;
+use std::fmt;
+
+use chrono::NaiveDate;
+use diesel::ConnectionError;
+
+/// Why leave could not be granted, requested or decided on.
+#[derive(Debug)]
+pub enum LeaveError {
+    EmployeeNotFound(i32),
+    LeaveTypeNotFound(i32),
+    RequestNotFound(i32),
+    /// A period ending before it starts.
+    InvalidPeriod {
+        start: NaiveDate,
+        end: NaiveDate,
+    },
+    /// Balances are per year, a request has to end in the year it starts.
+    SpansYears {
+        start: NaiveDate,
+        end: NaiveDate,
+    },
+    /// A request covering weekend days only.
+    NoWorkingDays {
+        start: NaiveDate,
+        end: NaiveDate,
+    },
+    InvalidYear(i32),
+    NegativeDays(i32),
+    LeaveTypeTaken(String),
+    /// Only the employee's manager decides on their requests.
+    NotManager {
+        approver_id: i32,
+        employee_id: i32,
+    },
+    NoManager(i32),
+    /// The caller decides as the employee linked to their user, and has none.
+    NoEmployee(i32),
+    /// The employee is already away for part of the period.
+    Overlap {
+        leave_request_id: i32,
+        start: NaiveDate,
+        end: NaiveDate,
+    },
+    InsufficientBalance {
+        requested: i64,
+        remaining: i64,
+    },
+    /// The request was decided on or cancelled already.
+    NotPending {
+        leave_request_id: i32,
+        status: String,
+    },
+    Internal(String),
+}
+
+impl LeaveError {
+    pub fn status(&self) -> i32 {
+        match self {
+            LeaveError::EmployeeNotFound(_)
+            | LeaveError::LeaveTypeNotFound(_)
+            | LeaveError::RequestNotFound(_) => 404,
+            LeaveError::InvalidPeriod {
+                ..
+            }
+            | LeaveError::SpansYears {
+                ..
+            }
+            | LeaveError::NoWorkingDays {
+                ..
+            }
+            | LeaveError::InvalidYear(_)
+            | LeaveError::NegativeDays(_) => 400,
+            LeaveError::NotManager {
+                ..
+            }
+            | LeaveError::NoEmployee(_) => 403,
+            LeaveError::LeaveTypeTaken(_)
+            | LeaveError::NoManager(_)
+            | LeaveError::Overlap {
+                ..
+            }
+            | LeaveError::InsufficientBalance {
+                ..
+            }
+            | LeaveError::NotPending {
+                ..
+            } => 409,
+            LeaveError::Internal(_) => 500,
+        }
+    }
+}
+
+impl fmt::Display for LeaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LeaveError::EmployeeNotFound(id) => write!(f, "employee {id} not found"),
+            LeaveError::LeaveTypeNotFound(id) => write!(f, "leave type {id} not found"),
+            LeaveError::RequestNotFound(id) => write!(f, "leave request {id} not found"),
+            LeaveError::InvalidPeriod {
+                start,
+                end,
+            } => write!(f, "the period from {start} to {end} ends before it starts"),
+            LeaveError::SpansYears {
+                start,
+                end,
+            } => write!(f, "the period from {start} to {end} spans two years, request each year"),
+            LeaveError::NoWorkingDays {
+                start,
+                end,
+            } => write!(f, "there are no working days from {start} to {end}"),
+            LeaveError::InvalidYear(year) => write!(f, "year {year} is out of range"),
+            LeaveError::NegativeDays(days) => write!(f, "{days} days is negative"),
+            LeaveError::LeaveTypeTaken(name) => write!(f, "leave type `{name}` already exists"),
+            LeaveError::NotManager {
+                approver_id,
+                employee_id,
+            } => write!(f, "employee {approver_id} is not the manager of employee {employee_id}"),
+            LeaveError::NoManager(id) => {
+                write!(f, "employee {id} has no manager to decide on their leave")
+            }
+            LeaveError::NoEmployee(id) => write!(f, "user {id} is not linked to an employee"),
+            LeaveError::Overlap {
+                leave_request_id,
+                start,
+                end,
+            } => write!(f, "leave request {leave_request_id} already covers {start} to {end}"),
+            LeaveError::InsufficientBalance {
+                requested,
+                remaining,
+            } => write!(f, "{requested} days requested but only {remaining} remaining"),
+            LeaveError::NotPending {
+                leave_request_id,
+                status,
+            } => write!(f, "leave request {leave_request_id} is {status}, not pending"),
+            LeaveError::Internal(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for LeaveError {}
+
+impl From<diesel::result::Error> for LeaveError {
+    fn from(e: diesel::result::Error) -> Self {
+        LeaveError::Internal(e.to_string())
+    }
+}
+
+impl From<ConnectionError> for LeaveError {
+    fn from(e: ConnectionError) -> Self {
+        LeaveError::Internal(e.to_string())
+    }
+}
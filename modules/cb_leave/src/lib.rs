@@ -0,0 +1,24 @@
+pub mod error;
+
+pub mod models {
+    pub mod leave_balance;
+    pub mod leave_request;
+    pub mod leave_type;
+}
+
+pub mod mappers {
+    pub mod leave_mapper;
+}
+
+pub mod controllers {
+    pub mod leave_controller;
+}
+
+pub mod routes {
+    pub mod leave_request_route;
+    pub mod leave_route;
+}
+
+pub mod services {
+    pub mod leave_service;
+}
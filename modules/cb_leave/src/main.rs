@@ -0,0 +1,37 @@
+#[macro_use]
+extern crate rocket;
+
+use crab_rocket_leave::routes::leave_request_route::*;
+use crab_rocket_leave::routes::leave_route::*;
+use dotenvy::dotenv;
+use std::env;
+#[launch]
+fn rocket() -> _ {
+    // Clear environment variable before running.
+    env::remove_var("DATABASE_URL");
+    dotenv().ok();
+    let figment = crab_rocket_config::init();
+
+    crab_rocket_utils::run_preload();
+
+    let cors = crab_rocket_config::cors::cors();
+
+    rocket::custom(figment)
+        .mount(
+            "/api",
+            routes![
+                get_leave_types,
+                insert_leave_type,
+                insert_leave_accrual,
+                get_employee_leave_balance,
+                get_employee_leave,
+                insert_employee_leave,
+                get_employee_leave_approvals,
+                approve_leave_request,
+                reject_leave_request,
+                cancel_leave_request,
+                options_leave_type
+            ],
+        )
+        .attach(cors)
+}
@@ -0,0 +1,219 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use crab_rocket_schema::schema::{
+    employee_table, leave_balance_table, leave_request_table, leave_type_table,
+};
+use crab_rocket_utils::time::get_e8_time;
+use diesel::{prelude::*, result::Error};
+
+use crate::models::leave_balance::{LeaveBalance, NewLeaveBalance};
+use crate::models::leave_request::{LeaveRequest, NewLeaveRequest, APPROVED, PENDING};
+use crate::models::leave_type::{LeaveType, PostLeaveType};
+
+pub struct LeaveMapper {}
+
+impl LeaveMapper {
+    pub fn get_types(conn: &mut PgConnection) -> Result<Vec<LeaveType>, Error> {
+        leave_type_table::table
+            .select(LeaveType::as_select())
+            .order(leave_type_table::leave_type_id.asc())
+            .load(conn)
+    }
+
+    pub fn get_type(conn: &mut PgConnection, pid: i32) -> Result<LeaveType, Error> {
+        leave_type_table::table.find(pid).select(LeaveType::as_select()).first(conn)
+    }
+
+    pub fn add_type(conn: &mut PgConnection, obj: &PostLeaveType) -> Result<LeaveType, Error> {
+        diesel::insert_into(leave_type_table::table)
+            .values(obj)
+            .returning(LeaveType::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn get_balance(
+        conn: &mut PgConnection,
+        employee_id: i32,
+        leave_type_id: i32,
+        year: i32,
+    ) -> Result<Option<LeaveBalance>, Error> {
+        leave_balance_table::table
+            .filter(leave_balance_table::employee_id.eq(employee_id))
+            .filter(leave_balance_table::leave_type_id.eq(leave_type_id))
+            .filter(leave_balance_table::year.eq(year))
+            .select(LeaveBalance::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    /// The employee's balances of `year` with the name of their leave type.
+    pub fn get_balances(
+        conn: &mut PgConnection,
+        employee_id: i32,
+        year: i32,
+    ) -> Result<Vec<(LeaveBalance, String)>, Error> {
+        leave_balance_table::table
+            .inner_join(leave_type_table::table)
+            .filter(leave_balance_table::employee_id.eq(employee_id))
+            .filter(leave_balance_table::year.eq(year))
+            .select((LeaveBalance::as_select(), leave_type_table::leave_type_name))
+            .order(leave_balance_table::leave_type_id.asc())
+            .load(conn)
+    }
+
+    /// Every employee's balances of `year`.
+    pub fn get_balances_of_year(
+        conn: &mut PgConnection,
+        year: i32,
+    ) -> Result<Vec<LeaveBalance>, Error> {
+        leave_balance_table::table
+            .filter(leave_balance_table::year.eq(year))
+            .select(LeaveBalance::as_select())
+            .load(conn)
+    }
+
+    /// Inserts the balances nobody has yet, returns how many.
+    pub fn add_balances(
+        conn: &mut PgConnection,
+        balances: &[NewLeaveBalance],
+    ) -> Result<usize, Error> {
+        let mut inserted = 0;
+        // Far below the bind parameter limit of Postgres for a handful of leave types.
+        for chunk in balances.chunks(10_000) {
+            inserted += diesel::insert_into(leave_balance_table::table)
+                .values(chunk)
+                .on_conflict((
+                    leave_balance_table::employee_id,
+                    leave_balance_table::leave_type_id,
+                    leave_balance_table::year,
+                ))
+                .do_nothing()
+                .execute(conn)?;
+        }
+        Ok(inserted)
+    }
+
+    /// `(employee_id, hire_date)` of every employee.
+    pub fn get_employees(
+        conn: &mut PgConnection,
+    ) -> Result<Vec<(i32, Option<NaiveDateTime>)>, Error> {
+        employee_table::table
+            .select((employee_table::employee_id, employee_table::hire_date))
+            .order(employee_table::employee_id.asc())
+            .load(conn)
+    }
+
+    /// The `manager_id` of an employee.
+    pub fn get_manager(conn: &mut PgConnection, employee_id: i32) -> Result<Option<i32>, Error> {
+        employee_table::table.find(employee_id).select(employee_table::manager_id).first(conn)
+    }
+
+    /// The employee linked to a user.
+    pub fn get_employee_of_user(
+        conn: &mut PgConnection,
+        user_id: i32,
+    ) -> Result<Option<i32>, Error> {
+        employee_table::table
+            .filter(employee_table::user_id.eq(user_id))
+            .select(employee_table::employee_id)
+            .first(conn)
+            .optional()
+    }
+
+    pub fn add_request(
+        conn: &mut PgConnection,
+        obj: &NewLeaveRequest,
+    ) -> Result<LeaveRequest, Error> {
+        diesel::insert_into(leave_request_table::table)
+            .values(obj)
+            .returning(LeaveRequest::as_returning())
+            .get_result(conn)
+    }
+
+    /// The request, locked until the end of the transaction.
+    pub fn get_request_for_update(
+        conn: &mut PgConnection,
+        pid: i32,
+    ) -> Result<LeaveRequest, Error> {
+        leave_request_table::table
+            .find(pid)
+            .select(LeaveRequest::as_select())
+            .for_update()
+            .first(conn)
+    }
+
+    /// The employee's requests, the latest first.
+    pub fn get_requests_by_employee(
+        conn: &mut PgConnection,
+        employee_id: i32,
+    ) -> Result<Vec<LeaveRequest>, Error> {
+        leave_request_table::table
+            .filter(leave_request_table::employee_id.eq(employee_id))
+            .select(LeaveRequest::as_select())
+            .order(leave_request_table::start_date.desc())
+            .load(conn)
+    }
+
+    /// Pending requests of the manager's direct reports, the earliest first.
+    pub fn get_pending_for_manager(
+        conn: &mut PgConnection,
+        manager_id: i32,
+    ) -> Result<Vec<LeaveRequest>, Error> {
+        let reports = employee_table::table
+            .filter(employee_table::manager_id.eq(manager_id))
+            .select(employee_table::employee_id);
+        leave_request_table::table
+            .filter(leave_request_table::employee_id.eq_any(reports))
+            .filter(leave_request_table::status.eq(PENDING))
+            .select(LeaveRequest::as_select())
+            .order(leave_request_table::start_date.asc())
+            .load(conn)
+    }
+
+    /// A pending or approved request of the employee covering any day of `start..=end`.
+    pub fn overlapping_request(
+        conn: &mut PgConnection,
+        employee_id: i32,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Option<LeaveRequest>, Error> {
+        leave_request_table::table
+            .filter(leave_request_table::employee_id.eq(employee_id))
+            .filter(leave_request_table::status.eq_any([PENDING, APPROVED]))
+            .filter(leave_request_table::start_date.le(end))
+            .filter(leave_request_table::end_date.ge(start))
+            .select(LeaveRequest::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    /// Approves or rejects the request on behalf of `approver_id`.
+    pub fn decide(
+        conn: &mut PgConnection,
+        pid: i32,
+        status: &str,
+        approver_id: i32,
+        decision_note: Option<&str>,
+    ) -> Result<LeaveRequest, Error> {
+        diesel::update(leave_request_table::table.find(pid))
+            .set((
+                leave_request_table::status.eq(status),
+                leave_request_table::approver_id.eq(approver_id),
+                leave_request_table::decision_note.eq(decision_note),
+                leave_request_table::decided_at.eq(get_e8_time()),
+            ))
+            .returning(LeaveRequest::as_returning())
+            .get_result(conn)
+    }
+
+    /// Sets the status alone, an approval stays on record when it is cancelled.
+    pub fn set_status(
+        conn: &mut PgConnection,
+        pid: i32,
+        status: &str,
+    ) -> Result<LeaveRequest, Error> {
+        diesel::update(leave_request_table::table.find(pid))
+            .set(leave_request_table::status.eq(status))
+            .returning(LeaveRequest::as_returning())
+            .get_result(conn)
+    }
+}
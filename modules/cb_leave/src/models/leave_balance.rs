@@ -0,0 +1,137 @@
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+use diesel::dsl::sql;
+use diesel::expression::SqlLiteral;
+use diesel::prelude::*;
+use diesel::sql_types::BigInt;
+use rocket::serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::models::leave_request::{APPROVED, PENDING};
+
+/// `SUM(days)` of the balance's requests in `status`, by the year they start in.
+pub fn days_in_status(status: &str) -> SqlLiteral<BigInt> {
+    sql::<BigInt>(&format!(
+        "(SELECT COALESCE(SUM(r.days), 0) FROM leave_request_table r \
+         WHERE r.employee_id = leave_balance_table.employee_id \
+         AND r.leave_type_id = leave_balance_table.leave_type_id \
+         AND r.status = '{status}' \
+         AND EXTRACT(YEAR FROM r.start_date) = leave_balance_table.year)"
+    ))
+}
+
+/// The days of a leave type an employee may take in a year.
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+#[diesel(table_name = crab_rocket_schema::schema::leave_balance_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct LeaveBalance {
+    leave_balance_id: i32,
+    employee_id: i32,
+    leave_type_id: i32,
+    year: i32,
+    accrued: i32,
+    /// Unused days of the year before.
+    carried_over: i32,
+    /// Days of approved requests, counted on every read.
+    #[diesel(select_expression = days_in_status(APPROVED))]
+    #[diesel(select_expression_type = SqlLiteral<BigInt>)]
+    used: i64,
+    /// Days of requests waiting for a decision, counted on every read.
+    #[diesel(select_expression = days_in_status(PENDING))]
+    #[diesel(select_expression_type = SqlLiteral<BigInt>)]
+    pending: i64,
+    created_at: NaiveDateTime,
+}
+
+impl LeaveBalance {
+    pub fn employee_id(&self) -> i32 {
+        self.employee_id
+    }
+
+    pub fn leave_type_id(&self) -> i32 {
+        self.leave_type_id
+    }
+
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    pub fn accrued(&self) -> i32 {
+        self.accrued
+    }
+
+    pub fn carried_over(&self) -> i32 {
+        self.carried_over
+    }
+
+    pub fn used(&self) -> i64 {
+        self.used
+    }
+
+    pub fn pending(&self) -> i64 {
+        self.pending
+    }
+
+    /// Days still free to request, pending requests are already taken off.
+    pub fn remaining(&self) -> i64 {
+        self.accrued as i64 + self.carried_over as i64 - self.used - self.pending
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Debug, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct LeaveBalanceDetail {
+    #[serde(flatten)]
+    pub balance: LeaveBalance,
+    pub leave_type_name: String,
+    pub remaining: i64,
+}
+
+impl LeaveBalanceDetail {
+    pub fn new(balance: LeaveBalance, leave_type_name: String) -> Self {
+        Self {
+            remaining: balance.remaining(),
+            balance,
+            leave_type_name,
+        }
+    }
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crab_rocket_schema::schema::leave_balance_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NewLeaveBalance {
+    pub employee_id: i32,
+    pub leave_type_id: i32,
+    pub year: i32,
+    pub accrued: i32,
+    pub carried_over: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct PostAccrual {
+    pub year: i32,
+}
+
+/// What an accrual run added.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct Accrual {
+    pub year: i32,
+    /// Balances created, employees that had one already are skipped.
+    pub balances: usize,
+}
+
+/// The `yearly_days` of an employee hired on `hire_date` for `year`, by the months from the
+/// month they were hired in, rounded half up. `None` when they were hired after the year.
+pub fn accrued_days(yearly_days: i32, hire_date: Option<NaiveDate>, year: i32) -> Option<i32> {
+    match hire_date {
+        Some(hired) if hired.year() > year => None,
+        Some(hired) if hired.year() == year => {
+            let months = 13 - hired.month() as i32;
+            Some((2 * yearly_days * months + 12) / 24)
+        }
+        _ => Some(yearly_days),
+    }
+}
@@ -0,0 +1,113 @@
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Weekday};
+use diesel::prelude::*;
+use rocket::serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+pub const PENDING: &str = "pending";
+pub const APPROVED: &str = "approved";
+pub const REJECTED: &str = "rejected";
+pub const CANCELLED: &str = "cancelled";
+
+/// Time off from `start_date` to `end_date`, both included, waiting for or decided on by the
+/// employee's manager.
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+#[diesel(table_name = crab_rocket_schema::schema::leave_request_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct LeaveRequest {
+    leave_request_id: i32,
+    employee_id: i32,
+    leave_type_id: i32,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    /// Working days taken off the balance.
+    days: i32,
+    reason: Option<String>,
+    /// `pending`, `approved`, `rejected` or `cancelled`.
+    status: String,
+    /// The manager that approved or rejected the request.
+    approver_id: Option<i32>,
+    decision_note: Option<String>,
+    decided_at: Option<NaiveDateTime>,
+    created_at: NaiveDateTime,
+}
+
+impl LeaveRequest {
+    pub fn leave_request_id(&self) -> i32 {
+        self.leave_request_id
+    }
+
+    pub fn employee_id(&self) -> i32 {
+        self.employee_id
+    }
+
+    pub fn leave_type_id(&self) -> i32 {
+        self.leave_type_id
+    }
+
+    pub fn start_date(&self) -> NaiveDate {
+        self.start_date
+    }
+
+    pub fn end_date(&self) -> NaiveDate {
+        self.end_date
+    }
+
+    pub fn days(&self) -> i32 {
+        self.days
+    }
+
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct PostLeaveRequest {
+    pub leave_type_id: i32,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub reason: Option<String>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crab_rocket_schema::schema::leave_request_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NewLeaveRequest {
+    employee_id: i32,
+    leave_type_id: i32,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    days: i32,
+    reason: Option<String>,
+}
+
+impl NewLeaveRequest {
+    pub fn new(employee_id: i32, obj: &PostLeaveRequest, days: i32) -> Self {
+        Self {
+            employee_id,
+            leave_type_id: obj.leave_type_id,
+            start_date: obj.start_date,
+            end_date: obj.end_date,
+            days,
+            reason: obj.reason.clone(),
+        }
+    }
+}
+
+/// An approval or rejection, the caller has to be the employee's manager.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct LeaveDecision {
+    pub note: Option<String>,
+}
+
+/// Monday to Friday in `start..=end`.
+pub fn working_days(start: NaiveDate, end: NaiveDate) -> i32 {
+    start
+        .iter_days()
+        .take_while(|day| *day <= end)
+        .filter(|day| !matches!(day.weekday(), Weekday::Sat | Weekday::Sun))
+        .count() as i32
+}
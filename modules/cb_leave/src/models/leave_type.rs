@@ -0,0 +1,54 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use rocket::serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A kind of leave and how many working days of it every employee accrues a year.
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+#[diesel(table_name = crab_rocket_schema::schema::leave_type_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct LeaveType {
+    leave_type_id: i32,
+    leave_type_name: String,
+    yearly_days: i32,
+    /// Unused days taken into the next year, at most.
+    max_carry_over: i32,
+    paid: bool,
+    created_at: NaiveDateTime,
+}
+
+impl LeaveType {
+    pub fn leave_type_id(&self) -> i32 {
+        self.leave_type_id
+    }
+
+    pub fn leave_type_name(&self) -> &str {
+        &self.leave_type_name
+    }
+
+    pub fn yearly_days(&self) -> i32 {
+        self.yearly_days
+    }
+
+    pub fn max_carry_over(&self) -> i32 {
+        self.max_carry_over
+    }
+
+    pub fn paid(&self) -> bool {
+        self.paid
+    }
+}
+
+#[derive(Insertable, Debug, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+#[diesel(table_name = crab_rocket_schema::schema::leave_type_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PostLeaveType {
+    pub leave_type_name: String,
+    pub yearly_days: i32,
+    /// Defaults to `0`, nothing is carried over.
+    pub max_carry_over: Option<i32>,
+    /// Defaults to `true`.
+    pub paid: Option<bool>,
+}
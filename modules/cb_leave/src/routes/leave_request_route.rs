@@ -0,0 +1,71 @@
+use crab_rocket_auth::guards::authorized::Authorized;
+use rocket::response::status;
+use rocket::serde::json::Json;
+use rocket::{get, post};
+
+use crate::controllers::leave_controller::LeaveController;
+use crate::models::leave_request::{LeaveDecision, PostLeaveRequest};
+use crate::routes::leave_route::{to_response, LeaveCreate, LeaveRead, LeaveUpdate};
+
+/// The employee's requests, the latest first.
+#[get("/employee/<id>/leave")]
+pub fn get_employee_leave(
+    _auth: Authorized<LeaveRead>,
+    id: i32,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = LeaveController::get_requests(id);
+    to_response(code, message, data)
+}
+
+/// Overlapping an earlier request or asking for more than is left of the balance answers
+/// `409`.
+#[post("/employee/<id>/leave", data = "<request>")]
+pub fn insert_employee_leave(
+    _auth: Authorized<LeaveCreate>,
+    id: i32,
+    request: Json<PostLeaveRequest>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = LeaveController::request(id, &request);
+    to_response(code, message, data)
+}
+
+/// Pending requests of the employee's direct reports.
+#[get("/employee/<id>/leave/approvals")]
+pub fn get_employee_leave_approvals(
+    _auth: Authorized<LeaveRead>,
+    id: i32,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = LeaveController::get_approvals(id);
+    to_response(code, message, data)
+}
+
+/// The caller's employee has to be the manager of the employee (`403` otherwise).
+#[post("/leave/request/<id>/approve", data = "<decision>")]
+pub fn approve_leave_request(
+    auth: Authorized<LeaveUpdate>,
+    id: i32,
+    decision: Json<LeaveDecision>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = LeaveController::approve(id, auth.auth.user_id(), &decision);
+    to_response(code, message, data)
+}
+
+/// The caller's employee has to be the manager of the employee (`403` otherwise).
+#[post("/leave/request/<id>/reject", data = "<decision>")]
+pub fn reject_leave_request(
+    auth: Authorized<LeaveUpdate>,
+    id: i32,
+    decision: Json<LeaveDecision>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = LeaveController::reject(id, auth.auth.user_id(), &decision);
+    to_response(code, message, data)
+}
+
+#[post("/leave/request/<id>/cancel")]
+pub fn cancel_leave_request(
+    _auth: Authorized<LeaveUpdate>,
+    id: i32,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = LeaveController::cancel(id);
+    to_response(code, message, data)
+}
@@ -0,0 +1,71 @@
+use chrono::Datelike;
+use crab_rocket_auth::guards::authorized::Authorized;
+use crab_rocket_auth::permission;
+use crab_rocket_utils::time::get_e8_time;
+use obj_traits::response::api_response::ApiResponse;
+use rocket::http::Status;
+use rocket::response::status;
+use rocket::serde::json::Json;
+use rocket::{get, options, post};
+
+use crate::controllers::leave_controller::LeaveController;
+use crate::models::leave_balance::PostAccrual;
+use crate::models::leave_type::PostLeaveType;
+
+permission!(pub LeaveRead, "leave", "read");
+permission!(pub LeaveCreate, "leave", "create");
+permission!(pub LeaveUpdate, "leave", "update");
+
+/// Answers with `code` as the HTTP status, in the shape of the other leave routes.
+pub(crate) fn to_response<T: rocket::serde::Serialize + Default>(
+    code: i32,
+    message: String,
+    data: T,
+) -> status::Custom<Json<serde_json::Value>> {
+    let response = serde_json::to_value(ApiResponse::new(code, message, data)).unwrap();
+    let status = Status::from_code(code as u16).unwrap_or(Status::InternalServerError);
+    status::Custom(status, Json(response))
+}
+
+#[get("/leave/type")]
+pub fn get_leave_types(_auth: Authorized<LeaveRead>) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = LeaveController::get_types();
+    to_response(code, message, data)
+}
+
+/// A taken `leave_type_name` answers `409`.
+#[post("/leave/type", data = "<leave_type>")]
+pub fn insert_leave_type(
+    _auth: Authorized<LeaveCreate>,
+    leave_type: Json<PostLeaveType>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = LeaveController::add_type(&leave_type);
+    to_response(code, message, data)
+}
+
+/// Creates the `year` balances employees do not have yet, safe to call again.
+#[post("/leave/accrual", data = "<accrual>")]
+pub fn insert_leave_accrual(
+    _auth: Authorized<LeaveCreate>,
+    accrual: Json<PostAccrual>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = LeaveController::accrue(accrual.year);
+    to_response(code, message, data)
+}
+
+/// The employee's balances of `year`, this year by default.
+#[get("/employee/<id>/leave/balance?<year>")]
+pub fn get_employee_leave_balance(
+    _auth: Authorized<LeaveRead>,
+    id: i32,
+    year: Option<i32>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let year = year.unwrap_or_else(|| get_e8_time().year());
+    let (code, message, data) = LeaveController::get_balances(id, year);
+    to_response(code, message, data)
+}
+
+#[options("/leave/type")]
+pub fn options_leave_type() -> Status {
+    Status::Ok
+}
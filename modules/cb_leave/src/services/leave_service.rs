@@ -0,0 +1,334 @@
+use std::collections::HashMap;
+
+use chrono::{Datelike, NaiveDate};
+use crab_rocket_schema::establish_pg_connection;
+use diesel::result::DatabaseErrorKind;
+use diesel::{Connection, PgConnection};
+
+use crate::error::LeaveError;
+use crate::mappers::leave_mapper::LeaveMapper;
+use crate::models::leave_balance::{accrued_days, Accrual, LeaveBalanceDetail, NewLeaveBalance};
+use crate::models::leave_request::{
+    working_days, LeaveDecision, LeaveRequest, NewLeaveRequest, PostLeaveRequest, APPROVED,
+    CANCELLED, PENDING, REJECTED,
+};
+use crate::models::leave_type::{LeaveType, PostLeaveType};
+
+pub struct LeaveService {}
+
+impl LeaveService {
+    pub fn get_types() -> Result<Vec<LeaveType>, LeaveError> {
+        let mut conn = establish_pg_connection()?;
+        Ok(LeaveMapper::get_types(&mut conn)?)
+    }
+
+    pub fn add_type(obj: &PostLeaveType) -> Result<LeaveType, LeaveError> {
+        for days in [Some(obj.yearly_days), obj.max_carry_over].into_iter().flatten() {
+            if days < 0 {
+                return Err(LeaveError::NegativeDays(days));
+            }
+        }
+        let mut conn = establish_pg_connection()?;
+        LeaveMapper::add_type(&mut conn, obj).map_err(|e| match e {
+            diesel::result::Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
+                LeaveError::LeaveTypeTaken(obj.leave_type_name.clone())
+            }
+            e => e.into(),
+        })
+    }
+
+    /// Gives every employee a `year` balance of every leave type: its `yearly_days`, by the
+    /// months left for employees hired during the year, plus what they did not use of the
+    /// year before up to `max_carry_over`. Balances that exist already are left as they are,
+    /// so running it twice changes nothing.
+    pub fn accrue(year: i32) -> Result<Accrual, LeaveError> {
+        check_year(year)?;
+        let mut conn = establish_pg_connection()?;
+        conn.transaction(|conn| {
+            let types = LeaveMapper::get_types(conn)?;
+            let previous: HashMap<_, _> = LeaveMapper::get_balances_of_year(conn, year - 1)?
+                .into_iter()
+                .map(|balance| ((balance.employee_id(), balance.leave_type_id()), balance))
+                .collect();
+            let mut balances = Vec::new();
+            for (employee_id, hire_date) in LeaveMapper::get_employees(conn)? {
+                for leave_type in &types {
+                    let hired = hire_date.map(|hired| hired.date());
+                    let Some(accrued) = accrued_days(leave_type.yearly_days(), hired, year) else {
+                        continue;
+                    };
+                    let unused = previous
+                        .get(&(employee_id, leave_type.leave_type_id()))
+                        .map_or(0, |balance| balance.remaining());
+                    balances.push(NewLeaveBalance {
+                        employee_id,
+                        leave_type_id: leave_type.leave_type_id(),
+                        year,
+                        accrued,
+                        carried_over: unused.clamp(0, leave_type.max_carry_over() as i64) as i32,
+                    });
+                }
+            }
+            Ok(Accrual {
+                year,
+                balances: LeaveMapper::add_balances(conn, &balances)?,
+            })
+        })
+    }
+
+    pub fn get_balances(
+        employee_id: i32,
+        year: i32,
+    ) -> Result<Vec<LeaveBalanceDetail>, LeaveError> {
+        check_year(year)?;
+        let mut conn = establish_pg_connection()?;
+        find_manager(&mut conn, employee_id)?;
+        Ok(LeaveMapper::get_balances(&mut conn, employee_id, year)?
+            .into_iter()
+            .map(|(balance, name)| LeaveBalanceDetail::new(balance, name))
+            .collect())
+    }
+
+    /// Asks the employee's manager for the working days of `start_date..=end_date`. The
+    /// period may not overlap a pending or approved request and has to fit in what is left of
+    /// the balance of the year it is in.
+    pub fn request(employee_id: i32, obj: &PostLeaveRequest) -> Result<LeaveRequest, LeaveError> {
+        let (start, end) = (obj.start_date, obj.end_date);
+        if end < start {
+            return Err(LeaveError::InvalidPeriod {
+                start,
+                end,
+            });
+        }
+        if start.year() != end.year() {
+            return Err(LeaveError::SpansYears {
+                start,
+                end,
+            });
+        }
+        let days = working_days(start, end);
+        if days == 0 {
+            return Err(LeaveError::NoWorkingDays {
+                start,
+                end,
+            });
+        }
+        let mut conn = establish_pg_connection()?;
+        conn.transaction(|conn| {
+            find_manager(conn, employee_id)?;
+            if let Err(diesel::result::Error::NotFound) =
+                LeaveMapper::get_type(conn, obj.leave_type_id)
+            {
+                return Err(LeaveError::LeaveTypeNotFound(obj.leave_type_id));
+            }
+            if let Some(other) = LeaveMapper::overlapping_request(conn, employee_id, start, end)? {
+                return Err(LeaveError::Overlap {
+                    leave_request_id: other.leave_request_id(),
+                    start: other.start_date(),
+                    end: other.end_date(),
+                });
+            }
+            let remaining = remaining(conn, employee_id, obj.leave_type_id, start)?;
+            if days as i64 > remaining {
+                return Err(LeaveError::InsufficientBalance {
+                    requested: days as i64,
+                    remaining,
+                });
+            }
+            let request = NewLeaveRequest::new(employee_id, obj, days);
+            Ok(LeaveMapper::add_request(conn, &request)?)
+        })
+    }
+
+    /// The employee's requests, the latest first.
+    pub fn get_requests(employee_id: i32) -> Result<Vec<LeaveRequest>, LeaveError> {
+        let mut conn = establish_pg_connection()?;
+        find_manager(&mut conn, employee_id)?;
+        Ok(LeaveMapper::get_requests_by_employee(&mut conn, employee_id)?)
+    }
+
+    /// Requests of the manager's direct reports waiting for them, the earliest first.
+    pub fn get_approvals(manager_id: i32) -> Result<Vec<LeaveRequest>, LeaveError> {
+        let mut conn = establish_pg_connection()?;
+        find_manager(&mut conn, manager_id)?;
+        Ok(LeaveMapper::get_pending_for_manager(&mut conn, manager_id)?)
+    }
+
+    pub fn approve(
+        pid: i32,
+        user_id: i32,
+        decision: &LeaveDecision,
+    ) -> Result<LeaveRequest, LeaveError> {
+        decide(pid, user_id, decision, APPROVED)
+    }
+
+    pub fn reject(
+        pid: i32,
+        user_id: i32,
+        decision: &LeaveDecision,
+    ) -> Result<LeaveRequest, LeaveError> {
+        decide(pid, user_id, decision, REJECTED)
+    }
+
+    /// Withdraws a pending or approved request, its days go back to the balance.
+    pub fn cancel(pid: i32) -> Result<LeaveRequest, LeaveError> {
+        let mut conn = establish_pg_connection()?;
+        conn.transaction(|conn| {
+            let request = find_request(conn, pid)?;
+            if ![PENDING, APPROVED].contains(&request.status()) {
+                return Err(LeaveError::NotPending {
+                    leave_request_id: pid,
+                    status: request.status().to_string(),
+                });
+            }
+            Ok(LeaveMapper::set_status(conn, pid, CANCELLED)?)
+        })
+    }
+}
+
+/// Only the manager of the employee decides on a pending request, as the employee linked to
+/// the user `user_id`. An approval is checked against the balance once more.
+fn decide(
+    pid: i32,
+    user_id: i32,
+    decision: &LeaveDecision,
+    status: &str,
+) -> Result<LeaveRequest, LeaveError> {
+    let mut conn = establish_pg_connection()?;
+    conn.transaction(|conn| {
+        let approver_id = LeaveMapper::get_employee_of_user(conn, user_id)?
+            .ok_or(LeaveError::NoEmployee(user_id))?;
+        let request = find_request(conn, pid)?;
+        if request.status() != PENDING {
+            return Err(LeaveError::NotPending {
+                leave_request_id: pid,
+                status: request.status().to_string(),
+            });
+        }
+        let employee_id = request.employee_id();
+        match find_manager(conn, employee_id)? {
+            None => return Err(LeaveError::NoManager(employee_id)),
+            Some(manager_id) if manager_id != approver_id => {
+                return Err(LeaveError::NotManager {
+                    approver_id,
+                    employee_id,
+                })
+            }
+            Some(_) => {}
+        }
+        if status == APPROVED {
+            // The request's own days are pending, and so taken off already.
+            let remaining =
+                remaining(conn, employee_id, request.leave_type_id(), request.start_date())?;
+            if remaining < 0 {
+                return Err(LeaveError::InsufficientBalance {
+                    requested: request.days() as i64,
+                    remaining: remaining + request.days() as i64,
+                });
+            }
+        }
+        Ok(LeaveMapper::decide(conn, pid, status, approver_id, decision.note.as_deref())?)
+    })
+}
+
+/// The `manager_id` of the employee.
+fn find_manager(conn: &mut PgConnection, employee_id: i32) -> Result<Option<i32>, LeaveError> {
+    match LeaveMapper::get_manager(conn, employee_id) {
+        Err(diesel::result::Error::NotFound) => Err(LeaveError::EmployeeNotFound(employee_id)),
+        result => Ok(result?),
+    }
+}
+
+fn find_request(conn: &mut PgConnection, pid: i32) -> Result<LeaveRequest, LeaveError> {
+    match LeaveMapper::get_request_for_update(conn, pid) {
+        Err(diesel::result::Error::NotFound) => Err(LeaveError::RequestNotFound(pid)),
+        result => Ok(result?),
+    }
+}
+
+/// What is left of the balance of the year `day` is in, nothing without a balance.
+fn remaining(
+    conn: &mut PgConnection,
+    employee_id: i32,
+    leave_type_id: i32,
+    day: NaiveDate,
+) -> Result<i64, LeaveError> {
+    let balance = LeaveMapper::get_balance(conn, employee_id, leave_type_id, day.year())?;
+    Ok(balance.map_or(0, |balance| balance.remaining()))
+}
+
+fn check_year(year: i32) -> Result<(), LeaveError> {
+    match year {
+        1900..=9999 => Ok(()),
+        year => Err(LeaveError::InvalidYear(year)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDate;
+    use crab_rocket_schema::schema::employee_table;
+    use crab_rocket_test_support::{fixtures, TestDb};
+    use diesel::prelude::*;
+
+    use super::LeaveService;
+    use crate::mappers::leave_mapper::LeaveMapper;
+    use crate::models::leave_request::{working_days, NewLeaveRequest, PostLeaveRequest, APPROVED};
+
+    fn day(d: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(d, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_working_days_skip_weekends() {
+        // Friday to the Monday after.
+        assert_eq!(working_days(day("2026-01-02"), day("2026-01-05")), 2);
+        assert_eq!(working_days(day("2026-01-03"), day("2026-01-04")), 0);
+        assert_eq!(working_days(day("2026-01-05"), day("2026-01-16")), 10);
+    }
+
+    #[test]
+    fn test_accrual_prorates_new_hires_and_carries_over() {
+        // `accrue` opens its own connection, the rows have to be committed.
+        let db = TestDb::new();
+        let mut conn = db.conn();
+        let veteran = fixtures::employee(&mut conn, "leave_veteran", None, None);
+        let newcomer = fixtures::employee(&mut conn, "leave_newcomer", None, None);
+        for (employee, hired) in [(veteran, "2020-01-01"), (newcomer, "2025-04-10")] {
+            diesel::update(employee_table::table.find(employee))
+                .set(employee_table::hire_date.eq(day(hired).and_hms_opt(9, 0, 0)))
+                .execute(&mut conn)
+                .unwrap();
+        }
+        let types = LeaveMapper::get_types(&mut conn).unwrap();
+        let (annual, sick) = (types[0].leave_type_id(), types[1].leave_type_id());
+        let balance = |conn: &mut PgConnection, employee, leave_type, year| {
+            let balance =
+                LeaveMapper::get_balance(conn, employee, leave_type, year).unwrap().unwrap();
+            (balance.accrued(), balance.carried_over(), balance.remaining())
+        };
+
+        assert!(LeaveService::accrue(2025).unwrap().balances >= 4);
+        assert_eq!(balance(&mut conn, veteran, annual, 2025), (20, 0, 20));
+        // Hired in April, nine months of twenty and ten days.
+        assert_eq!(balance(&mut conn, newcomer, annual, 2025), (15, 0, 15));
+        assert_eq!(balance(&mut conn, newcomer, sick, 2025), (8, 0, 8));
+
+        let obj = PostLeaveRequest {
+            leave_type_id: annual,
+            start_date: day("2025-08-04"),
+            end_date: day("2025-08-19"),
+            reason: None,
+        };
+        let taken = LeaveMapper::add_request(&mut conn, &NewLeaveRequest::new(veteran, &obj, 12));
+        LeaveMapper::set_status(&mut conn, taken.unwrap().leave_request_id(), APPROVED).unwrap();
+        assert_eq!(balance(&mut conn, veteran, annual, 2025), (20, 0, 8));
+
+        LeaveService::accrue(2026).unwrap();
+        assert_eq!(balance(&mut conn, veteran, annual, 2026), (20, 5, 25));
+        assert_eq!(balance(&mut conn, newcomer, annual, 2026), (20, 5, 25));
+        assert_eq!(balance(&mut conn, newcomer, sick, 2026), (10, 0, 10));
+        assert_eq!(LeaveService::accrue(2026).unwrap().balances, 0);
+        assert!(LeaveService::accrue(20260).is_err());
+    }
+}
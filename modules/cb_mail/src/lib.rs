@@ -0,0 +1,29 @@
+use std::sync::OnceLock;
+
+use crab_rocket_config::app_config;
+use crab_rocket_config::app_config::MailTransport;
+
+pub mod mail;
+pub mod memory;
+pub mod smtp;
+
+pub use mail::{Mail, MailError, Mailer};
+pub use memory::MemoryMailer;
+pub use smtp::SmtpMailer;
+
+static SMTP: OnceLock<SmtpMailer> = OnceLock::new();
+static OUTBOX: OnceLock<MemoryMailer> = OnceLock::new();
+
+/// The transport `[*.mail]` selects.
+pub fn mailer() -> &'static dyn Mailer {
+    let config = &app_config().mail;
+    match config.transport {
+        MailTransport::Smtp => SMTP.get_or_init(|| SmtpMailer::new(config)),
+        MailTransport::Memory => outbox(),
+    }
+}
+
+/// What the `memory` transport has sent so far.
+pub fn outbox() -> &'static MemoryMailer {
+    OUTBOX.get_or_init(MemoryMailer::default)
+}
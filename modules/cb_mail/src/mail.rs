@@ -0,0 +1,32 @@
+use std::fmt;
+
+/// A plain text message to a single recipient.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mail {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+/// Where mail goes, chosen by `[*.mail] transport`.
+pub trait Mailer: Send + Sync {
+    fn send(&self, mail: &Mail) -> Result<(), MailError>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MailError {
+    /// The recipient or sender is not a usable address.
+    Address(String),
+    Transport(String),
+}
+
+impl fmt::Display for MailError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MailError::Address(e) => write!(f, "invalid mail address: {e}"),
+            MailError::Transport(e) => write!(f, "sending mail failed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for MailError {}
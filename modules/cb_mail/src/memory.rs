@@ -0,0 +1,28 @@
+use std::sync::Mutex;
+
+use crate::mail::{Mail, MailError, Mailer};
+
+/// Keeps every mail in memory instead of sending it, for tests.
+#[derive(Debug, Default)]
+pub struct MemoryMailer {
+    sent: Mutex<Vec<Mail>>,
+}
+
+impl MemoryMailer {
+    pub fn sent(&self) -> Vec<Mail> {
+        self.sent.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Mails to `to`, oldest first. Tests running in parallel share the outbox, so they look
+    /// for their own recipients.
+    pub fn sent_to(&self, to: &str) -> Vec<Mail> {
+        self.sent().into_iter().filter(|mail| mail.to == to).collect()
+    }
+}
+
+impl Mailer for MemoryMailer {
+    fn send(&self, mail: &Mail) -> Result<(), MailError> {
+        self.sent.lock().unwrap_or_else(|e| e.into_inner()).push(mail.clone());
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use crab_rocket_config::app_config::MailConfig;
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Message, SmtpTransport, Transport};
+
+use crate::mail::{Mail, MailError, Mailer};
+
+/// Plain SMTP without TLS, for a local catcher or a relay on the same host.
+pub struct SmtpMailer {
+    from: String,
+    transport: SmtpTransport,
+}
+
+impl SmtpMailer {
+    pub fn new(config: &MailConfig) -> Self {
+        let mut transport =
+            SmtpTransport::builder_dangerous(&config.smtp_host).port(config.smtp_port);
+        if let (Some(username), Some(password)) = (&config.smtp_username, &config.smtp_password) {
+            transport = transport.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+        Self {
+            from: config.from.clone(),
+            transport: transport.build(),
+        }
+    }
+}
+
+impl Mailer for SmtpMailer {
+    fn send(&self, mail: &Mail) -> Result<(), MailError> {
+        let message = to_message(&self.from, mail)?;
+        self.transport.send(&message).map_err(|e| MailError::Transport(e.to_string()))?;
+        Ok(())
+    }
+}
+
+fn to_message(from: &str, mail: &Mail) -> Result<Message, MailError> {
+    let mailbox = |address: &str| {
+        address.parse::<Mailbox>().map_err(|e| MailError::Address(format!("{address}: {e}")))
+    };
+    Message::builder()
+        .from(mailbox(from)?)
+        .to(mailbox(&mail.to)?)
+        .subject(&mail.subject)
+        .header(ContentType::TEXT_PLAIN)
+        .body(mail.body.clone())
+        .map_err(|e| MailError::Address(e.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::to_message;
+    use crate::mail::{Mail, MailError};
+
+    #[test]
+    fn test_to_message() {
+        let mail = Mail {
+            to: String::from("ada@example.com"),
+            subject: String::from("Hello"),
+            body: String::from("Line one\nLine two"),
+        };
+        let message = String::from_utf8(
+            to_message("Crab Rocket <no-reply@localhost>", &mail).unwrap().formatted(),
+        )
+        .unwrap();
+        assert!(message.contains("To: ada@example.com"));
+        assert!(message.contains("Subject: Hello"));
+
+        let bad = Mail {
+            to: String::from("not an address"),
+            ..mail
+        };
+        assert!(matches!(to_message("no-reply@localhost", &bad), Err(MailError::Address(_))));
+    }
+}
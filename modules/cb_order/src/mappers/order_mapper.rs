@@ -40,8 +40,8 @@ impl MapperCRUD for OrderMapper {
         //
         // limit 始终为 per_page
         // 计算分页相关
-        let page = (param.pagination.offset.unwrap() / param.pagination.limit.unwrap()) + 1;
-        let per_page = param.pagination.limit.unwrap();
+        let page = (param.pagination.offset_or_default() / param.pagination.limit_or_default()) + 1;
+        let per_page = param.pagination.limit_or_default();
         // 获取总记录数
         let total_count = dsl::order_table.count().get_result::<i64>(conn)? as i32;
         // 计算总页数
@@ -64,6 +64,7 @@ impl MapperCRUD for OrderMapper {
         // 分页查询
         let data = dsl::order_table
             .order(dsl::order_date.desc())
+            .then_order_by(dsl::order_id.desc())
             .limit(per_page as i64)
             .offset(((page - 1) * per_page) as i64)
             .load::<Order>(conn)?;
@@ -126,8 +127,8 @@ impl MapperCRUD for OrderMapper {
         //
         // limit 始终为 per_page
         // 计算分页相关
-        let page = (param.pagination.offset.unwrap() / param.pagination.limit.unwrap()) + 1;
-        let per_page = param.pagination.limit.unwrap();
+        let page = (param.pagination.offset_or_default() / param.pagination.limit_or_default()) + 1;
+        let per_page = param.pagination.limit_or_default();
         // 获取总记录数
         let total_count = dsl::order_table.count().get_result::<i64>(conn)? as i32;
         // 计算总页数
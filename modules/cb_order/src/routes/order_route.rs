@@ -1,3 +1,5 @@
+use crab_rocket_auth::guards::authorized::Authorized;
+use crab_rocket_auth::permission;
 use obj_traits::controller::controller_crud::ControllerCRUD;
 use obj_traits::request::pagination_request_param::{PaginationParam, PaginationParamTrait};
 use obj_traits::request::request_param::RequestParam;
@@ -7,14 +9,12 @@ use crate::controllers::order_controller::OrderController;
 use crate::models::order::{PatchOrder, PostOrder};
 use crate::models::order_filter::OrderFilter;
 
+permission!(pub OrderCreate, "order", "create");
+permission!(pub OrderUpdate, "order", "update");
+permission!(pub OrderDelete, "order", "delete");
+
 #[get("/order?<limit>&<offset>")]
-pub fn get_orders(mut limit: Option<i32>, mut offset: Option<i32>) -> Json<serde_json::Value> {
-    if limit.is_none() {
-        limit = Some(10);
-    };
-    if offset.is_none() {
-        offset = Some(0);
-    };
+pub fn get_orders(limit: Option<i32>, offset: Option<i32>) -> Json<serde_json::Value> {
     let params = RequestParam::new(PaginationParam::new(limit, offset), None);
     println!("{:?}", params);
     crab_rocket_schema::update_reload::update_reload_count();
@@ -43,7 +43,10 @@ pub fn get_order_by_id(id: i32) -> Json<serde_json::Value> {
 }
 
 #[post("/order", data = "<order>")]
-pub fn insert_single_order(order: Json<PostOrder>) -> Json<serde_json::Value> {
+pub fn insert_single_order(
+    _auth: Authorized<OrderCreate>,
+    order: Json<PostOrder>,
+) -> Json<serde_json::Value> {
     let mut obj: PostOrder = order.into_inner();
 
     let resp = OrderController::add_single(&mut obj).unwrap();
@@ -52,14 +55,18 @@ pub fn insert_single_order(order: Json<PostOrder>) -> Json<serde_json::Value> {
 }
 
 #[delete("/order/<id>")]
-pub fn delete_order_by_id(id: i32) -> Json<serde_json::Value> {
+pub fn delete_order_by_id(_auth: Authorized<OrderDelete>, id: i32) -> Json<serde_json::Value> {
     let resp = OrderController::delete_by_id(id).unwrap();
     let json_value = serde_json::to_value(&resp).unwrap();
     Json(serde_json::from_value(json_value).unwrap())
 }
 
 #[patch("/order/<id>", data = "<task>")]
-pub fn update_order_by_id(id: i32, task: Json<PatchOrder>) -> Json<serde_json::Value> {
+pub fn update_order_by_id(
+    _auth: Authorized<OrderUpdate>,
+    id: i32,
+    task: Json<PatchOrder>,
+) -> Json<serde_json::Value> {
     let resp = OrderController::update_by_id(id, &task).unwrap();
     let json_value = serde_json::to_value(&resp).unwrap();
     Json(serde_json::from_value(json_value).unwrap())
@@ -0,0 +1,70 @@
+use crate::error::PayrollError;
+use crate::models::compensation::{Compensation, PatchCompensation, PostCompensation};
+use crate::models::payroll_run::{PayrollRun, PayrollRunDetail, PostPayrollRun};
+use crate::models::payslip::{Payslip, PayslipDetail};
+use crate::models::payslip_export::PayslipExport;
+use crate::services::compensation_service::CompensationService;
+use crate::services::payroll_service::PayrollService;
+
+pub struct PayrollController {}
+
+pub(crate) fn from_payroll_error<T>(e: PayrollError) -> (i32, String, Option<T>) {
+    match e {
+        PayrollError::Internal(_) => {
+            println!("{e:?}");
+            (500, String::from("Internal Server Error"), None)
+        }
+        e => (e.status(), e.to_string(), None),
+    }
+}
+
+fn to_tuple<T>(result: Result<T, PayrollError>) -> (i32, String, Option<T>) {
+    match result {
+        Ok(data) => (200, String::from("Success"), Some(data)),
+        Err(e) => from_payroll_error(e),
+    }
+}
+
+impl PayrollController {
+    pub fn get_compensation(employee_id: i32) -> (i32, String, Option<Vec<Compensation>>) {
+        to_tuple(CompensationService::get_by_employee(employee_id))
+    }
+
+    pub fn add_compensation(
+        employee_id: i32,
+        obj: &PostCompensation,
+    ) -> (i32, String, Option<Compensation>) {
+        to_tuple(CompensationService::add(employee_id, obj))
+    }
+
+    pub fn update_compensation(
+        pid: i32,
+        obj: &PatchCompensation,
+    ) -> (i32, String, Option<Compensation>) {
+        to_tuple(CompensationService::update(pid, obj))
+    }
+
+    pub fn run(obj: &PostPayrollRun, created_by: i32) -> (i32, String, Option<PayrollRun>) {
+        to_tuple(PayrollService::run(obj, Some(created_by)))
+    }
+
+    pub fn get_runs(limit: i64) -> (i32, String, Option<Vec<PayrollRun>>) {
+        to_tuple(PayrollService::get_runs(limit))
+    }
+
+    pub fn get_run(pid: i32) -> (i32, String, Option<PayrollRunDetail>) {
+        to_tuple(PayrollService::get_run(pid))
+    }
+
+    pub fn export(pid: i32) -> Result<PayslipExport, (i32, String, Option<()>)> {
+        PayrollService::export(pid).map_err(from_payroll_error)
+    }
+
+    pub fn get_payslip(pid: i32) -> (i32, String, Option<PayslipDetail>) {
+        to_tuple(PayrollService::get_payslip(pid))
+    }
+
+    pub fn get_payslips_by_employee(employee_id: i32) -> (i32, String, Option<Vec<Payslip>>) {
+        to_tuple(PayrollService::get_payslips_by_employee(employee_id))
+    }
+}
@@ -0,0 +1,112 @@
+use std::error::Error;
+use std::fmt;
+
+use chrono::NaiveDate;
+use diesel::ConnectionError;
+
+/// Why compensation or payroll data could not be read or written.
+#[derive(Debug)]
+pub enum PayrollError {
+    EmployeeNotFound(i32),
+    CompensationNotFound(i32),
+    RunNotFound(i32),
+    PayslipNotFound(i32),
+    /// Not one of `salary`, `allowance` or `deduction`.
+    UnknownComponent(String),
+    NegativeAmount(i32),
+    /// A period or compensation record ending before it starts.
+    InvalidPeriod {
+        start: NaiveDate,
+        end: NaiveDate,
+    },
+    /// An employee has one salary at a time, a new one has to start after the latest.
+    SalaryOverlap {
+        employee_id: i32,
+        effective_from: NaiveDate,
+    },
+    /// Every day is paid by one payroll run at most.
+    RunOverlap {
+        payroll_run_id: i32,
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+    },
+    Internal(String),
+}
+
+impl PayrollError {
+    pub fn status(&self) -> i32 {
+        match self {
+            PayrollError::EmployeeNotFound(_)
+            | PayrollError::CompensationNotFound(_)
+            | PayrollError::RunNotFound(_)
+            | PayrollError::PayslipNotFound(_) => 404,
+            PayrollError::UnknownComponent(_)
+            | PayrollError::NegativeAmount(_)
+            | PayrollError::InvalidPeriod {
+                ..
+            } => 400,
+            PayrollError::SalaryOverlap {
+                ..
+            }
+            | PayrollError::RunOverlap {
+                ..
+            } => 409,
+            PayrollError::Internal(_) => 500,
+        }
+    }
+}
+
+impl fmt::Display for PayrollError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PayrollError::EmployeeNotFound(id) => write!(f, "employee {id} not found"),
+            PayrollError::CompensationNotFound(id) => {
+                write!(f, "compensation record {id} not found")
+            }
+            PayrollError::RunNotFound(id) => write!(f, "payroll run {id} not found"),
+            PayrollError::PayslipNotFound(id) => write!(f, "payslip {id} not found"),
+            PayrollError::UnknownComponent(component) => write!(
+                f,
+                "unknown component `{component}`, expected `salary`, `allowance` or `deduction`"
+            ),
+            PayrollError::NegativeAmount(amount) => {
+                write!(f, "amount {amount} is negative, deductions are positive amounts too")
+            }
+            PayrollError::InvalidPeriod {
+                start,
+                end,
+            } => write!(f, "the period from {start} to {end} ends before it starts"),
+            PayrollError::SalaryOverlap {
+                employee_id,
+                effective_from,
+            } => write!(
+                f,
+                "the salary of employee {employee_id} would overlap the one effective from \
+                 {effective_from}"
+            ),
+            PayrollError::RunOverlap {
+                payroll_run_id,
+                period_start,
+                period_end,
+            } => write!(
+                f,
+                "payroll run {payroll_run_id} already pays {period_start} to {period_end}"
+            ),
+            PayrollError::Internal(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for PayrollError {}
+
+impl From<diesel::result::Error> for PayrollError {
+    fn from(e: diesel::result::Error) -> Self {
+        PayrollError::Internal(e.to_string())
+    }
+}
+
+impl From<ConnectionError> for PayrollError {
+    fn from(e: ConnectionError) -> Self {
+        PayrollError::Internal(e.to_string())
+    }
+}
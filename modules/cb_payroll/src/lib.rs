@@ -0,0 +1,27 @@
+pub mod error;
+
+pub mod models {
+    pub mod compensation;
+    pub mod payroll_run;
+    pub mod payslip;
+    pub mod payslip_export;
+}
+
+pub mod mappers {
+    pub mod compensation_mapper;
+    pub mod payroll_mapper;
+}
+
+pub mod controllers {
+    pub mod payroll_controller;
+}
+
+pub mod routes {
+    pub mod compensation_route;
+    pub mod payroll_route;
+}
+
+pub mod services {
+    pub mod compensation_service;
+    pub mod payroll_service;
+}
@@ -0,0 +1,36 @@
+#[macro_use]
+extern crate rocket;
+
+use crab_rocket_payroll::routes::compensation_route::*;
+use crab_rocket_payroll::routes::payroll_route::*;
+use dotenvy::dotenv;
+use std::env;
+#[launch]
+fn rocket() -> _ {
+    // Clear environment variable before running.
+    env::remove_var("DATABASE_URL");
+    dotenv().ok();
+    let figment = crab_rocket_config::init();
+
+    crab_rocket_utils::run_preload();
+
+    let cors = crab_rocket_config::cors::cors();
+
+    rocket::custom(figment)
+        .mount(
+            "/api",
+            routes![
+                get_employee_compensation,
+                insert_employee_compensation,
+                update_compensation_by_id,
+                get_employee_payslips,
+                insert_payroll_run,
+                get_payroll_runs,
+                get_payroll_run_by_id,
+                export_payroll_run,
+                get_payslip_by_id,
+                options_payroll_run
+            ],
+        )
+        .attach(cors)
+}
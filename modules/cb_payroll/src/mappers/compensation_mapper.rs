@@ -0,0 +1,119 @@
+use chrono::NaiveDate;
+use crab_rocket_schema::schema::compensation_table::dsl;
+use crab_rocket_schema::schema::employee_table;
+use diesel::{prelude::*, result::Error};
+
+use crate::models::compensation::{Compensation, NewCompensation, SALARY};
+
+pub struct CompensationMapper {}
+
+impl CompensationMapper {
+    /// The employee's records, the latest first.
+    pub fn get_by_employee(
+        conn: &mut PgConnection,
+        employee_id: i32,
+    ) -> Result<Vec<Compensation>, Error> {
+        dsl::compensation_table
+            .filter(dsl::employee_id.eq(employee_id))
+            .select(Compensation::as_select())
+            .order((dsl::effective_from.desc(), dsl::compensation_id.desc()))
+            .load(conn)
+    }
+
+    pub fn get_by_id(conn: &mut PgConnection, pid: i32) -> Result<Compensation, Error> {
+        dsl::compensation_table.find(pid).select(Compensation::as_select()).first(conn)
+    }
+
+    pub fn add_single(
+        conn: &mut PgConnection,
+        obj: &NewCompensation,
+    ) -> Result<Compensation, Error> {
+        diesel::insert_into(dsl::compensation_table)
+            .values(obj)
+            .returning(Compensation::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn set_effective_to(
+        conn: &mut PgConnection,
+        pid: i32,
+        effective_to: Option<NaiveDate>,
+    ) -> Result<Compensation, Error> {
+        diesel::update(dsl::compensation_table.find(pid))
+            .set(dsl::effective_to.eq(effective_to))
+            .returning(Compensation::as_returning())
+            .get_result(conn)
+    }
+
+    /// The salary record starting last, `None` for an employee never paid a salary.
+    pub fn latest_salary(
+        conn: &mut PgConnection,
+        employee_id: i32,
+    ) -> Result<Option<Compensation>, Error> {
+        dsl::compensation_table
+            .filter(dsl::employee_id.eq(employee_id).and(dsl::component.eq(SALARY)))
+            .select(Compensation::as_select())
+            .order(dsl::effective_from.desc())
+            .first(conn)
+            .optional()
+    }
+
+    /// The first salary record of the employee starting after `effective_from`.
+    pub fn next_salary(
+        conn: &mut PgConnection,
+        employee_id: i32,
+        effective_from: NaiveDate,
+    ) -> Result<Option<Compensation>, Error> {
+        dsl::compensation_table
+            .filter(dsl::employee_id.eq(employee_id).and(dsl::component.eq(SALARY)))
+            .filter(dsl::effective_from.gt(effective_from))
+            .select(Compensation::as_select())
+            .order(dsl::effective_from.asc())
+            .first(conn)
+            .optional()
+    }
+
+    /// Ends the salaries still running on `day` the day before it, returns how many.
+    pub fn end_salaries_before(
+        conn: &mut PgConnection,
+        employee_id: i32,
+        day: NaiveDate,
+    ) -> Result<usize, Error> {
+        let Some(day_before) = day.pred_opt() else {
+            return Ok(0);
+        };
+        diesel::update(
+            dsl::compensation_table
+                .filter(dsl::employee_id.eq(employee_id).and(dsl::component.eq(SALARY)))
+                .filter(dsl::effective_from.lt(day))
+                .filter(dsl::effective_to.is_null().or(dsl::effective_to.ge(day))),
+        )
+        .set(dsl::effective_to.eq(day_before))
+        .execute(conn)
+    }
+
+    /// Every record applying on at least one day of `start..=end`, by employee.
+    pub fn overlapping(
+        conn: &mut PgConnection,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Vec<Compensation>, Error> {
+        dsl::compensation_table
+            .filter(dsl::effective_from.le(end))
+            .filter(dsl::effective_to.is_null().or(dsl::effective_to.ge(start)))
+            .select(Compensation::as_select())
+            .order((dsl::employee_id.asc(), dsl::effective_from.asc(), dsl::compensation_id.asc()))
+            .load(conn)
+    }
+
+    /// `(employee_name, department_id)` of an employee.
+    pub fn get_employee(
+        conn: &mut PgConnection,
+        employee_id: i32,
+    ) -> Result<(String, Option<i32>), Error> {
+        employee_table::table
+            .find(employee_id)
+            .select((employee_table::employee_name, employee_table::department_id))
+            .first(conn)
+    }
+}
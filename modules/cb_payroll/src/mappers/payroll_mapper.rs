@@ -0,0 +1,107 @@
+use chrono::NaiveDate;
+use crab_rocket_schema::schema::{payroll_run_table, payslip_line_table, payslip_table};
+use diesel::{prelude::*, result::Error};
+
+use crate::models::payroll_run::{NewPayrollRun, PayrollRun};
+use crate::models::payslip::{NewPayslip, NewPayslipLine, Payslip, PayslipLine};
+
+pub struct PayrollMapper {}
+
+impl PayrollMapper {
+    pub fn add_run(conn: &mut PgConnection, obj: &NewPayrollRun) -> Result<PayrollRun, Error> {
+        diesel::insert_into(payroll_run_table::table)
+            .values(obj)
+            .returning(PayrollRun::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn set_totals(
+        conn: &mut PgConnection,
+        pid: i32,
+        employee_count: i32,
+        total_gross: i64,
+        total_net: i64,
+    ) -> Result<PayrollRun, Error> {
+        diesel::update(payroll_run_table::table.find(pid))
+            .set((
+                payroll_run_table::employee_count.eq(employee_count),
+                payroll_run_table::total_gross.eq(total_gross),
+                payroll_run_table::total_net.eq(total_net),
+            ))
+            .returning(PayrollRun::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn get_run(conn: &mut PgConnection, pid: i32) -> Result<PayrollRun, Error> {
+        payroll_run_table::table.find(pid).select(PayrollRun::as_select()).first(conn)
+    }
+
+    /// The latest runs first.
+    pub fn get_runs(conn: &mut PgConnection, limit: i64) -> Result<Vec<PayrollRun>, Error> {
+        payroll_run_table::table
+            .select(PayrollRun::as_select())
+            .order(payroll_run_table::period_start.desc())
+            .limit(limit)
+            .load(conn)
+    }
+
+    /// A run paying any day of `start..=end`.
+    pub fn overlapping_run(
+        conn: &mut PgConnection,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<Option<PayrollRun>, Error> {
+        payroll_run_table::table
+            .filter(payroll_run_table::period_start.le(end))
+            .filter(payroll_run_table::period_end.ge(start))
+            .select(PayrollRun::as_select())
+            .first(conn)
+            .optional()
+    }
+
+    pub fn add_payslip(conn: &mut PgConnection, obj: &NewPayslip) -> Result<Payslip, Error> {
+        diesel::insert_into(payslip_table::table)
+            .values(obj)
+            .returning(Payslip::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn add_lines(conn: &mut PgConnection, lines: &[NewPayslipLine]) -> Result<usize, Error> {
+        diesel::insert_into(payslip_line_table::table).values(lines).execute(conn)
+    }
+
+    pub fn get_payslip(conn: &mut PgConnection, pid: i32) -> Result<Payslip, Error> {
+        payslip_table::table.find(pid).select(Payslip::as_select()).first(conn)
+    }
+
+    pub fn get_payslips_by_run(
+        conn: &mut PgConnection,
+        payroll_run_id: i32,
+    ) -> Result<Vec<Payslip>, Error> {
+        payslip_table::table
+            .filter(payslip_table::payroll_run_id.eq(payroll_run_id))
+            .select(Payslip::as_select())
+            .order(payslip_table::payslip_id.asc())
+            .load(conn)
+    }
+
+    /// The latest payslips first.
+    pub fn get_payslips_by_employee(
+        conn: &mut PgConnection,
+        employee_id: i32,
+    ) -> Result<Vec<Payslip>, Error> {
+        payslip_table::table
+            .filter(payslip_table::employee_id.eq(employee_id))
+            .select(Payslip::as_select())
+            .order(payslip_table::period_start.desc())
+            .load(conn)
+    }
+
+    pub fn get_lines(conn: &mut PgConnection, payslip_id: i32) -> Result<Vec<PayslipLine>, Error> {
+        payslip_line_table::table
+            .filter(payslip_line_table::payslip_id.eq(payslip_id))
+            .select(PayslipLine::as_select())
+            .order(payslip_line_table::payslip_line_id.asc())
+            .load(conn)
+    }
+}
@@ -0,0 +1,125 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel::prelude::*;
+use rocket::serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+pub const SALARY: &str = "salary";
+pub const ALLOWANCE: &str = "allowance";
+pub const DEDUCTION: &str = "deduction";
+pub const COMPONENTS: [&str; 3] = [SALARY, ALLOWANCE, DEDUCTION];
+
+/// One effective-dated part of an employee's pay. `amount` is for a full pay period, a record
+/// covering part of a period is paid by the day.
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+#[diesel(table_name = crab_rocket_schema::schema::compensation_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Compensation {
+    compensation_id: i32,
+    employee_id: i32,
+    /// `salary`, `allowance` or `deduction`.
+    component: String,
+    name: String,
+    amount: i32,
+    effective_from: NaiveDate,
+    /// The last day the record applies, open-ended when `None`.
+    effective_to: Option<NaiveDate>,
+    created_at: NaiveDateTime,
+}
+
+impl Compensation {
+    pub fn compensation_id(&self) -> i32 {
+        self.compensation_id
+    }
+
+    pub fn employee_id(&self) -> i32 {
+        self.employee_id
+    }
+
+    pub fn component(&self) -> &str {
+        &self.component
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn amount(&self) -> i32 {
+        self.amount
+    }
+
+    pub fn effective_from(&self) -> NaiveDate {
+        self.effective_from
+    }
+
+    pub fn effective_to(&self) -> Option<NaiveDate> {
+        self.effective_to
+    }
+
+    /// How many days of `start..=end` the record applies on.
+    pub fn days_within(&self, start: NaiveDate, end: NaiveDate) -> i64 {
+        let from = self.effective_from.max(start);
+        let to = self.effective_to.map_or(end, |to| to.min(end));
+        ((to - from).num_days() + 1).max(0)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct PostCompensation {
+    pub component: String,
+    /// Defaults to `Base salary` for salaries and to the component otherwise.
+    pub name: Option<String>,
+    pub amount: i32,
+    pub effective_from: NaiveDate,
+    pub effective_to: Option<NaiveDate>,
+}
+
+/// Ends a record on `effective_to`, or reopens it with `None`. The amount of a record never
+/// changes, a raise is a new salary record.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct PatchCompensation {
+    pub effective_to: Option<NaiveDate>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crab_rocket_schema::schema::compensation_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NewCompensation {
+    employee_id: i32,
+    component: String,
+    name: String,
+    amount: i32,
+    effective_from: NaiveDate,
+    effective_to: Option<NaiveDate>,
+}
+
+impl NewCompensation {
+    pub fn new(employee_id: i32, obj: &PostCompensation) -> Self {
+        let name = obj.name.clone().unwrap_or_else(|| match obj.component.as_str() {
+            SALARY => String::from("Base salary"),
+            component => component.to_string(),
+        });
+        Self {
+            employee_id,
+            component: obj.component.clone(),
+            name,
+            amount: obj.amount,
+            effective_from: obj.effective_from,
+            effective_to: obj.effective_to,
+        }
+    }
+
+    /// An open-ended base salary.
+    pub fn salary(employee_id: i32, amount: i32, effective_from: NaiveDate) -> Self {
+        Self {
+            employee_id,
+            component: SALARY.to_string(),
+            name: String::from("Base salary"),
+            amount,
+            effective_from,
+            effective_to: None,
+        }
+    }
+}
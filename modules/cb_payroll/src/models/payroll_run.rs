@@ -0,0 +1,73 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel::prelude::*;
+use rocket::serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::models::payslip::Payslip;
+
+/// Pays every employee with compensation in `period_start..=period_end` once.
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+#[diesel(table_name = crab_rocket_schema::schema::payroll_run_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PayrollRun {
+    payroll_run_id: i32,
+    period_start: NaiveDate,
+    period_end: NaiveDate,
+    employee_count: i32,
+    total_gross: i64,
+    total_net: i64,
+    /// The `user_id` that started the run.
+    created_by: Option<i32>,
+    created_at: NaiveDateTime,
+}
+
+impl PayrollRun {
+    pub fn payroll_run_id(&self) -> i32 {
+        self.payroll_run_id
+    }
+
+    pub fn period_start(&self) -> NaiveDate {
+        self.period_start
+    }
+
+    pub fn period_end(&self) -> NaiveDate {
+        self.period_end
+    }
+
+    pub fn employee_count(&self) -> i32 {
+        self.employee_count
+    }
+
+    pub fn total_gross(&self) -> i64 {
+        self.total_gross
+    }
+
+    pub fn total_net(&self) -> i64 {
+        self.total_net
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct PostPayrollRun {
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crab_rocket_schema::schema::payroll_run_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NewPayrollRun {
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub created_by: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct PayrollRunDetail {
+    #[serde(flatten)]
+    pub run: PayrollRun,
+    pub payslips: Vec<Payslip>,
+}
@@ -0,0 +1,119 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel::prelude::*;
+use rocket::serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// What one employee is paid by a payroll run, with the name and department they had then.
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+#[diesel(table_name = crab_rocket_schema::schema::payslip_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Payslip {
+    payslip_id: i32,
+    payroll_run_id: i32,
+    /// `None` once the employee is deleted.
+    employee_id: Option<i32>,
+    employee_name: String,
+    department_id: Option<i32>,
+    period_start: NaiveDate,
+    period_end: NaiveDate,
+    salary: i64,
+    allowances: i64,
+    deductions: i64,
+    /// `salary + allowances`.
+    gross: i64,
+    /// `gross - deductions`.
+    net: i64,
+    created_at: NaiveDateTime,
+}
+
+impl Payslip {
+    pub const CSV_HEADER: &'static str = "payslip_id,payroll_run_id,employee_id,employee_name,\
+        department_id,period_start,period_end,salary,allowances,deductions,gross,net";
+
+    pub fn payslip_id(&self) -> i32 {
+        self.payslip_id
+    }
+
+    pub fn employee_id(&self) -> Option<i32> {
+        self.employee_id
+    }
+
+    pub fn gross(&self) -> i64 {
+        self.gross
+    }
+
+    pub fn net(&self) -> i64 {
+        self.net
+    }
+
+    /// The payslip as a line of [`Payslip::CSV_HEADER`] columns.
+    pub fn csv_row(&self) -> String {
+        let optional = |id: Option<i32>| id.map(|id| id.to_string()).unwrap_or_default();
+        format!(
+            "{},{},{},\"{}\",{},{},{},{},{},{},{},{}",
+            self.payslip_id,
+            self.payroll_run_id,
+            optional(self.employee_id),
+            self.employee_name.replace('"', "\"\""),
+            optional(self.department_id),
+            self.period_start,
+            self.period_end,
+            self.salary,
+            self.allowances,
+            self.deductions,
+            self.gross,
+            self.net
+        )
+    }
+}
+
+#[derive(Insertable, Debug, Default)]
+#[diesel(table_name = crab_rocket_schema::schema::payslip_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NewPayslip {
+    pub payroll_run_id: i32,
+    pub employee_id: Option<i32>,
+    pub employee_name: String,
+    pub department_id: Option<i32>,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub salary: i64,
+    pub allowances: i64,
+    pub deductions: i64,
+    pub gross: i64,
+    pub net: i64,
+}
+
+/// The share of one compensation record on a payslip.
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+#[diesel(table_name = crab_rocket_schema::schema::payslip_line_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PayslipLine {
+    payslip_line_id: i32,
+    payslip_id: i32,
+    compensation_id: Option<i32>,
+    component: String,
+    name: String,
+    amount: i64,
+}
+
+#[derive(Insertable, Debug, Clone, PartialEq, Eq)]
+#[diesel(table_name = crab_rocket_schema::schema::payslip_line_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NewPayslipLine {
+    pub payslip_id: i32,
+    pub compensation_id: Option<i32>,
+    pub component: String,
+    pub name: String,
+    pub amount: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct PayslipDetail {
+    #[serde(flatten)]
+    pub payslip: Payslip,
+    pub lines: Vec<PayslipLine>,
+}
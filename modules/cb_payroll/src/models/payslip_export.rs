@@ -0,0 +1,40 @@
+use rocket::{
+    http::{ContentType, Header},
+    response::Responder,
+    Request, Response,
+};
+
+use crate::models::payslip::Payslip;
+
+/// The payslips of a payroll run as a CSV download.
+pub struct PayslipExport {
+    pub file_name: String,
+    pub csv: String,
+}
+
+impl PayslipExport {
+    pub fn new(payroll_run_id: i32, payslips: &[Payslip]) -> Self {
+        let mut csv = String::from(Payslip::CSV_HEADER);
+        csv.push('\n');
+        for payslip in payslips {
+            csv.push_str(&payslip.csv_row());
+            csv.push('\n');
+        }
+        Self {
+            file_name: format!("payroll-run-{payroll_run_id}.csv"),
+            csv,
+        }
+    }
+}
+
+impl<'r> Responder<'r, 'static> for PayslipExport {
+    fn respond_to(self, req: &'r Request<'_>) -> Result<Response<'static>, rocket::http::Status> {
+        let mut response = self.csv.respond_to(req)?;
+        response.set_header(ContentType::CSV);
+        response.set_header(Header::new(
+            "Content-Disposition",
+            format!("attachment; filename=\"{}\"", self.file_name),
+        ));
+        Ok(response)
+    }
+}
@@ -0,0 +1,51 @@
+use crab_rocket_auth::guards::authorized::Authorized;
+use rocket::response::status;
+use rocket::serde::json::Json;
+use rocket::{get, patch, post};
+
+use crate::controllers::payroll_controller::PayrollController;
+use crate::models::compensation::{PatchCompensation, PostCompensation};
+use crate::routes::payroll_route::{to_response, PayrollCreate, PayrollRead, PayrollUpdate};
+
+/// The employee's compensation history, the latest record first.
+#[get("/employee/<id>/compensation")]
+pub fn get_employee_compensation(
+    _auth: Authorized<PayrollRead>,
+    id: i32,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = PayrollController::get_compensation(id);
+    to_response(code, message, data)
+}
+
+/// A salary has to start after the employee's latest one (`409` otherwise), which then ends
+/// the day before.
+#[post("/employee/<id>/compensation", data = "<compensation>")]
+pub fn insert_employee_compensation(
+    _auth: Authorized<PayrollCreate>,
+    id: i32,
+    compensation: Json<PostCompensation>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = PayrollController::add_compensation(id, &compensation);
+    to_response(code, message, data)
+}
+
+/// Ends a record on `effective_to`, or reopens it with `null`.
+#[patch("/compensation/<id>", data = "<compensation>")]
+pub fn update_compensation_by_id(
+    _auth: Authorized<PayrollUpdate>,
+    id: i32,
+    compensation: Json<PatchCompensation>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = PayrollController::update_compensation(id, &compensation);
+    to_response(code, message, data)
+}
+
+/// The employee's payslips, the latest first.
+#[get("/employee/<id>/payslips")]
+pub fn get_employee_payslips(
+    _auth: Authorized<PayrollRead>,
+    id: i32,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = PayrollController::get_payslips_by_employee(id);
+    to_response(code, message, data)
+}
@@ -0,0 +1,83 @@
+use crab_rocket_auth::guards::authorized::Authorized;
+use crab_rocket_auth::permission;
+use obj_traits::response::api_response::ApiResponse;
+use rocket::http::Status;
+use rocket::response::status;
+use rocket::serde::json::Json;
+use rocket::{get, options, post};
+
+use crate::controllers::payroll_controller::PayrollController;
+use crate::models::payroll_run::PostPayrollRun;
+use crate::models::payslip_export::PayslipExport;
+
+permission!(pub PayrollRead, "payroll", "read");
+permission!(pub PayrollCreate, "payroll", "create");
+permission!(pub PayrollUpdate, "payroll", "update");
+
+/// How many runs `GET /payroll/run` lists without a `limit`.
+const DEFAULT_RUN_LIMIT: i64 = 12;
+
+/// Answers with `code` as the HTTP status, in the shape of the other payroll routes.
+pub(crate) fn to_response<T: rocket::serde::Serialize + Default>(
+    code: i32,
+    message: String,
+    data: T,
+) -> status::Custom<Json<serde_json::Value>> {
+    let response = serde_json::to_value(ApiResponse::new(code, message, data)).unwrap();
+    let status = Status::from_code(code as u16).unwrap_or(Status::InternalServerError);
+    status::Custom(status, Json(response))
+}
+
+/// Pays `period_start..=period_end`, a period overlapping an earlier run answers `409`.
+#[post("/payroll/run", data = "<run>")]
+pub fn insert_payroll_run(
+    auth: Authorized<PayrollCreate>,
+    run: Json<PostPayrollRun>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = PayrollController::run(&run, auth.auth.user_id());
+    to_response(code, message, data)
+}
+
+/// The latest runs first.
+#[get("/payroll/run?<limit>")]
+pub fn get_payroll_runs(
+    _auth: Authorized<PayrollRead>,
+    limit: Option<i64>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = PayrollController::get_runs(limit.unwrap_or(DEFAULT_RUN_LIMIT));
+    to_response(code, message, data)
+}
+
+/// The run with its payslips.
+#[get("/payroll/run/<id>")]
+pub fn get_payroll_run_by_id(
+    _auth: Authorized<PayrollRead>,
+    id: i32,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = PayrollController::get_run(id);
+    to_response(code, message, data)
+}
+
+/// The payslips of the run as `payroll-run-<id>.csv`.
+#[get("/payroll/run/<id>/export")]
+pub fn export_payroll_run(
+    _auth: Authorized<PayrollRead>,
+    id: i32,
+) -> Result<PayslipExport, status::Custom<Json<serde_json::Value>>> {
+    PayrollController::export(id).map_err(|(code, message, data)| to_response(code, message, data))
+}
+
+/// The payslip with one line per compensation record.
+#[get("/payroll/payslip/<id>")]
+pub fn get_payslip_by_id(
+    _auth: Authorized<PayrollRead>,
+    id: i32,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = PayrollController::get_payslip(id);
+    to_response(code, message, data)
+}
+
+#[options("/payroll/run")]
+pub fn options_payroll_run() -> Status {
+    Status::Ok
+}
@@ -0,0 +1,99 @@
+use chrono::NaiveDate;
+use crab_rocket_schema::establish_pg_connection;
+use diesel::{Connection, PgConnection};
+
+use crate::error::PayrollError;
+use crate::mappers::compensation_mapper::CompensationMapper;
+use crate::models::compensation::{
+    Compensation, NewCompensation, PatchCompensation, PostCompensation, COMPONENTS, SALARY,
+};
+
+pub struct CompensationService {}
+
+impl CompensationService {
+    /// The employee's compensation history, the latest record first.
+    pub fn get_by_employee(employee_id: i32) -> Result<Vec<Compensation>, PayrollError> {
+        let mut conn = establish_pg_connection()?;
+        find_employee(&mut conn, employee_id)?;
+        Ok(CompensationMapper::get_by_employee(&mut conn, employee_id)?)
+    }
+
+    /// Records a component from `effective_from` on. A salary has to start after the latest
+    /// salary of the employee, which then ends the day before.
+    pub fn add(employee_id: i32, obj: &PostCompensation) -> Result<Compensation, PayrollError> {
+        if !COMPONENTS.contains(&obj.component.as_str()) {
+            return Err(PayrollError::UnknownComponent(obj.component.clone()));
+        }
+        if obj.amount < 0 {
+            return Err(PayrollError::NegativeAmount(obj.amount));
+        }
+        check_period(obj.effective_from, obj.effective_to)?;
+        let mut conn = establish_pg_connection()?;
+        conn.transaction(|conn| {
+            find_employee(conn, employee_id)?;
+            if obj.component == SALARY {
+                if let Some(latest) = CompensationMapper::latest_salary(conn, employee_id)? {
+                    if latest.effective_from() >= obj.effective_from {
+                        return Err(PayrollError::SalaryOverlap {
+                            employee_id,
+                            effective_from: latest.effective_from(),
+                        });
+                    }
+                }
+                CompensationMapper::end_salaries_before(conn, employee_id, obj.effective_from)?;
+            }
+            let record = NewCompensation::new(employee_id, obj);
+            Ok(CompensationMapper::add_single(conn, &record)?)
+        })
+    }
+
+    /// Moves the last day of a record, a salary can not run into the next one.
+    pub fn update(pid: i32, obj: &PatchCompensation) -> Result<Compensation, PayrollError> {
+        let mut conn = establish_pg_connection()?;
+        conn.transaction(|conn| {
+            let record = match CompensationMapper::get_by_id(conn, pid) {
+                Err(diesel::result::Error::NotFound) => {
+                    return Err(PayrollError::CompensationNotFound(pid))
+                }
+                result => result?,
+            };
+            check_period(record.effective_from(), obj.effective_to)?;
+            if record.component() == SALARY {
+                let next = CompensationMapper::next_salary(
+                    conn,
+                    record.employee_id(),
+                    record.effective_from(),
+                )?;
+                if let Some(next) = next {
+                    if obj.effective_to.is_none_or(|to| to >= next.effective_from()) {
+                        return Err(PayrollError::SalaryOverlap {
+                            employee_id: record.employee_id(),
+                            effective_from: next.effective_from(),
+                        });
+                    }
+                }
+            }
+            Ok(CompensationMapper::set_effective_to(conn, pid, obj.effective_to)?)
+        })
+    }
+}
+
+pub(crate) fn find_employee(
+    conn: &mut PgConnection,
+    employee_id: i32,
+) -> Result<(String, Option<i32>), PayrollError> {
+    match CompensationMapper::get_employee(conn, employee_id) {
+        Err(diesel::result::Error::NotFound) => Err(PayrollError::EmployeeNotFound(employee_id)),
+        result => Ok(result?),
+    }
+}
+
+pub(crate) fn check_period(start: NaiveDate, end: Option<NaiveDate>) -> Result<(), PayrollError> {
+    match end {
+        Some(end) if end < start => Err(PayrollError::InvalidPeriod {
+            start,
+            end,
+        }),
+        _ => Ok(()),
+    }
+}
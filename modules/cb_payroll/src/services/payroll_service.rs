@@ -0,0 +1,231 @@
+use chrono::NaiveDate;
+use crab_rocket_schema::establish_pg_connection;
+use diesel::{Connection, PgConnection};
+
+use crate::error::PayrollError;
+use crate::mappers::compensation_mapper::CompensationMapper;
+use crate::mappers::payroll_mapper::PayrollMapper;
+use crate::models::compensation::{Compensation, ALLOWANCE, DEDUCTION, SALARY};
+use crate::models::payroll_run::{NewPayrollRun, PayrollRun, PayrollRunDetail, PostPayrollRun};
+use crate::models::payslip::{NewPayslip, NewPayslipLine, Payslip, PayslipDetail};
+use crate::models::payslip_export::PayslipExport;
+use crate::services::compensation_service::{check_period, find_employee};
+
+pub struct PayrollService {}
+
+impl PayrollService {
+    /// Computes and stores a payslip for every employee with compensation in the period.
+    /// Periods of runs do not overlap, so nobody is paid twice for a day.
+    pub fn run(obj: &PostPayrollRun, created_by: Option<i32>) -> Result<PayrollRun, PayrollError> {
+        let (start, end) = (obj.period_start, obj.period_end);
+        check_period(start, Some(end))?;
+        let mut conn = establish_pg_connection()?;
+        conn.transaction(|conn| {
+            if let Some(run) = PayrollMapper::overlapping_run(conn, start, end)? {
+                return Err(PayrollError::RunOverlap {
+                    payroll_run_id: run.payroll_run_id(),
+                    period_start: run.period_start(),
+                    period_end: run.period_end(),
+                });
+            }
+            let run = PayrollMapper::add_run(
+                conn,
+                &NewPayrollRun {
+                    period_start: start,
+                    period_end: end,
+                    created_by,
+                },
+            )?;
+            let records = CompensationMapper::overlapping(conn, start, end)?;
+            let (mut employees, mut total_gross, mut total_net) = (0, 0, 0);
+            for records in records.chunk_by(|a, b| a.employee_id() == b.employee_id()) {
+                let payslip = add_payslip(conn, run.payroll_run_id(), records, start, end)?;
+                employees += 1;
+                total_gross += payslip.gross();
+                total_net += payslip.net();
+            }
+            Ok(PayrollMapper::set_totals(
+                conn,
+                run.payroll_run_id(),
+                employees,
+                total_gross,
+                total_net,
+            )?)
+        })
+    }
+
+    /// The latest `limit` runs.
+    pub fn get_runs(limit: i64) -> Result<Vec<PayrollRun>, PayrollError> {
+        let mut conn = establish_pg_connection()?;
+        Ok(PayrollMapper::get_runs(&mut conn, limit)?)
+    }
+
+    pub fn get_run(pid: i32) -> Result<PayrollRunDetail, PayrollError> {
+        let mut conn = establish_pg_connection()?;
+        let run = find_run(&mut conn, pid)?;
+        let payslips = PayrollMapper::get_payslips_by_run(&mut conn, pid)?;
+        Ok(PayrollRunDetail {
+            run,
+            payslips,
+        })
+    }
+
+    pub fn export(pid: i32) -> Result<PayslipExport, PayrollError> {
+        let mut conn = establish_pg_connection()?;
+        find_run(&mut conn, pid)?;
+        let payslips = PayrollMapper::get_payslips_by_run(&mut conn, pid)?;
+        Ok(PayslipExport::new(pid, &payslips))
+    }
+
+    pub fn get_payslip(pid: i32) -> Result<PayslipDetail, PayrollError> {
+        let mut conn = establish_pg_connection()?;
+        let payslip = match PayrollMapper::get_payslip(&mut conn, pid) {
+            Err(diesel::result::Error::NotFound) => return Err(PayrollError::PayslipNotFound(pid)),
+            result => result?,
+        };
+        let lines = PayrollMapper::get_lines(&mut conn, pid)?;
+        Ok(PayslipDetail {
+            payslip,
+            lines,
+        })
+    }
+
+    /// The employee's payslips, the latest first.
+    pub fn get_payslips_by_employee(employee_id: i32) -> Result<Vec<Payslip>, PayrollError> {
+        let mut conn = establish_pg_connection()?;
+        find_employee(&mut conn, employee_id)?;
+        Ok(PayrollMapper::get_payslips_by_employee(&mut conn, employee_id)?)
+    }
+}
+
+fn find_run(conn: &mut PgConnection, pid: i32) -> Result<PayrollRun, PayrollError> {
+    match PayrollMapper::get_run(conn, pid) {
+        Err(diesel::result::Error::NotFound) => Err(PayrollError::RunNotFound(pid)),
+        result => Ok(result?),
+    }
+}
+
+/// Stores the payslip of the employee all `records` belong to.
+fn add_payslip(
+    conn: &mut PgConnection,
+    payroll_run_id: i32,
+    records: &[Compensation],
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<Payslip, PayrollError> {
+    let employee_id = records[0].employee_id();
+    let (employee_name, department_id) = find_employee(conn, employee_id)?;
+    let mut lines = payslip_lines(records, start, end);
+    let sum = |component: &str| -> i64 {
+        lines.iter().filter(|line| line.component == component).map(|line| line.amount).sum()
+    };
+    let (salary, allowances, deductions) = (sum(SALARY), sum(ALLOWANCE), sum(DEDUCTION));
+    let payslip = PayrollMapper::add_payslip(
+        conn,
+        &NewPayslip {
+            payroll_run_id,
+            employee_id: Some(employee_id),
+            employee_name,
+            department_id,
+            period_start: start,
+            period_end: end,
+            salary,
+            allowances,
+            deductions,
+            gross: salary + allowances,
+            net: salary + allowances - deductions,
+        },
+    )?;
+    for line in &mut lines {
+        line.payslip_id = payslip.payslip_id();
+    }
+    PayrollMapper::add_lines(conn, &lines)?;
+    Ok(payslip)
+}
+
+/// One line per record, its `amount` paid by the day for the days of `start..=end` it covers.
+/// The lines are not attached to a payslip yet.
+pub fn payslip_lines(
+    records: &[Compensation],
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Vec<NewPayslipLine> {
+    let period_days = (end - start).num_days() + 1;
+    records
+        .iter()
+        .filter_map(|record| {
+            let days = record.days_within(start, end);
+            (days > 0).then(|| NewPayslipLine {
+                payslip_id: 0,
+                compensation_id: Some(record.compensation_id()),
+                component: record.component().to_string(),
+                name: record.name().to_string(),
+                amount: prorate(record.amount(), days, period_days),
+            })
+        })
+        .collect()
+}
+
+/// `amount * days / period_days`, rounded half up.
+fn prorate(amount: i32, days: i64, period_days: i64) -> i64 {
+    (2 * amount as i64 * days + period_days) / (2 * period_days)
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDate;
+    use crab_rocket_test_support::{fixtures, test_conn};
+    use diesel::prelude::*;
+
+    use super::{payslip_lines, prorate};
+    use crate::mappers::compensation_mapper::CompensationMapper;
+    use crate::models::compensation::{NewCompensation, PostCompensation};
+
+    fn day(d: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(d, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_prorate_rounds_half_up() {
+        assert_eq!(prorate(3000, 30, 30), 3000);
+        assert_eq!(prorate(3000, 15, 30), 1500);
+        assert_eq!(prorate(1000, 1, 3), 333);
+        assert_eq!(prorate(1000, 2, 3), 667);
+    }
+
+    #[test]
+    fn test_payslip_lines_follow_effective_dates() {
+        let mut conn = test_conn();
+        let employee = fixtures::employee(&mut conn, "lines_employee", None, None);
+        let add = |conn: &mut PgConnection, component: &str, amount, from, to: Option<&str>| {
+            let obj = PostCompensation {
+                component: component.to_string(),
+                name: None,
+                amount,
+                effective_from: day(from),
+                effective_to: to.map(day),
+            };
+            CompensationMapper::add_single(conn, &NewCompensation::new(employee, &obj)).unwrap()
+        };
+        // A raise on the 16th of a 30 day period.
+        add(&mut conn, "salary", 3000, "2026-01-01", Some("2026-09-15"));
+        add(&mut conn, "salary", 6000, "2026-09-16", None);
+        add(&mut conn, "allowance", 300, "2026-09-01", None);
+        add(&mut conn, "deduction", 500, "2026-01-01", None);
+        // Ended before the period.
+        add(&mut conn, "allowance", 999, "2026-01-01", Some("2026-08-31"));
+
+        let (start, end) = (day("2026-09-01"), day("2026-09-30"));
+        let records = CompensationMapper::overlapping(&mut conn, start, end)
+            .unwrap()
+            .into_iter()
+            .filter(|record| record.employee_id() == employee)
+            .collect::<Vec<_>>();
+        let lines: Vec<_> = payslip_lines(&records, start, end)
+            .into_iter()
+            .map(|line| (line.component, line.amount))
+            .collect();
+        let expected = [("salary", 1500), ("deduction", 500), ("allowance", 300), ("salary", 3000)];
+        assert_eq!(lines, expected.map(|(component, amount)| (component.to_string(), amount)));
+    }
+}
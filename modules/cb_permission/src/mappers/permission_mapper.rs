@@ -39,8 +39,8 @@ impl MapperCRUD for PermissionMapper {
         //
         // limit 始终为 per_page
         // 计算分页相关
-        let page = (param.pagination.offset.unwrap() / param.pagination.limit.unwrap()) + 1;
-        let per_page = param.pagination.limit.unwrap();
+        let page = (param.pagination.offset_or_default() / param.pagination.limit_or_default()) + 1;
+        let per_page = param.pagination.limit_or_default();
         // 获取总记录数
         let total_count = dsl::permission_table.count().get_result::<i64>(conn)? as i32;
         // 计算总页数
@@ -60,6 +60,7 @@ impl MapperCRUD for PermissionMapper {
         // 分页查询
         let data = dsl::permission_table
             .order(dsl::updated_at.desc())
+            .then_order_by(dsl::permission_id.desc())
             .limit(per_page as i64)
             .offset(((page - 1) * per_page) as i64)
             .load::<Permission>(conn)?;
@@ -129,8 +130,8 @@ impl MapperCRUD for PermissionMapper {
         //
         // limit 始终为 per_page
         // 计算分页相关
-        let page = (param.pagination.offset.unwrap() / param.pagination.limit.unwrap()) + 1;
-        let per_page = param.pagination.limit.unwrap();
+        let page = (param.pagination.offset_or_default() / param.pagination.limit_or_default()) + 1;
+        let per_page = param.pagination.limit_or_default();
         // 获取总记录数
         let total_count = dsl::permission_table.count().get_result::<i64>(conn)? as i32;
         // 计算总页数
@@ -152,6 +153,7 @@ impl MapperCRUD for PermissionMapper {
         // 分页查询
         query = query
             .order(dsl::created_at.desc())
+            .then_order_by(dsl::permission_id.desc())
             .limit(per_page as i64)
             .offset(((page - 1) * per_page) as i64);
 
@@ -201,3 +203,16 @@ impl MapperCRUD for PermissionMapper {
         Ok(body)
     }
 }
+
+impl PermissionMapper {
+    pub fn get_by_resource_action(
+        conn: &mut PgConnection,
+        resource: &str,
+        action: &str,
+    ) -> Result<Permission, Error> {
+        dsl::permission_table
+            .filter(dsl::resource.eq(resource))
+            .filter(dsl::action.eq(action))
+            .first::<Permission>(conn)
+    }
+}
@@ -59,6 +59,7 @@ pub struct PatchPermission {
 }
 
 impl Permission {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         permission_id: i32,
         permission_name: String,
@@ -187,6 +188,7 @@ impl Permission {
 // }
 // ```
 impl PostPermission {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         permission_name: String,
         permission_description: Option<String>,
@@ -228,6 +230,7 @@ impl PostPermission {
     }
 }
 impl PatchPermission {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         permission_name: String,
         permission_description: Option<String>,
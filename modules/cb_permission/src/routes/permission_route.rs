@@ -1,6 +1,8 @@
 use crate::controllers::permission_controller::PermissionController;
-use crate::models::permission::{PostPermission, PatchPermission};
+use crate::models::permission::{PatchPermission, PostPermission};
 use crate::models::permission_filter::PermissionFilter;
+use crab_rocket_auth::guards::authorized::Authorized;
+use crab_rocket_auth::permission;
 use obj_traits::controller::controller_crud::ControllerCRUD;
 use obj_traits::request::pagination_request_param::{PaginationParam, PaginationParamTrait};
 use obj_traits::request::request_param::RequestParam;
@@ -8,6 +10,10 @@ use rocket::http::Status;
 use rocket::serde::json::Json;
 use rocket::{delete, get, options, patch, post};
 
+permission!(pub PermissionCreate, "permission", "create");
+permission!(pub PermissionUpdate, "permission", "update");
+permission!(pub PermissionDelete, "permission", "delete");
+
 /// # Note
 /// 若业务逻辑复杂则启用controller层
 /// 目前只是把业务逻辑简单包含在路由中
@@ -17,13 +23,7 @@ use rocket::{delete, get, options, patch, post};
 /// 例如PUT/items/1的意思是替換/items/1，如果已經存在就替換，沒有就新增。
 /// PUT必須包含items/1的所有屬性資料
 #[get("/permission?<limit>&<offset>")]
-pub fn get_permissions(mut limit: Option<i32>, mut offset: Option<i32>) -> Json<serde_json::Value> {
-    if limit.is_none() {
-        limit = Some(10);
-    };
-    if offset.is_none() {
-        offset = Some(0);
-    };
+pub fn get_permissions(limit: Option<i32>, offset: Option<i32>) -> Json<serde_json::Value> {
     let params = RequestParam::new(PaginationParam::new(limit, offset), None);
     println!("{:?}", params);
     crab_rocket_schema::update_reload::update_reload_count();
@@ -53,7 +53,10 @@ pub fn get_permission_by_id(id: i32) -> Json<serde_json::Value> {
 }
 
 #[post("/permission", data = "<permission>")]
-pub fn insert_single_permission(permission: Json<PostPermission>) -> Json<serde_json::Value> {
+pub fn insert_single_permission(
+    _auth: Authorized<PermissionCreate>,
+    permission: Json<PostPermission>,
+) -> Json<serde_json::Value> {
     let mut obj: PostPermission = permission.into_inner();
 
     let resp = PermissionController::add_single(&mut obj).unwrap();
@@ -62,7 +65,10 @@ pub fn insert_single_permission(permission: Json<PostPermission>) -> Json<serde_
 }
 
 #[delete("/permission/<id>")]
-pub fn delete_permission_by_id(id: i32) -> Json<serde_json::Value> {
+pub fn delete_permission_by_id(
+    _auth: Authorized<PermissionDelete>,
+    id: i32,
+) -> Json<serde_json::Value> {
     let resp = PermissionController::delete_by_id(id).unwrap();
     let json_value = serde_json::to_value(&resp).unwrap();
     Json(serde_json::from_value(json_value).unwrap())
@@ -70,6 +76,7 @@ pub fn delete_permission_by_id(id: i32) -> Json<serde_json::Value> {
 
 #[patch("/permission/<id>", data = "<permission>")]
 pub fn update_permission_by_id(
+    _auth: Authorized<PermissionUpdate>,
     id: i32,
     permission: Json<PatchPermission>,
 ) -> Json<serde_json::Value> {
@@ -1,5 +1,8 @@
 use std::error::Error;
 
+use crab_rocket_auth::error::AuthError;
+use crab_rocket_auth::guards::auth_user::AuthUser;
+use crab_rocket_auth::services::ownership_service::OwnershipError;
 use obj_traits::{
     controller::controller_crud::{
         controller_add_single, controller_delete_by_id, controller_filter, controller_get_all,
@@ -11,7 +14,7 @@ use obj_traits::{
 
 use crate::{
     models::{
-        post::{PostPost, PatchPost, Post},
+        post::{PatchPost, Post, PostPost},
         post_filter::PostFilter,
     },
     services::post_service::PostService,
@@ -51,3 +54,49 @@ impl ControllerCRUD for PostController {
         controller_filter::<Self::Item, PostService, PostFilter>(param)
     }
 }
+
+fn from_ownership_error<T>(e: OwnershipError) -> (i32, String, Option<T>) {
+    match e {
+        OwnershipError::Auth(AuthError::Internal(_)) => {
+            println!("{e:?}");
+            (500, String::from("Internal Server Error"), None)
+        }
+        e => (e.status(), e.to_string(), None),
+    }
+}
+
+impl PostController {
+    pub fn add_owned(auth: &AuthUser, obj: &PostPost) -> (i32, String, Option<Post>) {
+        match PostService::add_owned(auth, obj) {
+            Ok(post) => (200, String::from("Success"), Some(post)),
+            Err(e) => from_ownership_error(e),
+        }
+    }
+
+    pub fn update_owned(auth: &AuthUser, pid: i32, obj: &PatchPost) -> (i32, String, Option<Post>) {
+        match PostService::update_owned(auth, pid, obj) {
+            Ok(post) => (200, String::from("Success"), Some(post)),
+            Err(e) => from_ownership_error(e),
+        }
+    }
+
+    pub fn delete_owned(auth: &AuthUser, pid: i32) -> (i32, String, Option<Post>) {
+        match PostService::delete_owned(auth, pid) {
+            Ok(post) => (200, String::from("Success"), Some(post)),
+            Err(e) => from_ownership_error(e),
+        }
+    }
+
+    pub fn filter_mine(
+        auth: &AuthUser,
+        param: RequestParam<PaginationParam, PostFilter>,
+    ) -> Result<ApiResponse<Data<Vec<Post>>>, Box<dyn Error>> {
+        match PostService::filter_mine(auth, param) {
+            Ok(posts) => Ok(ApiResponse::success(posts)),
+            Err(e) => {
+                println!("{e:?}");
+                Ok(ApiResponse::error(e))
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::models::post::{PostPost, PatchPost, Post};
+use crate::models::post::{PatchPost, Post, PostPost};
 use crate::models::post_filter::PostFilter;
 use crab_rocket_schema::schema::post_table::dsl;
 use crab_rocket_utils::time::get_e8_time;
@@ -36,8 +36,8 @@ impl MapperCRUD for PostMapper {
         //
         // limit 始终为 per_page
         // 计算分页相关
-        let page = (param.pagination.offset.unwrap() / param.pagination.limit.unwrap()) + 1;
-        let per_page = param.pagination.limit.unwrap();
+        let page = (param.pagination.offset_or_default() / param.pagination.limit_or_default()) + 1;
+        let per_page = param.pagination.limit_or_default();
         // 获取总记录数
         let total_count = dsl::post_table.count().get_result::<i64>(conn)? as i32;
         // 计算总页数
@@ -57,6 +57,7 @@ impl MapperCRUD for PostMapper {
         // 分页查询
         let data = dsl::post_table
             .order(dsl::updated_at.desc())
+            .then_order_by(dsl::post_id.desc())
             .limit(per_page as i64)
             .offset(((page - 1) * per_page) as i64)
             .load::<Post>(conn)?;
@@ -120,8 +121,8 @@ impl MapperCRUD for PostMapper {
         //
         // limit 始终为 per_page
         // 计算分页相关
-        let page = (param.pagination.offset.unwrap() / param.pagination.limit.unwrap()) + 1;
-        let per_page = param.pagination.limit.unwrap();
+        let page = (param.pagination.offset_or_default() / param.pagination.limit_or_default()) + 1;
+        let per_page = param.pagination.limit_or_default();
         // 获取总记录数
         let total_count = dsl::post_table.count().get_result::<i64>(conn)? as i32;
         // 计算总页数
@@ -186,19 +187,16 @@ mod tests {
     fn test_insert_post() {
         use super::*;
         use crab_rocket_schema::establish_pg_connection; // 建立数据库连接
-        match establish_pg_connection() {
-            Ok(mut conn) => {
-                // 创建一个新的 NewPost 实例
-                let new_post = PostPost::demo();
+        if let Ok(mut conn) = establish_pg_connection() {
+            // 创建一个新的 NewPost 实例
+            let new_post = PostPost::demo();
 
-                // 调用 insert_post 函数
-                let _ = PostMapper::add_single(&mut conn, &new_post);
-                // 删除插入的数据，以便下一次测试
-                // diesel::delete(post_table::table.filter(post_table::title.eq("Test
-                // Title"))) .execute(&mut conn)
-                // .expect("Failed to delete test data");
-            }
-            Err(_) => (),
+            // 调用 insert_post 函数
+            let _ = PostMapper::add_single(&mut conn, &new_post);
+            // 删除插入的数据，以便下一次测试
+            // diesel::delete(post_table::table.filter(post_table::title.eq("Test
+            // Title"))) .execute(&mut conn)
+            // .expect("Failed to delete test data");
         }
     }
 
@@ -207,14 +205,10 @@ mod tests {
         use super::*;
         use crab_rocket_schema::establish_pg_connection; // 建立数据库连接
         let param = RequestParam::new(PaginationParam::demo(), None);
-        match establish_pg_connection() {
-            Ok(mut conn) => match PostMapper::get_all(&mut conn, &param) {
-                Ok(all_post_table) => {
-                    println!("{all_post_table}")
-                }
-                Err(_) => (),
-            },
-            Err(_) => (),
+        if let Ok(mut conn) = establish_pg_connection() {
+            if let Ok(all_post_table) = PostMapper::get_all(&mut conn, &param) {
+                println!("{all_post_table}")
+            }
         }
     }
 
@@ -222,14 +216,10 @@ mod tests {
     fn test_fetch_post_by_id() {
         use super::*;
         use crab_rocket_schema::establish_pg_connection; // 建立数据库连接
-        match establish_pg_connection() {
-            Ok(mut conn) => match PostMapper::get_by_id(&mut conn, 1) {
-                Ok(post) => {
-                    println!("{post:?}")
-                }
-                Err(_) => (),
-            },
-            Err(_) => (),
+        if let Ok(mut conn) = establish_pg_connection() {
+            if let Ok(post) = PostMapper::get_by_id(&mut conn, 1) {
+                println!("{post:?}")
+            }
         }
     }
 
@@ -238,14 +228,10 @@ mod tests {
         use super::*;
         use crab_rocket_schema::establish_pg_connection; // 建立数据库连接
         let new_post = PostPost::demo();
-        match establish_pg_connection() {
-            Ok(mut conn) => match PostMapper::update_by_id(&mut conn, 4, &new_post.into()) {
-                Ok(updated_post) => {
-                    println!("{updated_post:?}")
-                }
-                Err(_) => (),
-            },
-            Err(_) => (),
+        if let Ok(mut conn) = establish_pg_connection() {
+            if let Ok(updated_post) = PostMapper::update_by_id(&mut conn, 4, &new_post.into()) {
+                println!("{updated_post:?}")
+            }
         }
     }
 
@@ -253,14 +239,10 @@ mod tests {
     fn test_delete_post_by_id() {
         use super::*;
         use crab_rocket_schema::establish_pg_connection; // 建立数据库连接
-        match establish_pg_connection() {
-            Ok(mut conn) => match PostMapper::delete_by_id(&mut conn, 1) {
-                Ok(deleted_post) => {
-                    println!("{deleted_post:?}")
-                }
-                Err(_) => (),
-            },
-            Err(_) => (),
+        if let Ok(mut conn) = establish_pg_connection() {
+            if let Ok(deleted_post) = PostMapper::delete_by_id(&mut conn, 1) {
+                println!("{deleted_post:?}")
+            }
         }
     }
 }
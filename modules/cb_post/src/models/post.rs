@@ -20,6 +20,7 @@ pub struct Post {
 }
 
 impl Post {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         post_id: i32,
         title: Option<String>,
@@ -291,15 +292,15 @@ impl PatchPost {
     }
 }
 
-impl Into<PatchPost> for PostPost {
-    fn into(self) -> PatchPost {
+impl From<PostPost> for PatchPost {
+    fn from(val: PostPost) -> Self {
         PatchPost {
-            title: self.title,
-            body: self.body,
-            user_id: self.user_id,
-            status: self.status,
-            created_at: self.created_at,
-            updated_at: self.updated_at,
+            title: val.title,
+            body: val.body,
+            user_id: val.user_id,
+            status: val.status,
+            created_at: val.created_at,
+            updated_at: val.updated_at,
         }
     }
 }
@@ -1,14 +1,28 @@
 use crate::controllers::post_controller::PostController;
-use crate::models::post::{PostPost, PatchPost};
+use crate::models::post::{PatchPost, PostPost};
 use crate::models::post_filter::PostFilter;
+use crab_rocket_auth::guards::auth_user::AuthUser;
 use obj_traits::controller::controller_crud::ControllerCRUD;
 use obj_traits::request::pagination_request_param::{PaginationParam, PaginationParamTrait};
 use obj_traits::request::request_param::RequestParam;
+use obj_traits::response::api_response::ApiResponse;
 use rocket::http::Status;
+use rocket::response::status;
 use rocket::serde::json::Json;
 use rocket::{delete, get, options, patch, post};
 use serde_json::json;
 
+/// Answers with `code` as the HTTP status, in the shape of the other post routes.
+fn to_response<T: rocket::serde::Serialize + Default>(
+    code: i32,
+    message: String,
+    data: T,
+) -> status::Custom<Json<serde_json::Value>> {
+    let response = serde_json::to_value(ApiResponse::new(code, message, data)).unwrap();
+    let status = Status::from_code(code as u16).unwrap_or(Status::InternalServerError);
+    status::Custom(status, Json(response))
+}
+
 /// # Note
 /// 若业务逻辑复杂则启用controller层
 /// 目前只是把业务逻辑简单包含在路由中
@@ -18,13 +32,7 @@ use serde_json::json;
 /// 例如PUT/items/1的意思是替換/items/1，如果已經存在就替換，沒有就新增。
 /// PUT必須包含items/1的所有屬性資料
 #[get("/post?<limit>&<offset>")]
-pub fn get_posts(mut limit: Option<i32>, mut offset: Option<i32>) -> Json<serde_json::Value> {
-    if limit.is_none() {
-        limit = Some(10);
-    };
-    if offset.is_none() {
-        offset = Some(0);
-    };
+pub fn get_posts(limit: Option<i32>, offset: Option<i32>) -> Json<serde_json::Value> {
     let params = RequestParam::new(PaginationParam::new(limit, offset), None);
     println!("{:?}", params);
     crab_rocket_schema::update_reload::update_reload_count();
@@ -45,6 +53,31 @@ pub fn filter_posts(
     Json(serde_json::from_value(json_value).unwrap())
 }
 
+/// The caller's posts.
+#[get("/post/mine?<limit>&<offset>")]
+pub fn get_my_posts(
+    auth: AuthUser,
+    limit: Option<i32>,
+    offset: Option<i32>,
+) -> Json<serde_json::Value> {
+    let params = RequestParam::new(PaginationParam::new(limit, offset), None);
+    let resp = PostController::filter_mine(&auth, params).unwrap();
+    let json_value = serde_json::to_value(&resp).unwrap();
+    Json(serde_json::from_value(json_value).unwrap())
+}
+
+/// `POST /post/filter` over the caller's posts, a `user_id` in the filter is ignored.
+#[post("/post/mine/filter", data = "<param>")]
+pub fn filter_my_posts(
+    auth: AuthUser,
+    param: Option<Json<RequestParam<PaginationParam, PostFilter>>>,
+) -> Json<serde_json::Value> {
+    let param = param.unwrap_or(Json(RequestParam::new(PaginationParam::default(), None)));
+    let resp = PostController::filter_mine(&auth, param.into_inner()).unwrap();
+    let json_value = serde_json::to_value(&resp).unwrap();
+    Json(serde_json::from_value(json_value).unwrap())
+}
+
 #[get("/post/<id>")]
 pub fn get_post_by_id(id: i32) -> Json<serde_json::Value> {
     crab_rocket_schema::update_reload::update_reload_count();
@@ -53,27 +86,32 @@ pub fn get_post_by_id(id: i32) -> Json<serde_json::Value> {
     Json(serde_json::from_value(json_value).unwrap())
 }
 
+/// The post is owned by the caller unless a moderator names another `user_id`.
 #[post("/post", data = "<post>")]
-pub fn insert_single_post(post: Json<PostPost>) -> Json<serde_json::Value> {
-    let mut obj: PostPost = post.into_inner();
-
-    let resp = PostController::add_single(&mut obj).unwrap();
-    let json_value = serde_json::to_value(&resp).unwrap();
-    Json(serde_json::from_value(json_value).unwrap())
+pub fn insert_single_post(
+    auth: AuthUser,
+    post: Json<PostPost>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = PostController::add_owned(&auth, &post);
+    to_response(code, message, data)
 }
 
+/// Only the owner, or a role granted `post:moderate`.
 #[delete("/post/<id>")]
-pub fn delete_post_by_id(id: i32) -> Json<serde_json::Value> {
-    let resp = PostController::delete_by_id(id).unwrap();
-    let json_value = serde_json::to_value(&resp).unwrap();
-    Json(serde_json::from_value(json_value).unwrap())
+pub fn delete_post_by_id(auth: AuthUser, id: i32) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = PostController::delete_owned(&auth, id);
+    to_response(code, message, data)
 }
 
+/// Only the owner, or a role granted `post:moderate`.
 #[patch("/post/<id>", data = "<post>")]
-pub fn update_post_by_id(id: i32, post: Json<PatchPost>) -> Json<serde_json::Value> {
-    let resp = PostController::update_by_id(id, &post).unwrap();
-    let json_value = serde_json::to_value(&resp).unwrap();
-    Json(serde_json::from_value(json_value).unwrap())
+pub fn update_post_by_id(
+    auth: AuthUser,
+    id: i32,
+    post: Json<PatchPost>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = PostController::update_owned(&auth, id, &post);
+    to_response(code, message, data)
 }
 
 #[get("/")]
@@ -1,5 +1,7 @@
 use std::error::Error;
 
+use crab_rocket_auth::guards::auth_user::AuthUser;
+use crab_rocket_auth::services::ownership_service::{OwnershipError, OwnershipService};
 use obj_traits::{
     request::{pagination_request_param::PaginationParam, request_param::RequestParam},
     response::data::Data,
@@ -12,7 +14,7 @@ use obj_traits::{
 use crate::{
     mappers::post_mapper::PostMapper,
     models::{
-        post::{PostPost, PatchPost, Post},
+        post::{PatchPost, Post, PostPost},
         post_filter::PostFilter,
     },
 };
@@ -50,6 +52,52 @@ impl ServiceCRUD for PostService {
     }
 }
 
+const RESOURCE: &str = "post";
+
+impl PostService {
+    /// Creates a post owned by the caller, moderators may name another `user_id`.
+    pub fn add_owned(auth: &AuthUser, obj: &PostPost) -> Result<Post, OwnershipError> {
+        let mut obj = obj.clone();
+        obj.set_user_id(Some(OwnershipService::assign_owner(auth, RESOURCE, obj.user_id())?));
+        Ok(Self::add_single(&obj)?)
+    }
+
+    /// Replaces post `pid` when the caller owns it or moderates posts. Without `user_id` the
+    /// post keeps its owner, handing it to someone else takes a moderator.
+    pub fn update_owned(
+        auth: &AuthUser,
+        pid: i32,
+        obj: &PatchPost,
+    ) -> Result<Post, OwnershipError> {
+        let post = Self::get_by_id(pid).map_err(|e| OwnershipError::lookup(RESOURCE, pid, e))?;
+        OwnershipService::authorize(auth, RESOURCE, "update", pid, post.user_id())?;
+        let mut obj = obj.clone();
+        match obj.user_id() {
+            Some(user_id) if Some(user_id) != post.user_id() => {
+                OwnershipService::assign_owner(auth, RESOURCE, Some(user_id))?;
+            }
+            _ => obj.set_user_id(post.user_id()),
+        }
+        Ok(Self::update_by_id(pid, &obj)?)
+    }
+
+    pub fn delete_owned(auth: &AuthUser, pid: i32) -> Result<Post, OwnershipError> {
+        let post = Self::get_by_id(pid).map_err(|e| OwnershipError::lookup(RESOURCE, pid, e))?;
+        OwnershipService::authorize(auth, RESOURCE, "delete", pid, post.user_id())?;
+        Ok(Self::delete_by_id(pid)?)
+    }
+
+    /// Like [`ServiceCRUD::filter`], narrowed to the caller's posts.
+    pub fn filter_mine(
+        auth: &AuthUser,
+        param: RequestParam<PaginationParam, PostFilter>,
+    ) -> Result<Data<Vec<Post>>, Box<dyn Error>> {
+        let mut filter = param.filter.unwrap_or_default();
+        filter.user_id = Some(auth.user_id());
+        Self::filter(&RequestParam::new(param.pagination, Some(filter)))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use obj_traits::{
@@ -72,31 +120,22 @@ mod test {
     #[test]
     fn test_get_all_posts() {
         let param = RequestParam::new(PaginationParam::demo(), None);
-        match PostService::get_all(&param) {
-            Ok(all_posts) => {
-                println!("{all_posts}");
-            }
-            Err(_) => (),
+        if let Ok(all_posts) = PostService::get_all(&param) {
+            println!("{all_posts}");
         }
     }
 
     #[test]
     fn test_get_post_by_id() {
-        match PostService::get_by_id(1) {
-            Ok(post) => {
-                println!("{post:?}");
-            }
-            Err(_) => (),
+        if let Ok(post) = PostService::get_by_id(1) {
+            println!("{post:?}");
         }
     }
 
     #[test]
     fn test_delete_post_by_id() {
-        match PostService::delete_by_id(4) {
-            Ok(deleted_post) => {
-                println!("{deleted_post:?}");
-            }
-            Err(_) => (),
+        if let Ok(deleted_post) = PostService::delete_by_id(4) {
+            println!("{deleted_post:?}");
         }
     }
 }
@@ -1,6 +1,9 @@
 use crate::models::product::{PatchProduct, PostProduct, Product};
 use crate::models::product_filter::ProductFilter;
 use crate::services::product_service::ProductService;
+use crab_rocket_auth::error::AuthError;
+use crab_rocket_auth::guards::auth_user::AuthUser;
+use crab_rocket_auth::services::ownership_service::OwnershipError;
 use obj_traits::controller::controller_crud::{
     controller_add_single, controller_delete_by_id, controller_filter, controller_get_all,
     controller_get_by_id, controller_update_by_id, ControllerCRUD,
@@ -45,3 +48,53 @@ impl ControllerCRUD for ProductController {
         controller_filter::<Product, ProductService, ProductFilter>(param)
     }
 }
+
+fn from_ownership_error<T>(e: OwnershipError) -> (i32, String, Option<T>) {
+    match e {
+        OwnershipError::Auth(AuthError::Internal(_)) => {
+            println!("{e:?}");
+            (500, String::from("Internal Server Error"), None)
+        }
+        e => (e.status(), e.to_string(), None),
+    }
+}
+
+impl ProductController {
+    pub fn add_owned(auth: &AuthUser, obj: &PostProduct) -> (i32, String, Option<Product>) {
+        match ProductService::add_owned(auth, obj) {
+            Ok(product) => (200, String::from("Success"), Some(product)),
+            Err(e) => from_ownership_error(e),
+        }
+    }
+
+    pub fn update_owned(
+        auth: &AuthUser,
+        pid: i32,
+        obj: &PatchProduct,
+    ) -> (i32, String, Option<Product>) {
+        match ProductService::update_owned(auth, pid, obj) {
+            Ok(product) => (200, String::from("Success"), Some(product)),
+            Err(e) => from_ownership_error(e),
+        }
+    }
+
+    pub fn delete_owned(auth: &AuthUser, pid: i32) -> (i32, String, Option<Product>) {
+        match ProductService::delete_owned(auth, pid) {
+            Ok(product) => (200, String::from("Success"), Some(product)),
+            Err(e) => from_ownership_error(e),
+        }
+    }
+
+    pub fn filter_mine(
+        auth: &AuthUser,
+        param: RequestParam<PaginationParam, ProductFilter>,
+    ) -> Result<ApiResponse<Data<Vec<Product>>>, Box<dyn Error>> {
+        match ProductService::filter_mine(auth, param) {
+            Ok(products) => Ok(ApiResponse::success(products)),
+            Err(e) => {
+                println!("{e:?}");
+                Ok(ApiResponse::error(e))
+            }
+        }
+    }
+}
@@ -36,6 +36,7 @@ pub struct Product {
 #[diesel(table_name = crab_rocket_schema::schema::product_table)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
 pub struct PostProduct {
+    pub user_id: Option<i32>,
     pub name: String,
     pub description: Option<String>,
     pub sku: String,
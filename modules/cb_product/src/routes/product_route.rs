@@ -1,20 +1,28 @@
+use crab_rocket_auth::guards::auth_user::AuthUser;
 use obj_traits::controller::controller_crud::ControllerCRUD;
 use obj_traits::request::pagination_request_param::{PaginationParam, PaginationParamTrait};
 use obj_traits::request::request_param::RequestParam;
+use obj_traits::response::api_response::ApiResponse;
+use rocket::response::status;
 use rocket::{delete, get, http::Status, options, patch, post, serde::json::Json};
 
 use crate::controllers::product_controller::ProductController;
 use crate::models::product::{PatchProduct, PostProduct};
 use crate::models::product_filter::ProductFilter;
 
+/// Answers with `code` as the HTTP status, in the shape of the other product routes.
+fn to_response<T: rocket::serde::Serialize + Default>(
+    code: i32,
+    message: String,
+    data: T,
+) -> status::Custom<Json<serde_json::Value>> {
+    let response = serde_json::to_value(ApiResponse::new(code, message, data)).unwrap();
+    let status = Status::from_code(code as u16).unwrap_or(Status::InternalServerError);
+    status::Custom(status, Json(response))
+}
+
 #[get("/product?<limit>&<offset>")]
-pub fn get_products(mut limit: Option<i32>, mut offset: Option<i32>) -> Json<serde_json::Value> {
-    if limit.is_none() {
-        limit = Some(10);
-    };
-    if offset.is_none() {
-        offset = Some(0);
-    };
+pub fn get_products(limit: Option<i32>, offset: Option<i32>) -> Json<serde_json::Value> {
     let params = RequestParam::new(PaginationParam::new(limit, offset), None);
     println!("{:?}", params);
     crab_rocket_schema::update_reload::update_reload_count();
@@ -34,6 +42,31 @@ pub fn filter_products(
     Json(serde_json::from_value(json_value).unwrap())
 }
 
+/// The caller's products.
+#[get("/product/mine?<limit>&<offset>")]
+pub fn get_my_products(
+    auth: AuthUser,
+    limit: Option<i32>,
+    offset: Option<i32>,
+) -> Json<serde_json::Value> {
+    let params = RequestParam::new(PaginationParam::new(limit, offset), None);
+    let resp = ProductController::filter_mine(&auth, params).unwrap();
+    let json_value = serde_json::to_value(&resp).unwrap();
+    Json(serde_json::from_value(json_value).unwrap())
+}
+
+/// `POST /product/filter` over the caller's products, a `user_id` in the filter is ignored.
+#[post("/product/mine/filter", data = "<param>")]
+pub fn filter_my_products(
+    auth: AuthUser,
+    param: Option<Json<RequestParam<PaginationParam, ProductFilter>>>,
+) -> Json<serde_json::Value> {
+    let param = param.unwrap_or(Json(RequestParam::new(PaginationParam::default(), None)));
+    let resp = ProductController::filter_mine(&auth, param.into_inner()).unwrap();
+    let json_value = serde_json::to_value(&resp).unwrap();
+    Json(serde_json::from_value(json_value).unwrap())
+}
+
 #[get("/product/<id>")]
 pub fn get_product_by_id(id: i32) -> Json<serde_json::Value> {
     crab_rocket_schema::update_reload::update_reload_count();
@@ -42,27 +75,32 @@ pub fn get_product_by_id(id: i32) -> Json<serde_json::Value> {
     Json(serde_json::from_value(json_value).unwrap())
 }
 
+/// The product is owned by the caller unless a moderator names another `user_id`.
 #[post("/product", data = "<product>")]
-pub fn insert_single_product(product: Json<PostProduct>) -> Json<serde_json::Value> {
-    let mut obj: PostProduct = product.into_inner();
-
-    let resp = ProductController::add_single(&mut obj).unwrap();
-    let json_value = serde_json::to_value(&resp).unwrap();
-    Json(serde_json::from_value(json_value).unwrap())
+pub fn insert_single_product(
+    auth: AuthUser,
+    product: Json<PostProduct>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = ProductController::add_owned(&auth, &product);
+    to_response(code, message, data)
 }
 
+/// Only the owner, or a role granted `product:moderate`.
 #[delete("/product/<id>")]
-pub fn delete_product_by_id(id: i32) -> Json<serde_json::Value> {
-    let resp = ProductController::delete_by_id(id).unwrap();
-    let json_value = serde_json::to_value(&resp).unwrap();
-    Json(serde_json::from_value(json_value).unwrap())
+pub fn delete_product_by_id(auth: AuthUser, id: i32) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = ProductController::delete_owned(&auth, id);
+    to_response(code, message, data)
 }
 
-#[patch("/product/<id>", data = "<task>")]
-pub fn update_product_by_id(id: i32, task: Json<PatchProduct>) -> Json<serde_json::Value> {
-    let resp = ProductController::update_by_id(id, &task).unwrap();
-    let json_value = serde_json::to_value(&resp).unwrap();
-    Json(serde_json::from_value(json_value).unwrap())
+/// Only the owner, or a role granted `product:moderate`.
+#[patch("/product/<id>", data = "<product>")]
+pub fn update_product_by_id(
+    auth: AuthUser,
+    id: i32,
+    product: Json<PatchProduct>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = ProductController::update_owned(&auth, id, &product);
+    to_response(code, message, data)
 }
 
 #[options("/product")]
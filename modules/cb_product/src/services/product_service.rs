@@ -1,6 +1,8 @@
 use crate::mappers::product_mapper::ProductMapper;
 use crate::models::product::{PatchProduct, PostProduct, Product};
 use crate::models::product_filter::ProductFilter;
+use crab_rocket_auth::guards::auth_user::AuthUser;
+use crab_rocket_auth::services::ownership_service::{OwnershipError, OwnershipService};
 use obj_traits::request::pagination_request_param::PaginationParam;
 use obj_traits::request::request_param::RequestParam;
 use obj_traits::response::data::Data;
@@ -44,6 +46,52 @@ impl ServiceCRUD for ProductService {
     }
 }
 
+const RESOURCE: &str = "product";
+
+impl ProductService {
+    /// Creates a product owned by the caller, moderators may name another `user_id`.
+    pub fn add_owned(auth: &AuthUser, obj: &PostProduct) -> Result<Product, OwnershipError> {
+        let mut obj = obj.clone();
+        obj.user_id = Some(OwnershipService::assign_owner(auth, RESOURCE, obj.user_id)?);
+        Ok(Self::add_single(&obj)?)
+    }
+
+    /// Replaces product `pid` when the caller owns it or moderates products. Without `user_id`
+    /// the product keeps its owner, handing it to someone else takes a moderator.
+    pub fn update_owned(
+        auth: &AuthUser,
+        pid: i32,
+        obj: &PatchProduct,
+    ) -> Result<Product, OwnershipError> {
+        let product = Self::get_by_id(pid).map_err(|e| OwnershipError::lookup(RESOURCE, pid, e))?;
+        OwnershipService::authorize(auth, RESOURCE, "update", pid, product.user_id)?;
+        let mut obj = obj.clone();
+        match obj.user_id {
+            Some(user_id) if Some(user_id) != product.user_id => {
+                OwnershipService::assign_owner(auth, RESOURCE, Some(user_id))?;
+            }
+            _ => obj.user_id = product.user_id,
+        }
+        Ok(Self::update_by_id(pid, &obj)?)
+    }
+
+    pub fn delete_owned(auth: &AuthUser, pid: i32) -> Result<Product, OwnershipError> {
+        let product = Self::get_by_id(pid).map_err(|e| OwnershipError::lookup(RESOURCE, pid, e))?;
+        OwnershipService::authorize(auth, RESOURCE, "delete", pid, product.user_id)?;
+        Ok(Self::delete_by_id(pid)?)
+    }
+
+    /// Like [`ServiceCRUD::filter`], narrowed to the caller's products.
+    pub fn filter_mine(
+        auth: &AuthUser,
+        param: RequestParam<PaginationParam, ProductFilter>,
+    ) -> Result<Data<Vec<Product>>, Box<dyn Error>> {
+        let mut filter = param.filter.unwrap_or_default();
+        filter.user_id = Some(auth.user_id());
+        Self::filter(&RequestParam::new(param.pagination, Some(filter)))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::services::product_service::ProductService;
@@ -0,0 +1,71 @@
+use crate::error::ReviewError;
+use crate::models::review::{Review, ReviewAssessment, ReviewDetail};
+use crate::models::review_completion::DepartmentCompletion;
+use crate::models::review_cycle::{PostReviewCycle, ReviewCycle, ReviewCycleDetail, ReviewLaunch};
+use crate::services::review_service::ReviewService;
+
+pub struct ReviewController {}
+
+fn to_tuple<T>(result: Result<T, ReviewError>) -> (i32, String, Option<T>) {
+    match result {
+        Ok(data) => (200, String::from("Success"), Some(data)),
+        Err(ReviewError::Internal(e)) => {
+            println!("{e:?}");
+            (500, String::from("Internal Server Error"), None)
+        }
+        Err(e) => (e.status(), e.to_string(), None),
+    }
+}
+
+impl ReviewController {
+    pub fn get_cycles() -> (i32, String, Option<Vec<ReviewCycle>>) {
+        to_tuple(ReviewService::get_cycles())
+    }
+
+    pub fn get_cycle(pid: i32) -> (i32, String, Option<ReviewCycleDetail>) {
+        to_tuple(ReviewService::get_cycle(pid))
+    }
+
+    pub fn add_cycle(obj: &PostReviewCycle) -> (i32, String, Option<ReviewCycleDetail>) {
+        to_tuple(ReviewService::add_cycle(obj))
+    }
+
+    pub fn launch(pid: i32) -> (i32, String, Option<ReviewLaunch>) {
+        to_tuple(ReviewService::launch(pid))
+    }
+
+    pub fn get_completion(pid: i32) -> (i32, String, Option<Vec<DepartmentCompletion>>) {
+        to_tuple(ReviewService::get_completion(pid))
+    }
+
+    pub fn get_reviews(
+        employee_id: i32,
+        review_cycle_id: Option<i32>,
+    ) -> (i32, String, Option<Vec<Review>>) {
+        to_tuple(ReviewService::get_reviews(employee_id, review_cycle_id))
+    }
+
+    pub fn get_assigned(employee_id: i32) -> (i32, String, Option<Vec<Review>>) {
+        to_tuple(ReviewService::get_assigned(employee_id))
+    }
+
+    pub fn get_review(pid: i32) -> (i32, String, Option<ReviewDetail>) {
+        to_tuple(ReviewService::get_review(pid))
+    }
+
+    pub fn assess(
+        pid: i32,
+        user_id: i32,
+        obj: &ReviewAssessment,
+    ) -> (i32, String, Option<ReviewDetail>) {
+        to_tuple(ReviewService::assess(pid, user_id, obj))
+    }
+
+    pub fn submit(pid: i32, user_id: i32) -> (i32, String, Option<Review>) {
+        to_tuple(ReviewService::submit(pid, user_id))
+    }
+
+    pub fn acknowledge(pid: i32, user_id: i32) -> (i32, String, Option<Review>) {
+        to_tuple(ReviewService::acknowledge(pid, user_id))
+    }
+}
@@ -0,0 +1,183 @@
+use std::error::Error;
+use std::fmt;
+
+use chrono::NaiveDate;
+use diesel::ConnectionError;
+
+/// Why a review cycle or a review could not be set up, filled in or signed off.
+#[derive(Debug)]
+pub enum ReviewError {
+    EmployeeNotFound(i32),
+    CycleNotFound(i32),
+    ReviewNotFound(i32),
+    /// A period ending before it starts.
+    InvalidPeriod {
+        start: NaiveDate,
+        end: NaiveDate,
+    },
+    /// A cycle needs at least one criterion to be reviewed on.
+    NoCriteria,
+    InvalidWeight {
+        criterion_name: String,
+        weight: i32,
+    },
+    DuplicateCriterion(String),
+    /// Scores go from 1 to 5.
+    InvalidScore {
+        review_criterion_id: i32,
+        score: i32,
+    },
+    /// The criterion belongs to the form of another cycle.
+    UnknownCriterion {
+        review_criterion_id: i32,
+        review_cycle_id: i32,
+    },
+    CycleTaken(String),
+    /// Only the reviewer fills in and submits a review.
+    NotReviewer {
+        employee_id: i32,
+        review_id: i32,
+    },
+    /// Only the employee acknowledges their manager's review, and only their manager their
+    /// self review.
+    NotAcknowledger {
+        employee_id: i32,
+        review_id: i32,
+    },
+    NoManager(i32),
+    /// The caller signs as the employee linked to their user, and has none.
+    NoEmployee(i32),
+    NotDraft {
+        review_id: i32,
+        status: String,
+    },
+    NotSubmitted {
+        review_id: i32,
+        status: String,
+    },
+    /// Criteria of the form are not scored yet.
+    Incomplete {
+        review_id: i32,
+        missing: usize,
+    },
+    Internal(String),
+}
+
+impl ReviewError {
+    pub fn status(&self) -> i32 {
+        match self {
+            ReviewError::EmployeeNotFound(_)
+            | ReviewError::CycleNotFound(_)
+            | ReviewError::ReviewNotFound(_) => 404,
+            ReviewError::InvalidPeriod {
+                ..
+            }
+            | ReviewError::NoCriteria
+            | ReviewError::InvalidWeight {
+                ..
+            }
+            | ReviewError::DuplicateCriterion(_)
+            | ReviewError::InvalidScore {
+                ..
+            }
+            | ReviewError::UnknownCriterion {
+                ..
+            } => 400,
+            ReviewError::NotReviewer {
+                ..
+            }
+            | ReviewError::NotAcknowledger {
+                ..
+            }
+            | ReviewError::NoEmployee(_) => 403,
+            ReviewError::CycleTaken(_)
+            | ReviewError::NoManager(_)
+            | ReviewError::NotDraft {
+                ..
+            }
+            | ReviewError::NotSubmitted {
+                ..
+            }
+            | ReviewError::Incomplete {
+                ..
+            } => 409,
+            ReviewError::Internal(_) => 500,
+        }
+    }
+}
+
+impl fmt::Display for ReviewError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReviewError::EmployeeNotFound(id) => write!(f, "employee {id} not found"),
+            ReviewError::CycleNotFound(id) => write!(f, "review cycle {id} not found"),
+            ReviewError::ReviewNotFound(id) => write!(f, "review {id} not found"),
+            ReviewError::InvalidPeriod {
+                start,
+                end,
+            } => write!(f, "the period from {start} to {end} ends before it starts"),
+            ReviewError::NoCriteria => write!(f, "a review cycle needs at least one criterion"),
+            ReviewError::InvalidWeight {
+                criterion_name,
+                weight,
+            } => write!(f, "the weight {weight} of `{criterion_name}` is not positive"),
+            ReviewError::DuplicateCriterion(name) => {
+                write!(f, "criterion `{name}` is on the form twice")
+            }
+            ReviewError::InvalidScore {
+                review_criterion_id,
+                score,
+            } => {
+                write!(f, "score {score} of criterion {review_criterion_id} is not between 1 and 5")
+            }
+            ReviewError::UnknownCriterion {
+                review_criterion_id,
+                review_cycle_id,
+            } => write!(
+                f,
+                "criterion {review_criterion_id} is not on the form of review cycle \
+                 {review_cycle_id}"
+            ),
+            ReviewError::CycleTaken(name) => write!(f, "review cycle `{name}` already exists"),
+            ReviewError::NotReviewer {
+                employee_id,
+                review_id,
+            } => write!(f, "employee {employee_id} is not the reviewer of review {review_id}"),
+            ReviewError::NotAcknowledger {
+                employee_id,
+                review_id,
+            } => write!(f, "employee {employee_id} may not acknowledge review {review_id}"),
+            ReviewError::NoManager(id) => {
+                write!(f, "employee {id} has no manager to review them")
+            }
+            ReviewError::NoEmployee(id) => write!(f, "user {id} is not linked to an employee"),
+            ReviewError::NotDraft {
+                review_id,
+                status,
+            } => write!(f, "review {review_id} is {status}, not a draft"),
+            ReviewError::NotSubmitted {
+                review_id,
+                status,
+            } => write!(f, "review {review_id} is {status}, not submitted"),
+            ReviewError::Incomplete {
+                review_id,
+                missing,
+            } => write!(f, "review {review_id} has {missing} criteria left to score"),
+            ReviewError::Internal(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for ReviewError {}
+
+impl From<diesel::result::Error> for ReviewError {
+    fn from(e: diesel::result::Error) -> Self {
+        ReviewError::Internal(e.to_string())
+    }
+}
+
+impl From<ConnectionError> for ReviewError {
+    fn from(e: ConnectionError) -> Self {
+        ReviewError::Internal(e.to_string())
+    }
+}
@@ -0,0 +1,24 @@
+pub mod error;
+
+pub mod models {
+    pub mod review;
+    pub mod review_completion;
+    pub mod review_cycle;
+}
+
+pub mod mappers {
+    pub mod review_mapper;
+}
+
+pub mod controllers {
+    pub mod review_controller;
+}
+
+pub mod routes {
+    pub mod review_cycle_route;
+    pub mod review_route;
+}
+
+pub mod services {
+    pub mod review_service;
+}
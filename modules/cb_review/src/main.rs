@@ -0,0 +1,38 @@
+#[macro_use]
+extern crate rocket;
+
+use crab_rocket_review::routes::review_cycle_route::*;
+use crab_rocket_review::routes::review_route::*;
+use dotenvy::dotenv;
+use std::env;
+#[launch]
+fn rocket() -> _ {
+    // Clear environment variable before running.
+    env::remove_var("DATABASE_URL");
+    dotenv().ok();
+    let figment = crab_rocket_config::init();
+
+    crab_rocket_utils::run_preload();
+
+    let cors = crab_rocket_config::cors::cors();
+
+    rocket::custom(figment)
+        .mount(
+            "/api",
+            routes![
+                get_review_cycles,
+                get_review_cycle_by_id,
+                insert_review_cycle,
+                launch_review_cycle,
+                get_review_cycle_completion,
+                get_employee_reviews,
+                get_employee_assigned_reviews,
+                get_review_by_id,
+                update_review_by_id,
+                submit_review,
+                acknowledge_review,
+                options_review_cycle
+            ],
+        )
+        .attach(cors)
+}
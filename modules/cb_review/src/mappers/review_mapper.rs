@@ -0,0 +1,328 @@
+use crab_rocket_schema::schema::{
+    employee_table, review_criterion_table, review_cycle_table, review_score_table, review_table,
+};
+use crab_rocket_utils::time::get_e8_time;
+use diesel::dsl::{exists, not};
+use diesel::sql_types::Integer;
+use diesel::upsert::excluded;
+use diesel::{prelude::*, result::Error, sql_query};
+
+use crate::models::review::{NewReview, Review, ReviewScore, ACKNOWLEDGED, SUBMITTED};
+use crate::models::review_completion::DepartmentCompletion;
+use crate::models::review_cycle::{
+    NewReviewCriterion, NewReviewCycle, ReviewCriterion, ReviewCycle,
+};
+
+pub struct ReviewMapper {}
+
+impl ReviewMapper {
+    /// Every cycle, the latest first.
+    pub fn get_cycles(conn: &mut PgConnection) -> Result<Vec<ReviewCycle>, Error> {
+        review_cycle_table::table
+            .select(ReviewCycle::as_select())
+            .order((review_cycle_table::start_date.desc(), review_cycle_table::review_cycle_id))
+            .load(conn)
+    }
+
+    pub fn get_cycle(conn: &mut PgConnection, pid: i32) -> Result<ReviewCycle, Error> {
+        review_cycle_table::table.find(pid).select(ReviewCycle::as_select()).first(conn)
+    }
+
+    pub fn add_cycle(conn: &mut PgConnection, obj: &NewReviewCycle) -> Result<ReviewCycle, Error> {
+        diesel::insert_into(review_cycle_table::table)
+            .values(obj)
+            .returning(ReviewCycle::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn add_criteria(
+        conn: &mut PgConnection,
+        criteria: &[NewReviewCriterion],
+    ) -> Result<Vec<ReviewCriterion>, Error> {
+        diesel::insert_into(review_criterion_table::table)
+            .values(criteria)
+            .returning(ReviewCriterion::as_returning())
+            .get_results(conn)
+    }
+
+    /// The form of the cycle, in the order it was given.
+    pub fn get_criteria(
+        conn: &mut PgConnection,
+        review_cycle_id: i32,
+    ) -> Result<Vec<ReviewCriterion>, Error> {
+        review_criterion_table::table
+            .filter(review_criterion_table::review_cycle_id.eq(review_cycle_id))
+            .select(ReviewCriterion::as_select())
+            .order(review_criterion_table::review_criterion_id.asc())
+            .load(conn)
+    }
+
+    /// `(employee_id, manager_id)` of every employee.
+    pub fn get_employees(conn: &mut PgConnection) -> Result<Vec<(i32, Option<i32>)>, Error> {
+        employee_table::table
+            .select((employee_table::employee_id, employee_table::manager_id))
+            .order(employee_table::employee_id.asc())
+            .load(conn)
+    }
+
+    /// The `manager_id` of an employee.
+    pub fn get_manager(conn: &mut PgConnection, employee_id: i32) -> Result<Option<i32>, Error> {
+        employee_table::table.find(employee_id).select(employee_table::manager_id).first(conn)
+    }
+
+    /// The employee linked to a user.
+    pub fn get_employee_of_user(
+        conn: &mut PgConnection,
+        user_id: i32,
+    ) -> Result<Option<i32>, Error> {
+        employee_table::table
+            .filter(employee_table::user_id.eq(user_id))
+            .select(employee_table::employee_id)
+            .first(conn)
+            .optional()
+    }
+
+    /// Inserts the reviews that do not exist yet, returns how many.
+    pub fn add_reviews(conn: &mut PgConnection, reviews: &[NewReview]) -> Result<usize, Error> {
+        let mut inserted = 0;
+        // Four parameters a review, well below the bind parameter limit of Postgres.
+        for chunk in reviews.chunks(10_000) {
+            inserted += diesel::insert_into(review_table::table)
+                .values(chunk)
+                .on_conflict((
+                    review_table::review_cycle_id,
+                    review_table::employee_id,
+                    review_table::kind,
+                ))
+                .do_nothing()
+                .execute(conn)?;
+        }
+        Ok(inserted)
+    }
+
+    pub fn get_review(conn: &mut PgConnection, pid: i32) -> Result<Review, Error> {
+        review_table::table.find(pid).select(Review::as_select()).first(conn)
+    }
+
+    /// The review, locked until the end of the transaction.
+    pub fn get_review_for_update(conn: &mut PgConnection, pid: i32) -> Result<Review, Error> {
+        review_table::table.find(pid).select(Review::as_select()).for_update().first(conn)
+    }
+
+    /// Reviews of the employee, of one cycle or of all of them, the latest cycle first.
+    pub fn get_reviews_by_employee(
+        conn: &mut PgConnection,
+        employee_id: i32,
+        review_cycle_id: Option<i32>,
+    ) -> Result<Vec<Review>, Error> {
+        let mut query = review_table::table
+            .filter(review_table::employee_id.eq(employee_id))
+            .select(Review::as_select())
+            .order((review_table::review_cycle_id.desc(), review_table::review_id.asc()))
+            .into_boxed();
+        if let Some(review_cycle_id) = review_cycle_id {
+            query = query.filter(review_table::review_cycle_id.eq(review_cycle_id));
+        }
+        query.load(conn)
+    }
+
+    /// Reviews in `status` the employee writes, the earliest first.
+    pub fn get_reviews_by_reviewer(
+        conn: &mut PgConnection,
+        reviewer_id: i32,
+        status: &str,
+    ) -> Result<Vec<Review>, Error> {
+        review_table::table
+            .filter(review_table::reviewer_id.eq(reviewer_id))
+            .filter(review_table::status.eq(status))
+            .select(Review::as_select())
+            .order(review_table::review_id.asc())
+            .load(conn)
+    }
+
+    pub fn get_scores(conn: &mut PgConnection, review_id: i32) -> Result<Vec<ReviewScore>, Error> {
+        review_score_table::table
+            .filter(review_score_table::review_id.eq(review_id))
+            .select(ReviewScore::as_select())
+            .order(review_score_table::review_criterion_id.asc())
+            .load(conn)
+    }
+
+    /// Scores criteria, replacing their earlier score.
+    pub fn set_scores(conn: &mut PgConnection, scores: &[ReviewScore]) -> Result<usize, Error> {
+        diesel::insert_into(review_score_table::table)
+            .values(scores)
+            .on_conflict((review_score_table::review_id, review_score_table::review_criterion_id))
+            .do_update()
+            .set((
+                review_score_table::score.eq(excluded(review_score_table::score)),
+                review_score_table::comment.eq(excluded(review_score_table::comment)),
+            ))
+            .execute(conn)
+    }
+
+    /// Marks the review as changed, with a new overall comment when given.
+    pub fn touch(
+        conn: &mut PgConnection,
+        pid: i32,
+        comment: Option<&str>,
+    ) -> Result<Review, Error> {
+        let review = diesel::update(review_table::table.find(pid));
+        match comment {
+            Some(comment) => review
+                .set((
+                    review_table::comment.eq(comment),
+                    review_table::updated_at.eq(get_e8_time()),
+                ))
+                .returning(Review::as_returning())
+                .get_result(conn),
+            None => review
+                .set(review_table::updated_at.eq(get_e8_time()))
+                .returning(Review::as_returning())
+                .get_result(conn),
+        }
+    }
+
+    /// Criteria of the cycle the review has no score for.
+    pub fn count_unscored(
+        conn: &mut PgConnection,
+        review_id: i32,
+        review_cycle_id: i32,
+    ) -> Result<i64, Error> {
+        let scored =
+            review_score_table::table.filter(review_score_table::review_id.eq(review_id)).filter(
+                review_score_table::review_criterion_id
+                    .eq(review_criterion_table::review_criterion_id),
+            );
+        review_criterion_table::table
+            .filter(review_criterion_table::review_cycle_id.eq(review_cycle_id))
+            .filter(not(exists(scored)))
+            .count()
+            .get_result(conn)
+    }
+
+    pub fn submit(conn: &mut PgConnection, pid: i32) -> Result<Review, Error> {
+        let now = get_e8_time();
+        diesel::update(review_table::table.find(pid))
+            .set((
+                review_table::status.eq(SUBMITTED),
+                review_table::submitted_at.eq(now),
+                review_table::updated_at.eq(now),
+            ))
+            .returning(Review::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn acknowledge(conn: &mut PgConnection, pid: i32) -> Result<Review, Error> {
+        let now = get_e8_time();
+        diesel::update(review_table::table.find(pid))
+            .set((
+                review_table::status.eq(ACKNOWLEDGED),
+                review_table::acknowledged_at.eq(now),
+                review_table::updated_at.eq(now),
+            ))
+            .returning(Review::as_returning())
+            .get_result(conn)
+    }
+
+    /// One entry per department with reviews in the cycle, by name, employees without a
+    /// department last.
+    pub fn completion_by_department(
+        conn: &mut PgConnection,
+        review_cycle_id: i32,
+    ) -> Result<Vec<DepartmentCompletion>, Error> {
+        sql_query(
+            "SELECT d.department_id, d.department_name, \
+               COUNT(DISTINCT r.employee_id) AS employees, \
+               COUNT(*) AS reviews, \
+               COUNT(*) FILTER (WHERE r.status = 'draft') AS draft, \
+               COUNT(*) FILTER (WHERE r.status = 'submitted') AS submitted, \
+               COUNT(*) FILTER (WHERE r.status = 'acknowledged') AS acknowledged, \
+               ROUND(100.0 * COUNT(*) FILTER (WHERE r.status <> 'draft') / COUNT(*), 1)::FLOAT8 \
+                 AS completion \
+             FROM review_table r JOIN employee_table e ON e.employee_id = r.employee_id \
+             LEFT JOIN department_table d ON d.department_id = e.department_id \
+             WHERE r.review_cycle_id = $1 \
+             GROUP BY d.department_id, d.department_name \
+             ORDER BY d.department_name NULLS LAST, d.department_id",
+        )
+        .bind::<Integer, _>(review_cycle_id)
+        .load(conn)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDate;
+    use crab_rocket_test_support::{fixtures, test_conn};
+
+    use super::ReviewMapper;
+    use crate::models::review::{
+        NewReview, PostReviewScore, ReviewScore, MANAGER_REVIEW, SELF_REVIEW,
+    };
+    use crate::models::review_cycle::{
+        NewReviewCriterion, NewReviewCycle, PostReviewCriterion, PostReviewCycle,
+    };
+
+    #[test]
+    fn test_scores_are_weighted_and_completion_is_per_department() {
+        let mut conn = test_conn();
+        let department = fixtures::department(&mut conn, "Review floor", None);
+        let manager = fixtures::employee(&mut conn, "rev_manager", Some(department), None);
+        let worker = fixtures::employee(&mut conn, "rev_worker", Some(department), Some(manager));
+        let obj = PostReviewCycle {
+            review_cycle_name: String::from("Mapper quarter"),
+            start_date: NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            end_date: NaiveDate::from_ymd_opt(2026, 3, 31).unwrap(),
+            criteria: vec![],
+        };
+        let cycle = ReviewMapper::add_cycle(&mut conn, &NewReviewCycle::new(&obj)).unwrap();
+        let cycle_id = cycle.review_cycle_id();
+        let posted = [("Delivery", 3), ("Teamwork", 1)].map(|(name, weight)| PostReviewCriterion {
+            criterion_name: String::from(name),
+            description: None,
+            weight,
+        });
+        let criteria: Vec<_> =
+            posted.iter().map(|criterion| NewReviewCriterion::new(cycle_id, criterion)).collect();
+        let criteria = ReviewMapper::add_criteria(&mut conn, &criteria).unwrap();
+        let reviews = [
+            (manager, Some(manager), SELF_REVIEW),
+            (worker, Some(worker), SELF_REVIEW),
+            (worker, Some(manager), MANAGER_REVIEW),
+        ]
+        .map(|(employee_id, reviewer_id, kind)| NewReview {
+            review_cycle_id: cycle_id,
+            employee_id,
+            reviewer_id,
+            kind,
+        });
+        assert_eq!(ReviewMapper::add_reviews(&mut conn, &reviews).unwrap(), 3);
+        assert_eq!(ReviewMapper::add_reviews(&mut conn, &reviews).unwrap(), 0);
+        let review = ReviewMapper::get_reviews_by_reviewer(&mut conn, manager, "draft").unwrap()[1]
+            .review_id();
+
+        let score = |criterion: usize, score| {
+            let obj = PostReviewScore {
+                review_criterion_id: criteria[criterion].review_criterion_id(),
+                score,
+                comment: None,
+            };
+            ReviewScore::new(review, &obj)
+        };
+        ReviewMapper::set_scores(&mut conn, &[score(0, 5)]).unwrap();
+        assert_eq!(ReviewMapper::count_unscored(&mut conn, review, cycle_id).unwrap(), 1);
+        ReviewMapper::set_scores(&mut conn, &[score(0, 4), score(1, 1)]).unwrap();
+        assert_eq!(ReviewMapper::count_unscored(&mut conn, review, cycle_id).unwrap(), 0);
+        // (4 * 3 + 1 * 1) / 4
+        assert_eq!(ReviewMapper::get_review(&mut conn, review).unwrap().score(), Some(3.25));
+
+        ReviewMapper::submit(&mut conn, review).unwrap();
+        let completion = ReviewMapper::completion_by_department(&mut conn, cycle_id).unwrap();
+        let summary: Vec<_> = completion
+            .iter()
+            .map(|c| (c.department_id(), c.reviews(), c.draft(), c.submitted(), c.completion()))
+            .collect();
+        assert_eq!(summary, [(Some(department), 3, 2, 1, 33.3)]);
+    }
+}
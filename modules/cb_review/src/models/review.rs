@@ -0,0 +1,153 @@
+use chrono::NaiveDateTime;
+use diesel::dsl::sql;
+use diesel::expression::SqlLiteral;
+use diesel::prelude::*;
+use diesel::sql_types::{Double, Nullable};
+use rocket::serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+pub const DRAFT: &str = "draft";
+pub const SUBMITTED: &str = "submitted";
+pub const ACKNOWLEDGED: &str = "acknowledged";
+
+/// The employee assesses themselves.
+pub const SELF_REVIEW: &str = "self";
+/// The employee's manager assesses them.
+pub const MANAGER_REVIEW: &str = "manager";
+
+/// The scores of the review weighted by their criterion, to two decimals.
+pub fn weighted_score() -> SqlLiteral<Nullable<Double>> {
+    sql::<Nullable<Double>>(
+        "(SELECT ROUND(SUM(s.score * c.weight)::NUMERIC / SUM(c.weight), 2)::FLOAT8 \
+         FROM review_score_table s JOIN review_criterion_table c \
+           ON c.review_criterion_id = s.review_criterion_id \
+         WHERE s.review_id = review_table.review_id)",
+    )
+}
+
+/// A self or manager assessment of an employee in a review cycle.
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+#[diesel(table_name = crab_rocket_schema::schema::review_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Review {
+    review_id: i32,
+    review_cycle_id: i32,
+    employee_id: i32,
+    /// The employee themselves or, for a manager review, their manager when the cycle was
+    /// launched.
+    reviewer_id: Option<i32>,
+    /// `self` or `manager`.
+    kind: String,
+    /// `draft`, `submitted` or `acknowledged`.
+    status: String,
+    comment: Option<String>,
+    /// The weighted average of the scores so far, counted on every read.
+    #[diesel(select_expression = weighted_score())]
+    #[diesel(select_expression_type = SqlLiteral<Nullable<Double>>)]
+    score: Option<f64>,
+    submitted_at: Option<NaiveDateTime>,
+    acknowledged_at: Option<NaiveDateTime>,
+    created_at: NaiveDateTime,
+    updated_at: NaiveDateTime,
+}
+
+impl Review {
+    pub fn review_id(&self) -> i32 {
+        self.review_id
+    }
+
+    pub fn review_cycle_id(&self) -> i32 {
+        self.review_cycle_id
+    }
+
+    pub fn employee_id(&self) -> i32 {
+        self.employee_id
+    }
+
+    pub fn reviewer_id(&self) -> Option<i32> {
+        self.reviewer_id
+    }
+
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    pub fn status(&self) -> &str {
+        &self.status
+    }
+
+    pub fn score(&self) -> Option<f64> {
+        self.score
+    }
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crab_rocket_schema::schema::review_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NewReview {
+    pub review_cycle_id: i32,
+    pub employee_id: i32,
+    pub reviewer_id: Option<i32>,
+    pub kind: &'static str,
+}
+
+/// The score of one criterion of a review.
+#[derive(
+    Queryable, Selectable, Debug, Clone, Serialize, Deserialize, ToSchema, Default, Insertable,
+)]
+#[serde(crate = "rocket::serde")]
+#[diesel(table_name = crab_rocket_schema::schema::review_score_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ReviewScore {
+    review_id: i32,
+    review_criterion_id: i32,
+    /// From 1 to 5.
+    score: i32,
+    comment: Option<String>,
+}
+
+impl ReviewScore {
+    pub fn new(review_id: i32, obj: &PostReviewScore) -> Self {
+        Self {
+            review_id,
+            review_criterion_id: obj.review_criterion_id,
+            score: obj.score,
+            comment: obj.comment.clone(),
+        }
+    }
+
+    pub fn review_criterion_id(&self) -> i32 {
+        self.review_criterion_id
+    }
+
+    pub fn score(&self) -> i32 {
+        self.score
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct PostReviewScore {
+    pub review_criterion_id: i32,
+    pub score: i32,
+    pub comment: Option<String>,
+}
+
+/// Scores filled in by the caller, who has to be the reviewer. Criteria scored before and left
+/// out keep their score.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct ReviewAssessment {
+    pub scores: Vec<PostReviewScore>,
+    /// Replaces the overall comment when given.
+    pub comment: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Debug, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct ReviewDetail {
+    #[serde(flatten)]
+    pub review: Review,
+    pub scores: Vec<ReviewScore>,
+}
@@ -0,0 +1,55 @@
+use diesel::sql_types::{Double, Int4, Int8, Nullable, Varchar};
+use diesel::QueryableByName;
+use rocket::serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// How far the reviews of a cycle got in one department, employees without a department
+/// have a `department_id` of `null`. Sub-departments have entries of their own.
+#[derive(QueryableByName, Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct DepartmentCompletion {
+    #[diesel(sql_type = Nullable<Int4>)]
+    department_id: Option<i32>,
+    #[diesel(sql_type = Nullable<Varchar>)]
+    department_name: Option<String>,
+    /// Employees with a review in the cycle.
+    #[diesel(sql_type = Int8)]
+    employees: i64,
+    #[diesel(sql_type = Int8)]
+    reviews: i64,
+    #[diesel(sql_type = Int8)]
+    draft: i64,
+    #[diesel(sql_type = Int8)]
+    submitted: i64,
+    #[diesel(sql_type = Int8)]
+    acknowledged: i64,
+    /// Percent of the reviews submitted or acknowledged, to one decimal.
+    #[diesel(sql_type = Double)]
+    completion: f64,
+}
+
+impl DepartmentCompletion {
+    pub fn department_id(&self) -> Option<i32> {
+        self.department_id
+    }
+
+    pub fn reviews(&self) -> i64 {
+        self.reviews
+    }
+
+    pub fn draft(&self) -> i64 {
+        self.draft
+    }
+
+    pub fn submitted(&self) -> i64 {
+        self.submitted
+    }
+
+    pub fn acknowledged(&self) -> i64 {
+        self.acknowledged
+    }
+
+    pub fn completion(&self) -> f64 {
+        self.completion
+    }
+}
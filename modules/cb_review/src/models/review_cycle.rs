@@ -0,0 +1,141 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel::prelude::*;
+use rocket::serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A review period, e.g. a quarter, with the form of criteria its reviews are filled in on.
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+#[diesel(table_name = crab_rocket_schema::schema::review_cycle_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ReviewCycle {
+    review_cycle_id: i32,
+    review_cycle_name: String,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    created_at: NaiveDateTime,
+}
+
+impl ReviewCycle {
+    pub fn review_cycle_id(&self) -> i32 {
+        self.review_cycle_id
+    }
+
+    pub fn review_cycle_name(&self) -> &str {
+        &self.review_cycle_name
+    }
+
+    pub fn start_date(&self) -> NaiveDate {
+        self.start_date
+    }
+
+    pub fn end_date(&self) -> NaiveDate {
+        self.end_date
+    }
+}
+
+/// One line of the review form of a cycle.
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+#[diesel(table_name = crab_rocket_schema::schema::review_criterion_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ReviewCriterion {
+    review_criterion_id: i32,
+    review_cycle_id: i32,
+    criterion_name: String,
+    description: Option<String>,
+    /// Counts `weight` over the sum of the weights of the cycle in the score of a review.
+    weight: i32,
+}
+
+impl ReviewCriterion {
+    pub fn review_criterion_id(&self) -> i32 {
+        self.review_criterion_id
+    }
+
+    pub fn review_cycle_id(&self) -> i32 {
+        self.review_cycle_id
+    }
+
+    pub fn criterion_name(&self) -> &str {
+        &self.criterion_name
+    }
+
+    pub fn weight(&self) -> i32 {
+        self.weight
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct PostReviewCycle {
+    pub review_cycle_name: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub criteria: Vec<PostReviewCriterion>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct PostReviewCriterion {
+    pub criterion_name: String,
+    pub description: Option<String>,
+    pub weight: i32,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crab_rocket_schema::schema::review_cycle_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NewReviewCycle<'a> {
+    review_cycle_name: &'a str,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+}
+
+impl<'a> NewReviewCycle<'a> {
+    pub fn new(obj: &'a PostReviewCycle) -> Self {
+        Self {
+            review_cycle_name: &obj.review_cycle_name,
+            start_date: obj.start_date,
+            end_date: obj.end_date,
+        }
+    }
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = crab_rocket_schema::schema::review_criterion_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct NewReviewCriterion<'a> {
+    review_cycle_id: i32,
+    criterion_name: &'a str,
+    description: Option<&'a str>,
+    weight: i32,
+}
+
+impl<'a> NewReviewCriterion<'a> {
+    pub fn new(review_cycle_id: i32, obj: &'a PostReviewCriterion) -> Self {
+        Self {
+            review_cycle_id,
+            criterion_name: &obj.criterion_name,
+            description: obj.description.as_deref(),
+            weight: obj.weight,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Debug, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct ReviewCycleDetail {
+    #[serde(flatten)]
+    pub cycle: ReviewCycle,
+    pub criteria: Vec<ReviewCriterion>,
+}
+
+/// What launching a cycle added.
+#[derive(Debug, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct ReviewLaunch {
+    pub review_cycle_id: i32,
+    /// Draft reviews created, the ones that existed already are skipped.
+    pub reviews: usize,
+}
@@ -0,0 +1,76 @@
+use crab_rocket_auth::guards::authorized::Authorized;
+use crab_rocket_auth::permission;
+use obj_traits::response::api_response::ApiResponse;
+use rocket::http::Status;
+use rocket::response::status;
+use rocket::serde::json::Json;
+use rocket::{get, options, post};
+
+use crate::controllers::review_controller::ReviewController;
+use crate::models::review_cycle::PostReviewCycle;
+
+permission!(pub ReviewRead, "review", "read");
+permission!(pub ReviewCreate, "review", "create");
+permission!(pub ReviewUpdate, "review", "update");
+
+/// Answers with `code` as the HTTP status, in the shape of the other review routes.
+pub(crate) fn to_response<T: rocket::serde::Serialize + Default>(
+    code: i32,
+    message: String,
+    data: T,
+) -> status::Custom<Json<serde_json::Value>> {
+    let response = serde_json::to_value(ApiResponse::new(code, message, data)).unwrap();
+    let status = Status::from_code(code as u16).unwrap_or(Status::InternalServerError);
+    status::Custom(status, Json(response))
+}
+
+#[get("/review/cycle")]
+pub fn get_review_cycles(_auth: Authorized<ReviewRead>) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = ReviewController::get_cycles();
+    to_response(code, message, data)
+}
+
+/// The cycle with its criteria.
+#[get("/review/cycle/<id>")]
+pub fn get_review_cycle_by_id(
+    _auth: Authorized<ReviewRead>,
+    id: i32,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = ReviewController::get_cycle(id);
+    to_response(code, message, data)
+}
+
+/// A taken `review_cycle_name` answers `409`, a form without criteria `400`.
+#[post("/review/cycle", data = "<cycle>")]
+pub fn insert_review_cycle(
+    _auth: Authorized<ReviewCreate>,
+    cycle: Json<PostReviewCycle>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = ReviewController::add_cycle(&cycle);
+    to_response(code, message, data)
+}
+
+/// Opens the self and manager reviews employees do not have yet, safe to call again.
+#[post("/review/cycle/<id>/launch")]
+pub fn launch_review_cycle(
+    _auth: Authorized<ReviewCreate>,
+    id: i32,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = ReviewController::launch(id);
+    to_response(code, message, data)
+}
+
+/// Draft, submitted and acknowledged reviews of the cycle per department.
+#[get("/review/cycle/<id>/completion")]
+pub fn get_review_cycle_completion(
+    _auth: Authorized<ReviewRead>,
+    id: i32,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = ReviewController::get_completion(id);
+    to_response(code, message, data)
+}
+
+#[options("/review/cycle")]
+pub fn options_review_cycle() -> Status {
+    Status::Ok
+}
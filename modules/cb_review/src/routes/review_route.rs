@@ -0,0 +1,71 @@
+use crab_rocket_auth::guards::authorized::Authorized;
+use rocket::response::status;
+use rocket::serde::json::Json;
+use rocket::{get, post, put};
+
+use crate::controllers::review_controller::ReviewController;
+use crate::models::review::ReviewAssessment;
+use crate::routes::review_cycle_route::{to_response, ReviewRead, ReviewUpdate};
+
+/// Reviews of the employee, of the `cycle` or of all cycles.
+#[get("/employee/<id>/review?<cycle>")]
+pub fn get_employee_reviews(
+    _auth: Authorized<ReviewRead>,
+    id: i32,
+    cycle: Option<i32>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = ReviewController::get_reviews(id, cycle);
+    to_response(code, message, data)
+}
+
+/// Drafts the employee writes, their self reviews and the manager reviews of their reports.
+#[get("/employee/<id>/review/assigned")]
+pub fn get_employee_assigned_reviews(
+    _auth: Authorized<ReviewRead>,
+    id: i32,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = ReviewController::get_assigned(id);
+    to_response(code, message, data)
+}
+
+/// The review with its scores.
+#[get("/review/<id>")]
+pub fn get_review_by_id(
+    _auth: Authorized<ReviewRead>,
+    id: i32,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = ReviewController::get_review(id);
+    to_response(code, message, data)
+}
+
+/// The caller's employee has to be the reviewer (`403` otherwise) and the review a draft
+/// (`409`).
+#[put("/review/<id>", data = "<assessment>")]
+pub fn update_review_by_id(
+    auth: Authorized<ReviewUpdate>,
+    id: i32,
+    assessment: Json<ReviewAssessment>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = ReviewController::assess(id, auth.auth.user_id(), &assessment);
+    to_response(code, message, data)
+}
+
+/// By the reviewer, with every criterion scored (`409` otherwise).
+#[post("/review/<id>/submit")]
+pub fn submit_review(
+    auth: Authorized<ReviewUpdate>,
+    id: i32,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = ReviewController::submit(id, auth.auth.user_id());
+    to_response(code, message, data)
+}
+
+/// A manager review by the employee, a self review by their manager (`403` otherwise).
+#[post("/review/<id>/acknowledge")]
+pub fn acknowledge_review(
+    auth: Authorized<ReviewUpdate>,
+    id: i32,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = ReviewController::acknowledge(id, auth.auth.user_id());
+    to_response(code, message, data)
+}
@@ -0,0 +1,278 @@
+use std::collections::HashSet;
+
+use crab_rocket_schema::establish_pg_connection;
+use diesel::result::DatabaseErrorKind;
+use diesel::{Connection, PgConnection};
+
+use crate::error::ReviewError;
+use crate::mappers::review_mapper::ReviewMapper;
+use crate::models::review::{
+    NewReview, Review, ReviewAssessment, ReviewDetail, ReviewScore, DRAFT, MANAGER_REVIEW,
+    SELF_REVIEW, SUBMITTED,
+};
+use crate::models::review_completion::DepartmentCompletion;
+use crate::models::review_cycle::{
+    NewReviewCriterion, NewReviewCycle, PostReviewCycle, ReviewCycle, ReviewCycleDetail,
+    ReviewLaunch,
+};
+
+pub struct ReviewService {}
+
+impl ReviewService {
+    pub fn get_cycles() -> Result<Vec<ReviewCycle>, ReviewError> {
+        let mut conn = establish_pg_connection()?;
+        Ok(ReviewMapper::get_cycles(&mut conn)?)
+    }
+
+    pub fn get_cycle(pid: i32) -> Result<ReviewCycleDetail, ReviewError> {
+        let mut conn = establish_pg_connection()?;
+        let cycle = find_cycle(&mut conn, pid)?;
+        Ok(ReviewCycleDetail {
+            criteria: ReviewMapper::get_criteria(&mut conn, pid)?,
+            cycle,
+        })
+    }
+
+    /// Creates the cycle with its form, at least one criterion of a positive weight and each
+    /// named once.
+    pub fn add_cycle(obj: &PostReviewCycle) -> Result<ReviewCycleDetail, ReviewError> {
+        let (start, end) = (obj.start_date, obj.end_date);
+        if end < start {
+            return Err(ReviewError::InvalidPeriod {
+                start,
+                end,
+            });
+        }
+        if obj.criteria.is_empty() {
+            return Err(ReviewError::NoCriteria);
+        }
+        let mut names = HashSet::new();
+        for criterion in &obj.criteria {
+            if criterion.weight <= 0 {
+                return Err(ReviewError::InvalidWeight {
+                    criterion_name: criterion.criterion_name.clone(),
+                    weight: criterion.weight,
+                });
+            }
+            if !names.insert(criterion.criterion_name.as_str()) {
+                return Err(ReviewError::DuplicateCriterion(criterion.criterion_name.clone()));
+            }
+        }
+        let mut conn = establish_pg_connection()?;
+        conn.transaction(|conn| {
+            let cycle =
+                ReviewMapper::add_cycle(conn, &NewReviewCycle::new(obj)).map_err(|e| match e {
+                    diesel::result::Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
+                        ReviewError::CycleTaken(obj.review_cycle_name.clone())
+                    }
+                    e => e.into(),
+                })?;
+            let criteria: Vec<_> = obj
+                .criteria
+                .iter()
+                .map(|criterion| NewReviewCriterion::new(cycle.review_cycle_id(), criterion))
+                .collect();
+            Ok(ReviewCycleDetail {
+                criteria: ReviewMapper::add_criteria(conn, &criteria)?,
+                cycle,
+            })
+        })
+    }
+
+    /// Opens a draft self review for every employee and a manager review for everyone with a
+    /// `manager_id`, written by that manager. Employees that have their reviews already are
+    /// skipped, so launching again picks up new hires only.
+    pub fn launch(pid: i32) -> Result<ReviewLaunch, ReviewError> {
+        let mut conn = establish_pg_connection()?;
+        conn.transaction(|conn| {
+            find_cycle(conn, pid)?;
+            let mut reviews = Vec::new();
+            for (employee_id, manager_id) in ReviewMapper::get_employees(conn)? {
+                reviews.push(NewReview {
+                    review_cycle_id: pid,
+                    employee_id,
+                    reviewer_id: Some(employee_id),
+                    kind: SELF_REVIEW,
+                });
+                if manager_id.is_some() {
+                    reviews.push(NewReview {
+                        review_cycle_id: pid,
+                        employee_id,
+                        reviewer_id: manager_id,
+                        kind: MANAGER_REVIEW,
+                    });
+                }
+            }
+            Ok(ReviewLaunch {
+                review_cycle_id: pid,
+                reviews: ReviewMapper::add_reviews(conn, &reviews)?,
+            })
+        })
+    }
+
+    pub fn get_completion(pid: i32) -> Result<Vec<DepartmentCompletion>, ReviewError> {
+        let mut conn = establish_pg_connection()?;
+        find_cycle(&mut conn, pid)?;
+        Ok(ReviewMapper::completion_by_department(&mut conn, pid)?)
+    }
+
+    /// Reviews of the employee, of one cycle or of all of them.
+    pub fn get_reviews(
+        employee_id: i32,
+        review_cycle_id: Option<i32>,
+    ) -> Result<Vec<Review>, ReviewError> {
+        let mut conn = establish_pg_connection()?;
+        find_manager(&mut conn, employee_id)?;
+        Ok(ReviewMapper::get_reviews_by_employee(&mut conn, employee_id, review_cycle_id)?)
+    }
+
+    /// Drafts the employee has to fill in, their own and those of their reports.
+    pub fn get_assigned(employee_id: i32) -> Result<Vec<Review>, ReviewError> {
+        let mut conn = establish_pg_connection()?;
+        find_manager(&mut conn, employee_id)?;
+        Ok(ReviewMapper::get_reviews_by_reviewer(&mut conn, employee_id, DRAFT)?)
+    }
+
+    pub fn get_review(pid: i32) -> Result<ReviewDetail, ReviewError> {
+        let mut conn = establish_pg_connection()?;
+        let review = match ReviewMapper::get_review(&mut conn, pid) {
+            Err(diesel::result::Error::NotFound) => return Err(ReviewError::ReviewNotFound(pid)),
+            result => result?,
+        };
+        Ok(ReviewDetail {
+            scores: ReviewMapper::get_scores(&mut conn, pid)?,
+            review,
+        })
+    }
+
+    /// Scores criteria of a draft, `user_id` has to be linked to its reviewer.
+    pub fn assess(
+        pid: i32,
+        user_id: i32,
+        obj: &ReviewAssessment,
+    ) -> Result<ReviewDetail, ReviewError> {
+        let mut conn = establish_pg_connection()?;
+        conn.transaction(|conn| {
+            let reviewer_id = find_employee(conn, user_id)?;
+            let review = find_draft(conn, pid, reviewer_id)?;
+            let criteria: HashSet<_> = ReviewMapper::get_criteria(conn, review.review_cycle_id())?
+                .iter()
+                .map(|criterion| criterion.review_criterion_id())
+                .collect();
+            for score in &obj.scores {
+                if !(1..=5).contains(&score.score) {
+                    return Err(ReviewError::InvalidScore {
+                        review_criterion_id: score.review_criterion_id,
+                        score: score.score,
+                    });
+                }
+                if !criteria.contains(&score.review_criterion_id) {
+                    return Err(ReviewError::UnknownCriterion {
+                        review_criterion_id: score.review_criterion_id,
+                        review_cycle_id: review.review_cycle_id(),
+                    });
+                }
+            }
+            let scores: Vec<_> =
+                obj.scores.iter().map(|score| ReviewScore::new(pid, score)).collect();
+            if !scores.is_empty() {
+                ReviewMapper::set_scores(conn, &scores)?;
+            }
+            Ok(ReviewDetail {
+                review: ReviewMapper::touch(conn, pid, obj.comment.as_deref())?,
+                scores: ReviewMapper::get_scores(conn, pid)?,
+            })
+        })
+    }
+
+    /// Hands a draft with every criterion scored over to be acknowledged, `user_id` has to be
+    /// linked to its reviewer.
+    pub fn submit(pid: i32, user_id: i32) -> Result<Review, ReviewError> {
+        let mut conn = establish_pg_connection()?;
+        conn.transaction(|conn| {
+            let reviewer_id = find_employee(conn, user_id)?;
+            let review = find_draft(conn, pid, reviewer_id)?;
+            let missing = ReviewMapper::count_unscored(conn, pid, review.review_cycle_id())?;
+            if missing > 0 {
+                return Err(ReviewError::Incomplete {
+                    review_id: pid,
+                    missing: missing as usize,
+                });
+            }
+            Ok(ReviewMapper::submit(conn, pid)?)
+        })
+    }
+
+    /// Signs off a submitted review as the employee linked to `user_id`: a manager review by
+    /// the employee, a self review by the employee's manager.
+    pub fn acknowledge(pid: i32, user_id: i32) -> Result<Review, ReviewError> {
+        let mut conn = establish_pg_connection()?;
+        conn.transaction(|conn| {
+            let signer_id = find_employee(conn, user_id)?;
+            let review = find_review(conn, pid)?;
+            if review.status() != SUBMITTED {
+                return Err(ReviewError::NotSubmitted {
+                    review_id: pid,
+                    status: review.status().to_string(),
+                });
+            }
+            let employee_id = review.employee_id();
+            let acknowledger = match review.kind() {
+                MANAGER_REVIEW => employee_id,
+                _ => find_manager(conn, employee_id)?.ok_or(ReviewError::NoManager(employee_id))?,
+            };
+            if acknowledger != signer_id {
+                return Err(ReviewError::NotAcknowledger {
+                    employee_id: signer_id,
+                    review_id: pid,
+                });
+            }
+            Ok(ReviewMapper::acknowledge(conn, pid)?)
+        })
+    }
+}
+
+fn find_cycle(conn: &mut PgConnection, pid: i32) -> Result<ReviewCycle, ReviewError> {
+    match ReviewMapper::get_cycle(conn, pid) {
+        Err(diesel::result::Error::NotFound) => Err(ReviewError::CycleNotFound(pid)),
+        result => Ok(result?),
+    }
+}
+
+fn find_review(conn: &mut PgConnection, pid: i32) -> Result<Review, ReviewError> {
+    match ReviewMapper::get_review_for_update(conn, pid) {
+        Err(diesel::result::Error::NotFound) => Err(ReviewError::ReviewNotFound(pid)),
+        result => Ok(result?),
+    }
+}
+
+/// The employee linked to the user.
+fn find_employee(conn: &mut PgConnection, user_id: i32) -> Result<i32, ReviewError> {
+    ReviewMapper::get_employee_of_user(conn, user_id)?.ok_or(ReviewError::NoEmployee(user_id))
+}
+
+/// The review, as long as it is a draft written by `reviewer_id`.
+fn find_draft(conn: &mut PgConnection, pid: i32, reviewer_id: i32) -> Result<Review, ReviewError> {
+    let review = find_review(conn, pid)?;
+    if review.reviewer_id() != Some(reviewer_id) {
+        return Err(ReviewError::NotReviewer {
+            employee_id: reviewer_id,
+            review_id: pid,
+        });
+    }
+    if review.status() != DRAFT {
+        return Err(ReviewError::NotDraft {
+            review_id: pid,
+            status: review.status().to_string(),
+        });
+    }
+    Ok(review)
+}
+
+/// The `manager_id` of the employee.
+fn find_manager(conn: &mut PgConnection, employee_id: i32) -> Result<Option<i32>, ReviewError> {
+    match ReviewMapper::get_manager(conn, employee_id) {
+        Err(diesel::result::Error::NotFound) => Err(ReviewError::EmployeeNotFound(employee_id)),
+        result => Ok(result?),
+    }
+}
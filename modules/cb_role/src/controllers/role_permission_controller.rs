@@ -0,0 +1,54 @@
+use crab_rocket_permission::models::permission::Permission;
+
+use crate::models::role::Role;
+use crate::services::role_permission_service::{GrantChange, RolePermissionService};
+
+pub fn get_permissions_of_role(role_id: i32) -> (i32, String, Option<Vec<Permission>>) {
+    match RolePermissionService::get_permissions_of_role(role_id) {
+        Ok(Some(permissions)) => (200, String::from("Ok"), Some(permissions)),
+        Ok(None) => (404, String::from("Role not found"), None),
+        Err(e) => {
+            println!("{e:?}");
+            (500, String::from("Internal Server Error"), None)
+        }
+    }
+}
+
+pub fn grant(role_id: i32, permission_id: i32) -> (i32, String) {
+    to_status(RolePermissionService::grant(role_id, permission_id), "Granted", "Already granted")
+}
+
+pub fn revoke(role_id: i32, permission_id: i32) -> (i32, String) {
+    to_status(RolePermissionService::revoke(role_id, permission_id), "Revoked", "Was not granted")
+}
+
+pub fn set_two_factor_policy(role_id: i32, required: bool) -> (i32, String, Option<Role>) {
+    match RolePermissionService::set_two_factor_policy(role_id, required) {
+        Ok(Some(role)) if required => {
+            (200, String::from("Members now need a second factor"), Some(role))
+        }
+        Ok(Some(role)) => (200, String::from("Second factor is optional for members"), Some(role)),
+        Ok(None) => (404, String::from("Role not found"), None),
+        Err(e) => {
+            println!("{e:?}");
+            (500, String::from("Internal Server Error"), None)
+        }
+    }
+}
+
+fn to_status(
+    result: Result<GrantChange, Box<dyn std::error::Error>>,
+    changed: &str,
+    unchanged: &str,
+) -> (i32, String) {
+    match result {
+        Ok(GrantChange::Changed) => (200, String::from(changed)),
+        Ok(GrantChange::Unchanged) => (200, String::from(unchanged)),
+        Ok(GrantChange::RoleNotFound) => (404, String::from("Role not found")),
+        Ok(GrantChange::PermissionNotFound) => (404, String::from("Permission not found")),
+        Err(e) => {
+            println!("{e:?}");
+            (500, String::from("Internal Server Error"))
+        }
+    }
+}
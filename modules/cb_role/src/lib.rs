@@ -1,9 +1,11 @@
 pub mod controllers {
     pub mod role_controller;
+    pub mod role_permission_controller;
 }
 
 pub mod mappers {
     pub mod role_mapper;
+    pub mod role_permission_mapper;
 }
 
 pub mod models {
@@ -16,5 +18,6 @@ pub mod routes {
 }
 
 pub mod services {
+    pub mod role_permission_service;
     pub mod role_service;
 }
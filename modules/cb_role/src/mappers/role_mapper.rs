@@ -1,4 +1,4 @@
-use crate::models::role::{PostRole, PatchRole, Role};
+use crate::models::role::{PatchRole, PostRole, Role};
 use crate::models::role_filter::RoleFilter;
 use crab_rocket_schema::schema::role_table::dsl; //配合下面的 `posts.filter()`
 use crab_rocket_schema::schema::role_table::{self};
@@ -37,8 +37,8 @@ impl MapperCRUD for RoleMapper {
         //
         // limit 始终为 per_page
         // 计算分页相关
-        let page = (param.pagination.offset.unwrap() / param.pagination.limit.unwrap()) + 1;
-        let per_page = param.pagination.limit.unwrap();
+        let page = (param.pagination.offset_or_default() / param.pagination.limit_or_default()) + 1;
+        let per_page = param.pagination.limit_or_default();
         // 获取总记录数
         let total_count = dsl::role_table.count().get_result::<i64>(conn)? as i32;
         // 计算总页数
@@ -58,6 +58,7 @@ impl MapperCRUD for RoleMapper {
         // 分页查询
         let data = dsl::role_table
             .order(dsl::updated_at.desc())
+            .then_order_by(dsl::role_id.desc())
             .limit(per_page as i64)
             .offset(((page - 1) * per_page) as i64)
             .load::<Role>(conn)?;
@@ -119,8 +120,8 @@ impl MapperCRUD for RoleMapper {
         //
         // limit 始终为 per_page
         // 计算分页相关
-        let page = (param.pagination.offset.unwrap() / param.pagination.limit.unwrap()) + 1;
-        let per_page = param.pagination.limit.unwrap();
+        let page = (param.pagination.offset_or_default() / param.pagination.limit_or_default()) + 1;
+        let per_page = param.pagination.limit_or_default();
         // 获取总记录数
         let total_count = dsl::role_table.count().get_result::<i64>(conn)? as i32;
         // 计算总页数
@@ -142,6 +143,7 @@ impl MapperCRUD for RoleMapper {
         // 分页查询
         query = query
             .order(dsl::created_at.desc())
+            .then_order_by(dsl::role_id.desc())
             .limit(per_page as i64)
             .offset(((page - 1) * per_page) as i64);
 
@@ -159,6 +161,9 @@ impl MapperCRUD for RoleMapper {
             if let Some(permissions) = &f.permissions {
                 query = query.filter(dsl::permissions.like(format!("%{}%", permissions)));
             }
+            if let Some(require_two_factor) = f.require_two_factor {
+                query = query.filter(dsl::require_two_factor.eq(require_two_factor));
+            }
             if let Some(created_at_min) = &f.created_at_min {
                 query = query.filter(dsl::created_at.ge(created_at_min));
             }
@@ -178,6 +183,25 @@ impl MapperCRUD for RoleMapper {
     }
 }
 
+impl RoleMapper {
+    pub fn get_by_name(conn: &mut PgConnection, name: &str) -> Result<Role, Error> {
+        dsl::role_table.filter(dsl::role_name.eq(name)).first::<Role>(conn)
+    }
+
+    pub fn set_require_two_factor(
+        conn: &mut PgConnection,
+        pid: i32,
+        required: bool,
+    ) -> Result<Role, Error> {
+        diesel::update(dsl::role_table.filter(dsl::role_id.eq(pid)))
+            .set((
+                role_table::require_two_factor.eq(required),
+                role_table::updated_at.eq(get_e8_time()),
+            ))
+            .get_result(conn)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use obj_traits::request::pagination_request_param::PaginationParamTrait;
@@ -186,13 +210,10 @@ mod test {
     fn test_insert_role() {
         use super::*;
         use crab_rocket_schema::establish_pg_connection; // 建立数据库连接
-        match establish_pg_connection() {
-            Ok(mut conn) => {
-                // 创建一个新的 NewPost 实例
-                let new_role = PostRole::demo();
-                let _ = RoleMapper::add_single(&mut conn, &new_role);
-            }
-            Err(_) => (),
+        if let Ok(mut conn) = establish_pg_connection() {
+            // 创建一个新的 NewPost 实例
+            let new_role = PostRole::demo();
+            let _ = RoleMapper::add_single(&mut conn, &new_role);
         }
     }
 
@@ -202,15 +223,10 @@ mod test {
         use crab_rocket_schema::establish_pg_connection; // 建立数据库连接
         let param = RequestParam::new(PaginationParam::demo(), None);
 
-        match establish_pg_connection() {
-            Ok(mut conn) => match RoleMapper::get_all(&mut conn, &param) {
-                Ok(roles) => {
-                    println!("{roles}");
-                    ()
-                }
-                Err(_) => (),
-            },
-            Err(_) => (),
+        if let Ok(mut conn) = establish_pg_connection() {
+            if let Ok(roles) = RoleMapper::get_all(&mut conn, &param) {
+                println!("{roles}");
+            }
         }
     }
 }
@@ -0,0 +1,77 @@
+use crab_rocket_permission::models::permission::Permission;
+use crab_rocket_schema::schema::{permission_table, role_permission_table};
+use crab_rocket_utils::time::get_e8_time;
+use diesel::prelude::*;
+use diesel::result::Error;
+
+pub struct RolePermissionMapper {}
+
+impl RolePermissionMapper {
+    pub fn get_permissions_of_role(
+        conn: &mut PgConnection,
+        role_id: i32,
+    ) -> Result<Vec<Permission>, Error> {
+        role_permission_table::table
+            .inner_join(permission_table::table)
+            .filter(role_permission_table::role_id.eq(role_id))
+            .order((permission_table::resource, permission_table::action))
+            .select(Permission::as_select())
+            .load(conn)
+    }
+
+    /// `false` when the role had the permission already.
+    pub fn grant(conn: &mut PgConnection, role_id: i32, permission_id: i32) -> Result<bool, Error> {
+        let inserted = diesel::insert_into(role_permission_table::table)
+            .values((
+                role_permission_table::role_id.eq(role_id),
+                role_permission_table::permission_id.eq(permission_id),
+                role_permission_table::granted_at.eq(get_e8_time()),
+            ))
+            .on_conflict_do_nothing()
+            .execute(conn)?;
+        Ok(inserted > 0)
+    }
+
+    /// `false` when the role did not have the permission.
+    pub fn revoke(
+        conn: &mut PgConnection,
+        role_id: i32,
+        permission_id: i32,
+    ) -> Result<bool, Error> {
+        let deleted = diesel::delete(role_permission_table::table.find((role_id, permission_id)))
+            .execute(conn)?;
+        Ok(deleted > 0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crab_rocket_test_support::{fixtures, test_conn};
+
+    use super::RolePermissionMapper;
+
+    #[test]
+    fn test_grant_and_revoke() {
+        let mut conn = test_conn();
+        let role_id = fixtures::role(&mut conn, "Clerk");
+        let read = fixtures::grant(&mut conn, role_id, "invoice", "read");
+        let other = fixtures::role(&mut conn, "Other");
+        let write = fixtures::grant(&mut conn, other, "invoice", "update");
+
+        assert!(!RolePermissionMapper::grant(&mut conn, role_id, read).unwrap());
+        assert!(RolePermissionMapper::grant(&mut conn, role_id, write).unwrap());
+        let names: Vec<_> = RolePermissionMapper::get_permissions_of_role(&mut conn, role_id)
+            .unwrap()
+            .into_iter()
+            .map(|p| p.permission_name)
+            .collect();
+        assert_eq!(names, ["invoice:read", "invoice:update"]);
+
+        assert!(RolePermissionMapper::revoke(&mut conn, role_id, read).unwrap());
+        assert!(!RolePermissionMapper::revoke(&mut conn, role_id, read).unwrap());
+        assert_eq!(
+            RolePermissionMapper::get_permissions_of_role(&mut conn, role_id).unwrap().len(),
+            1
+        );
+    }
+}
@@ -14,6 +14,10 @@ pub struct Role {
     permissions: Option<String>,
     created_at: Option<chrono::NaiveDateTime>,
     updated_at: Option<chrono::NaiveDateTime>,
+    /// Members get no permissions until they log in with a second factor, changed through
+    /// `PUT /role/<id>/two-factor`.
+    #[serde(default)]
+    require_two_factor: bool,
 }
 
 impl Role {
@@ -24,6 +28,7 @@ impl Role {
         permissions: Option<String>,
         created_at: Option<chrono::NaiveDateTime>,
         updated_at: Option<chrono::NaiveDateTime>,
+        require_two_factor: bool,
     ) -> Self {
         Self {
             role_id,
@@ -32,6 +37,7 @@ impl Role {
             permissions,
             created_at,
             updated_at,
+            require_two_factor,
         }
     }
 
@@ -59,6 +65,10 @@ impl Role {
         self.updated_at
     }
 
+    pub fn require_two_factor(&self) -> bool {
+        self.require_two_factor
+    }
+
     pub fn set_role_id(&mut self, role_id: i32) {
         self.role_id = role_id;
     }
@@ -84,6 +94,13 @@ impl Role {
     }
 }
 
+/// Body of `PUT /role/<id>/two-factor`.
+#[derive(Debug, Clone, Copy, Deserialize, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct TwoFactorPolicy {
+    pub required: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(crate = "rocket::serde")]
 #[derive(Queryable, Selectable, Insertable)]
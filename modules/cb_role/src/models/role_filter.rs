@@ -8,6 +8,7 @@ pub struct RoleFilter {
     pub role_name: Option<String>,
     pub description: Option<String>,
     pub permissions: Option<String>,
+    pub require_two_factor: Option<bool>,
     pub created_at_min: Option<NaiveDateTime>,
     pub created_at_max: Option<NaiveDateTime>,
     pub updated_at_min: Option<NaiveDateTime>,
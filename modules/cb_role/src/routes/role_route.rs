@@ -1,12 +1,39 @@
 use crate::controllers::role_controller::RoleController;
-use crate::models::role::{PostRole, PatchRole};
+use crate::controllers::role_permission_controller;
+use crate::models::role::{PatchRole, PostRole, TwoFactorPolicy};
 use crate::models::role_filter::RoleFilter;
+use crab_rocket_auth::guards::authorized::Authorized;
+use crab_rocket_auth::permission;
 use obj_traits::controller::controller_crud::ControllerCRUD;
 use obj_traits::request::pagination_request_param::{PaginationParam, PaginationParamTrait};
 use obj_traits::request::request_param::RequestParam;
 use rocket::http::Status;
+use rocket::response::status;
 use rocket::serde::json::Json;
-use rocket::{delete, get, options, patch, post};
+use rocket::{delete, get, options, patch, post, put};
+use serde_json::json;
+
+permission!(pub RoleCreate, "role", "create");
+permission!(pub RoleUpdate, "role", "update");
+permission!(pub RoleDelete, "role", "delete");
+
+fn to_response(
+    status: i32,
+    message: String,
+    data: impl rocket::serde::Serialize,
+) -> status::Custom<Json<serde_json::Value>> {
+    let response = json!(
+        {
+            "status": status,
+            "message": message,
+            "body":{
+                "data":data
+            }
+        }
+    );
+    let code = Status::from_code(status as u16).unwrap_or(Status::InternalServerError);
+    status::Custom(code, Json(response))
+}
 
 /// # Note
 /// 若业务逻辑复杂则启用controller层
@@ -17,13 +44,7 @@ use rocket::{delete, get, options, patch, post};
 /// 例如PUT/items/1的意思是替換/items/1，如果已經存在就替換，沒有就新增。
 /// PUT必須包含items/1的所有屬性資料
 #[get("/role?<limit>&<offset>")]
-pub fn get_roles(mut limit: Option<i32>, mut offset: Option<i32>) -> Json<serde_json::Value> {
-    if limit.is_none() {
-        limit = Some(10);
-    };
-    if offset.is_none() {
-        offset = Some(0);
-    };
+pub fn get_roles(limit: Option<i32>, offset: Option<i32>) -> Json<serde_json::Value> {
     let params = RequestParam::new(PaginationParam::new(limit, offset), None);
     println!("{:?}", params);
     crab_rocket_schema::update_reload::update_reload_count();
@@ -53,7 +74,10 @@ pub fn get_role_by_id(id: i32) -> Json<serde_json::Value> {
 }
 
 #[post("/role", data = "<role>")]
-pub fn insert_single_role(role: Json<PostRole>) -> Json<serde_json::Value> {
+pub fn insert_single_role(
+    _auth: Authorized<RoleCreate>,
+    role: Json<PostRole>,
+) -> Json<serde_json::Value> {
     let mut obj: PostRole = role.into_inner();
 
     let resp = RoleController::add_single(&mut obj).unwrap();
@@ -62,19 +86,62 @@ pub fn insert_single_role(role: Json<PostRole>) -> Json<serde_json::Value> {
 }
 
 #[delete("/role/<id>")]
-pub fn delete_role_by_id(id: i32) -> Json<serde_json::Value> {
+pub fn delete_role_by_id(_auth: Authorized<RoleDelete>, id: i32) -> Json<serde_json::Value> {
     let resp = RoleController::delete_by_id(id).unwrap();
     let json_value = serde_json::to_value(&resp).unwrap();
     Json(serde_json::from_value(json_value).unwrap())
 }
 
 #[patch("/role/<id>", data = "<role>")]
-pub fn update_role_by_id(id: i32, role: Json<PatchRole>) -> Json<serde_json::Value> {
+pub fn update_role_by_id(
+    _auth: Authorized<RoleUpdate>,
+    id: i32,
+    role: Json<PatchRole>,
+) -> Json<serde_json::Value> {
     let resp = RoleController::update_by_id(id, &role).unwrap();
     let json_value = serde_json::to_value(&resp).unwrap();
     Json(serde_json::from_value(json_value).unwrap())
 }
 
+#[get("/role/<id>/permission")]
+pub fn get_permissions_of_role(id: i32) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, permissions) = role_permission_controller::get_permissions_of_role(id);
+    to_response(status, message, permissions)
+}
+
+/// Idempotent, granting a permission the role already has answers `200` as well.
+#[put("/role/<id>/permission/<permission_id>")]
+pub fn grant_permission_to_role(
+    _auth: Authorized<RoleUpdate>,
+    id: i32,
+    permission_id: i32,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message) = role_permission_controller::grant(id, permission_id);
+    to_response(status, message, ())
+}
+
+#[delete("/role/<id>/permission/<permission_id>")]
+pub fn revoke_permission_from_role(
+    _auth: Authorized<RoleUpdate>,
+    id: i32,
+    permission_id: i32,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message) = role_permission_controller::revoke(id, permission_id);
+    to_response(status, message, ())
+}
+
+/// Members of a role that requires it get no permissions until they log in with TOTP.
+#[put("/role/<id>/two-factor", data = "<policy>")]
+pub fn set_role_two_factor_policy(
+    _auth: Authorized<RoleUpdate>,
+    id: i32,
+    policy: Json<TwoFactorPolicy>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (status, message, role) =
+        role_permission_controller::set_two_factor_policy(id, policy.required);
+    to_response(status, message, role)
+}
+
 #[get("/")]
 pub fn index() -> &'static str {
     "hello world!"
@@ -0,0 +1,75 @@
+use crab_rocket_permission::mappers::permission_mapper::PermissionMapper;
+use crab_rocket_permission::models::permission::Permission;
+use crab_rocket_schema::establish_pg_connection;
+use diesel::prelude::*;
+use diesel::result::Error;
+use obj_traits::mapper::mapper_crud::MapperCRUD;
+
+use crate::mappers::role_mapper::RoleMapper;
+use crate::mappers::role_permission_mapper::RolePermissionMapper;
+use crate::models::role::Role;
+
+/// Outcome of granting or revoking, `Unchanged` when there was nothing to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrantChange {
+    Changed,
+    Unchanged,
+    RoleNotFound,
+    PermissionNotFound,
+}
+
+pub struct RolePermissionService {}
+
+impl RolePermissionService {
+    /// `None` when the role does not exist.
+    pub fn get_permissions_of_role(
+        role_id: i32,
+    ) -> Result<Option<Vec<Permission>>, Box<dyn std::error::Error>> {
+        let mut conn = establish_pg_connection()?;
+        if RoleMapper::get_by_id(&mut conn, role_id).optional()?.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(RolePermissionMapper::get_permissions_of_role(&mut conn, role_id)?))
+    }
+
+    pub fn grant(
+        role_id: i32,
+        permission_id: i32,
+    ) -> Result<GrantChange, Box<dyn std::error::Error>> {
+        Self::change(role_id, permission_id, RolePermissionMapper::grant)
+    }
+
+    pub fn revoke(
+        role_id: i32,
+        permission_id: i32,
+    ) -> Result<GrantChange, Box<dyn std::error::Error>> {
+        Self::change(role_id, permission_id, RolePermissionMapper::revoke)
+    }
+
+    /// Whether members need a second factor, `None` when the role does not exist.
+    pub fn set_two_factor_policy(
+        role_id: i32,
+        required: bool,
+    ) -> Result<Option<Role>, Box<dyn std::error::Error>> {
+        let mut conn = establish_pg_connection()?;
+        Ok(RoleMapper::set_require_two_factor(&mut conn, role_id, required).optional()?)
+    }
+
+    fn change(
+        role_id: i32,
+        permission_id: i32,
+        apply: fn(&mut PgConnection, i32, i32) -> Result<bool, Error>,
+    ) -> Result<GrantChange, Box<dyn std::error::Error>> {
+        let mut conn = establish_pg_connection()?;
+        if RoleMapper::get_by_id(&mut conn, role_id).optional()?.is_none() {
+            return Ok(GrantChange::RoleNotFound);
+        }
+        if PermissionMapper::get_by_id(&mut conn, permission_id).optional()?.is_none() {
+            return Ok(GrantChange::PermissionNotFound);
+        }
+        Ok(match apply(&mut conn, role_id, permission_id)? {
+            true => GrantChange::Changed,
+            false => GrantChange::Unchanged,
+        })
+    }
+}
@@ -1,2 +1,2 @@
 -- This file should undo anything in `up.sql`
-DROP TABLE users;
\ No newline at end of file
+DROP TABLE user_table;
@@ -1,2 +1,4 @@
 -- This file should undo anything in `up.sql`
-DROP TABLE employee_table;
\ No newline at end of file
+ALTER TABLE "department_table" DROP CONSTRAINT "manager_id";
+ALTER TABLE "department_table" DROP CONSTRAINT "parent_department_id";
+DROP TABLE employee_table;
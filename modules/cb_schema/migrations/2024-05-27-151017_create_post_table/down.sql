@@ -1,2 +1,2 @@
 -- This file should undo anything in `up.sql`
-DROP TABLE posts;
\ No newline at end of file
+DROP TABLE post_table;
@@ -1,2 +1,2 @@
 -- This file should undo anything in `up.sql`
-DROP TABLE tasks;
\ No newline at end of file
+DROP TABLE task_table;
@@ -1 +1,5 @@
 -- This file should undo anything in `up.sql`
+DROP TRIGGER IF EXISTS fill_trigger ON employee_table;
+DROP FUNCTION IF EXISTS fill();
+DROP TRIGGER IF EXISTS fill_username_trigger ON post_table;
+DROP FUNCTION IF EXISTS fill_username();
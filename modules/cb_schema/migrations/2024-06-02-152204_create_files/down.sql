@@ -1 +1,2 @@
 -- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS file_table;
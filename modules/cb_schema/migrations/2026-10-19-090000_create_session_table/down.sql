@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS refresh_token_table;
+DROP TABLE IF EXISTS session_table;
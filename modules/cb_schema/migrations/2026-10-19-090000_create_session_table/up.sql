@@ -0,0 +1,25 @@
+-- Your SQL goes here
+-- A login session, one per device. Its refresh tokens rotate on every use.
+CREATE TABLE IF NOT EXISTS session_table (
+    session_id UUID PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES user_table(user_id) ON DELETE CASCADE,
+    device_name VARCHAR(255),
+    user_agent TEXT,
+    ip_address VARCHAR(64),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_used_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at TIMESTAMP NOT NULL,
+    revoked_at TIMESTAMP,
+    revoked_reason VARCHAR(255)
+);
+CREATE INDEX IF NOT EXISTS session_table_user_id_idx ON session_table (user_id);
+
+-- Only hashes are stored. A token with `used_at` set was rotated already, seeing it again
+-- means it was stolen.
+CREATE TABLE IF NOT EXISTS refresh_token_table (
+    token_hash VARCHAR(64) PRIMARY KEY,
+    session_id UUID NOT NULL REFERENCES session_table(session_id) ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    used_at TIMESTAMP
+);
+CREATE INDEX IF NOT EXISTS refresh_token_table_session_id_idx ON refresh_token_table (session_id);
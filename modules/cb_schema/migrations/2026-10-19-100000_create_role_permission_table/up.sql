@@ -0,0 +1,9 @@
+-- Your SQL goes here
+-- Which permissions a role grants, replaces the free-form `role_table.permissions`.
+CREATE TABLE IF NOT EXISTS role_permission_table (
+    role_id INTEGER NOT NULL REFERENCES role_table(role_id) ON DELETE CASCADE,
+    permission_id INTEGER NOT NULL REFERENCES permission_table(permission_id) ON DELETE CASCADE,
+    granted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (role_id, permission_id)
+);
+CREATE INDEX IF NOT EXISTS role_permission_table_permission_id_idx ON role_permission_table (permission_id);
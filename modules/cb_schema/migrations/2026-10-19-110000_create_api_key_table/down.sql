@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS api_key_scope_table;
+DROP TABLE IF EXISTS api_key_table;
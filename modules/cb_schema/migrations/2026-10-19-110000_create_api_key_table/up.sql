@@ -0,0 +1,22 @@
+-- Your SQL goes here
+-- Long-lived credentials for scripts and devices. Only the hash of a key is stored, the key
+-- itself is shown once when it is created.
+CREATE TABLE IF NOT EXISTS api_key_table (
+    api_key_id UUID PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES user_table(user_id) ON DELETE CASCADE,
+    name VARCHAR(255) NOT NULL,
+    key_prefix VARCHAR(16) NOT NULL,
+    key_hash VARCHAR(64) NOT NULL UNIQUE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at TIMESTAMP,
+    last_used_at TIMESTAMP,
+    revoked_at TIMESTAMP
+);
+CREATE INDEX IF NOT EXISTS api_key_table_user_id_idx ON api_key_table (user_id);
+
+-- The permissions a key may use, on top of what the role of its user grants.
+CREATE TABLE IF NOT EXISTS api_key_scope_table (
+    api_key_id UUID NOT NULL REFERENCES api_key_table(api_key_id) ON DELETE CASCADE,
+    permission_id INTEGER NOT NULL REFERENCES permission_table(permission_id) ON DELETE CASCADE,
+    PRIMARY KEY (api_key_id, permission_id)
+);
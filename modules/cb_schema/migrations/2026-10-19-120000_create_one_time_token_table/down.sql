@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS email_verification_table;
+DROP TABLE IF EXISTS one_time_token_table;
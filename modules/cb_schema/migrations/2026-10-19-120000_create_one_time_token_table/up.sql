@@ -0,0 +1,19 @@
+-- Your SQL goes here
+-- Tokens mailed for email verification and password resets. The token itself is a signed
+-- JWT, this table only makes it single use.
+CREATE TABLE IF NOT EXISTS one_time_token_table (
+    token_id UUID PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES user_table(user_id) ON DELETE CASCADE,
+    purpose VARCHAR(32) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at TIMESTAMP NOT NULL,
+    used_at TIMESTAMP
+);
+CREATE INDEX IF NOT EXISTS one_time_token_table_user_id_idx ON one_time_token_table (user_id);
+
+-- The address a user proved to own. Changing `user_table.email` makes it unverified again.
+CREATE TABLE IF NOT EXISTS email_verification_table (
+    user_id INTEGER PRIMARY KEY REFERENCES user_table(user_id) ON DELETE CASCADE,
+    email VARCHAR(255) NOT NULL,
+    verified_at TIMESTAMP NOT NULL
+);
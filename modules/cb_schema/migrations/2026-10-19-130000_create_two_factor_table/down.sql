@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE session_table DROP COLUMN IF EXISTS two_factor;
+ALTER TABLE role_table DROP COLUMN IF EXISTS require_two_factor;
+DROP TABLE IF EXISTS recovery_code_table;
+DROP TABLE IF EXISTS two_factor_table;
@@ -0,0 +1,25 @@
+-- Your SQL goes here
+-- The TOTP secret of a user. `enabled_at` stays empty until the first code confirmed the
+-- enrollment, `last_used_step` keeps a code from being used twice.
+CREATE TABLE IF NOT EXISTS two_factor_table (
+    user_id INTEGER PRIMARY KEY REFERENCES user_table(user_id) ON DELETE CASCADE,
+    secret VARCHAR(64) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    enabled_at TIMESTAMP,
+    last_used_step BIGINT
+);
+
+-- Single use codes for when the authenticator is lost, stored hashed.
+CREATE TABLE IF NOT EXISTS recovery_code_table (
+    code_hash VARCHAR(64) PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES user_table(user_id) ON DELETE CASCADE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    used_at TIMESTAMP
+);
+CREATE INDEX IF NOT EXISTS recovery_code_table_user_id_idx ON recovery_code_table (user_id);
+
+-- Members of these roles get no permissions until they log in with a second factor.
+ALTER TABLE role_table ADD COLUMN IF NOT EXISTS require_two_factor BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Whether the login of a session was confirmed with a second factor.
+ALTER TABLE session_table ADD COLUMN IF NOT EXISTS two_factor BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS security_event_table;
+DROP TABLE IF EXISTS login_throttle_table;
@@ -0,0 +1,24 @@
+-- Your SQL goes here
+-- Recent failed logins of an account (`scope` 'account', `subject` its user_id) or a client
+-- address (`scope` 'ip'). Logins are refused while `locked_until` lies ahead.
+CREATE TABLE IF NOT EXISTS login_throttle_table (
+    scope VARCHAR(16) NOT NULL,
+    subject VARCHAR(64) NOT NULL,
+    failed_count INTEGER NOT NULL,
+    last_failed_at TIMESTAMP NOT NULL,
+    locked_until TIMESTAMP,
+    PRIMARY KEY (scope, subject)
+);
+
+-- Logins, failures, lockouts and who cleared them. Kept when the user is deleted.
+CREATE TABLE IF NOT EXISTS security_event_table (
+    event_id BIGSERIAL PRIMARY KEY,
+    event_type VARCHAR(32) NOT NULL,
+    user_id INTEGER REFERENCES user_table(user_id) ON DELETE SET NULL,
+    login VARCHAR(255),
+    ip_address VARCHAR(64),
+    detail VARCHAR(255),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+CREATE INDEX IF NOT EXISTS security_event_table_user_id_idx
+    ON security_event_table (user_id, created_at);
@@ -0,0 +1,22 @@
+-- Your SQL goes here
+-- The account at an OpenID Connect provider a user logs in with, `subject` is its `sub`.
+CREATE TABLE IF NOT EXISTS oidc_identity_table (
+    provider VARCHAR(64) NOT NULL,
+    subject VARCHAR(255) NOT NULL,
+    user_id INTEGER NOT NULL REFERENCES user_table(user_id) ON DELETE CASCADE,
+    email VARCHAR(255),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_login_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (provider, subject)
+);
+CREATE INDEX IF NOT EXISTS oidc_identity_table_user_id_idx ON oidc_identity_table (user_id);
+
+-- Logins sent to a provider and not back yet. Only the hash of `state` is kept, the PKCE
+-- `code_verifier` and the `nonce` are checked when the user returns.
+CREATE TABLE IF NOT EXISTS oidc_login_table (
+    state_hash VARCHAR(64) PRIMARY KEY,
+    provider VARCHAR(64) NOT NULL,
+    code_verifier VARCHAR(128) NOT NULL,
+    nonce VARCHAR(64) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
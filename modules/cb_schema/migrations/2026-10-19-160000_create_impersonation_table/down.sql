@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE security_event_table DROP COLUMN IF EXISTS actor_user_id;
+DROP TABLE IF EXISTS impersonation_table;
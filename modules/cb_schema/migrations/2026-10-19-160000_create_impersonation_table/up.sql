@@ -0,0 +1,21 @@
+-- Your SQL goes here
+-- Support staff acting as another user, one row per impersonation token. Kept when either
+-- user is deleted.
+CREATE TABLE IF NOT EXISTS impersonation_table (
+    impersonation_id UUID PRIMARY KEY,
+    actor_user_id INTEGER REFERENCES user_table(user_id) ON DELETE SET NULL,
+    target_user_id INTEGER REFERENCES user_table(user_id) ON DELETE SET NULL,
+    reason VARCHAR(255) NOT NULL,
+    ip_address VARCHAR(64),
+    started_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    expires_at TIMESTAMP NOT NULL,
+    ended_at TIMESTAMP
+);
+CREATE INDEX IF NOT EXISTS impersonation_table_target_user_id_idx
+    ON impersonation_table (target_user_id, started_at);
+CREATE INDEX IF NOT EXISTS impersonation_table_actor_user_id_idx
+    ON impersonation_table (actor_user_id, started_at);
+
+-- Who really acted when it differs from `user_id`, e.g. during an impersonation.
+ALTER TABLE security_event_table
+    ADD COLUMN IF NOT EXISTS actor_user_id INTEGER REFERENCES user_table(user_id) ON DELETE SET NULL;
@@ -0,0 +1,22 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS department_table_parent_department_id_idx;
+DROP INDEX IF EXISTS employee_table_department_id_idx;
+
+ALTER TABLE employee_table
+  DROP CONSTRAINT department_id,
+  ADD CONSTRAINT department_id FOREIGN KEY (department_id)
+    REFERENCES department_table (department_id) ON DELETE CASCADE ON UPDATE CASCADE;
+
+ALTER TABLE department_table
+  DROP CONSTRAINT department_not_own_parent,
+  DROP CONSTRAINT parent_department_id,
+  ADD CONSTRAINT parent_department_id FOREIGN KEY (parent_department_id)
+    REFERENCES department_table (department_id) ON DELETE CASCADE ON UPDATE CASCADE,
+  DROP CONSTRAINT manager_id,
+  ADD CONSTRAINT manager_id FOREIGN KEY (manager_id)
+    REFERENCES employee_table (employee_id) ON DELETE CASCADE ON UPDATE CASCADE;
+
+ALTER TABLE department_table ADD COLUMN number_of_employees int4 DEFAULT 0;
+UPDATE department_table d
+SET number_of_employees =
+  (SELECT COUNT(*) FROM employee_table e WHERE e.department_id = d.department_id);
@@ -0,0 +1,22 @@
+-- Your SQL goes here
+-- The head count is counted from employee_table whenever a department is read.
+ALTER TABLE department_table DROP COLUMN number_of_employees;
+
+-- Losing its manager no longer deletes a department, and a department can only be deleted
+-- once it holds neither employees nor sub-departments.
+ALTER TABLE department_table
+  DROP CONSTRAINT manager_id,
+  ADD CONSTRAINT manager_id FOREIGN KEY (manager_id)
+    REFERENCES employee_table (employee_id) ON DELETE SET NULL ON UPDATE CASCADE,
+  DROP CONSTRAINT parent_department_id,
+  ADD CONSTRAINT parent_department_id FOREIGN KEY (parent_department_id)
+    REFERENCES department_table (department_id) ON DELETE RESTRICT ON UPDATE CASCADE,
+  ADD CONSTRAINT department_not_own_parent CHECK (parent_department_id <> department_id);
+
+ALTER TABLE employee_table
+  DROP CONSTRAINT department_id,
+  ADD CONSTRAINT department_id FOREIGN KEY (department_id)
+    REFERENCES department_table (department_id) ON DELETE RESTRICT ON UPDATE CASCADE;
+
+CREATE INDEX employee_table_department_id_idx ON employee_table (department_id);
+CREATE INDEX department_table_parent_department_id_idx ON department_table (parent_department_id);
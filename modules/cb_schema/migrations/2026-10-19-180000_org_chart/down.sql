@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX employee_table_manager_id_idx;
+
+ALTER TABLE employee_table
+  DROP CONSTRAINT employee_not_own_manager,
+  DROP CONSTRAINT manager_id,
+  ADD CONSTRAINT manager_id FOREIGN KEY (manager_id)
+    REFERENCES employee_table (employee_id) ON DELETE CASCADE ON UPDATE CASCADE;
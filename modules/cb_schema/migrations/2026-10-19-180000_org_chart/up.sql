@@ -0,0 +1,10 @@
+-- Your SQL goes here
+-- Reporting lines are walked with recursive queries over manager_id. Deleting a manager
+-- leaves their reports without one instead of deleting the whole subtree.
+ALTER TABLE employee_table
+  DROP CONSTRAINT manager_id,
+  ADD CONSTRAINT manager_id FOREIGN KEY (manager_id)
+    REFERENCES employee_table (employee_id) ON DELETE SET NULL ON UPDATE CASCADE,
+  ADD CONSTRAINT employee_not_own_manager CHECK (manager_id <> employee_id);
+
+CREATE INDEX employee_table_manager_id_idx ON employee_table (manager_id);
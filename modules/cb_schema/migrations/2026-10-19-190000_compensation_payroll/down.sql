@@ -0,0 +1,15 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE payslip_line_table;
+DROP TABLE payslip_table;
+DROP TABLE payroll_run_table;
+
+ALTER TABLE employee_table ADD COLUMN salary INT4 DEFAULT 0;
+UPDATE employee_table e SET salary = (
+  SELECT c.amount FROM compensation_table c
+  WHERE c.employee_id = e.employee_id AND c.component = 'salary'
+    AND c.effective_from <= CURRENT_DATE
+    AND (c.effective_to IS NULL OR c.effective_to >= CURRENT_DATE)
+  ORDER BY c.effective_from DESC LIMIT 1
+);
+
+DROP TABLE compensation_table;
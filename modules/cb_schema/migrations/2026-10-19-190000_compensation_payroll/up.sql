@@ -0,0 +1,67 @@
+-- Your SQL goes here
+-- Pay is a history of effective-dated components instead of a single salary column. Amounts
+-- are per full pay period.
+CREATE TABLE compensation_table (
+  compensation_id SERIAL PRIMARY KEY,
+  employee_id INT4 NOT NULL REFERENCES employee_table (employee_id) ON DELETE CASCADE,
+  component VARCHAR(16) NOT NULL CHECK (component IN ('salary', 'allowance', 'deduction')),
+  name VARCHAR(255) NOT NULL,
+  amount INT4 NOT NULL CHECK (amount >= 0),
+  effective_from DATE NOT NULL,
+  effective_to DATE CHECK (effective_to >= effective_from),
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX compensation_table_employee_id_idx
+  ON compensation_table (employee_id, component, effective_from);
+
+INSERT INTO compensation_table (employee_id, component, name, amount, effective_from)
+SELECT employee_id, 'salary', 'Base salary', salary,
+       COALESCE(hire_date::date, last_update::date, CURRENT_DATE)
+FROM employee_table
+WHERE salary IS NOT NULL AND salary > 0;
+
+ALTER TABLE employee_table DROP COLUMN salary;
+
+CREATE TABLE payroll_run_table (
+  payroll_run_id SERIAL PRIMARY KEY,
+  period_start DATE NOT NULL,
+  period_end DATE NOT NULL CHECK (period_end >= period_start),
+  employee_count INT4 NOT NULL DEFAULT 0,
+  total_gross INT8 NOT NULL DEFAULT 0,
+  total_net INT8 NOT NULL DEFAULT 0,
+  created_by INT4 REFERENCES user_table (user_id) ON DELETE SET NULL,
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Payslips keep the name and department the employee had when they were paid, and outlive
+-- the employee.
+CREATE TABLE payslip_table (
+  payslip_id SERIAL PRIMARY KEY,
+  payroll_run_id INT4 NOT NULL REFERENCES payroll_run_table (payroll_run_id) ON DELETE CASCADE,
+  employee_id INT4 REFERENCES employee_table (employee_id) ON DELETE SET NULL,
+  employee_name VARCHAR(255) NOT NULL,
+  department_id INT4,
+  period_start DATE NOT NULL,
+  period_end DATE NOT NULL,
+  salary INT8 NOT NULL,
+  allowances INT8 NOT NULL,
+  deductions INT8 NOT NULL,
+  gross INT8 NOT NULL,
+  net INT8 NOT NULL,
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX payslip_table_payroll_run_id_idx ON payslip_table (payroll_run_id);
+CREATE INDEX payslip_table_employee_id_idx ON payslip_table (employee_id);
+
+CREATE TABLE payslip_line_table (
+  payslip_line_id SERIAL PRIMARY KEY,
+  payslip_id INT4 NOT NULL REFERENCES payslip_table (payslip_id) ON DELETE CASCADE,
+  compensation_id INT4 REFERENCES compensation_table (compensation_id) ON DELETE SET NULL,
+  component VARCHAR(16) NOT NULL,
+  name VARCHAR(255) NOT NULL,
+  amount INT8 NOT NULL
+);
+
+CREATE INDEX payslip_line_table_payslip_id_idx ON payslip_line_table (payslip_id);
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE attendance_table;
+DROP TABLE leave_request_table;
+DROP TABLE leave_balance_table;
+DROP TABLE leave_type_table;
@@ -0,0 +1,58 @@
+-- Your SQL goes here
+-- Leave is counted in working days, whole days only.
+CREATE TABLE leave_type_table (
+  leave_type_id SERIAL PRIMARY KEY,
+  leave_type_name VARCHAR(255) NOT NULL UNIQUE,
+  yearly_days INT4 NOT NULL CHECK (yearly_days >= 0),
+  max_carry_over INT4 NOT NULL DEFAULT 0 CHECK (max_carry_over >= 0),
+  paid BOOLEAN NOT NULL DEFAULT TRUE,
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT INTO leave_type_table (leave_type_name, yearly_days, max_carry_over, paid)
+VALUES ('Annual leave', 20, 5, TRUE), ('Sick leave', 10, 0, TRUE);
+
+-- What an employee may take of a leave type in a year, days used are counted from the
+-- approved requests.
+CREATE TABLE leave_balance_table (
+  leave_balance_id SERIAL PRIMARY KEY,
+  employee_id INT4 NOT NULL REFERENCES employee_table (employee_id) ON DELETE CASCADE,
+  leave_type_id INT4 NOT NULL REFERENCES leave_type_table (leave_type_id) ON DELETE CASCADE,
+  year INT4 NOT NULL,
+  accrued INT4 NOT NULL CHECK (accrued >= 0),
+  carried_over INT4 NOT NULL DEFAULT 0 CHECK (carried_over >= 0),
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  CONSTRAINT leave_balance_employee_type_year_key UNIQUE (employee_id, leave_type_id, year)
+);
+
+CREATE TABLE leave_request_table (
+  leave_request_id SERIAL PRIMARY KEY,
+  employee_id INT4 NOT NULL REFERENCES employee_table (employee_id) ON DELETE CASCADE,
+  leave_type_id INT4 NOT NULL REFERENCES leave_type_table (leave_type_id),
+  start_date DATE NOT NULL,
+  end_date DATE NOT NULL CHECK (end_date >= start_date),
+  days INT4 NOT NULL CHECK (days > 0),
+  reason TEXT,
+  status VARCHAR(16) NOT NULL DEFAULT 'pending'
+    CHECK (status IN ('pending', 'approved', 'rejected', 'cancelled')),
+  approver_id INT4 REFERENCES employee_table (employee_id) ON DELETE SET NULL,
+  decision_note TEXT,
+  decided_at TIMESTAMP,
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX leave_request_table_employee_id_idx
+  ON leave_request_table (employee_id, start_date);
+
+CREATE TABLE attendance_table (
+  attendance_id SERIAL PRIMARY KEY,
+  employee_id INT4 NOT NULL REFERENCES employee_table (employee_id) ON DELETE CASCADE,
+  clock_in TIMESTAMP NOT NULL,
+  clock_out TIMESTAMP CHECK (clock_out >= clock_in)
+);
+
+CREATE INDEX attendance_table_employee_id_idx ON attendance_table (employee_id, clock_in);
+
+-- Nobody is clocked in twice at the same time.
+CREATE UNIQUE INDEX attendance_table_open_idx
+  ON attendance_table (employee_id) WHERE clock_out IS NULL;
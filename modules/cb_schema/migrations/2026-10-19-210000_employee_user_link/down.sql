@@ -0,0 +1,38 @@
+-- This file should undo anything in `up.sql`
+DROP TRIGGER sync_user_role ON user_table;
+DROP FUNCTION sync_user_role();
+DROP TRIGGER sync_employee_role ON employee_table;
+DROP FUNCTION sync_employee_role();
+
+CREATE OR REPLACE FUNCTION fill()
+RETURNS TRIGGER AS $$
+BEGIN
+  SELECT role_name INTO NEW.role_name FROM role_table WHERE role_id = NEW.role_id;
+  RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER fill_trigger
+BEFORE INSERT ON employee_table
+FOR EACH ROW EXECUTE FUNCTION fill();
+
+CREATE OR REPLACE FUNCTION update_role_name()
+RETURNS TRIGGER AS $$
+BEGIN
+  SELECT role_name INTO NEW.role_name FROM role_table WHERE role_id = NEW.role_id;
+  RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER set_role_name
+BEFORE INSERT OR UPDATE ON employee_table
+FOR EACH ROW EXECUTE FUNCTION update_role_name();
+
+ALTER TABLE employee_table DROP CONSTRAINT role_id, DROP CONSTRAINT role_name;
+ALTER TABLE employee_table
+  ADD CONSTRAINT role_id FOREIGN KEY (role_id) REFERENCES role_table (role_id)
+    ON UPDATE CASCADE ON DELETE CASCADE,
+  ADD CONSTRAINT role_name FOREIGN KEY (role_name) REFERENCES role_table (role_name)
+    ON UPDATE CASCADE ON DELETE CASCADE;
+
+ALTER TABLE employee_table DROP COLUMN user_id;
@@ -0,0 +1,58 @@
+-- Your SQL goes here
+-- An employee logs in as at most one user and a user is at most one employee.
+ALTER TABLE employee_table
+  ADD COLUMN user_id INT4 UNIQUE REFERENCES user_table (user_id) ON DELETE SET NULL;
+
+-- Deleting a role leaves its employees without one instead of deleting them.
+ALTER TABLE employee_table DROP CONSTRAINT role_id, DROP CONSTRAINT role_name;
+ALTER TABLE employee_table
+  ADD CONSTRAINT role_id FOREIGN KEY (role_id) REFERENCES role_table (role_id)
+    ON UPDATE CASCADE ON DELETE SET NULL,
+  ADD CONSTRAINT role_name FOREIGN KEY (role_name) REFERENCES role_table (role_name)
+    ON UPDATE CASCADE ON DELETE SET NULL;
+
+-- `fill_trigger` only ran on insert, `set_role_name` ignored the user. One trigger now keeps
+-- both columns right on every write: a linked employee has the role of their user and
+-- `role_name` always names `role_id`. Renaming a role reaches `role_name` through its
+-- foreign key.
+DROP TRIGGER fill_trigger ON employee_table;
+DROP TRIGGER set_role_name ON employee_table;
+DROP FUNCTION fill();
+DROP FUNCTION update_role_name();
+
+CREATE FUNCTION sync_employee_role()
+RETURNS TRIGGER AS $$
+BEGIN
+  IF NEW.user_id IS NOT NULL THEN
+    SELECT role_id INTO NEW.role_id FROM user_table WHERE user_id = NEW.user_id;
+  END IF;
+  NEW.role_name := (SELECT role_name FROM role_table WHERE role_id = NEW.role_id);
+  RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER sync_employee_role
+BEFORE INSERT OR UPDATE ON employee_table
+FOR EACH ROW EXECUTE FUNCTION sync_employee_role();
+
+-- A new role of a user is the new role of their employee.
+CREATE FUNCTION sync_user_role()
+RETURNS TRIGGER AS $$
+BEGIN
+  UPDATE employee_table SET role_id = NEW.role_id WHERE user_id = NEW.user_id;
+  RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER sync_user_role
+AFTER UPDATE OF role_id ON user_table
+FOR EACH ROW WHEN (OLD.role_id IS DISTINCT FROM NEW.role_id)
+EXECUTE FUNCTION sync_user_role();
+
+-- Link the employees sharing their email with exactly one user, and that user with no other
+-- employee. The trigger takes over the user's role.
+UPDATE employee_table e SET user_id = u.user_id
+FROM user_table u
+WHERE lower(u.email) = lower(e.email)
+  AND (SELECT COUNT(*) FROM user_table o WHERE lower(o.email) = lower(e.email)) = 1
+  AND (SELECT COUNT(*) FROM employee_table o WHERE lower(o.email) = lower(e.email)) = 1;
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE review_score_table;
+DROP TABLE review_table;
+DROP TABLE review_criterion_table;
+DROP TABLE review_cycle_table;
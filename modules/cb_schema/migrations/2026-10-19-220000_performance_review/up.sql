@@ -0,0 +1,48 @@
+-- Your SQL goes here
+-- A review period, its criteria make up the review form everyone of the cycle fills in.
+CREATE TABLE review_cycle_table (
+  review_cycle_id SERIAL PRIMARY KEY,
+  review_cycle_name VARCHAR(255) NOT NULL UNIQUE,
+  start_date DATE NOT NULL,
+  end_date DATE NOT NULL CHECK (end_date >= start_date),
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- A criterion counts `weight` over the sum of the weights of its cycle.
+CREATE TABLE review_criterion_table (
+  review_criterion_id SERIAL PRIMARY KEY,
+  review_cycle_id INT4 NOT NULL REFERENCES review_cycle_table (review_cycle_id) ON DELETE CASCADE,
+  criterion_name VARCHAR(255) NOT NULL,
+  description TEXT,
+  weight INT4 NOT NULL CHECK (weight > 0),
+  CONSTRAINT review_criterion_cycle_name_key UNIQUE (review_cycle_id, criterion_name)
+);
+
+-- One self and one manager assessment per employee and cycle, written by `reviewer_id`.
+CREATE TABLE review_table (
+  review_id SERIAL PRIMARY KEY,
+  review_cycle_id INT4 NOT NULL REFERENCES review_cycle_table (review_cycle_id) ON DELETE CASCADE,
+  employee_id INT4 NOT NULL REFERENCES employee_table (employee_id) ON DELETE CASCADE,
+  reviewer_id INT4 REFERENCES employee_table (employee_id) ON DELETE SET NULL,
+  kind VARCHAR(16) NOT NULL CHECK (kind IN ('self', 'manager')),
+  status VARCHAR(16) NOT NULL DEFAULT 'draft'
+    CHECK (status IN ('draft', 'submitted', 'acknowledged')),
+  comment TEXT,
+  submitted_at TIMESTAMP,
+  acknowledged_at TIMESTAMP,
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  CONSTRAINT review_cycle_employee_kind_key UNIQUE (review_cycle_id, employee_id, kind)
+);
+
+CREATE INDEX review_table_reviewer_id_idx ON review_table (reviewer_id, status);
+
+-- Scores from 1 to 5.
+CREATE TABLE review_score_table (
+  review_id INT4 NOT NULL REFERENCES review_table (review_id) ON DELETE CASCADE,
+  review_criterion_id INT4 NOT NULL
+    REFERENCES review_criterion_table (review_criterion_id) ON DELETE CASCADE,
+  score INT4 NOT NULL CHECK (score BETWEEN 1 AND 5),
+  comment TEXT,
+  PRIMARY KEY (review_id, review_criterion_id)
+);
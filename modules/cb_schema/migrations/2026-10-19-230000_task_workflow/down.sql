@@ -0,0 +1,13 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE task_table
+  DROP COLUMN status_id,
+  DROP COLUMN priority,
+  DROP COLUMN start_date,
+  DROP COLUMN due_date,
+  DROP COLUMN assignee_id,
+  DROP COLUMN labels,
+  DROP COLUMN completed_at;
+
+DROP FUNCTION initial_task_status();
+DROP TABLE task_transition_table;
+DROP TABLE task_status_table;
@@ -0,0 +1,52 @@
+-- Your SQL goes here
+-- The statuses a task goes through, in the order of `position`. A task in a `closed` status
+-- is done.
+CREATE TABLE task_status_table (
+  task_status_id SERIAL PRIMARY KEY,
+  status_name VARCHAR(64) NOT NULL UNIQUE,
+  position INT4 NOT NULL DEFAULT 0,
+  closed BOOLEAN NOT NULL DEFAULT FALSE,
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+INSERT INTO task_status_table (status_name, position, closed)
+VALUES ('To do', 0, FALSE), ('In progress', 1, FALSE), ('Done', 2, TRUE);
+
+-- The moves the workflow allows between statuses.
+CREATE TABLE task_transition_table (
+  from_status_id INT4 NOT NULL REFERENCES task_status_table (task_status_id) ON DELETE CASCADE,
+  to_status_id INT4 NOT NULL REFERENCES task_status_table (task_status_id) ON DELETE CASCADE,
+  PRIMARY KEY (from_status_id, to_status_id),
+  CHECK (from_status_id <> to_status_id)
+);
+
+INSERT INTO task_transition_table (from_status_id, to_status_id)
+SELECT f.task_status_id, t.task_status_id
+FROM task_status_table f, task_status_table t
+WHERE (f.status_name, t.status_name) IN (
+  ('To do', 'In progress'), ('To do', 'Done'),
+  ('In progress', 'To do'), ('In progress', 'Done'),
+  ('Done', 'In progress')
+);
+
+-- New tasks start in the first status of the workflow.
+CREATE FUNCTION initial_task_status() RETURNS INT4 AS $$
+  SELECT task_status_id FROM task_status_table ORDER BY position, task_status_id LIMIT 1;
+$$ LANGUAGE SQL STABLE;
+
+-- `priority` goes from 1 (low) to 4 (urgent).
+ALTER TABLE task_table
+  ADD COLUMN status_id INT4 NOT NULL DEFAULT initial_task_status()
+    REFERENCES task_status_table (task_status_id),
+  ADD COLUMN priority INT4 NOT NULL DEFAULT 2 CHECK (priority BETWEEN 1 AND 4),
+  ADD COLUMN start_date DATE,
+  ADD COLUMN due_date DATE,
+  ADD COLUMN assignee_id INT4 REFERENCES user_table (user_id) ON DELETE SET NULL,
+  ADD COLUMN labels TEXT[] NOT NULL DEFAULT '{}',
+  ADD COLUMN completed_at TIMESTAMP,
+  ADD CONSTRAINT task_table_due_date_check CHECK (due_date >= start_date);
+
+CREATE INDEX task_table_status_id_idx ON task_table (status_id);
+CREATE INDEX task_table_assignee_id_idx ON task_table (assignee_id);
+CREATE INDEX task_table_due_date_idx ON task_table (due_date);
+CREATE INDEX task_table_labels_idx ON task_table USING GIN (labels);
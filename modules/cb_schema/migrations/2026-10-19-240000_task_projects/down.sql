@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE task_dependency_table;
+
+ALTER TABLE task_table
+  DROP COLUMN parent_id,
+  DROP COLUMN project_id;
+
+DROP TABLE project_table;
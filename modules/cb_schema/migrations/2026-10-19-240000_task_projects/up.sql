@@ -0,0 +1,30 @@
+-- Your SQL goes here
+-- Projects group tasks and belong to a user like tasks do. Deleting a project keeps its
+-- tasks, deleting a task deletes its subtasks and the dependencies it is part of.
+CREATE TABLE project_table (
+  project_id SERIAL PRIMARY KEY,
+  project_name VARCHAR(255) NOT NULL,
+  description TEXT,
+  user_id INT4 REFERENCES user_table (user_id) ON DELETE SET NULL,
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+ALTER TABLE task_table
+  ADD COLUMN project_id INT4 REFERENCES project_table (project_id) ON DELETE SET NULL,
+  ADD COLUMN parent_id INT4 REFERENCES task_table (task_id) ON DELETE CASCADE,
+  ADD CONSTRAINT task_not_own_parent CHECK (parent_id <> task_id);
+
+CREATE INDEX task_table_project_id_idx ON task_table (project_id);
+CREATE INDEX task_table_parent_id_idx ON task_table (parent_id);
+
+-- `task_id` cannot be completed while `blocker_id` is open.
+CREATE TABLE task_dependency_table (
+  task_id INT4 NOT NULL REFERENCES task_table (task_id) ON DELETE CASCADE,
+  blocker_id INT4 NOT NULL REFERENCES task_table (task_id) ON DELETE CASCADE,
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (task_id, blocker_id),
+  CONSTRAINT task_not_own_blocker CHECK (blocker_id <> task_id)
+);
+
+CREATE INDEX task_dependency_table_blocker_id_idx ON task_dependency_table (blocker_id);
@@ -108,4 +108,4 @@
 //             is_sub_query: false,
 //         }
 //     }
-// }
\ No newline at end of file
+// }
@@ -1,24 +1,39 @@
 use colored::Colorize;
 use diesel::{Connection, PgConnection};
-use std::env;
 use dotenv::dotenv;
+use std::cell::RefCell;
+use std::env;
 
 pub mod common;
 pub mod controllers;
 pub mod mappers;
+pub mod migrations;
 pub mod models;
 pub mod routes;
 pub mod schema;
 pub mod update_reload;
 
-pub fn establish_pg_connection() -> Result<PgConnection, diesel::result::ConnectionError> {
-    dotenv().ok();
+thread_local! {
+    static DATABASE_URL_OVERRIDE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
 
-    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
-    println!("{} {}", "Current DATABASE_URL: \t".green(), database_url.to_string().blue());
+/// Points every `establish_pg_connection` on the current thread at `url` instead of
+/// `DATABASE_URL`, `None` restores the default. Lets parallel tests use their own database.
+pub fn set_thread_database_url(url: Option<String>) {
+    DATABASE_URL_OVERRIDE.with(|o| *o.borrow_mut() = url);
+}
+
+/// The thread's override when set, `DATABASE_URL` otherwise.
+pub fn database_url() -> String {
+    DATABASE_URL_OVERRIDE.with(|o| o.borrow().clone()).unwrap_or_else(|| {
+        dotenv().ok();
+        env::var("DATABASE_URL").expect("DATABASE_URL must be set")
+    })
+}
+
+pub fn establish_pg_connection() -> Result<PgConnection, diesel::result::ConnectionError> {
+    let database_url = database_url();
+    eprintln!("{} {}", "Current DATABASE_URL: \t".green(), database_url.to_string().blue());
 
-    match PgConnection::establish(&database_url) {
-        Ok(conn) => Ok(conn),
-        Err(e) => Err(e),
-    }
+    PgConnection::establish(&database_url)
 }
@@ -0,0 +1,72 @@
+use diesel::migration::{MigrationSource, Result as MigrationResult};
+use diesel::pg::Pg;
+use diesel::PgConnection;
+use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+
+/// All migrations under `modules/cb_schema/migrations`, compiled into the binary.
+pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+
+/// One embedded migration and whether the database has it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationStatus {
+    pub version: String,
+    pub name: String,
+    pub applied: bool,
+}
+
+/// Versions of the embedded migrations that have not been applied to the database yet.
+pub fn pending_migrations(conn: &mut PgConnection) -> MigrationResult<Vec<String>> {
+    let pending = conn.pending_migrations(MIGRATIONS)?;
+    Ok(pending.iter().map(|m| m.name().version().to_string()).collect())
+}
+
+/// Every embedded migration in order, flagged with whether it was applied.
+pub fn migration_status(conn: &mut PgConnection) -> MigrationResult<Vec<MigrationStatus>> {
+    let applied: Vec<String> =
+        conn.applied_migrations()?.iter().map(|version| version.to_string()).collect();
+    let migrations = MigrationSource::<Pg>::migrations(&MIGRATIONS)?;
+    Ok(migrations
+        .iter()
+        .map(|m| {
+            let version = m.name().version().to_string();
+            MigrationStatus {
+                applied: applied.contains(&version),
+                name: m.name().to_string(),
+                version,
+            }
+        })
+        .collect())
+}
+
+/// Applies every pending migration, returns the versions that were run.
+pub fn run_pending_migrations(conn: &mut PgConnection) -> MigrationResult<Vec<String>> {
+    let versions = conn.run_pending_migrations(MIGRATIONS)?;
+    Ok(versions.iter().map(|version| version.to_string()).collect())
+}
+
+/// Reverts the last `steps` applied migrations, newest first.
+pub fn revert_migrations(conn: &mut PgConnection, steps: usize) -> MigrationResult<Vec<String>> {
+    let mut reverted = Vec::new();
+    for _ in 0..steps {
+        if conn.applied_migrations()?.is_empty() {
+            break;
+        }
+        reverted.push(conn.revert_last_migration(MIGRATIONS)?.to_string());
+    }
+    Ok(reverted)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{migration_status, pending_migrations};
+    use crate::establish_pg_connection;
+
+    #[test]
+    fn test_migration_status() {
+        if let Ok(mut conn) = establish_pg_connection() {
+            let status = migration_status(&mut conn).unwrap();
+            let pending = pending_migrations(&mut conn).unwrap();
+            assert_eq!(status.iter().filter(|m| !m.applied).count(), pending.len());
+        }
+    }
+}
@@ -1,5 +1,38 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    api_key_scope_table (api_key_id, permission_id) {
+        api_key_id -> Uuid,
+        permission_id -> Int4,
+    }
+}
+
+diesel::table! {
+    api_key_table (api_key_id) {
+        api_key_id -> Uuid,
+        user_id -> Int4,
+        #[max_length = 255]
+        name -> Varchar,
+        #[max_length = 16]
+        key_prefix -> Varchar,
+        #[max_length = 64]
+        key_hash -> Varchar,
+        created_at -> Timestamp,
+        expires_at -> Nullable<Timestamp>,
+        last_used_at -> Nullable<Timestamp>,
+        revoked_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    attendance_table (attendance_id) {
+        attendance_id -> Int4,
+        employee_id -> Int4,
+        clock_in -> Timestamp,
+        clock_out -> Nullable<Timestamp>,
+    }
+}
+
 diesel::table! {
     category_table (category_id) {
         category_id -> Int4,
@@ -12,6 +45,21 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    compensation_table (compensation_id) {
+        compensation_id -> Int4,
+        employee_id -> Int4,
+        #[max_length = 16]
+        component -> Varchar,
+        #[max_length = 255]
+        name -> Varchar,
+        amount -> Int4,
+        effective_from -> Date,
+        effective_to -> Nullable<Date>,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     customer_table (customer_id) {
         customer_id -> Int4,
@@ -38,7 +86,6 @@ diesel::table! {
         #[max_length = 255]
         description -> Nullable<Varchar>,
         budget -> Nullable<Int4>,
-        number_of_employees -> Nullable<Int4>,
         parent_department_id -> Nullable<Int4>,
         #[max_length = 255]
         email -> Nullable<Varchar>,
@@ -55,6 +102,15 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    email_verification_table (user_id) {
+        user_id -> Int4,
+        #[max_length = 255]
+        email -> Varchar,
+        verified_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     employee_table (employee_id) {
         employee_id -> Int4,
@@ -75,7 +131,6 @@ diesel::table! {
         department_id -> Nullable<Int4>,
         #[max_length = 255]
         job_title -> Nullable<Varchar>,
-        salary -> Nullable<Int4>,
         manager_id -> Nullable<Int4>,
         #[max_length = 255]
         address -> Nullable<Varchar>,
@@ -90,6 +145,7 @@ diesel::table! {
         #[max_length = 255]
         role_name -> Nullable<Varchar>,
         role_id -> Nullable<Int4>,
+        user_id -> Nullable<Int4>,
     }
 }
 
@@ -111,6 +167,21 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    impersonation_table (impersonation_id) {
+        impersonation_id -> Uuid,
+        actor_user_id -> Nullable<Int4>,
+        target_user_id -> Nullable<Int4>,
+        #[max_length = 255]
+        reason -> Varchar,
+        #[max_length = 64]
+        ip_address -> Nullable<Varchar>,
+        started_at -> Timestamp,
+        expires_at -> Timestamp,
+        ended_at -> Nullable<Timestamp>,
+    }
+}
+
 diesel::table! {
     inventory_table (inventory_id) {
         inventory_id -> Int4,
@@ -122,6 +193,100 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    leave_balance_table (leave_balance_id) {
+        leave_balance_id -> Int4,
+        employee_id -> Int4,
+        leave_type_id -> Int4,
+        year -> Int4,
+        accrued -> Int4,
+        carried_over -> Int4,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    leave_request_table (leave_request_id) {
+        leave_request_id -> Int4,
+        employee_id -> Int4,
+        leave_type_id -> Int4,
+        start_date -> Date,
+        end_date -> Date,
+        days -> Int4,
+        reason -> Nullable<Text>,
+        #[max_length = 16]
+        status -> Varchar,
+        approver_id -> Nullable<Int4>,
+        decision_note -> Nullable<Text>,
+        decided_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    leave_type_table (leave_type_id) {
+        leave_type_id -> Int4,
+        #[max_length = 255]
+        leave_type_name -> Varchar,
+        yearly_days -> Int4,
+        max_carry_over -> Int4,
+        paid -> Bool,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    login_throttle_table (scope, subject) {
+        #[max_length = 16]
+        scope -> Varchar,
+        #[max_length = 64]
+        subject -> Varchar,
+        failed_count -> Int4,
+        last_failed_at -> Timestamp,
+        locked_until -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    oidc_identity_table (provider, subject) {
+        #[max_length = 64]
+        provider -> Varchar,
+        #[max_length = 255]
+        subject -> Varchar,
+        user_id -> Int4,
+        #[max_length = 255]
+        email -> Nullable<Varchar>,
+        created_at -> Timestamp,
+        last_login_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    oidc_login_table (state_hash) {
+        #[max_length = 64]
+        state_hash -> Varchar,
+        #[max_length = 64]
+        provider -> Varchar,
+        #[max_length = 128]
+        code_verifier -> Varchar,
+        #[max_length = 64]
+        nonce -> Varchar,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    one_time_token_table (token_id) {
+        token_id -> Uuid,
+        user_id -> Int4,
+        #[max_length = 32]
+        purpose -> Varchar,
+        created_at -> Timestamp,
+        expires_at -> Timestamp,
+        used_at -> Nullable<Timestamp>,
+    }
+}
+
 diesel::table! {
     order_table (order_id) {
         order_id -> Int4,
@@ -133,6 +298,51 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    payroll_run_table (payroll_run_id) {
+        payroll_run_id -> Int4,
+        period_start -> Date,
+        period_end -> Date,
+        employee_count -> Int4,
+        total_gross -> Int8,
+        total_net -> Int8,
+        created_by -> Nullable<Int4>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    payslip_line_table (payslip_line_id) {
+        payslip_line_id -> Int4,
+        payslip_id -> Int4,
+        compensation_id -> Nullable<Int4>,
+        #[max_length = 16]
+        component -> Varchar,
+        #[max_length = 255]
+        name -> Varchar,
+        amount -> Int8,
+    }
+}
+
+diesel::table! {
+    payslip_table (payslip_id) {
+        payslip_id -> Int4,
+        payroll_run_id -> Int4,
+        employee_id -> Nullable<Int4>,
+        #[max_length = 255]
+        employee_name -> Varchar,
+        department_id -> Nullable<Int4>,
+        period_start -> Date,
+        period_end -> Date,
+        salary -> Int8,
+        allowances -> Int8,
+        deductions -> Int8,
+        gross -> Int8,
+        net -> Int8,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     permission_table (permission_id) {
         permission_id -> Int4,
@@ -170,6 +380,18 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    project_table (project_id) {
+        project_id -> Int4,
+        #[max_length = 255]
+        project_name -> Varchar,
+        description -> Nullable<Text>,
+        user_id -> Nullable<Int4>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     product_table (product_id) {
         product_id -> Int4,
@@ -199,6 +421,26 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    recovery_code_table (code_hash) {
+        #[max_length = 64]
+        code_hash -> Varchar,
+        user_id -> Int4,
+        created_at -> Timestamp,
+        used_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    refresh_token_table (token_hash) {
+        #[max_length = 64]
+        token_hash -> Varchar,
+        session_id -> Uuid,
+        created_at -> Timestamp,
+        used_at -> Nullable<Timestamp>,
+    }
+}
+
 diesel::table! {
     reload_counts (reload_date) {
         reload_date -> Date,
@@ -206,6 +448,63 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    review_criterion_table (review_criterion_id) {
+        review_criterion_id -> Int4,
+        review_cycle_id -> Int4,
+        #[max_length = 255]
+        criterion_name -> Varchar,
+        description -> Nullable<Text>,
+        weight -> Int4,
+    }
+}
+
+diesel::table! {
+    review_cycle_table (review_cycle_id) {
+        review_cycle_id -> Int4,
+        #[max_length = 255]
+        review_cycle_name -> Varchar,
+        start_date -> Date,
+        end_date -> Date,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    review_score_table (review_id, review_criterion_id) {
+        review_id -> Int4,
+        review_criterion_id -> Int4,
+        score -> Int4,
+        comment -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    review_table (review_id) {
+        review_id -> Int4,
+        review_cycle_id -> Int4,
+        employee_id -> Int4,
+        reviewer_id -> Nullable<Int4>,
+        #[max_length = 16]
+        kind -> Varchar,
+        #[max_length = 16]
+        status -> Varchar,
+        comment -> Nullable<Text>,
+        submitted_at -> Nullable<Timestamp>,
+        acknowledged_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    role_permission_table (role_id, permission_id) {
+        role_id -> Int4,
+        permission_id -> Int4,
+        granted_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     role_table (role_id) {
         role_id -> Int4,
@@ -217,6 +516,43 @@ diesel::table! {
         permissions -> Nullable<Varchar>,
         created_at -> Nullable<Timestamp>,
         updated_at -> Nullable<Timestamp>,
+        require_two_factor -> Bool,
+    }
+}
+
+diesel::table! {
+    security_event_table (event_id) {
+        event_id -> Int8,
+        #[max_length = 32]
+        event_type -> Varchar,
+        user_id -> Nullable<Int4>,
+        #[max_length = 255]
+        login -> Nullable<Varchar>,
+        #[max_length = 64]
+        ip_address -> Nullable<Varchar>,
+        #[max_length = 255]
+        detail -> Nullable<Varchar>,
+        created_at -> Timestamp,
+        actor_user_id -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    session_table (session_id) {
+        session_id -> Uuid,
+        user_id -> Int4,
+        #[max_length = 255]
+        device_name -> Nullable<Varchar>,
+        user_agent -> Nullable<Text>,
+        #[max_length = 64]
+        ip_address -> Nullable<Varchar>,
+        created_at -> Timestamp,
+        last_used_at -> Timestamp,
+        expires_at -> Timestamp,
+        revoked_at -> Nullable<Timestamp>,
+        #[max_length = 255]
+        revoked_reason -> Nullable<Varchar>,
+        two_factor -> Bool,
     }
 }
 
@@ -247,6 +583,25 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    task_dependency_table (task_id, blocker_id) {
+        task_id -> Int4,
+        blocker_id -> Int4,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    task_status_table (task_status_id) {
+        task_status_id -> Int4,
+        #[max_length = 64]
+        status_name -> Varchar,
+        position -> Int4,
+        closed -> Bool,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     task_table (task_id) {
         task_id -> Int4,
@@ -255,6 +610,33 @@ diesel::table! {
         created_at -> Nullable<Timestamp>,
         updated_at -> Nullable<Timestamp>,
         user_id -> Nullable<Int4>,
+        status_id -> Int4,
+        priority -> Int4,
+        start_date -> Nullable<Date>,
+        due_date -> Nullable<Date>,
+        assignee_id -> Nullable<Int4>,
+        labels -> Array<Text>,
+        completed_at -> Nullable<Timestamp>,
+        project_id -> Nullable<Int4>,
+        parent_id -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    task_transition_table (from_status_id, to_status_id) {
+        from_status_id -> Int4,
+        to_status_id -> Int4,
+    }
+}
+
+diesel::table! {
+    two_factor_table (user_id) {
+        user_id -> Int4,
+        #[max_length = 64]
+        secret -> Varchar,
+        created_at -> Timestamp,
+        enabled_at -> Nullable<Timestamp>,
+        last_used_step -> Nullable<Int8>,
     }
 }
 
@@ -280,30 +662,91 @@ diesel::table! {
     }
 }
 
+diesel::joinable!(api_key_scope_table -> api_key_table (api_key_id));
+diesel::joinable!(api_key_scope_table -> permission_table (permission_id));
+diesel::joinable!(api_key_table -> user_table (user_id));
+diesel::joinable!(attendance_table -> employee_table (employee_id));
+diesel::joinable!(compensation_table -> employee_table (employee_id));
+diesel::joinable!(email_verification_table -> user_table (user_id));
+diesel::joinable!(employee_table -> user_table (user_id));
 diesel::joinable!(inventory_table -> product_table (product_id));
+diesel::joinable!(leave_balance_table -> employee_table (employee_id));
+diesel::joinable!(leave_balance_table -> leave_type_table (leave_type_id));
+diesel::joinable!(leave_request_table -> leave_type_table (leave_type_id));
+diesel::joinable!(oidc_identity_table -> user_table (user_id));
+diesel::joinable!(one_time_token_table -> user_table (user_id));
 diesel::joinable!(order_table -> customer_table (customer_id));
+diesel::joinable!(payroll_run_table -> user_table (created_by));
+diesel::joinable!(payslip_line_table -> compensation_table (compensation_id));
+diesel::joinable!(payslip_line_table -> payslip_table (payslip_id));
+diesel::joinable!(payslip_table -> employee_table (employee_id));
+diesel::joinable!(payslip_table -> payroll_run_table (payroll_run_id));
 diesel::joinable!(product_table -> supplier_table (supplier_id));
 diesel::joinable!(product_table -> user_table (user_id));
+diesel::joinable!(project_table -> user_table (user_id));
+diesel::joinable!(recovery_code_table -> user_table (user_id));
+diesel::joinable!(refresh_token_table -> session_table (session_id));
+diesel::joinable!(review_criterion_table -> review_cycle_table (review_cycle_id));
+diesel::joinable!(review_score_table -> review_criterion_table (review_criterion_id));
+diesel::joinable!(review_score_table -> review_table (review_id));
+diesel::joinable!(review_table -> review_cycle_table (review_cycle_id));
+diesel::joinable!(role_permission_table -> permission_table (permission_id));
+diesel::joinable!(role_permission_table -> role_table (role_id));
+diesel::joinable!(security_event_table -> user_table (user_id));
+diesel::joinable!(session_table -> user_table (user_id));
 diesel::joinable!(shipment_table -> order_table (order_id));
+diesel::joinable!(task_table -> project_table (project_id));
+diesel::joinable!(task_table -> task_status_table (status_id));
 diesel::joinable!(task_table -> user_table (user_id));
+diesel::joinable!(two_factor_table -> user_table (user_id));
 diesel::joinable!(user_table -> role_table (role_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    api_key_scope_table,
+    api_key_table,
+    attendance_table,
     category_table,
+    compensation_table,
     customer_table,
     department_table,
+    email_verification_table,
     employee_table,
     file_table,
     follow_table,
+    impersonation_table,
     inventory_table,
+    leave_balance_table,
+    leave_request_table,
+    leave_type_table,
+    login_throttle_table,
+    oidc_identity_table,
+    oidc_login_table,
+    one_time_token_table,
     order_table,
+    payroll_run_table,
+    payslip_line_table,
+    payslip_table,
     permission_table,
     post_table,
     product_table,
+    project_table,
+    recovery_code_table,
+    refresh_token_table,
     reload_counts,
+    review_criterion_table,
+    review_cycle_table,
+    review_score_table,
+    review_table,
+    role_permission_table,
     role_table,
+    security_event_table,
+    session_table,
     shipment_table,
     supplier_table,
+    task_dependency_table,
+    task_status_table,
     task_table,
+    task_transition_table,
+    two_factor_table,
     user_table,
 );
@@ -0,0 +1,51 @@
+/// Seed and volume knobs, every count is the number of rows inserted for that entity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeedConfig {
+    pub seed: u64,
+    /// Extra functional roles on top of the existing ones, at most [`crate::faker::MAX_ROLES`].
+    pub roles: usize,
+    pub users: usize,
+    pub employees: usize,
+    pub suppliers: usize,
+    pub categories: usize,
+    pub products: usize,
+    /// Stock locations per product.
+    pub warehouses: usize,
+    pub customers: usize,
+    pub orders: usize,
+}
+
+impl Default for SeedConfig {
+    fn default() -> Self {
+        Self {
+            seed: 42,
+            roles: 4,
+            users: 50,
+            employees: 30,
+            suppliers: 10,
+            categories: 12,
+            products: 100,
+            warehouses: 2,
+            customers: 80,
+            orders: 200,
+        }
+    }
+}
+
+impl SeedConfig {
+    /// The default volumes multiplied by `factor`, roles and warehouses are not scaled.
+    pub fn scaled(seed: u64, factor: usize) -> Self {
+        let base = Self::default();
+        Self {
+            seed,
+            users: base.users * factor,
+            employees: base.employees * factor,
+            suppliers: base.suppliers * factor,
+            categories: base.categories * factor,
+            products: base.products * factor,
+            customers: base.customers * factor,
+            orders: base.orders * factor,
+            ..base
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::words;
+
+/// How many extra roles [`crate::seed`] can add, one per built-in functional role.
+pub const MAX_ROLES: usize = words::ROLES.len();
+
+pub struct Person {
+    pub first_name: String,
+    pub last_name: String,
+}
+
+pub struct Address {
+    pub street: String,
+    pub city: String,
+    pub state: String,
+    pub postal_code: String,
+}
+
+impl Address {
+    pub fn one_line(&self) -> String {
+        format!("{}, {}, {} {}", self.street, self.city, self.state, self.postal_code)
+    }
+}
+
+/// Random but reproducible values. ChaCha is used instead of `StdRng` because its output is
+/// stable across `rand` releases.
+pub struct Faker {
+    rng: ChaCha8Rng,
+    tag: String,
+}
+
+impl Faker {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            tag: format!("{seed:x}"),
+        }
+    }
+
+    /// Suffix for columns with a unique constraint, different seeds never collide: the `-`
+    /// keeps `2a-10000` and `2a1-0000` apart.
+    pub fn unique(&self, index: usize) -> String {
+        format!("{}-{index:04}", self.tag)
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        items.choose(&mut self.rng).expect("pick from an empty list")
+    }
+
+    pub fn pick_many<T: Copy>(&mut self, items: &[T], amount: usize) -> Vec<T> {
+        items.choose_multiple(&mut self.rng, amount).copied().collect()
+    }
+
+    pub fn range(&mut self, low: i64, high: i64) -> i64 {
+        self.rng.gen_range(low..=high)
+    }
+
+    pub fn chance(&mut self, probability: f64) -> bool {
+        self.rng.gen_bool(probability)
+    }
+
+    /// A price with two decimals.
+    pub fn money(&mut self, low: f64, high: f64) -> f64 {
+        (self.rng.gen_range(low..high) * 100.0).round() / 100.0
+    }
+
+    pub fn person(&mut self) -> Person {
+        Person {
+            first_name: self.pick(words::FIRST_NAMES).to_string(),
+            last_name: self.pick(words::LAST_NAMES).to_string(),
+        }
+    }
+
+    pub fn email(&mut self, person: &Person, index: usize) -> String {
+        format!(
+            "{}.{}.{}@{}",
+            person.first_name.to_lowercase(),
+            person.last_name.to_lowercase(),
+            self.unique(index),
+            self.pick(words::EMAIL_DOMAINS)
+        )
+    }
+
+    /// A `555-` number, short enough for the 20 character phone columns.
+    pub fn phone(&mut self) -> String {
+        format!("555-{:03}-{:04}", self.range(100, 999), self.range(0, 9999))
+    }
+
+    pub fn address(&mut self) -> Address {
+        let (city, state, zip) = *self.pick(words::CITIES);
+        Address {
+            street: format!("{} {}", self.range(1, 9999), self.pick(words::STREETS)),
+            city: city.to_string(),
+            state: state.to_string(),
+            postal_code: format!("{zip}{:02}", self.range(0, 99)),
+        }
+    }
+
+    pub fn company(&mut self) -> String {
+        format!("{} {}", self.pick(words::COMPANY_PREFIXES), self.pick(words::COMPANY_SUFFIXES))
+    }
+
+    pub fn product_name(&mut self) -> String {
+        format!("{} {}", self.pick(words::PRODUCT_ADJECTIVES), self.pick(words::PRODUCT_NOUNS))
+    }
+
+    /// A point in time between `start` and `start + days`, to the second.
+    pub fn datetime_after(&mut self, start: NaiveDateTime, days: i64) -> NaiveDateTime {
+        start + Duration::seconds(self.range(0, days * 24 * 3600))
+    }
+}
+
+/// Generated timestamps are anchored here rather than at "now" to stay reproducible.
+pub fn epoch() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2024, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use super::Faker;
+
+    #[test]
+    fn test_same_seed_same_values() {
+        let (mut a, mut b) = (Faker::new(7), Faker::new(7));
+        for _ in 0..50 {
+            assert_eq!(a.person().last_name, b.person().last_name);
+            assert_eq!(a.address().one_line(), b.address().one_line());
+            assert_eq!(a.money(1.0, 10.0), b.money(1.0, 10.0));
+        }
+        assert_ne!(Faker::new(7).unique(1), Faker::new(8).unique(1));
+        assert_ne!(Faker::new(0x2a).unique(10000), Faker::new(0x2a1).unique(0));
+    }
+}
@@ -0,0 +1,11 @@
+//! Generates reproducible, referentially consistent demo data.
+//!
+//! The same [`SeedConfig`] always produces the same rows, so load tests and UI demos can be
+//! rebuilt from a seed and a handful of volume knobs.
+pub mod config;
+pub mod faker;
+pub mod seeder;
+mod words;
+
+pub use config::SeedConfig;
+pub use seeder::{seed, SeedReport};
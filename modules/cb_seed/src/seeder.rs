@@ -0,0 +1,539 @@
+use std::fmt::Display;
+
+use chrono::Duration;
+use crab_rocket_category::models::category::{Category, PostCategory};
+use crab_rocket_customer::models::customer::{Customer, PostCustomer};
+use crab_rocket_employee::models::employee::{Employee, PostEmployee};
+use crab_rocket_inventory::models::inventory::{Inventory, PostInventory};
+use crab_rocket_order::models::order::{Order, PostOrder};
+use crab_rocket_payroll::models::compensation::{Compensation, NewCompensation};
+use crab_rocket_product::models::product::{PostProduct, Product};
+use crab_rocket_role::mappers::role_mapper::RoleMapper;
+use crab_rocket_role::models::role::{PostRole, Role};
+use crab_rocket_schema::schema::{
+    category_table, compensation_table, customer_table, employee_table, inventory_table,
+    order_table, product_table, role_table, shipment_table, supplier_table, user_table,
+};
+use crab_rocket_shipment::models::shipment::{PostShipment, Shipment};
+use crab_rocket_supplier::models::supplier::{PostSupplier, Supplier};
+use crab_rocket_user::models::user::{PostUser, User};
+use crab_rocket_utils::password::hash_password;
+use diesel::prelude::*;
+use diesel::result::Error;
+
+use crate::config::SeedConfig;
+use crate::faker::{epoch, Faker, Person};
+use crate::words;
+
+/// Password of every generated user, so demo accounts can log in.
+pub const SEED_PASSWORD: &str = "crab-rocket-seed";
+
+/// Rows per `INSERT`, well below PostgreSQL's bind parameter limit for every table.
+const CHUNK_SIZE: usize = 500;
+
+/// Rows inserted per entity by one [`seed`] run.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SeedReport {
+    pub roles: usize,
+    pub users: usize,
+    pub employees: usize,
+    pub suppliers: usize,
+    pub categories: usize,
+    pub products: usize,
+    pub inventory: usize,
+    pub customers: usize,
+    pub orders: usize,
+    pub shipments: usize,
+}
+
+impl Display for SeedReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "roles: {}, users: {}, employees: {}, suppliers: {}, categories: {}, products: {}, \
+             inventory: {}, customers: {}, orders: {}, shipments: {}",
+            self.roles,
+            self.users,
+            self.employees,
+            self.suppliers,
+            self.categories,
+            self.products,
+            self.inventory,
+            self.customers,
+            self.orders,
+            self.shipments
+        )
+    }
+}
+
+/// Batch inserts `$rows` into `$table` and returns the stored rows in insertion order.
+macro_rules! insert_all {
+    ($conn:expr, $table:path, $rows:expr, $item:ty) => {{
+        let mut inserted: Vec<$item> = Vec::with_capacity($rows.len());
+        for chunk in $rows.chunks(CHUNK_SIZE) {
+            inserted.extend(
+                diesel::insert_into($table)
+                    .values(chunk)
+                    .returning(<$item>::as_returning())
+                    .get_results($conn)?,
+            );
+        }
+        inserted
+    }};
+}
+
+/// Inserts a full dataset in one transaction, entities are created parents first so every
+/// foreign key points at a row of the same run (or at an existing role).
+///
+/// Values depend only on `config`. Unique columns carry a suffix derived from the seed, so
+/// different seeds can share a database; running the same seed twice fails on those
+/// constraints and rolls back.
+pub fn seed(conn: &mut PgConnection, config: &SeedConfig) -> Result<SeedReport, Error> {
+    conn.transaction(|conn| {
+        let mut faker = Faker::new(config.seed);
+        let mut report = SeedReport::default();
+
+        let roles = seed_roles(conn, config, &mut report)?;
+        let users = seed_users(conn, &mut faker, config, &roles)?;
+        report.users = users.len();
+        report.employees = seed_employees(conn, &mut faker, config, &roles)?.len();
+        let suppliers = seed_suppliers(conn, &mut faker, config)?;
+        report.suppliers = suppliers.len();
+        report.categories = seed_categories(conn, &mut faker, config)?.len();
+        let products = seed_products(conn, &mut faker, config, &users, &suppliers, &mut report)?;
+        report.products = products.len();
+        let customers = seed_customers(conn, &mut faker, config)?;
+        report.customers = customers.len();
+        let orders = seed_orders(conn, &mut faker, config, &customers, &products)?;
+        report.orders = orders.len();
+        report.shipments = seed_shipments(conn, &mut faker, &orders, &customers)?.len();
+        Ok(report)
+    })
+}
+
+/// Adds the first `config.roles` functional roles when missing and returns every real role.
+fn seed_roles(
+    conn: &mut PgConnection,
+    config: &SeedConfig,
+    report: &mut SeedReport,
+) -> Result<Vec<Role>, Error> {
+    for (name, description) in words::ROLES.iter().take(config.roles) {
+        if RoleMapper::get_by_name(conn, name).optional()?.is_none() {
+            let role = PostRole::new(name.to_string(), Some(description.to_string()), None);
+            diesel::insert_into(role_table::table).values(&role).execute(conn)?;
+            report.roles += 1;
+        }
+    }
+    // `-1` is the placeholder role the migrations use as a column default.
+    role_table::table.filter(role_table::role_id.gt(0)).order(role_table::role_id).load(conn)
+}
+
+fn seed_users(
+    conn: &mut PgConnection,
+    faker: &mut Faker,
+    config: &SeedConfig,
+    roles: &[Role],
+) -> Result<Vec<User>, Error> {
+    // Every row shares one hash, argon2 per user would dominate the run.
+    let password =
+        hash_password(SEED_PASSWORD).map_err(|e| Error::SerializationError(Box::new(e)))?;
+    let rows: Vec<PostUser> = (0..config.users)
+        .map(|i| {
+            let person = faker.person();
+            let created_at = faker.datetime_after(epoch(), 365);
+            let role_id = (!roles.is_empty()).then(|| faker.pick(roles).role_id());
+            PostUser::new(
+                format!(
+                    "{}_{}_{}",
+                    person.first_name.to_lowercase(),
+                    person.last_name.to_lowercase(),
+                    faker.unique(i)
+                ),
+                role_id,
+                Some(created_at),
+                Some(faker.email(&person, i)),
+                password.clone(),
+                Some(full_name(&person)),
+                Some(format!("https://example.com/avatars/{}.png", faker.unique(i))),
+                Some(format!("Hi, I am {}.", person.first_name)),
+                Some(created_at),
+                format!("555-{}", faker.unique(i)),
+            )
+        })
+        .collect();
+    Ok(insert_all!(conn, user_table::table, rows, User))
+}
+
+/// One head, `employees / 8` managers reporting to the head and everybody else reporting to
+/// a random manager.
+fn seed_employees(
+    conn: &mut PgConnection,
+    faker: &mut Faker,
+    config: &SeedConfig,
+    roles: &[Role],
+) -> Result<Vec<Employee>, Error> {
+    if config.employees == 0 {
+        return Ok(Vec::new());
+    }
+    let managers = (config.employees / 8).max(1).min(config.employees - 1);
+
+    let (head, mut salaries): (Vec<_>, Vec<_>) =
+        [employee_row(faker, roles, 0, None)].into_iter().unzip();
+    let mut inserted = insert_all!(conn, employee_table::table, head, Employee);
+    let head_id = inserted[0].employee_id();
+
+    let (rows, manager_salaries): (Vec<PostEmployee>, Vec<i32>) =
+        (1..=managers).map(|i| employee_row(faker, roles, i, Some(head_id))).unzip();
+    salaries.extend(manager_salaries);
+    let manager_ids: Vec<i32> = insert_all!(conn, employee_table::table, rows, Employee)
+        .into_iter()
+        .map(|manager| {
+            let id = manager.employee_id();
+            inserted.push(manager);
+            id
+        })
+        .collect();
+
+    let (rows, staff_salaries): (Vec<PostEmployee>, Vec<i32>) = (managers + 1..config.employees)
+        .map(|i| {
+            let manager_id = *faker.pick(&manager_ids);
+            employee_row(faker, roles, i, Some(manager_id))
+        })
+        .unzip();
+    salaries.extend(staff_salaries);
+    inserted.extend(insert_all!(conn, employee_table::table, rows, Employee));
+
+    // Everybody earns their salary since the day they were hired.
+    let rows: Vec<NewCompensation> = inserted
+        .iter()
+        .zip(salaries)
+        .map(|(employee, salary)| {
+            let hired = employee.hire_date().unwrap_or_else(epoch).date();
+            NewCompensation::salary(employee.employee_id(), salary, hired)
+        })
+        .collect();
+    insert_all!(conn, compensation_table::table, rows, Compensation);
+    Ok(inserted)
+}
+
+fn employee_row(
+    faker: &mut Faker,
+    roles: &[Role],
+    index: usize,
+    manager_id: Option<i32>,
+) -> (PostEmployee, i32) {
+    let person = faker.person();
+    let address = faker.address();
+    let date_of_birth = faker.datetime_after(epoch() - Duration::days(365 * 60), 365 * 40);
+    let hire_date = faker.datetime_after(epoch() - Duration::days(365 * 8), 365 * 8);
+    let gender = faker.pick(&["Male", "Female"]).to_string();
+    let email = faker.email(&person, index);
+    let phone = faker.phone();
+    let job_title = faker.pick(words::JOB_TITLES).to_string();
+    let salary = faker.range(30, 150) as i32 * 1000;
+    let employee = PostEmployee::new(
+        format!("{} ({})", full_name(&person), faker.unique(index)),
+        Some(person.first_name.clone()),
+        Some(person.last_name.clone()),
+        Some(gender),
+        Some(date_of_birth),
+        Some(hire_date),
+        Some(email),
+        Some(phone),
+        None,
+        Some(job_title),
+        manager_id,
+        Some(address.street),
+        Some(address.city),
+        Some(address.state),
+        Some(address.postal_code),
+        Some(true),
+        // Filled from `role_id` by the `set_role_name` trigger.
+        None,
+        (!roles.is_empty()).then(|| faker.pick(roles).role_id()),
+    );
+    (employee, salary)
+}
+
+fn seed_suppliers(
+    conn: &mut PgConnection,
+    faker: &mut Faker,
+    config: &SeedConfig,
+) -> Result<Vec<Supplier>, Error> {
+    let rows: Vec<PostSupplier> = (0..config.suppliers)
+        .map(|i| {
+            let name = faker.company();
+            let created_at = faker.datetime_after(epoch(), 365);
+            PostSupplier::new(
+                format!("{name} {}", i + 1),
+                Some(faker.address().one_line()),
+                Some(faker.phone()),
+                Some(format!("sales.{}@example.com", name.to_lowercase().replace(' ', "-"))),
+                Some(created_at),
+                Some(created_at),
+            )
+        })
+        .collect();
+    Ok(insert_all!(conn, supplier_table::table, rows, Supplier))
+}
+
+/// A third of the categories are top level, the rest are children of one of them.
+fn seed_categories(
+    conn: &mut PgConnection,
+    faker: &mut Faker,
+    config: &SeedConfig,
+) -> Result<Vec<Category>, Error> {
+    let roots = config.categories.div_ceil(3);
+    let name = |i: usize| match i / words::CATEGORY_NAMES.len() {
+        0 => words::CATEGORY_NAMES[i].to_string(),
+        round => {
+            format!("{} {}", words::CATEGORY_NAMES[i % words::CATEGORY_NAMES.len()], round + 1)
+        }
+    };
+
+    let rows: Vec<PostCategory> = (0..roots)
+        .map(|i| {
+            let created_at = faker.datetime_after(epoch(), 30);
+            PostCategory::new(name(i), None, None, Some(created_at), Some(created_at))
+        })
+        .collect();
+    let mut inserted = insert_all!(conn, category_table::table, rows, Category);
+    let root_ids: Vec<i32> = inserted.iter().map(|c| c.category_id()).collect();
+
+    let rows: Vec<PostCategory> = (roots..config.categories)
+        .map(|i| {
+            let created_at = faker.datetime_after(epoch(), 365);
+            let parent_id = *faker.pick(&root_ids);
+            PostCategory::new(name(i), None, Some(parent_id), Some(created_at), Some(created_at))
+        })
+        .collect();
+    inserted.extend(insert_all!(conn, category_table::table, rows, Category));
+    Ok(inserted)
+}
+
+/// Products with their stock rows, `product.inventory` is the sum over its warehouses.
+fn seed_products(
+    conn: &mut PgConnection,
+    faker: &mut Faker,
+    config: &SeedConfig,
+    users: &[User],
+    suppliers: &[Supplier],
+    report: &mut SeedReport,
+) -> Result<Vec<Product>, Error> {
+    let warehouses = config.warehouses.min(words::WAREHOUSES.len());
+    let mut stock: Vec<Vec<(&str, i32)>> = Vec::with_capacity(config.products);
+    let rows: Vec<_> = (0..config.products)
+        .map(|i| {
+            let locations = faker.pick_many(words::WAREHOUSES, warehouses);
+            let quantities: Vec<(&str, i32)> =
+                locations.into_iter().map(|l| (l, faker.range(0, 200) as i32)).collect();
+            let total: i32 = quantities.iter().map(|(_, q)| q).sum();
+            stock.push(quantities);
+
+            let price = faker.money(5.0, 1500.0);
+            let is_discounted = faker.chance(0.2);
+            let created_at = faker.datetime_after(epoch(), 365);
+            let owner = (!users.is_empty()).then(|| faker.pick(users).user_id());
+            PostProduct {
+                user_id: owner,
+                name: faker.product_name(),
+                description: Some(String::from("Generated by the demo seeder.")),
+                sku: format!("SKU-{}", faker.unique(i)),
+                image: Some(format!("https://example.com/products/{}.jpg", faker.unique(i))),
+                price: Some(price),
+                discount_price: Some(if is_discounted {
+                    (price * 0.8 * 100.0).round() / 100.0
+                } else {
+                    price
+                }),
+                is_discounted: Some(is_discounted),
+                is_valid: Some(true),
+                inventory: Some(total),
+                is_in_stock: Some(total > 0),
+                created_at: Some(created_at),
+                updated_at: Some(created_at),
+                supplier_id: (!suppliers.is_empty()).then(|| faker.pick(suppliers).supplier_id()),
+                weight: Some(faker.money(0.1, 25.0)),
+                dimensions: Some(format!(
+                    "{}x{}x{}",
+                    faker.range(5, 100),
+                    faker.range(5, 100),
+                    faker.range(5, 100)
+                )),
+                status: Some(faker.pick(words::PRODUCT_STATUSES).to_string()),
+                public: Some(faker.chance(0.9)),
+            }
+        })
+        .collect();
+    let products = insert_all!(conn, product_table::table, rows, Product);
+
+    let rows: Vec<PostInventory> = products
+        .iter()
+        .zip(&stock)
+        .flat_map(|(product, quantities)| {
+            quantities.iter().map(|(location, quantity)| PostInventory {
+                product_id: Some(product.product_id),
+                location: Some(location.to_string()),
+                quantity: Some(*quantity),
+                last_updated: product.updated_at,
+            })
+        })
+        .collect();
+    report.inventory = insert_all!(conn, inventory_table::table, rows, Inventory).len();
+    Ok(products)
+}
+
+fn seed_customers(
+    conn: &mut PgConnection,
+    faker: &mut Faker,
+    config: &SeedConfig,
+) -> Result<Vec<Customer>, Error> {
+    let rows: Vec<PostCustomer> = (0..config.customers)
+        .map(|i| {
+            let person = faker.person();
+            PostCustomer {
+                name: full_name(&person),
+                email: faker.email(&person, i),
+                phone: Some(faker.phone()),
+                address: Some(faker.address().one_line()),
+            }
+        })
+        .collect();
+    Ok(insert_all!(conn, customer_table::table, rows, Customer))
+}
+
+/// `total_amount` is the price of one to four random products, there is no line item table.
+fn seed_orders(
+    conn: &mut PgConnection,
+    faker: &mut Faker,
+    config: &SeedConfig,
+    customers: &[Customer],
+    products: &[Product],
+) -> Result<Vec<Order>, Error> {
+    if customers.is_empty() {
+        return Ok(Vec::new());
+    }
+    let rows: Vec<PostOrder> = (0..config.orders)
+        .map(|_| {
+            let lines = faker.range(1, 4);
+            let total: f64 = (0..lines)
+                .map(|_| match products.is_empty() {
+                    true => faker.money(5.0, 500.0),
+                    false => {
+                        let price = faker.pick(products).discount_price.unwrap_or(0.0);
+                        price * faker.range(1, 3) as f64
+                    }
+                })
+                .sum();
+            PostOrder {
+                customer_id: Some(faker.pick(customers).customer_id),
+                order_date: Some(faker.datetime_after(epoch(), 365)),
+                total_amount: Some((total * 100.0).round() / 100.0),
+                status: Some(faker.pick(words::ORDER_STATUSES).to_string()),
+            }
+        })
+        .collect();
+    Ok(insert_all!(conn, order_table::table, rows, Order))
+}
+
+/// One shipment per shipped or completed order, sent to the customer's address.
+fn seed_shipments(
+    conn: &mut PgConnection,
+    faker: &mut Faker,
+    orders: &[Order],
+    customers: &[Customer],
+) -> Result<Vec<Shipment>, Error> {
+    let rows: Vec<PostShipment> = orders
+        .iter()
+        .filter_map(|order| {
+            let status = match order.status.as_deref() {
+                Some("SHIPPED") => "IN_TRANSIT",
+                Some("COMPLETED") => "DELIVERED",
+                _ => return None,
+            };
+            let address = customers
+                .iter()
+                .find(|c| Some(c.customer_id) == order.customer_id)
+                .and_then(|c| c.address.clone());
+            Some(PostShipment {
+                order_id: Some(order.order_id),
+                shipment_date: order.order_date.map(|d| d + Duration::days(faker.range(1, 5))),
+                delivery_address: address,
+                status: Some(status.to_string()),
+            })
+        })
+        .collect();
+    Ok(insert_all!(conn, shipment_table::table, rows, Shipment))
+}
+
+fn full_name(person: &Person) -> String {
+    format!("{} {}", person.first_name, person.last_name)
+}
+
+#[cfg(test)]
+mod test {
+    use crab_rocket_schema::schema::{
+        category_table, customer_table, employee_table, inventory_table, order_table,
+        product_table, shipment_table, supplier_table, user_table,
+    };
+    use crab_rocket_test_support::test_conn;
+    use diesel::prelude::*;
+
+    use super::seed;
+    use crate::config::SeedConfig;
+
+    /// Row counts of every table the seeder fills, in a fixed order.
+    fn counts(conn: &mut PgConnection) -> [i64; 9] {
+        [
+            user_table::table.count().get_result(conn).unwrap(),
+            employee_table::table.count().get_result(conn).unwrap(),
+            supplier_table::table.count().get_result(conn).unwrap(),
+            category_table::table.count().get_result(conn).unwrap(),
+            product_table::table.count().get_result(conn).unwrap(),
+            inventory_table::table.count().get_result(conn).unwrap(),
+            customer_table::table.count().get_result(conn).unwrap(),
+            order_table::table.count().get_result(conn).unwrap(),
+            shipment_table::table.count().get_result(conn).unwrap(),
+        ]
+    }
+
+    #[test]
+    fn test_seed_small_dataset() {
+        let mut conn = test_conn();
+        let config = SeedConfig {
+            seed: 20240101,
+            roles: 2,
+            users: 5,
+            employees: 9,
+            suppliers: 2,
+            categories: 4,
+            products: 6,
+            warehouses: 2,
+            customers: 3,
+            orders: 10,
+        };
+        let before = counts(&mut conn);
+        let report = seed(&mut conn, &config).unwrap();
+        assert_eq!(report.users, 5);
+        assert_eq!(report.employees, 9);
+        assert_eq!(report.categories, 4);
+        assert_eq!(report.inventory, 12);
+        assert_eq!(report.orders, 10);
+        assert!(report.shipments <= report.orders);
+
+        let after = counts(&mut conn);
+        let added: Vec<i64> = after.iter().zip(before).map(|(a, b)| a - b).collect();
+        let expected = [
+            report.users,
+            report.employees,
+            report.suppliers,
+            report.categories,
+            report.products,
+            report.inventory,
+            report.customers,
+            report.orders,
+            report.shipments,
+        ];
+        assert_eq!(added, expected.map(|n| n as i64));
+    }
+}
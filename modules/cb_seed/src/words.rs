@@ -0,0 +1,251 @@
+pub(crate) const FIRST_NAMES: &[&str] = &[
+    "James",
+    "Mary",
+    "John",
+    "Patricia",
+    "Robert",
+    "Jennifer",
+    "Michael",
+    "Linda",
+    "William",
+    "Elizabeth",
+    "David",
+    "Barbara",
+    "Richard",
+    "Susan",
+    "Joseph",
+    "Jessica",
+    "Thomas",
+    "Sarah",
+    "Charles",
+    "Karen",
+    "Daniel",
+    "Nancy",
+    "Matthew",
+    "Lisa",
+    "Anthony",
+    "Betty",
+    "Mark",
+    "Sandra",
+    "Steven",
+    "Ashley",
+    "Paul",
+    "Emily",
+    "Andrew",
+    "Donna",
+    "Joshua",
+    "Michelle",
+    "Wei",
+    "Yuki",
+    "Carlos",
+    "Sofia",
+    "Ahmed",
+    "Fatima",
+    "Ivan",
+    "Olga",
+    "Kenji",
+    "Mei",
+    "Luca",
+    "Chiara",
+];
+
+pub(crate) const LAST_NAMES: &[&str] = &[
+    "Smith",
+    "Johnson",
+    "Williams",
+    "Brown",
+    "Jones",
+    "Garcia",
+    "Miller",
+    "Davis",
+    "Rodriguez",
+    "Martinez",
+    "Hernandez",
+    "Lopez",
+    "Gonzalez",
+    "Wilson",
+    "Anderson",
+    "Thomas",
+    "Taylor",
+    "Moore",
+    "Jackson",
+    "Martin",
+    "Lee",
+    "Perez",
+    "Thompson",
+    "White",
+    "Harris",
+    "Clark",
+    "Lewis",
+    "Walker",
+    "Young",
+    "Allen",
+    "Wang",
+    "Li",
+    "Zhang",
+    "Tanaka",
+    "Sato",
+    "Rossi",
+    "Ivanov",
+    "Khan",
+];
+
+pub(crate) const STREETS: &[&str] = &[
+    "Main St",
+    "Oak Ave",
+    "Pine St",
+    "Maple Ave",
+    "Cedar Rd",
+    "Elm St",
+    "Lakeview Dr",
+    "Hill Rd",
+    "Park Ave",
+    "Washington Blvd",
+    "River Rd",
+    "Sunset Blvd",
+    "Church St",
+    "Mill Ln",
+    "High St",
+];
+
+/// `(city, state, postal code prefix)`
+pub(crate) const CITIES: &[(&str, &str, &str)] = &[
+    ("Springfield", "IL", "627"),
+    ("Portland", "OR", "972"),
+    ("Austin", "TX", "787"),
+    ("Denver", "CO", "802"),
+    ("Boston", "MA", "021"),
+    ("Seattle", "WA", "981"),
+    ("Madison", "WI", "537"),
+    ("Raleigh", "NC", "276"),
+    ("Phoenix", "AZ", "850"),
+    ("Columbus", "OH", "432"),
+];
+
+pub(crate) const EMAIL_DOMAINS: &[&str] =
+    &["example.com", "example.org", "example.net", "mail.example.com"];
+
+pub(crate) const COMPANY_PREFIXES: &[&str] = &[
+    "Acme",
+    "Globex",
+    "Initech",
+    "Umbrella",
+    "Stark",
+    "Wayne",
+    "Cyberdyne",
+    "Soylent",
+    "Hooli",
+    "Vandelay",
+    "Wonka",
+    "Tyrell",
+    "Aperture",
+    "Monarch",
+    "Oscorp",
+    "Nakatomi",
+];
+
+pub(crate) const COMPANY_SUFFIXES: &[&str] =
+    &["Supply", "Trading", "Industries", "Logistics", "Wholesale", "Goods", "Partners"];
+
+pub(crate) const ROLES: &[(&str, &str)] = &[
+    ("Manager", "Manages a team and approves its requests"),
+    ("Sales", "Handles customers and orders"),
+    ("Support", "Answers customer tickets"),
+    ("Warehouse", "Maintains inventory and ships orders"),
+    ("Finance", "Runs payroll and reviews spending"),
+    ("Marketing", "Owns campaigns and public product pages"),
+    ("Engineering", "Builds and operates the platform"),
+    ("Procurement", "Negotiates with suppliers"),
+    ("HR", "Manages employee records"),
+    ("Auditor", "Read-only access for audits"),
+];
+
+pub(crate) const JOB_TITLES: &[&str] = &[
+    "Software Engineer",
+    "Sales Representative",
+    "Account Manager",
+    "Support Specialist",
+    "Warehouse Associate",
+    "Accountant",
+    "Marketing Coordinator",
+    "Product Manager",
+    "Operations Analyst",
+    "HR Generalist",
+    "Buyer",
+    "Data Analyst",
+];
+
+pub(crate) const CATEGORY_NAMES: &[&str] = &[
+    "Electronics",
+    "Computers",
+    "Phones",
+    "Audio",
+    "Home",
+    "Kitchen",
+    "Furniture",
+    "Garden",
+    "Sports",
+    "Outdoors",
+    "Toys",
+    "Books",
+    "Office",
+    "Health",
+    "Beauty",
+    "Automotive",
+    "Tools",
+    "Pets",
+    "Grocery",
+    "Clothing",
+];
+
+pub(crate) const PRODUCT_ADJECTIVES: &[&str] = &[
+    "Compact",
+    "Deluxe",
+    "Ergonomic",
+    "Portable",
+    "Smart",
+    "Classic",
+    "Rugged",
+    "Wireless",
+    "Premium",
+    "Eco",
+    "Ultra",
+    "Lightweight",
+    "Heavy-Duty",
+    "Modular",
+];
+
+pub(crate) const PRODUCT_NOUNS: &[&str] = &[
+    "Lamp",
+    "Chair",
+    "Headphones",
+    "Kettle",
+    "Backpack",
+    "Keyboard",
+    "Monitor",
+    "Blender",
+    "Speaker",
+    "Tent",
+    "Drill",
+    "Notebook",
+    "Bottle",
+    "Charger",
+    "Desk",
+    "Jacket",
+    "Camera",
+    "Router",
+];
+
+pub(crate) const WAREHOUSES: &[&str] = &[
+    "Warehouse A",
+    "Warehouse B",
+    "Warehouse C",
+    "Distribution Center East",
+    "Distribution Center West",
+    "Store Backroom",
+];
+
+pub(crate) const ORDER_STATUSES: &[&str] =
+    &["PENDING", "PROCESSING", "SHIPPED", "COMPLETED", "CANCELLED"];
+
+pub(crate) const PRODUCT_STATUSES: &[&str] = &["Available", "Available", "Available", "DRAFT"];
@@ -1,3 +1,5 @@
+use crab_rocket_auth::guards::authorized::Authorized;
+use crab_rocket_auth::permission;
 use obj_traits::controller::controller_crud::ControllerCRUD;
 use obj_traits::request::pagination_request_param::{PaginationParam, PaginationParamTrait};
 use obj_traits::request::request_param::RequestParam;
@@ -7,14 +9,12 @@ use crate::controllers::shipment_controller::ShipmentController;
 use crate::models::shipment::{PatchShipment, PostShipment};
 use crate::models::shipment_filter::ShipmentFilter;
 
+permission!(pub ShipmentCreate, "shipment", "create");
+permission!(pub ShipmentUpdate, "shipment", "update");
+permission!(pub ShipmentDelete, "shipment", "delete");
+
 #[get("/shipment?<limit>&<offset>")]
-pub fn get_shipments(mut limit: Option<i32>, mut offset: Option<i32>) -> Json<serde_json::Value> {
-    if limit.is_none() {
-        limit = Some(10);
-    };
-    if offset.is_none() {
-        offset = Some(0);
-    };
+pub fn get_shipments(limit: Option<i32>, offset: Option<i32>) -> Json<serde_json::Value> {
     let params = RequestParam::new(PaginationParam::new(limit, offset), None);
     println!("{:?}", params);
     crab_rocket_schema::update_reload::update_reload_count();
@@ -43,7 +43,10 @@ pub fn get_shipment_by_id(id: i32) -> Json<serde_json::Value> {
 }
 
 #[post("/shipment", data = "<shipment>")]
-pub fn insert_single_shipment(shipment: Json<PostShipment>) -> Json<serde_json::Value> {
+pub fn insert_single_shipment(
+    _auth: Authorized<ShipmentCreate>,
+    shipment: Json<PostShipment>,
+) -> Json<serde_json::Value> {
     let mut obj: PostShipment = shipment.into_inner();
 
     let resp = ShipmentController::add_single(&mut obj).unwrap();
@@ -52,14 +55,21 @@ pub fn insert_single_shipment(shipment: Json<PostShipment>) -> Json<serde_json::
 }
 
 #[delete("/shipment/<id>")]
-pub fn delete_shipment_by_id(id: i32) -> Json<serde_json::Value> {
+pub fn delete_shipment_by_id(
+    _auth: Authorized<ShipmentDelete>,
+    id: i32,
+) -> Json<serde_json::Value> {
     let resp = ShipmentController::delete_by_id(id).unwrap();
     let json_value = serde_json::to_value(&resp).unwrap();
     Json(serde_json::from_value(json_value).unwrap())
 }
 
 #[patch("/shipment/<id>", data = "<task>")]
-pub fn update_shipment_by_id(id: i32, task: Json<PatchShipment>) -> Json<serde_json::Value> {
+pub fn update_shipment_by_id(
+    _auth: Authorized<ShipmentUpdate>,
+    id: i32,
+    task: Json<PatchShipment>,
+) -> Json<serde_json::Value> {
     let resp = ShipmentController::update_by_id(id, &task).unwrap();
     let json_value = serde_json::to_value(&resp).unwrap();
     Json(serde_json::from_value(json_value).unwrap())
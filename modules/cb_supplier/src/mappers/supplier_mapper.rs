@@ -41,8 +41,8 @@ impl MapperCRUD for SupplierMapper {
         //
         // limit 始终为 per_page
         // 计算分页相关
-        let page = (param.pagination.offset.unwrap() / param.pagination.limit.unwrap()) + 1;
-        let per_page = param.pagination.limit.unwrap();
+        let page = (param.pagination.offset_or_default() / param.pagination.limit_or_default()) + 1;
+        let per_page = param.pagination.limit_or_default();
         // 获取总记录数
         let total_count = dsl::supplier_table.count().get_result::<i64>(conn)? as i32;
         // 计算总页数
@@ -65,6 +65,7 @@ impl MapperCRUD for SupplierMapper {
         // 分页查询
         let data = dsl::supplier_table
             .order(dsl::updated_at.desc())
+            .then_order_by(dsl::supplier_id.desc())
             .limit(per_page as i64)
             .offset(((page - 1) * per_page) as i64)
             .load::<Supplier>(conn)?;
@@ -129,8 +130,8 @@ impl MapperCRUD for SupplierMapper {
         //
         // limit 始终为 per_page
         // 计算分页相关
-        let page = (param.pagination.offset.unwrap() / param.pagination.limit.unwrap()) + 1;
-        let per_page = param.pagination.limit.unwrap();
+        let page = (param.pagination.offset_or_default() / param.pagination.limit_or_default()) + 1;
+        let per_page = param.pagination.limit_or_default();
         // 获取总记录数
         let total_count = dsl::supplier_table.count().get_result::<i64>(conn)? as i32;
         // 计算总页数
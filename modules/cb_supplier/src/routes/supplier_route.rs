@@ -1,3 +1,5 @@
+use crab_rocket_auth::guards::authorized::Authorized;
+use crab_rocket_auth::permission;
 use obj_traits::controller::controller_crud::ControllerCRUD;
 use obj_traits::request::pagination_request_param::{PaginationParam, PaginationParamTrait};
 use obj_traits::request::request_param::RequestParam;
@@ -7,14 +9,12 @@ use crate::controllers::supplier_controller::SupplierController;
 use crate::models::supplier::{PatchSupplier, PostSupplier};
 use crate::models::supplier_filter::SupplierFilter;
 
+permission!(pub SupplierCreate, "supplier", "create");
+permission!(pub SupplierUpdate, "supplier", "update");
+permission!(pub SupplierDelete, "supplier", "delete");
+
 #[get("/supplier?<limit>&<offset>")]
-pub fn get_suppliers(mut limit: Option<i32>, mut offset: Option<i32>) -> Json<serde_json::Value> {
-    if limit.is_none() {
-        limit = Some(10);
-    };
-    if offset.is_none() {
-        offset = Some(0);
-    };
+pub fn get_suppliers(limit: Option<i32>, offset: Option<i32>) -> Json<serde_json::Value> {
     let params = RequestParam::new(PaginationParam::new(limit, offset), None);
     println!("{:?}", params);
     crab_rocket_schema::update_reload::update_reload_count();
@@ -43,7 +43,10 @@ pub fn get_supplier_by_id(id: i32) -> Json<serde_json::Value> {
 }
 
 #[post("/supplier", data = "<supplier>")]
-pub fn insert_single_supplier(supplier: Json<PostSupplier>) -> Json<serde_json::Value> {
+pub fn insert_single_supplier(
+    _auth: Authorized<SupplierCreate>,
+    supplier: Json<PostSupplier>,
+) -> Json<serde_json::Value> {
     let mut obj: PostSupplier = supplier.into_inner();
 
     let resp = SupplierController::add_single(&mut obj).unwrap();
@@ -52,14 +55,21 @@ pub fn insert_single_supplier(supplier: Json<PostSupplier>) -> Json<serde_json::
 }
 
 #[delete("/supplier/<id>")]
-pub fn delete_supplier_by_id(id: i32) -> Json<serde_json::Value> {
+pub fn delete_supplier_by_id(
+    _auth: Authorized<SupplierDelete>,
+    id: i32,
+) -> Json<serde_json::Value> {
     let resp = SupplierController::delete_by_id(id).unwrap();
     let json_value = serde_json::to_value(&resp).unwrap();
     Json(serde_json::from_value(json_value).unwrap())
 }
 
 #[patch("/supplier/<id>", data = "<task>")]
-pub fn update_supplier_by_id(id: i32, task: Json<PatchSupplier>) -> Json<serde_json::Value> {
+pub fn update_supplier_by_id(
+    _auth: Authorized<SupplierUpdate>,
+    id: i32,
+    task: Json<PatchSupplier>,
+) -> Json<serde_json::Value> {
     let resp = SupplierController::update_by_id(id, &task).unwrap();
     let json_value = serde_json::to_value(&resp).unwrap();
     Json(serde_json::from_value(json_value).unwrap())
@@ -0,0 +1,45 @@
+use crab_rocket_auth::guards::auth_user::AuthUser;
+use obj_traits::request::pagination_request_param::PaginationParam;
+use obj_traits::request::request_param::RequestParam;
+use obj_traits::response::data::Data;
+
+use crate::controllers::task_controller::to_tuple;
+use crate::models::project::{PatchProject, PostProject, Project, ProjectDetail};
+use crate::models::task::Task;
+use crate::models::task_filter::TaskFilter;
+use crate::services::project_service::ProjectService;
+
+pub struct ProjectController {}
+
+impl ProjectController {
+    pub fn get_projects() -> (i32, String, Option<Vec<Project>>) {
+        to_tuple(ProjectService::get_projects())
+    }
+
+    pub fn get_project(pid: i32) -> (i32, String, Option<ProjectDetail>) {
+        to_tuple(ProjectService::get_project(pid))
+    }
+
+    pub fn get_tasks(
+        pid: i32,
+        param: RequestParam<PaginationParam, TaskFilter>,
+    ) -> (i32, String, Option<Data<Vec<Task>>>) {
+        to_tuple(ProjectService::get_tasks(pid, param))
+    }
+
+    pub fn add_owned(auth: &AuthUser, obj: &PostProject) -> (i32, String, Option<Project>) {
+        to_tuple(ProjectService::add_owned(auth, obj))
+    }
+
+    pub fn update_owned(
+        auth: &AuthUser,
+        pid: i32,
+        obj: &PatchProject,
+    ) -> (i32, String, Option<Project>) {
+        to_tuple(ProjectService::update_owned(auth, pid, obj))
+    }
+
+    pub fn delete_owned(auth: &AuthUser, pid: i32) -> (i32, String, Option<Project>) {
+        to_tuple(ProjectService::delete_owned(auth, pid))
+    }
+}
@@ -1,6 +1,11 @@
-use crate::models::task::{PostTask, PatchTask, Task};
+use crate::error::TaskError;
+use crate::models::task::{PatchTask, PostTask, Task};
 use crate::models::task_filter::TaskFilter;
+use crate::models::task_progress::TaskProgress;
+use crate::models::task_status::{PostTaskStatus, TaskStatusDetail};
 use crate::services::task_service::TaskService;
+use crate::services::task_status_service::TaskStatusService;
+use crab_rocket_auth::guards::auth_user::AuthUser;
 use obj_traits::controller::controller_crud::{
     controller_add_single, controller_delete_by_id, controller_filter, controller_get_all,
     controller_get_by_id, controller_update_by_id, ControllerCRUD,
@@ -45,3 +50,105 @@ impl ControllerCRUD for TaskController {
         controller_filter::<Self::Item, TaskService, TaskFilter>(param)
     }
 }
+
+pub(crate) fn to_tuple<T>(result: Result<T, TaskError>) -> (i32, String, Option<T>) {
+    match result {
+        Ok(data) => (200, String::from("Success"), Some(data)),
+        Err(TaskError::Internal(e)) => {
+            println!("{e:?}");
+            (500, String::from("Internal Server Error"), None)
+        }
+        Err(e) => (e.status(), e.to_string(), None),
+    }
+}
+
+impl TaskController {
+    pub fn add_owned(auth: &AuthUser, obj: &PostTask) -> (i32, String, Option<Task>) {
+        to_tuple(TaskService::add_owned(auth, obj))
+    }
+
+    pub fn update_owned(auth: &AuthUser, pid: i32, obj: &PatchTask) -> (i32, String, Option<Task>) {
+        to_tuple(TaskService::update_owned(auth, pid, obj))
+    }
+
+    pub fn set_status_owned(
+        auth: &AuthUser,
+        pid: i32,
+        status_id: i32,
+    ) -> (i32, String, Option<Task>) {
+        to_tuple(TaskService::set_status_owned(auth, pid, status_id))
+    }
+
+    pub fn delete_owned(auth: &AuthUser, pid: i32) -> (i32, String, Option<Task>) {
+        to_tuple(TaskService::delete_owned(auth, pid))
+    }
+
+    pub fn get_subtasks(pid: i32) -> (i32, String, Option<Vec<Task>>) {
+        to_tuple(TaskService::get_subtasks(pid))
+    }
+
+    pub fn get_progress(pid: i32) -> (i32, String, Option<TaskProgress>) {
+        to_tuple(TaskService::get_progress(pid))
+    }
+
+    pub fn get_blockers(pid: i32) -> (i32, String, Option<Vec<Task>>) {
+        to_tuple(TaskService::get_blockers(pid))
+    }
+
+    pub fn add_blocker_owned(
+        auth: &AuthUser,
+        pid: i32,
+        blocker_id: i32,
+    ) -> (i32, String, Option<Vec<Task>>) {
+        to_tuple(TaskService::add_blocker_owned(auth, pid, blocker_id))
+    }
+
+    pub fn delete_blocker_owned(
+        auth: &AuthUser,
+        pid: i32,
+        blocker_id: i32,
+    ) -> (i32, String, Option<Vec<Task>>) {
+        to_tuple(TaskService::delete_blocker_owned(auth, pid, blocker_id))
+    }
+
+    pub fn filter_mine(
+        auth: &AuthUser,
+        param: RequestParam<PaginationParam, TaskFilter>,
+    ) -> Result<ApiResponse<Data<Vec<Task>>>, Box<dyn Error>> {
+        match TaskService::filter_mine(auth, param) {
+            Ok(tasks) => Ok(ApiResponse::success(tasks)),
+            Err(e) => {
+                println!("{e:?}");
+                Ok(ApiResponse::error(e))
+            }
+        }
+    }
+
+    pub fn get_overdue(
+        param: RequestParam<PaginationParam, TaskFilter>,
+    ) -> Result<ApiResponse<Data<Vec<Task>>>, Box<dyn Error>> {
+        match TaskService::get_overdue(param) {
+            Ok(tasks) => Ok(ApiResponse::success(tasks)),
+            Err(e) => {
+                println!("{e:?}");
+                Ok(ApiResponse::error(e))
+            }
+        }
+    }
+
+    pub fn get_statuses() -> (i32, String, Option<Vec<TaskStatusDetail>>) {
+        to_tuple(TaskStatusService::get_statuses())
+    }
+
+    pub fn add_status(obj: &PostTaskStatus) -> (i32, String, Option<TaskStatusDetail>) {
+        to_tuple(TaskStatusService::add_status(obj))
+    }
+
+    pub fn add_transition(from: i32, to: i32) -> (i32, String, Option<TaskStatusDetail>) {
+        to_tuple(TaskStatusService::add_transition(from, to))
+    }
+
+    pub fn delete_transition(from: i32, to: i32) -> (i32, String, Option<TaskStatusDetail>) {
+        to_tuple(TaskStatusService::delete_transition(from, to))
+    }
+}
@@ -0,0 +1,156 @@
+use std::error::Error;
+use std::fmt;
+
+use chrono::NaiveDate;
+use crab_rocket_auth::error::AuthError;
+use crab_rocket_auth::services::ownership_service::OwnershipError;
+use diesel::ConnectionError;
+
+/// Why a task or a project could not be written, or a task moved through the workflow.
+#[derive(Debug)]
+pub enum TaskError {
+    /// The task is missing or the caller may not touch it.
+    Ownership(OwnershipError),
+    StatusNotFound(i32),
+    AssigneeNotFound(i32),
+    ProjectNotFound(i32),
+    /// A parent or a blocker that does not exist.
+    TaskNotFound(i32),
+    /// Priorities go from 1 to 4.
+    InvalidPriority(i32),
+    /// A task due before it starts.
+    InvalidDates {
+        start: NaiveDate,
+        due: NaiveDate,
+    },
+    /// The workflow has no move from one status to the other.
+    InvalidTransition {
+        from: i32,
+        to: i32,
+    },
+    StatusTaken(String),
+    /// The new parent is a subtask of the task, or the task itself.
+    ParentCycle {
+        task_id: i32,
+        parent_id: i32,
+    },
+    /// The blocker waits on the task already, or is the task itself.
+    DependencyCycle {
+        task_id: i32,
+        blocker_id: i32,
+    },
+    /// A task cannot be closed while it waits on open tasks.
+    Blocked {
+        task_id: i32,
+        blocker_ids: Vec<i32>,
+    },
+    Internal(String),
+}
+
+impl TaskError {
+    pub fn status(&self) -> i32 {
+        match self {
+            TaskError::Ownership(e) => e.status(),
+            TaskError::StatusNotFound(_)
+            | TaskError::AssigneeNotFound(_)
+            | TaskError::ProjectNotFound(_)
+            | TaskError::TaskNotFound(_) => 404,
+            TaskError::InvalidPriority(_)
+            | TaskError::InvalidDates {
+                ..
+            } => 400,
+            TaskError::InvalidTransition {
+                ..
+            }
+            | TaskError::StatusTaken(_)
+            | TaskError::ParentCycle {
+                ..
+            }
+            | TaskError::DependencyCycle {
+                ..
+            }
+            | TaskError::Blocked {
+                ..
+            } => 409,
+            TaskError::Internal(_) => 500,
+        }
+    }
+}
+
+impl fmt::Display for TaskError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TaskError::Ownership(e) => write!(f, "{e}"),
+            TaskError::StatusNotFound(id) => write!(f, "task status {id} not found"),
+            TaskError::AssigneeNotFound(id) => write!(f, "user {id} not found"),
+            TaskError::ProjectNotFound(id) => write!(f, "project {id} not found"),
+            TaskError::TaskNotFound(id) => write!(f, "task {id} not found"),
+            TaskError::InvalidPriority(priority) => {
+                write!(f, "priority {priority} is not between 1 and 4")
+            }
+            TaskError::InvalidDates {
+                start,
+                due,
+            } => write!(f, "the task is due on {due}, before it starts on {start}"),
+            TaskError::InvalidTransition {
+                from,
+                to,
+            } => write!(f, "the workflow has no move from task status {from} to {to}"),
+            TaskError::StatusTaken(name) => write!(f, "task status `{name}` already exists"),
+            TaskError::ParentCycle {
+                task_id,
+                parent_id,
+            } => write!(
+                f,
+                "task {parent_id} is a subtask of task {task_id}, it cannot be its parent"
+            ),
+            TaskError::DependencyCycle {
+                task_id,
+                blocker_id,
+            } => write!(f, "task {blocker_id} waits on task {task_id}, it cannot block it"),
+            TaskError::Blocked {
+                task_id,
+                blocker_ids,
+            } => {
+                let blocker_ids: Vec<String> = blocker_ids.iter().map(i32::to_string).collect();
+                write!(f, "task {task_id} waits on open tasks {}", blocker_ids.join(", "))
+            }
+            TaskError::Internal(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for TaskError {}
+
+impl From<OwnershipError> for TaskError {
+    fn from(e: OwnershipError) -> Self {
+        match e {
+            OwnershipError::Auth(AuthError::Internal(e)) => TaskError::Internal(e),
+            e => TaskError::Ownership(e),
+        }
+    }
+}
+
+impl From<AuthError> for TaskError {
+    fn from(e: AuthError) -> Self {
+        OwnershipError::Auth(e).into()
+    }
+}
+
+impl From<Box<dyn Error>> for TaskError {
+    fn from(e: Box<dyn Error>) -> Self {
+        TaskError::Internal(e.to_string())
+    }
+}
+
+impl From<diesel::result::Error> for TaskError {
+    fn from(e: diesel::result::Error) -> Self {
+        TaskError::Internal(e.to_string())
+    }
+}
+
+impl From<ConnectionError> for TaskError {
+    fn from(e: ConnectionError) -> Self {
+        TaskError::Internal(e.to_string())
+    }
+}
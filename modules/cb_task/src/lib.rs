@@ -1,22 +1,34 @@
 // src/lib.rs
 
+pub mod error;
+
 pub mod controllers {
+    pub mod project_controller;
     pub mod task_controller;
 }
 
 pub mod mappers {
+    pub mod project_mapper;
     pub mod task_mapper;
+    pub mod task_status_mapper;
 }
 
 pub mod models {
+    pub mod project;
     pub mod task;
     pub mod task_filter;
+    pub mod task_progress;
+    pub mod task_status;
 }
 
 pub mod routes {
+    pub mod project_route;
     pub mod task_route;
+    pub mod task_status_route;
 }
 
 pub mod services {
+    pub mod project_service;
     pub mod task_service;
+    pub mod task_status_service;
 }
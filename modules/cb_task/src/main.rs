@@ -1,63 +1,54 @@
 #[macro_use]
 extern crate rocket;
 
+use crab_rocket_task::routes::project_route::*;
 use crab_rocket_task::routes::task_route::*;
-use crab_rocket_utils;
+use crab_rocket_task::routes::task_status_route::*;
 use dotenvy::dotenv;
-use rocket::http::Method;
-use rocket_cors::{AllowedHeaders, AllowedOrigins};
 use std::env;
 #[launch]
 fn rocket() -> _ {
     // Clear environment variable before running.
     env::remove_var("DATABASE_URL");
-    // Load env
-    // env_variables::load_env();
     dotenv().ok();
+    let figment = crab_rocket_config::init();
 
     crab_rocket_utils::run_preload();
 
-    let allowed_origins = AllowedOrigins::All;
-    // You can also deserialize this
-    let cors = rocket_cors::CorsOptions {
-        allowed_origins,
-        allowed_methods: vec![
-            Method::Get,
-            Method::Post,
-            Method::Patch,
-            Method::Put,
-            Method::Delete,
-        ]
-        .into_iter()
-        .map(From::from)
-        .collect(),
-        allowed_headers: AllowedHeaders::some(&[
-            "Content-Type",
-            "Authorization",
-            "Another-Header", // 允许的其他头部信息
-            "Authorization",
-            "Accept",
-            "User-Agent",
-            "X-Requested-With",
-            "Referer",
-        ]),
-        allow_credentials: true,
-        ..Default::default()
-    }
-    .to_cors()
-    .unwrap();
+    let cors = crab_rocket_config::cors::cors();
 
-    rocket::build()
+    rocket::custom(figment)
         .mount(
             "/api",
             routes![
                 get_tasks,
                 filter_tasks,
+                get_my_tasks,
+                filter_my_tasks,
+                get_overdue_tasks,
                 get_task_by_id,
                 insert_single_task,
                 delete_task_by_id,
                 update_task_by_id,
-                options_task_filter
+                update_task_status,
+                get_task_subtasks,
+                get_task_progress,
+                get_task_blockers,
+                insert_task_blocker,
+                delete_task_blocker,
+                options_task_filter,
+                get_task_statuses,
+                insert_task_status,
+                insert_task_transition,
+                delete_task_transition,
+                options_task_status,
+                get_projects,
+                get_project_by_id,
+                get_project_tasks,
+                insert_project,
+                update_project_by_id,
+                delete_project_by_id,
+                options_project
             ],
         )
         .attach(cors)
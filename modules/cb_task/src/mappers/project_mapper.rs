@@ -0,0 +1,56 @@
+use crab_rocket_schema::schema::project_table;
+use crab_rocket_utils::time::get_e8_time;
+use diesel::dsl::exists;
+use diesel::{prelude::*, result::Error};
+
+use crate::models::project::{PatchProject, PostProject, Project};
+
+pub struct ProjectMapper {}
+
+impl ProjectMapper {
+    /// Every project, the latest first.
+    pub fn get_projects(conn: &mut PgConnection) -> Result<Vec<Project>, Error> {
+        project_table::table
+            .select(Project::as_select())
+            .order((project_table::created_at.desc(), project_table::project_id.desc()))
+            .load(conn)
+    }
+
+    pub fn get_project(conn: &mut PgConnection, pid: i32) -> Result<Project, Error> {
+        project_table::table.find(pid).select(Project::as_select()).first(conn)
+    }
+
+    pub fn add_project(conn: &mut PgConnection, obj: &PostProject) -> Result<Project, Error> {
+        diesel::insert_into(project_table::table)
+            .values(obj)
+            .returning(Project::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn update_project(
+        conn: &mut PgConnection,
+        pid: i32,
+        obj: &PatchProject,
+    ) -> Result<Project, Error> {
+        diesel::update(project_table::table.find(pid))
+            .set((
+                project_table::project_name.eq(obj.project_name()),
+                project_table::description.eq(obj.description()),
+                project_table::user_id.eq(obj.user_id()),
+                project_table::updated_at.eq(get_e8_time()),
+            ))
+            .returning(Project::as_returning())
+            .get_result(conn)
+    }
+
+    /// Deletes the project, its tasks stay without one.
+    pub fn delete_project(conn: &mut PgConnection, pid: i32) -> Result<Project, Error> {
+        diesel::delete(project_table::table.find(pid))
+            .returning(Project::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn project_exists(conn: &mut PgConnection, pid: i32) -> Result<bool, Error> {
+        diesel::select(exists(project_table::table.find(pid))).get_result(conn)
+    }
+}
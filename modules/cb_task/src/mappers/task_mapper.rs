@@ -1,9 +1,16 @@
-use crate::models::task::{PostTask, PatchTask, Task};
-use crate::models::task_filter::TaskFilter;
+use crate::models::task::{PatchTask, PostTask, Task};
+use crate::models::task_filter::{TaskFilter, TaskSort};
+use crate::models::task_progress::TaskNode;
+use chrono::{NaiveDate, NaiveDateTime};
 use crab_rocket_schema::schema::task_table::dsl; //配合下面的 `tasks.filter()`
 use crab_rocket_schema::schema::task_table::{self};
+use crab_rocket_schema::schema::{task_dependency_table, task_status_table, user_table};
 use crab_rocket_utils::time::get_e8_time;
+use diesel::dsl::{not, sql};
+use diesel::pg::Pg;
 use diesel::prelude::*;
+use diesel::sql_query;
+use diesel::sql_types::Integer;
 use obj_traits::mapper::mapper_crud::MapperCRUD;
 use obj_traits::request::pagination_request_param::{Pagination, PaginationParam};
 use obj_traits::request::request_param::RequestParam;
@@ -11,6 +18,15 @@ use obj_traits::response::data::Data;
 
 pub struct TaskMapper {}
 
+/// Columns a [`PatchTask`] keeps when it leaves them out, they cannot be empty.
+#[derive(AsChangeset)]
+#[diesel(table_name = task_table)]
+struct KeptColumns<'a> {
+    status_id: Option<i32>,
+    priority: Option<i32>,
+    labels: Option<&'a Vec<String>>,
+}
+
 impl MapperCRUD for TaskMapper {
     type Item = Task;
     type PostItem = PostTask;
@@ -35,8 +51,8 @@ impl MapperCRUD for TaskMapper {
         //
         // limit 始终为 per_page
         // 计算分页相关
-        let page = (param.pagination.offset.unwrap() / param.pagination.limit.unwrap()) + 1;
-        let per_page = param.pagination.limit.unwrap();
+        let page = (param.pagination.offset_or_default() / param.pagination.limit_or_default()) + 1;
+        let per_page = param.pagination.limit_or_default();
         // 获取总记录数
         let total_count = dsl::task_table.count().get_result::<i64>(conn)? as i32;
         // 计算总页数
@@ -56,6 +72,7 @@ impl MapperCRUD for TaskMapper {
         // 分页查询
         let data = dsl::task_table
             .order(dsl::updated_at.desc())
+            .then_order_by(dsl::task_id.desc())
             .limit(per_page as i64)
             .offset(((page - 1) * per_page) as i64)
             .load::<Task>(conn)?;
@@ -91,12 +108,23 @@ impl MapperCRUD for TaskMapper {
         pid: i32,
         obj: &PatchTask,
     ) -> Result<Task, diesel::result::Error> {
+        let kept = KeptColumns {
+            status_id: obj.status_id(),
+            priority: obj.priority(),
+            labels: obj.labels().as_ref(),
+        };
         diesel::update(dsl::task_table.filter(dsl::task_id.eq(pid)))
             .set((
                 task_table::title.eq(obj.title()),
                 task_table::content.eq(obj.content()),
                 task_table::updated_at.eq(Some(get_e8_time())), //Update time
                 task_table::user_id.eq(obj.user_id()),
+                task_table::start_date.eq(obj.start_date()),
+                task_table::due_date.eq(obj.due_date()),
+                task_table::assignee_id.eq(obj.assignee_id()),
+                task_table::project_id.eq(obj.project_id()),
+                task_table::parent_id.eq(obj.parent_id()),
+                kept,
             ))
             .get_result(conn)
     }
@@ -119,10 +147,12 @@ impl MapperCRUD for TaskMapper {
         //
         // limit 始终为 per_page
         // 计算分页相关
-        let page = (param.pagination.offset.unwrap() / param.pagination.limit.unwrap()) + 1;
-        let per_page = param.pagination.limit.unwrap();
-        // 获取总记录数
-        let total_count = dsl::task_table.count().get_result::<i64>(conn)? as i32;
+        let page = (param.pagination.offset_or_default() / param.pagination.limit_or_default()) + 1;
+        let per_page = param.pagination.limit_or_default();
+        let filter = param.filter.as_ref();
+        let today = get_e8_time().date();
+        // 获取总记录数, of the filtered tasks
+        let total_count = filtered(filter, today).count().get_result::<i64>(conn)? as i32;
         // 计算总页数
         let total_pages = (total_count + per_page - 1) / per_page;
 
@@ -137,51 +167,294 @@ impl MapperCRUD for TaskMapper {
             Some(format!("?limit={}&offset={}", per_page, previous_page_offset)),
         );
 
-        let mut query = dsl::task_table.into_boxed();
-
+        let sort_by = filter.and_then(|f| f.sort_by).unwrap_or_default();
+        let descending = filter.and_then(|f| f.descending);
         // 分页查询
-        query = query
-            .order(dsl::created_at.desc())
+        let query = sorted(filtered(filter, today), sort_by, descending)
             .limit(per_page as i64)
             .offset(((page - 1) * per_page) as i64);
 
-        let filter = &param.filter;
-        if let Some(f) = filter {
-            if let Some(id) = &f.id {
-                query = query.filter(dsl::task_id.eq(id));
-            }
-            if let Some(title) = &f.title {
-                query = query.filter(dsl::title.like(format!("%{}%", title)));
-            }
-            if let Some(content) = &f.content {
-                query = query.filter(dsl::content.like(format!("%{}%", content)));
-            }
-            if let Some(created_at_min) = &f.created_at_min {
-                query = query.filter(dsl::created_at.ge(created_at_min));
-            }
-            if let Some(created_at_max) = &f.created_at_max {
-                query = query.filter(dsl::created_at.le(created_at_max));
-            }
-            if let Some(updated_at_min) = &f.updated_at_min {
-                query = query.filter(dsl::updated_at.ge(updated_at_min));
-            }
-            if let Some(updated_at_max) = &f.updated_at_max {
-                query = query.filter(dsl::updated_at.le(updated_at_max));
-            }
-            if let Some(user_id) = &f.user_id {
-                query = query.filter(dsl::user_id.eq(user_id));
-            }
-        }
         let data = query.load::<Task>(conn)?;
         let body = Data::new(data, pagination);
         Ok(body)
     }
 }
 
+impl TaskMapper {
+    /// Moves the task to `status_id`, `completed_at` tells whether that closed it.
+    pub fn set_status(
+        conn: &mut PgConnection,
+        pid: i32,
+        status_id: i32,
+        completed_at: Option<NaiveDateTime>,
+    ) -> Result<Task, diesel::result::Error> {
+        diesel::update(dsl::task_table.find(pid))
+            .set((
+                dsl::status_id.eq(status_id),
+                dsl::completed_at.eq(completed_at),
+                dsl::updated_at.eq(Some(get_e8_time())),
+            ))
+            .get_result(conn)
+    }
+
+    pub fn user_exists(
+        conn: &mut PgConnection,
+        user_id: i32,
+    ) -> Result<bool, diesel::result::Error> {
+        diesel::select(diesel::dsl::exists(user_table::table.find(user_id))).get_result(conn)
+    }
+
+    /// The direct subtasks of `pid`.
+    pub fn get_subtasks(
+        conn: &mut PgConnection,
+        pid: i32,
+    ) -> Result<Vec<Task>, diesel::result::Error> {
+        dsl::task_table.filter(dsl::parent_id.eq(pid)).order(dsl::task_id).load(conn)
+    }
+
+    /// `pid` followed by its subtasks at any depth. The walk stops at the first task met twice.
+    pub fn subtree(
+        conn: &mut PgConnection,
+        pid: i32,
+    ) -> Result<Vec<TaskNode>, diesel::result::Error> {
+        sql_query(format!(
+            "WITH RECURSIVE subtree AS ( \
+                 SELECT t.task_id, 0 AS depth, ARRAY[t.task_id] AS path, false AS cycle \
+                 FROM task_table t WHERE t.task_id = $1 \
+               UNION ALL \
+                 SELECT c.task_id, p.depth + 1, p.path || c.task_id, c.task_id = ANY(p.path) \
+                 FROM subtree p JOIN task_table c ON c.parent_id = p.task_id \
+                 WHERE NOT p.cycle \
+             ) \
+             SELECT {NODE_COLUMNS} \
+             FROM subtree p JOIN task_table t ON t.task_id = p.task_id \
+             WHERE NOT p.cycle \
+             ORDER BY p.depth, t.task_id"
+        ))
+        .bind::<Integer, _>(pid)
+        .load(conn)
+    }
+
+    pub fn project_nodes(
+        conn: &mut PgConnection,
+        project_id: i32,
+    ) -> Result<Vec<TaskNode>, diesel::result::Error> {
+        sql_query(format!(
+            "SELECT {NODE_COLUMNS} FROM task_table t WHERE t.project_id = $1 ORDER BY t.task_id"
+        ))
+        .bind::<Integer, _>(project_id)
+        .load(conn)
+    }
+
+    /// The tasks `pid` waits on.
+    pub fn get_blockers(
+        conn: &mut PgConnection,
+        pid: i32,
+    ) -> Result<Vec<Task>, diesel::result::Error> {
+        dsl::task_table
+            .filter(
+                dsl::task_id.eq_any(
+                    task_dependency_table::table
+                        .filter(task_dependency_table::task_id.eq(pid))
+                        .select(task_dependency_table::blocker_id),
+                ),
+            )
+            .order(dsl::task_id)
+            .load(conn)
+    }
+
+    /// The blockers of `pid` not in a closed status.
+    pub fn open_blockers(
+        conn: &mut PgConnection,
+        pid: i32,
+    ) -> Result<Vec<i32>, diesel::result::Error> {
+        task_dependency_table::table
+            .inner_join(dsl::task_table.on(dsl::task_id.eq(task_dependency_table::blocker_id)))
+            .inner_join(
+                task_status_table::table.on(task_status_table::task_status_id.eq(dsl::status_id)),
+            )
+            .filter(task_dependency_table::task_id.eq(pid))
+            .filter(task_status_table::closed.eq(false))
+            .select(task_dependency_table::blocker_id)
+            .order(task_dependency_table::blocker_id)
+            .load(conn)
+    }
+
+    /// Whether `pid` waits on `task_id`, directly or through the tasks it waits on.
+    pub fn waits_on(
+        conn: &mut PgConnection,
+        pid: i32,
+        task_id: i32,
+    ) -> Result<bool, diesel::result::Error> {
+        #[derive(QueryableByName)]
+        struct Found {
+            #[diesel(sql_type = diesel::sql_types::Bool)]
+            found: bool,
+        }
+        let found: Found = sql_query(
+            "WITH RECURSIVE blockers AS ( \
+                 SELECT d.blocker_id FROM task_dependency_table d WHERE d.task_id = $1 \
+               UNION \
+                 SELECT d.blocker_id \
+                 FROM blockers b JOIN task_dependency_table d ON d.task_id = b.blocker_id \
+             ) \
+             SELECT EXISTS (SELECT 1 FROM blockers WHERE blocker_id = $2) AS found",
+        )
+        .bind::<Integer, _>(pid)
+        .bind::<Integer, _>(task_id)
+        .get_result(conn)?;
+        Ok(found.found)
+    }
+
+    /// Makes `pid` wait on `blocker_id`, a second time is a no-op.
+    pub fn add_blocker(
+        conn: &mut PgConnection,
+        pid: i32,
+        blocker_id: i32,
+    ) -> Result<usize, diesel::result::Error> {
+        diesel::insert_into(task_dependency_table::table)
+            .values((
+                task_dependency_table::task_id.eq(pid),
+                task_dependency_table::blocker_id.eq(blocker_id),
+            ))
+            .on_conflict_do_nothing()
+            .execute(conn)
+    }
+
+    pub fn delete_blocker(
+        conn: &mut PgConnection,
+        pid: i32,
+        blocker_id: i32,
+    ) -> Result<usize, diesel::result::Error> {
+        diesel::delete(task_dependency_table::table.find((pid, blocker_id))).execute(conn)
+    }
+}
+
+/// The columns of a [`TaskNode`] for a row `t` of `task_table`.
+const NODE_COLUMNS: &str = "t.task_id, t.parent_id, (SELECT s.closed FROM task_status_table s \
+     WHERE s.task_status_id = t.status_id) AS closed";
+
+/// The tasks the filter lets through, unsorted.
+fn filtered(filter: Option<&TaskFilter>, today: NaiveDate) -> task_table::BoxedQuery<'_, Pg> {
+    let mut query = dsl::task_table.into_boxed();
+    let Some(f) = filter else {
+        return query;
+    };
+    if let Some(id) = &f.id {
+        query = query.filter(dsl::task_id.eq(id));
+    }
+    if let Some(title) = &f.title {
+        query = query.filter(dsl::title.like(format!("%{}%", title)));
+    }
+    if let Some(content) = &f.content {
+        query = query.filter(dsl::content.like(format!("%{}%", content)));
+    }
+    if let Some(created_at_min) = &f.created_at_min {
+        query = query.filter(dsl::created_at.ge(created_at_min));
+    }
+    if let Some(created_at_max) = &f.created_at_max {
+        query = query.filter(dsl::created_at.le(created_at_max));
+    }
+    if let Some(updated_at_min) = &f.updated_at_min {
+        query = query.filter(dsl::updated_at.ge(updated_at_min));
+    }
+    if let Some(updated_at_max) = &f.updated_at_max {
+        query = query.filter(dsl::updated_at.le(updated_at_max));
+    }
+    if let Some(user_id) = &f.user_id {
+        query = query.filter(dsl::user_id.eq(user_id));
+    }
+    if let Some(status_id) = &f.status_id {
+        query = query.filter(dsl::status_id.eq(status_id));
+    }
+    let closed_statuses = || {
+        task_status_table::table
+            .filter(task_status_table::closed.eq(true))
+            .select(task_status_table::task_status_id)
+    };
+    match f.closed {
+        Some(true) => query = query.filter(dsl::status_id.eq_any(closed_statuses())),
+        Some(false) => query = query.filter(not(dsl::status_id.eq_any(closed_statuses()))),
+        None => {}
+    }
+    if let Some(priority_min) = &f.priority_min {
+        query = query.filter(dsl::priority.ge(priority_min));
+    }
+    if let Some(priority_max) = &f.priority_max {
+        query = query.filter(dsl::priority.le(priority_max));
+    }
+    if let Some(start_date_min) = &f.start_date_min {
+        query = query.filter(dsl::start_date.ge(start_date_min));
+    }
+    if let Some(start_date_max) = &f.start_date_max {
+        query = query.filter(dsl::start_date.le(start_date_max));
+    }
+    if let Some(due_date_min) = &f.due_date_min {
+        query = query.filter(dsl::due_date.ge(due_date_min));
+    }
+    if let Some(due_date_max) = &f.due_date_max {
+        query = query.filter(dsl::due_date.le(due_date_max));
+    }
+    if let Some(assignee_id) = &f.assignee_id {
+        query = query.filter(dsl::assignee_id.eq(assignee_id));
+    }
+    if let Some(project_id) = &f.project_id {
+        query = query.filter(dsl::project_id.eq(project_id));
+    }
+    if let Some(parent_id) = &f.parent_id {
+        query = query.filter(dsl::parent_id.eq(parent_id));
+    }
+    if let Some(labels) = &f.labels {
+        query = query.filter(dsl::labels.contains(labels));
+    }
+    if f.overdue == Some(true) {
+        query = query
+            .filter(dsl::due_date.lt(today))
+            .filter(not(dsl::status_id.eq_any(closed_statuses())));
+    }
+    query
+}
+
+/// Orders by `sort_by`, the latest first for timestamps and the earliest for everything else
+/// unless `descending` says otherwise. Ties are broken by `task_id`.
+fn sorted(
+    query: task_table::BoxedQuery<'_, Pg>,
+    sort_by: TaskSort,
+    descending: Option<bool>,
+) -> task_table::BoxedQuery<'_, Pg> {
+    let descending =
+        descending.unwrap_or(matches!(sort_by, TaskSort::CreatedAt | TaskSort::UpdatedAt));
+    let position = || {
+        sql::<Integer>(
+            "(SELECT s.position FROM task_status_table s \
+             WHERE s.task_status_id = task_table.status_id)",
+        )
+    };
+    let query = match (sort_by, descending) {
+        (TaskSort::CreatedAt, false) => query.order(dsl::created_at.asc().nulls_last()),
+        (TaskSort::CreatedAt, true) => query.order(dsl::created_at.desc().nulls_last()),
+        (TaskSort::UpdatedAt, false) => query.order(dsl::updated_at.asc().nulls_last()),
+        (TaskSort::UpdatedAt, true) => query.order(dsl::updated_at.desc().nulls_last()),
+        (TaskSort::Title, false) => query.order(dsl::title.asc()),
+        (TaskSort::Title, true) => query.order(dsl::title.desc()),
+        (TaskSort::Status, false) => query.order(position().asc()),
+        (TaskSort::Status, true) => query.order(position().desc()),
+        (TaskSort::Priority, false) => query.order(dsl::priority.asc()),
+        (TaskSort::Priority, true) => query.order(dsl::priority.desc()),
+        (TaskSort::StartDate, false) => query.order(dsl::start_date.asc().nulls_last()),
+        (TaskSort::StartDate, true) => query.order(dsl::start_date.desc().nulls_last()),
+        (TaskSort::DueDate, false) => query.order(dsl::due_date.asc().nulls_last()),
+        (TaskSort::DueDate, true) => query.order(dsl::due_date.desc().nulls_last()),
+        (TaskSort::Assignee, false) => query.order(dsl::assignee_id.asc().nulls_last()),
+        (TaskSort::Assignee, true) => query.order(dsl::assignee_id.desc().nulls_last()),
+    };
+    query.then_order_by(dsl::task_id.asc())
+}
+
 #[cfg(test)]
 mod tests {
     use super::TaskMapper;
-    use crate::models::task::{PostTask, PatchTask};
+    use crate::models::task::{PatchTask, PostTask};
     use crab_rocket_schema::establish_pg_connection;
     use obj_traits::mapper::mapper_crud::MapperCRUD;
     use obj_traits::request::pagination_request_param::{PaginationParam, PaginationParamTrait};
@@ -192,7 +465,7 @@ mod tests {
         match establish_pg_connection() {
             Ok(mut conn) => {
                 let task = PostTask::new(
-                    "title".to_string().into(),
+                    "title".to_string(),
                     "new content".to_string().into(),
                     Some(chrono::Local::now().naive_utc()),
                     Some(chrono::Local::now().naive_utc()),
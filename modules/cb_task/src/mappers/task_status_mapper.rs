@@ -0,0 +1,58 @@
+use crab_rocket_schema::schema::{task_status_table, task_transition_table};
+use diesel::dsl::exists;
+use diesel::{prelude::*, result::Error};
+
+use crate::models::task_status::{PostTaskStatus, TaskStatus, TaskTransition};
+
+pub struct TaskStatusMapper {}
+
+impl TaskStatusMapper {
+    /// The workflow in order.
+    pub fn get_statuses(conn: &mut PgConnection) -> Result<Vec<TaskStatus>, Error> {
+        task_status_table::table
+            .select(TaskStatus::as_select())
+            .order((task_status_table::position, task_status_table::task_status_id))
+            .load(conn)
+    }
+
+    pub fn get_status(conn: &mut PgConnection, pid: i32) -> Result<TaskStatus, Error> {
+        task_status_table::table.find(pid).select(TaskStatus::as_select()).first(conn)
+    }
+
+    pub fn add_status(conn: &mut PgConnection, obj: &PostTaskStatus) -> Result<TaskStatus, Error> {
+        diesel::insert_into(task_status_table::table)
+            .values(obj)
+            .returning(TaskStatus::as_returning())
+            .get_result(conn)
+    }
+
+    pub fn get_transitions(conn: &mut PgConnection) -> Result<Vec<TaskTransition>, Error> {
+        task_transition_table::table
+            .select(TaskTransition::as_select())
+            .order((task_transition_table::from_status_id, task_transition_table::to_status_id))
+            .load(conn)
+    }
+
+    /// Allows the move, allowing it twice is a no-op.
+    pub fn add_transition(conn: &mut PgConnection, obj: &TaskTransition) -> Result<usize, Error> {
+        diesel::insert_into(task_transition_table::table)
+            .values(obj)
+            .on_conflict_do_nothing()
+            .execute(conn)
+    }
+
+    pub fn delete_transition(
+        conn: &mut PgConnection,
+        obj: &TaskTransition,
+    ) -> Result<usize, Error> {
+        diesel::delete(task_transition_table::table.find((obj.from_status_id, obj.to_status_id)))
+            .execute(conn)
+    }
+
+    pub fn transition_exists(conn: &mut PgConnection, obj: &TaskTransition) -> Result<bool, Error> {
+        diesel::select(exists(
+            task_transition_table::table.find((obj.from_status_id, obj.to_status_id)),
+        ))
+        .get_result(conn)
+    }
+}
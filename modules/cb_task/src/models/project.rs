@@ -0,0 +1,107 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use rocket::serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::models::task_progress::TaskProgress;
+
+/// A group of tasks, owned by a user like a task.
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+#[diesel(table_name = crab_rocket_schema::schema::project_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Project {
+    project_id: i32,
+    project_name: String,
+    description: Option<String>,
+    user_id: Option<i32>,
+    created_at: NaiveDateTime,
+    updated_at: NaiveDateTime,
+}
+
+impl Project {
+    pub fn project_id(&self) -> i32 {
+        self.project_id
+    }
+
+    pub fn project_name(&self) -> &str {
+        &self.project_name
+    }
+
+    pub fn description(&self) -> &Option<String> {
+        &self.description
+    }
+
+    pub fn user_id(&self) -> Option<i32> {
+        self.user_id
+    }
+}
+
+/// A new project, owned by the caller unless a moderator names another `user_id`.
+#[derive(Insertable, Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(crate = "rocket::serde")]
+#[diesel(table_name = crab_rocket_schema::schema::project_table)]
+pub struct PostProject {
+    project_name: String,
+    description: Option<String>,
+    user_id: Option<i32>,
+}
+
+impl PostProject {
+    pub fn new(project_name: String, description: Option<String>, user_id: Option<i32>) -> Self {
+        Self {
+            project_name,
+            description,
+            user_id,
+        }
+    }
+
+    pub fn project_name(&self) -> &str {
+        &self.project_name
+    }
+
+    pub fn user_id(&self) -> Option<i32> {
+        self.user_id
+    }
+
+    pub fn set_user_id(&mut self, user_id: Option<i32>) {
+        self.user_id = user_id;
+    }
+}
+
+/// Replaces the project, a missing `description` is cleared and a missing `user_id` keeps
+/// the owner.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct PatchProject {
+    project_name: String,
+    description: Option<String>,
+    user_id: Option<i32>,
+}
+
+impl PatchProject {
+    pub fn project_name(&self) -> &str {
+        &self.project_name
+    }
+
+    pub fn description(&self) -> &Option<String> {
+        &self.description
+    }
+
+    pub fn user_id(&self) -> Option<i32> {
+        self.user_id
+    }
+
+    pub fn set_user_id(&mut self, user_id: Option<i32>) {
+        self.user_id = user_id;
+    }
+}
+
+/// A project with how far its tasks got.
+#[derive(Serialize, Deserialize, ToSchema, Debug, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct ProjectDetail {
+    #[serde(flatten)]
+    pub project: Project,
+    pub progress: TaskProgress,
+}
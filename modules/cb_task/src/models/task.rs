@@ -1,9 +1,15 @@
 use std::fmt::Display;
 
+use chrono::{NaiveDate, NaiveDateTime};
 use diesel::prelude::*;
 use rocket::serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+pub const LOW: i32 = 1;
+pub const NORMAL: i32 = 2;
+pub const HIGH: i32 = 3;
+pub const URGENT: i32 = 4;
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
 #[serde(crate = "rocket::serde")]
 #[derive(Queryable, Selectable, Insertable)]
@@ -16,6 +22,20 @@ pub struct Task {
     created_at: Option<chrono::NaiveDateTime>,
     updated_at: Option<chrono::NaiveDateTime>,
     user_id: Option<i32>,
+    /// A status of the workflow, see [`TaskStatus`](crate::models::task_status::TaskStatus).
+    status_id: i32,
+    /// From [`LOW`] to [`URGENT`].
+    priority: i32,
+    start_date: Option<NaiveDate>,
+    due_date: Option<NaiveDate>,
+    /// The user working on the task, `user_id` is who owns it.
+    assignee_id: Option<i32>,
+    labels: Vec<String>,
+    /// When the task moved into a closed status, `None` while it is open.
+    completed_at: Option<NaiveDateTime>,
+    project_id: Option<i32>,
+    /// The task this one is a subtask of.
+    parent_id: Option<i32>,
 }
 
 impl Task {
@@ -43,6 +63,42 @@ impl Task {
         self.user_id
     }
 
+    pub fn status_id(&self) -> i32 {
+        self.status_id
+    }
+
+    pub fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    pub fn start_date(&self) -> Option<NaiveDate> {
+        self.start_date
+    }
+
+    pub fn due_date(&self) -> Option<NaiveDate> {
+        self.due_date
+    }
+
+    pub fn assignee_id(&self) -> Option<i32> {
+        self.assignee_id
+    }
+
+    pub fn labels(&self) -> &[String] {
+        &self.labels
+    }
+
+    pub fn completed_at(&self) -> Option<NaiveDateTime> {
+        self.completed_at
+    }
+
+    pub fn project_id(&self) -> Option<i32> {
+        self.project_id
+    }
+
+    pub fn parent_id(&self) -> Option<i32> {
+        self.parent_id
+    }
+
     pub fn set_task_id(&mut self, task_id: i32) {
         self.task_id = task_id;
     }
@@ -67,6 +123,7 @@ impl Task {
         self.user_id = user_id;
     }
 
+    /// A task in status `1`, of normal priority and without dates, assignee or labels.
     pub fn new(
         task_id: i32,
         title: String,
@@ -82,6 +139,9 @@ impl Task {
             created_at,
             updated_at,
             user_id,
+            status_id: 1,
+            priority: NORMAL,
+            ..Default::default()
         }
     }
 }
@@ -115,6 +175,8 @@ impl Display for TaskList {
     }
 }
 
+/// A new task, without `status_id` it starts in the first status of the workflow. A subtask
+/// without `project_id` joins the project of its parent.
 #[derive(Insertable, Debug, Clone, Serialize, Deserialize)]
 #[serde(crate = "rocket::serde")]
 #[diesel(table_name = crab_rocket_schema::schema::task_table)]
@@ -124,6 +186,15 @@ pub struct PostTask {
     created_at: Option<chrono::NaiveDateTime>,
     updated_at: Option<chrono::NaiveDateTime>,
     user_id: Option<i32>,
+    status_id: Option<i32>,
+    /// Defaults to [`NORMAL`].
+    priority: Option<i32>,
+    start_date: Option<NaiveDate>,
+    due_date: Option<NaiveDate>,
+    assignee_id: Option<i32>,
+    labels: Option<Vec<String>>,
+    project_id: Option<i32>,
+    parent_id: Option<i32>,
 }
 
 impl PostTask {
@@ -167,6 +238,66 @@ impl PostTask {
         self.user_id = user_id;
     }
 
+    pub fn status_id(&self) -> Option<i32> {
+        self.status_id
+    }
+
+    pub fn priority(&self) -> Option<i32> {
+        self.priority
+    }
+
+    pub fn start_date(&self) -> Option<NaiveDate> {
+        self.start_date
+    }
+
+    pub fn due_date(&self) -> Option<NaiveDate> {
+        self.due_date
+    }
+
+    pub fn assignee_id(&self) -> Option<i32> {
+        self.assignee_id
+    }
+
+    pub fn labels(&self) -> &Option<Vec<String>> {
+        &self.labels
+    }
+
+    pub fn set_status_id(&mut self, status_id: Option<i32>) {
+        self.status_id = status_id;
+    }
+
+    pub fn set_priority(&mut self, priority: Option<i32>) {
+        self.priority = priority;
+    }
+
+    pub fn set_start_date(&mut self, start_date: Option<NaiveDate>) {
+        self.start_date = start_date;
+    }
+
+    pub fn set_due_date(&mut self, due_date: Option<NaiveDate>) {
+        self.due_date = due_date;
+    }
+
+    pub fn set_assignee_id(&mut self, assignee_id: Option<i32>) {
+        self.assignee_id = assignee_id;
+    }
+
+    pub fn set_labels(&mut self, labels: Option<Vec<String>>) {
+        self.labels = labels;
+    }
+
+    pub fn project_id(&self) -> Option<i32> {
+        self.project_id
+    }
+
+    pub fn parent_id(&self) -> Option<i32> {
+        self.parent_id
+    }
+
+    pub fn set_project_id(&mut self, project_id: Option<i32>) {
+        self.project_id = project_id;
+    }
+
     pub fn new(
         title: String,
         content: Option<String>,
@@ -180,10 +311,21 @@ impl PostTask {
             created_at,
             updated_at,
             user_id,
+            status_id: None,
+            priority: None,
+            start_date: None,
+            due_date: None,
+            assignee_id: None,
+            labels: None,
+            project_id: None,
+            parent_id: None,
         }
     }
 }
 
+/// Replaces the task. Like `content`, a missing `start_date`, `due_date`, `assignee_id`,
+/// `project_id` or `parent_id` is cleared; a missing `status_id`, `priority` or `labels` is
+/// kept.
 #[derive(Insertable, Debug, Clone, Serialize, Deserialize)]
 #[serde(crate = "rocket::serde")]
 #[diesel(table_name = crab_rocket_schema::schema::task_table)]
@@ -191,6 +333,14 @@ pub struct PatchTask {
     title: String,
     content: Option<String>,
     user_id: Option<i32>,
+    status_id: Option<i32>,
+    priority: Option<i32>,
+    start_date: Option<NaiveDate>,
+    due_date: Option<NaiveDate>,
+    assignee_id: Option<i32>,
+    labels: Option<Vec<String>>,
+    project_id: Option<i32>,
+    parent_id: Option<i32>,
 }
 
 impl PatchTask {
@@ -218,11 +368,63 @@ impl PatchTask {
         self.user_id = user_id;
     }
 
+    pub fn status_id(&self) -> Option<i32> {
+        self.status_id
+    }
+
+    pub fn priority(&self) -> Option<i32> {
+        self.priority
+    }
+
+    pub fn start_date(&self) -> Option<NaiveDate> {
+        self.start_date
+    }
+
+    pub fn due_date(&self) -> Option<NaiveDate> {
+        self.due_date
+    }
+
+    pub fn assignee_id(&self) -> Option<i32> {
+        self.assignee_id
+    }
+
+    pub fn labels(&self) -> &Option<Vec<String>> {
+        &self.labels
+    }
+
+    pub fn set_status_id(&mut self, status_id: Option<i32>) {
+        self.status_id = status_id;
+    }
+
+    pub fn set_labels(&mut self, labels: Option<Vec<String>>) {
+        self.labels = labels;
+    }
+
+    pub fn project_id(&self) -> Option<i32> {
+        self.project_id
+    }
+
+    pub fn parent_id(&self) -> Option<i32> {
+        self.parent_id
+    }
+
+    pub fn set_project_id(&mut self, project_id: Option<i32>) {
+        self.project_id = project_id;
+    }
+
     pub fn new(title: String, content: Option<String>, user_id: Option<i32>) -> Self {
         Self {
             title,
             content,
             user_id,
+            status_id: None,
+            priority: None,
+            start_date: None,
+            due_date: None,
+            assignee_id: None,
+            labels: None,
+            project_id: None,
+            parent_id: None,
         }
     }
 }
@@ -238,6 +440,7 @@ mod test {
             created_at: Some(crab_rocket_utils::time::get_e8_time()),
             updated_at: Some(crab_rocket_utils::time::get_e8_time()),
             user_id: Some(1),
+            ..Default::default()
         };
         println!("{task}");
 
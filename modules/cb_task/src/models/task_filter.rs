@@ -1,7 +1,7 @@
-use chrono::NaiveDateTime;
+use chrono::{NaiveDate, NaiveDateTime};
 use rocket::serde::Deserialize;
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Default)]
 #[serde(crate = "rocket::serde")]
 pub struct TaskFilter {
     pub id: Option<i32>,
@@ -12,4 +12,41 @@ pub struct TaskFilter {
     pub updated_at_min: Option<NaiveDateTime>,
     pub updated_at_max: Option<NaiveDateTime>,
     pub user_id: Option<i32>,
+    pub status_id: Option<i32>,
+    /// Tasks in a closed status, or in an open one.
+    pub closed: Option<bool>,
+    pub priority_min: Option<i32>,
+    pub priority_max: Option<i32>,
+    pub start_date_min: Option<NaiveDate>,
+    pub start_date_max: Option<NaiveDate>,
+    pub due_date_min: Option<NaiveDate>,
+    pub due_date_max: Option<NaiveDate>,
+    pub assignee_id: Option<i32>,
+    pub project_id: Option<i32>,
+    /// The subtasks of a task.
+    pub parent_id: Option<i32>,
+    /// Tasks carrying every one of the labels.
+    pub labels: Option<Vec<String>>,
+    /// Open tasks due before today.
+    pub overdue: Option<bool>,
+    /// `created_at` by default.
+    pub sort_by: Option<TaskSort>,
+    /// Defaults to `true` for `created_at` and `updated_at`, `false` otherwise.
+    pub descending: Option<bool>,
+}
+
+/// What tasks are sorted by, tasks without a date or an assignee come last.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(crate = "rocket::serde", rename_all = "snake_case")]
+pub enum TaskSort {
+    #[default]
+    CreatedAt,
+    UpdatedAt,
+    Title,
+    /// By the position of the status in the workflow.
+    Status,
+    Priority,
+    StartDate,
+    DueDate,
+    Assignee,
 }
@@ -0,0 +1,155 @@
+use std::collections::{HashMap, HashSet};
+
+use diesel::sql_types::{Bool, Int4, Nullable};
+use diesel::QueryableByName;
+use rocket::serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A task of a subtree or a project with whether its status is closed, as read by
+/// [`TaskMapper::subtree`](crate::mappers::task_mapper::TaskMapper::subtree).
+#[derive(QueryableByName, Debug, Clone, Copy, Default)]
+pub struct TaskNode {
+    #[diesel(sql_type = Int4)]
+    task_id: i32,
+    #[diesel(sql_type = Nullable<Int4>)]
+    parent_id: Option<i32>,
+    #[diesel(sql_type = Bool)]
+    closed: bool,
+}
+
+impl TaskNode {
+    pub fn task_id(&self) -> i32 {
+        self.task_id
+    }
+
+    pub fn parent_id(&self) -> Option<i32> {
+        self.parent_id
+    }
+
+    pub fn closed(&self) -> bool {
+        self.closed
+    }
+}
+
+/// How far a task or a project got. A closed task is done, an open one is as far as the
+/// average of its subtasks, and an open one without subtasks is not started. A project is as
+/// far as the average of its top-level tasks.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, Default, PartialEq)]
+#[serde(crate = "rocket::serde")]
+pub struct TaskProgress {
+    /// The subtasks of a task at any depth, every task of a project.
+    tasks: usize,
+    closed: usize,
+    /// Percent, to one decimal.
+    progress: f64,
+}
+
+impl TaskProgress {
+    /// Of `task`, rolled up from its `subtasks` at any depth.
+    pub fn of_task(task: &TaskNode, subtasks: &[TaskNode]) -> Self {
+        let children = children(subtasks);
+        Self::new(subtasks, Self::done(task, &children, &mut HashSet::new()))
+    }
+
+    /// Of a project, rolled up from its `tasks` without a parent in the project.
+    pub fn of_project(tasks: &[TaskNode]) -> Self {
+        let ids: HashSet<i32> = tasks.iter().map(|t| t.task_id).collect();
+        let children = children(tasks);
+        let mut seen = HashSet::new();
+        let done: Vec<f64> = tasks
+            .iter()
+            .filter(|t| !t.parent_id.is_some_and(|parent_id| ids.contains(&parent_id)))
+            .map(|root| Self::done(root, &children, &mut seen))
+            .collect();
+        Self::new(tasks, average(&done))
+    }
+
+    fn new(tasks: &[TaskNode], done: f64) -> Self {
+        TaskProgress {
+            tasks: tasks.len(),
+            closed: tasks.iter().filter(|t| t.closed).count(),
+            progress: (done * 1000.0).round() / 10.0,
+        }
+    }
+
+    /// Of the task itself, between `0` and `1`. A task met twice counts as not started.
+    fn done(
+        node: &TaskNode,
+        children: &HashMap<i32, Vec<&TaskNode>>,
+        seen: &mut HashSet<i32>,
+    ) -> f64 {
+        if !seen.insert(node.task_id) {
+            return 0.0;
+        }
+        if node.closed {
+            return 1.0;
+        }
+        let subtasks = children.get(&node.task_id).map(Vec::as_slice).unwrap_or_default();
+        let done: Vec<f64> =
+            subtasks.iter().map(|child| Self::done(child, children, seen)).collect();
+        average(&done)
+    }
+
+    pub fn tasks(&self) -> usize {
+        self.tasks
+    }
+
+    pub fn closed(&self) -> usize {
+        self.closed
+    }
+
+    pub fn progress(&self) -> f64 {
+        self.progress
+    }
+}
+
+fn children(tasks: &[TaskNode]) -> HashMap<i32, Vec<&TaskNode>> {
+    let mut children: HashMap<i32, Vec<&TaskNode>> = HashMap::new();
+    for task in tasks {
+        if let Some(parent_id) = task.parent_id {
+            children.entry(parent_id).or_default().push(task);
+        }
+    }
+    children
+}
+
+fn average(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(task_id: i32, parent_id: Option<i32>, closed: bool) -> TaskNode {
+        TaskNode {
+            task_id,
+            parent_id,
+            closed,
+        }
+    }
+
+    #[test]
+    fn test_rollup_averages_the_subtasks() {
+        // 1 has a closed subtask and an open one with one of its two subtasks closed.
+        let nodes = [
+            node(1, None, false),
+            node(2, Some(1), true),
+            node(3, Some(1), false),
+            node(4, Some(3), true),
+            node(5, Some(3), false),
+            node(6, None, true),
+        ];
+        let task = TaskProgress::of_task(&nodes[0], &nodes[1..5]);
+        assert_eq!((task.tasks(), task.closed(), task.progress()), (4, 2, 75.0));
+        let leaf = TaskProgress::of_task(&nodes[5], &[]);
+        assert_eq!((leaf.tasks(), leaf.progress()), (0, 100.0));
+        // 6 is on top, 3 is below 1 which is in another project.
+        let project = TaskProgress::of_project(&nodes[2..]);
+        assert_eq!((project.tasks(), project.closed(), project.progress()), (4, 2, 75.0));
+        assert_eq!(TaskProgress::of_project(&[]).progress(), 0.0);
+    }
+}
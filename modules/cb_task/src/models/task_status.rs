@@ -0,0 +1,74 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use rocket::serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A step of the task workflow.
+#[derive(Queryable, Selectable, Debug, Clone, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+#[diesel(table_name = crab_rocket_schema::schema::task_status_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct TaskStatus {
+    task_status_id: i32,
+    status_name: String,
+    /// Where the status is in the workflow, new tasks start in the lowest.
+    position: i32,
+    /// A task in a closed status is done and no longer overdue.
+    closed: bool,
+    created_at: NaiveDateTime,
+}
+
+impl TaskStatus {
+    pub fn task_status_id(&self) -> i32 {
+        self.task_status_id
+    }
+
+    pub fn status_name(&self) -> &str {
+        &self.status_name
+    }
+
+    pub fn position(&self) -> i32 {
+        self.position
+    }
+
+    pub fn closed(&self) -> bool {
+        self.closed
+    }
+}
+
+#[derive(Insertable, Debug, Serialize, Deserialize, ToSchema, Default)]
+#[serde(crate = "rocket::serde")]
+#[diesel(table_name = crab_rocket_schema::schema::task_status_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PostTaskStatus {
+    pub status_name: String,
+    /// Defaults to `0`.
+    pub position: Option<i32>,
+    /// Defaults to `false`.
+    pub closed: Option<bool>,
+}
+
+/// A move the workflow allows.
+#[derive(Queryable, Selectable, Insertable, Debug, Clone, Copy)]
+#[diesel(table_name = crab_rocket_schema::schema::task_transition_table)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct TaskTransition {
+    pub from_status_id: i32,
+    pub to_status_id: i32,
+}
+
+/// A status with the statuses a task may move on to from it.
+#[derive(Serialize, Deserialize, ToSchema, Debug, Default)]
+#[serde(crate = "rocket::serde")]
+pub struct TaskStatusDetail {
+    #[serde(flatten)]
+    pub status: TaskStatus,
+    pub next_status_ids: Vec<i32>,
+}
+
+/// Where to move a task, see `PUT /task/<id>/status`.
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, Copy)]
+#[serde(crate = "rocket::serde")]
+pub struct TaskStatusChange {
+    pub status_id: i32,
+}
@@ -0,0 +1,69 @@
+use crab_rocket_auth::guards::auth_user::AuthUser;
+use obj_traits::request::pagination_request_param::{PaginationParam, PaginationParamTrait};
+use obj_traits::request::request_param::RequestParam;
+use rocket::http::Status;
+use rocket::response::status;
+use rocket::serde::json::Json;
+use rocket::{delete, get, options, patch, post};
+
+use crate::controllers::project_controller::ProjectController;
+use crate::models::project::{PatchProject, PostProject};
+use crate::routes::task_route::to_response;
+
+#[get("/project")]
+pub fn get_projects(_auth: AuthUser) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = ProjectController::get_projects();
+    to_response(code, message, data)
+}
+
+/// The project with the `progress` of its tasks.
+#[get("/project/<id>")]
+pub fn get_project_by_id(_auth: AuthUser, id: i32) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = ProjectController::get_project(id);
+    to_response(code, message, data)
+}
+
+#[get("/project/<id>/task?<limit>&<offset>")]
+pub fn get_project_tasks(
+    _auth: AuthUser,
+    id: i32,
+    limit: Option<i32>,
+    offset: Option<i32>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let params = RequestParam::new(PaginationParam::new(limit, offset), None);
+    let (code, message, data) = ProjectController::get_tasks(id, params);
+    to_response(code, message, data)
+}
+
+/// The project is owned by the caller unless a moderator names another `user_id`.
+#[post("/project", data = "<project>")]
+pub fn insert_project(
+    auth: AuthUser,
+    project: Json<PostProject>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = ProjectController::add_owned(&auth, &project);
+    to_response(code, message, data)
+}
+
+/// Only the owner, or a role granted `project:moderate`.
+#[patch("/project/<id>", data = "<project>")]
+pub fn update_project_by_id(
+    auth: AuthUser,
+    id: i32,
+    project: Json<PatchProject>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = ProjectController::update_owned(&auth, id, &project);
+    to_response(code, message, data)
+}
+
+/// Only the owner, or a role granted `project:moderate`. The tasks of the project stay.
+#[delete("/project/<id>")]
+pub fn delete_project_by_id(auth: AuthUser, id: i32) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = ProjectController::delete_owned(&auth, id);
+    to_response(code, message, data)
+}
+
+#[options("/project")]
+pub fn options_project() -> Status {
+    Status::Ok
+}
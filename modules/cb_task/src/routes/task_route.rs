@@ -1,14 +1,29 @@
 use crate::controllers::task_controller::TaskController;
-use crate::models::task::{PostTask, PatchTask};
+use crate::models::task::{PatchTask, PostTask};
 use crate::models::task_filter::TaskFilter;
+use crate::models::task_status::TaskStatusChange;
+use crab_rocket_auth::guards::auth_user::AuthUser;
 use obj_traits::controller::controller_crud::ControllerCRUD;
 use obj_traits::request::pagination_request_param::{PaginationParam, PaginationParamTrait};
 use obj_traits::request::request_param::RequestParam;
+use obj_traits::response::api_response::ApiResponse;
 use rocket::http::Status;
+use rocket::response::status;
 use rocket::serde::json::Json;
-use rocket::{delete, get, options, patch, post};
+use rocket::{delete, get, options, patch, post, put};
 use serde_json::json;
 
+/// Answers with `code` as the HTTP status, in the shape of the other task routes.
+pub(crate) fn to_response<T: rocket::serde::Serialize + Default>(
+    code: i32,
+    message: String,
+    data: T,
+) -> status::Custom<Json<serde_json::Value>> {
+    let response = serde_json::to_value(ApiResponse::new(code, message, data)).unwrap();
+    let status = Status::from_code(code as u16).unwrap_or(Status::InternalServerError);
+    status::Custom(status, Json(response))
+}
+
 /// # Note
 /// 若业务逻辑复杂则启用controller层
 /// 目前只是把业务逻辑简单包含在路由中
@@ -18,13 +33,7 @@ use serde_json::json;
 /// 例如PUT/items/1的意思是替換/items/1，如果已經存在就替換，沒有就新增。
 /// PUT必須包含items/1的所有屬性資料
 #[get("/task?<limit>&<offset>")]
-pub fn get_tasks(mut limit: Option<i32>, mut offset: Option<i32>) -> Json<serde_json::Value> {
-    if limit.is_none() {
-        limit = Some(10);
-    };
-    if offset.is_none() {
-        offset = Some(0);
-    };
+pub fn get_tasks(limit: Option<i32>, offset: Option<i32>) -> Json<serde_json::Value> {
     let params = RequestParam::new(PaginationParam::new(limit, offset), None);
     println!("{:?}", params);
     crab_rocket_schema::update_reload::update_reload_count();
@@ -45,6 +54,40 @@ pub fn filter_tasks(
     Json(serde_json::from_value(json_value).unwrap())
 }
 
+/// The caller's tasks.
+#[get("/task/mine?<limit>&<offset>")]
+pub fn get_my_tasks(
+    auth: AuthUser,
+    limit: Option<i32>,
+    offset: Option<i32>,
+) -> Json<serde_json::Value> {
+    let params = RequestParam::new(PaginationParam::new(limit, offset), None);
+    let resp = TaskController::filter_mine(&auth, params).unwrap();
+    let json_value = serde_json::to_value(&resp).unwrap();
+    Json(serde_json::from_value(json_value).unwrap())
+}
+
+/// `POST /task/filter` over the caller's tasks, a `user_id` in the filter is ignored.
+#[post("/task/mine/filter", data = "<param>")]
+pub fn filter_my_tasks(
+    auth: AuthUser,
+    param: Option<Json<RequestParam<PaginationParam, TaskFilter>>>,
+) -> Json<serde_json::Value> {
+    let param = param.unwrap_or(Json(RequestParam::new(PaginationParam::default(), None)));
+    let resp = TaskController::filter_mine(&auth, param.into_inner()).unwrap();
+    let json_value = serde_json::to_value(&resp).unwrap();
+    Json(serde_json::from_value(json_value).unwrap())
+}
+
+/// Open tasks past their due date, the longest overdue first.
+#[get("/task/overdue?<limit>&<offset>")]
+pub fn get_overdue_tasks(limit: Option<i32>, offset: Option<i32>) -> Json<serde_json::Value> {
+    let params = RequestParam::new(PaginationParam::new(limit, offset), None);
+    let resp = TaskController::get_overdue(params).unwrap();
+    let json_value = serde_json::to_value(&resp).unwrap();
+    Json(serde_json::from_value(json_value).unwrap())
+}
+
 #[get("/task/<id>")]
 pub fn get_task_by_id(id: i32) -> Json<serde_json::Value> {
     crab_rocket_schema::update_reload::update_reload_count();
@@ -53,27 +96,88 @@ pub fn get_task_by_id(id: i32) -> Json<serde_json::Value> {
     Json(serde_json::from_value(json_value).unwrap())
 }
 
+/// The task is owned by the caller unless a moderator names another `user_id`.
 #[post("/task", data = "<task>")]
-pub fn insert_single_task(task: Json<PostTask>) -> Json<serde_json::Value> {
-    let mut obj: PostTask = task.into_inner();
-
-    let resp = TaskController::add_single(&mut obj).unwrap();
-    let json_value = serde_json::to_value(&resp).unwrap();
-    Json(serde_json::from_value(json_value).unwrap())
+pub fn insert_single_task(
+    auth: AuthUser,
+    task: Json<PostTask>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = TaskController::add_owned(&auth, &task);
+    to_response(code, message, data)
 }
 
+/// Only the owner, or a role granted `task:moderate`.
 #[delete("/task/<id>")]
-pub fn delete_task_by_id(id: i32) -> Json<serde_json::Value> {
-    let resp = TaskController::delete_by_id(id).unwrap();
-    let json_value = serde_json::to_value(&resp).unwrap();
-    Json(serde_json::from_value(json_value).unwrap())
+pub fn delete_task_by_id(auth: AuthUser, id: i32) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = TaskController::delete_owned(&auth, id);
+    to_response(code, message, data)
 }
 
+/// Only the owner, or a role granted `task:moderate`.
 #[patch("/task/<id>", data = "<task>")]
-pub fn update_task_by_id(id: i32, task: Json<PatchTask>) -> Json<serde_json::Value> {
-    let resp = TaskController::update_by_id(id, &task).unwrap();
-    let json_value = serde_json::to_value(&resp).unwrap();
-    Json(serde_json::from_value(json_value).unwrap())
+pub fn update_task_by_id(
+    auth: AuthUser,
+    id: i32,
+    task: Json<PatchTask>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = TaskController::update_owned(&auth, id, &task);
+    to_response(code, message, data)
+}
+
+/// The owner, the assignee or a role granted `task:moderate`. A move the workflow does not
+/// allow answers `409`.
+#[put("/task/<id>/status", data = "<change>")]
+pub fn update_task_status(
+    auth: AuthUser,
+    id: i32,
+    change: Json<TaskStatusChange>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = TaskController::set_status_owned(&auth, id, change.status_id);
+    to_response(code, message, data)
+}
+
+/// The direct subtasks of the task.
+#[get("/task/<id>/subtask")]
+pub fn get_task_subtasks(id: i32) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = TaskController::get_subtasks(id);
+    to_response(code, message, data)
+}
+
+/// How far the task got, rolled up from its subtasks.
+#[get("/task/<id>/progress")]
+pub fn get_task_progress(id: i32) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = TaskController::get_progress(id);
+    to_response(code, message, data)
+}
+
+/// The tasks the task waits on.
+#[get("/task/<id>/blocker")]
+pub fn get_task_blockers(id: i32) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = TaskController::get_blockers(id);
+    to_response(code, message, data)
+}
+
+/// Only the owner, or a role granted `task:moderate`. A blocker that waits on the task answers
+/// `409`.
+#[put("/task/<id>/blocker/<blocker_id>")]
+pub fn insert_task_blocker(
+    auth: AuthUser,
+    id: i32,
+    blocker_id: i32,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = TaskController::add_blocker_owned(&auth, id, blocker_id);
+    to_response(code, message, data)
+}
+
+/// Only the owner, or a role granted `task:moderate`.
+#[delete("/task/<id>/blocker/<blocker_id>")]
+pub fn delete_task_blocker(
+    auth: AuthUser,
+    id: i32,
+    blocker_id: i32,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = TaskController::delete_blocker_owned(&auth, id, blocker_id);
+    to_response(code, message, data)
 }
 
 #[get("/")]
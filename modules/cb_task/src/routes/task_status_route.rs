@@ -0,0 +1,56 @@
+use crab_rocket_auth::guards::auth_user::AuthUser;
+use crab_rocket_auth::guards::authorized::Authorized;
+use crab_rocket_auth::permission;
+use rocket::http::Status;
+use rocket::response::status;
+use rocket::serde::json::Json;
+use rocket::{delete, get, options, post, put};
+
+use crate::controllers::task_controller::TaskController;
+use crate::models::task_status::PostTaskStatus;
+use crate::routes::task_route::to_response;
+
+permission!(pub TaskModerate, "task", "moderate");
+
+/// The workflow in order, each status with `next_status_ids` a task may move on to.
+#[get("/task/status")]
+pub fn get_task_statuses(_auth: AuthUser) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = TaskController::get_statuses();
+    to_response(code, message, data)
+}
+
+/// A taken `status_name` answers `409`.
+#[post("/task/status", data = "<status>")]
+pub fn insert_task_status(
+    _auth: Authorized<TaskModerate>,
+    status: Json<PostTaskStatus>,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = TaskController::add_status(&status);
+    to_response(code, message, data)
+}
+
+/// Lets tasks move from status `id` to `next_id`.
+#[put("/task/status/<id>/next/<next_id>")]
+pub fn insert_task_transition(
+    _auth: Authorized<TaskModerate>,
+    id: i32,
+    next_id: i32,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = TaskController::add_transition(id, next_id);
+    to_response(code, message, data)
+}
+
+#[delete("/task/status/<id>/next/<next_id>")]
+pub fn delete_task_transition(
+    _auth: Authorized<TaskModerate>,
+    id: i32,
+    next_id: i32,
+) -> status::Custom<Json<serde_json::Value>> {
+    let (code, message, data) = TaskController::delete_transition(id, next_id);
+    to_response(code, message, data)
+}
+
+#[options("/task/status")]
+pub fn options_task_status() -> Status {
+    Status::Ok
+}
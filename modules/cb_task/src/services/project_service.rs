@@ -0,0 +1,98 @@
+use crab_rocket_auth::guards::auth_user::AuthUser;
+use crab_rocket_auth::services::ownership_service::{OwnershipError, OwnershipService};
+use crab_rocket_schema::establish_pg_connection;
+use diesel::PgConnection;
+use obj_traits::request::pagination_request_param::PaginationParam;
+use obj_traits::request::request_param::RequestParam;
+use obj_traits::response::data::Data;
+use obj_traits::service::service_crud::ServiceCRUD;
+
+use crate::error::TaskError;
+use crate::mappers::project_mapper::ProjectMapper;
+use crate::mappers::task_mapper::TaskMapper;
+use crate::models::project::{PatchProject, PostProject, Project, ProjectDetail};
+use crate::models::task::Task;
+use crate::models::task_filter::TaskFilter;
+use crate::models::task_progress::TaskProgress;
+use crate::services::task_service::TaskService;
+
+pub struct ProjectService {}
+
+const RESOURCE: &str = "project";
+
+impl ProjectService {
+    pub fn get_projects() -> Result<Vec<Project>, TaskError> {
+        let mut conn = establish_pg_connection()?;
+        Ok(ProjectMapper::get_projects(&mut conn)?)
+    }
+
+    /// The project with how far its tasks got.
+    pub fn get_project(pid: i32) -> Result<ProjectDetail, TaskError> {
+        let mut conn = establish_pg_connection()?;
+        let project = find(&mut conn, pid)?;
+        let tasks = TaskMapper::project_nodes(&mut conn, pid)?;
+        Ok(ProjectDetail {
+            project,
+            progress: TaskProgress::of_project(&tasks),
+        })
+    }
+
+    /// Like `POST /task/filter`, narrowed to the tasks of project `pid`.
+    pub fn get_tasks(
+        pid: i32,
+        param: RequestParam<PaginationParam, TaskFilter>,
+    ) -> Result<Data<Vec<Task>>, TaskError> {
+        let mut conn = establish_pg_connection()?;
+        find(&mut conn, pid)?;
+        let mut filter = param.filter.unwrap_or_default();
+        filter.project_id = Some(pid);
+        Ok(TaskService::filter(&RequestParam::new(param.pagination, Some(filter)))?)
+    }
+
+    /// Creates a project owned by the caller, moderators may name another `user_id`.
+    pub fn add_owned(auth: &AuthUser, obj: &PostProject) -> Result<Project, TaskError> {
+        let mut obj = obj.clone();
+        obj.set_user_id(Some(OwnershipService::assign_owner(auth, RESOURCE, obj.user_id())?));
+        let mut conn = establish_pg_connection()?;
+        Ok(ProjectMapper::add_project(&mut conn, &obj)?)
+    }
+
+    /// Replaces project `pid` when the caller owns it or moderates projects. Without `user_id`
+    /// the project keeps its owner, handing it to someone else takes a moderator.
+    pub fn update_owned(
+        auth: &AuthUser,
+        pid: i32,
+        obj: &PatchProject,
+    ) -> Result<Project, TaskError> {
+        let mut conn = establish_pg_connection()?;
+        let project = find(&mut conn, pid)?;
+        OwnershipService::authorize(auth, RESOURCE, "update", pid, project.user_id())?;
+        let mut obj = obj.clone();
+        match obj.user_id() {
+            Some(user_id) if Some(user_id) != project.user_id() => {
+                OwnershipService::assign_owner(auth, RESOURCE, Some(user_id))?;
+            }
+            _ => obj.set_user_id(project.user_id()),
+        }
+        Ok(ProjectMapper::update_project(&mut conn, pid, &obj)?)
+    }
+
+    /// Deletes project `pid`, its tasks stay without a project.
+    pub fn delete_owned(auth: &AuthUser, pid: i32) -> Result<Project, TaskError> {
+        let mut conn = establish_pg_connection()?;
+        let project = find(&mut conn, pid)?;
+        OwnershipService::authorize(auth, RESOURCE, "delete", pid, project.user_id())?;
+        Ok(ProjectMapper::delete_project(&mut conn, pid)?)
+    }
+}
+
+fn find(conn: &mut PgConnection, pid: i32) -> Result<Project, TaskError> {
+    ProjectMapper::get_project(conn, pid).map_err(|e| match e {
+        diesel::result::Error::NotFound => OwnershipError::NotFound {
+            resource: RESOURCE,
+            id: pid,
+        }
+        .into(),
+        e => e.into(),
+    })
+}
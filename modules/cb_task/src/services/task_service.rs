@@ -1,8 +1,22 @@
 use std::error::Error;
 
+use chrono::NaiveDate;
+use crab_rocket_auth::guards::auth_user::AuthUser;
+use crab_rocket_auth::services::ownership_service::{OwnershipError, OwnershipService};
+use crab_rocket_schema::establish_pg_connection;
+use crab_rocket_utils::time::get_e8_time;
+use diesel::{Connection, PgConnection};
+
+use crate::error::TaskError;
+use crate::mappers::project_mapper::ProjectMapper;
 use crate::mappers::task_mapper::TaskMapper;
-use crate::models::task::{PostTask, PatchTask, Task};
-use crate::models::task_filter::TaskFilter;
+use crate::mappers::task_status_mapper::TaskStatusMapper;
+use crate::models::task::{PatchTask, PostTask, Task, LOW, URGENT};
+use crate::models::task_filter::{TaskFilter, TaskSort};
+use crate::models::task_progress::TaskProgress;
+use crate::models::task_status::TaskTransition;
+use crate::services::task_status_service::find_status;
+use obj_traits::mapper::mapper_crud::MapperCRUD;
 use obj_traits::request::pagination_request_param::PaginationParam;
 use obj_traits::request::request_param::RequestParam;
 use obj_traits::response::data::Data;
@@ -45,6 +59,278 @@ impl ServiceCRUD for TaskService {
     }
 }
 
+const RESOURCE: &str = "task";
+
+impl TaskService {
+    /// Creates a task owned by the caller, moderators may name another `user_id`. Without a
+    /// `status_id` the task starts in the first status of the workflow, a subtask without a
+    /// `project_id` joins the project of its parent.
+    pub fn add_owned(auth: &AuthUser, obj: &PostTask) -> Result<Task, TaskError> {
+        let mut obj = obj.clone();
+        obj.set_user_id(Some(OwnershipService::assign_owner(auth, RESOURCE, obj.user_id())?));
+        obj.set_labels(obj.labels().as_deref().map(normalize_labels));
+        let mut conn = establish_pg_connection()?;
+        validate(&mut conn, obj.priority(), obj.start_date(), obj.due_date(), obj.assignee_id())?;
+        if let Some(parent_id) = obj.parent_id() {
+            let parent = find_task(&mut conn, parent_id)?;
+            if obj.project_id().is_none() {
+                obj.set_project_id(parent.project_id());
+            }
+        }
+        check_project(&mut conn, obj.project_id())?;
+        let status = match obj.status_id() {
+            Some(status_id) => Some(find_status(&mut conn, status_id)?),
+            None => None,
+        };
+        conn.transaction(|conn| {
+            let task = TaskMapper::add_single(conn, &obj)?;
+            match status {
+                Some(status) if status.closed() => Ok(TaskMapper::set_status(
+                    conn,
+                    task.task_id(),
+                    status.task_status_id(),
+                    Some(get_e8_time()),
+                )?),
+                _ => Ok(task),
+            }
+        })
+    }
+
+    /// Replaces task `pid` when the caller owns it or moderates tasks. Without `user_id` the
+    /// task keeps its owner, handing it to someone else takes a moderator. A new `status_id`
+    /// has to be a move the workflow allows, a new `parent_id` cannot be one of its subtasks.
+    pub fn update_owned(auth: &AuthUser, pid: i32, obj: &PatchTask) -> Result<Task, TaskError> {
+        let task = Self::get_by_id(pid).map_err(|e| OwnershipError::lookup(RESOURCE, pid, e))?;
+        OwnershipService::authorize(auth, RESOURCE, "update", pid, task.user_id())?;
+        let mut obj = obj.clone();
+        match obj.user_id() {
+            Some(user_id) if Some(user_id) != task.user_id() => {
+                OwnershipService::assign_owner(auth, RESOURCE, Some(user_id))?;
+            }
+            _ => obj.set_user_id(task.user_id()),
+        }
+        obj.set_labels(obj.labels().as_deref().map(normalize_labels));
+        let mut conn = establish_pg_connection()?;
+        validate(&mut conn, obj.priority(), obj.start_date(), obj.due_date(), obj.assignee_id())?;
+        check_project(&mut conn, obj.project_id())?;
+        // The status moves on its own, see `set_status_owned`.
+        let status_id = obj.status_id().filter(|&status_id| status_id != task.status_id());
+        obj.set_status_id(None);
+        conn.transaction(|conn| {
+            if let Some(parent_id) = obj.parent_id().filter(|&p| Some(p) != task.parent_id()) {
+                find_task(conn, parent_id)?;
+                let subtree = TaskMapper::subtree(conn, pid)?;
+                if subtree.iter().any(|subtask| subtask.task_id() == parent_id) {
+                    return Err(TaskError::ParentCycle {
+                        task_id: pid,
+                        parent_id,
+                    });
+                }
+            }
+            let updated = TaskMapper::update_by_id(conn, pid, &obj)?;
+            match status_id {
+                Some(status_id) => move_task(conn, &updated, status_id),
+                None => Ok(updated),
+            }
+        })
+    }
+
+    /// Moves task `pid` to `status_id`. Its assignee may as well as its owner, entering a closed
+    /// status completes the task and leaving it reopens it.
+    pub fn set_status_owned(auth: &AuthUser, pid: i32, status_id: i32) -> Result<Task, TaskError> {
+        let task = Self::get_by_id(pid).map_err(|e| OwnershipError::lookup(RESOURCE, pid, e))?;
+        let acting_for = match task.assignee_id() {
+            Some(assignee_id) if assignee_id == auth.user_id() => Some(assignee_id),
+            _ => task.user_id(),
+        };
+        OwnershipService::authorize(auth, RESOURCE, "update", pid, acting_for)?;
+        let mut conn = establish_pg_connection()?;
+        conn.transaction(|conn| move_task(conn, &task, status_id))
+    }
+
+    pub fn delete_owned(auth: &AuthUser, pid: i32) -> Result<Task, TaskError> {
+        let task = Self::get_by_id(pid).map_err(|e| OwnershipError::lookup(RESOURCE, pid, e))?;
+        OwnershipService::authorize(auth, RESOURCE, "delete", pid, task.user_id())?;
+        Ok(Self::delete_by_id(pid)?)
+    }
+
+    /// The direct subtasks of `pid`.
+    pub fn get_subtasks(pid: i32) -> Result<Vec<Task>, TaskError> {
+        Self::get_by_id(pid).map_err(|e| OwnershipError::lookup(RESOURCE, pid, e))?;
+        let mut conn = establish_pg_connection()?;
+        Ok(TaskMapper::get_subtasks(&mut conn, pid)?)
+    }
+
+    /// How far `pid` got, rolled up from its subtasks at any depth.
+    pub fn get_progress(pid: i32) -> Result<TaskProgress, TaskError> {
+        let mut conn = establish_pg_connection()?;
+        match TaskMapper::subtree(&mut conn, pid)?.split_first() {
+            Some((task, subtasks)) => Ok(TaskProgress::of_task(task, subtasks)),
+            None => Err(OwnershipError::NotFound {
+                resource: RESOURCE,
+                id: pid,
+            }
+            .into()),
+        }
+    }
+
+    /// The tasks `pid` waits on.
+    pub fn get_blockers(pid: i32) -> Result<Vec<Task>, TaskError> {
+        Self::get_by_id(pid).map_err(|e| OwnershipError::lookup(RESOURCE, pid, e))?;
+        let mut conn = establish_pg_connection()?;
+        Ok(TaskMapper::get_blockers(&mut conn, pid)?)
+    }
+
+    /// Makes `pid` wait on `blocker_id`, for the owner of `pid` or a moderator. The blocker may
+    /// not wait on `pid` itself, and a closed task cannot start waiting on an open one.
+    pub fn add_blocker_owned(
+        auth: &AuthUser,
+        pid: i32,
+        blocker_id: i32,
+    ) -> Result<Vec<Task>, TaskError> {
+        let task = Self::get_by_id(pid).map_err(|e| OwnershipError::lookup(RESOURCE, pid, e))?;
+        OwnershipService::authorize(auth, RESOURCE, "update", pid, task.user_id())?;
+        let mut conn = establish_pg_connection()?;
+        conn.transaction(|conn| {
+            let blocker = find_task(conn, blocker_id)?;
+            if blocker_id == pid || TaskMapper::waits_on(conn, blocker_id, pid)? {
+                return Err(TaskError::DependencyCycle {
+                    task_id: pid,
+                    blocker_id,
+                });
+            }
+            if task.completed_at().is_some() && blocker.completed_at().is_none() {
+                return Err(TaskError::Blocked {
+                    task_id: pid,
+                    blocker_ids: vec![blocker_id],
+                });
+            }
+            TaskMapper::add_blocker(conn, pid, blocker_id)?;
+            Ok(TaskMapper::get_blockers(conn, pid)?)
+        })
+    }
+
+    pub fn delete_blocker_owned(
+        auth: &AuthUser,
+        pid: i32,
+        blocker_id: i32,
+    ) -> Result<Vec<Task>, TaskError> {
+        let task = Self::get_by_id(pid).map_err(|e| OwnershipError::lookup(RESOURCE, pid, e))?;
+        OwnershipService::authorize(auth, RESOURCE, "update", pid, task.user_id())?;
+        let mut conn = establish_pg_connection()?;
+        TaskMapper::delete_blocker(&mut conn, pid, blocker_id)?;
+        Ok(TaskMapper::get_blockers(&mut conn, pid)?)
+    }
+
+    /// Like [`ServiceCRUD::filter`], narrowed to the caller's tasks.
+    pub fn filter_mine(
+        auth: &AuthUser,
+        param: RequestParam<PaginationParam, TaskFilter>,
+    ) -> Result<Data<Vec<Task>>, Box<dyn Error>> {
+        let mut filter = param.filter.unwrap_or_default();
+        filter.user_id = Some(auth.user_id());
+        Self::filter(&RequestParam::new(param.pagination, Some(filter)))
+    }
+
+    /// Open tasks past their due date, the longest overdue first.
+    pub fn get_overdue(
+        param: RequestParam<PaginationParam, TaskFilter>,
+    ) -> Result<Data<Vec<Task>>, Box<dyn Error>> {
+        let mut filter = param.filter.unwrap_or_default();
+        filter.overdue = Some(true);
+        filter.sort_by.get_or_insert(TaskSort::DueDate);
+        Self::filter(&RequestParam::new(param.pagination, Some(filter)))
+    }
+}
+
+/// Trimmed, without blanks and each label once, in the order given.
+fn normalize_labels(labels: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(labels.len());
+    for label in labels.iter().map(|label| label.trim()).filter(|label| !label.is_empty()) {
+        if !normalized.iter().any(|seen| seen == label) {
+            normalized.push(label.to_string());
+        }
+    }
+    normalized
+}
+
+fn validate(
+    conn: &mut PgConnection,
+    priority: Option<i32>,
+    start_date: Option<NaiveDate>,
+    due_date: Option<NaiveDate>,
+    assignee_id: Option<i32>,
+) -> Result<(), TaskError> {
+    if let Some(priority) = priority.filter(|p| !(LOW..=URGENT).contains(p)) {
+        return Err(TaskError::InvalidPriority(priority));
+    }
+    if let (Some(start), Some(due)) = (start_date, due_date) {
+        if due < start {
+            return Err(TaskError::InvalidDates {
+                start,
+                due,
+            });
+        }
+    }
+    if let Some(assignee_id) = assignee_id {
+        if !TaskMapper::user_exists(conn, assignee_id)? {
+            return Err(TaskError::AssigneeNotFound(assignee_id));
+        }
+    }
+    Ok(())
+}
+
+/// A parent or a blocker.
+fn find_task(conn: &mut PgConnection, task_id: i32) -> Result<Task, TaskError> {
+    TaskMapper::get_by_id(conn, task_id).map_err(|e| match e {
+        diesel::result::Error::NotFound => TaskError::TaskNotFound(task_id),
+        e => e.into(),
+    })
+}
+
+fn check_project(conn: &mut PgConnection, project_id: Option<i32>) -> Result<(), TaskError> {
+    match project_id {
+        Some(project_id) if !ProjectMapper::project_exists(conn, project_id)? => {
+            Err(TaskError::ProjectNotFound(project_id))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Takes `task` to `status_id` when the workflow allows it, stamping `completed_at` when that
+/// closes the task and clearing it when it reopens. Closing waits for the blockers of the task.
+fn move_task(conn: &mut PgConnection, task: &Task, status_id: i32) -> Result<Task, TaskError> {
+    let to = find_status(conn, status_id)?;
+    if status_id == task.status_id() {
+        return Ok(task.clone());
+    }
+    let transition = TaskTransition {
+        from_status_id: task.status_id(),
+        to_status_id: status_id,
+    };
+    if !TaskStatusMapper::transition_exists(conn, &transition)? {
+        return Err(TaskError::InvalidTransition {
+            from: task.status_id(),
+            to: status_id,
+        });
+    }
+    if to.closed() {
+        let blocker_ids = TaskMapper::open_blockers(conn, task.task_id())?;
+        if !blocker_ids.is_empty() {
+            return Err(TaskError::Blocked {
+                task_id: task.task_id(),
+                blocker_ids,
+            });
+        }
+    }
+    let completed_at = match (to.closed(), task.completed_at()) {
+        (true, Some(completed_at)) => Some(completed_at),
+        (true, None) => Some(get_e8_time()),
+        (false, _) => None,
+    };
+    Ok(TaskMapper::set_status(conn, task.task_id(), status_id, completed_at)?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,93 @@
+use crab_rocket_schema::establish_pg_connection;
+use diesel::result::DatabaseErrorKind;
+use diesel::PgConnection;
+
+use crate::error::TaskError;
+use crate::mappers::task_status_mapper::TaskStatusMapper;
+use crate::models::task_status::{PostTaskStatus, TaskStatus, TaskStatusDetail, TaskTransition};
+
+pub struct TaskStatusService {}
+
+impl TaskStatusService {
+    /// The workflow in order, each status with the ones a task may move on to.
+    pub fn get_statuses() -> Result<Vec<TaskStatusDetail>, TaskError> {
+        let mut conn = establish_pg_connection()?;
+        let transitions = TaskStatusMapper::get_transitions(&mut conn)?;
+        Ok(TaskStatusMapper::get_statuses(&mut conn)?
+            .into_iter()
+            .map(|status| detail(status, &transitions))
+            .collect())
+    }
+
+    /// Adds a status to the workflow, tasks only reach it once a transition leads there.
+    pub fn add_status(obj: &PostTaskStatus) -> Result<TaskStatusDetail, TaskError> {
+        let mut conn = establish_pg_connection()?;
+        match TaskStatusMapper::add_status(&mut conn, obj) {
+            Ok(status) => Ok(detail(status, &[])),
+            Err(diesel::result::Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => {
+                Err(TaskError::StatusTaken(obj.status_name.clone()))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Lets tasks move from one status to the other.
+    pub fn add_transition(from: i32, to: i32) -> Result<TaskStatusDetail, TaskError> {
+        let mut conn = establish_pg_connection()?;
+        let status = find_status(&mut conn, from)?;
+        find_status(&mut conn, to)?;
+        if from == to {
+            return Err(TaskError::InvalidTransition {
+                from,
+                to,
+            });
+        }
+        TaskStatusMapper::add_transition(
+            &mut conn,
+            &TaskTransition {
+                from_status_id: from,
+                to_status_id: to,
+            },
+        )?;
+        Ok(detail(status, &TaskStatusMapper::get_transitions(&mut conn)?))
+    }
+
+    /// Stops tasks moving from one status to the other, tasks already there stay.
+    pub fn delete_transition(from: i32, to: i32) -> Result<TaskStatusDetail, TaskError> {
+        let mut conn = establish_pg_connection()?;
+        let status = find_status(&mut conn, from)?;
+        let transition = TaskTransition {
+            from_status_id: from,
+            to_status_id: to,
+        };
+        if TaskStatusMapper::delete_transition(&mut conn, &transition)? == 0 {
+            return Err(TaskError::InvalidTransition {
+                from,
+                to,
+            });
+        }
+        Ok(detail(status, &TaskStatusMapper::get_transitions(&mut conn)?))
+    }
+}
+
+pub(crate) fn find_status(
+    conn: &mut PgConnection,
+    status_id: i32,
+) -> Result<TaskStatus, TaskError> {
+    TaskStatusMapper::get_status(conn, status_id).map_err(|e| match e {
+        diesel::result::Error::NotFound => TaskError::StatusNotFound(status_id),
+        e => e.into(),
+    })
+}
+
+fn detail(status: TaskStatus, transitions: &[TaskTransition]) -> TaskStatusDetail {
+    let next_status_ids = transitions
+        .iter()
+        .filter(|t| t.from_status_id == status.task_status_id())
+        .map(|t| t.to_status_id)
+        .collect();
+    TaskStatusDetail {
+        status,
+        next_status_ids,
+    }
+}
@@ -0,0 +1,132 @@
+use std::cell::RefCell;
+
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::asynchronous::{Client, LocalRequest};
+use rocket::tokio::runtime::{Builder, Runtime};
+use rocket::{Build, Rocket, Route};
+use serde_json::Value;
+
+/// A blocking wrapper around Rocket's local client.
+///
+/// Requests run on a current-thread runtime, so handlers execute on the test's own thread
+/// and pick up its [`crate::TestDb`]. Rocket's blocking client would hand them to worker
+/// threads instead.
+pub struct TestClient {
+    // Dropped before the runtime it was created on.
+    client: Client,
+    runtime: Runtime,
+    token: RefCell<Option<String>>,
+}
+
+/// Status, headers and body of a dispatched request.
+#[derive(Debug)]
+pub struct TestResponse {
+    pub status: Status,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl TestResponse {
+    /// # Panics
+    /// When the body is not JSON.
+    pub fn json(&self) -> Value {
+        serde_json::from_str(&self.body)
+            .unwrap_or_else(|e| panic!("response is not json ({e}): {}", self.body))
+    }
+
+    /// The first value of header `name`, compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(n, _)| n.eq_ignore_ascii_case(name)).map(|(_, v)| v.as_str())
+    }
+
+    /// The application code of the JSON envelope, `code` for the CRUD routes and `status`
+    /// for the others.
+    pub fn code(&self) -> i64 {
+        let json = self.json();
+        json.get("code").or_else(|| json.get("status")).and_then(Value::as_i64).unwrap_or_default()
+    }
+}
+
+impl TestClient {
+    /// Mounts `routes` under `/api` on a Rocket using the `test` profile.
+    pub fn new(routes: Vec<Route>) -> Self {
+        Self::with(routes, |rocket| rocket)
+    }
+
+    /// Like [`TestClient::new`], `configure` attaches fairings or registers catchers.
+    pub fn with(
+        routes: Vec<Route>,
+        configure: impl FnOnce(Rocket<Build>) -> Rocket<Build>,
+    ) -> Self {
+        let figment = crab_rocket_config::figment::with_derived_limits(
+            crab_rocket_config::figment::figment()
+                .select(crab_rocket_config::figment::TEST_PROFILE),
+            crab_rocket_config::init_profile(crab_rocket_config::figment::TEST_PROFILE),
+        );
+        let rocket = configure(rocket::custom(figment).mount("/api", routes));
+        let runtime = Builder::new_current_thread().enable_all().build().expect("test runtime");
+        let client = runtime.block_on(Client::tracked(rocket)).expect("valid rocket instance");
+        Self {
+            client,
+            runtime,
+            token: RefCell::new(None),
+        }
+    }
+
+    /// Sends `Authorization: Bearer <token>` with every following request, `None` stops it.
+    pub fn set_token(&self, token: Option<&str>) {
+        *self.token.borrow_mut() = token.map(String::from);
+    }
+
+    pub fn get(&self, uri: &str) -> TestResponse {
+        self.dispatch(self.client.get(uri.to_string()))
+    }
+
+    pub fn delete(&self, uri: &str) -> TestResponse {
+        self.dispatch(self.client.delete(uri.to_string()))
+    }
+
+    pub fn post_json(&self, uri: &str, body: &Value) -> TestResponse {
+        self.dispatch(
+            self.client.post(uri.to_string()).header(ContentType::JSON).body(body.to_string()),
+        )
+    }
+
+    pub fn put_json(&self, uri: &str, body: &Value) -> TestResponse {
+        self.dispatch(
+            self.client.put(uri.to_string()).header(ContentType::JSON).body(body.to_string()),
+        )
+    }
+
+    pub fn patch_json(&self, uri: &str, body: &Value) -> TestResponse {
+        self.dispatch(
+            self.client.patch(uri.to_string()).header(ContentType::JSON).body(body.to_string()),
+        )
+    }
+
+    /// Sends a request built from [`TestClient::inner`], e.g. with extra headers.
+    pub fn dispatch(&self, mut request: LocalRequest<'_>) -> TestResponse {
+        if let Some(token) = self.token.borrow().as_ref() {
+            request.add_header(Header::new("Authorization", format!("Bearer {token}")));
+        }
+        self.runtime.block_on(async move {
+            let response = request.dispatch().await;
+            let status = response.status();
+            let headers = response
+                .headers()
+                .iter()
+                .map(|h| (h.name().to_string(), h.value().to_string()))
+                .collect();
+            let body = response.into_string().await.unwrap_or_default();
+            TestResponse {
+                status,
+                headers,
+                body,
+            }
+        })
+    }
+
+    pub fn inner(&self) -> &Client {
+        &self.client
+    }
+}
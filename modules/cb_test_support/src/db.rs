@@ -0,0 +1,161 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crab_rocket_schema::migrations::run_pending_migrations;
+use crab_rocket_schema::set_thread_database_url;
+use diesel::{sql_query, Connection, PgConnection, RunQueryDsl};
+use rocket::{Build, Rocket, Route};
+
+use crate::client::TestClient;
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A freshly migrated database that only the current test uses, dropped again with the value.
+///
+/// While it is alive, `establish_pg_connection` on this thread connects to it, so services and
+/// routes called from the test (including through [`TestDb::client`]) see only its rows.
+pub struct TestDb {
+    name: String,
+    url: String,
+    server_url: String,
+}
+
+impl TestDb {
+    /// Creates the database on the server `DATABASE_URL` points at and applies the migrations.
+    /// The process then reads its configuration from the `test` profile, see
+    /// [`crab_rocket_config::init_profile`].
+    ///
+    /// # Panics
+    /// When the server is unreachable or the migrations fail.
+    pub fn new() -> Self {
+        crab_rocket_config::init_profile(crab_rocket_config::figment::TEST_PROFILE);
+        let server_url = server_url();
+        let name = format!(
+            "crab_rocket_test_{}_{}",
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        );
+        let mut server = PgConnection::establish(&server_url)
+            .unwrap_or_else(|e| panic!("test database server unavailable: {e}"));
+        sql_query(format!("CREATE DATABASE \"{name}\""))
+            .execute(&mut server)
+            .unwrap_or_else(|e| panic!("creating {name} failed: {e}"));
+
+        let url = with_database(&server_url, &name);
+        let db = Self {
+            name,
+            url,
+            server_url,
+        };
+        let mut conn = db.conn();
+        run_pending_migrations(&mut conn)
+            .unwrap_or_else(|e| panic!("migrating test database: {e}"));
+        set_thread_database_url(Some(db.url.clone()));
+        db
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn conn(&self) -> PgConnection {
+        PgConnection::establish(&self.url).unwrap_or_else(|e| panic!("connecting to test db: {e}"))
+    }
+
+    /// A local client for `routes` mounted under `/api`, backed by this database.
+    pub fn client(&self, routes: Vec<Route>) -> TestClient {
+        TestClient::new(routes)
+    }
+
+    /// Like [`TestDb::client`], `configure` attaches fairings or registers catchers.
+    pub fn client_with(
+        &self,
+        routes: Vec<Route>,
+        configure: impl FnOnce(Rocket<Build>) -> Rocket<Build>,
+    ) -> TestClient {
+        TestClient::with(routes, configure)
+    }
+}
+
+impl Default for TestDb {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for TestDb {
+    fn drop(&mut self) {
+        set_thread_database_url(None);
+        if let Ok(mut server) = PgConnection::establish(&self.server_url) {
+            let _ = sql_query(format!("DROP DATABASE IF EXISTS \"{}\" WITH (FORCE)", self.name))
+                .execute(&mut server);
+        }
+    }
+}
+
+/// A connection to the current database inside a transaction that is never committed, every
+/// change is gone once the connection is dropped.
+///
+/// # Panics
+/// When no connection can be made.
+pub fn test_conn() -> PgConnection {
+    crab_rocket_config::init_profile(crab_rocket_config::figment::TEST_PROFILE);
+    let mut conn = crab_rocket_schema::establish_pg_connection()
+        .unwrap_or_else(|e| panic!("test database unavailable: {e}"));
+    conn.begin_test_transaction().expect("begin test transaction");
+    conn
+}
+
+/// `DATABASE_URL` itself, never the thread's override.
+fn server_url() -> String {
+    dotenvy::dotenv().ok();
+    std::env::var("DATABASE_URL").expect("DATABASE_URL must be set")
+}
+
+/// `url` with its database name replaced, query parameters are kept.
+fn with_database(url: &str, name: &str) -> String {
+    let (base, query) = match url.split_once('?') {
+        Some((base, query)) => (base, Some(query)),
+        None => (url, None),
+    };
+    let server = match base.rsplit_once('/') {
+        Some((server, _)) if server.contains("://") && !server.ends_with('/') => server,
+        _ => base.trim_end_matches('/'),
+    };
+    match query {
+        Some(query) => format!("{server}/{name}?{query}"),
+        None => format!("{server}/{name}"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{with_database, TestDb};
+    use crab_rocket_schema::migrations::{
+        migration_status, pending_migrations, revert_migrations, run_pending_migrations,
+    };
+
+    #[test]
+    fn test_every_migration_reverts_and_runs_again() {
+        let db = TestDb::new();
+        let mut conn = db.conn();
+        let total = migration_status(&mut conn).unwrap().len();
+        let reverted = revert_migrations(&mut conn, total).unwrap();
+        assert_eq!(reverted.len(), total);
+        assert_eq!(pending_migrations(&mut conn).unwrap().len(), total);
+        assert_eq!(run_pending_migrations(&mut conn).unwrap().len(), total);
+        assert!(pending_migrations(&mut conn).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_with_database() {
+        assert_eq!(
+            with_database("postgres://u:p@localhost:5432/hello_rocket", "t1"),
+            "postgres://u:p@localhost:5432/t1"
+        );
+        assert_eq!(
+            with_database("postgres://u:p@db/hello?sslmode=disable", "t1"),
+            "postgres://u:p@db/t1?sslmode=disable"
+        );
+        assert_eq!(with_database("postgres://u:p@db", "t1"), "postgres://u:p@db/t1");
+    }
+}
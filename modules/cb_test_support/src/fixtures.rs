@@ -0,0 +1,161 @@
+//! Minimal rows for tests, inserted straight into the tables so any module can use them
+//! without depending on the others. Each returns the new primary key.
+use std::sync::OnceLock;
+
+use chrono::NaiveDateTime;
+use crab_rocket_schema::schema::{
+    department_table, employee_table, file_table, follow_table, permission_table, post_table,
+    role_permission_table, role_table, user_table,
+};
+use crab_rocket_utils::password::hash_password;
+use diesel::connection::SimpleConnection;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+/// The password of every [`user`] fixture.
+pub const PASSWORD: &str = "password";
+
+/// Runs a SQL script, e.g. `include_str!("fixtures/orders.sql")`.
+pub fn load_sql(conn: &mut PgConnection, sql: &str) {
+    conn.batch_execute(sql).unwrap_or_else(|e| panic!("loading fixture failed: {e}"));
+}
+
+pub fn role(conn: &mut PgConnection, role_name: &str) -> i32 {
+    diesel::insert_into(role_table::table)
+        .values(role_table::role_name.eq(role_name))
+        .returning(role_table::role_id)
+        .get_result(conn)
+        .expect("insert role fixture")
+}
+
+/// A user whose password is [`PASSWORD`] and whose phone number is derived from the name.
+pub fn user(conn: &mut PgConnection, username: &str) -> i32 {
+    // Hashed once, argon2 is deliberately slow.
+    static HASH: OnceLock<String> = OnceLock::new();
+    let hash = HASH.get_or_init(|| hash_password(PASSWORD).expect("hash fixture password"));
+    diesel::insert_into(user_table::table)
+        .values((
+            user_table::username.eq(username),
+            user_table::password.eq(hash),
+            user_table::email.eq(format!("{username}@example.com")),
+            user_table::mobile_phone.eq(format!("phone-{username}")),
+        ))
+        .returning(user_table::user_id)
+        .get_result(conn)
+        .expect("insert user fixture")
+}
+
+pub fn assign_role(conn: &mut PgConnection, user_id: i32, role_id: i32) {
+    diesel::update(user_table::table.find(user_id))
+        .set(user_table::role_id.eq(role_id))
+        .execute(conn)
+        .expect("assign role fixture");
+}
+
+/// Grants `resource:action` to the role, creating the permission when it does not exist.
+/// Returns the permission id.
+pub fn grant(conn: &mut PgConnection, role_id: i32, resource: &str, action: &str) -> i32 {
+    let existing = permission_table::table
+        .filter(permission_table::resource.eq(resource))
+        .filter(permission_table::action.eq(action))
+        .select(permission_table::permission_id)
+        .first(conn)
+        .optional()
+        .expect("look up permission fixture");
+    let permission_id = match existing {
+        Some(permission_id) => permission_id,
+        None => diesel::insert_into(permission_table::table)
+            .values((
+                permission_table::permission_name.eq(format!("{resource}:{action}")),
+                permission_table::resource.eq(resource),
+                permission_table::action.eq(action),
+            ))
+            .returning(permission_table::permission_id)
+            .get_result(conn)
+            .expect("insert permission fixture"),
+    };
+    diesel::insert_into(role_permission_table::table)
+        .values((
+            role_permission_table::role_id.eq(role_id),
+            role_permission_table::permission_id.eq(permission_id),
+        ))
+        .on_conflict_do_nothing()
+        .execute(conn)
+        .expect("insert grant fixture");
+    permission_id
+}
+
+pub fn post(conn: &mut PgConnection, user_id: i32, title: &str) -> i32 {
+    diesel::insert_into(post_table::table)
+        .values((
+            post_table::user_id.eq(user_id),
+            post_table::title.eq(title),
+            post_table::body.eq(format!("Body of {title}")),
+        ))
+        .returning(post_table::post_id)
+        .get_result(conn)
+        .expect("insert post fixture")
+}
+
+pub fn department(conn: &mut PgConnection, department_name: &str, parent: Option<i32>) -> i32 {
+    diesel::insert_into(department_table::table)
+        .values((
+            department_table::department_name.eq(department_name),
+            department_table::parent_department_id.eq(parent),
+        ))
+        .returning(department_table::department_id)
+        .get_result(conn)
+        .expect("insert department fixture")
+}
+
+/// An employee named `employee_name`, in `department_id` and reporting to `manager_id`.
+pub fn employee(
+    conn: &mut PgConnection,
+    employee_name: &str,
+    department_id: Option<i32>,
+    manager_id: Option<i32>,
+) -> i32 {
+    diesel::insert_into(employee_table::table)
+        .values((
+            employee_table::employee_name.eq(employee_name),
+            employee_table::department_id.eq(department_id),
+            employee_table::manager_id.eq(manager_id),
+        ))
+        .returning(employee_table::employee_id)
+        .get_result(conn)
+        .expect("insert employee fixture")
+}
+
+/// Makes `user_id` log in as the employee.
+pub fn link_employee(conn: &mut PgConnection, employee_id: i32, user_id: i32) {
+    diesel::update(employee_table::table.find(employee_id))
+        .set(employee_table::user_id.eq(user_id))
+        .execute(conn)
+        .expect("link employee fixture");
+}
+
+pub fn follow(conn: &mut PgConnection, following_user_id: i32, followed_user_id: i32) -> i32 {
+    diesel::insert_into(follow_table::table)
+        .values((
+            follow_table::following_user_id.eq(following_user_id),
+            follow_table::followed_user_id.eq(followed_user_id),
+        ))
+        .returning(follow_table::follow_id)
+        .get_result(conn)
+        .expect("insert follow fixture")
+}
+
+/// A `file_table` row only, nothing is written to the upload directory.
+pub fn file(conn: &mut PgConnection, file_name: &str, uploaded_at: Option<NaiveDateTime>) -> Uuid {
+    let file_id = Uuid::new_v4();
+    diesel::insert_into(file_table::table)
+        .values((
+            file_table::file_id.eq(file_id),
+            file_table::file_name.eq(file_name),
+            file_table::file_url.eq(format!("upload/{file_name}")),
+            uploaded_at.map(|at| file_table::uploaded_at.eq(at)),
+        ))
+        .execute(conn)
+        .expect("insert file fixture");
+    file_id
+}
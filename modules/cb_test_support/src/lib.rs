@@ -0,0 +1,17 @@
+//! Test harness: a throwaway database per test, rolled-back connections, fixtures, a local
+//! Rocket client and a mock OpenID Connect provider.
+//!
+//! ```ignore
+//! let db = TestDb::new();
+//! let client = db.client(module_routes());
+//! let response = client.get("/api/info");
+//! assert_eq!(response.status, Status::Ok);
+//! ```
+pub mod client;
+pub mod db;
+pub mod fixtures;
+pub mod oidc;
+
+pub use client::{TestClient, TestResponse};
+pub use db::{test_conn, TestDb};
+pub use oidc::MockOidc;
@@ -0,0 +1,220 @@
+//! A local OpenID Connect provider for the `mock` providers of the `test` profile.
+//!
+//! ```ignore
+//! let mock = MockOidc::start();
+//! let url = client.get("/api/auth/oidc/mock/authorize").json()["body"]["data"]["authorization_url"].clone();
+//! // What the provider appends to the redirect URI after the user logged in as `claims`.
+//! let query = mock.authorize(url.as_str().unwrap(), json!({"sub": "42", "email": "a@example.com"}));
+//! let tokens = client.get(&format!("/api/auth/oidc/mock/callback?{query}"));
+//! ```
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+
+use crab_rocket_config::app_config;
+use crab_rocket_utils::token::{generate_token, pkce_challenge};
+use jsonwebtoken::{encode, EncodingKey, Header};
+use serde_json::{json, Value};
+use url::{form_urlencoded, Url};
+
+const KEY_ID: &str = "mock-key";
+/// A throwaway 2048 bit RSA key, its public half is [`MODULUS`].
+const PRIVATE_KEY: &str = include_str!("oidc_test_key.pem");
+const MODULUS: &str = "zF6_wwf0ERqB4JftgVpq9JEcjFnFvLNRr_B7NebMptH_cO-GcKQgRtZKmO1XlXY9cDIpI6CQJAtIkjQiswSi7YrxkPU0Mhpa3gQ6hCKT1kJX_uaO5QSEs1L01H7SdgtHCN8lhUasGX7Sco5W8B5qHehZRg7cKArPEOBrkDjZAHomUb21SAohlewFUkxyV-nq9b5lTCFCk4eNqdaXCz1zIhsSEHztsmP9ClqoadbTgzma9yC6gr8h3oik00AUbTTxJkGpjbIlzpt69nGQOYlrYWv3N2NCcns8NnmxiKczPUYBsmPaIHttd_dPXz_dgaxjWVyGol8UEflHsUyR_d0alQ";
+
+/// A code handed out by [`MockOidc::authorize`], waiting to be traded for tokens.
+struct IssuedCode {
+    client_id: String,
+    redirect_uri: String,
+    code_challenge: String,
+    nonce: String,
+    claims: Value,
+}
+
+type Codes = Arc<Mutex<HashMap<String, IssuedCode>>>;
+
+pub struct MockOidc {
+    codes: Codes,
+}
+
+impl MockOidc {
+    /// Serves on the address of the `mock` provider's `issuer`, once per process.
+    ///
+    /// # Panics
+    /// When the address is taken.
+    pub fn start() -> &'static MockOidc {
+        static MOCK: OnceLock<MockOidc> = OnceLock::new();
+        MOCK.get_or_init(|| {
+            crab_rocket_config::init_profile(crab_rocket_config::figment::TEST_PROFILE);
+            let issuer = app_config().oidc.providers["mock"].issuer.clone();
+            let address = issuer.trim_start_matches("http://").to_string();
+            let listener = TcpListener::bind(&address)
+                .unwrap_or_else(|e| panic!("mock OIDC provider on {address}: {e}"));
+            let codes = Codes::default();
+            let server = Server {
+                issuer,
+                codes: codes.clone(),
+            };
+            thread::spawn(move || {
+                for stream in listener.incoming().flatten() {
+                    server.handle(stream);
+                }
+            });
+            MockOidc {
+                codes,
+            }
+        })
+    }
+
+    /// Plays the user logging in at `authorization_url` as `claims`, which are merged over the
+    /// standard claims of the ID token. Returns the query the provider would redirect back
+    /// with, `code` and `state`.
+    pub fn authorize(&self, authorization_url: &str, claims: Value) -> String {
+        let url = Url::parse(authorization_url).expect("authorization_url");
+        let params: HashMap<String, String> = url.query_pairs().into_owned().collect();
+        assert_eq!(params["response_type"], "code");
+        assert_eq!(params["code_challenge_method"], "S256");
+        assert!(params["scope"].split(' ').any(|scope| scope == "openid"));
+        let code = generate_token();
+        self.codes.lock().unwrap().insert(
+            code.clone(),
+            IssuedCode {
+                client_id: params["client_id"].clone(),
+                redirect_uri: params["redirect_uri"].clone(),
+                code_challenge: params["code_challenge"].clone(),
+                nonce: params["nonce"].clone(),
+                claims,
+            },
+        );
+        form_urlencoded::Serializer::new(String::new())
+            .append_pair("code", &code)
+            .append_pair("state", &params["state"])
+            .finish()
+    }
+}
+
+struct Server {
+    issuer: String,
+    codes: Codes,
+}
+
+impl Server {
+    /// One request per connection, answered with `Connection: close`.
+    fn handle(&self, stream: TcpStream) {
+        let mut reader = BufReader::new(&stream);
+        let mut request_line = String::new();
+        let mut content_length = 0;
+        if reader.read_line(&mut request_line).is_err() {
+            return;
+        }
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).unwrap_or(0) == 0 || line.trim().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap_or(0);
+                }
+            }
+        }
+        let mut body = vec![0; content_length];
+        if reader.read_exact(&mut body).is_err() {
+            return;
+        }
+
+        let mut parts = request_line.split_whitespace();
+        let (status, response) = match (parts.next(), parts.next()) {
+            (Some("GET"), Some("/.well-known/openid-configuration")) => {
+                ("200 OK", self.discovery())
+            }
+            (Some("GET"), Some("/jwks")) => ("200 OK", jwks()),
+            (Some("POST"), Some("/token")) => match self.token(&body) {
+                Ok(tokens) => ("200 OK", tokens),
+                Err(error) => ("400 Bad Request", json!({ "error": error })),
+            },
+            _ => ("404 Not Found", json!({ "error": "not_found" })),
+        };
+        let response = response.to_string();
+        let _ = write!(
+            &stream,
+            "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+            response.len()
+        );
+    }
+
+    fn discovery(&self) -> Value {
+        json!({
+            "issuer": self.issuer,
+            "authorization_endpoint": format!("{}/authorize", self.issuer),
+            "token_endpoint": format!("{}/token", self.issuer),
+            "jwks_uri": format!("{}/jwks", self.issuer),
+            "response_types_supported": ["code"],
+            "subject_types_supported": ["public"],
+            "id_token_signing_alg_values_supported": ["RS256"],
+            "code_challenge_methods_supported": ["S256"],
+        })
+    }
+
+    /// The authorization code grant, with client secret and PKCE verifier checked.
+    fn token(&self, body: &[u8]) -> Result<Value, &'static str> {
+        let form: HashMap<String, String> = form_urlencoded::parse(body).into_owned().collect();
+        let field = |name: &str| form.get(name).map(String::as_str).unwrap_or_default();
+        if field("grant_type") != "authorization_code" {
+            return Err("unsupported_grant_type");
+        }
+        let client_secret = app_config()
+            .oidc
+            .providers
+            .values()
+            .find(|provider| provider.client_id == field("client_id"))
+            .map(|provider| provider.client_secret.as_str());
+        if client_secret != Some(field("client_secret")) {
+            return Err("invalid_client");
+        }
+        let issued = self.codes.lock().unwrap().remove(field("code")).ok_or("invalid_grant")?;
+        if issued.client_id != field("client_id")
+            || issued.redirect_uri != field("redirect_uri")
+            || issued.code_challenge != pkce_challenge(field("code_verifier"))
+        {
+            return Err("invalid_grant");
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        let mut claims = json!({
+            "iss": self.issuer,
+            "aud": issued.client_id,
+            "iat": now,
+            "exp": now + 300,
+            "nonce": issued.nonce,
+        });
+        if let (Some(claims), Some(extra)) = (claims.as_object_mut(), issued.claims.as_object()) {
+            claims.extend(extra.clone());
+        }
+        let mut header = Header::new(jsonwebtoken::Algorithm::RS256);
+        header.kid = Some(KEY_ID.to_string());
+        let key = EncodingKey::from_rsa_pem(PRIVATE_KEY.as_bytes()).expect("mock OIDC key");
+        let id_token = encode(&header, &claims, &key).expect("signing the ID token");
+        Ok(json!({
+            "access_token": generate_token(),
+            "token_type": "Bearer",
+            "expires_in": 300,
+            "id_token": id_token,
+        }))
+    }
+}
+
+fn jwks() -> Value {
+    json!({
+        "keys": [{
+            "kty": "RSA",
+            "use": "sig",
+            "alg": "RS256",
+            "kid": KEY_ID,
+            "n": MODULUS,
+            "e": "AQAB",
+        }]
+    })
+}
@@ -1,4 +1,4 @@
-use crate::models::user::{PostUser, PatchUser, User};
+use crate::models::user::{PatchUser, PostUser, User};
 use crate::models::user_filter::UserFilter;
 use crate::services::user_service::UserService;
 use obj_traits::controller::controller_crud::{
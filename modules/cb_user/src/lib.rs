@@ -11,10 +11,6 @@ pub mod controllers {
     pub mod user_controller;
 }
 
-pub mod routes {
-    pub mod user_route;
-}
-
 pub mod services {
     pub mod user_service;
 }
@@ -1,7 +1,9 @@
-use crate::models::user::{PostUser, PatchUser, User};
+use crate::models::user::{PatchUser, PostUser, User};
 use crate::models::user_filter::UserFilter;
+use crab_rocket_schema::schema::session_table;
 use crab_rocket_schema::schema::user_table::dsl;
 use crab_rocket_schema::schema::user_table::{self};
+use crab_rocket_utils::password::{hash_password, verify_password, PasswordCheck};
 use crab_rocket_utils::time::get_e8_time;
 use diesel::prelude::*;
 use diesel::result::Error;
@@ -36,8 +38,8 @@ impl MapperCRUD for UserMapper {
         //
         // limit 始终为 per_page
         // 计算分页相关
-        let page = (param.pagination.offset.unwrap() / param.pagination.limit.unwrap()) + 1;
-        let per_page = param.pagination.limit.unwrap();
+        let page = (param.pagination.offset_or_default() / param.pagination.limit_or_default()) + 1;
+        let per_page = param.pagination.limit_or_default();
         // 获取总记录数
         let total_count = dsl::user_table.count().get_result::<i64>(conn)? as i32;
         // 计算总页数
@@ -57,6 +59,7 @@ impl MapperCRUD for UserMapper {
         // 分页查询
         let data = dsl::user_table
             .order(dsl::updated_at.desc())
+            .then_order_by(dsl::user_id.desc())
             .limit(per_page as i64)
             .offset(((page - 1) * per_page) as i64)
             .load::<User>(conn)?;
@@ -67,8 +70,10 @@ impl MapperCRUD for UserMapper {
         dsl::user_table.filter(dsl::user_id.eq(pid)).first(conn)
     }
     fn add_single(conn: &mut PgConnection, obj: &PostUser) -> Result<User, Error> {
+        let mut obj = obj.clone();
+        obj.set_password(hash(obj.password())?);
         diesel::insert_into(dsl::user_table)
-            .values(obj)
+            .values(&obj)
             .returning(User::as_returning())
             .get_result(conn)
     }
@@ -79,7 +84,6 @@ impl MapperCRUD for UserMapper {
         diesel::update(dsl::user_table.filter(dsl::user_id.eq(pid)))
             .set((
                 user_table::username.eq(obj.username()),
-                user_table::password.eq(obj.password()),
                 user_table::role_id.eq(obj.role_id()),
                 user_table::email.eq(obj.email()),
                 user_table::full_name.eq(obj.full_name()),
@@ -112,8 +116,8 @@ impl MapperCRUD for UserMapper {
         let filter = &param.filter;
         println!("{filter:?}");
         // 计算分页相关
-        let page = (param.pagination.offset.unwrap() / param.pagination.limit.unwrap()) + 1;
-        let per_page = param.pagination.limit.unwrap();
+        let page = (param.pagination.offset_or_default() / param.pagination.limit_or_default()) + 1;
+        let per_page = param.pagination.limit_or_default();
         // 获取总记录数
         let total_count = dsl::user_table.count().get_result::<i64>(conn)? as i32;
         // 计算总页数
@@ -174,10 +178,91 @@ impl MapperCRUD for UserMapper {
     }
 }
 
+impl UserMapper {
+    pub fn get_by_username(conn: &mut PgConnection, username: &str) -> Result<User, Error> {
+        dsl::user_table.filter(dsl::username.eq(username)).first::<User>(conn)
+    }
+
+    /// The user a login name refers to, tried as username, mobile phone number and email
+    /// address in that order. Emails are not unique, one shared by several users matches none.
+    pub fn get_by_login(conn: &mut PgConnection, login: &str) -> Result<User, Error> {
+        if login.is_empty() {
+            return Err(Error::NotFound);
+        }
+        if let Some(user) =
+            dsl::user_table.filter(dsl::username.eq(login)).first(conn).optional()?
+        {
+            return Ok(user);
+        }
+        if let Some(user) =
+            dsl::user_table.filter(dsl::mobile_phone.eq(login)).first(conn).optional()?
+        {
+            return Ok(user);
+        }
+        let mut users = dsl::user_table.filter(dsl::email.eq(login)).limit(2).load::<User>(conn)?;
+        match users.len() {
+            1 => Ok(users.remove(0)),
+            _ => Err(Error::NotFound),
+        }
+    }
+
+    /// Hashes `password` and overwrites the stored one, leaving every other column untouched.
+    /// Every login session of the user ends with it.
+    pub fn update_password(
+        conn: &mut PgConnection,
+        pid: i32,
+        password: &str,
+    ) -> Result<User, Error> {
+        let password = hash(password)?;
+        conn.transaction(|conn| {
+            let now = get_e8_time();
+            diesel::update(
+                session_table::table
+                    .filter(session_table::user_id.eq(pid))
+                    .filter(session_table::revoked_at.is_null()),
+            )
+            .set((
+                session_table::revoked_at.eq(now),
+                session_table::revoked_reason.eq("password changed"),
+            ))
+            .execute(conn)?;
+            diesel::update(dsl::user_table.filter(dsl::user_id.eq(pid)))
+                .set((user_table::password.eq(password), user_table::updated_at.eq(Some(now))))
+                .get_result(conn)
+        })
+    }
+
+    /// Checks `password` for `user`. A match against a plain text or outdated hash is
+    /// rehashed with the current parameters on the spot.
+    pub fn verify_password(
+        conn: &mut PgConnection,
+        user: &User,
+        password: &str,
+    ) -> Result<bool, Error> {
+        match verify_password(password, user.password()) {
+            PasswordCheck::Invalid => Ok(false),
+            PasswordCheck::Valid {
+                needs_rehash,
+            } => {
+                if needs_rehash {
+                    diesel::update(dsl::user_table.filter(dsl::user_id.eq(user.user_id())))
+                        .set(user_table::password.eq(hash(password)?))
+                        .execute(conn)?;
+                }
+                Ok(true)
+            }
+        }
+    }
+}
+
+fn hash(password: &str) -> Result<String, Error> {
+    hash_password(password).map_err(|e| Error::SerializationError(Box::new(e)))
+}
+
 #[cfg(test)]
 mod test {
     use crate::mappers::user_mapper::UserMapper;
-    use crate::models::user::{PostUser, PatchUser};
+    use crate::models::user::{PatchUser, PostUser};
     use obj_traits::mapper::mapper_crud::MapperCRUD;
     use obj_traits::request::pagination_request_param::{PaginationParam, PaginationParamTrait};
     use obj_traits::request::request_param::RequestParam;
@@ -253,4 +338,34 @@ mod test {
             Err(_) => println!("establish_pg_connection error"),
         }
     }
+
+    #[test]
+    fn test_passwords_are_hashed_and_upgraded() {
+        use crab_rocket_schema::schema::user_table::dsl;
+        use crab_rocket_test_support::{fixtures, test_conn};
+        use diesel::prelude::*;
+
+        let mut conn = test_conn();
+        let mut user = PostUser::demo();
+        user.set_username("hashed_user".to_string());
+        user.set_mobile_phone("phone-hashed_user".to_string());
+        let inserted = UserMapper::add_single(&mut conn, &user).unwrap();
+        assert!(inserted.password().starts_with("$argon2id$"));
+        assert!(UserMapper::verify_password(&mut conn, &inserted, "password").unwrap());
+        assert!(!UserMapper::verify_password(&mut conn, &inserted, "Password").unwrap());
+        // Never part of a response.
+        assert!(!serde_json::to_string(&inserted).unwrap().contains("argon2"));
+
+        // A plain text password from before hashing is replaced on the first login.
+        let legacy = fixtures::user(&mut conn, "legacy_user");
+        diesel::update(dsl::user_table.find(legacy))
+            .set(dsl::password.eq("plain text"))
+            .execute(&mut conn)
+            .unwrap();
+        let user = UserMapper::get_by_id(&mut conn, legacy).unwrap();
+        assert!(UserMapper::verify_password(&mut conn, &user, "plain text").unwrap());
+        let user = UserMapper::get_by_id(&mut conn, legacy).unwrap();
+        assert!(user.password().starts_with("$argon2id$"));
+        assert!(UserMapper::verify_password(&mut conn, &user, "plain text").unwrap());
+    }
 }
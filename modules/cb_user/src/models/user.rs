@@ -1,8 +1,8 @@
+use crab_rocket_utils::time::get_e8_time;
 use diesel::{deserialize::Queryable, prelude::Insertable, Selectable};
 use rocket::serde::{Deserialize, Serialize};
 use std::fmt::Display;
 use utoipa::ToSchema;
-use crab_rocket_utils::time::get_e8_time;
 
 #[derive(Serialize, Deserialize, Debug, Clone, ToSchema, Default)]
 #[serde(crate = "rocket::serde")]
@@ -15,6 +15,7 @@ pub struct User {
     role_id: Option<i32>,
     created_at: Option<chrono::NaiveDateTime>,
     email: Option<String>,
+    #[serde(skip_serializing, default)]
     password: String,
     full_name: Option<String>,
     avatar_url: Option<String>,
@@ -24,6 +25,7 @@ pub struct User {
 }
 
 impl User {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         user_id: i32,
         username: String,
@@ -170,6 +172,7 @@ pub struct PostUser {
     role_id: Option<i32>,
     created_at: Option<chrono::NaiveDateTime>,
     email: Option<String>,
+    #[serde(skip_serializing)]
     password: String,
     full_name: Option<String>,
     avatar_url: Option<String>,
@@ -179,6 +182,7 @@ pub struct PostUser {
 }
 
 impl PostUser {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         username: String,
         role_id: Option<i32>,
@@ -311,7 +315,6 @@ pub struct PatchUser {
     role_id: Option<i32>,
     created_at: Option<chrono::NaiveDateTime>,
     email: Option<String>,
-    password: String,
     full_name: Option<String>,
     avatar_url: Option<String>,
     bio: Option<String>,
@@ -320,12 +323,12 @@ pub struct PatchUser {
 }
 
 impl PatchUser {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         username: String,
         role_id: Option<i32>,
         created_at: Option<chrono::NaiveDateTime>,
         email: Option<String>,
-        password: String,
         full_name: Option<String>,
         avatar_url: Option<String>,
         bio: Option<String>,
@@ -337,7 +340,6 @@ impl PatchUser {
             role_id,
             created_at,
             email,
-            password,
             full_name,
             avatar_url,
             bio,
@@ -362,10 +364,6 @@ impl PatchUser {
         &self.email
     }
 
-    pub fn password(&self) -> &str {
-        &self.password
-    }
-
     pub fn full_name(&self) -> &Option<String> {
         &self.full_name
     }
@@ -402,10 +400,6 @@ impl PatchUser {
         self.email = email;
     }
 
-    pub fn set_password(&mut self, password: String) {
-        self.password = password;
-    }
-
     pub fn set_full_name(&mut self, full_name: Option<String>) {
         self.full_name = full_name;
     }
@@ -427,6 +421,14 @@ impl PatchUser {
     }
 }
 
+/// Body of `PATCH /user/<id>/password`.
+#[derive(Deserialize, Debug, ToSchema)]
+#[serde(crate = "rocket::serde")]
+pub struct ChangePassword {
+    pub old_password: String,
+    pub new_password: String,
+}
+
 #[cfg(test)]
 mod test {
     #[test]
@@ -1,6 +1,7 @@
 use crate::mappers::user_mapper::UserMapper;
-use crate::models::user::{PostUser, PatchUser, User};
+use crate::models::user::{PatchUser, PostUser, User};
 use crate::models::user_filter::UserFilter;
+use crab_rocket_utils::password::validate_password;
 use obj_traits::request::pagination_request_param::PaginationParam;
 use obj_traits::request::request_param::RequestParam;
 use obj_traits::response::data::Data;
@@ -27,6 +28,7 @@ impl ServiceCRUD for UserService {
     }
 
     fn add_single(obj: &PostUser) -> Result<User, Box<dyn Error>> {
+        validate_password(obj.password())?;
         service_add_single::<User, UserMapper, PostUser>(obj)
     }
 
@@ -1,11 +1,14 @@
 use colored::Colorize;
 
 pub mod mkdir;
+pub mod password;
 pub mod time;
+pub mod token;
 pub fn run_preload() {
     println!("{}", "Running preload...".blue());
-    mkdir::make_directory("upload");
-    print!("{}\n", "Finished preload...".blue());
+    let upload_dir = &crab_rocket_config::app_config().storage.upload_dir;
+    mkdir::make_directory(&upload_dir.to_string_lossy());
+    println!("{}", "Finished preload...".blue());
 }
 
 #[test]
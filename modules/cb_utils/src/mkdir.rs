@@ -7,22 +7,15 @@ use std::{fs, path::Path};
 ///
 /// * `path` - 要创建目录的路径。
 ///
-/// ## 返回
-///
-/// 返回目录路径的字符串表示，如果失败则返回空字符串。
-///
 /// ## 示例
 ///
 /// ```
+/// use crab_rocket_utils::mkdir::make_directory;
+///
 /// let path = "upload";
-/// let result = make_directory(path);
-/// if !result.is_empty() {
-///     println!("目录创建成功: {}", result);
-/// } else {
-///     println!("目录创建失败");
-/// }
+/// make_directory(path);
 /// ```
-pub fn make_directory(path: &str) -> () {
+pub fn make_directory(path: &str) {
     match fs::create_dir(Path::new(path)) {
         Ok(_) => {
             println!("{}{}{}", "Created `/{".green(), path.green(), "}` successfully.".green());
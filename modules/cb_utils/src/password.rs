@@ -0,0 +1,169 @@
+use std::fmt;
+
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
+use crab_rocket_config::app_config;
+
+#[derive(Debug)]
+pub enum PasswordError {
+    TooShort {
+        min_length: usize,
+    },
+    Hash(argon2::password_hash::Error),
+}
+
+impl fmt::Display for PasswordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PasswordError::TooShort {
+                min_length,
+            } => write!(f, "password must be at least {min_length} characters"),
+            PasswordError::Hash(e) => write!(f, "password hashing failed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for PasswordError {}
+
+impl From<argon2::password_hash::Error> for PasswordError {
+    fn from(e: argon2::password_hash::Error) -> Self {
+        PasswordError::Hash(e)
+    }
+}
+
+impl From<argon2::Error> for PasswordError {
+    fn from(e: argon2::Error) -> Self {
+        PasswordError::Hash(e.into())
+    }
+}
+
+/// Result of checking a password against the stored value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordCheck {
+    Invalid,
+    /// The password matches. `needs_rehash` is set when the stored value is plain text or
+    /// was hashed with other parameters than the configured ones.
+    Valid {
+        needs_rehash: bool,
+    },
+}
+
+impl PasswordCheck {
+    pub fn is_valid(&self) -> bool {
+        matches!(self, PasswordCheck::Valid { .. })
+    }
+}
+
+/// Argon2id with the cost from `[password]` in `Rocket.toml`.
+fn argon2() -> Result<Argon2<'static>, PasswordError> {
+    let config = &app_config().password;
+    let params = Params::new(config.memory_kib, config.iterations, config.parallelism, None)?;
+    Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+}
+
+/// Rejects passwords the policy does not allow, before anything is hashed.
+pub fn validate_password(password: &str) -> Result<(), PasswordError> {
+    let min_length = app_config().password.min_length;
+    if password.chars().count() < min_length {
+        return Err(PasswordError::TooShort {
+            min_length,
+        });
+    }
+    Ok(())
+}
+
+/// A PHC string (`$argon2id$v=19$m=...`) with a fresh random salt.
+pub fn hash_password(password: &str) -> Result<String, PasswordError> {
+    let salt = SaltString::generate(&mut OsRng);
+    Ok(argon2()?.hash_password(password.as_bytes(), &salt)?.to_string())
+}
+
+/// Checks `password` against `stored`, which is either a PHC string or, for rows written
+/// before passwords were hashed, the password itself.
+pub fn verify_password(password: &str, stored: &str) -> PasswordCheck {
+    let Ok(hash) = PasswordHash::new(stored) else {
+        return match constant_time_eq(password.as_bytes(), stored.as_bytes()) {
+            true => PasswordCheck::Valid {
+                needs_rehash: true,
+            },
+            false => PasswordCheck::Invalid,
+        };
+    };
+    match Argon2::default().verify_password(password.as_bytes(), &hash) {
+        Ok(()) => PasswordCheck::Valid {
+            needs_rehash: !uses_current_params(&hash),
+        },
+        Err(_) => PasswordCheck::Invalid,
+    }
+}
+
+fn uses_current_params(hash: &PasswordHash<'_>) -> bool {
+    let config = &app_config().password;
+    let Ok(params) = Params::try_from(hash) else {
+        return false;
+    };
+    hash.algorithm == Algorithm::Argon2id.ident()
+        && hash.version == Some(Version::V0x13.into())
+        && params.m_cost() == config.memory_kib
+        && params.t_cost() == config.iterations
+        && params.p_cost() == config.parallelism
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod test {
+    use argon2::password_hash::rand_core::OsRng;
+    use argon2::password_hash::{PasswordHasher, SaltString};
+    use argon2::{Algorithm, Argon2, Params, Version};
+
+    use super::{hash_password, validate_password, verify_password, PasswordCheck};
+
+    #[test]
+    fn test_hash_and_verify() {
+        let hash = hash_password("correct horse").unwrap();
+        assert!(hash.starts_with("$argon2id$"));
+        assert_ne!(hash, hash_password("correct horse").unwrap());
+
+        assert_eq!(
+            verify_password("correct horse", &hash),
+            PasswordCheck::Valid {
+                needs_rehash: false
+            }
+        );
+        assert_eq!(verify_password("wrong horse", &hash), PasswordCheck::Invalid);
+    }
+
+    #[test]
+    fn test_outdated_hashes_need_rehash() {
+        // Plain text rows from before hashing was introduced.
+        assert_eq!(
+            verify_password("password1", "password1"),
+            PasswordCheck::Valid {
+                needs_rehash: true
+            }
+        );
+        assert_eq!(verify_password("password", "password1"), PasswordCheck::Invalid);
+
+        let params = Params::new(1024, 1, 1, None).unwrap();
+        let weaker = Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password(b"cheap", &SaltString::generate(&mut OsRng))
+            .unwrap()
+            .to_string();
+        assert_eq!(
+            verify_password("cheap", &weaker),
+            PasswordCheck::Valid {
+                needs_rehash: true
+            }
+        );
+    }
+
+    #[test]
+    fn test_validate_password() {
+        assert!(validate_password("short").is_err());
+        assert!(validate_password("long enough").is_ok());
+    }
+}
@@ -12,6 +12,7 @@ use crate::service::service_crud::ServiceCRUD;
 /// U is for the new added object, typically for no id.
 ///
 /// V is for the updated object, typically for no id.
+#[allow(clippy::type_complexity)]
 pub trait ControllerCRUD {
     type Item;
     type PostItem;
@@ -1,3 +1,4 @@
+use crab_rocket_config::app_config;
 use rocket::serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -26,12 +27,28 @@ impl PaginationParamTrait for PaginationParam {
     }
     fn default() -> Self {
         Self {
-            limit: Some(10),
+            limit: Some(app_config().pagination.default_limit),
             offset: Some(0),
         }
     }
 }
 
+impl PaginationParam {
+    /// The requested page size, falling back to `pagination.default_limit` and capped at
+    /// `pagination.max_limit`.
+    pub fn limit_or_default(&self) -> i32 {
+        let config = &app_config().pagination;
+        match self.limit {
+            Some(limit) if limit > 0 => limit.min(config.max_limit),
+            _ => config.default_limit,
+        }
+    }
+
+    pub fn offset_or_default(&self) -> i32 {
+        self.offset.unwrap_or(0).max(0)
+    }
+}
+
 #[derive(Serialize, Default, Clone, Debug)]
 #[serde(crate = "rocket::serde")]
 pub struct Pagination {
@@ -23,6 +23,7 @@ impl<P: PaginationParamTrait, T> RequestParam<P, T> {
             filter,
         }
     }
+    #[allow(clippy::should_implement_trait)]
     pub fn default() -> Self {
         Self {
             pagination: P::default(),
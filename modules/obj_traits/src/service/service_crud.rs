@@ -134,7 +134,7 @@ where
     ObjMapper: MapperCRUD<Item=Obj, Param=RequestParam<PaginationParam, ObjFilter>>,
 {
     match establish_pg_connection() {
-        Ok(mut conn) => match ObjMapper::get_all(&mut conn, param) {
+        Ok(mut conn) => match ObjMapper::filter(&mut conn, param) {
             Ok(data) => Ok(data),
             Err(e) => {
                 println!("{e:?}");
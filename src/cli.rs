@@ -0,0 +1,34 @@
+use clap::{Parser, Subcommand};
+
+/// Crab Rocket API server.
+#[derive(Parser, Debug)]
+#[command(name = "crab_rocket", version, about)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Start the HTTP server (default).
+    Serve,
+    /// Manage the embedded database migrations.
+    Migrate {
+        #[command(subcommand)]
+        action: MigrateAction,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone, Copy)]
+pub enum MigrateAction {
+    /// List every embedded migration and whether it was applied.
+    Status,
+    /// Apply all pending migrations.
+    Run,
+    /// Revert the most recently applied migrations.
+    Revert {
+        /// How many migrations to revert.
+        #[arg(long, default_value_t = 1)]
+        steps: usize,
+    },
+}
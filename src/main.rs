@@ -1,52 +1,48 @@
-#[macro_use]
-extern crate rocket;
-
-use crab_rocket::{env_variables, routes::routes::module_routes};
-use crab_rocket_utils;
+use clap::Parser;
+use colored::Colorize;
+use crab_rocket::cli::{Cli, Command};
+use crab_rocket::migrate;
+use crab_rocket::routes::routes::{health_routes, module_catchers, module_routes};
+use crab_rocket_auth::fairings::actor_tracking::ActorTracking;
 use dotenvy::dotenv;
-use rocket::{http::Method, Route};
-use rocket_cors::{AllowedHeaders, AllowedOrigins};
+use rocket::{Build, Rocket, Route};
 use std::env;
 
-#[launch]
-fn rocket() -> _ {
+fn main() {
     // Clear environment variable before running.
     env::remove_var("DATABASE_URL");
-    // Load env
-    env_variables::load_env();
     dotenv().ok();
 
-    crab_rocket_utils::run_preload();
+    let cli = Cli::parse();
+    match cli.command.unwrap_or(Command::Serve) {
+        Command::Serve => serve(),
+        Command::Migrate {
+            action,
+        } => std::process::exit(migrate::run(action)),
+    }
+}
 
-    let allowed_origins = AllowedOrigins::All;
-    // You can also deserialize this
-    let cors = rocket_cors::CorsOptions {
-        allowed_origins,
-        allowed_methods: vec![
-            Method::Get,
-            Method::Post,
-            Method::Patch,
-            Method::Put,
-            Method::Delete,
-        ]
-        .into_iter()
-        .map(From::from)
-        .collect(),
-        allowed_headers: AllowedHeaders::some(&[
-            "Content-Type",
-            "Authorization",
-            "Another-Header", // 允许的其他头部信息
-            "Authorization",
-            "Accept",
-            "User-Agent",
-            "X-Requested-With",
-            "Referer",
-        ]),
-        allow_credentials: true,
-        ..Default::default()
+fn serve() {
+    let rocket = rocket();
+    if let Err(e) = rocket::execute(rocket.launch()) {
+        eprintln!("{}", e.to_string().red());
+        std::process::exit(1);
     }
-    .to_cors()
-    .unwrap();
+}
+
+fn rocket() -> Rocket<Build> {
+    // Load config, exits on invalid values.
+    let figment = crab_rocket_config::init();
+    let config = crab_rocket_config::app_config();
+
+    if let Err(e) = migrate::ensure_schema_is_current(config.database.run_migrations_on_launch) {
+        eprintln!("{}", e.red());
+        std::process::exit(1);
+    }
+
+    crab_rocket_utils::run_preload();
+
+    let cors = crab_rocket_config::cors::cors();
 
     let mut routes = Vec::<Route>::new();
 
@@ -57,5 +53,10 @@ fn rocket() -> _ {
     // routes.extend(doc_routes.clone());
     routes.extend(module_routes.clone());
 
-    rocket::build().mount("/api", routes).attach(cors)
+    rocket::custom(figment)
+        .mount("/api", routes)
+        .mount("/health", health_routes())
+        .register("/", module_catchers())
+        .attach(ActorTracking::new("/api"))
+        .attach(cors)
 }
@@ -0,0 +1,75 @@
+use colored::Colorize;
+use crab_rocket_schema::establish_pg_connection;
+use crab_rocket_schema::migrations::{
+    migration_status, pending_migrations, revert_migrations, run_pending_migrations,
+};
+
+use crate::cli::MigrateAction;
+
+/// Runs a `crab_rocket migrate` subcommand, returns the process exit code.
+pub fn run(action: MigrateAction) -> i32 {
+    let mut conn = match establish_pg_connection() {
+        Ok(conn) => conn,
+        Err(e) => {
+            eprintln!("{} {e}", "Failed to connect to database:".red());
+            return 1;
+        }
+    };
+    let result = match action {
+        MigrateAction::Status => migration_status(&mut conn).map(|status| {
+            for migration in status {
+                let mark = if migration.applied {
+                    "[x]".green()
+                } else {
+                    "[ ]".yellow()
+                };
+                println!("{mark} {}", migration.name);
+            }
+        }),
+        MigrateAction::Run => run_pending_migrations(&mut conn).map(|versions| {
+            if versions.is_empty() {
+                println!("{}", "Schema is up to date.".green());
+            }
+            for version in versions {
+                println!("{} {version}", "Applied".green());
+            }
+        }),
+        MigrateAction::Revert {
+            steps,
+        } => revert_migrations(&mut conn, steps).map(|versions| {
+            for version in versions {
+                println!("{} {version}", "Reverted".yellow());
+            }
+        }),
+    };
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("{} {e}", "Migration failed:".red());
+            1
+        }
+    }
+}
+
+/// Brings the schema up to date when `database.run_migrations_on_launch` is set, then makes
+/// sure nothing is pending. The server must not run against an older schema.
+pub fn ensure_schema_is_current(run_on_launch: bool) -> Result<(), String> {
+    let mut conn =
+        establish_pg_connection().map_err(|e| format!("Failed to connect to database: {e}"))?;
+    if run_on_launch {
+        let versions = run_pending_migrations(&mut conn).map_err(|e| e.to_string())?;
+        for version in versions {
+            println!("{} {version}", "Applied migration".green());
+        }
+    }
+    let pending = pending_migrations(&mut conn).map_err(|e| e.to_string())?;
+    if pending.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Database schema is behind, pending migrations: {}. Run `crab_rocket migrate run` \
+             or enable `database.run_migrations_on_launch`.",
+            pending.join(", ")
+        ))
+    }
+}
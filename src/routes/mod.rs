@@ -1,2 +1,3 @@
 // pub mod docs;
+#[allow(clippy::module_inception)]
 pub mod routes;
@@ -1,21 +1,33 @@
+use crab_rocket_attendance::routes::attendance_route::*;
+use crab_rocket_auth::routes::auth_route;
+use crab_rocket_auth::routes::user_route::*;
 use crab_rocket_category::routes::category_route::*;
 use crab_rocket_customer::routes::customer_route::*;
+use crab_rocket_department::routes::department_route::*;
 use crab_rocket_employee::routes::employee_route::*;
 use crab_rocket_file::routes::{bin_file_route, form_file_route};
 use crab_rocket_follow::routes::follow_route::*;
+use crab_rocket_health::routes::health_route;
 use crab_rocket_info::routes::info_route;
 use crab_rocket_inventory::routes::inventory_route::*;
+use crab_rocket_leave::routes::leave_request_route::*;
+use crab_rocket_leave::routes::leave_route::*;
 use crab_rocket_order::routes::order_route::*;
+use crab_rocket_payroll::routes::compensation_route::*;
+use crab_rocket_payroll::routes::payroll_route::*;
 use crab_rocket_permission::routes::permission_route::*;
 use crab_rocket_post::routes::post_route::*;
 use crab_rocket_product::routes::product_route::*;
+use crab_rocket_review::routes::review_cycle_route::*;
+use crab_rocket_review::routes::review_route::*;
 use crab_rocket_role::routes::role_route::*;
 use crab_rocket_schema::routes::schema_routes;
 use crab_rocket_shipment::routes::shipment_route::*;
 use crab_rocket_supplier::routes::supplier_route::*;
+use crab_rocket_task::routes::project_route::*;
 use crab_rocket_task::routes::task_route::*;
-use crab_rocket_user::routes::user_route::*;
-use rocket::{get, routes, Route};
+use crab_rocket_task::routes::task_status_route::*;
+use rocket::{catchers, get, routes, Catcher, Route};
 
 pub fn module_routes() -> Vec<Route> {
     routes![
@@ -31,14 +43,66 @@ pub fn module_routes() -> Vec<Route> {
         form_file_route::file_stream,
         form_file_route::options_upload,
         info_route::get_info,
+        // auth routes
+        auth_route::login,
+        auth_route::refresh,
+        auth_route::logout,
+        auth_route::get_me,
+        auth_route::get_sessions,
+        auth_route::revoke_session,
+        auth_route::revoke_sessions,
+        auth_route::get_permissions,
+        auth_route::create_api_key,
+        auth_route::get_api_keys,
+        auth_route::revoke_api_key,
+        auth_route::get_email_status,
+        auth_route::request_email_verification,
+        auth_route::confirm_email_verification,
+        auth_route::request_password_reset,
+        auth_route::confirm_password_reset,
+        auth_route::get_two_factor_status,
+        auth_route::enroll_two_factor,
+        auth_route::activate_two_factor,
+        auth_route::disable_two_factor,
+        auth_route::regenerate_recovery_codes,
+        auth_route::get_lockout,
+        auth_route::clear_lockout,
+        auth_route::get_oidc_providers,
+        auth_route::authorize_oidc,
+        auth_route::oidc_callback,
+        auth_route::get_oidc_identities,
+        auth_route::impersonate,
+        auth_route::get_impersonations,
         // task routes
         get_tasks,
         filter_tasks,
+        get_my_tasks,
+        filter_my_tasks,
+        get_overdue_tasks,
         get_task_by_id,
         insert_single_task,
         delete_task_by_id,
         update_task_by_id,
+        update_task_status,
+        get_task_subtasks,
+        get_task_progress,
+        get_task_blockers,
+        insert_task_blocker,
+        delete_task_blocker,
         options_task_filter,
+        get_task_statuses,
+        insert_task_status,
+        insert_task_transition,
+        delete_task_transition,
+        options_task_status,
+        // project routes
+        get_projects,
+        get_project_by_id,
+        get_project_tasks,
+        insert_project,
+        update_project_by_id,
+        delete_project_by_id,
+        options_project,
         //user routes
         get_users,
         filter_users,
@@ -46,10 +110,13 @@ pub fn module_routes() -> Vec<Route> {
         insert_single_user,
         delete_user_by_id,
         update_user_by_id,
+        change_user_password,
         options_user,
         // post routes
         get_posts,
         filter_posts,
+        get_my_posts,
+        filter_my_posts,
         get_post_by_id,
         insert_single_post,
         delete_post_by_id,
@@ -67,10 +134,69 @@ pub fn module_routes() -> Vec<Route> {
         get_employees,
         filter_employees,
         get_employee_by_id,
+        get_employee_managers,
+        get_employee_reports,
         insert_single_employee,
         delete_employee_by_id,
         update_employee_by_id,
+        get_my_employee,
+        onboard_employee,
+        link_employee_user,
+        unlink_employee_user,
         options_employee,
+        // department routes
+        get_departments,
+        filter_departments,
+        get_department_by_id,
+        get_department_children,
+        get_department_tree,
+        insert_single_department,
+        delete_department_by_id,
+        update_department_by_id,
+        set_department_manager,
+        remove_department_manager,
+        options_department,
+        // payroll routes
+        get_employee_compensation,
+        insert_employee_compensation,
+        update_compensation_by_id,
+        get_employee_payslips,
+        insert_payroll_run,
+        get_payroll_runs,
+        get_payroll_run_by_id,
+        export_payroll_run,
+        get_payslip_by_id,
+        options_payroll_run,
+        // leave routes
+        get_leave_types,
+        insert_leave_type,
+        insert_leave_accrual,
+        get_employee_leave_balance,
+        get_employee_leave,
+        insert_employee_leave,
+        get_employee_leave_approvals,
+        approve_leave_request,
+        reject_leave_request,
+        cancel_leave_request,
+        options_leave_type,
+        // attendance routes
+        clock_in_employee,
+        clock_out_employee,
+        get_employee_attendance,
+        get_department_attendance,
+        // review routes
+        get_review_cycles,
+        get_review_cycle_by_id,
+        insert_review_cycle,
+        launch_review_cycle,
+        get_review_cycle_completion,
+        get_employee_reviews,
+        get_employee_assigned_reviews,
+        get_review_by_id,
+        update_review_by_id,
+        submit_review,
+        acknowledge_review,
+        options_review_cycle,
         // role routes
         get_roles,
         filter_roles,
@@ -78,6 +204,10 @@ pub fn module_routes() -> Vec<Route> {
         insert_single_role,
         delete_role_by_id,
         update_role_by_id,
+        get_permissions_of_role,
+        grant_permission_to_role,
+        revoke_permission_from_role,
+        set_role_two_factor_policy,
         options_role,
         // permission routes
         get_permissions,
@@ -106,6 +236,8 @@ pub fn module_routes() -> Vec<Route> {
         //product routes
         get_products,
         filter_products,
+        get_my_products,
+        filter_my_products,
         get_product_by_id,
         insert_single_product,
         delete_product_by_id,
@@ -147,6 +279,16 @@ pub fn module_routes() -> Vec<Route> {
         schema_routes::get_reload_count
     ]
 }
+/// JSON bodies for errors raised by request guards, e.g. a missing access token.
+pub fn module_catchers() -> Vec<Catcher> {
+    catchers![auth_route::unauthorized, auth_route::forbidden]
+}
+
+/// Probes for load balancers and orchestrators, mounted outside of `/api`.
+pub fn health_routes() -> Vec<Route> {
+    routes![health_route::get_live, health_route::get_ready]
+}
+
 #[get("/")]
 pub fn root() -> String {
     String::from("hello")
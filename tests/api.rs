@@ -0,0 +1,1654 @@
+use crab_rocket::routes::routes::{module_catchers, module_routes};
+use crab_rocket_auth::fairings::actor_tracking::{
+    ActorTracking, ACTOR_HEADER, EFFECTIVE_USER_HEADER,
+};
+use crab_rocket_test_support::{fixtures, MockOidc, TestClient, TestDb};
+use rocket::http::{ContentType, Header, Status};
+use serde_json::{json, Value};
+use totp_rs::{Algorithm, Secret, TOTP};
+
+#[test]
+fn test_get_info() {
+    let db = TestDb::new();
+    let mut conn = db.conn();
+    let user_id = fixtures::user(&mut conn, "info_user");
+    fixtures::post(&mut conn, user_id, "Counted");
+    let client = db.client(module_routes());
+
+    let response = client.get("/api/info");
+    assert_eq!(response.status, Status::Ok);
+    let info = &response.json()["body"]["data"];
+    // Ten users and their posts come with the migrations.
+    assert_eq!(info["user_count"], 11);
+    assert!(info["post_count"].as_i64().unwrap() >= 1);
+}
+
+#[test]
+fn test_role_crud() {
+    let db = TestDb::new();
+    let mut conn = db.conn();
+    let admin = fixtures::role(&mut conn, "Role admin");
+    for action in ["create", "delete"] {
+        fixtures::grant(&mut conn, admin, "role", action);
+    }
+    let user_id = fixtures::user(&mut conn, "role_admin");
+    fixtures::assign_role(&mut conn, user_id, admin);
+    let client = db.client(module_routes());
+    client.set_token(login(&client, "role_admin", "test")["access_token"].as_str());
+
+    let created = client.post_json(
+        "/api/role",
+        &json!({"role_name": "Tester", "description": "Runs tests", "permissions": "read"}),
+    );
+    assert_eq!(created.status, Status::Ok);
+    assert_eq!(created.code(), 200);
+    let role_id = created.json()["body"]["role_id"].as_i64().unwrap();
+
+    let fetched = client.get(&format!("/api/role/{role_id}"));
+    assert_eq!(fetched.json()["body"]["role_name"], "Tester");
+
+    let deleted = client.delete(&format!("/api/role/{role_id}"));
+    assert_eq!(deleted.code(), 200);
+    let missing = client.get(&format!("/api/role/{role_id}"));
+    assert_eq!(missing.code(), 204);
+}
+
+#[test]
+fn test_follow_roundtrip() {
+    let db = TestDb::new();
+    let mut conn = db.conn();
+    let alice = fixtures::user(&mut conn, "alice");
+    let bob = fixtures::user(&mut conn, "bob");
+    let client = db.client(module_routes());
+
+    let follow = json!({"following_user_id": alice, "followed_user_id": bob});
+    assert_eq!(client.post_json("/api/follow", &follow).status, Status::Unauthorized);
+    client.set_token(login(&client, "alice", "test")["access_token"].as_str());
+    let created = client.post_json("/api/follow", &follow);
+    assert_eq!(created.code(), 200);
+    assert_eq!(created.json()["body"]["followed_user_id"], bob);
+    let follow_id = created.json()["body"]["follow_id"].as_i64().unwrap();
+
+    let listed = client.get("/api/follow?limit=100");
+    let follows = listed.json()["body"]["data"].as_array().unwrap().clone();
+    assert!(follows.iter().any(|f| f["following_user_id"] == alice));
+
+    // Follows belong to the follower, bob can neither add nor remove them for alice.
+    client.set_token(login(&client, "bob", "test")["access_token"].as_str());
+    let for_alice = client
+        .post_json("/api/follow", &json!({"following_user_id": alice, "followed_user_id": alice}));
+    assert_eq!(for_alice.status, Status::Forbidden);
+    assert_eq!(for_alice.json()["message"], "missing permission `follow:moderate`");
+    assert_eq!(client.delete(&format!("/api/follow/{follow_id}")).status, Status::Forbidden);
+}
+
+#[test]
+fn test_write_routes_need_permissions() {
+    let db = TestDb::new();
+    let mut conn = db.conn();
+    let clerks = fixtures::role(&mut conn, "Clerks");
+    fixtures::grant(&mut conn, clerks, "supplier", "create");
+    let clerk_id = fixtures::user(&mut conn, "clerk");
+    fixtures::assign_role(&mut conn, clerk_id, clerks);
+    let admins = fixtures::role(&mut conn, "User admins");
+    for (resource, action) in [("user", "update"), ("employee", "update"), ("employee", "delete")] {
+        fixtures::grant(&mut conn, admins, resource, action);
+    }
+    let admin_id = fixtures::user(&mut conn, "user_admin");
+    fixtures::assign_role(&mut conn, admin_id, admins);
+    let employee_id = fixtures::employee(&mut conn, "staffer", None, None);
+    let client = db.client(module_routes());
+
+    let supplier = json!({"name": "Acme"});
+    assert_eq!(client.post_json("/api/supplier", &supplier).status, Status::Unauthorized);
+    let employee = json!({"employee_name": "staffer", "manager_id": null});
+    assert_eq!(client.post_json("/api/employee", &employee).status, Status::Unauthorized);
+    client.set_token(login(&client, "clerk", "test")["access_token"].as_str());
+    let created = client.post_json("/api/supplier", &supplier);
+    assert_eq!(created.status, Status::Ok);
+    let supplier_id = created.json()["body"]["supplier_id"].as_i64().unwrap();
+    let denied = client.delete(&format!("/api/supplier/{supplier_id}"));
+    assert_eq!(denied.status, Status::Forbidden);
+
+    // Without `user:update` nobody moves themselves into another role.
+    let uri = format!("/api/user/{clerk_id}");
+    let promote = json!({"username": "clerk", "mobile_phone": "phone-clerk", "role_id": admins});
+    assert_eq!(client.patch_json(&uri, &promote).status, Status::Forbidden);
+    assert_eq!(client.delete(&uri).status, Status::Forbidden);
+    // Nor rewrites the reporting lines that leave and reviews are routed along.
+    let employee_uri = format!("/api/employee/{employee_id}");
+    assert_eq!(client.post_json("/api/employee", &employee).status, Status::Forbidden);
+    assert_eq!(client.patch_json(&employee_uri, &employee).status, Status::Forbidden);
+    assert_eq!(client.delete(&employee_uri).status, Status::Forbidden);
+    client.set_token(login(&client, "user_admin", "test")["access_token"].as_str());
+    let promoted = client.patch_json(&uri, &promote);
+    assert_eq!(promoted.status, Status::Ok);
+    assert_eq!(promoted.json()["body"]["role_id"], admins);
+    assert_eq!(client.patch_json(&employee_uri, &employee).status, Status::Ok);
+    assert_eq!(client.delete(&employee_uri).status, Status::Ok);
+}
+
+#[test]
+fn test_unknown_route_is_404() {
+    let db = TestDb::new();
+    let client = db.client(module_routes());
+    assert_eq!(client.get("/api/does-not-exist").status, Status::NotFound);
+}
+
+#[test]
+fn test_change_password() {
+    let db = TestDb::new();
+    let mut conn = db.conn();
+    let user_id = fixtures::user(&mut conn, "changer");
+    let other_id = fixtures::user(&mut conn, "bystander");
+    let admin = fixtures::role(&mut conn, "User admin");
+    fixtures::grant(&mut conn, admin, "user", "update");
+    let admin_id = fixtures::user(&mut conn, "user_admin");
+    fixtures::assign_role(&mut conn, admin_id, admin);
+    let client = db.client(module_routes());
+    let uri = format!("/api/user/{user_id}/password");
+    let change = json!({"old_password": fixtures::PASSWORD, "new_password": "brand new password"});
+    let wrong = json!({"old_password": "not it", "new_password": "brand new password"});
+
+    assert_eq!(client.patch_json(&uri, &change).status, Status::Unauthorized);
+    let changer = login(&client, "changer", "laptop");
+    client.set_token(changer["access_token"].as_str());
+    let foreign = client.patch_json(&format!("/api/user/{other_id}/password"), &change);
+    assert_eq!(foreign.status, Status::Forbidden);
+    assert_eq!(client.patch_json(&uri, &wrong).status, Status::Forbidden);
+    let too_short = client
+        .patch_json(&uri, &json!({"old_password": fixtures::PASSWORD, "new_password": "short"}));
+    assert_eq!(too_short.status, Status::BadRequest);
+
+    let user_admin = login(&client, "user_admin", "desk");
+    client.set_token(user_admin["access_token"].as_str());
+    let missing = client.patch_json("/api/user/999999/password", &change);
+    assert_eq!(missing.status, Status::NotFound);
+    assert_eq!(client.patch_json(&uri, &change).status, Status::Ok);
+    let again = client.patch_json(
+        &uri,
+        &json!({"old_password": fixtures::PASSWORD, "new_password": "another password"}),
+    );
+    assert_eq!(again.status, Status::Forbidden);
+
+    // The third wrong password locks the account, even the right one has to wait.
+    assert_eq!(client.patch_json(&uri, &wrong).status, Status::Forbidden);
+    let locked = client.patch_json(
+        &uri,
+        &json!({"old_password": "brand new password", "new_password": "another password"}),
+    );
+    assert_eq!(locked.status, Status::TooManyRequests);
+
+    let fetched = client.get(&format!("/api/user/{user_id}"));
+    assert_eq!(fetched.json()["body"]["username"], "changer");
+    assert!(fetched.json()["body"].get("password").is_none());
+}
+
+#[test]
+fn test_login_and_current_user() {
+    let db = TestDb::new();
+    let mut conn = db.conn();
+    let user_id = fixtures::user(&mut conn, "walker");
+    let client = db.client(module_routes());
+
+    // Username, mobile phone number and email address all identify the user.
+    for login in ["walker", "phone-walker", "walker@example.com"] {
+        let response = client
+            .post_json("/api/auth/login", &json!({"login": login, "password": fixtures::PASSWORD}));
+        assert_eq!(response.status, Status::Ok, "login with {login}");
+        assert_eq!(response.json()["body"]["data"]["user"]["user_id"], user_id);
+    }
+    let wrong =
+        client.post_json("/api/auth/login", &json!({"login": "walker", "password": "not it"}));
+    assert_eq!(wrong.status, Status::Unauthorized);
+    let unknown = client
+        .post_json("/api/auth/login", &json!({"login": "nobody", "password": fixtures::PASSWORD}));
+    assert_eq!(unknown.status, Status::Unauthorized);
+    assert_eq!(unknown.json()["message"], wrong.json()["message"]);
+
+    assert_eq!(client.get("/api/auth/me").status, Status::Unauthorized);
+    client.set_token(Some("not.a.token"));
+    assert_eq!(client.get("/api/auth/me").status, Status::Unauthorized);
+
+    let login = client
+        .post_json("/api/auth/login", &json!({"login": "walker", "password": fixtures::PASSWORD}));
+    let token = login.json()["body"]["data"]["access_token"].as_str().unwrap().to_string();
+    client.set_token(Some(&token));
+    let me = client.get("/api/auth/me");
+    assert_eq!(me.status, Status::Ok);
+    assert_eq!(me.json()["body"]["data"]["username"], "walker");
+}
+
+fn login(client: &TestClient, login: &str, device: &str) -> Value {
+    let response = client.post_json(
+        "/api/auth/login",
+        &json!({"login": login, "password": fixtures::PASSWORD, "device_name": device}),
+    );
+    assert_eq!(response.status, Status::Ok);
+    response.json()["body"]["data"].clone()
+}
+
+#[test]
+fn test_sessions_refresh_and_revoke() {
+    let db = TestDb::new();
+    let mut conn = db.conn();
+    let user_id = fixtures::user(&mut conn, "sessions");
+    let client = db.client(module_routes());
+
+    let laptop = login(&client, "sessions", "laptop");
+    let phone = login(&client, "sessions", "phone");
+
+    client.set_token(laptop["access_token"].as_str());
+    let sessions = client.get("/api/auth/sessions").json()["body"]["data"].clone();
+    let sessions = sessions.as_array().unwrap();
+    assert_eq!(sessions.len(), 2);
+    let current: Vec<_> = sessions.iter().filter(|s| s["current"] == true).collect();
+    assert_eq!(current.len(), 1);
+    assert_eq!(current[0]["device_name"], "laptop");
+    assert_eq!(current[0]["user_id"], user_id);
+
+    // Rotation hands out a new refresh token, the old one must not work twice.
+    let rotated =
+        client.post_json("/api/auth/refresh", &json!({"refresh_token": phone["refresh_token"]}));
+    assert_eq!(rotated.status, Status::Ok);
+    let rotated = rotated.json()["body"]["data"].clone();
+    assert_ne!(rotated["refresh_token"], phone["refresh_token"]);
+    let reused =
+        client.post_json("/api/auth/refresh", &json!({"refresh_token": phone["refresh_token"]}));
+    assert_eq!(reused.status, Status::Unauthorized);
+    // Reuse revoked the phone session, including the access token from the rotation.
+    client.set_token(rotated["access_token"].as_str());
+    assert_eq!(client.get("/api/auth/me").status, Status::Unauthorized);
+
+    client.set_token(laptop["access_token"].as_str());
+    let tablet = login(&client, "sessions", "tablet");
+    let tablet_id = client.get("/api/auth/sessions").json()["body"]["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|s| s["device_name"] == "tablet")
+        .unwrap()["session_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    assert_eq!(client.delete(&format!("/api/auth/sessions/{tablet_id}")).status, Status::Ok);
+    assert_eq!(client.delete(&format!("/api/auth/sessions/{tablet_id}")).status, Status::NotFound);
+    client.set_token(tablet["access_token"].as_str());
+    assert_eq!(client.get("/api/auth/me").status, Status::Unauthorized);
+
+    client.set_token(laptop["access_token"].as_str());
+    assert_eq!(client.post_json("/api/auth/logout", &json!({})).status, Status::Ok);
+    assert_eq!(client.get("/api/auth/me").status, Status::Unauthorized);
+}
+
+#[test]
+fn test_role_permissions_are_enforced() {
+    let db = TestDb::new();
+    let mut conn = db.conn();
+    let editor = fixtures::role(&mut conn, "Editor");
+    let role_update = fixtures::grant(&mut conn, editor, "role", "update");
+    let other = fixtures::role(&mut conn, "Other");
+    let role_create = fixtures::grant(&mut conn, other, "role", "create");
+    let user_id = fixtures::user(&mut conn, "editor");
+    fixtures::assign_role(&mut conn, user_id, editor);
+    let client = db.client(module_routes());
+    let new_role = json!({"role_name": "Intern", "description": "Learns", "permissions": "read"});
+
+    assert_eq!(client.post_json("/api/role", &new_role).status, Status::Unauthorized);
+    client.set_token(login(&client, "editor", "test")["access_token"].as_str());
+    assert_eq!(client.post_json("/api/role", &new_role).status, Status::Forbidden);
+    assert_eq!(client.get("/api/auth/permissions").json()["body"]["data"], json!(["role:update"]));
+
+    let grant = format!("/api/role/{editor}/permission/{role_create}");
+    let granted = client.dispatch(client.inner().put(grant.clone()));
+    assert_eq!(granted.status, Status::Ok);
+    assert_eq!(granted.json()["message"], "Granted");
+    assert_eq!(
+        client.dispatch(client.inner().put(grant.clone())).json()["message"],
+        "Already granted"
+    );
+    let missing = client.dispatch(client.inner().put(format!("/api/role/{editor}/permission/0")));
+    assert_eq!(missing.status, Status::NotFound);
+
+    // Permissions are resolved per request, the grant applies to the same token right away.
+    assert_eq!(client.post_json("/api/role", &new_role).code(), 200);
+    let listed = client.get(&format!("/api/role/{editor}/permission"));
+    let names: Vec<_> = listed.json()["body"]["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["permission_name"].clone())
+        .collect();
+    assert_eq!(names, [json!("role:create"), json!("role:update")]);
+
+    assert_eq!(client.delete(&grant).status, Status::Ok);
+    assert_eq!(client.delete(&grant).json()["message"], "Was not granted");
+    assert_eq!(client.post_json("/api/role", &new_role).status, Status::Forbidden);
+    assert_eq!(
+        client.delete(&format!("/api/role/{editor}/permission/{role_update}")).status,
+        Status::Ok
+    );
+    let revoke_again = client.delete(&format!("/api/role/{editor}/permission/{role_update}"));
+    assert_eq!(revoke_again.status, Status::Forbidden);
+}
+
+#[test]
+fn test_api_keys_share_the_permission_guard() {
+    let db = TestDb::new();
+    let mut conn = db.conn();
+    let integrations = fixtures::role(&mut conn, "Integrations");
+    for action in ["create", "delete"] {
+        fixtures::grant(&mut conn, integrations, "role", action);
+    }
+    let admin = fixtures::role(&mut conn, "Role managers");
+    fixtures::grant(&mut conn, admin, "role", "update");
+    let user_id = fixtures::user(&mut conn, "erp");
+    fixtures::assign_role(&mut conn, user_id, integrations);
+    let client = db.client(module_routes());
+    client.set_token(login(&client, "erp", "laptop")["access_token"].as_str());
+
+    let created = client.post_json(
+        "/api/auth/api-keys",
+        &json!({"name": "ERP sync", "scopes": ["role:create"], "expires_in_days": 90}),
+    );
+    assert_eq!(created.status, Status::Ok);
+    let created = created.json()["body"]["data"].clone();
+    let key = created["key"].as_str().unwrap().to_string();
+    assert!(key.starts_with("crk_"));
+    let not_held = client
+        .post_json("/api/auth/api-keys", &json!({"name": "Too much", "scopes": ["role:update"]}));
+    assert_eq!(not_held.status, Status::Forbidden);
+
+    let listed = client.get("/api/auth/api-keys").json()["body"]["data"].clone();
+    assert_eq!(listed.as_array().unwrap().len(), 1);
+    assert_eq!(listed[0]["scopes"], json!(["role:create"]));
+    assert!(listed[0].get("key").is_none() && listed[0].get("key_hash").is_none());
+    assert!(listed[0]["last_used_at"].is_null());
+
+    // The key can do what its scopes allow and the role grants, nothing more.
+    client.set_token(Some(&key));
+    let new_role = json!({"role_name": "Synced", "description": "From ERP", "permissions": "read"});
+    let role = client.post_json("/api/role", &new_role);
+    assert_eq!(role.code(), 200);
+    let role_id = role.json()["body"]["role_id"].as_i64().unwrap();
+    assert_eq!(client.delete(&format!("/api/role/{role_id}")).status, Status::Forbidden);
+    assert_eq!(client.get("/api/auth/permissions").json()["body"]["data"], json!(["role:create"]));
+    assert_eq!(client.get("/api/auth/me").json()["body"]["data"]["user_id"], user_id);
+    assert_eq!(client.get("/api/auth/api-keys").status, Status::Forbidden);
+
+    client.set_token(login(&client, "erp", "laptop")["access_token"].as_str());
+    let listed = client.get("/api/auth/api-keys").json()["body"]["data"].clone();
+    assert!(!listed[0]["last_used_at"].is_null());
+    let api_key_id = created["api_key_id"].as_str().unwrap();
+    assert_eq!(client.delete(&format!("/api/auth/api-keys/{api_key_id}")).status, Status::Ok);
+    assert_eq!(client.delete(&format!("/api/auth/api-keys/{api_key_id}")).status, Status::NotFound);
+    client.set_token(Some(&key));
+    assert_eq!(client.post_json("/api/role", &new_role).status, Status::Unauthorized);
+}
+
+#[test]
+fn test_password_change_ends_sessions() {
+    let db = TestDb::new();
+    let mut conn = db.conn();
+    let user_id = fixtures::user(&mut conn, "forgetful");
+    let client = db.client(module_routes());
+    let first = login(&client, "forgetful", "laptop");
+    let second = login(&client, "forgetful", "phone");
+
+    client.set_token(first["access_token"].as_str());
+    let revoked = client.delete("/api/auth/sessions?keep_current=true");
+    assert_eq!(revoked.json()["body"]["data"], 1);
+    assert_eq!(client.get("/api/auth/me").status, Status::Ok);
+    client.set_token(second["access_token"].as_str());
+    assert_eq!(client.get("/api/auth/me").status, Status::Unauthorized);
+
+    client.set_token(first["access_token"].as_str());
+    let changed = client.patch_json(
+        &format!("/api/user/{user_id}/password"),
+        &json!({"old_password": fixtures::PASSWORD, "new_password": "a better password"}),
+    );
+    assert_eq!(changed.status, Status::Ok);
+    assert_eq!(client.get("/api/auth/me").status, Status::Unauthorized);
+    let refreshed =
+        client.post_json("/api/auth/refresh", &json!({"refresh_token": first["refresh_token"]}));
+    assert_eq!(refreshed.status, Status::Unauthorized);
+}
+
+/// The token from the newest mail to `to`.
+fn mailed_token(to: &str) -> String {
+    let mail = crab_rocket_mail::outbox().sent_to(to).pop().expect("a mail");
+    let token = mail.body.lines().find(|line| line.split('.').count() == 3 && !line.contains(' '));
+    token.expect("a token in the mail").to_string()
+}
+
+#[test]
+fn test_email_verification_and_password_reset() {
+    let db = TestDb::new();
+    let mut conn = db.conn();
+    fixtures::user(&mut conn, "forgetful_ada");
+    let email = "forgetful_ada@example.com";
+    let client = db.client(module_routes());
+    let session = login(&client, "forgetful_ada", "laptop");
+
+    client.set_token(session["access_token"].as_str());
+    assert!(client.get("/api/auth/verify-email").json()["body"]["data"]["verified_at"].is_null());
+    let requested = client.post_json("/api/auth/verify-email/request", &json!({}));
+    assert_eq!(requested.json()["message"], "Verification mail sent");
+    let verify_token = mailed_token(email);
+    client.set_token(None);
+    let confirm = json!({"token": verify_token});
+    assert_eq!(client.post_json("/api/auth/verify-email/confirm", &confirm).status, Status::Ok);
+    let again = client.post_json("/api/auth/verify-email/confirm", &confirm);
+    assert_eq!(again.status, Status::BadRequest);
+    client.set_token(session["access_token"].as_str());
+    let status = client.get("/api/auth/verify-email").json()["body"]["data"].clone();
+    assert_eq!(status["email"], email);
+    assert!(!status["verified_at"].is_null());
+    let requested = client.post_json("/api/auth/verify-email/request", &json!({}));
+    assert_eq!(requested.json()["message"], "Email address is verified already");
+    client.set_token(None);
+
+    // Unknown accounts get the same answer and no mail.
+    let mails = crab_rocket_mail::outbox().sent().len();
+    let unknown =
+        client.post_json("/api/auth/password-reset/request", &json!({"login": "nobody_ada"}));
+    assert_eq!(unknown.status, Status::Ok);
+    let requested =
+        client.post_json("/api/auth/password-reset/request", &json!({"login": "forgetful_ada"}));
+    assert_eq!(requested.json()["message"], unknown.json()["message"]);
+    assert!(crab_rocket_mail::outbox().sent().len() > mails);
+    let reset_token = mailed_token(email);
+    assert_ne!(reset_token, verify_token);
+
+    let wrong_purpose = json!({"token": verify_token, "new_password": "a new password"});
+    let wrong = client.post_json("/api/auth/password-reset/confirm", &wrong_purpose);
+    assert_eq!(wrong.status, Status::BadRequest);
+    let too_short = json!({"token": reset_token, "new_password": "short"});
+    let short = client.post_json("/api/auth/password-reset/confirm", &too_short);
+    assert_eq!(short.status, Status::BadRequest);
+    let reset = json!({"token": reset_token, "new_password": "a new password"});
+    assert_eq!(client.post_json("/api/auth/password-reset/confirm", &reset).status, Status::Ok);
+    let reused = client.post_json("/api/auth/password-reset/confirm", &reset);
+    assert_eq!(reused.status, Status::BadRequest);
+
+    // The reset ended the old session, the new password works.
+    client.set_token(session["access_token"].as_str());
+    assert_eq!(client.get("/api/auth/me").status, Status::Unauthorized);
+    let relogin = client.post_json(
+        "/api/auth/login",
+        &json!({"login": "forgetful_ada", "password": "a new password"}),
+    );
+    assert_eq!(relogin.status, Status::Ok);
+}
+
+/// What an authenticator app shows for `secret`, `offset_secs` from now.
+fn totp_code(secret: &str, offset_secs: u64) -> String {
+    let secret = Secret::Encoded(secret.to_string()).to_bytes().unwrap();
+    let totp = TOTP::new_unchecked(Algorithm::SHA1, 6, 0, 30, secret, None, String::new());
+    totp.generate(chrono::Utc::now().timestamp() as u64 + offset_secs)
+}
+
+#[test]
+fn test_two_factor_login_and_role_policy() {
+    let db = TestDb::new();
+    let mut conn = db.conn();
+    let officers = fixtures::role(&mut conn, "Security officers");
+    fixtures::grant(&mut conn, officers, "role", "update");
+    let user_id = fixtures::user(&mut conn, "officer");
+    fixtures::assign_role(&mut conn, user_id, officers);
+    let client = db.client(module_routes());
+    let policy = format!("/api/role/{officers}/two-factor");
+    let put_policy = |required: bool| client.put_json(&policy, &json!({ "required": required }));
+
+    client.set_token(login(&client, "officer", "laptop")["access_token"].as_str());
+    let required = put_policy(true);
+    assert_eq!(required.status, Status::Ok);
+    assert_eq!(required.json()["body"]["data"]["require_two_factor"], true);
+
+    // The password-only session keeps working, but the role grants it nothing.
+    assert_eq!(put_policy(true).status, Status::Forbidden);
+    let refused = client.get("/api/auth/permissions");
+    assert_eq!(refused.status, Status::Forbidden);
+    assert!(refused.json()["message"].as_str().unwrap().contains("two-factor"));
+    let status = client.get("/api/auth/2fa").json()["body"]["data"].clone();
+    assert_eq!(status["required"], true);
+    assert!(status["enabled_at"].is_null());
+
+    let enrollment = client.post_json("/api/auth/2fa/enroll", &json!({}));
+    assert_eq!(enrollment.status, Status::Ok);
+    let enrollment = enrollment.json()["body"]["data"].clone();
+    let secret = enrollment["secret"].as_str().unwrap().to_string();
+    assert!(enrollment["otpauth_uri"]
+        .as_str()
+        .unwrap()
+        .starts_with("otpauth://totp/crab_rocket:officer%40example.com?secret="));
+    let wrong = client.post_json("/api/auth/2fa/activate", &json!({"code": "12345"}));
+    assert_eq!(wrong.status, Status::BadRequest);
+    let first_code = totp_code(&secret, 0);
+    let activated = client.post_json("/api/auth/2fa/activate", &json!({"code": first_code}));
+    assert_eq!(activated.status, Status::Ok);
+    let recovery_codes = activated.json()["body"]["data"]["recovery_codes"].clone();
+    assert_eq!(recovery_codes.as_array().unwrap().len(), 10);
+    assert_eq!(put_policy(true).status, Status::Ok);
+    assert_eq!(client.post_json("/api/auth/2fa/enroll", &json!({})).status, Status::Conflict);
+
+    client.set_token(None);
+    let login_with = |code: Option<&str>| {
+        client.post_json(
+            "/api/auth/login",
+            &json!({"login": "officer", "password": fixtures::PASSWORD, "code": code}),
+        )
+    };
+    let missing = login_with(None);
+    assert_eq!(missing.status, Status::Unauthorized);
+    assert_eq!(missing.json()["message"], "two-factor code required");
+    // A code works once, the next one from the app does.
+    assert_eq!(login_with(Some(&first_code)).status, Status::Unauthorized);
+    let verified = login_with(Some(&totp_code(&secret, 30)));
+    assert_eq!(verified.status, Status::Ok);
+    let recovery_code = recovery_codes[0].as_str().unwrap().to_uppercase();
+    assert_eq!(login_with(Some(&recovery_code)).status, Status::Ok);
+    assert_eq!(login_with(Some(&recovery_code)).status, Status::Unauthorized);
+
+    client.set_token(verified.json()["body"]["data"]["access_token"].as_str());
+    let status = client.get("/api/auth/2fa").json()["body"]["data"].clone();
+    assert_eq!(status["recovery_codes_left"], 9);
+    assert_eq!(status["session_verified"], true);
+    let disable = json!({"code": recovery_codes[1]});
+    assert_eq!(client.post_json("/api/auth/2fa/disable", &disable).status, Status::Forbidden);
+    assert_eq!(put_policy(false).status, Status::Ok);
+    assert_eq!(client.post_json("/api/auth/2fa/disable", &disable).status, Status::Ok);
+    assert_eq!(login_with(None).status, Status::Ok);
+}
+
+#[test]
+fn test_failed_logins_lock_the_account_and_the_address() {
+    let db = TestDb::new();
+    let mut conn = db.conn();
+    let guards = fixtures::role(&mut conn, "Account guards");
+    fixtures::grant(&mut conn, guards, "lockout", "manage");
+    let guard_id = fixtures::user(&mut conn, "lock_guard");
+    fixtures::assign_role(&mut conn, guard_id, guards);
+    let user_id = fixtures::user(&mut conn, "lockable");
+    fixtures::user(&mut conn, "bystander");
+    let client = db.client(module_routes());
+    let login_from = |ip: &str, login: &str, password: &str| {
+        client.dispatch(
+            client
+                .inner()
+                .post("/api/auth/login")
+                .remote(format!("{ip}:4000").parse().unwrap())
+                .header(ContentType::JSON)
+                .body(json!({"login": login, "password": password}).to_string()),
+        )
+    };
+
+    // The test profile locks an account after three failures and an address after five.
+    for _ in 0..3 {
+        assert_eq!(login_from("10.0.0.1", "lockable", "wrong").status, Status::Unauthorized);
+    }
+    let locked = login_from("10.0.0.2", "lockable", fixtures::PASSWORD);
+    assert_eq!(locked.status, Status::TooManyRequests);
+    assert!(locked.json()["message"].as_str().unwrap().starts_with("too many failed logins"));
+
+    let lockout = format!("/api/auth/lockouts/{user_id}");
+    client.set_token(login(&client, "bystander", "laptop")["access_token"].as_str());
+    assert_eq!(client.get(&lockout).status, Status::Forbidden);
+    client.set_token(login(&client, "lock_guard", "desk")["access_token"].as_str());
+    let status = client.get(&lockout).json()["body"]["data"].clone();
+    assert_eq!(status["locked"], true);
+    assert_eq!(status["throttle"]["failed_count"], 3);
+    let events: Vec<_> = status["recent_events"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|event| event["event_type"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(events[..3], ["login_blocked", "account_locked", "login_failed"]);
+    assert_eq!(client.get("/api/auth/lockouts/999999").status, Status::NotFound);
+
+    let cleared = client.delete(&lockout);
+    assert_eq!(cleared.status, Status::Ok);
+    assert_eq!(cleared.json()["message"], "Lockout cleared");
+    assert_eq!(client.delete(&lockout).json()["message"], "No failed logins to clear");
+    assert_eq!(login_from("10.0.0.1", "lockable", fixtures::PASSWORD).status, Status::Ok);
+
+    // Unknown logins count against the address only.
+    for i in 0..5 {
+        let probe = login_from("10.0.0.3", &format!("nobody_{i}"), "guess");
+        assert_eq!(probe.status, Status::Unauthorized);
+    }
+    let blocked = login_from("10.0.0.3", "lockable", fixtures::PASSWORD);
+    assert_eq!(blocked.status, Status::TooManyRequests);
+    assert_eq!(login_from("10.0.0.4", "lockable", fixtures::PASSWORD).status, Status::Ok);
+}
+
+#[test]
+fn test_oidc_login_links_and_provisions_users() {
+    let db = TestDb::new();
+    let mut conn = db.conn();
+    let ada_id = fixtures::user(&mut conn, "sso_ada");
+    let client = db.client(module_routes());
+    let mock = MockOidc::start();
+    let login_at = |provider: &str, claims: Value| {
+        let authorize = client.get(&format!("/api/auth/oidc/{provider}/authorize"));
+        assert_eq!(authorize.status, Status::Ok);
+        let url = authorize.json()["body"]["data"]["authorization_url"].clone();
+        let query = mock.authorize(url.as_str().unwrap(), claims);
+        (client.get(&format!("/api/auth/oidc/{provider}/callback?{query}")), query)
+    };
+
+    let providers = client.get("/api/auth/oidc/providers").json()["body"]["data"].clone();
+    assert_eq!(providers[0]["name"], "mock");
+    assert_eq!(client.get("/api/auth/oidc/nowhere/authorize").status, Status::NotFound);
+
+    // A verified email links the identity to the user who has it.
+    let ada = json!({"sub": "ada-1", "email": "sso_ada@example.com", "email_verified": true});
+    let (linked, query) = login_at("mock_linked_only", ada.clone());
+    assert_eq!(linked.status, Status::Ok);
+    let tokens = linked.json()["body"]["data"].clone();
+    assert_eq!(tokens["user"]["user_id"], ada_id);
+    let replayed = client.get(&format!("/api/auth/oidc/mock_linked_only/callback?{query}"));
+    assert_eq!(replayed.status, Status::BadRequest);
+    client.set_token(tokens["access_token"].as_str());
+    let identities = client.get("/api/auth/oidc/identities").json()["body"]["data"].clone();
+    assert_eq!(identities[0]["provider"], "mock_linked_only");
+    assert_eq!(identities[0]["subject"], "ada-1");
+    client.set_token(None);
+
+    let stranger = json!({"sub": "x-1", "email": "stranger@example.com", "email_verified": true});
+    let (refused, _) = login_at("mock_linked_only", stranger);
+    assert_eq!(refused.status, Status::Forbidden);
+    let unverified = json!({"sub": "x-2", "email": "sso_ada@example.com", "email_verified": false});
+    assert_eq!(login_at("mock_linked_only", unverified).0.status, Status::Forbidden);
+    let (forged, _) = login_at("mock_linked_only", json!({"sub": "ada-1", "nonce": "replayed"}));
+    assert_eq!(forged.status, Status::Unauthorized);
+    assert!(forged.json()["message"].as_str().unwrap().contains("nonce"));
+    let denied = client.get("/api/auth/oidc/mock/callback?error=access_denied&state=x");
+    assert_eq!(denied.status, Status::Unauthorized);
+
+    // `mock` provisions unknown emails, the taken username gets a number.
+    let newcomer = json!({
+        "sub": "new-1",
+        "email": "newcomer@example.com",
+        "email_verified": true,
+        "preferred_username": "sso_ada",
+        "name": "New Comer",
+    });
+    let (provisioned, _) = login_at("mock", newcomer);
+    assert_eq!(provisioned.status, Status::Ok);
+    let user = provisioned.json()["body"]["data"]["user"].clone();
+    assert_eq!(user["username"], "sso_ada2");
+    assert_eq!(user["full_name"], "New Comer");
+    assert!(user["role_id"].is_i64());
+    // Later logins find the user by `sub`, even with another email.
+    let renamed = json!({"sub": "new-1", "email": "renamed@example.com", "email_verified": true});
+    let (again, _) = login_at("mock", renamed);
+    assert_eq!(again.json()["body"]["data"]["user"]["user_id"], user["user_id"]);
+}
+
+#[test]
+fn test_only_owners_and_moderators_change_posts_and_tasks() {
+    let db = TestDb::new();
+    let mut conn = db.conn();
+    let alice_id = fixtures::user(&mut conn, "owner_alice");
+    let bob_id = fixtures::user(&mut conn, "owner_bob");
+    let editors = fixtures::role(&mut conn, "Editors");
+    fixtures::grant(&mut conn, editors, "post", "moderate");
+    let editor_id = fixtures::user(&mut conn, "editor");
+    fixtures::assign_role(&mut conn, editor_id, editors);
+    let client = db.client(module_routes());
+    let draft = json!({"title": "Draft", "body": "First words"});
+    assert_eq!(client.post_json("/api/post", &draft).status, Status::Unauthorized);
+
+    client.set_token(login(&client, "owner_alice", "laptop")["access_token"].as_str());
+    let created = client.post_json("/api/post", &draft);
+    assert_eq!(created.status, Status::Ok);
+    let post = created.json()["body"].clone();
+    assert_eq!(post["user_id"], alice_id);
+    let post_id = post["post_id"].as_i64().unwrap();
+    let post_url = format!("/api/post/{post_id}");
+    let for_bob = client.post_json("/api/post", &json!({"title": "Yours", "user_id": bob_id}));
+    assert_eq!(for_bob.status, Status::Forbidden);
+    assert_eq!(for_bob.json()["message"], "missing permission `post:moderate`");
+    // Leaving out `user_id` keeps the owner.
+    let edited = client.patch_json(&post_url, &json!({"title": "Edited", "body": "Better"}));
+    assert_eq!(edited.status, Status::Ok);
+    assert_eq!(edited.json()["body"]["user_id"], alice_id);
+    let mine = client.get("/api/post/mine").json()["body"]["data"].clone();
+    assert_eq!(mine.as_array().unwrap().len(), 1);
+    assert_eq!(mine[0]["post_id"], post_id);
+
+    client.set_token(login(&client, "owner_bob", "laptop")["access_token"].as_str());
+    let denied = client.patch_json(&post_url, &json!({"title": "Defaced"}));
+    assert_eq!(denied.status, Status::Forbidden);
+    assert_eq!(
+        denied.json()["message"],
+        format!("post {post_id} belongs to another user, changing it needs `post:moderate`")
+    );
+    assert_eq!(client.delete(&post_url).status, Status::Forbidden);
+    let filtered =
+        client.post_json("/api/post/mine/filter", &json!({"filter": {"user_id": alice_id}}));
+    assert_eq!(filtered.json()["body"]["data"], json!([]));
+
+    let task = client.post_json("/api/task", &json!({"title": "Bob's chore"}));
+    assert_eq!(task.json()["body"]["user_id"], bob_id);
+    let task_url = format!("/api/task/{}", task.json()["body"]["task_id"]);
+    assert_eq!(client.get("/api/task/mine").json()["body"]["data"].as_array().unwrap().len(), 1);
+
+    // Posts are theirs to moderate, tasks are not.
+    client.set_token(login(&client, "editor", "laptop")["access_token"].as_str());
+    let handed_over = client.patch_json(&post_url, &json!({"title": "Edited", "user_id": bob_id}));
+    assert_eq!(handed_over.status, Status::Ok);
+    assert_eq!(handed_over.json()["body"]["user_id"], bob_id);
+    let task_denied = client.delete(&task_url);
+    assert_eq!(task_denied.status, Status::Forbidden);
+    assert!(task_denied.json()["message"].as_str().unwrap().contains("`task:moderate`"));
+    assert_eq!(client.delete(&post_url).status, Status::Ok);
+    assert_eq!(client.delete(&post_url).status, Status::NotFound);
+
+    client.set_token(login(&client, "owner_bob", "laptop")["access_token"].as_str());
+    assert_eq!(client.delete(&task_url).status, Status::Ok);
+}
+
+#[test]
+fn test_impersonation_is_audited_and_limited() {
+    let db = TestDb::new();
+    let mut conn = db.conn();
+    let support = fixtures::role(&mut conn, "Support");
+    for action in ["impersonate", "update"] {
+        fixtures::grant(&mut conn, support, "user", action);
+    }
+    fixtures::grant(&mut conn, support, "lockout", "manage");
+    let agent_id = fixtures::user(&mut conn, "support_agent");
+    fixtures::assign_role(&mut conn, agent_id, support);
+    let peer_id = fixtures::user(&mut conn, "support_peer");
+    fixtures::assign_role(&mut conn, peer_id, support);
+    let carol_id = fixtures::user(&mut conn, "customer_carol");
+    fixtures::post(&mut conn, carol_id, "Carol's post");
+    let payroll = fixtures::role(&mut conn, "Payroll");
+    fixtures::grant(&mut conn, payroll, "payroll", "read");
+    let clerk_id = fixtures::user(&mut conn, "payroll_clerk");
+    fixtures::assign_role(&mut conn, clerk_id, payroll);
+    let client = db.client_with(module_routes(), |rocket| {
+        rocket.register("/", module_catchers()).attach(ActorTracking::new("/api"))
+    });
+    let impersonate = |user_id: i32, reason: &str| {
+        client.post_json(&format!("/api/auth/impersonate/{user_id}"), &json!({"reason": reason}))
+    };
+
+    client.set_token(login(&client, "customer_carol", "laptop")["access_token"].as_str());
+    assert_eq!(impersonate(agent_id, "Curious").status, Status::Forbidden);
+
+    client.set_token(login(&client, "support_agent", "desk")["access_token"].as_str());
+    let own = client.get("/api/auth/me");
+    assert_eq!(own.header(ACTOR_HEADER), Some(agent_id.to_string().as_str()));
+    assert_eq!(own.header(EFFECTIVE_USER_HEADER), Some(agent_id.to_string().as_str()));
+    assert_eq!(impersonate(carol_id, " ").status, Status::BadRequest);
+    assert_eq!(impersonate(peer_id, "Ticket 41").status, Status::Forbidden);
+    assert_eq!(impersonate(999999, "Ticket 41").status, Status::NotFound);
+    // Nobody gains a permission by impersonating its holder.
+    let stronger = impersonate(clerk_id, "Ticket 41");
+    assert_eq!(stronger.status, Status::Forbidden);
+    assert_eq!(
+        stronger.json()["message"],
+        "users with permissions you do not hold can not be impersonated"
+    );
+    let started = impersonate(carol_id, "Ticket 42");
+    assert_eq!(started.status, Status::Ok);
+    let started = started.json()["body"]["data"].clone();
+    assert_eq!(started["user"]["username"], "customer_carol");
+    assert_eq!(started["impersonation"]["actor_user_id"], agent_id);
+
+    // Requests are made as Carol and say who made them.
+    client.set_token(started["access_token"].as_str());
+    let me = client.get("/api/auth/me");
+    assert_eq!(me.json()["body"]["data"]["user_id"], carol_id);
+    assert_eq!(me.header(ACTOR_HEADER), Some(agent_id.to_string().as_str()));
+    assert_eq!(me.header(EFFECTIVE_USER_HEADER), Some(carol_id.to_string().as_str()));
+    assert_eq!(client.get("/api/post/mine").json()["body"]["data"].as_array().unwrap().len(), 1);
+    for blocked in [
+        client.patch_json(&format!("/api/user/{carol_id}/password"), &json!({})),
+        client.post_json("/api/role", &json!({"role_name": "Sneaky"})),
+        client.post_json("/api/auth/api-keys", &json!({"name": "Backdoor", "scopes": []})),
+        client.post_json("/api/auth/2fa/disable", &json!({"code": "000000"})),
+        impersonate(agent_id, "Chained"),
+    ] {
+        assert_eq!(blocked.status, Status::Forbidden);
+        assert_eq!(blocked.json()["message"], "not allowed while impersonating");
+        assert_eq!(blocked.header(ACTOR_HEADER), Some(agent_id.to_string().as_str()));
+    }
+    // The scheme is matched without regard to case, here as everywhere else.
+    let token = started["access_token"].as_str().unwrap().to_string();
+    client.set_token(None);
+    let lowercase = client.dispatch(
+        client
+            .inner()
+            .post("/api/role")
+            .header(Header::new("Authorization", format!("bearer {token}")))
+            .header(ContentType::JSON)
+            .body(json!({"role_name": "Sneaky"}).to_string()),
+    );
+    assert_eq!(lowercase.status, Status::Forbidden);
+    assert_eq!(lowercase.json()["message"], "not allowed while impersonating");
+    assert_eq!(lowercase.header(ACTOR_HEADER), Some(agent_id.to_string().as_str()));
+    client.set_token(Some(&token));
+    assert_eq!(
+        client.post_json("/api/auth/logout", &json!({})).json()["message"],
+        "Impersonation ended"
+    );
+    assert_eq!(client.get("/api/auth/me").status, Status::Unauthorized);
+
+    client.set_token(login(&client, "support_agent", "desk")["access_token"].as_str());
+    let history = client.get(&format!("/api/auth/impersonations?user_id={carol_id}"));
+    let history = history.json()["body"]["data"].clone();
+    assert_eq!(history.as_array().unwrap().len(), 1);
+    assert_eq!(history[0]["reason"], "Ticket 42");
+    assert!(!history[0]["ended_at"].is_null());
+    let events = client.get(&format!("/api/auth/lockouts/{carol_id}")).json()["body"]["data"]
+        ["recent_events"]
+        .clone();
+    let events = events.as_array().unwrap();
+    for event_type in ["impersonation_started", "impersonation_ended"] {
+        assert!(events
+            .iter()
+            .any(|e| e["event_type"] == event_type && e["actor_user_id"] == agent_id));
+    }
+    let blocked_request = events
+        .iter()
+        .find(|e| e["detail"] == format!("PATCH /api/user/{carol_id}/password -> 403 (blocked)"))
+        .expect("blocked request is logged");
+    assert_eq!(blocked_request["event_type"], "impersonated_request");
+    assert_eq!(blocked_request["actor_user_id"], agent_id);
+}
+
+#[test]
+fn test_department_hierarchy_managers_and_head_count() {
+    let db = TestDb::new();
+    let mut conn = db.conn();
+    let planners = fixtures::role(&mut conn, "Planners");
+    for action in ["create", "update", "delete"] {
+        fixtures::grant(&mut conn, planners, "department", action);
+    }
+    let planner_id = fixtures::user(&mut conn, "planner");
+    fixtures::assign_role(&mut conn, planner_id, planners);
+    fixtures::user(&mut conn, "onlooker");
+    let client = db.client(module_routes());
+    let create = |name: &str, parent: Option<i64>| {
+        client.post_json(
+            "/api/department",
+            &json!({"department_name": name, "parent_department_id": parent}),
+        )
+    };
+
+    client.set_token(login(&client, "onlooker", "laptop")["access_token"].as_str());
+    assert_eq!(create("Logistics", None).status, Status::Forbidden);
+
+    client.set_token(login(&client, "planner", "laptop")["access_token"].as_str());
+    let logistics = create("Logistics", None);
+    assert_eq!(logistics.status, Status::Ok);
+    let logistics_id = logistics.json()["body"]["department_id"].as_i64().unwrap();
+    assert_eq!(create("Logistics", None).status, Status::Conflict);
+    assert_eq!(create("Orphans", Some(999999)).status, Status::BadRequest);
+    let fleet_id =
+        create("Fleet", Some(logistics_id)).json()["body"]["department_id"].as_i64().unwrap();
+    let trucks_id =
+        create("Trucks", Some(fleet_id)).json()["body"]["department_id"].as_i64().unwrap();
+
+    // Logistics can not move below its own grandchild.
+    let cycle = client.patch_json(
+        &format!("/api/department/{logistics_id}"),
+        &json!({"department_name": "Logistics", "parent_department_id": trucks_id}),
+    );
+    assert_eq!(cycle.status, Status::Conflict);
+    let children = client.get(&format!("/api/department/{logistics_id}/children"));
+    assert_eq!(children.json()["body"][0]["department_id"], fleet_id);
+
+    let (fleet, trucks) = (Some(fleet_id as i32), Some(trucks_id as i32));
+    let head = fixtures::employee(&mut conn, "fleet_head", fleet, None);
+    fixtures::employee(&mut conn, "driver", trucks, Some(head));
+    let manager =
+        client.put_json(&format!("/api/department/{fleet_id}/manager/{head}"), &json!({}));
+    assert_eq!(manager.status, Status::Ok);
+    assert_eq!(manager.json()["body"]["manager_id"], head);
+    assert_eq!(manager.json()["body"]["number_of_employees"], 1);
+    let unknown =
+        client.put_json(&format!("/api/department/{fleet_id}/manager/999999"), &json!({}));
+    assert_eq!(unknown.status, Status::BadRequest);
+    let filtered = client.post_json(
+        "/api/department/filter",
+        &json!({"pagination": {"limit": 10, "offset": 0}, "filter": {"manager_id": head}}),
+    );
+    assert_eq!(filtered.json()["body"]["data"][0]["department_id"], fleet_id);
+
+    let not_empty = client.delete(&format!("/api/department/{fleet_id}"));
+    assert_eq!(not_empty.status, Status::Conflict);
+    assert_eq!(
+        not_empty.json()["message"],
+        "department still has 1 employees and 1 sub-departments"
+    );
+    let removed = client.delete(&format!("/api/department/{fleet_id}/manager"));
+    assert!(removed.json()["body"]["manager_id"].is_null());
+}
+
+#[test]
+fn test_org_chart_follows_reporting_lines() {
+    let db = TestDb::new();
+    let mut conn = db.conn();
+    let client = db.client(module_routes());
+    let company = fixtures::department(&mut conn, "Org company", None);
+    let engineering = fixtures::department(&mut conn, "Org engineering", Some(company));
+    let platform = fixtures::department(&mut conn, "Org platform", Some(engineering));
+    let ceo = fixtures::employee(&mut conn, "org_ceo", Some(company), None);
+    let cto = fixtures::employee(&mut conn, "org_cto", Some(engineering), Some(ceo));
+    let lead = fixtures::employee(&mut conn, "org_lead", Some(platform), Some(cto));
+    let dev = fixtures::employee(&mut conn, "org_dev", Some(platform), Some(lead));
+    fixtures::employee(&mut conn, "org_ops", Some(platform), Some(lead));
+    let planners = fixtures::role(&mut conn, "Org planners");
+    fixtures::grant(&mut conn, planners, "employee", "update");
+    let planner_id = fixtures::user(&mut conn, "org_planner");
+    fixtures::assign_role(&mut conn, planner_id, planners);
+
+    let managers = client.get(&format!("/api/employee/{dev}/managers"));
+    assert_eq!(managers.status, Status::Ok);
+    let chain: Vec<_> = managers.json()["body"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| (m["employee_id"].as_i64().unwrap(), m["depth"].as_i64().unwrap()))
+        .collect();
+    assert_eq!(chain, [(lead as i64, 1), (cto as i64, 2), (ceo as i64, 3)]);
+
+    let tree = client.get(&format!("/api/employee/{ceo}/reports?depth=2")).json();
+    let cto_node = &tree["body"]["reports"][0];
+    assert_eq!(cto_node["employee_id"], cto);
+    assert_eq!(cto_node["reports"][0]["employee_id"], lead);
+    // The lead's reports are below the depth limit, but still counted.
+    assert_eq!(cto_node["reports"][0]["reports"], json!([]));
+    assert_eq!(cto_node["reports"][0]["direct_reports"], 2);
+    let full = client.get(&format!("/api/employee/{ceo}/reports")).json();
+    assert_eq!(full["body"]["reports"][0]["reports"][0]["reports"].as_array().unwrap().len(), 2);
+    let too_deep = client.get(&format!("/api/employee/{ceo}/reports?depth=0"));
+    assert_eq!(too_deep.status, Status::BadRequest);
+    assert_eq!(client.get("/api/employee/999999/managers").status, Status::NotFound);
+
+    client.set_token(login(&client, "org_planner", "laptop")["access_token"].as_str());
+    let cyclic = client.patch_json(
+        &format!("/api/employee/{ceo}"),
+        &json!({"employee_name": "org_ceo", "manager_id": dev}),
+    );
+    assert_eq!(cyclic.status, Status::Conflict);
+    assert_eq!(
+        cyclic.json()["message"],
+        format!("employee {dev} can not manage employee {ceo}, they report to employee {ceo}")
+    );
+    let own = client.patch_json(
+        &format!("/api/employee/{ceo}"),
+        &json!({"employee_name": "org_ceo", "manager_id": ceo}),
+    );
+    assert_eq!(own.status, Status::Conflict);
+
+    let departments = client.get(&format!("/api/department/{company}/tree?depth=1")).json();
+    assert_eq!(departments["body"]["total_employees"], 5);
+    assert_eq!(departments["body"]["number_of_employees"], 1);
+    let sub = &departments["body"]["sub_departments"][0];
+    assert_eq!(
+        (sub["department_id"].as_i64(), sub["total_employees"].as_i64()),
+        (Some(engineering as i64), Some(4))
+    );
+    assert_eq!(sub["sub_departments"], json!([]));
+}
+
+#[test]
+fn test_payroll_pays_compensation_by_effective_dates() {
+    let db = TestDb::new();
+    let mut conn = db.conn();
+    let clerks = fixtures::role(&mut conn, "Payroll clerks");
+    for action in ["read", "create", "update"] {
+        fixtures::grant(&mut conn, clerks, "payroll", action);
+    }
+    let clerk_id = fixtures::user(&mut conn, "clerk");
+    fixtures::assign_role(&mut conn, clerk_id, clerks);
+    fixtures::user(&mut conn, "nosy");
+    let sales = fixtures::department(&mut conn, "Pay sales", None);
+    let employee = fixtures::employee(&mut conn, "pay_employee", Some(sales), None);
+    let client = db.client(module_routes());
+    let compensation = format!("/api/employee/{employee}/compensation");
+    let add = |component: &str, name: &str, amount: i32, from: &str| {
+        client.post_json(
+            &compensation,
+            &json!({"component": component, "name": name, "amount": amount, "effective_from": from}),
+        )
+    };
+
+    client.set_token(login(&client, "nosy", "laptop")["access_token"].as_str());
+    assert_eq!(client.get(&compensation).status, Status::Forbidden);
+    assert_eq!(add("salary", "Base salary", 3000, "2020-01-01").status, Status::Forbidden);
+
+    client.set_token(login(&client, "clerk", "laptop")["access_token"].as_str());
+    assert_eq!(add("salary", "Base salary", 3000, "2020-01-01").status, Status::Ok);
+    assert_eq!(add("allowance", "Travel", 300, "2020-01-01").status, Status::Ok);
+    assert_eq!(add("deduction", "Pension", 500, "2020-01-01").status, Status::Ok);
+    assert_eq!(add("bonus", "Signing", 100, "2020-01-01").status, Status::BadRequest);
+    assert_eq!(add("allowance", "Meals", -1, "2020-01-01").status, Status::BadRequest);
+    // A raise halfway through September, a backdated salary would rewrite history.
+    assert_eq!(add("salary", "Raise", 6000, "2020-09-16").status, Status::Ok);
+    assert_eq!(add("salary", "Backdated", 4000, "2020-06-01").status, Status::Conflict);
+
+    let history = client.get(&compensation).json()["body"].clone();
+    assert_eq!(
+        (history[0]["name"].clone(), history[0]["effective_to"].clone()),
+        (json!("Raise"), Value::Null)
+    );
+    let base = history.as_array().unwrap().iter().find(|c| c["name"] == "Base salary").unwrap();
+    assert_eq!(base["effective_to"], "2020-09-15");
+    let reopen = client.patch_json(
+        &format!("/api/compensation/{}", base["compensation_id"]),
+        &json!({"effective_to": null}),
+    );
+    assert_eq!(reopen.status, Status::Conflict);
+    assert_eq!(client.get(&format!("/api/employee/{employee}")).json()["body"]["salary"], 6000);
+
+    let run = |start: &str, end: &str| {
+        client.post_json("/api/payroll/run", &json!({"period_start": start, "period_end": end}))
+    };
+    assert_eq!(run("2020-09-30", "2020-09-01").status, Status::BadRequest);
+    let september = run("2020-09-01", "2020-09-30");
+    assert_eq!(september.status, Status::Ok);
+    let run_id = september.json()["body"]["payroll_run_id"].as_i64().unwrap();
+    assert_eq!(run("2020-09-15", "2020-10-15").status, Status::Conflict);
+
+    let detail = client.get(&format!("/api/payroll/run/{run_id}")).json();
+    let payslip = detail["body"]["payslips"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|p| p["employee_id"] == employee)
+        .unwrap()
+        .clone();
+    let totals: Vec<_> = ["salary", "allowances", "deductions", "gross", "net"]
+        .iter()
+        .map(|field| payslip[field].as_i64().unwrap())
+        .collect();
+    assert_eq!(totals, [4500, 300, 500, 4800, 4300]);
+    assert_eq!(payslip["department_id"], sales);
+
+    let lines = client.get(&format!("/api/payroll/payslip/{}", payslip["payslip_id"])).json();
+    let amounts: Vec<_> = lines["body"]["lines"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|line| (line["name"].as_str().unwrap().to_string(), line["amount"].as_i64().unwrap()))
+        .collect();
+    assert_eq!(amounts.len(), 4);
+    assert!(amounts.contains(&("Base salary".to_string(), 1500)));
+    assert!(amounts.contains(&("Raise".to_string(), 3000)));
+    let payslips = client.get(&format!("/api/employee/{employee}/payslips")).json();
+    assert_eq!(payslips["body"].as_array().unwrap().len(), 1);
+
+    let export = client.get(&format!("/api/payroll/run/{run_id}/export"));
+    assert_eq!(export.status, Status::Ok);
+    assert_eq!(export.header("Content-Type"), Some("text/csv; charset=utf-8"));
+    assert_eq!(
+        export.header("Content-Disposition"),
+        Some(format!("attachment; filename=\"payroll-run-{run_id}.csv\"").as_str())
+    );
+    let mut rows = export.body.lines();
+    assert!(rows.next().unwrap().starts_with("payslip_id,payroll_run_id,employee_id"));
+    let row = rows.find(|row| row.contains("\"pay_employee\"")).unwrap();
+    assert!(row.ends_with(",4500,300,500,4800,4300"));
+}
+
+#[test]
+fn test_leave_goes_through_the_manager_and_the_balance() {
+    let db = TestDb::new();
+    let mut conn = db.conn();
+    let leads = fixtures::role(&mut conn, "Team leads");
+    for action in ["read", "create", "update"] {
+        fixtures::grant(&mut conn, leads, "leave", action);
+    }
+    let lead_id = fixtures::user(&mut conn, "lead");
+    fixtures::assign_role(&mut conn, lead_id, leads);
+    let teammate_id = fixtures::user(&mut conn, "teammate");
+    fixtures::assign_role(&mut conn, teammate_id, leads);
+    fixtures::user(&mut conn, "bystander");
+    let team = fixtures::department(&mut conn, "Leave team", None);
+    let manager = fixtures::employee(&mut conn, "leave_manager", Some(team), None);
+    let worker = fixtures::employee(&mut conn, "leave_worker", Some(team), Some(manager));
+    fixtures::link_employee(&mut conn, manager, lead_id);
+    fixtures::link_employee(&mut conn, worker, teammate_id);
+    let client = db.client(module_routes());
+    let leave = format!("/api/employee/{worker}/leave");
+    let request = |start: &str, end: &str| {
+        client.post_json(&leave, &json!({"leave_type_id": 1, "start_date": start, "end_date": end}))
+    };
+    let annual = || {
+        let balances = client.get(&format!("/api/employee/{worker}/leave/balance?year=2030"));
+        let annual = balances.json()["body"][0].clone();
+        assert_eq!(annual["leave_type_name"], "Annual leave");
+        (annual["used"].as_i64(), annual["pending"].as_i64(), annual["remaining"].as_i64())
+    };
+
+    client.set_token(login(&client, "bystander", "laptop")["access_token"].as_str());
+    assert_eq!(request("2030-03-04", "2030-03-08").status, Status::Forbidden);
+
+    let lead = login(&client, "lead", "laptop")["access_token"].clone();
+    client.set_token(lead.as_str());
+    // Nothing accrued yet.
+    assert_eq!(request("2030-03-04", "2030-03-08").status, Status::Conflict);
+    let accrual = client.post_json("/api/leave/accrual", &json!({"year": 2030}));
+    assert_eq!(accrual.status, Status::Ok);
+    assert!(accrual.json()["body"]["balances"].as_i64().unwrap() >= 4);
+    assert_eq!(annual(), (Some(0), Some(0), Some(20)));
+
+    let week = request("2030-03-04", "2030-03-08");
+    assert_eq!(week.status, Status::Ok);
+    let week = week.json()["body"].clone();
+    assert_eq!((week["days"].clone(), week["status"].clone()), (json!(5), json!("pending")));
+    assert_eq!(annual(), (Some(0), Some(5), Some(15)));
+    assert_eq!(request("2030-03-07", "2030-03-11").status, Status::Conflict);
+    assert_eq!(request("2030-03-09", "2030-03-10").status, Status::BadRequest);
+    assert_eq!(request("2030-12-30", "2031-01-03").status, Status::BadRequest);
+    assert_eq!(request("2030-04-01", "2030-05-31").status, Status::Conflict);
+
+    let approvals = client.get(&format!("/api/employee/{manager}/leave/approvals")).json();
+    assert_eq!(approvals["body"].as_array().unwrap().len(), 1);
+    let approve = format!("/api/leave/request/{}/approve", week["leave_request_id"]);
+    // The approver is the caller's employee, whoever the body names.
+    client.set_token(login(&client, "teammate", "laptop")["access_token"].as_str());
+    let by_self = client.post_json(&approve, &json!({"approver_id": manager}));
+    assert_eq!(by_self.status, Status::Forbidden);
+    assert_eq!(
+        by_self.json()["message"],
+        format!("employee {worker} is not the manager of employee {worker}")
+    );
+    client.set_token(lead.as_str());
+    let approved = client.post_json(&approve, &json!({"note": "Enjoy"}));
+    assert_eq!(approved.status, Status::Ok);
+    assert_eq!(approved.json()["body"]["status"], "approved");
+    assert_eq!(approved.json()["body"]["approver_id"], manager);
+    assert_eq!(client.post_json(&approve, &json!({})).status, Status::Conflict);
+    assert_eq!(annual(), (Some(5), Some(0), Some(15)));
+    let manager_leave = client.post_json(
+        &format!("/api/employee/{manager}/leave"),
+        &json!({"leave_type_id": 1, "start_date": "2030-06-03", "end_date": "2030-06-03"}),
+    );
+    let approve_own =
+        format!("/api/leave/request/{}/approve", manager_leave.json()["body"]["leave_request_id"]);
+    assert_eq!(client.post_json(&approve_own, &json!({})).status, Status::Conflict);
+
+    let cancel = format!("/api/leave/request/{}/cancel", week["leave_request_id"]);
+    assert_eq!(client.dispatch(client.inner().post(cancel.clone())).status, Status::Ok);
+    assert_eq!(annual(), (Some(0), Some(0), Some(20)));
+    assert_eq!(client.dispatch(client.inner().post(cancel)).status, Status::Conflict);
+    assert_eq!(client.get(&leave).json()["body"][0]["status"], "cancelled");
+}
+
+#[test]
+fn test_attendance_is_summed_up_by_day() {
+    let db = TestDb::new();
+    let mut conn = db.conn();
+    let staff = fixtures::role(&mut conn, "Front desk");
+    for action in ["read", "create", "update"] {
+        fixtures::grant(&mut conn, staff, "attendance", action);
+    }
+    let desk_id = fixtures::user(&mut conn, "desk");
+    fixtures::assign_role(&mut conn, desk_id, staff);
+    let floor = fixtures::department(&mut conn, "Attendance shop floor", None);
+    let worker = fixtures::employee(&mut conn, "shift_worker", Some(floor), None);
+    fixtures::employee(&mut conn, "shift_absent", Some(floor), None);
+    let client = db.client(module_routes());
+    let clock = |action: &str| {
+        client.dispatch(client.inner().post(format!("/api/employee/{worker}/attendance/{action}")))
+    };
+
+    assert_eq!(clock("clock-in").status, Status::Unauthorized);
+    client.set_token(login(&client, "desk", "kiosk")["access_token"].as_str());
+    assert_eq!(clock("clock-out").status, Status::Conflict);
+    let clocked_in = clock("clock-in");
+    assert_eq!(clocked_in.status, Status::Ok);
+    assert_eq!(clocked_in.json()["body"]["clock_out"], Value::Null);
+    assert_eq!(clock("clock-in").status, Status::Conflict);
+    let clocked_out = clock("clock-out");
+    assert_eq!(clocked_out.status, Status::Ok);
+    assert!(clocked_out.json()["body"]["clock_out"].is_string());
+
+    let days = client.get(&format!("/api/employee/{worker}/attendance")).json();
+    let today = &days["body"][0];
+    assert_eq!((today["records"].clone(), today["clocked_in"].clone()), (json!(1), json!(false)));
+    let department = client.get(&format!("/api/department/{floor}/attendance")).json();
+    assert_eq!(department["body"]["employees"], 2);
+    assert_eq!(department["body"]["present"], 1);
+    assert_eq!(department["body"]["attendance"][0]["employee_name"], "shift_absent");
+    let bad_day = client.get(&format!("/api/department/{floor}/attendance?day=tomorrow"));
+    assert_eq!(bad_day.status, Status::BadRequest);
+    let backwards =
+        client.get(&format!("/api/employee/{worker}/attendance?from=2026-02-01&to=2026-01-01"));
+    assert_eq!(backwards.status, Status::BadRequest);
+}
+
+#[test]
+fn test_onboarded_employees_follow_the_role_of_their_user() {
+    let db = TestDb::new();
+    let mut conn = db.conn();
+    let hr = fixtures::role(&mut conn, "People team");
+    for (resource, action) in [("user", "create"), ("employee", "create"), ("employee", "update")] {
+        fixtures::grant(&mut conn, hr, resource, action);
+    }
+    let staff = fixtures::role(&mut conn, "Staff");
+    let hr_id = fixtures::user(&mut conn, "people");
+    fixtures::assign_role(&mut conn, hr_id, hr);
+    fixtures::user(&mut conn, "outsider");
+    let veteran = fixtures::employee(&mut conn, "veteran", None, None);
+    let client = db.client(module_routes());
+    let onboarding = |username: &str, password: &str| {
+        json!({
+            "user": {
+                "username": username,
+                "password": password,
+                "role_id": staff,
+                "email": format!("{username}@example.com"),
+                "mobile_phone": format!("phone-{username}")
+            },
+            "employee": {"employee_name": username}
+        })
+    };
+
+    client.set_token(login(&client, "outsider", "laptop")["access_token"].as_str());
+    let forbidden =
+        client.post_json("/api/employee/onboard", &onboarding("newbie", fixtures::PASSWORD));
+    assert_eq!(forbidden.status, Status::Forbidden);
+
+    client.set_token(login(&client, "people", "laptop")["access_token"].as_str());
+    let weak = client.post_json("/api/employee/onboard", &onboarding("weakling", "short"));
+    assert_eq!(weak.status, Status::BadRequest);
+    let onboarded =
+        client.post_json("/api/employee/onboard", &onboarding("newbie", fixtures::PASSWORD));
+    assert_eq!(onboarded.status, Status::Created);
+    let body = onboarded.json()["body"].clone();
+    let (user_id, employee) = (body["user"]["user_id"].clone(), body["employee"].clone());
+    assert_eq!(employee["user_id"], user_id);
+    assert_eq!(employee["email"], "newbie@example.com");
+    assert_eq!(
+        (employee["role_id"].clone(), employee["role_name"].clone()),
+        (json!(staff), json!("Staff"))
+    );
+    let again =
+        client.post_json("/api/employee/onboard", &onboarding("newbie", fixtures::PASSWORD));
+    assert_eq!(again.status, Status::Conflict);
+
+    let employee_id = employee["employee_id"].as_i64().unwrap();
+    let user_id = user_id.as_i64().unwrap() as i32;
+    let newbie = format!("/api/employee/{employee_id}");
+    let role = || {
+        let employee = client.get(&newbie).json()["body"].clone();
+        (employee["role_id"].clone(), employee["role_name"].clone())
+    };
+    fixtures::assign_role(&mut conn, user_id, hr);
+    assert_eq!(role(), (json!(hr), json!("People team")));
+    fixtures::load_sql(
+        &mut conn,
+        &format!("UPDATE role_table SET role_name = 'HR' WHERE role_id = {hr}"),
+    );
+    assert_eq!(role(), (json!(hr), json!("HR")));
+    let escalate =
+        client.patch_json(&newbie, &json!({"employee_name": "newbie", "role_id": staff}));
+    assert_eq!(escalate.status, Status::Conflict);
+    assert_eq!(
+        client.patch_json(&newbie, &json!({"employee_name": "newbie", "role_id": hr})).status,
+        Status::Ok
+    );
+
+    let link = |employee: i64, user: i32| {
+        client.dispatch(client.inner().put(format!("/api/employee/{employee}/user/{user}")))
+    };
+    assert_eq!(link(employee_id, user_id).status, Status::Ok);
+    assert_eq!(link(employee_id, hr_id).status, Status::Conflict);
+    assert_eq!(link(veteran as i64, user_id).status, Status::Conflict);
+    assert_eq!(link(veteran as i64, 999_999).status, Status::NotFound);
+    assert_eq!(client.get("/api/employee/me").status, Status::NotFound);
+    let linked = link(veteran as i64, hr_id);
+    assert_eq!(linked.status, Status::Ok);
+    assert_eq!(linked.json()["body"]["role_name"], "HR");
+    let me = client.get("/api/employee/me");
+    assert_eq!(me.status, Status::Ok);
+    assert_eq!(me.json()["body"]["employee_id"], veteran);
+
+    let unlinked = client.delete(&format!("/api/employee/{veteran}/user"));
+    assert_eq!(unlinked.status, Status::Ok);
+    assert_eq!(unlinked.json()["body"]["user_id"], Value::Null);
+    assert_eq!(client.get("/api/employee/me").status, Status::NotFound);
+}
+
+#[test]
+fn test_reviews_go_from_draft_to_acknowledged() {
+    let db = TestDb::new();
+    let mut conn = db.conn();
+    let leads = fixtures::role(&mut conn, "Review leads");
+    for action in ["read", "create", "update"] {
+        fixtures::grant(&mut conn, leads, "review", action);
+    }
+    let lead_id = fixtures::user(&mut conn, "review_lead");
+    fixtures::assign_role(&mut conn, lead_id, leads);
+    let member_id = fixtures::user(&mut conn, "review_member");
+    fixtures::assign_role(&mut conn, member_id, leads);
+    let unlinked_id = fixtures::user(&mut conn, "review_unlinked");
+    fixtures::assign_role(&mut conn, unlinked_id, leads);
+    fixtures::user(&mut conn, "review_bystander");
+    let team = fixtures::department(&mut conn, "Review team", None);
+    let manager = fixtures::employee(&mut conn, "review_manager", Some(team), None);
+    let worker = fixtures::employee(&mut conn, "review_worker", Some(team), Some(manager));
+    fixtures::link_employee(&mut conn, manager, lead_id);
+    fixtures::link_employee(&mut conn, worker, member_id);
+    let client = db.client(module_routes());
+    let cycle = json!({
+        "review_cycle_name": "2030 Q1",
+        "start_date": "2030-01-01",
+        "end_date": "2030-03-31",
+        "criteria": [
+            {"criterion_name": "Delivery", "weight": 3},
+            {"criterion_name": "Teamwork", "description": "Helps others", "weight": 1}
+        ]
+    });
+
+    client.set_token(login(&client, "review_bystander", "laptop")["access_token"].as_str());
+    assert_eq!(client.post_json("/api/review/cycle", &cycle).status, Status::Forbidden);
+
+    let lead = login(&client, "review_lead", "laptop")["access_token"].clone();
+    let member = login(&client, "review_member", "laptop")["access_token"].clone();
+    let unlinked = login(&client, "review_unlinked", "laptop")["access_token"].clone();
+    client.set_token(lead.as_str());
+    let created = client.post_json("/api/review/cycle", &cycle);
+    assert_eq!(created.status, Status::Ok);
+    let created = created.json()["body"].clone();
+    let criteria: Vec<_> = created["criteria"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["review_criterion_id"].clone())
+        .collect();
+    assert_eq!(criteria.len(), 2);
+    assert_eq!(client.post_json("/api/review/cycle", &cycle).status, Status::Conflict);
+    let empty = json!({"review_cycle_name": "Empty", "start_date": "2030-01-01",
+        "end_date": "2030-03-31", "criteria": []});
+    assert_eq!(client.post_json("/api/review/cycle", &empty).status, Status::BadRequest);
+
+    let cycle_id = created["review_cycle_id"].clone();
+    let launch = format!("/api/review/cycle/{cycle_id}/launch");
+    let launched = client.dispatch(client.inner().post(launch.as_str()));
+    assert_eq!(launched.status, Status::Ok);
+    assert!(launched.json()["body"]["reviews"].as_i64().unwrap() >= 3);
+    let relaunched = client.dispatch(client.inner().post(launch.as_str()));
+    assert_eq!(relaunched.json()["body"]["reviews"], 0);
+
+    let assigned = client.get(&format!("/api/employee/{manager}/review/assigned")).json();
+    let assigned = assigned["body"].as_array().unwrap().clone();
+    let kinds: Vec<_> =
+        assigned.iter().map(|r| (r["employee_id"].clone(), r["kind"].clone())).collect();
+    assert_eq!(kinds, [(json!(manager), json!("self")), (json!(worker), json!("manager"))]);
+    let review = format!("/api/review/{}", assigned[1]["review_id"]);
+    let assess =
+        |scores: Value| client.put_json(&review, &json!({"scores": scores, "comment": "Solid"}));
+    let submit = format!("{review}/submit");
+    let acknowledge = format!("{review}/acknowledge");
+    let post = |uri: &str| client.dispatch(client.inner().post(uri));
+
+    client.set_token(unlinked.as_str());
+    let by_unlinked = assess(json!([{"review_criterion_id": criteria[0], "score": 4}]));
+    assert_eq!(by_unlinked.status, Status::Forbidden);
+    let message = format!("user {unlinked_id} is not linked to an employee");
+    assert_eq!(by_unlinked.json()["message"], message);
+
+    client.set_token(member.as_str());
+    let by_worker = client.put_json(
+        &review,
+        &json!({"reviewer_id": manager,
+            "scores": [{"review_criterion_id": criteria[0], "score": 4}]}),
+    );
+    assert_eq!(by_worker.status, Status::Forbidden);
+
+    client.set_token(lead.as_str());
+    let out_of_range = assess(json!([{"review_criterion_id": criteria[0], "score": 6}]));
+    assert_eq!(out_of_range.status, Status::BadRequest);
+    let partial = assess(json!([{"review_criterion_id": criteria[0], "score": 4}]));
+    assert_eq!(partial.status, Status::Ok);
+    assert_eq!(post(&submit).status, Status::Conflict);
+    let full = assess(json!([{"review_criterion_id": criteria[1], "score": 1}]));
+    let full = full.json()["body"].clone();
+    assert_eq!((full["score"].clone(), full["comment"].clone()), (json!(3.25), json!("Solid")));
+    assert_eq!(full["scores"].as_array().unwrap().len(), 2);
+    client.set_token(member.as_str());
+    assert_eq!(post(&submit).status, Status::Forbidden);
+    client.set_token(lead.as_str());
+    let submitted = post(&submit);
+    assert_eq!(submitted.status, Status::Ok);
+    assert_eq!(submitted.json()["body"]["status"], "submitted");
+    assert_eq!(assess(json!([])).status, Status::Conflict);
+
+    assert_eq!(post(&acknowledge).status, Status::Forbidden);
+    client.set_token(member.as_str());
+    let acknowledged = post(&acknowledge);
+    assert_eq!(acknowledged.status, Status::Ok);
+    assert_eq!(acknowledged.json()["body"]["status"], "acknowledged");
+
+    let reviews = client.get(&format!("/api/employee/{worker}/review?cycle={cycle_id}")).json();
+    let statuses: Vec<_> =
+        reviews["body"].as_array().unwrap().iter().map(|r| r["status"].clone()).collect();
+    assert_eq!(statuses, [json!("draft"), json!("acknowledged")]);
+    let completion = client.get(&format!("/api/review/cycle/{cycle_id}/completion")).json();
+    let team = completion["body"].as_array().unwrap().iter().find(|d| d["department_id"] == team);
+    let team = team.unwrap();
+    assert_eq!((team["employees"].clone(), team["reviews"].clone()), (json!(2), json!(3)));
+    assert_eq!((team["acknowledged"].clone(), team["completion"].clone()), (json!(1), json!(33.3)));
+}
+
+#[test]
+fn test_tasks_move_through_the_workflow() {
+    let db = TestDb::new();
+    let mut conn = db.conn();
+    let leads = fixtures::role(&mut conn, "Task leads");
+    fixtures::grant(&mut conn, leads, "task", "moderate");
+    let lead_id = fixtures::user(&mut conn, "task_lead");
+    fixtures::assign_role(&mut conn, lead_id, leads);
+    fixtures::user(&mut conn, "task_owner");
+    let helper_id = fixtures::user(&mut conn, "task_helper");
+    let client = db.client(module_routes());
+
+    client.set_token(login(&client, "task_owner", "laptop")["access_token"].as_str());
+    let statuses = client.get("/api/task/status").json()["body"].clone();
+    let status = |name: &str| {
+        statuses.as_array().unwrap().iter().find(|s| s["status_name"] == name).unwrap().clone()
+    };
+    let (to_do, doing, done) = (status("To do"), status("In progress"), status("Done"));
+    assert_eq!(done["closed"], true);
+    assert_eq!(doing["next_status_ids"], json!([to_do["task_status_id"], done["task_status_id"]]));
+    let (to_do, doing, done) = (
+        to_do["task_status_id"].clone(),
+        doing["task_status_id"].clone(),
+        done["task_status_id"].clone(),
+    );
+
+    let create = |task: Value| client.post_json("/api/task", &task);
+    let release = create(json!({"title": "Ship release", "priority": 4, "due_date": "2020-01-10",
+        "assignee_id": helper_id, "labels": [" release", "backend", "release", ""]}));
+    assert_eq!(release.status, Status::Ok);
+    let release = release.json()["body"].clone();
+    assert_eq!(release["status_id"], to_do);
+    assert_eq!(release["labels"], json!(["release", "backend"]));
+    let notes = create(json!({"title": "Write notes", "priority": 1, "due_date": "2020-01-05"}));
+    let notes = notes.json()["body"].clone();
+    create(json!({"title": "Plan", "due_date": "2999-01-01", "labels": ["backend"]}));
+    assert_eq!(create(json!({"title": "Bad", "priority": 5})).status, Status::BadRequest);
+    let backwards = create(json!({"title": "Bad", "start_date": "2020-02-01",
+        "due_date": "2020-01-01"}));
+    assert_eq!(backwards.status, Status::BadRequest);
+    assert_eq!(create(json!({"title": "Bad", "assignee_id": 999999})).status, Status::NotFound);
+
+    let titles = |response: Value| -> Vec<Value> {
+        response["body"]["data"].as_array().unwrap().iter().map(|t| t["title"].clone()).collect()
+    };
+    assert_eq!(titles(client.get("/api/task/overdue").json()), ["Write notes", "Ship release"]);
+    let backend = client.post_json(
+        "/api/task/mine/filter",
+        &json!({"pagination": {}, "filter": {"labels": ["backend"], "sort_by": "priority", "descending": true}}),
+    );
+    assert_eq!(titles(backend.json()), ["Ship release", "Plan"]);
+    assert_eq!(backend.json()["body"]["pagination"]["count"], 2);
+
+    // The assignee moves the task on, other tasks stay the owner's.
+    client.set_token(login(&client, "task_helper", "laptop")["access_token"].as_str());
+    let release_status = format!("/api/task/{}/status", release["task_id"]);
+    let closed = client.put_json(&release_status, &json!({"status_id": done}));
+    assert_eq!(closed.status, Status::Ok);
+    assert!(closed.json()["body"]["completed_at"].is_string());
+    let notes_status = format!("/api/task/{}/status", notes["task_id"]);
+    assert_eq!(
+        client.put_json(&notes_status, &json!({"status_id": doing})).status,
+        Status::Forbidden
+    );
+    assert_eq!(titles(client.get("/api/task/overdue").json()), ["Write notes"]);
+
+    client.set_token(login(&client, "task_owner", "laptop")["access_token"].as_str());
+    assert_eq!(client.put_json(&notes_status, &json!({"status_id": doing})).status, Status::Ok);
+    assert_eq!(
+        client.post_json("/api/task/status", &json!({"status_name": "Review"})).status,
+        Status::Forbidden
+    );
+
+    client.set_token(login(&client, "task_lead", "laptop")["access_token"].as_str());
+    let review = client.post_json(
+        "/api/task/status",
+        &json!({"status_name": "Review",
+        "position": 2}),
+    );
+    assert_eq!(review.status, Status::Ok);
+    assert_eq!(
+        client.post_json("/api/task/status", &json!({"status_name": "Review"})).status,
+        Status::Conflict
+    );
+    let shortcut = format!("/api/task/status/{doing}/next/{done}");
+    assert_eq!(client.delete(&shortcut).status, Status::Ok);
+
+    client.set_token(login(&client, "task_owner", "laptop")["access_token"].as_str());
+    let skipped = client.put_json(&notes_status, &json!({"status_id": done}));
+    assert_eq!(skipped.status, Status::Conflict);
+    assert_eq!(
+        skipped.json()["message"],
+        format!("the workflow has no move from task status {doing} to {done}")
+    );
+
+    client.set_token(login(&client, "task_lead", "laptop")["access_token"].as_str());
+    let restored = client.dispatch(client.inner().put(shortcut.as_str()));
+    assert_eq!(restored.json()["body"]["next_status_ids"], json!([to_do, done]));
+
+    client.set_token(login(&client, "task_owner", "laptop")["access_token"].as_str());
+    assert_eq!(client.put_json(&notes_status, &json!({"status_id": done})).status, Status::Ok);
+    let closed = client
+        .post_json("/api/task/mine/filter", &json!({"pagination": {}, "filter": {"closed": true}}));
+    assert_eq!(titles(closed.json()), ["Write notes", "Ship release"]);
+
+    // Reopening clears `completed_at`, a patch keeps the priority and labels it leaves out.
+    let reopened = client.patch_json(
+        &format!("/api/task/{}", release["task_id"]),
+        &json!({"title": "Ship release", "status_id": doing}),
+    );
+    assert_eq!(reopened.status, Status::Ok);
+    let reopened = reopened.json()["body"].clone();
+    assert_eq!(reopened["completed_at"], Value::Null);
+    assert_eq!(reopened["due_date"], Value::Null);
+    assert_eq!(
+        (reopened["priority"].clone(), reopened["labels"].clone()),
+        (json!(4), json!(["release", "backend"]))
+    );
+}
+
+#[test]
+fn test_projects_roll_up_subtasks_and_enforce_blockers() {
+    let db = TestDb::new();
+    let mut conn = db.conn();
+    fixtures::user(&mut conn, "project_owner");
+    fixtures::user(&mut conn, "project_other");
+    let client = db.client(module_routes());
+
+    client.set_token(login(&client, "project_owner", "laptop")["access_token"].as_str());
+    let project = client.post_json("/api/project", &json!({"project_name": "Launch"}));
+    assert_eq!(project.status, Status::Ok);
+    let project_id = project.json()["body"]["project_id"].clone();
+    let statuses = client.get("/api/task/status").json()["body"].clone();
+    let done = statuses.as_array().unwrap().iter().find(|s| s["status_name"] == "Done");
+    let done = done.unwrap()["task_status_id"].clone();
+
+    let create = |task: Value| client.post_json("/api/task", &task).json()["body"].clone();
+    let release = create(json!({"title": "Release", "project_id": project_id}));
+    let release_id = release["task_id"].clone();
+    let docs = create(json!({"title": "Docs", "parent_id": release_id}));
+    let docs_id = docs["task_id"].clone();
+    assert_eq!(docs["project_id"], project_id);
+    let build_id = create(json!({"title": "Build", "parent_id": release_id}))["task_id"].clone();
+    let changelog_id =
+        create(json!({"title": "Changelog", "parent_id": docs_id}))["task_id"].clone();
+    let orphan = client.post_json("/api/task", &json!({"title": "Orphan", "parent_id": 999999}));
+    assert_eq!(orphan.status, Status::NotFound);
+
+    let subtasks = client.get(&format!("/api/task/{release_id}/subtask")).json();
+    let titles: Vec<_> =
+        subtasks["body"].as_array().unwrap().iter().map(|t| t["title"].clone()).collect();
+    assert_eq!(titles, ["Docs", "Build"]);
+    let looped = client.patch_json(
+        &format!("/api/task/{release_id}"),
+        &json!({"title": "Release", "project_id": project_id, "parent_id": changelog_id}),
+    );
+    assert_eq!(looped.status, Status::Conflict);
+
+    // Build waits on Docs, Changelog on Build, so Docs cannot wait on Changelog.
+    let blocker = |task_id: &Value, blocker_id: &Value| {
+        client.dispatch(client.inner().put(format!("/api/task/{task_id}/blocker/{blocker_id}")))
+    };
+    let blockers = blocker(&build_id, &docs_id);
+    assert_eq!(blockers.status, Status::Ok);
+    assert_eq!(blockers.json()["body"][0]["task_id"], docs_id);
+    assert_eq!(blocker(&changelog_id, &build_id).status, Status::Ok);
+    let cycle = blocker(&docs_id, &changelog_id);
+    assert_eq!(cycle.status, Status::Conflict);
+    assert_eq!(
+        cycle.json()["message"],
+        format!("task {changelog_id} waits on task {docs_id}, it cannot block it")
+    );
+    assert_eq!(blocker(&docs_id, &docs_id).status, Status::Conflict);
+
+    client.set_token(login(&client, "project_other", "laptop")["access_token"].as_str());
+    assert_eq!(blocker(&build_id, &release_id).status, Status::Forbidden);
+    let renamed = client.patch_json(
+        &format!("/api/project/{project_id}"),
+        &json!({
+        "project_name": "Mine"}),
+    );
+    assert_eq!(renamed.status, Status::Forbidden);
+
+    client.set_token(login(&client, "project_owner", "laptop")["access_token"].as_str());
+    let close = |task_id: &Value| {
+        client.put_json(&format!("/api/task/{task_id}/status"), &json!({"status_id": done}))
+    };
+    let blocked = close(&build_id);
+    assert_eq!(blocked.status, Status::Conflict);
+    assert_eq!(blocked.json()["message"], format!("task {build_id} waits on open tasks {docs_id}"));
+    assert_eq!(close(&docs_id).status, Status::Ok);
+
+    let progress = client.get(&format!("/api/task/{release_id}/progress")).json()["body"].clone();
+    assert_eq!(progress, json!({"tasks": 3, "closed": 1, "progress": 50.0}));
+    let detail = client.get(&format!("/api/project/{project_id}")).json()["body"].clone();
+    assert_eq!(detail["project_name"], "Launch");
+    assert_eq!(detail["progress"], json!({"tasks": 4, "closed": 1, "progress": 50.0}));
+
+    assert_eq!(close(&build_id).status, Status::Ok);
+    assert_eq!(close(&changelog_id).status, Status::Ok);
+    let detail = client.get(&format!("/api/project/{project_id}")).json()["body"].clone();
+    assert_eq!(detail["progress"], json!({"tasks": 4, "closed": 3, "progress": 100.0}));
+    let tasks = client.get(&format!("/api/project/{project_id}/task")).json();
+    assert_eq!(tasks["body"]["pagination"]["count"], 4);
+
+    assert_eq!(client.delete(&format!("/api/project/{project_id}")).status, Status::Ok);
+    assert_eq!(client.get(&format!("/api/project/{project_id}")).status, Status::NotFound);
+    let release = client.get(&format!("/api/task/{release_id}")).json();
+    assert_eq!(release["body"]["project_id"], Value::Null);
+}